@@ -0,0 +1,346 @@
+//! References in array items (`<item>@string/api/error/unauthorized</item>`).
+//!
+//! Items are resolved to literals so the generated constant keeps its plain
+//! `&[&str]`/`&[i64]`/`&[f64]` type. String items take the flattened text of
+//! a string resource (references and interpolations included); numeric
+//! items take a number resource that fits the array's item type.
+
+use crate::generator::ir::types::{literal_item, number_literal, sort_items};
+use crate::generator::ir::{
+    ArrayItem, ResourceGraph, ResourceKey, ResourceKind, ResourceNode,
+    ResourceValue,
+};
+
+use super::interpolation::{self, InterpolationIssue};
+use super::references;
+use super::{codes, AnalysisError, AnalysisResult};
+
+/// Why an item reference could not be resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ItemIssue {
+    /// Missing target or cycle, as for interpolations
+    Text(InterpolationIssue),
+    /// The target is not a resource of the array's item kind
+    KindMismatch {
+        target: ResourceKey,
+        found: ResourceKind,
+    },
+    /// The target number does not fit the item type
+    Invalid { target: ResourceKey, message: String },
+}
+
+/// First item of an array that could not be resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemError {
+    /// `item 1`, or `row 0, item 1` in an array with rows
+    pub position: String,
+    pub issue: ItemIssue,
+}
+
+/// Whether any item of the value is a reference
+pub fn has_item_references(value: &ResourceValue) -> bool {
+    let is_reference =
+        |item: &ArrayItem| matches!(item, ArrayItem::Reference(_));
+    match value {
+        ResourceValue::Array(items) => items.iter().any(is_reference),
+        ResourceValue::ArrayRows(rows) => {
+            rows.iter().flatten().any(is_reference)
+        }
+        _ => false,
+    }
+}
+
+/// The array value of `node` with every reference replaced by the literal
+/// it resolves to; `sorted="true"` arrays are sorted again afterwards
+pub fn resolve_array(
+    graph: &ResourceGraph,
+    node: &ResourceNode,
+) -> Result<ResourceValue, ItemError> {
+    let ResourceKind::Array(item_type) = &node.kind else {
+        return Ok(node.value.clone());
+    };
+    match &node.value {
+        ResourceValue::Array(items) => {
+            let mut items = resolve_items(graph, item_type, items, None)?;
+            if node.has_flag("sorted") {
+                sort_items(&mut items);
+            }
+            Ok(ResourceValue::Array(items))
+        }
+        ResourceValue::ArrayRows(rows) => rows
+            .iter()
+            .enumerate()
+            .map(|(row, items)| {
+                resolve_items(graph, item_type, items, Some(row))
+            })
+            .collect::<Result<_, _>>()
+            .map(ResourceValue::ArrayRows),
+        other => Ok(other.clone()),
+    }
+}
+
+fn resolve_items(
+    graph: &ResourceGraph,
+    item_type: &str,
+    items: &[ArrayItem],
+    row: Option<usize>,
+) -> Result<Vec<ArrayItem>, ItemError> {
+    items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let ArrayItem::Reference(target) = item else {
+                return Ok(item.clone());
+            };
+            resolve_item(graph, item_type, target).map_err(|issue| {
+                let position = match row {
+                    Some(row) => format!("row {row}, item {index}"),
+                    None => format!("item {index}"),
+                };
+                ItemError { position, issue }
+            })
+        })
+        .collect()
+}
+
+fn resolve_item(
+    graph: &ResourceGraph,
+    item_type: &str,
+    target: &ResourceKey,
+) -> Result<ArrayItem, ItemIssue> {
+    let node = references::resolve(graph, target)
+        .map_err(|issue| ItemIssue::Text(issue.into()))?;
+    if node.kind != item_kind(item_type) {
+        return Err(ItemIssue::KindMismatch {
+            target: target.clone(),
+            found: node.kind.clone(),
+        });
+    }
+    let ResourceValue::Number(number) = &node.value else {
+        return interpolation::resolve_text(graph, target)
+            .map(ArrayItem::String)
+            .map_err(ItemIssue::Text);
+    };
+    let literal = number_literal(number).replace('_', "");
+    literal_item(item_type, &literal).map_err(|message| {
+        ItemIssue::Invalid {
+            target: target.clone(),
+            message,
+        }
+    })
+}
+
+/// Kind of the resources an array of `item_type` may reference
+fn item_kind(item_type: &str) -> ResourceKind {
+    if item_type == "string" {
+        ResourceKind::String
+    } else {
+        ResourceKind::Number
+    }
+}
+
+/// Validates the item references of every array in the graph
+pub(super) fn validate_array_references(
+    graph: &ResourceGraph,
+    result: &mut AnalysisResult,
+) {
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first() else {
+            continue;
+        };
+        if !has_item_references(&node.value) {
+            continue;
+        }
+        if let Err(error) = resolve_array(graph, node) {
+            result.errors.push(item_error(graph, key, node, error));
+        }
+    }
+}
+
+fn item_error(
+    graph: &ResourceGraph,
+    key: &ResourceKey,
+    node: &ResourceNode,
+    error: ItemError,
+) -> AnalysisError {
+    let file = node.origin.file.display();
+    let at = format!("{} of array '{}'", error.position, key.full_name());
+    let (code, message) = match error.issue {
+        ItemIssue::Text(InterpolationIssue::Unresolved(target)) => (
+            codes::UNRESOLVED_REFERENCE,
+            format!(
+                "Unresolved reference '@{}' in {at} ({file}){}",
+                target.full_name(),
+                references::did_you_mean(graph, &target)
+            ),
+        ),
+        ItemIssue::Text(InterpolationIssue::Cycle(chain)) => {
+            let path: Vec<String> =
+                chain.iter().map(ResourceKey::full_name).collect();
+            (
+                codes::REFERENCE_CYCLE,
+                format!(
+                    "Reference cycle detected in {at}: {} ({file})",
+                    path.join(" -> ")
+                ),
+            )
+        }
+        ItemIssue::Text(
+            InterpolationIssue::Template(target)
+            | InterpolationIssue::NoText(target),
+        ) => (
+            codes::INTERPOLATED_NO_TEXT,
+            format!(
+                "Reference '@{}' in {at} has no text value ({file})",
+                target.full_name()
+            ),
+        ),
+        ItemIssue::KindMismatch { target, found } => {
+            let expected = match &node.kind {
+                ResourceKind::Array(item_type) => item_kind(item_type),
+                other => other.clone(),
+            };
+            (
+                codes::KIND_MISMATCH,
+                format!(
+                    "Reference '@{}' in {at} points to a {found:?} resource, \
+                     expected {expected:?} ({file})",
+                    target.full_name()
+                ),
+            )
+        }
+        ItemIssue::Invalid { target, message } => (
+            codes::INVALID_VALUE,
+            format!(
+                "Reference '@{}' in {at}: {message} ({file})",
+                target.full_name()
+            ),
+        ),
+    };
+    AnalysisError::new(code, message, Some(key.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::input::RawResourceFile;
+    use crate::generator::ir::ResourceGraphBuilder;
+    use crate::generator::parsing::parse_raw_files;
+    use std::path::PathBuf;
+
+    fn graph(xml: &str) -> ResourceGraph {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            xml.to_string(),
+            false,
+        );
+        let parsed = parse_raw_files(&[raw], &Default::default()).unwrap();
+        ResourceGraphBuilder::from_parsed_files(&parsed)
+    }
+
+    fn resolved(graph: &ResourceGraph, name: &str) -> ResourceValue {
+        let node = graph.get(&ResourceKey::from_path(name)).unwrap();
+        resolve_array(graph, node).unwrap()
+    }
+
+    fn errors(graph: &ResourceGraph) -> Vec<AnalysisError> {
+        let mut result = AnalysisResult::default();
+        validate_array_references(graph, &mut result);
+        result.errors
+    }
+
+    #[test]
+    fn resolves_string_items_and_resorts() {
+        let graph = graph(
+            r#"<resources>
+                <string name="app">Demo</string>
+                <string name="title">Welcome to @string/app</string>
+                <string-array name="labels" sorted="true">
+                    <item>Custom</item>
+                    <item>@string/title</item>
+                    <item>@string/app</item>
+                </string-array>
+            </resources>"#,
+        );
+        let ResourceValue::Array(items) = resolved(&graph, "labels") else {
+            panic!("expected an array");
+        };
+        assert_eq!(
+            items,
+            vec![
+                ArrayItem::String("Custom".to_string()),
+                ArrayItem::String("Demo".to_string()),
+                ArrayItem::String("Welcome to Demo".to_string()),
+            ]
+        );
+        assert!(errors(&graph).is_empty());
+    }
+
+    #[test]
+    fn resolves_number_items_in_rows() {
+        let graph = graph(
+            r#"<resources>
+                <number name="wall">9</number>
+                <int-array name="level">
+                    <row><item>0</item><item>@number/wall</item></row>
+                </int-array>
+            </resources>"#,
+        );
+        let ResourceValue::ArrayRows(rows) = resolved(&graph, "level") else {
+            panic!("expected rows");
+        };
+        assert_eq!(rows, vec![vec![ArrayItem::Int(0), ArrayItem::Int(9)]]);
+    }
+
+    #[test]
+    fn reports_unresolved_item_with_index() {
+        let graph = graph(
+            r#"<resources>
+                <string-array name="labels">
+                    <item>Custom</item>
+                    <item>@string/missing</item>
+                </string-array>
+            </resources>"#,
+        );
+        let errors = errors(&graph);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, codes::UNRESOLVED_REFERENCE);
+        assert!(errors[0].message.contains(
+            "Unresolved reference '@missing' in item 1 of array 'labels'"
+        ));
+    }
+
+    #[test]
+    fn reports_items_of_the_wrong_kind_or_range() {
+        let graph = graph(
+            r#"<resources>
+                <bool name="enabled">true</bool>
+                <number name="ratio">2.5</number>
+                <string-array name="labels">
+                    <item>@./enabled</item>
+                </string-array>
+                <int-array name="sizes">
+                    <row><item>1</item></row>
+                    <row><item>@number/ratio</item></row>
+                </int-array>
+            </resources>"#,
+        );
+        let errors = errors(&graph);
+        assert_eq!(errors.len(), 2);
+        let mismatch = errors
+            .iter()
+            .find(|error| error.code == codes::KIND_MISMATCH)
+            .unwrap();
+        assert!(mismatch
+            .message
+            .contains("points to a Bool resource, expected String"));
+        let invalid = errors
+            .iter()
+            .find(|error| error.code == codes::INVALID_VALUE)
+            .unwrap();
+        assert!(invalid.message.contains(
+            "'@ratio' in row 1, item 0 of array 'sizes': '2.5' is not an \
+             integer"
+        ));
+    }
+}
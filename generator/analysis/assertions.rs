@@ -0,0 +1,559 @@
+//! Build-time invariants (`<assert expr="min_pool_size <= max_pool_size"/>`).
+//!
+//! The grammar is deliberately tiny: comparisons (`< <= == != >= >`) between
+//! number/bool resources, integer literals, and `true`/`false`, joined with `&&`.
+//! Identifiers resolve from the assertion's namespace outwards to the root.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::generator::ir::{
+    Assertion, NumberValue, ResourceGraph, ResourceKey, ResourceNode,
+    ResourceValue,
+};
+
+use super::references;
+use super::{codes, AnalysisError, AnalysisResult};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    Int(i128),
+    Op(CompareOp),
+    And,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompareOp {
+    Lt,
+    Le,
+    Eq,
+    Ne,
+    Ge,
+    Gt,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Operand {
+    Resource(String),
+    Int(i128),
+    Bool(bool),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Comparison {
+    left: Operand,
+    op: CompareOp,
+    right: Operand,
+}
+
+/// Resolved value of an operand
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Value {
+    Int(i128),
+    Float(f64),
+    Bool(bool),
+}
+
+/// Resource read while evaluating a comparison, kept for the error message
+struct Binding<'a> {
+    key: ResourceKey,
+    value: Value,
+    node: &'a ResourceNode,
+}
+
+impl CompareOp {
+    fn symbol(self) -> &'static str {
+        match self {
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Eq => "==",
+            Self::Ne => "!=",
+            Self::Ge => ">=",
+            Self::Gt => ">",
+        }
+    }
+
+    fn holds(self, ordering: Ordering) -> bool {
+        match self {
+            Self::Lt => ordering.is_lt(),
+            Self::Le => ordering.is_le(),
+            Self::Eq => ordering.is_eq(),
+            Self::Ne => ordering.is_ne(),
+            Self::Ge => ordering.is_ge(),
+            Self::Gt => ordering.is_gt(),
+        }
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ident(name) => write!(f, "'{name}'"),
+            Self::Int(value) => write!(f, "'{value}'"),
+            Self::Op(op) => write!(f, "'{}'", op.symbol()),
+            Self::And => f.write_str("'&&'"),
+        }
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Resource(name) => f.write_str(name),
+            Self::Int(value) => write!(f, "{value}"),
+            Self::Bool(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.left, self.op.symbol(), self.right)
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(value) => write!(f, "{value}"),
+            Self::Float(value) => write!(f, "{value}"),
+            Self::Bool(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl Value {
+    fn as_f64(self) -> f64 {
+        match self {
+            Self::Int(value) => value as f64,
+            Self::Float(value) => value,
+            Self::Bool(value) => f64::from(u8::from(value)),
+        }
+    }
+}
+
+/// Evaluates every `<assert>` of the graph
+pub(super) fn validate_assertions(
+    graph: &ResourceGraph,
+    result: &mut AnalysisResult,
+) {
+    for assertion in graph.assertions() {
+        if let Some(error) = check_assertion(graph, assertion) {
+            result.errors.push(error);
+        }
+    }
+}
+
+fn check_assertion(
+    graph: &ResourceGraph,
+    assertion: &Assertion,
+) -> Option<AnalysisError> {
+    let file = assertion.origin.file.display();
+    let expr = &assertion.expr;
+    let message = match evaluate(graph, assertion) {
+        Ok(None) => return None,
+        Ok(Some(details)) => {
+            format!("Assertion `{expr}` failed ({file}){details}")
+        }
+        Err(message) => {
+            format!("Invalid assertion `{expr}` ({file}): {message}")
+        }
+    };
+    Some(AnalysisError::new(codes::ASSERTION, message, None))
+}
+
+/// `Ok(None)` if the assertion holds, `Ok(Some(details))` if it is false
+fn evaluate(
+    graph: &ResourceGraph,
+    assertion: &Assertion,
+) -> Result<Option<String>, String> {
+    let comparisons = parse(&assertion.expr)?;
+    for comparison in &comparisons {
+        let mut bindings = Vec::new();
+        let scope = &assertion.namespace;
+        let left =
+            operand_value(graph, scope, &comparison.left, &mut bindings)?;
+        let right =
+            operand_value(graph, scope, &comparison.right, &mut bindings)?;
+        if compare(left, comparison.op, right)? {
+            continue;
+        }
+        return Ok(Some(failure_details(
+            comparison,
+            comparisons.len() > 1,
+            &bindings,
+        )));
+    }
+    Ok(None)
+}
+
+/// `: `max_pool_size <= 100` is false; db/max_pool_size = 500 (limits.xml)`
+fn failure_details(
+    comparison: &Comparison,
+    name_comparison: bool,
+    bindings: &[Binding<'_>],
+) -> String {
+    let mut parts = Vec::new();
+    if name_comparison {
+        parts.push(format!("`{comparison}` is false"));
+    }
+    let values: Vec<String> = bindings
+        .iter()
+        .map(|binding| {
+            format!(
+                "{} = {} ({})",
+                binding.key.full_name(),
+                binding.value,
+                binding.node.origin.file.display()
+            )
+        })
+        .collect();
+    if !values.is_empty() {
+        parts.push(values.join(", "));
+    }
+    if parts.is_empty() {
+        return String::new();
+    }
+    format!(": {}", parts.join("; "))
+}
+
+fn operand_value<'a>(
+    graph: &'a ResourceGraph,
+    namespace: &[String],
+    operand: &Operand,
+    bindings: &mut Vec<Binding<'a>>,
+) -> Result<Value, String> {
+    let name = match operand {
+        Operand::Int(value) => return Ok(Value::Int(*value)),
+        Operand::Bool(value) => return Ok(Value::Bool(*value)),
+        Operand::Resource(name) => name,
+    };
+    let (key, node) = lookup(graph, namespace, name)?;
+    let value = resource_value(&key, node)?;
+    bindings.push(Binding { key, value, node });
+    Ok(value)
+}
+
+/// Finds `name` in `namespace`, then in each enclosing namespace up to the root
+fn lookup<'a>(
+    graph: &'a ResourceGraph,
+    namespace: &[String],
+    name: &str,
+) -> Result<(ResourceKey, &'a ResourceNode), String> {
+    for depth in (0..=namespace.len()).rev() {
+        let mut path = namespace[..depth].to_vec();
+        path.push(name.to_string());
+        let key = ResourceKey::from_path(&path.join("/"));
+        if graph.get(&key).is_none() {
+            continue;
+        }
+        let node = references::resolve(graph, &key)
+            .map_err(|_| format!("'{name}' could not be resolved"))?;
+        return Ok((key, node));
+    }
+    Err(format!("unknown resource '{name}'"))
+}
+
+fn resource_value(
+    key: &ResourceKey,
+    node: &ResourceNode,
+) -> Result<Value, String> {
+    let invalid = || {
+        format!(
+            "'{}' is a {:?} resource; assertions compare number and bool resources",
+            key.full_name(),
+            node.kind
+        )
+    };
+    match &node.value {
+        ResourceValue::Bool(value) => Ok(Value::Bool(*value)),
+        ResourceValue::Number(number) => {
+            number_value(number).ok_or_else(invalid)
+        }
+        _ => Err(invalid()),
+    }
+}
+
+fn number_value(number: &NumberValue) -> Option<Value> {
+    let literal = match number {
+        NumberValue::Int(value) => return Some(Value::Int((*value).into())),
+        NumberValue::Float(value) => return Some(Value::Float(*value)),
+        NumberValue::BigDecimal(literal)
+        | NumberValue::Typed { literal, .. } => literal.replace('_', ""),
+    };
+    literal
+        .parse::<i128>()
+        .map(Value::Int)
+        .ok()
+        .or_else(|| literal.parse::<f64>().ok().map(Value::Float))
+}
+
+fn compare(
+    left: Value,
+    op: CompareOp,
+    right: Value,
+) -> Result<bool, String> {
+    let ordering = match (left, right) {
+        (Value::Int(left), Value::Int(right)) => Some(left.cmp(&right)),
+        (Value::Bool(left), Value::Bool(right)) => {
+            if !matches!(op, CompareOp::Eq | CompareOp::Ne) {
+                return Err(format!(
+                    "`{}` does not apply to bool values",
+                    op.symbol()
+                ));
+            }
+            Some(left.cmp(&right))
+        }
+        (Value::Bool(_), _) | (_, Value::Bool(_)) => {
+            return Err(format!("cannot compare {left} with {right}"));
+        }
+        (left, right) => left.as_f64().partial_cmp(&right.as_f64()),
+    };
+    Ok(ordering.is_some_and(|ordering| op.holds(ordering)))
+}
+
+fn parse(expr: &str) -> Result<Vec<Comparison>, String> {
+    if expr.trim().is_empty() {
+        return Err("missing `expr` attribute".to_string());
+    }
+    let mut tokens = tokenize(expr)?.into_iter();
+    let mut comparisons = vec![parse_comparison(&mut tokens)?];
+    while let Some(token) = tokens.next() {
+        if token != Token::And {
+            return Err(format!(
+                "expected `&&` between comparisons, found {token}"
+            ));
+        }
+        comparisons.push(parse_comparison(&mut tokens)?);
+    }
+    Ok(comparisons)
+}
+
+fn parse_comparison(
+    tokens: &mut impl Iterator<Item = Token>,
+) -> Result<Comparison, String> {
+    let left = parse_operand(tokens.next())?;
+    let op = match tokens.next() {
+        Some(Token::Op(op)) => op,
+        Some(other) => {
+            return Err(format!(
+                "expected a comparison operator, found {other}"
+            ))
+        }
+        None => return Err("expected a comparison operator".to_string()),
+    };
+    let right = parse_operand(tokens.next())?;
+    Ok(Comparison { left, op, right })
+}
+
+fn parse_operand(token: Option<Token>) -> Result<Operand, String> {
+    match token {
+        Some(Token::Ident(name)) if name == "true" => Ok(Operand::Bool(true)),
+        Some(Token::Ident(name)) if name == "false" => {
+            Ok(Operand::Bool(false))
+        }
+        Some(Token::Ident(name)) => Ok(Operand::Resource(name)),
+        Some(Token::Int(value)) => Ok(Operand::Int(value)),
+        Some(other) => Err(format!(
+            "expected a resource name or an integer, found {other}"
+        )),
+        None => Err(
+            "expression ends early, expected a resource name or an integer"
+                .to_string(),
+        ),
+    }
+}
+
+fn tokenize(expr: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = expr.trim_start();
+    while let Some(first) = rest.chars().next() {
+        let negative_int = first == '-'
+            && rest[1..].starts_with(|c: char| c.is_ascii_digit());
+        let (token, len) = if first.is_ascii_digit() || negative_int {
+            lex_int(rest)?
+        } else if first.is_ascii_alphabetic() || first == '_' {
+            lex_ident(rest)
+        } else {
+            lex_operator(rest)?
+        };
+        tokens.push(token);
+        rest = rest[len..].trim_start();
+    }
+    Ok(tokens)
+}
+
+fn lex_int(text: &str) -> Result<(Token, usize), String> {
+    let len = text
+        .char_indices()
+        .skip(1)
+        .find(|(_, c)| !c.is_ascii_digit() && *c != '_')
+        .map_or(text.len(), |(index, _)| index);
+    let literal = &text[..len];
+    literal
+        .replace('_', "")
+        .parse::<i128>()
+        .map(|value| (Token::Int(value), len))
+        .map_err(|_| format!("integer '{literal}' is out of range"))
+}
+
+/// Resource names, with `/` separating namespaces (`db/max_pool_size`)
+fn lex_ident(text: &str) -> (Token, usize) {
+    let len = text
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '/'))
+        .unwrap_or(text.len());
+    (Token::Ident(text[..len].to_string()), len)
+}
+
+fn lex_operator(text: &str) -> Result<(Token, usize), String> {
+    let token = match text.get(..2) {
+        Some("<=") => Some((Token::Op(CompareOp::Le), 2)),
+        Some(">=") => Some((Token::Op(CompareOp::Ge), 2)),
+        Some("==") => Some((Token::Op(CompareOp::Eq), 2)),
+        Some("!=") => Some((Token::Op(CompareOp::Ne), 2)),
+        Some("&&") => Some((Token::And, 2)),
+        _ if text.starts_with('<') => Some((Token::Op(CompareOp::Lt), 1)),
+        _ if text.starts_with('>') => Some((Token::Op(CompareOp::Gt), 1)),
+        _ => None,
+    };
+    token.ok_or_else(|| {
+        let found = text.split_whitespace().next().unwrap_or(text);
+        format!(
+            "unsupported syntax '{found}'; only comparisons (< <= == != >= >) joined with && are supported"
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::ResourceGraphBuilder;
+    use crate::generator::parsing::{
+        ParsedAssertion, ParsedResource, ParsedResourceFile,
+    };
+    use std::path::PathBuf;
+
+    fn errors_for(
+        resources: Vec<ParsedResource>,
+        expr: &str,
+        namespace: &[&str],
+    ) -> Vec<String> {
+        let mut file = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            resources,
+        );
+        file.assertions.push(ParsedAssertion {
+            expr: expr.to_string(),
+            namespace: namespace.iter().map(|s| s.to_string()).collect(),
+        });
+        let graph = ResourceGraphBuilder::from_parsed_files(&[file]);
+        let mut result = AnalysisResult::default();
+        validate_assertions(&graph, &mut result);
+        result.errors.into_iter().map(|e| e.message).collect()
+    }
+
+    fn pool(min: &str, max: &str) -> Vec<ParsedResource> {
+        vec![
+            ParsedResource::number("db/min_pool_size", min, None),
+            ParsedResource::number("db/max_pool_size", max, None),
+            ParsedResource::bool("db/tls", true),
+        ]
+    }
+
+    #[test]
+    fn test_parse() {
+        let comparisons = parse("a <= 10 && b/c != -2").unwrap();
+        assert_eq!(
+            comparisons,
+            vec![
+                Comparison {
+                    left: Operand::Resource("a".into()),
+                    op: CompareOp::Le,
+                    right: Operand::Int(10),
+                },
+                Comparison {
+                    left: Operand::Resource("b/c".into()),
+                    op: CompareOp::Ne,
+                    right: Operand::Int(-2),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_unsupported_syntax() {
+        let err = parse("a < b || c").unwrap_err();
+        assert!(err.contains("unsupported syntax '||'"), "{err}");
+        let err = parse("a < b < c").unwrap_err();
+        assert!(err.contains("expected `&&`"), "{err}");
+        let err = parse("a + 1 < b").unwrap_err();
+        assert!(err.contains("unsupported syntax '+'"), "{err}");
+        let err = parse("a <").unwrap_err();
+        assert!(err.contains("ends early"), "{err}");
+        assert!(parse("").unwrap_err().contains("missing `expr`"));
+    }
+
+    #[test]
+    fn passing_assertions_report_nothing() {
+        let expr = "min_pool_size <= max_pool_size && tls == true";
+        assert!(errors_for(pool("5", "20"), expr, &["db"]).is_empty());
+        let expr = "db/max_pool_size < 100";
+        assert!(errors_for(pool("5", "20"), expr, &[]).is_empty());
+    }
+
+    #[test]
+    fn failing_assertion_names_values_and_files() {
+        let errors = errors_for(
+            pool("10", "5"),
+            "min_pool_size <= max_pool_size",
+            &["db"],
+        );
+        assert_eq!(
+            errors,
+            vec![
+                "Assertion `min_pool_size <= max_pool_size` failed (values.xml): db/min_pool_size = 10 (values.xml), db/max_pool_size = 5 (values.xml)"
+            ]
+        );
+    }
+
+    #[test]
+    fn failing_conjunction_names_the_false_comparison() {
+        let errors = errors_for(
+            pool("5", "500"),
+            "min_pool_size > 0 && max_pool_size <= 100",
+            &["db"],
+        );
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains(
+            "`max_pool_size <= 100` is false; db/max_pool_size = 500"
+        ));
+    }
+
+    #[test]
+    fn invalid_operands_are_reported() {
+        let errors = errors_for(pool("5", "20"), "missing < 3", &["db"]);
+        assert!(errors[0].contains("unknown resource 'missing'"));
+        let errors = errors_for(pool("5", "20"), "tls < 3", &["db"]);
+        assert!(errors[0].contains("cannot compare true with 3"));
+        let errors = errors_for(pool("5", "20"), "tls < true", &["db"]);
+        assert!(errors[0].contains("does not apply to bool values"));
+
+        let mut resources = pool("5", "20");
+        resources.push(ParsedResource::string("db/name", "main"));
+        let errors = errors_for(resources, "name == 1", &["db"]);
+        assert!(errors[0].starts_with("Invalid assertion `name == 1`"));
+        assert!(errors[0].contains("'db/name' is a String resource"));
+    }
+
+    #[test]
+    fn float_and_typed_numbers_compare() {
+        let resources = vec![
+            ParsedResource::number("ratio", "0.75", None),
+            ParsedResource::number("limit", "200", Some("u8".into())),
+        ];
+        assert!(errors_for(resources.clone(), "ratio < 1", &[]).is_empty());
+        assert!(errors_for(resources, "limit == 200", &[]).is_empty());
+    }
+}
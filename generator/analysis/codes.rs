@@ -0,0 +1,139 @@
+//! Stable diagnostic codes, one per class of problem.
+//!
+//! Codes never change meaning once released, so CI can filter on them and
+//! `ValidationOptions::deny`/`allow` can name them. Numbers are grouped by
+//! area; gaps are left for new checks.
+//!
+//! | Code | Default | Problem |
+//! | --- | --- | --- |
+//! | `RR0001` | warning | Key defined in several files |
+//! | `RR0002` | warning | Key defined in several files with different types (an error for numbers) |
+//! | `RR0003` | error | Value rejected by its type (e.g. `120%`) |
+//! | `RR0004` | error | Unknown code in `deny`/`allow` |
+//! | `RR0005` | error | More warnings than `max_warnings` |
+//! | `RR0006` | error | Override of a missing key or of a resource without a single value |
+//! | `RR0010` | error | Reference, interpolation, or template call to a missing key |
+//! | `RR0011` | error | Reference, interpolation, or template call cycle |
+//! | `RR0012` | error | Reference, interpolation, or template call to a resource of another kind |
+//! | `RR0013` | error | Interpolation of a template |
+//! | `RR0014` | error | Interpolation of a resource without text |
+//! | `RR0015` | error | String longer than its `max-len`, or invalid limit |
+//! | `RR0016` | warning | Template parameters reordered since the last build |
+//! | `RR0020` | error | `<assert>` false or invalid |
+//! | `RR0030` | error | Key missing from a required locale |
+//! | `RR0031` | warning | Translations with different placeholders |
+//! | `RR0040` | error | Invalid `visibility` |
+//! | `RR0041` | warning | Public resource referencing a restricted one |
+//! | `RR0050` | error | Invalid `feature` name |
+//! | `RR0051` | error | Reference into a feature-gated namespace |
+//! | `RR0052` | warning | Namespace gated in only some files |
+//! | `RR0060` | warning | String that looks like a secret |
+//! | `RR0061` | warning | Value repeated under several keys (opt-in) |
+//! | `RR0062` | warning | String that looks like a number, bool, or color |
+//! | `RR0063` | warning | Invisible or bidi-control character in a value |
+//! | `RR0064` | warning | Fully transparent color |
+//! | `RR0065` | warning | Color in a short `#RGB`/`#ARGB` form (opt-in) |
+//! | `RR0066` | warning | Value larger than `max_value_bytes` |
+//! | `RR0067` | error | Value larger than `hard_max_value_bytes` |
+//! | `RR0070` | warning | No resources found |
+//! | `RR0071` | warning | `<!-- region -->` marker without its counterpart |
+//! | `RR0080` | error | Namespace reserved for a generated module |
+//! | `RR0081` | error | Key nested deeper than `max_namespace_depth` |
+//! | `RR0082` | warning | Key outside any namespace (opt-in) |
+//! | `RR0083` | warning | Name with characters replaced in Rust |
+//! | `RR0084` | error | Two names generated as the same Rust item |
+//! | `RR0085` | warning | Namespace continued in another file without `extend="true"` (opt-in) |
+//! | `RR0086` | warning | Names left out of the generated `prelude` |
+//! | `RR0087` | warning | Key that is also the namespace of other keys |
+//! | `RR0090` | error | Message or code without its counterpart in an `error-enum` namespace |
+//! | `RR0091` | error | Invalid `error-enum` name or code |
+//! | `RR0100` | error | `<raw>` resource without `allow_raw` |
+
+pub const DUPLICATE_KEY: &str = "RR0001";
+pub const TYPE_CONFLICT: &str = "RR0002";
+pub const INVALID_VALUE: &str = "RR0003";
+pub const UNKNOWN_CODE: &str = "RR0004";
+pub const TOO_MANY_WARNINGS: &str = "RR0005";
+pub const INVALID_OVERRIDE: &str = "RR0006";
+pub const UNRESOLVED_REFERENCE: &str = "RR0010";
+pub const REFERENCE_CYCLE: &str = "RR0011";
+pub const KIND_MISMATCH: &str = "RR0012";
+pub const INTERPOLATED_TEMPLATE: &str = "RR0013";
+pub const INTERPOLATED_NO_TEXT: &str = "RR0014";
+pub const STRING_TOO_LONG: &str = "RR0015";
+pub const REORDERED_PARAMETERS: &str = "RR0016";
+pub const ASSERTION: &str = "RR0020";
+pub const MISSING_LOCALE_KEY: &str = "RR0030";
+pub const PLACEHOLDER_MISMATCH: &str = "RR0031";
+pub const INVALID_VISIBILITY: &str = "RR0040";
+pub const VISIBILITY_LEAK: &str = "RR0041";
+pub const INVALID_FEATURE: &str = "RR0050";
+pub const GATED_REFERENCE: &str = "RR0051";
+pub const PARTIALLY_GATED: &str = "RR0052";
+pub const POSSIBLE_SECRET: &str = "RR0060";
+pub const DUPLICATE_VALUE: &str = "RR0061";
+pub const TYPED_LITERAL: &str = "RR0062";
+pub const INVISIBLE_CHARACTER: &str = "RR0063";
+pub const TRANSPARENT_COLOR: &str = "RR0064";
+pub const SHORT_COLOR: &str = "RR0065";
+pub const LARGE_VALUE: &str = "RR0066";
+pub const VALUE_TOO_LARGE: &str = "RR0067";
+pub const NO_RESOURCES: &str = "RR0070";
+pub const UNBALANCED_REGION: &str = "RR0071";
+pub const RESERVED_NAMESPACE: &str = "RR0080";
+pub const NAMESPACE_TOO_DEEP: &str = "RR0081";
+pub const TOP_LEVEL_KEY: &str = "RR0082";
+pub const NAME_SANITIZED: &str = "RR0083";
+pub const NAME_COLLISION: &str = "RR0084";
+pub const IMPLICIT_NAMESPACE_EXTENSION: &str = "RR0085";
+pub const PRELUDE_CONFLICT: &str = "RR0086";
+pub const LEAF_NAMESPACE: &str = "RR0087";
+pub const INCOMPLETE_ERROR_PAIR: &str = "RR0090";
+pub const INVALID_ERROR_ENUM: &str = "RR0091";
+pub const RAW_NOT_ALLOWED: &str = "RR0100";
+
+/// Every code above, in order
+pub const ALL: &[&str] = &[
+    DUPLICATE_KEY,
+    TYPE_CONFLICT,
+    INVALID_VALUE,
+    UNKNOWN_CODE,
+    TOO_MANY_WARNINGS,
+    INVALID_OVERRIDE,
+    UNRESOLVED_REFERENCE,
+    REFERENCE_CYCLE,
+    KIND_MISMATCH,
+    INTERPOLATED_TEMPLATE,
+    INTERPOLATED_NO_TEXT,
+    STRING_TOO_LONG,
+    REORDERED_PARAMETERS,
+    ASSERTION,
+    MISSING_LOCALE_KEY,
+    PLACEHOLDER_MISMATCH,
+    INVALID_VISIBILITY,
+    VISIBILITY_LEAK,
+    INVALID_FEATURE,
+    GATED_REFERENCE,
+    PARTIALLY_GATED,
+    POSSIBLE_SECRET,
+    DUPLICATE_VALUE,
+    TYPED_LITERAL,
+    INVISIBLE_CHARACTER,
+    TRANSPARENT_COLOR,
+    SHORT_COLOR,
+    LARGE_VALUE,
+    VALUE_TOO_LARGE,
+    NO_RESOURCES,
+    UNBALANCED_REGION,
+    RESERVED_NAMESPACE,
+    NAMESPACE_TOO_DEEP,
+    TOP_LEVEL_KEY,
+    NAME_SANITIZED,
+    NAME_COLLISION,
+    IMPLICIT_NAMESPACE_EXTENSION,
+    PRELUDE_CONFLICT,
+    LEAF_NAMESPACE,
+    INCOMPLETE_ERROR_PAIR,
+    INVALID_ERROR_ENUM,
+    RAW_NOT_ALLOWED,
+];
@@ -0,0 +1,155 @@
+//! Checks on hex color values beyond their format.
+//!
+//! A color whose alpha is 0 (`#00FF5722`) renders as nothing, which is
+//! rarely what a designer export meant; it is a warning unless the name says
+//! `transparent` or the resource has `allow-transparent="true"`. With
+//! `ValidationOptions::canonical_colors`, the short forms (`#RGB`, `#ARGB`)
+//! are warnings too, naming the 6- or 8-digit form to write instead.
+
+use crate::generator::ir::types::parse_hex_color;
+use crate::generator::ir::{
+    ResourceGraph, ResourceKey, ResourceNode, ResourceValue,
+};
+
+use super::{codes, AnalysisResult, AnalysisWarning};
+
+/// Warns about fully transparent colors, and about short forms when
+/// `canonical` is set
+pub(super) fn validate_colors(
+    graph: &ResourceGraph,
+    canonical: bool,
+    result: &mut AnalysisResult,
+) {
+    for (key, nodes) in graph.nodes() {
+        for node in nodes {
+            check_color(key, node, canonical, result);
+        }
+    }
+}
+
+fn check_color(
+    key: &ResourceKey,
+    node: &ResourceNode,
+    canonical: bool,
+    result: &mut AnalysisResult,
+) {
+    let ResourceValue::Color(value) = &node.value else {
+        return;
+    };
+    let Some(argb) = parse_hex_color(value) else {
+        return;
+    };
+    let file = node.origin.file.display();
+    if argb[0] == 0 && !transparent_on_purpose(key, node) {
+        result.warnings.push(AnalysisWarning::new(
+            codes::TRANSPARENT_COLOR,
+            format!(
+                "Color '{}' ({file}) is '{}', whose alpha is 0: it is fully transparent. Use an opaque alpha, or add allow-transparent=\"true\" (or name it *transparent*) if that is intended",
+                key.full_name(),
+                value.trim()
+            ),
+            Some(key.clone()),
+        ));
+    }
+    if let Some(long) = long_form(value).filter(|_| canonical) {
+        result.warnings.push(AnalysisWarning::new(
+            codes::SHORT_COLOR,
+            format!(
+                "Color '{}' ({file}) is written '{}'; write it '{long}'",
+                key.full_name(),
+                value.trim()
+            ),
+            Some(key.clone()),
+        ));
+    }
+}
+
+fn transparent_on_purpose(key: &ResourceKey, node: &ResourceNode) -> bool {
+    node.has_flag("allow-transparent")
+        || key.name.to_lowercase().contains("transparent")
+}
+
+/// `#F53` → `#FF5533`, `#8F53` → `#88FF5533`; `None` for other forms
+fn long_form(value: &str) -> Option<String> {
+    let hex = value.trim().strip_prefix('#')?;
+    if !matches!(hex.len(), 3 | 4) {
+        return None;
+    }
+    Some(hex.chars().fold(String::from("#"), |mut long, c| {
+        long.push(c);
+        long.push(c);
+        long
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::analysis::testing::{self, color};
+    use crate::generator::parsing::ParsedResource;
+
+    fn warnings_for(
+        resources: Vec<ParsedResource>,
+        canonical: bool,
+    ) -> Vec<String> {
+        testing::warnings_for(resources, |graph, result| {
+            validate_colors(graph, canonical, result)
+        })
+    }
+
+    #[test]
+    fn flags_fully_transparent_colors() {
+        let warnings = warnings_for(
+            vec![
+                color("button", "#00FF5722"),
+                color("ghost", "#0FFF"),
+                color("overlay", "#80000000"),
+                color("primary", "#FF5722"),
+                color("named", "transparent"),
+            ],
+            false,
+        );
+        assert_eq!(
+            warnings,
+            [
+                "Color 'button' (values.xml) is '#00FF5722', whose alpha is 0: it is fully transparent. Use an opaque alpha, or add allow-transparent=\"true\" (or name it *transparent*) if that is intended",
+                "Color 'ghost' (values.xml) is '#0FFF', whose alpha is 0: it is fully transparent. Use an opaque alpha, or add allow-transparent=\"true\" (or name it *transparent*) if that is intended",
+            ]
+        );
+    }
+
+    #[test]
+    fn transparent_names_and_flag_are_intended() {
+        let mut scrim = color("scrim", "#00000000");
+        scrim
+            .attributes
+            .insert("allow-transparent".to_string(), "true".to_string());
+        let warnings = warnings_for(
+            vec![
+                scrim,
+                color("ui/Transparent_Black", "#00000000"),
+            ],
+            false,
+        );
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+
+    #[test]
+    fn short_forms_only_with_canonical_colors() {
+        let colors = || {
+            vec![
+                color("accent", "#F53"),
+                color("shade", "#8F53"),
+                color("primary", "#FF5533"),
+            ]
+        };
+        assert!(warnings_for(colors(), false).is_empty());
+        assert_eq!(
+            warnings_for(colors(), true),
+            [
+                "Color 'accent' (values.xml) is written '#F53'; write it '#FF5533'",
+                "Color 'shade' (values.xml) is written '#8F53'; write it '#88FF5533'",
+            ]
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! Resources that hold the same value under different keys.
+//!
+//! Copies drift apart when one is edited and the other is forgotten, so
+//! strings equal once trimmed and colors equal regardless of case
+//! (`#ff5722` and `#FF5722`) are reported, one warning per value, with a
+//! reference to the first key as the suggested fix. Many duplicates are
+//! legitimate, so the check is opt-in.
+
+use std::collections::BTreeMap;
+
+use crate::generator::ir::{
+    ResourceGraph, ResourceKey, ResourceNode, ResourceValue,
+};
+
+use super::{codes, AnalysisResult, AnalysisWarning};
+
+/// Warns once per value shared by several string or color resources
+pub(super) fn detect_value_duplicates(
+    graph: &ResourceGraph,
+    result: &mut AnalysisResult,
+) {
+    // Keys come out of the graph sorted, so groups and their keys are too
+    let mut groups: BTreeMap<_, Vec<(&ResourceKey, &ResourceNode)>> =
+        BTreeMap::new();
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first() else {
+            continue;
+        };
+        if let Some(normalized) = normalized_value(node) {
+            groups.entry(normalized).or_default().push((key, node));
+        }
+    }
+
+    for ((kind, value), keys) in groups {
+        if keys.len() < 2 {
+            continue;
+        }
+        let (first, _) = keys[0];
+        let listed: Vec<String> = keys
+            .iter()
+            .map(|(key, node)| {
+                format!(
+                    "'{}' ({})",
+                    key.full_name(),
+                    node.origin.file.display()
+                )
+            })
+            .collect();
+        result.warnings.push(AnalysisWarning::new(
+            codes::DUPLICATE_VALUE,
+            format!(
+                "{} resources share the {kind} value {value:?}: {}. Define it once and reference it (`@{kind}/{}`) so the copies cannot drift apart",
+                keys.len(),
+                listed.join(", "),
+                first.full_name()
+            ),
+            Some(first.clone()),
+        ));
+    }
+}
+
+/// Kind and value compared: strings trimmed, colors also lowercased.
+/// Empty values are left out, as are references, which already share.
+fn normalized_value(node: &ResourceNode) -> Option<(&'static str, String)> {
+    let (kind, value) = match &node.value {
+        ResourceValue::String(text) => ("string", text.trim().to_string()),
+        ResourceValue::Color(color) => {
+            ("color", color.trim().to_ascii_lowercase())
+        }
+        _ => return None,
+    };
+    (!value.is_empty()).then_some((kind, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::analysis::testing::{self, color};
+    use crate::generator::parsing::ParsedResource;
+
+    fn warnings_for(resources: Vec<ParsedResource>) -> Vec<String> {
+        testing::warnings_for(resources, detect_value_duplicates)
+    }
+
+    #[test]
+    fn groups_identical_strings() {
+        let warnings = warnings_for(vec![
+            ParsedResource::string("login/title", "Sign in"),
+            ParsedResource::string("signup/cta", "Sign in "),
+            ParsedResource::string("menu/sign_in", "Sign in"),
+            ParsedResource::string("unique", "Welcome"),
+        ]);
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+        assert_eq!(
+            warnings[0],
+            "3 resources share the string value \"Sign in\": \
+             'login/title' (values.xml), 'menu/sign_in' (values.xml), \
+             'signup/cta' (values.xml). Define it once and reference it \
+             (`@string/login/title`) so the copies cannot drift apart"
+        );
+    }
+
+    #[test]
+    fn colors_differing_in_case_are_duplicates() {
+        let warnings = warnings_for(vec![
+            color("accent", "#FF5722"),
+            color("highlight", "#ff5722"),
+            color("primary", "#3366FF"),
+            // Same text as a color, but not the same kind of resource
+            ParsedResource::string("accent_hex", "#ff5722"),
+        ]);
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+        assert!(warnings[0].contains("color value \"#ff5722\""));
+        assert!(warnings[0].contains("'accent' (values.xml), 'highlight'"));
+        assert!(warnings[0].contains("`@color/accent`"));
+    }
+
+    #[test]
+    fn ignores_empty_values() {
+        let warnings = warnings_for(vec![
+            ParsedResource::string("a", ""),
+            ParsedResource::string("b", "  "),
+        ]);
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+}
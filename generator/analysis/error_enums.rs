@@ -0,0 +1,277 @@
+//! Error enums (`<ns name="error" error-enum="ApiError">`).
+//!
+//! Every string `foo` directly in the namespace pairs with the number
+//! `foo_code`, and each pair becomes a variant of the generated enum. A
+//! string without its code, or a code without its string, is an error naming
+//! the missing half; other resources of the namespace are left out of the
+//! enum.
+
+use std::collections::BTreeSet;
+
+use crate::generator::ir::types::KEYWORDS;
+use crate::generator::ir::{
+    NumberValue, ResourceGraph, ResourceKey, ResourceKind, ResourceValue,
+};
+
+use super::references;
+use super::{codes, AnalysisError, AnalysisResult};
+
+/// Suffix of the number holding the code of a message
+pub(crate) const CODE_SUFFIX: &str = "_code";
+
+/// A message string and the number holding its code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ErrorPair<'a> {
+    pub message: &'a ResourceKey,
+    pub code: &'a ResourceKey,
+}
+
+/// Message and code pairs directly in `namespace`, by message name, then
+/// the strings and codes missing their counterpart
+pub(crate) fn error_pairs<'a>(
+    graph: &'a ResourceGraph,
+    namespace: &[String],
+) -> (Vec<ErrorPair<'a>>, Vec<&'a ResourceKey>) {
+    let of_kind = |kind: ResourceKind| {
+        graph
+            .nodes()
+            .iter()
+            .filter(|(key, _)| key.namespace == namespace)
+            .filter(move |(_, nodes)| {
+                nodes.first().is_some_and(|node| node.kind == kind)
+            })
+            .map(|(key, _)| key)
+    };
+    let codes: Vec<&ResourceKey> = of_kind(ResourceKind::Number)
+        .filter(|key| key.name.ends_with(CODE_SUFFIX))
+        .collect();
+    let mut pairs = Vec::new();
+    let mut unpaired = Vec::new();
+    let mut paired_codes = BTreeSet::new();
+    for message in of_kind(ResourceKind::String) {
+        let code_name = format!("{}{CODE_SUFFIX}", message.name);
+        match codes.iter().find(|code| code.name == code_name) {
+            Some(code) => {
+                paired_codes.insert(*code);
+                pairs.push(ErrorPair { message, code });
+            }
+            None => unpaired.push(message),
+        }
+    }
+    unpaired.extend(codes.into_iter().filter(|c| !paired_codes.contains(c)));
+    (pairs, unpaired)
+}
+
+/// The code a number resource holds, when it is an integer fitting `i64`
+pub(crate) fn error_code(value: &ResourceValue) -> Option<i64> {
+    match value {
+        ResourceValue::Number(NumberValue::Int(code)) => Some(*code),
+        ResourceValue::Number(NumberValue::Typed { literal, ty })
+            if !ty.is_float() =>
+        {
+            literal.parse().ok()
+        }
+        _ => None,
+    }
+}
+
+pub(super) fn validate_error_enums(
+    graph: &ResourceGraph,
+    result: &mut AnalysisResult,
+) {
+    let mut checked = BTreeSet::new();
+    for declared in graph.error_enums() {
+        let namespace = declared.namespace.join("/");
+        let file = declared.origin.file.display();
+        if !is_type_name(&declared.name) {
+            result.errors.push(AnalysisError::new(
+                codes::INVALID_ERROR_ENUM,
+                format!(
+                    "error-enum '{}' on namespace '{namespace}' ({file}) is not an UpperCamelCase type name",
+                    declared.name
+                ),
+                None,
+            ));
+        }
+        let first = graph.error_enum_of(&declared.namespace);
+        if first.is_some_and(|name| name != declared.name) {
+            result.errors.push(AnalysisError::new(
+                codes::INVALID_ERROR_ENUM,
+                format!(
+                    "Namespace '{namespace}' declares error-enum '{}' in {file} but '{}' elsewhere; keep one name",
+                    declared.name,
+                    first.unwrap_or_default()
+                ),
+                None,
+            ));
+        }
+        if checked.insert(&declared.namespace) {
+            check_pairs(graph, &declared.namespace, &declared.name, result);
+        }
+    }
+}
+
+/// Halves without their counterpart, codes that are not integers, and
+/// namespaces without any pair
+fn check_pairs(
+    graph: &ResourceGraph,
+    namespace: &[String],
+    enum_name: &str,
+    result: &mut AnalysisResult,
+) {
+    let (pairs, unpaired) = error_pairs(graph, namespace);
+    if pairs.is_empty() && unpaired.is_empty() {
+        result.errors.push(AnalysisError::new(
+            codes::INVALID_ERROR_ENUM,
+            format!(
+                "error-enum '{enum_name}' on namespace '{}' has no string and `{CODE_SUFFIX}` number pairs to become variants",
+                namespace.join("/")
+            ),
+            None,
+        ));
+    }
+    for key in unpaired {
+        let file = graph
+            .get_all(key)
+            .and_then(<[_]>::first)
+            .map(|node| node.origin.file.display().to_string())
+            .unwrap_or_default();
+        let (missing, half) = match key.name.strip_suffix(CODE_SUFFIX) {
+            Some(message) if is_code(graph, key) => {
+                (format!("string '{message}'"), "code")
+            }
+            _ => (format!("number '{}{CODE_SUFFIX}'", key.name), "message"),
+        };
+        result.errors.push(AnalysisError::new(
+            codes::INCOMPLETE_ERROR_PAIR,
+            format!(
+                "'{}' ({file}) is the {half} of an incomplete pair of error-enum '{enum_name}': {missing} is missing from the namespace",
+                key.full_name()
+            ),
+            Some(key.clone()),
+        ));
+    }
+    for pair in pairs {
+        let Ok(node) = references::resolve(graph, pair.code) else {
+            // Reported with the references
+            continue;
+        };
+        if error_code(&node.value).is_none() {
+            result.errors.push(AnalysisError::new(
+                codes::INVALID_ERROR_ENUM,
+                format!(
+                    "'{}' ({}) is the code of error-enum '{enum_name}' but is not an integer fitting i64",
+                    pair.code.full_name(),
+                    node.origin.file.display()
+                ),
+                Some(pair.code.clone()),
+            ));
+        }
+    }
+}
+
+fn is_code(graph: &ResourceGraph, key: &ResourceKey) -> bool {
+    graph
+        .get_all(key)
+        .and_then(<[_]>::first)
+        .is_some_and(|node| node.kind == ResourceKind::Number)
+}
+
+/// `ApiError`: an ASCII uppercase letter, then letters and digits
+fn is_type_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name.chars().all(|c| c.is_ascii_alphanumeric())
+        && !KEYWORDS.contains(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::ResourceGraphBuilder;
+    use crate::generator::parsing::{
+        ParsedErrorEnum, ParsedResource, ParsedResourceFile,
+    };
+    use std::path::PathBuf;
+
+    fn graph(name: &str, resources: Vec<ParsedResource>) -> ResourceGraph {
+        let mut file = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            resources,
+        );
+        file.error_enums.push(ParsedErrorEnum {
+            namespace: vec!["api".to_string(), "error".to_string()],
+            name: name.to_string(),
+        });
+        ResourceGraphBuilder::from_parsed_files(&[file])
+    }
+
+    fn errors(graph: &ResourceGraph) -> Vec<(&'static str, String)> {
+        let mut result = AnalysisResult::default();
+        validate_error_enums(graph, &mut result);
+        result
+            .errors
+            .into_iter()
+            .map(|error| (error.code, error.message))
+            .collect()
+    }
+
+    #[test]
+    fn pairs_messages_with_their_codes() {
+        let graph = graph(
+            "ApiError",
+            vec![
+                ParsedResource::string("api/error/not_found", "Not found"),
+                ParsedResource::number("api/error/not_found_code", "404", None),
+                ParsedResource::string("api/error/forbidden", "Forbidden"),
+                ParsedResource::number(
+                    "api/error/forbidden_code",
+                    "403",
+                    Some("u16".into()),
+                ),
+                // Neither a message nor a code
+                ParsedResource::number("api/error/retry_after", "30", None),
+            ],
+        );
+
+        let (pairs, unpaired) =
+            error_pairs(&graph, &["api".to_string(), "error".to_string()]);
+        let names: Vec<(&str, &str)> = pairs
+            .iter()
+            .map(|pair| (pair.message.name.as_str(), pair.code.name.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            [("forbidden", "forbidden_code"), ("not_found", "not_found_code")]
+        );
+        assert!(unpaired.is_empty());
+        assert!(errors(&graph).is_empty());
+    }
+
+    #[test]
+    fn reports_incomplete_pairs_and_invalid_enums() {
+        let graph = graph(
+            "api_error",
+            vec![
+                ParsedResource::string("api/error/not_found", "Not found"),
+                ParsedResource::number("api/error/timeout_code", "408", None),
+                ParsedResource::string("api/error/teapot", "I'm a teapot"),
+                ParsedResource::number("api/error/teapot_code", "4.18", None),
+            ],
+        );
+
+        let errors = errors(&graph);
+        assert_eq!(errors.len(), 4, "{errors:?}");
+        assert_eq!(errors[0].0, codes::INVALID_ERROR_ENUM);
+        assert!(errors[0].1.contains("'api_error'"));
+        assert_eq!(errors[1].0, codes::INCOMPLETE_ERROR_PAIR);
+        assert!(errors[1].1.contains(
+            "'api/error/not_found' (values.xml) is the message of an incomplete pair of error-enum 'api_error': number 'not_found_code' is missing"
+        ));
+        assert!(errors[2].1.contains(
+            "'api/error/timeout_code' (values.xml) is the code of an incomplete pair of error-enum 'api_error': string 'timeout' is missing"
+        ));
+        assert_eq!(errors[3].0, codes::INVALID_ERROR_ENUM);
+        assert!(errors[3].1.contains("'api/error/teapot_code'"));
+    }
+}
@@ -0,0 +1,126 @@
+//! Explicit namespace extension (`ValidationOptions::explicit_namespace_extension`).
+//!
+//! Any file may open `<ns name="auth">` and add keys to it, which is how
+//! two files end up colliding by accident. With the option on, the first
+//! file declaring a namespace opens it, and every later file must declare
+//! it `<ns name="auth" extend="true">`. Each level is checked on its own:
+//! extending `auth` does not extend the `auth/login` nested in it. Test
+//! resources are their own module, so they are checked apart.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::generator::ir::ResourceGraph;
+
+use super::{codes, AnalysisResult, AnalysisWarning};
+
+/// Files declaring one namespace, in file order, and whether each marked
+/// all its declarations `extend="true"`
+type Contributors = Vec<(PathBuf, bool)>;
+
+pub(super) fn validate_namespace_extension(
+    graph: &ResourceGraph,
+    result: &mut AnalysisResult,
+) {
+    let mut namespaces: BTreeMap<(bool, &[String]), Contributors> =
+        BTreeMap::new();
+    for declared in graph.namespace_declarations() {
+        let files = namespaces
+            .entry((declared.origin.is_test, &declared.namespace))
+            .or_default();
+        let file = &declared.origin.file;
+        match files.iter_mut().find(|(path, _)| path == file) {
+            // A declaration without `extend` anywhere in the file counts
+            Some((_, extends)) => *extends &= declared.extend,
+            None => files.push((file.clone(), declared.extend)),
+        }
+    }
+    for ((_, namespace), files) in namespaces {
+        let unmarked: Vec<String> = files
+            .iter()
+            .skip(1)
+            .filter(|(_, extends)| !extends)
+            .map(|(path, _)| path.display().to_string())
+            .collect();
+        if unmarked.is_empty() {
+            continue;
+        }
+        let all: Vec<String> = files
+            .iter()
+            .map(|(path, _)| path.display().to_string())
+            .collect();
+        result.warnings.push(AnalysisWarning::new(
+            codes::IMPLICIT_NAMESPACE_EXTENSION,
+            format!(
+                "Namespace '{}' is defined in {} files ({}) but {} continues it without extend=\"true\"; add it where the namespace is extended on purpose",
+                namespace.join("/"),
+                all.len(),
+                all.join(", "),
+                unmarked.join(", ")
+            ),
+            None,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::input::RawResourceFile;
+    use crate::generator::ir::ResourceGraphBuilder;
+    use crate::generator::parsing::parse_raw_files;
+
+    fn warnings(files: &[(&str, &str)]) -> Vec<String> {
+        let raw: Vec<RawResourceFile> = files
+            .iter()
+            .map(|(path, xml)| {
+                RawResourceFile::new(
+                    PathBuf::from(path),
+                    xml.to_string(),
+                    false,
+                )
+            })
+            .collect();
+        let parsed = parse_raw_files(&raw, &Default::default()).unwrap();
+        let graph = ResourceGraphBuilder::from_parsed_files(&parsed);
+        let mut result = AnalysisResult::default();
+        validate_namespace_extension(&graph, &mut result);
+        result
+            .warnings
+            .into_iter()
+            .map(|warning| warning.message)
+            .collect()
+    }
+
+    #[test]
+    fn warns_when_a_later_file_continues_a_namespace_implicitly() {
+        let warnings = warnings(&[
+            ("a.xml", r#"<resources><ns name="auth"><string name="title">A</string></ns></resources>"#),
+            ("b.xml", r#"<resources><ns name="auth" extend="true"><string name="login">B</string></ns></resources>"#),
+            ("c.xml", r#"<resources><ns name="auth"><string name="logout">C</string></ns></resources>"#),
+            ("d.xml", r#"<resources><ns name="ui"><string name="ok">OK</string></ns></resources>"#),
+        ]);
+        assert_eq!(
+            warnings,
+            ["Namespace 'auth' is defined in 3 files (a.xml, b.xml, c.xml) but c.xml continues it without extend=\"true\"; add it where the namespace is extended on purpose"]
+        );
+    }
+
+    #[test]
+    fn checks_nested_namespaces_level_by_level() {
+        let opened = r#"<resources><ns name="auth"><ns name="login"><string name="title">A</string></ns></ns></resources>"#;
+        let both_extended = r#"<resources><ns name="auth" extend="true"><ns name="login" extend="true"><string name="hint">B</string></ns></ns></resources>"#;
+        assert!(warnings(&[("a.xml", opened), ("b.xml", both_extended)])
+            .is_empty());
+
+        let outer_extended = r#"<resources><ns name="auth" extend="true"><ns name="login"><string name="hint">B</string></ns></ns></resources>"#;
+        let found = warnings(&[("a.xml", opened), ("b.xml", outer_extended)]);
+        assert_eq!(found.len(), 1);
+        assert!(found[0].starts_with("Namespace 'auth/login' is defined in 2 files"));
+
+        // A namespace new to the later file needs nothing
+        let new_inner = r#"<resources><ns name="auth" extend="true"><ns name="signup"><string name="hint">B</string></ns></ns></resources>"#;
+        assert!(warnings(&[("a.xml", opened), ("b.xml", new_inner)])
+            .is_empty());
+    }
+}
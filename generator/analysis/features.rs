@@ -0,0 +1,266 @@
+//! Feature-gated namespaces (`<ns name="premium" feature="premium">`).
+//!
+//! A gated module only exists when the consumer enables the cargo feature,
+//! so a resource outside it must not reference one inside (its value would
+//! be inlined into every build). A namespace gated in one file but declared
+//! without the feature in another is a warning: all of its resources end up
+//! behind the feature, whatever their own file says.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use crate::generator::ir::ResourceGraph;
+use crate::generator::parsing::OVERRIDE_FILE;
+
+use super::visibility::referenced_keys;
+use super::{codes, AnalysisError, AnalysisResult, AnalysisWarning};
+
+pub(super) fn validate_features(
+    graph: &ResourceGraph,
+    result: &mut AnalysisResult,
+) {
+    report_invalid_names(graph, result);
+    report_gated_references(graph, result);
+    report_mixed_declarations(graph, result);
+}
+
+/// Cargo feature names: ASCII letters, digits, `_`, `-`, `+`, and `.`
+fn is_valid_feature_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().all(|c| {
+            c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '+' | '.')
+        })
+}
+
+fn report_invalid_names(graph: &ResourceGraph, result: &mut AnalysisResult) {
+    let invalid: BTreeSet<(&PathBuf, &str)> = graph
+        .namespace_features()
+        .iter()
+        .filter(|declared| !is_valid_feature_name(&declared.feature))
+        .map(|declared| (&declared.origin.file, declared.feature.as_str()))
+        .collect();
+    for (file, feature) in invalid {
+        result.errors.push(AnalysisError::new(
+            codes::INVALID_FEATURE,
+            format!(
+                "Invalid feature name '{feature}' in {}",
+                file.display()
+            ),
+            None,
+        ));
+    }
+}
+
+/// A reference must not need features its source does not have
+fn report_gated_references(
+    graph: &ResourceGraph,
+    result: &mut AnalysisResult,
+) {
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first() else {
+            continue;
+        };
+        let available = graph.required_features(key);
+        for target in referenced_keys(&node.value) {
+            let missing: Vec<&str> = graph
+                .required_features(target)
+                .into_iter()
+                .filter(|feature| !available.contains(feature))
+                .collect();
+            if missing.is_empty() || graph.get_all(target).is_none() {
+                continue;
+            }
+            result.errors.push(AnalysisError::new(
+                codes::GATED_REFERENCE,
+                format!(
+                    "'{}' ({}) references '{}', which only exists with feature {}; move the reference under the same feature or the target out of it",
+                    key.full_name(),
+                    node.origin.file.display(),
+                    target.full_name(),
+                    quoted_list(&missing)
+                ),
+                Some(key.clone()),
+            ));
+        }
+    }
+}
+
+/// Files putting resources in a gated namespace without declaring the
+/// feature themselves, reported once per namespace, feature, and file.
+/// Overridden resources are left out: [`OVERRIDE_FILE`] declares no
+/// namespace, their own file was checked with its other resources.
+fn report_mixed_declarations(
+    graph: &ResourceGraph,
+    result: &mut AnalysisResult,
+) {
+    let mut mixed = BTreeSet::new();
+    for declared in graph.namespace_features() {
+        let gates_file = |file: &PathBuf| {
+            graph.namespace_features().iter().any(|other| {
+                other.namespace == declared.namespace
+                    && other.feature == declared.feature
+                    && &other.origin.file == file
+            })
+        };
+        for (key, nodes) in graph.nodes() {
+            if !key.namespace.starts_with(&declared.namespace) {
+                continue;
+            }
+            let ungated = nodes.iter().filter(|n| {
+                n.origin.file != Path::new(OVERRIDE_FILE)
+                    && !gates_file(&n.origin.file)
+            });
+            for node in ungated {
+                mixed.insert((
+                    declared.namespace.join("/"),
+                    declared.feature.clone(),
+                    node.origin.file.clone(),
+                    declared.origin.file.clone(),
+                ));
+            }
+        }
+    }
+    for (namespace, feature, ungated, gated) in mixed {
+        result.warnings.push(AnalysisWarning::new(
+            codes::PARTIALLY_GATED,
+            format!(
+                "Namespace '{namespace}' is gated by feature '{feature}' in {} but not in {}; its resources from {} only exist with the feature too",
+                gated.display(),
+                ungated.display(),
+                ungated.display()
+            ),
+            None,
+        ));
+    }
+}
+
+fn quoted_list(features: &[&str]) -> String {
+    features
+        .iter()
+        .map(|feature| format!("'{feature}'"))
+        .collect::<Vec<_>>()
+        .join(" and ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::input::RawResourceFile;
+    use crate::generator::ir::{ResourceGraphBuilder, ResourceKey};
+    use crate::generator::parsing::parse_raw_files;
+
+    fn graph(files: &[(&str, &str)]) -> ResourceGraph {
+        let raw: Vec<RawResourceFile> = files
+            .iter()
+            .map(|(path, xml)| {
+                RawResourceFile::new(
+                    PathBuf::from(path),
+                    xml.to_string(),
+                    false,
+                )
+            })
+            .collect();
+        let parsed = parse_raw_files(&raw, &Default::default()).unwrap();
+        ResourceGraphBuilder::from_parsed_files(&parsed)
+    }
+
+    fn validate(files: &[(&str, &str)]) -> AnalysisResult {
+        let mut result = AnalysisResult::default();
+        validate_features(&graph(files), &mut result);
+        result
+    }
+
+    #[test]
+    fn nested_namespaces_require_every_ancestor_feature() {
+        let graph = graph(&[(
+            "values.xml",
+            r#"<resources>
+                <ns name="premium" feature="premium">
+                    <ns name="beta" feature="beta">
+                        <string name="key">k</string>
+                    </ns>
+                </ns>
+                <string name="title">T</string>
+            </resources>"#,
+        )]);
+        let required =
+            |path| graph.required_features(&ResourceKey::from_path(path));
+        assert_eq!(required("premium/beta/key"), ["beta", "premium"]);
+        assert!(required("title").is_empty());
+    }
+
+    #[test]
+    fn rejects_references_into_gated_namespaces() {
+        let result = validate(&[(
+            "values.xml",
+            r#"<resources>
+                <ns name="premium" feature="premium">
+                    <string name="banner">Gold</string>
+                    <string name="alias">@string/premium/banner</string>
+                </ns>
+                <string name="title">@string/premium/banner</string>
+                <string name="label">Plan: @string/premium/banner</string>
+            </resources>"#,
+        )]);
+        assert!(result.warnings.is_empty());
+        let messages: Vec<&str> =
+            result.errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages.len(), 2);
+        assert!(messages[0].starts_with(
+            "'label' (values.xml) references 'premium/banner', which only exists with feature 'premium'"
+        ));
+        assert!(messages[1].starts_with("'title' (values.xml)"));
+    }
+
+    #[test]
+    fn warns_when_a_namespace_is_gated_in_only_some_files() {
+        let result = validate(&[
+            (
+                "a.xml",
+                r#"<resources>
+                    <ns name="premium" feature="premium">
+                        <string name="banner">Gold</string>
+                    </ns>
+                </resources>"#,
+            ),
+            (
+                "b.xml",
+                r#"<resources>
+                    <ns name="premium">
+                        <string name="footer">F</string>
+                        <string name="note">N</string>
+                    </ns>
+                </resources>"#,
+            ),
+        ]);
+        assert!(result.errors.is_empty());
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].message.starts_with(
+            "Namespace 'premium' is gated by feature 'premium' in a.xml but not in b.xml"
+        ));
+    }
+
+    #[test]
+    fn invalid_feature_names_are_errors() {
+        let result = validate(&[(
+            "values.xml",
+            r#"<resources>
+                <ns name="a" feature="has space">
+                    <string name="x">x</string>
+                </ns>
+                <ns name="b" feature="">
+                    <string name="y">y</string>
+                </ns>
+            </resources>"#,
+        )]);
+        let messages: Vec<&str> =
+            result.errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Invalid feature name '' in values.xml",
+                "Invalid feature name 'has space' in values.xml",
+            ]
+        );
+    }
+}
@@ -0,0 +1,283 @@
+//! Names that change on their way into Rust: `<string name="My-Key!">`
+//! becomes `MY_KEY_`, and `<ns name="error-codes">` the module
+//! `error_codes`. The generated names are computed by the same functions
+//! the emitters use, so the warning shows what the author will find.
+//!
+//! The same names decide collisions: `app-name` and `app_name` both become
+//! `APP_NAME`, and under `Case::Pascal` so do `appName` and `app_name`, so
+//! the check follows the naming configuration of the build.
+
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::generator::casing::NamingConfig;
+use crate::generator::ir::{
+    ResourceGraph, ResourceKey, ResourceKind, ResourceNode,
+};
+use crate::generator::utils::{pascal_case, sanitize_identifier};
+
+use super::{codes, AnalysisError, AnalysisResult, AnalysisWarning};
+
+/// One warning per resource name and per namespace in which characters
+/// were replaced; case changes (`title` → `TITLE`) are not reported
+pub(super) fn validate_identifiers(
+    graph: &ResourceGraph,
+    naming: &NamingConfig,
+    result: &mut AnalysisResult,
+) {
+    let mut namespaces = BTreeSet::new();
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first() else {
+            continue;
+        };
+        if node.origin.is_builtin() {
+            continue;
+        }
+        let file = node.origin.file.display();
+        for depth in 1..=key.namespace.len() {
+            let part = &key.namespace[depth - 1];
+            if sanitize_identifier(part) == *part
+                || !namespaces.insert(key.namespace[..depth].to_vec())
+            {
+                continue;
+            }
+            result.warnings.push(AnalysisWarning::new(
+                codes::NAME_SANITIZED,
+                format!(
+                    "Namespace '{}' ({file}) is generated as the module `{}`, with `_` for the characters a Rust name cannot hold",
+                    key.namespace[..depth].join("/"),
+                    naming.module_identifier(part)
+                ),
+                Some(key.clone()),
+            ));
+        }
+        if sanitize_identifier(&key.name) == key.name {
+            continue;
+        }
+        result.warnings.push(AnalysisWarning::new(
+            codes::NAME_SANITIZED,
+            format!(
+                "Resource '{}' ({file}) is generated as `{}`, with `_` for the characters a Rust name cannot hold",
+                key.full_name(),
+                generated_name(&key.name, node, naming)
+            ),
+            Some(key.clone()),
+        ));
+    }
+}
+
+/// The main item of the resource: templates are functions and enums
+/// types, everything else a constant
+fn generated_name(
+    name: &str,
+    node: &ResourceNode,
+    naming: &NamingConfig,
+) -> String {
+    match node.kind {
+        ResourceKind::Template => naming.fn_identifier(name),
+        ResourceKind::Enum => pascal_case(name),
+        _ => naming.const_identifier(name),
+    }
+}
+
+/// Where a generated name lives: the resources of the test module, the
+/// module path as generated, and whether the name is a type (enums) or a
+/// value (constants and functions), which Rust keeps apart
+type Scope = (bool, Vec<String>, bool);
+
+/// One error per pair of sibling namespaces generated as the same module,
+/// and per pair of resources generated as the same item in one module
+pub(super) fn validate_name_collisions(
+    graph: &ResourceGraph,
+    naming: &NamingConfig,
+    result: &mut AnalysisResult,
+) {
+    let mut modules = ModuleNames::default();
+    let mut items: BTreeMap<(Scope, String), &ResourceKey> = BTreeMap::new();
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first() else {
+            continue;
+        };
+        if node.origin.is_builtin() {
+            continue;
+        }
+        let is_test = node.origin.is_test;
+        let path = modules.path(key, is_test, naming, result);
+        let is_type = node.kind == ResourceKind::Enum;
+        let name = generated_name(&key.name, node, naming);
+        match items.entry(((is_test, path, is_type), name)) {
+            Entry::Vacant(entry) => {
+                entry.insert(key);
+            }
+            Entry::Occupied(entry) => {
+                let ((_, name), first) = (entry.key(), *entry.get());
+                result.errors.push(AnalysisError::new(
+                    codes::NAME_COLLISION,
+                    format!(
+                        "Resources '{}' ({}) and '{}' ({}) are both generated as `{name}`; rename one of them",
+                        first.full_name(),
+                        origin_file(graph, first),
+                        key.full_name(),
+                        node.origin.file.display()
+                    ),
+                    Some(key.clone()),
+                ));
+            }
+        }
+    }
+}
+
+/// Modules generated so far, to catch sibling namespaces generated as the
+/// same module
+#[derive(Default)]
+struct ModuleNames {
+    /// (test, parent module path, module) → the XML namespace taking it
+    taken: BTreeMap<(bool, Vec<String>, String), Vec<String>>,
+    /// Namespace pairs already reported, as the later namespace
+    reported: BTreeSet<Vec<String>>,
+}
+
+impl ModuleNames {
+    /// The generated module path of `key`, reporting its namespaces that
+    /// land on a module another namespace takes
+    fn path(
+        &mut self,
+        key: &ResourceKey,
+        is_test: bool,
+        naming: &NamingConfig,
+        result: &mut AnalysisResult,
+    ) -> Vec<String> {
+        let mut path = Vec::new();
+        for depth in 1..=key.namespace.len() {
+            let namespace = &key.namespace[..depth];
+            let module = naming.module_identifier(&namespace[depth - 1]);
+            let slot = (is_test, path.clone(), module.clone());
+            let taken =
+                self.taken.entry(slot).or_insert_with(|| namespace.to_vec());
+            if taken.as_slice() != namespace
+                && self.reported.insert(namespace.to_vec())
+            {
+                result.errors.push(AnalysisError::new(
+                    codes::NAME_COLLISION,
+                    format!(
+                        "Namespaces '{}' and '{}' are both generated as the module `{module}`; rename one of them",
+                        taken.join("/"),
+                        namespace.join("/")
+                    ),
+                    Some(key.clone()),
+                ));
+            }
+            path.push(module);
+        }
+        path
+    }
+}
+
+fn origin_file(graph: &ResourceGraph, key: &ResourceKey) -> String {
+    graph
+        .get_all(key)
+        .and_then(<[_]>::first)
+        .map(|node| node.origin.file.display().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::analysis::testing;
+    use crate::generator::casing::Case;
+    use crate::generator::parsing::ParsedResource;
+
+    fn warnings_for(resources: Vec<ParsedResource>) -> Vec<String> {
+        testing::warnings_for(resources, |graph, result| {
+            validate_identifiers(graph, &NamingConfig::default(), result)
+        })
+    }
+
+    #[test]
+    fn reports_replaced_characters_but_not_case() {
+        let warnings = warnings_for(vec![
+            ParsedResource::string("appName", "Demo"),
+            ParsedResource::string("My-Key!", "x"),
+        ]);
+        assert_eq!(
+            warnings,
+            ["Resource 'My-Key!' (values.xml) is generated as `MY_KEY_`, \
+              with `_` for the characters a Rust name cannot hold"]
+        );
+    }
+
+    fn collisions_for(resources: Vec<ParsedResource>) -> Vec<String> {
+        collisions_named(resources, &NamingConfig::default())
+    }
+
+    fn collisions_named(
+        resources: Vec<ParsedResource>,
+        naming: &NamingConfig,
+    ) -> Vec<String> {
+        let result = testing::run_pass(resources, |graph, result| {
+            validate_name_collisions(graph, naming, result)
+        });
+        result.errors.into_iter().map(|e| e.message).collect()
+    }
+
+    #[test]
+    fn names_generated_twice_collide() {
+        let errors = collisions_for(vec![
+            ParsedResource::string("app-name", "Demo"),
+            ParsedResource::string("app_name", "Demo"),
+            ParsedResource::string("error-codes/not_found", "404"),
+            ParsedResource::string("error_codes/gone", "410"),
+            ParsedResource::string("error_codes/forbidden", "403"),
+        ]);
+        assert_eq!(
+            errors,
+            [
+                "Resources 'app-name' (values.xml) and 'app_name' \
+                 (values.xml) are both generated as `APP_NAME`; rename one of \
+                 them",
+                "Namespaces 'error-codes' and 'error_codes' are both \
+                 generated as the module `error_codes`; rename one of them",
+            ]
+        );
+    }
+
+    #[test]
+    fn collisions_follow_the_naming_configuration() {
+        let resources = || {
+            vec![
+                ParsedResource::string("appName", "Demo"),
+                ParsedResource::string("app_name", "Demo"),
+            ]
+        };
+        assert!(collisions_for(resources()).is_empty());
+        let pascal = NamingConfig {
+            const_case: Case::Pascal,
+            ..NamingConfig::default()
+        };
+        let errors = collisions_named(resources(), &pascal);
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert!(
+            errors[0].contains("both generated as `AppName`"),
+            "{errors:?}"
+        );
+    }
+
+    #[test]
+    fn reports_each_namespace_once() {
+        let warnings = warnings_for(vec![
+            ParsedResource::string("error-codes/not_found", "404"),
+            ParsedResource::string("error-codes/forbidden", "403"),
+            ParsedResource::string("error-codes/http.v2/gone", "410"),
+        ]);
+        assert_eq!(warnings.len(), 2, "{warnings:?}");
+        assert!(warnings[0].starts_with(
+            "Namespace 'error-codes' (values.xml) is generated as the \
+             module `error_codes`"
+        ));
+        assert!(warnings[1].starts_with(
+            "Namespace 'error-codes/http.v2' (values.xml) is generated as \
+             the module `http_v2`"
+        ));
+    }
+}
@@ -0,0 +1,390 @@
+//! String interpolation (`Retrying up to @number/max_retries times`).
+//!
+//! Interpolated strings are flattened to plain text: each embedded reference is
+//! resolved and rendered with `value_as_display_string`. Templates have no single
+//! text value and cannot be interpolated, and a reference naming a kind
+//! (`@bool/flag`) must point to a resource of that kind.
+
+use crate::generator::ir::types::{is_raw, number_literal};
+use crate::generator::ir::{
+    InterpolationPart, ResourceGraph, ResourceKey, ResourceNode,
+    ResourceValue,
+};
+
+use super::references::{self, ReferenceIssue};
+use super::{codes, AnalysisError, AnalysisResult};
+
+/// Why an interpolated string could not be flattened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InterpolationIssue {
+    /// A referenced key does not exist
+    Unresolved(ResourceKey),
+    /// Interpolations (or references) loop back (chain includes the repeated key)
+    Cycle(Vec<ResourceKey>),
+    /// The referenced resource is a template
+    Template(ResourceKey),
+    /// The referenced resource has no single text value (e.g. a mime-map)
+    NoText(ResourceKey),
+}
+
+impl From<ReferenceIssue> for InterpolationIssue {
+    fn from(issue: ReferenceIssue) -> Self {
+        match issue {
+            ReferenceIssue::Unresolved(target) => Self::Unresolved(target),
+            ReferenceIssue::Cycle(cycle) => Self::Cycle(cycle),
+        }
+    }
+}
+
+/// Text of a concrete value as it appears when interpolated.
+///
+/// Numbers use the literal formatting of the number emitter (`3`, not `3.0`),
+/// BigDecimals keep their literal text, and bools render as `true`/`false`.
+/// Returns `None` for values without a single text form (templates,
+/// `<raw>` Rust expressions, and references or interpolations that must be
+/// resolved first).
+pub fn value_as_display_string(node: &ResourceNode) -> Option<String> {
+    if is_raw(node) {
+        return None;
+    }
+    match &node.value {
+        ResourceValue::String(value) | ResourceValue::Color(value) => {
+            Some(value.clone())
+        }
+        ResourceValue::Number(number) => Some(number_literal(number)),
+        ResourceValue::Bool(value) => Some(value.to_string()),
+        ResourceValue::Char(value) => Some(value.to_string()),
+        ResourceValue::Percent { raw, .. } => Some(raw.clone()),
+        ResourceValue::Cron { expression, .. } => Some(expression.clone()),
+        ResourceValue::Size { raw, .. }
+        | ResourceValue::DateTime { raw, .. }
+        | ResourceValue::Dimension { raw, .. } => Some(raw.clone()),
+        ResourceValue::Date { year, month, day } => {
+            Some(format!("{year:04}-{month:02}-{day:02}"))
+        }
+        ResourceValue::Version {
+            major,
+            minor,
+            patch,
+        } => Some(format!("{major}.{minor}.{patch}")),
+        ResourceValue::MimeMap(_)
+        | ResourceValue::Enum { .. }
+        | ResourceValue::Array(_)
+        | ResourceValue::ArrayRows(_)
+        | ResourceValue::Template { .. }
+        | ResourceValue::Reference { .. }
+        | ResourceValue::Interpolated(_) => None,
+    }
+}
+
+/// Flattens the resource at `key` to text, resolving references and nested
+/// interpolations.
+pub fn resolve_text(
+    graph: &ResourceGraph,
+    key: &ResourceKey,
+) -> Result<String, InterpolationIssue> {
+    resolve_with_chain(graph, key, &mut Vec::new())
+}
+
+fn resolve_with_chain(
+    graph: &ResourceGraph,
+    key: &ResourceKey,
+    chain: &mut Vec<ResourceKey>,
+) -> Result<String, InterpolationIssue> {
+    let is_cycle = chain.contains(key);
+    chain.push(key.clone());
+    if is_cycle {
+        return Err(InterpolationIssue::Cycle(chain.clone()));
+    }
+
+    let node = references::resolve(graph, key)?;
+
+    let text = match &node.value {
+        ResourceValue::Interpolated(parts) => {
+            join_parts(graph, parts, chain)?
+        }
+        ResourceValue::Template { .. } => {
+            return Err(InterpolationIssue::Template(key.clone()))
+        }
+        _ => value_as_display_string(node)
+            .ok_or_else(|| InterpolationIssue::NoText(key.clone()))?,
+    };
+    chain.pop();
+    Ok(text)
+}
+
+fn join_parts(
+    graph: &ResourceGraph,
+    parts: &[InterpolationPart],
+    chain: &mut Vec<ResourceKey>,
+) -> Result<String, InterpolationIssue> {
+    let mut text = String::new();
+    for part in parts {
+        match part {
+            InterpolationPart::Text(literal) => text.push_str(literal),
+            InterpolationPart::Reference { target, .. } => {
+                text.push_str(&resolve_with_chain(graph, target, chain)?);
+            }
+        }
+    }
+    Ok(text)
+}
+
+/// Validates every interpolated string in the graph
+pub(super) fn validate_interpolations(
+    graph: &ResourceGraph,
+    result: &mut AnalysisResult,
+) {
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first() else {
+            continue;
+        };
+        if !matches!(node.value, ResourceValue::Interpolated(_)) {
+            continue;
+        }
+        if let Some(error) = check_interpolation(graph, key, node) {
+            result.errors.push(error);
+        }
+    }
+}
+
+fn check_interpolation(
+    graph: &ResourceGraph,
+    key: &ResourceKey,
+    node: &ResourceNode,
+) -> Option<AnalysisError> {
+    let file = node.origin.file.display();
+    let (code, message) = match resolve_text(graph, key) {
+        Ok(_) => return check_kinds(graph, key, node),
+        Err(InterpolationIssue::Unresolved(target)) => (
+            codes::UNRESOLVED_REFERENCE,
+            format!(
+                "Unresolved reference '{}' in interpolated string '{}' ({file}){}",
+                references::written_reference(graph, node, &target),
+                key.full_name(),
+                references::did_you_mean(graph, &target)
+            ),
+        ),
+        Err(InterpolationIssue::Template(target)) => (
+            codes::INTERPOLATED_TEMPLATE,
+            format!(
+                "Interpolated string '{}' references template '{}'; templates take parameters and cannot be interpolated ({file})",
+                key.full_name(),
+                target.full_name()
+            ),
+        ),
+        Err(InterpolationIssue::NoText(target)) => (
+            codes::INTERPOLATED_NO_TEXT,
+            format!(
+                "Interpolated string '{}' references '{}', which has no text value ({file})",
+                key.full_name(),
+                target.full_name()
+            ),
+        ),
+        Err(InterpolationIssue::Cycle(chain)) => {
+            // Report a cycle once, from its smallest key
+            let cycle_start = chain.iter().min()?;
+            if cycle_start != key {
+                return None;
+            }
+            let path: Vec<String> =
+                chain.iter().map(ResourceKey::full_name).collect();
+            let message = format!(
+                "Interpolation cycle detected: {} ({file})",
+                path.join(" -> ")
+            );
+            (codes::REFERENCE_CYCLE, message)
+        }
+    };
+    Some(AnalysisError::new(code, message, Some(key.clone())))
+}
+
+/// The first reference of the interpolated string at `key` whose target is
+/// not of the kind it names, like `@bool/flag` on a string
+fn check_kinds(
+    graph: &ResourceGraph,
+    key: &ResourceKey,
+    node: &ResourceNode,
+) -> Option<AnalysisError> {
+    let ResourceValue::Interpolated(parts) = &node.value else {
+        return None;
+    };
+    parts.iter().find_map(|part| {
+        let InterpolationPart::Reference {
+            target,
+            kind: Some(expected),
+        } = part
+        else {
+            return None;
+        };
+        let found = &references::resolve(graph, target).ok()?.kind;
+        (found != expected).then(|| {
+            AnalysisError::new(
+                codes::KIND_MISMATCH,
+                format!(
+                    "Reference '@{}/{}' in interpolated string '{}' points to a {found:?} resource, expected {expected:?} ({})",
+                    expected.tag(),
+                    target.full_name(),
+                    key.full_name(),
+                    node.origin.file.display()
+                ),
+                Some(key.clone()),
+            )
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::analysis::testing::graph;
+    use crate::generator::input::RawResourceFile;
+    use crate::generator::ir::ResourceGraphBuilder;
+    use crate::generator::parsing::{
+        parse_raw_files, InterpolationPart as ParsedPart, ParsedResource,
+        ResourceKind, ScalarValue,
+    };
+    use std::path::PathBuf;
+
+    fn interpolated(
+        name: &str,
+        before: &str,
+        path: &str,
+    ) -> ParsedResource {
+        ParsedResource {
+            name: name.to_string(),
+            kind: ResourceKind::String,
+            value: ScalarValue::Interpolated(vec![
+                ParsedPart::Text(before.to_string()),
+                ParsedPart::Reference {
+                    kind: None,
+                    path: path.to_string(),
+                },
+            ]),
+            attributes: Default::default(),
+        }
+    }
+
+    fn text_of(graph: &ResourceGraph, path: &str) -> String {
+        resolve_text(graph, &ResourceKey::from_path(path))
+            .expect("resolves")
+    }
+
+    #[test]
+    fn numbers_use_emitted_literal_format() {
+        let graph = graph(vec![
+            ParsedResource::number("retries", "3", None),
+            ParsedResource::number("ratio", "2.50", None),
+            ParsedResource::number(
+                "precise",
+                "3.14159265358979323846",
+                None,
+            ),
+            interpolated("a", "up to ", "retries"),
+            interpolated("b", "x", "ratio"),
+            interpolated("c", "pi=", "precise"),
+        ]);
+        assert_eq!(text_of(&graph, "a"), "up to 3");
+        assert_eq!(text_of(&graph, "b"), "x2.5");
+        assert_eq!(text_of(&graph, "c"), "pi=3.14159265358979323846");
+    }
+
+    #[test]
+    fn bools_and_nested_interpolations_resolve() {
+        let graph = graph(vec![
+            ParsedResource::bool("enabled", true),
+            interpolated("inner", "enabled=", "enabled"),
+            interpolated("outer", "[", "inner"),
+        ]);
+        assert_eq!(text_of(&graph, "outer"), "[enabled=true");
+
+        let mut result = AnalysisResult::default();
+        validate_interpolations(&graph, &mut result);
+        assert!(result.errors.is_empty());
+    }
+
+    #[test]
+    fn template_targets_are_rejected() {
+        let graph = graph(vec![
+            ParsedResource {
+                name: "greeting".to_string(),
+                kind: ResourceKind::Template,
+                value: ScalarValue::Template {
+                    text: "Hello {name}".to_string(),
+                    params: Vec::new(),
+                },
+                attributes: Default::default(),
+            },
+            interpolated("banner", "Say ", "greeting"),
+        ]);
+        let mut result = AnalysisResult::default();
+        validate_interpolations(&graph, &mut result);
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.errors[0]
+            .message
+            .contains("references template 'greeting'"));
+    }
+
+    #[test]
+    fn reports_unresolved_and_cycles_once() {
+        let graph = graph(vec![
+            interpolated("a", "", "b"),
+            interpolated("b", "", "a"),
+            interpolated("c", "", "missing"),
+        ]);
+        let mut result = AnalysisResult::default();
+        validate_interpolations(&graph, &mut result);
+        let messages: Vec<&str> =
+            result.errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages.len(), 2, "{messages:?}");
+        assert!(messages[0].contains("a -> b -> a"));
+        assert!(messages[1].contains("Unresolved reference '@missing'"));
+    }
+
+    #[test]
+    fn unresolved_references_are_quoted_as_written() {
+        let xml = r#"<resources>
+            <number name="max_retries">3</number>
+            <string name="hint">Up to @number/max_retry times</string>
+        </resources>"#;
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            xml.to_string(),
+            false,
+        );
+        let files = parse_raw_files(&[raw], &Default::default()).unwrap();
+        let graph = ResourceGraphBuilder::from_parsed_files(&files);
+        let mut result = AnalysisResult::default();
+        validate_interpolations(&graph, &mut result);
+        assert_eq!(result.errors.len(), 1, "{:?}", result.errors);
+        assert_eq!(
+            result.errors[0].message,
+            "Unresolved reference '@number/max_retry' in interpolated string 'hint' (values.xml); did you mean '@max_retries'?"
+        );
+    }
+
+    #[test]
+    fn named_kinds_must_match_the_target() {
+        let xml = r#"<resources>
+            <bool name="flag">true</bool>
+            <number name="retries">3</number>
+            <string name="value">Value: @bool/flag</string>
+            <string name="wrong">Retrying @string/retries times</string>
+        </resources>"#;
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            xml.to_string(),
+            false,
+        );
+        let files = parse_raw_files(&[raw], &Default::default()).unwrap();
+        let graph = ResourceGraphBuilder::from_parsed_files(&files);
+        let mut result = AnalysisResult::default();
+        validate_interpolations(&graph, &mut result);
+        assert_eq!(result.errors.len(), 1, "{:?}", result.errors);
+        assert_eq!(result.errors[0].code, codes::KIND_MISMATCH);
+        assert_eq!(
+            result.errors[0].message,
+            "Reference '@string/retries' in interpolated string 'wrong' points to a Number resource, expected String (values.xml)"
+        );
+    }
+}
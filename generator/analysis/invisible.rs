@@ -0,0 +1,187 @@
+//! Invisible characters in string and template values.
+//!
+//! A zero-width space or a stray bidi control pasted with a translation
+//! renders as nothing, yet breaks comparisons, search, and layout. Each
+//! string or template text holding one produces a warning listing the code
+//! points and where they first appear; `allow-invisible="true"` silences a
+//! resource that needs them (a `ZWJ` in an emoji sequence, say). No-break
+//! spaces are legitimate in much typography, so they are only flagged with
+//! `ValidationOptions::flag_nbsp`.
+
+use crate::generator::ir::{
+    InterpolationPart, ResourceGraph, ResourceNode, ResourceValue,
+};
+
+use super::{codes, AnalysisResult, AnalysisWarning};
+
+/// Characters that render as nothing, or only reorder the text around them
+const INVISIBLE: &[(char, &str)] = &[
+    ('\u{00AD}', "soft hyphen"),
+    ('\u{061C}', "arabic letter mark"),
+    ('\u{180E}', "mongolian vowel separator"),
+    ('\u{200B}', "zero-width space"),
+    ('\u{200C}', "zero-width non-joiner"),
+    ('\u{200D}', "zero-width joiner"),
+    ('\u{200E}', "left-to-right mark"),
+    ('\u{200F}', "right-to-left mark"),
+    ('\u{202A}', "left-to-right embedding"),
+    ('\u{202B}', "right-to-left embedding"),
+    ('\u{202C}', "pop directional formatting"),
+    ('\u{202D}', "left-to-right override"),
+    ('\u{202E}', "right-to-left override"),
+    ('\u{2060}', "word joiner"),
+    ('\u{2066}', "left-to-right isolate"),
+    ('\u{2067}', "right-to-left isolate"),
+    ('\u{2068}', "first strong isolate"),
+    ('\u{2069}', "pop directional isolate"),
+    ('\u{FEFF}', "byte order mark"),
+];
+
+/// No-break spaces, flagged only when asked
+const NO_BREAK: &[(char, &str)] = &[
+    ('\u{00A0}', "no-break space"),
+    ('\u{202F}', "narrow no-break space"),
+];
+
+/// Warns about strings and templates holding invisible characters
+pub(super) fn detect_invisible_chars(
+    graph: &ResourceGraph,
+    flag_nbsp: bool,
+    result: &mut AnalysisResult,
+) {
+    for (key, nodes) in graph.nodes() {
+        for node in nodes {
+            if node.has_flag("allow-invisible") {
+                continue;
+            }
+            let Some(text) = value_text(node) else {
+                continue;
+            };
+            let found = invisible_chars(&text, flag_nbsp);
+            if found.is_empty() {
+                continue;
+            }
+            let listed: Vec<String> = found
+                .iter()
+                .map(|(c, name, at)| {
+                    format!(
+                        "U+{:04X} {name} at character {at}",
+                        *c as u32
+                    )
+                })
+                .collect();
+            result.warnings.push(AnalysisWarning::new(
+                codes::INVISIBLE_CHARACTER,
+                format!(
+                    "'{}' ({}) contains invisible characters: {}. Remove them, or add allow-invisible=\"true\" if they are intended",
+                    key.full_name(),
+                    node.origin.file.display(),
+                    listed.join(", ")
+                ),
+                Some(key.clone()),
+            ));
+        }
+    }
+}
+
+/// Text of a string or template, interpolated references left out
+fn value_text(node: &ResourceNode) -> Option<String> {
+    match &node.value {
+        ResourceValue::String(text)
+        | ResourceValue::Template { text, .. } => Some(text.clone()),
+        ResourceValue::Interpolated(parts) => Some(
+            parts
+                .iter()
+                .filter_map(|part| match part {
+                    InterpolationPart::Text(text) => {
+                        Some(text.as_str())
+                    }
+                    InterpolationPart::Reference { .. } => None,
+                })
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// Each invisible character of `text` once, with its name and the 1-based
+/// position of its first occurrence, in order of appearance
+fn invisible_chars(
+    text: &str,
+    flag_nbsp: bool,
+) -> Vec<(char, &'static str, usize)> {
+    let mut found: Vec<(char, &'static str, usize)> = Vec::new();
+    for (index, c) in text.chars().enumerate() {
+        if found.iter().any(|(seen, _, _)| *seen == c) {
+            continue;
+        }
+        let listed = INVISIBLE
+            .iter()
+            .chain(NO_BREAK.iter().filter(|_| flag_nbsp))
+            .find(|(invisible, _)| *invisible == c);
+        if let Some((_, name)) = listed {
+            found.push((c, name, index + 1));
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::analysis::testing;
+    use crate::generator::parsing::ParsedResource;
+
+    fn warnings_for(
+        resources: Vec<ParsedResource>,
+        flag_nbsp: bool,
+    ) -> Vec<String> {
+        testing::warnings_for(resources, |graph, result| {
+            detect_invisible_chars(graph, flag_nbsp, result)
+        })
+    }
+
+    #[test]
+    fn flags_zero_width_and_bidi_characters() {
+        let warnings = warnings_for(
+            vec![
+                ParsedResource::string(
+                    "greeting",
+                    "Hel\u{200B}lo\u{200B}",
+                ),
+                ParsedResource::string("price", "\u{FEFF}\u{202E}42"),
+                ParsedResource::string("plain", "Hello"),
+            ],
+            false,
+        );
+        assert_eq!(
+            warnings,
+            [
+                "'greeting' (values.xml) contains invisible characters: U+200B zero-width space at character 4. Remove them, or add allow-invisible=\"true\" if they are intended",
+                "'price' (values.xml) contains invisible characters: U+FEFF byte order mark at character 1, U+202E right-to-left override at character 2. Remove them, or add allow-invisible=\"true\" if they are intended",
+            ]
+        );
+    }
+
+    #[test]
+    fn no_break_spaces_only_when_asked() {
+        let price =
+            || vec![ParsedResource::string("price", "42\u{00A0}€")];
+        assert!(warnings_for(price(), false).is_empty());
+        let warnings = warnings_for(price(), true);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0]
+            .contains("U+00A0 no-break space at character 3"));
+    }
+
+    #[test]
+    fn allow_invisible_attribute_suppresses_finding() {
+        let mut family =
+            ParsedResource::string("family", "👩\u{200D}👧");
+        family.attributes.insert(
+            "allow-invisible".to_string(),
+            "true".to_string(),
+        );
+        assert!(warnings_for(vec![family], true).is_empty());
+    }
+}
@@ -0,0 +1,131 @@
+//! Keys that are also namespaces.
+//!
+//! `<string name="api">` next to `<ns name="api">` generates `API` beside
+//! `mod api`, which compiles but reads as one thing, and stops making sense
+//! once someone adds `api/api`. Each such key is a warning naming the files
+//! of the value and of the namespace; `deny` makes it an error. Test
+//! resources are their own module, so they only conflict among themselves.
+
+use std::collections::BTreeMap;
+
+use crate::generator::ir::{ResourceGraph, ResourceKey};
+
+use super::{codes, AnalysisResult, AnalysisWarning};
+
+/// Keys under a namespace path and the files holding them, in file order
+#[derive(Default)]
+struct Contents<'a> {
+    keys: usize,
+    files: Vec<String>,
+    first: Option<&'a ResourceKey>,
+}
+
+pub(super) fn validate_leaf_namespaces(
+    graph: &ResourceGraph,
+    result: &mut AnalysisResult,
+) {
+    let mut namespaces: BTreeMap<(bool, &[String]), Contents<'_>> =
+        BTreeMap::new();
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first() else {
+            continue;
+        };
+        let file = node.origin.file.display().to_string();
+        for depth in 1..=key.namespace.len() {
+            let contents = namespaces
+                .entry((node.origin.is_test, &key.namespace[..depth]))
+                .or_default();
+            contents.keys += 1;
+            contents.first.get_or_insert(key);
+            if !contents.files.contains(&file) {
+                contents.files.push(file.clone());
+            }
+        }
+    }
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first() else {
+            continue;
+        };
+        let path: Vec<String> = key
+            .namespace
+            .iter()
+            .chain(std::iter::once(&key.name))
+            .cloned()
+            .collect();
+        let Some(contents) =
+            namespaces.get(&(node.origin.is_test, path.as_slice()))
+        else {
+            continue;
+        };
+        let name = key.full_name();
+        let held = match (contents.keys, contents.first) {
+            (1, Some(first)) => format!("'{}'", first.full_name()),
+            (count, _) => format!("{count} keys"),
+        };
+        result.warnings.push(AnalysisWarning::new(
+            codes::LEAF_NAMESPACE,
+            format!(
+                "'{name}' is both a value ({}) and a namespace holding {held} ({}); rename the value, or nest it in the namespace as '{name}/_self'",
+                node.origin.file.display(),
+                contents.files.join(", ")
+            ),
+            Some(key.clone()),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::input::RawResourceFile;
+    use crate::generator::ir::ResourceGraphBuilder;
+    use crate::generator::parsing::parse_raw_files;
+    use std::path::PathBuf;
+
+    fn warnings(files: &[(&str, &str, bool)]) -> Vec<String> {
+        let raw: Vec<RawResourceFile> = files
+            .iter()
+            .map(|(path, xml, is_test)| {
+                RawResourceFile::new(
+                    PathBuf::from(path),
+                    xml.to_string(),
+                    *is_test,
+                )
+            })
+            .collect();
+        let parsed = parse_raw_files(&raw, &Default::default()).unwrap();
+        let graph = ResourceGraphBuilder::from_parsed_files(&parsed);
+        let mut result = AnalysisResult::default();
+        validate_leaf_namespaces(&graph, &mut result);
+        result
+            .warnings
+            .into_iter()
+            .map(|warning| warning.message)
+            .collect()
+    }
+
+    #[test]
+    fn warns_when_a_value_is_also_a_namespace() {
+        let warnings = warnings(&[
+            ("values.xml", r#"<resources><string name="api">v2</string><ns name="ui"><string name="theme">dark</string></ns></resources>"#, false),
+            ("api.xml", r#"<resources><ns name="api"><string name="base">https://example.com</string></ns></resources>"#, false),
+            ("ui.xml", r#"<resources><ns name="ui"><ns name="theme"><color name="bg">#FFFFFF</color><color name="fg">#000000</color></ns></ns></resources>"#, false),
+        ]);
+        assert_eq!(
+            warnings,
+            [
+                "'api' is both a value (values.xml) and a namespace holding 'api/base' (api.xml); rename the value, or nest it in the namespace as 'api/_self'",
+                "'ui/theme' is both a value (values.xml) and a namespace holding 2 keys (ui.xml); rename the value, or nest it in the namespace as 'ui/theme/_self'",
+            ]
+        );
+    }
+
+    #[test]
+    fn main_and_test_resources_do_not_conflict() {
+        let warnings = warnings(&[
+            ("values.xml", r#"<resources><string name="api">v2</string><ns name="auth"><string name="title">A</string></ns></resources>"#, false),
+            ("tests/values.xml", r#"<resources><ns name="api"><string name="base">mock</string></ns></resources>"#, true),
+        ]);
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+}
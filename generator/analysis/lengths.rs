@@ -0,0 +1,133 @@
+//! `max-len` limits on strings copied into fixed-size buffers.
+//!
+//! `<string name="device_name" max-len="32">` caps the UTF-8 byte length of
+//! the value, as resolved when generated (references and interpolations
+//! included). Bytes are what a buffer holds, so `é` counts as two.
+
+use crate::generator::ir::{ResourceGraph, ResourceKind};
+
+use super::{codes, interpolation, AnalysisError, AnalysisResult};
+
+/// Attribute holding the limit, in bytes
+const MAX_LEN: &str = "max-len";
+
+/// One error per string longer than its `max-len`, or whose limit is not
+/// a number
+pub(super) fn validate_string_lengths(
+    graph: &ResourceGraph,
+    result: &mut AnalysisResult,
+) {
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first() else {
+            continue;
+        };
+        let Some(limit) = node.attributes.get(MAX_LEN) else {
+            continue;
+        };
+        if node.kind != ResourceKind::String {
+            continue;
+        }
+        let file = node.origin.file.display();
+        let Ok(limit) = limit.trim().parse::<usize>() else {
+            result.errors.push(AnalysisError::new(
+                codes::STRING_TOO_LONG,
+                format!(
+                    "String '{}' ({file}) has max-len=\"{limit}\", which is not a byte count",
+                    key.full_name()
+                ),
+                Some(key.clone()),
+            ));
+            continue;
+        };
+        // Unresolvable values are reported by the reference checks
+        let Ok(text) = interpolation::resolve_text(graph, key) else {
+            continue;
+        };
+        if text.len() <= limit {
+            continue;
+        }
+        let chars = text.chars().count();
+        let counted = if chars == text.len() {
+            format!("{} bytes", text.len())
+        } else {
+            format!("{} bytes ({chars} characters)", text.len())
+        };
+        result.errors.push(AnalysisError::new(
+            codes::STRING_TOO_LONG,
+            format!(
+                "String '{}' ({file}) is {counted} of UTF-8, over its max-len of {limit}",
+                key.full_name()
+            ),
+            Some(key.clone()),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::ResourceGraphBuilder;
+    use crate::generator::parsing::{
+        ParsedResource, ParsedResourceFile, ScalarValue,
+    };
+    use std::path::PathBuf;
+
+    fn limited(name: &str, value: &str, limit: &str) -> ParsedResource {
+        let mut resource = ParsedResource::string(name, value);
+        resource
+            .attributes
+            .insert(MAX_LEN.to_string(), limit.to_string());
+        resource
+    }
+
+    fn errors_for(resources: Vec<ParsedResource>) -> Vec<String> {
+        let file = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            resources,
+        );
+        let graph = ResourceGraphBuilder::from_parsed_files(&[file]);
+        let mut result = AnalysisResult::default();
+        validate_string_lengths(&graph, &mut result);
+        result.errors.into_iter().map(|e| e.message).collect()
+    }
+
+    #[test]
+    fn counts_bytes_not_characters() {
+        // 6 characters, but `é` takes two bytes
+        let errors = errors_for(vec![
+            limited("fits", "Sensor", "6"),
+            limited("accented", "Séance", "6"),
+        ]);
+        assert_eq!(
+            errors,
+            ["String 'accented' (values.xml) is 7 bytes (6 characters) of \
+              UTF-8, over its max-len of 6"]
+        );
+    }
+
+    #[test]
+    fn checks_the_resolved_text() {
+        let mut device_name = limited("device_name", "", "3");
+        device_name.value = ScalarValue::Reference {
+            kind: Some("string".to_string()),
+            path: "brand".to_string(),
+        };
+        let errors = errors_for(vec![
+            ParsedResource::string("brand", "Acme"),
+            device_name,
+        ]);
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert!(errors[0].contains("is 4 bytes of UTF-8"), "{errors:?}");
+    }
+
+    #[test]
+    fn limit_must_be_a_byte_count() {
+        let errors = errors_for(vec![limited("name", "x", "32b")]);
+        assert_eq!(
+            errors,
+            ["String 'name' (values.xml) has max-len=\"32b\", which is not \
+              a byte count"]
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! Heuristic detection of typed values declared as strings.
+//!
+//! Files migrated from other formats often hold `<string name="max_items">25</string>`
+//! where a `<number>` was meant, and the value is only parsed at runtime. A string is
+//! flagged when its whole trimmed value is a number, `true`/`false`, or a hex color;
+//! text around the value (`25 items`) is left alone. Findings are warnings:
+//! `allow-literal="true"` on the resource or its key in
+//! `ValidationOptions::literal_allow_list` silences them.
+
+use crate::generator::ir::types::parse_hex_color;
+use crate::generator::ir::{
+    ResourceGraph, ResourceKind, ResourceNode, ResourceValue,
+};
+
+use super::{codes, AnalysisResult, AnalysisWarning};
+
+/// Warns about string resources whose value belongs in a typed tag
+pub(super) fn detect_typed_literals(
+    graph: &ResourceGraph,
+    allow_list: &[String],
+    result: &mut AnalysisResult,
+) {
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first() else {
+            continue;
+        };
+        if allow_list.iter().any(|allowed| *allowed == key.full_name()) {
+            continue;
+        }
+        let Some((value, tag)) = typed_literal(node) else {
+            continue;
+        };
+        result.warnings.push(AnalysisWarning::new(
+            codes::TYPED_LITERAL,
+            format!(
+                "String '{}' ({}) holds \"{value}\", which looks like a {tag}. Declare it as <{tag} name=\"{}\">{value}</{tag}> so it is typed at build time, or add allow-literal=\"true\" if it is text",
+                key.full_name(),
+                node.origin.file.display(),
+                key.name
+            ),
+            Some(key.clone()),
+        ));
+    }
+}
+
+/// The trimmed value and the tag it belongs in, if it looks typed
+fn typed_literal(node: &ResourceNode) -> Option<(&str, &'static str)> {
+    let ResourceValue::String(value) = &node.value else {
+        return None;
+    };
+    if node.kind != ResourceKind::String || node.has_flag("allow-literal") {
+        return None;
+    }
+    let value = value.trim();
+    let tag = if value == "true" || value == "false" {
+        "bool"
+    } else if is_number(value) {
+        "number"
+    } else if value.starts_with('#') && parse_hex_color(value).is_some() {
+        "color"
+    } else {
+        return None;
+    };
+    Some((value, tag))
+}
+
+/// `25`, `-3`, `0.75`, `1e6`; not `inf`, `NaN`, or `1.2.3`
+fn is_number(value: &str) -> bool {
+    value.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '+')
+        && value.chars().any(|c| c.is_ascii_digit())
+        && value.parse::<f64>().is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::analysis::testing;
+    use crate::generator::parsing::ParsedResource;
+
+    fn warnings_for(
+        resources: Vec<ParsedResource>,
+        allow_list: &[String],
+    ) -> Vec<String> {
+        testing::warnings_for(resources, |graph, result| {
+            detect_typed_literals(graph, allow_list, result)
+        })
+    }
+
+    #[test]
+    fn flags_numbers_bools_and_colors() {
+        let warnings = warnings_for(
+            vec![
+                ParsedResource::string("accent", "#FF5722"),
+                ParsedResource::string("enabled", " true "),
+                ParsedResource::string("limits/max_items", "25"),
+                ParsedResource::string("ratio", "-0.75"),
+            ],
+            &[],
+        );
+        assert_eq!(warnings.len(), 4, "{warnings:?}");
+        let warning = |key: &str| -> &String {
+            let key = format!("'{key}' (values.xml)");
+            warnings.iter().find(|w| w.contains(&key)).unwrap()
+        };
+        let accent = warning("accent");
+        assert!(accent.contains("looks like a color"), "{accent}");
+        assert!(accent.contains("<color name=\"accent\">#FF5722</color>"));
+        let enabled = warning("enabled");
+        assert!(enabled.contains("<bool name=\"enabled\">true</bool>"));
+        let max_items = warning("limits/max_items");
+        assert!(max_items.contains("<number name=\"max_items\">25</number>"));
+        assert!(warning("ratio").contains("looks like a number"));
+    }
+
+    #[test]
+    fn ignores_text_around_values() {
+        let warnings = warnings_for(
+            vec![
+                ParsedResource::string("items", "25 items"),
+                ParsedResource::string("version", "1.2.3"),
+                ParsedResource::string("infinity", "inf"),
+                ParsedResource::string("answer", "True story"),
+                ParsedResource::string("hashtag", "#rust"),
+                ParsedResource::string("empty", ""),
+            ],
+            &[],
+        );
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+
+    #[test]
+    fn allow_literal_and_allow_list_suppress_finding() {
+        let mut resource = ParsedResource::string("zip_code", "02134");
+        resource
+            .attributes
+            .insert("allow-literal".to_string(), "true".to_string());
+        let warnings = warnings_for(
+            vec![resource, ParsedResource::string("build/year", "2024")],
+            &["build/year".to_string()],
+        );
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+}
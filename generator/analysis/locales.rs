@@ -0,0 +1,347 @@
+//! Required locales (`<ns name="api" require-locales="en,fr">` or
+//! `ValidationOptions::required_complete`).
+//!
+//! Locales are namespaces directly below the governed one (`api/en`,
+//! `api/fr`). Every key defined under one locale must exist under all the
+//! others; each missing locale+key combination is an error naming the file
+//! that defines the reference copy.
+//!
+//! Translations of the same key must also take the same placeholders
+//! (`%1$s`, `{name}`): a translation that drops or adds one formats its
+//! arguments wrongly at runtime, so each difference is a warning.
+
+use std::collections::BTreeMap;
+
+use crate::generator::ir::types::{placeholders, Placeholder};
+use crate::generator::ir::{
+    ResourceGraph, ResourceKey, ResourceNode, ResourceValue,
+};
+
+use super::{
+    codes, AnalysisError, AnalysisResult, AnalysisWarning, RequiredComplete,
+};
+
+/// Key relative to its locale namespace -> its node, one map per locale
+type LocaleKeys<'a> = Vec<BTreeMap<Vec<String>, &'a ResourceNode>>;
+
+/// Checks the requirements declared in XML and those passed as options
+pub(super) fn validate_required_locales(
+    graph: &ResourceGraph,
+    required: &[RequiredComplete],
+    result: &mut AnalysisResult,
+) {
+    let mut requirements: Vec<(Vec<String>, Vec<String>)> = required
+        .iter()
+        .map(|req| (path_parts(&req.namespace), req.locales.clone()))
+        .collect();
+    for req in graph.locale_requirements() {
+        requirements.push((req.namespace.clone(), req.locales.clone()));
+    }
+    // The same policy in XML and in options is reported once
+    requirements.sort();
+    requirements.dedup();
+
+    for (namespace, locales) in &requirements {
+        let by_locale: LocaleKeys<'_> = locales
+            .iter()
+            .map(|locale| {
+                let mut prefix = namespace.clone();
+                prefix.push(locale.clone());
+                keys_below(graph, &prefix)
+            })
+            .collect();
+        check_requirement(namespace, locales, &by_locale, result);
+        check_placeholders(namespace, locales, &by_locale, result);
+    }
+}
+
+fn path_parts(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter(|part| !part.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn check_requirement(
+    namespace: &[String],
+    locales: &[String],
+    by_locale: &LocaleKeys<'_>,
+    result: &mut AnalysisResult,
+) {
+    for relative in all_keys(by_locale) {
+        let Some((reference_locale, reference_node)) = locales
+            .iter()
+            .zip(by_locale)
+            .find_map(|(locale, keys)| Some((locale, *keys.get(relative)?)))
+        else {
+            continue;
+        };
+        for (locale, keys) in locales.iter().zip(by_locale) {
+            if keys.contains_key(relative) {
+                continue;
+            }
+            let missing = locale_key(namespace, locale, relative);
+            let reference = locale_key(namespace, reference_locale, relative);
+            result.errors.push(AnalysisError::new(
+                codes::MISSING_LOCALE_KEY,
+                format!(
+                    "Missing '{}' for locale '{locale}': namespace '{}' requires locales {}. Reference copy '{}' is defined in {}",
+                    missing.full_name(),
+                    namespace.join("/"),
+                    locales.join(", "),
+                    reference.full_name(),
+                    reference_node.origin.file.display()
+                ),
+                Some(missing),
+            ));
+        }
+    }
+}
+
+/// Compares the placeholders of every translated text with those of the
+/// first locale defining the key; one warning per differing locale
+fn check_placeholders(
+    namespace: &[String],
+    locales: &[String],
+    by_locale: &LocaleKeys<'_>,
+    result: &mut AnalysisResult,
+) {
+    for relative in all_keys(by_locale) {
+        let mut translations =
+            locales.iter().zip(by_locale).filter_map(|(locale, keys)| {
+                let node = *keys.get(relative)?;
+                Some((locale, node, sorted_placeholders(node)?))
+            });
+        let Some((reference_locale, reference_node, expected)) =
+            translations.next()
+        else {
+            continue;
+        };
+        for (locale, node, found) in translations {
+            if found == expected {
+                continue;
+            }
+            let key = locale_key(namespace, locale, relative);
+            let reference = locale_key(namespace, reference_locale, relative);
+            let mut differences = Vec::new();
+            let missing = difference(&expected, &found);
+            if !missing.is_empty() {
+                differences.push(format!("missing {}", missing.join(", ")));
+            }
+            let extra = difference(&found, &expected);
+            if !extra.is_empty() {
+                differences.push(format!("extra {}", extra.join(", ")));
+            }
+            result.warnings.push(AnalysisWarning::new(
+                codes::PLACEHOLDER_MISMATCH,
+                format!(
+                    "Placeholders of '{}' (locale '{locale}', {}) differ from '{}' (locale '{reference_locale}', {}): {}",
+                    key.full_name(),
+                    node.origin.file.display(),
+                    reference.full_name(),
+                    reference_node.origin.file.display(),
+                    differences.join("; ")
+                ),
+                Some(key),
+            ));
+        }
+    }
+}
+
+/// Placeholders of a string or template, sorted so that translations
+/// ordering their arguments differently compare equal. `None` for other
+/// kinds of resources, which are not translated text.
+fn sorted_placeholders(node: &ResourceNode) -> Option<Vec<Placeholder>> {
+    let text = match &node.value {
+        ResourceValue::String(text)
+        | ResourceValue::Template { text, .. } => text,
+        _ => return None,
+    };
+    let mut found = placeholders(text);
+    found.sort();
+    Some(found)
+}
+
+/// Placeholders of `from` left once each one of `other` has been matched,
+/// so a placeholder used twice in one text and once in the other counts
+fn difference(from: &[Placeholder], other: &[Placeholder]) -> Vec<String> {
+    let mut unmatched = other.to_vec();
+    from.iter()
+        .filter(|placeholder| {
+            let matched = unmatched.iter().position(|o| o == *placeholder);
+            matched.map(|index| unmatched.remove(index)).is_none()
+        })
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Every relative key defined under at least one locale, sorted
+fn all_keys<'a>(by_locale: &'a LocaleKeys<'_>) -> Vec<&'a Vec<String>> {
+    let mut keys: Vec<&Vec<String>> =
+        by_locale.iter().flat_map(BTreeMap::keys).collect();
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+fn keys_below<'a>(
+    graph: &'a ResourceGraph,
+    prefix: &[String],
+) -> BTreeMap<Vec<String>, &'a ResourceNode> {
+    graph
+        .nodes()
+        .iter()
+        .filter(|(key, _)| key.namespace.starts_with(prefix))
+        .filter_map(|(key, nodes)| {
+            let mut relative = key.namespace[prefix.len()..].to_vec();
+            relative.push(key.name.clone());
+            Some((relative, nodes.first()?))
+        })
+        .collect()
+}
+
+fn locale_key(
+    namespace: &[String],
+    locale: &str,
+    relative: &[String],
+) -> ResourceKey {
+    let mut path = namespace.to_vec();
+    path.push(locale.to_string());
+    path.extend_from_slice(relative);
+    ResourceKey::from_path(&path.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::ResourceGraphBuilder;
+    use crate::generator::parsing::{
+        ParsedLocaleRequirement, ParsedResource, ParsedResourceFile,
+    };
+    use std::path::PathBuf;
+
+    fn file(path: &str, names: &[&str]) -> ParsedResourceFile {
+        let resources = names
+            .iter()
+            .map(|name| ParsedResource::string(*name, "text"))
+            .collect();
+        ParsedResourceFile::new(PathBuf::from(path), false, resources)
+    }
+
+    fn required(namespace: &str, locales: &[&str]) -> RequiredComplete {
+        RequiredComplete {
+            namespace: namespace.to_string(),
+            locales: locales.iter().map(|l| l.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn reports_each_missing_locale_and_key() {
+        let graph = ResourceGraphBuilder::from_parsed_files(&[
+            file("en.xml", &["api/en/error/denied", "api/en/error/missing"]),
+            file("fr.xml", &["api/fr/error/denied", "api/fr/title"]),
+        ]);
+        let mut result = AnalysisResult::default();
+        validate_required_locales(
+            &graph,
+            &[required("api", &["en", "fr"])],
+            &mut result,
+        );
+
+        let messages: Vec<&str> =
+            result.errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages.len(), 2);
+        assert!(messages[0].starts_with(
+            "Missing 'api/fr/error/missing' for locale 'fr': namespace 'api' requires locales en, fr."
+        ));
+        assert!(messages[0].ends_with(
+            "Reference copy 'api/en/error/missing' is defined in en.xml"
+        ));
+        assert!(messages[1].starts_with("Missing 'api/en/title'"));
+        assert!(messages[1].ends_with("defined in fr.xml"));
+    }
+
+    #[test]
+    fn xml_and_option_requirements_are_reported_once() {
+        let mut en = file("en.xml", &["api/en/title"]);
+        en.locale_requirements.push(ParsedLocaleRequirement {
+            namespace: vec!["api".to_string()],
+            locales: vec!["en".to_string(), "fr".to_string()],
+        });
+        let graph = ResourceGraphBuilder::from_parsed_files(&[en]);
+        let mut result = AnalysisResult::default();
+        validate_required_locales(
+            &graph,
+            &[required("api/", &["en", "fr"])],
+            &mut result,
+        );
+        assert_eq!(result.errors.len(), 1);
+    }
+
+    #[test]
+    fn complete_locales_pass() {
+        let graph = ResourceGraphBuilder::from_parsed_files(&[
+            file("en.xml", &["api/en/title", "other/x"]),
+            file("fr.xml", &["api/fr/title"]),
+        ]);
+        let mut result = AnalysisResult::default();
+        validate_required_locales(
+            &graph,
+            &[required("api", &["en", "fr"])],
+            &mut result,
+        );
+        assert!(result.errors.is_empty());
+    }
+
+    fn translations(texts: &[(&str, &str)]) -> Vec<String> {
+        let resources = texts
+            .iter()
+            .map(|(name, text)| ParsedResource::string(*name, *text))
+            .collect();
+        let file =
+            ParsedResourceFile::new(PathBuf::from("ui.xml"), false, resources);
+        let graph = ResourceGraphBuilder::from_parsed_files(&[file]);
+        let mut result = AnalysisResult::default();
+        validate_required_locales(
+            &graph,
+            &[required("ui", &["en", "fr", "de"])],
+            &mut result,
+        );
+        result.warnings.into_iter().map(|w| w.message).collect()
+    }
+
+    #[test]
+    fn translations_must_keep_their_placeholders() {
+        let warnings = translations(&[
+            ("ui/en/hello", "Hello %1$s, %2$d new"),
+            ("ui/fr/hello", "Bonjour"),
+            ("ui/de/hello", "%2$d neu, hallo %1$s"),
+            ("ui/en/bye", "Bye {name}"),
+            ("ui/fr/bye", "Au revoir {name} {name} %1$s"),
+            ("ui/de/bye", "Tschüss {name}"),
+        ]);
+        assert_eq!(
+            warnings,
+            [
+                "Placeholders of 'ui/fr/bye' (locale 'fr', ui.xml) differ \
+                 from 'ui/en/bye' (locale 'en', ui.xml): extra %1$s, {name}",
+                "Placeholders of 'ui/fr/hello' (locale 'fr', ui.xml) differ \
+                 from 'ui/en/hello' (locale 'en', ui.xml): missing %1$s, %2$d",
+            ]
+        );
+    }
+
+    #[test]
+    fn placeholders_compare_against_the_first_locale_defining_the_key() {
+        // `en` comes first but lacks the key (an error of its own)
+        let warnings = translations(&[
+            ("ui/fr/title", "Titre {page}"),
+            ("ui/de/title", "Titel"),
+            ("ui/fr/count", "%1$s"),
+        ]);
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+        assert!(warnings[0].starts_with("Placeholders of 'ui/de/title'"));
+        assert!(warnings[0].contains("from 'ui/fr/title' (locale 'fr'"));
+        assert!(warnings[0].ends_with("missing {page}"));
+    }
+}
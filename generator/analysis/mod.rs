@@ -2,54 +2,145 @@
 //!
 //! This module performs validations on the resource graph, including:
 //! - Duplicate detection (with configurable warnings/errors)
-//! - Reference resolution (future)
-//! - Interpolation analysis (future)
+//! - Reference resolution (unresolved targets, cycles, kind mismatches)
+//! - Interpolation analysis (unresolved targets, cycles, template targets)
+//! - References in array items, resolved against the array's item type
+//! - Template calls (unknown callees, cycles, callees behind a feature)
+//! - `<assert>` invariants evaluated against the resolved values
+//! - Strings longer than their `max-len`, in UTF-8 bytes
+//! - Resources rejected by their type handler while building the graph
+//! - String values that look like committed secrets (warnings only)
+//! - String and color values repeated under several keys (opt-in warnings)
+//! - Keys nested deeper than `ValidationOptions::max_namespace_depth`
+//! - Keys that are also the namespace of other keys
+//! - Keys outside any namespace, when `ValidationOptions::require_namespace`
+//!   is set
+//! - Keys missing from a locale that a namespace requires, and translations
+//!   whose placeholders differ
+//! - Invalid `visibility` values, and public resources referencing
+//!   restricted ones
+//! - Invalid `feature` names, references into feature-gated namespaces
+//!   from outside them, and namespaces gated in only some files
+//! - `<!-- region -->` comments without their counterpart
+//! - Message and code pairs of `error-enum` namespaces
+//! - `<raw>` Rust constants, unless `ValidationOptions::allow_raw` is set
+//! - `BuildPlan::overrides` that could not be applied
+//!
+//! Diagnostics about a resource quote its element from the XML source.
 //!
 //! All validations return structured `AnalysisResult` with separate warnings and errors.
+//! Every diagnostic carries a stable code from [`codes`].
+
+pub mod arrays;
+mod assertions;
+pub mod codes;
+mod colors;
+mod duplicate_values;
+mod extension;
+pub(crate) mod error_enums;
+mod features;
+mod identifiers;
+mod invisible;
+pub mod interpolation;
+mod leaf_namespaces;
+mod lengths;
+mod literals;
+mod locales;
+pub mod references;
+mod regions;
+mod secrets;
+mod sizes;
+mod snippets;
+pub mod template_calls;
+#[cfg(test)]
+pub(crate) mod testing;
+mod visibility;
 
-use crate::generator::ir::{ResourceGraph, ResourceKey};
+pub(crate) use snippets::attach_snippets;
+
+use crate::generator::casing::NamingConfig;
+use crate::generator::ir::types::{is_raw, number_rust_type};
+use crate::generator::ir::{
+    ResourceGraph, ResourceKey, ResourceNode, ResourceValue,
+};
+use crate::generator::parsing::{OverrideIssue, OVERRIDE_FILE};
+use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
 #[allow(dead_code)] // Fields are used in Display/Error implementations
 pub struct AnalysisError {
+    /// Stable code from [`codes`], e.g. `RR0010`
+    pub code: &'static str,
     pub message: String,
     pub key: Option<ResourceKey>,
+    /// Excerpt of the XML declaring `key`, filled in by the pipeline
+    pub snippet: Option<String>,
 }
 
 impl AnalysisError {
     pub fn new(
+        code: &'static str,
         message: impl Into<String>,
         key: Option<ResourceKey>,
     ) -> Self {
         Self {
+            code,
             message: message.into(),
             key,
+            snippet: None,
         }
     }
 }
 
+impl std::fmt::Display for AnalysisError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}", self.code, self.message)
+    }
+}
+
+/// A problem that does not stop the build, as listed in
+/// `OutputArtifacts::warnings`
 #[derive(Debug, Clone)]
 pub struct AnalysisWarning {
+    /// Stable code from [`codes`], e.g. `RR0001`
+    pub code: &'static str,
     pub message: String,
     pub key: Option<ResourceKey>,
+    /// File declaring `key`, filled in by the pipeline
+    pub file: Option<PathBuf>,
+    /// Excerpt of the XML declaring `key`, filled in by the pipeline
+    pub snippet: Option<String>,
 }
 
 impl AnalysisWarning {
     pub fn new(
+        code: &'static str,
         message: impl Into<String>,
         key: Option<ResourceKey>,
     ) -> Self {
         Self {
+            code,
             message: message.into(),
             key,
+            file: None,
+            snippet: None,
         }
     }
 }
 
+impl std::fmt::Display for AnalysisWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}", self.code, self.message)
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct AnalysisResult {
     pub warnings: Vec<AnalysisWarning>,
     pub errors: Vec<AnalysisError>,
+    /// Informational notes, such as the elements left out for another
+    /// profile; only printed with `BuildPlan::verbose`
+    pub notes: Vec<String>,
 }
 
 impl AnalysisResult {
@@ -59,26 +150,202 @@ impl AnalysisResult {
     }
 }
 
+/// Default for [`ValidationOptions::max_namespace_depth`]
+pub const DEFAULT_MAX_NAMESPACE_DEPTH: usize = 8;
+
+pub use sizes::{DEFAULT_HARD_MAX_VALUE_BYTES, DEFAULT_MAX_VALUE_BYTES};
+
+/// Keys listed by name in the namespace depth error before the rest are
+/// only counted
+const LISTED_DEEP_KEYS: usize = 5;
+
 /// Validation options
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone)]
 pub struct ValidationOptions {
     /// If true, duplicate warnings become errors
     pub treat_duplicates_as_errors: bool,
+    /// If true, numbers duplicated with different Rust types (`u16` in one
+    /// file, `i64` in another) are errors even when other duplicates are
+    /// warnings
+    pub type_mismatch_duplicates_as_errors: bool,
+    /// If true, strings that look like secrets produce warnings
+    pub detect_secrets: bool,
+    /// If true, strings and templates holding zero-width, bidi-control, or
+    /// byte order mark characters produce warnings
+    pub detect_invisible_chars: bool,
+    /// If true, no-break spaces count as invisible characters too
+    pub flag_nbsp: bool,
+    /// If true, colors written `#RGB` or `#ARGB` produce warnings naming
+    /// their 6- or 8-digit form
+    pub canonical_colors: bool,
+    /// If true, string and color values shared by several keys produce
+    /// one warning per value
+    pub detect_value_duplicates: bool,
+    /// If true, strings whose whole value is a number, `true`/`false`, or
+    /// a hex color produce warnings suggesting the typed tag
+    pub detect_typed_literals: bool,
+    /// Keys (`"build/year"`) whose string value is text even though it
+    /// looks typed, like `allow-literal="true"` on the resource
+    pub literal_allow_list: Vec<String>,
+    /// Most namespaces a key may be nested in (`a/b/c/key` is 3); deeper
+    /// keys are errors. `None` lifts the limit, in which case generation
+    /// recurses once per level.
+    pub max_namespace_depth: Option<usize>,
+    /// Largest value, in bytes, that is a literal without a warning; an
+    /// array counts its items together. `None` lifts the limit.
+    pub max_value_bytes: Option<usize>,
+    /// Largest value, in bytes, that builds at all. `None` lifts the
+    /// limit.
+    pub hard_max_value_bytes: Option<usize>,
+    /// If true, every key outside a namespace produces a warning, unless
+    /// the resource has `allow-top-level="true"`
+    pub require_namespace: bool,
+    /// If true, a namespace that several files declare must be declared
+    /// `<ns extend="true">` by all but the first; the others are warned about
+    pub explicit_namespace_extension: bool,
+    /// If true, `<raw>` resources emit their Rust expression as written;
+    /// otherwise each one is an error
+    pub allow_raw: bool,
+    /// If true, names whose characters are replaced in the generated code
+    /// (`error-codes` → `error_codes`) are not reported
+    pub quiet_sanitization: bool,
+    /// Namespaces whose locales must all define the same keys, in addition
+    /// to those declared with `<ns require-locales="...">`
+    pub required_complete: Vec<RequiredComplete>,
+    /// Codes (`"RR0001"`) whose warnings become errors
+    pub deny: Vec<String>,
+    /// Codes whose warnings are dropped; errors cannot be allowed, and
+    /// `deny` wins over `allow`
+    pub allow: Vec<String>,
+    /// `(region, code)` pairs: warnings with the code about resources in
+    /// the `<!-- region: ... -->` become errors
+    pub deny_in_regions: Vec<(String, String)>,
+    /// `(region, code)` pairs: warnings with the code about resources in
+    /// the region are dropped, unless the code is denied there
+    pub allow_in_regions: Vec<(String, String)>,
+    /// Most warnings a build may have, counted after `deny` and `allow`;
+    /// more is one error listing the first few. The budget covers the whole
+    /// build: every resource set and the warnings of generation. `None` for
+    /// no limit.
+    pub max_warnings: Option<usize>,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        Self {
+            treat_duplicates_as_errors: false,
+            type_mismatch_duplicates_as_errors: true,
+            detect_secrets: true,
+            detect_invisible_chars: true,
+            flag_nbsp: false,
+            canonical_colors: false,
+            detect_value_duplicates: false,
+            detect_typed_literals: true,
+            literal_allow_list: Vec::new(),
+            max_namespace_depth: Some(DEFAULT_MAX_NAMESPACE_DEPTH),
+            max_value_bytes: Some(DEFAULT_MAX_VALUE_BYTES),
+            hard_max_value_bytes: Some(DEFAULT_HARD_MAX_VALUE_BYTES),
+            require_namespace: false,
+            explicit_namespace_extension: false,
+            allow_raw: false,
+            quiet_sanitization: false,
+            required_complete: Vec::new(),
+            deny: Vec::new(),
+            allow: Vec::new(),
+            deny_in_regions: Vec::new(),
+            allow_in_regions: Vec::new(),
+            max_warnings: None,
+        }
+    }
+}
+
+impl ValidationOptions {
+    /// Promotes warnings whose code is denied and drops those allowed
+    pub fn apply_levels(&self, result: &mut AnalysisResult) {
+        let listed = |codes: &[String], code: &str| {
+            codes.iter().any(|listed| listed == code)
+        };
+        let warnings = std::mem::take(&mut result.warnings);
+        for warning in warnings {
+            if listed(&self.deny, warning.code) {
+                result.errors.push(AnalysisError::new(
+                    warning.code,
+                    warning.message,
+                    warning.key,
+                ));
+            } else if !listed(&self.allow, warning.code) {
+                result.warnings.push(warning);
+            }
+        }
+    }
+}
+
+/// Locales that must be complete below `namespace`: each locale is the
+/// namespace `<namespace>/<locale>`, and a key defined under one of them but
+/// missing under another fails the build
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequiredComplete {
+    /// Namespace path, e.g. `"api/error"`
+    pub namespace: String,
+    pub locales: Vec<String>,
 }
 
 /// Validates the resource graph and returns warnings and errors found.
 ///
 /// Currently checks:
-/// - Duplicates (same key defined multiple times) → warnings (or errors if option enabled)
+/// - Duplicates (same key defined multiple times) → warnings (or errors if option enabled),
+///   with their own code when the definitions have different types
+/// - Content rejected by its type handler (e.g. an out-of-range percentage) → errors
+/// - References (unresolved, cyclic, or of the wrong kind) → errors
+/// - Interpolations (unresolved, cyclic, or targeting a template) → errors
+/// - `<assert>` expressions that are false or invalid → errors
+/// - Strings longer than their `max-len` (UTF-8 bytes) → errors
+/// - Strings that look like secrets → warnings (if `detect_secrets` is set)
+/// - Strings and templates with invisible characters → warnings (if
+///   `detect_invisible_chars` is set; no-break spaces with `flag_nbsp`)
+/// - Fully transparent colors → warnings; short `#RGB` colors → warnings
+///   (if `canonical_colors` is set)
+/// - String and color values shared by several keys → warnings (if
+///   `detect_value_duplicates` is set)
+/// - Keys nested deeper than `max_namespace_depth` → one error listing them
+/// - Keys outside any namespace → warnings (if `require_namespace` is set)
+/// - Namespaces continued in a later file without `extend="true"` →
+///   warnings (if `explicit_namespace_extension` is set)
+/// - Names and namespaces with characters replaced in Rust → warnings
+///   (unless `quiet_sanitization` is set)
+/// - Names and namespaces generated as the same Rust item, under the
+///   default naming configuration ([`validate_with_naming`] takes
+///   another) → errors
+/// - Keys missing from a required locale → errors; translations with
+///   different placeholders → warnings
+/// - Invalid `visibility` values → errors; public resources referencing
+///   restricted ones → warnings
+/// - Invalid `feature` names and references into a feature-gated namespace
+///   from outside it → errors; namespaces gated in only some files → warnings
+/// - Unbalanced `<!-- region -->` markers → warnings
+/// - `<raw>` resources → errors (unless `allow_raw` is set)
+///
+/// `max_warnings` is checked by the build, over all of its warnings.
 #[allow(dead_code)] // Reserved for future use
 pub fn validate(graph: &ResourceGraph) -> AnalysisResult {
     validate_with_options(graph, ValidationOptions::default())
 }
 
-/// Validates the graph with custom options
+/// Validates the graph with custom options; the codes in `options.deny`
+/// and `options.allow` are applied last, after those scoped to a region
 pub fn validate_with_options(
     graph: &ResourceGraph,
     options: ValidationOptions,
+) -> AnalysisResult {
+    validate_with_naming(graph, options, &NamingConfig::default())
+}
+
+/// [`validate_with_options`], checking the generated names (`RR0083`,
+/// `RR0084`) as `naming` writes them
+pub fn validate_with_naming(
+    graph: &ResourceGraph,
+    options: ValidationOptions,
+    naming: &NamingConfig,
 ) -> AnalysisResult {
     let mut result = AnalysisResult::default();
 
@@ -91,21 +358,27 @@ pub fn validate_with_options(
                 .collect();
             let primary_file = &file_list[0];
             let duplicate_files = &file_list[1..];
+            let (code, types, number_mismatch) = duplicate_code(nodes);
             let message = format!(
-                "Duplicate resource key '{}' defined in {} files. Using '{}' (first occurrence). Duplicates in: {}",
+                "Duplicate resource key '{}' defined in {} files{types}. Using '{}' (first occurrence). Duplicates in: {}",
                 key.full_name(),
                 nodes.len(),
                 primary_file,
                 duplicate_files.join(", ")
             );
 
-            if options.treat_duplicates_as_errors {
+            let is_error = options.treat_duplicates_as_errors
+                || (number_mismatch
+                    && options.type_mismatch_duplicates_as_errors);
+            if is_error {
                 result.errors.push(AnalysisError::new(
+                    code,
                     message,
                     Some(key.clone()),
                 ));
             } else {
                 result.warnings.push(AnalysisWarning::new(
+                    code,
                     message,
                     Some(key.clone()),
                 ));
@@ -113,9 +386,323 @@ pub fn validate_with_options(
         }
     }
 
+    if let Some(limit) = options.max_namespace_depth {
+        check_namespace_depth(graph, limit, &mut result);
+    }
+    if options.require_namespace {
+        check_top_level_keys(graph, &mut result);
+    }
+    if options.explicit_namespace_extension {
+        extension::validate_namespace_extension(graph, &mut result);
+    }
+    if !options.allow_raw {
+        check_raw_resources(graph, &mut result);
+    }
+    if !options.quiet_sanitization {
+        identifiers::validate_identifiers(graph, naming, &mut result);
+    }
+    identifiers::validate_name_collisions(graph, naming, &mut result);
+    leaf_namespaces::validate_leaf_namespaces(graph, &mut result);
+    report_rejected(graph, &mut result);
+    colors::validate_colors(graph, options.canonical_colors, &mut result);
+    references::validate_references(graph, &mut result);
+    interpolation::validate_interpolations(graph, &mut result);
+    template_calls::validate_template_calls(graph, &mut result);
+    arrays::validate_array_references(graph, &mut result);
+    assertions::validate_assertions(graph, &mut result);
+    lengths::validate_string_lengths(graph, &mut result);
+    sizes::validate_value_sizes(
+        graph,
+        options.max_value_bytes,
+        options.hard_max_value_bytes,
+        &mut result,
+    );
+    locales::validate_required_locales(
+        graph,
+        &options.required_complete,
+        &mut result,
+    );
+    visibility::validate_visibility(graph, &mut result);
+    features::validate_features(graph, &mut result);
+    error_enums::validate_error_enums(graph, &mut result);
+    regions::validate_regions(graph, &mut result);
+    if options.detect_secrets {
+        secrets::detect_secrets(graph, &mut result);
+    }
+    if options.detect_invisible_chars {
+        invisible::detect_invisible_chars(
+            graph,
+            options.flag_nbsp,
+            &mut result,
+        );
+    }
+    if options.detect_value_duplicates {
+        duplicate_values::detect_value_duplicates(graph, &mut result);
+    }
+    if options.detect_typed_literals {
+        literals::detect_typed_literals(
+            graph,
+            &options.literal_allow_list,
+            &mut result,
+        );
+    }
+
+    report_unknown_codes(&options, &mut result);
+    regions::apply_region_levels(graph, &options, &mut result);
+    options.apply_levels(&mut result);
     result
 }
 
+/// Duplicates of different kinds, or numbers of different Rust types, are
+/// a type conflict; the second value is the message fragment naming the
+/// types, and the third is true for numbers
+fn duplicate_code(nodes: &[ResourceNode]) -> (&'static str, String, bool) {
+    let mut kinds: Vec<String> = Vec::new();
+    for node in nodes {
+        let kind = format!("{:?}", node.kind);
+        if !kinds.contains(&kind) {
+            kinds.push(kind);
+        }
+    }
+    if kinds.len() > 1 {
+        let types = format!(" with different types ({})", kinds.join(", "));
+        return (codes::TYPE_CONFLICT, types, false);
+    }
+    match number_types(nodes) {
+        Some(types) => (codes::TYPE_CONFLICT, types, true),
+        None => (codes::DUPLICATE_KEY, String::new(), false),
+    }
+}
+
+/// ` with different number types (u16 in 'a.xml', i64 in 'b.xml')` when
+/// the copies would not emit the same Rust type
+fn number_types(nodes: &[ResourceNode]) -> Option<String> {
+    let types: Vec<(&str, &ResourceNode)> = nodes
+        .iter()
+        .map(|node| match &node.value {
+            ResourceValue::Number(number) => {
+                Some((number_rust_type(number), node))
+            }
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+    let first = types.first()?.0;
+    if types.iter().all(|(ty, _)| *ty == first) {
+        return None;
+    }
+    let declared: Vec<String> = types
+        .iter()
+        .map(|(ty, node)| {
+            let ty = ty.trim_start_matches("r_resources::");
+            format!("{ty} in '{}'", node.origin.file.display())
+        })
+        .collect();
+    let first = first.trim_start_matches("r_resources::");
+    Some(format!(
+        " with different number types ({}); the constant is {first}, as declared first",
+        declared.join(", ")
+    ))
+}
+
+/// A typo in `deny` would otherwise silently let the warning through
+fn report_unknown_codes(
+    options: &ValidationOptions,
+    result: &mut AnalysisResult,
+) {
+    let listed = options
+        .deny
+        .iter()
+        .map(|code| (code, "deny"))
+        .chain(options.allow.iter().map(|code| (code, "allow")))
+        .chain(
+            options
+                .deny_in_regions
+                .iter()
+                .map(|(_, code)| (code, "deny_in_regions")),
+        )
+        .chain(
+            options
+                .allow_in_regions
+                .iter()
+                .map(|(_, code)| (code, "allow_in_regions")),
+        );
+    for (code, list) in listed {
+        if codes::ALL.contains(&code.as_str()) {
+            continue;
+        }
+        result.errors.push(AnalysisError::new(
+            codes::UNKNOWN_CODE,
+            format!("Unknown diagnostic code '{code}' in `{list}`"),
+            None,
+        ));
+    }
+}
+
+/// One error for every key nested deeper than `limit`, so a runaway
+/// generator does not print thousands of them
+fn check_namespace_depth(
+    graph: &ResourceGraph,
+    limit: usize,
+    result: &mut AnalysisResult,
+) {
+    let deep: Vec<&ResourceKey> = graph
+        .nodes()
+        .keys()
+        .filter(|key| key.namespace.len() > limit)
+        .collect();
+    let Some(deepest) = deep.iter().max_by_key(|key| key.namespace.len())
+    else {
+        return;
+    };
+    let mut listed: Vec<String> = deep
+        .iter()
+        .take(LISTED_DEEP_KEYS)
+        .map(|key| format!("'{}'", key.full_name()))
+        .collect();
+    if deep.len() > LISTED_DEEP_KEYS {
+        listed.push(format!("and {} more", deep.len() - LISTED_DEEP_KEYS));
+    }
+    result.errors.push(AnalysisError::new(
+        codes::NAMESPACE_TOO_DEEP,
+        format!(
+            "{} resources are nested in more than {limit} namespaces (up to {}): {}. Flatten them or raise `max_namespace_depth`",
+            deep.len(),
+            deepest.namespace.len(),
+            listed.join(", ")
+        ),
+        Some((*deepest).clone()),
+    ));
+}
+
+/// One warning per key outside any namespace, except resources marked
+/// `allow-top-level="true"`
+fn check_top_level_keys(graph: &ResourceGraph, result: &mut AnalysisResult) {
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first() else {
+            continue;
+        };
+        if !key.namespace.is_empty() || node.has_flag("allow-top-level") {
+            continue;
+        }
+        result.warnings.push(AnalysisWarning::new(
+            codes::TOP_LEVEL_KEY,
+            format!(
+                "'{}' ({}) is not in any namespace. Move it into an `<ns>`, or add allow-top-level=\"true\" if it belongs at the top",
+                key.full_name(),
+                node.origin.file.display()
+            ),
+            Some(key.clone()),
+        ));
+    }
+}
+
+/// `<raw>` constants are emitted unchecked, so a project has to opt in
+fn check_raw_resources(graph: &ResourceGraph, result: &mut AnalysisResult) {
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first().filter(|node| is_raw(node)) else {
+            continue;
+        };
+        result.errors.push(AnalysisError::new(
+            codes::RAW_NOT_ALLOWED,
+            format!(
+                "'{}' ({}) is a <raw> Rust constant, which is only emitted with ValidationOptions::allow_raw (R_RESOURCES_ALLOW_RAW=1)",
+                key.full_name(),
+                node.origin.file.display()
+            ),
+            Some(key.clone()),
+        ));
+    }
+}
+
+/// Warnings listed in the error when there are too many
+const LISTED_WARNINGS: usize = 3;
+
+/// One error when there are more than `limit` warnings, for CI that
+/// keeps a warning budget. Checked once on the warnings of the whole build.
+pub fn check_warning_budget(
+    limit: usize,
+    warnings: &[AnalysisWarning],
+) -> Option<AnalysisError> {
+    let count = warnings.len();
+    if count <= limit {
+        return None;
+    }
+    let first: Vec<String> = warnings
+        .iter()
+        .take(LISTED_WARNINGS)
+        .map(|warning| format!("\n  {warning}"))
+        .collect();
+    Some(AnalysisError::new(
+        codes::TOO_MANY_WARNINGS,
+        format!(
+            "{count} warnings (limit {limit}), raise `max_warnings` or fix some. The first ones:{}",
+            first.concat()
+        ),
+        None,
+    ))
+}
+
+/// Resources whose content was rejected while building the graph
+fn report_rejected(graph: &ResourceGraph, result: &mut AnalysisResult) {
+    for rejected in graph.rejected() {
+        result.errors.push(AnalysisError::new(
+            codes::INVALID_VALUE,
+            format!(
+                "Invalid value for '{}' ({}): {}",
+                rejected.key.full_name(),
+                rejected.origin.file.display(),
+                rejected.message
+            ),
+            Some(rejected.key.clone()),
+        ));
+    }
+}
+
+/// Errors for the overrides of the build plan that were not applied;
+/// a missing key lists the keys close to it
+pub fn report_override_issues(
+    graph: &ResourceGraph,
+    issues: &[OverrideIssue],
+    result: &mut AnalysisResult,
+) {
+    for issue in issues {
+        let (code, message, key) = match issue {
+            OverrideIssue::Missing(key) => {
+                let close: Vec<String> = references::close_keys(
+                    graph,
+                    &ResourceKey::from_path(key),
+                )
+                .iter()
+                .map(|key| key.full_name())
+                .collect();
+                let hint = if close.is_empty() {
+                    String::new()
+                } else {
+                    format!("; close keys: {}", close.join(", "))
+                };
+                (
+                    codes::INVALID_OVERRIDE,
+                    format!("Override of '{key}' matches no resource{hint}"),
+                    None,
+                )
+            }
+            OverrideIssue::Unsupported { key, kind } => (
+                codes::INVALID_OVERRIDE,
+                format!(
+                    "Override of '{key}' cannot apply: a <{kind}> has no single value to replace"
+                ),
+                Some(ResourceKey::from_path(key)),
+            ),
+            OverrideIssue::Invalid { key, message } => (
+                codes::INVALID_VALUE,
+                format!("Invalid value for '{key}' ({OVERRIDE_FILE}): {message}"),
+                Some(ResourceKey::from_path(key)),
+            ),
+        };
+        result.errors.push(AnalysisError::new(code, message, key));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,6 +713,103 @@ mod tests {
     };
     use std::path::PathBuf;
 
+    #[test]
+    fn value_duplicates_are_reported_only_on_request() {
+        let file = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![
+                ParsedResource::string("home/title", "Welcome"),
+                ParsedResource::string("onboarding/title", "Welcome"),
+            ],
+        );
+        let graph = ResourceGraphBuilder::from_parsed_files(&[file]);
+        assert!(validate(&graph).warnings.is_empty());
+
+        let options = ValidationOptions {
+            detect_value_duplicates: true,
+            ..Default::default()
+        };
+        let result = validate_with_options(&graph, options);
+        assert!(result.errors.is_empty());
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].code, codes::DUPLICATE_VALUE);
+        assert!(result.warnings[0]
+            .message
+            .contains("'home/title' (values.xml), 'onboarding/title'"));
+    }
+
+    #[test]
+    fn keys_deeper_than_the_limit_are_errors() {
+        let deep = (0..10).map(|i| format!("ns{i}/")).collect::<String>();
+        let file = ParsedResourceFile::new(
+            PathBuf::from("generated.xml"),
+            false,
+            vec![
+                ParsedResource::string(format!("{deep}title"), "Deep"),
+                ParsedResource::string(format!("{deep}subtitle"), "Deep"),
+                ParsedResource::string("a/b/c/d/e/f/g/h/fits", "Ok"),
+            ],
+        );
+        let graph = ResourceGraphBuilder::from_parsed_files(&[file]);
+
+        let result = validate(&graph);
+        assert_eq!(result.errors.len(), 1, "{:?}", result.errors);
+        assert_eq!(result.errors[0].code, codes::NAMESPACE_TOO_DEEP);
+        let message = &result.errors[0].message;
+        assert!(
+            message.starts_with(
+                "2 resources are nested in more than 8 namespaces (up to 10)"
+            ),
+            "{message}"
+        );
+        assert!(message.contains(&format!("'{deep}title'")), "{message}");
+
+        let options = ValidationOptions {
+            max_namespace_depth: None,
+            ..Default::default()
+        };
+        assert!(validate_with_options(&graph, options).errors.is_empty());
+    }
+
+    #[test]
+    fn top_level_keys_are_reported_when_a_namespace_is_required() {
+        let mut app_name = ParsedResource::string("app_name", "Demo");
+        app_name
+            .attributes
+            .insert("allow-top-level".to_string(), "true".to_string());
+        let file = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![
+                app_name,
+                ParsedResource::string("stray", "Misc"),
+                ParsedResource::string("auth/title", "Sign in"),
+            ],
+        );
+        let graph = ResourceGraphBuilder::from_parsed_files(&[file]);
+        assert!(validate(&graph).warnings.is_empty());
+
+        let options = ValidationOptions {
+            require_namespace: true,
+            ..Default::default()
+        };
+        let result = validate_with_options(&graph, options.clone());
+        assert_eq!(result.warnings.len(), 1, "{:?}", result.warnings);
+        assert_eq!(result.warnings[0].code, codes::TOP_LEVEL_KEY);
+        assert!(result.warnings[0]
+            .message
+            .starts_with("'stray' (values.xml) is not in any namespace"));
+
+        let denied = ValidationOptions {
+            deny: vec![codes::TOP_LEVEL_KEY.to_string()],
+            ..options
+        };
+        let result = validate_with_options(&graph, denied);
+        assert_eq!(result.errors.len(), 1, "{:?}", result.errors);
+        assert!(result.warnings.is_empty());
+    }
+
     #[test]
     fn detects_duplicate_keys() {
         // Note: The builder currently overwrites duplicates (last one wins),
@@ -138,6 +822,7 @@ mod tests {
                 name: "title".to_string(),
                 kind: ParsedKind::String,
                 value: ScalarValue::Text("First".to_string()),
+                attributes: Default::default(),
             }],
         );
         let parsed2 = ParsedResourceFile::new(
@@ -147,6 +832,7 @@ mod tests {
                 name: "title".to_string(),
                 kind: ParsedKind::String,
                 value: ScalarValue::Text("Second".to_string()),
+                attributes: Default::default(),
             }],
         );
 
@@ -187,6 +873,7 @@ mod tests {
                     name: "title".to_string(),
                     kind: ParsedKind::String,
                     value: ScalarValue::Text("Hello".to_string()),
+                    attributes: Default::default(),
                 },
                 ParsedResource {
                     name: "count".to_string(),
@@ -195,6 +882,7 @@ mod tests {
                         value: "42".to_string(),
                         explicit_type: None,
                     },
+                    attributes: Default::default(),
                 },
             ],
         );
@@ -206,4 +894,134 @@ mod tests {
         assert!(result.warnings.is_empty());
         assert!(result.errors.is_empty());
     }
+
+    fn duplicated(second: ParsedResource) -> ResourceGraph {
+        let files = [
+            ParsedResourceFile::new(
+                PathBuf::from("file1.xml"),
+                false,
+                vec![ParsedResource::string("title", "First")],
+            ),
+            ParsedResourceFile::new(
+                PathBuf::from("file2.xml"),
+                false,
+                vec![second],
+            ),
+        ];
+        ResourceGraphBuilder::from_parsed_files(&files)
+    }
+
+    #[test]
+    fn diagnostics_render_their_code() {
+        let result = validate(&duplicated(ParsedResource::string(
+            "title", "Second",
+        )));
+        assert_eq!(result.warnings[0].code, codes::DUPLICATE_KEY);
+        assert!(result.warnings[0]
+            .to_string()
+            .starts_with("[RR0001] Duplicate resource key 'title'"));
+
+        let result =
+            validate(&duplicated(ParsedResource::bool("title", true)));
+        assert_eq!(result.warnings[0].code, codes::TYPE_CONFLICT);
+        assert!(result.warnings[0]
+            .message
+            .contains("with different types (String, Bool)"));
+    }
+
+    #[test]
+    fn numbers_duplicated_with_different_types_are_errors() {
+        let port = |file: &str, ty: Option<&str>| {
+            ParsedResourceFile::new(
+                PathBuf::from(file),
+                false,
+                vec![ParsedResource::number(
+                    "port",
+                    "8080",
+                    ty.map(String::from),
+                )],
+            )
+        };
+        let graph = |second: Option<&str>| {
+            ResourceGraphBuilder::from_parsed_files(&[
+                port("values1.xml", Some("u16")),
+                port("values2.xml", second),
+            ])
+        };
+
+        let result = validate(&graph(Some("u16")));
+        assert!(result.errors.is_empty());
+        assert_eq!(result.warnings[0].code, codes::DUPLICATE_KEY);
+
+        for second in [Some("i64"), None] {
+            let result = validate(&graph(second));
+            assert!(result.warnings.is_empty());
+            assert_eq!(result.errors[0].code, codes::TYPE_CONFLICT);
+            let message = &result.errors[0].message;
+            assert!(
+                message.contains(
+                    "with different number types (u16 in 'values1.xml', \
+                     i64 in 'values2.xml'); the constant is u16"
+                ),
+                "{message}"
+            );
+        }
+
+        let options = ValidationOptions {
+            type_mismatch_duplicates_as_errors: false,
+            ..Default::default()
+        };
+        let result = validate_with_options(&graph(Some("f32")), options);
+        assert!(result.errors.is_empty());
+        assert_eq!(result.warnings[0].code, codes::TYPE_CONFLICT);
+    }
+
+    #[test]
+    fn deny_promotes_and_allow_drops_warnings() {
+        let graph = duplicated(ParsedResource::string("title", "Second"));
+        let options = |deny: &[&str], allow: &[&str]| ValidationOptions {
+            deny: deny.iter().map(ToString::to_string).collect(),
+            allow: allow.iter().map(ToString::to_string).collect(),
+            ..Default::default()
+        };
+
+        let result = validate_with_options(&graph, options(&["RR0001"], &[]));
+        assert!(result.warnings.is_empty());
+        assert_eq!(result.errors[0].code, codes::DUPLICATE_KEY);
+
+        let result = validate_with_options(&graph, options(&[], &["RR0001"]));
+        assert!(result.is_empty());
+
+        // A denied code stays an error even when also allowed
+        let result = validate_with_options(
+            &graph,
+            options(&["RR0001"], &["RR0001"]),
+        );
+        assert_eq!(result.errors.len(), 1);
+    }
+
+    #[test]
+    fn unknown_codes_are_errors() {
+        let graph = duplicated(ParsedResource::string("other", "x"));
+        let result = validate_with_options(
+            &graph,
+            ValidationOptions {
+                allow: vec!["RR9999".to_string()],
+                ..Default::default()
+            },
+        );
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(
+            result.errors[0].to_string(),
+            "[RR0004] Unknown diagnostic code 'RR9999' in `allow`"
+        );
+    }
+
+    #[test]
+    fn codes_are_unique() {
+        let mut sorted = codes::ALL.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(sorted, codes::ALL);
+    }
 }
@@ -0,0 +1,366 @@
+//! Reference resolution (`@string/app_name`).
+//!
+//! References are stored unresolved in the graph; this module follows them to the
+//! concrete node they designate and reports unresolved targets, cycles, and kind
+//! mismatches. Unresolved targets suggest the closest existing key.
+//!
+//! Before analysis, the pipeline also indexes the reverse direction: for each
+//! key, the resources whose references, interpolations, array items, or
+//! template calls name it ([`ResourceGraph::dependents_of`]).
+
+use std::collections::BTreeMap;
+
+use crate::generator::ir::{
+    ArrayItem, InterpolationPart, ResourceGraph, ResourceKey, ResourceKind,
+    ResourceNode, ResourceValue,
+};
+
+use super::template_calls::called_keys;
+use super::{codes, AnalysisError, AnalysisResult};
+
+/// Why a reference could not be resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReferenceIssue {
+    /// The referenced key does not exist
+    Unresolved(ResourceKey),
+    /// Following the references loops back (chain includes the repeated key)
+    Cycle(Vec<ResourceKey>),
+}
+
+/// Follows references starting at `key` until a concrete (non-reference) node.
+pub fn resolve<'a>(
+    graph: &'a ResourceGraph,
+    key: &ResourceKey,
+) -> Result<&'a ResourceNode, ReferenceIssue> {
+    let mut chain = vec![key.clone()];
+    let mut current = graph
+        .get(key)
+        .ok_or_else(|| ReferenceIssue::Unresolved(key.clone()))?;
+
+    while let ResourceValue::Reference { target, .. } = &current.value {
+        let is_cycle = chain.contains(target);
+        chain.push(target.clone());
+        if is_cycle {
+            return Err(ReferenceIssue::Cycle(chain));
+        }
+        current = graph
+            .get(target)
+            .ok_or_else(|| ReferenceIssue::Unresolved(target.clone()))?;
+    }
+    Ok(current)
+}
+
+/// Keys `node`, at `key`, names directly: its reference target, the
+/// references interpolated or listed in it, and the templates it calls
+pub fn dependencies(key: &ResourceKey, node: &ResourceNode) -> Vec<ResourceKey> {
+    let item_key = |item: &ArrayItem| match item {
+        ArrayItem::Reference(target) => Some(target.clone()),
+        _ => None,
+    };
+    match &node.value {
+        ResourceValue::Reference { target, .. } => vec![target.clone()],
+        ResourceValue::Interpolated(parts) => parts
+            .iter()
+            .filter_map(|part| match part {
+                InterpolationPart::Reference { target, .. } => {
+                    Some(target.clone())
+                }
+                InterpolationPart::Text(_) => None,
+            })
+            .collect(),
+        ResourceValue::Array(items) => {
+            items.iter().filter_map(item_key).collect()
+        }
+        ResourceValue::ArrayRows(rows) => {
+            rows.iter().flatten().filter_map(item_key).collect()
+        }
+        ResourceValue::Template { .. } => called_keys(key, node),
+        _ => Vec::new(),
+    }
+}
+
+/// Fills the reverse-reference index of `graph` from every definition of
+/// every key, duplicates included
+pub fn index_dependents(graph: &mut ResourceGraph) {
+    let mut dependents: BTreeMap<ResourceKey, Vec<ResourceKey>> =
+        BTreeMap::new();
+    for (key, nodes) in graph.nodes() {
+        for target in nodes.iter().flat_map(|node| dependencies(key, node)) {
+            let users = dependents.entry(target).or_default();
+            if !users.contains(key) {
+                users.push(key.clone());
+            }
+        }
+    }
+    graph.set_dependents(dependents);
+}
+
+/// The existing key closest to the missing `target`, when a small typo
+/// away: at most a third of its characters differ
+pub fn nearest_key<'a>(
+    graph: &'a ResourceGraph,
+    target: &ResourceKey,
+) -> Option<&'a ResourceKey> {
+    close_keys(graph, target).into_iter().next()
+}
+
+/// Existing keys a small typo away from the missing `target`, closest
+/// first
+pub fn close_keys<'a>(
+    graph: &'a ResourceGraph,
+    target: &ResourceKey,
+) -> Vec<&'a ResourceKey> {
+    let wanted = target.full_name();
+    let limit = (wanted.chars().count() / 3).max(1);
+    let mut close: Vec<(usize, &ResourceKey)> = graph
+        .nodes()
+        .keys()
+        .map(|key| (edit_distance(&wanted, &key.full_name()), key))
+        .filter(|(distance, _)| *distance <= limit)
+        .collect();
+    // Stable, so keys equally close stay in key order
+    close.sort_by_key(|(distance, _)| *distance);
+    close.into_iter().map(|(_, key)| key).collect()
+}
+
+/// `; did you mean '@auth/title'?` when a key close to `target` exists
+pub(crate) fn did_you_mean(
+    graph: &ResourceGraph,
+    target: &ResourceKey,
+) -> String {
+    nearest_key(graph, target)
+        .map(|key| format!("; did you mean '@{}'?", key.full_name()))
+        .unwrap_or_default()
+}
+
+/// `target` as the reference naming it was written (`@string/auth/title`),
+/// from `node` or else from any resource; `@auth/title` when the reference
+/// names no kind (`@./title`)
+pub(crate) fn written_reference(
+    graph: &ResourceGraph,
+    node: &ResourceNode,
+    target: &ResourceKey,
+) -> String {
+    let kind = named_kind(node, target).or_else(|| {
+        graph
+            .nodes()
+            .values()
+            .flatten()
+            .find_map(|other| named_kind(other, target))
+    });
+    match kind {
+        Some(kind) => format!("@{}/{}", kind.tag(), target.full_name()),
+        None => format!("@{}", target.full_name()),
+    }
+}
+
+/// The kind a reference of `node` to `target` names
+fn named_kind<'a>(
+    node: &'a ResourceNode,
+    target: &ResourceKey,
+) -> Option<&'a ResourceKind> {
+    match &node.value {
+        ResourceValue::Reference { target: named, kind } if named == target => {
+            kind.as_ref()
+        }
+        ResourceValue::Interpolated(parts) => {
+            parts.iter().find_map(|part| match part {
+                InterpolationPart::Reference { target: named, kind }
+                    if named == target =>
+                {
+                    kind.as_ref()
+                }
+                _ => None,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Levenshtein distance, in characters
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Validates every reference in the graph
+pub(super) fn validate_references(
+    graph: &ResourceGraph,
+    result: &mut AnalysisResult,
+) {
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first() else {
+            continue;
+        };
+        if !matches!(node.value, ResourceValue::Reference { .. }) {
+            continue;
+        }
+        if let Some(error) = check_reference(graph, key, node) {
+            result.errors.push(error);
+        }
+    }
+}
+
+fn check_reference(
+    graph: &ResourceGraph,
+    key: &ResourceKey,
+    node: &ResourceNode,
+) -> Option<AnalysisError> {
+    let file = node.origin.file.display();
+    let (code, message) = match resolve(graph, key) {
+        Err(ReferenceIssue::Unresolved(target)) => (
+            codes::UNRESOLVED_REFERENCE,
+            format!(
+                "Unresolved reference '{}' in '{}' ({file}){}",
+                written_reference(graph, node, &target),
+                key.full_name(),
+                did_you_mean(graph, &target)
+            ),
+        ),
+        Err(ReferenceIssue::Cycle(chain)) => {
+            // Report a cycle once, from its smallest key
+            let cycle_start = chain.iter().min()?;
+            if cycle_start != key {
+                return None;
+            }
+            let path: Vec<String> =
+                chain.iter().map(ResourceKey::full_name).collect();
+            let message = format!(
+                "Reference cycle detected: {} ({file})",
+                path.join(" -> ")
+            );
+            (codes::REFERENCE_CYCLE, message)
+        }
+        Ok(target) => {
+            let ResourceValue::Reference {
+                target: target_key,
+                kind: requested,
+            } = &node.value
+            else {
+                return None;
+            };
+            let expected = requested.as_ref().unwrap_or(&node.kind);
+            if &target.kind == expected && target.kind == node.kind {
+                return None;
+            }
+            let message = format!(
+                "Reference '{}' in '{}' points to a {:?} resource, expected {:?} ({file})",
+                written_reference(graph, node, target_key),
+                key.full_name(),
+                target.kind,
+                node.kind
+            );
+            (codes::KIND_MISMATCH, message)
+        }
+    };
+    Some(AnalysisError::new(code, message, Some(key.clone())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::analysis::testing::graph;
+    use crate::generator::parsing::{ParsedResource, ResourceKind, ScalarValue};
+
+    fn reference(name: &str, path: &str) -> ParsedResource {
+        ParsedResource {
+            name: name.to_string(),
+            kind: ResourceKind::String,
+            value: ScalarValue::Reference {
+                kind: Some("string".to_string()),
+                path: path.to_string(),
+            },
+            attributes: Default::default(),
+        }
+    }
+
+    #[test]
+    fn resolves_reference_chain() {
+        let graph = graph(vec![
+            ParsedResource::string("app_name", "Demo"),
+            reference("alias", "app_name"),
+            reference("alias_of_alias", "alias"),
+        ]);
+        let key = ResourceKey::from_path("alias_of_alias");
+        let node = resolve(&graph, &key).expect("resolves");
+        assert!(matches!(
+            &node.value,
+            ResourceValue::String(value) if value == "Demo"
+        ));
+
+        let mut result = AnalysisResult::default();
+        validate_references(&graph, &mut result);
+        assert!(result.errors.is_empty());
+    }
+
+    #[test]
+    fn reports_unresolved_reference() {
+        let graph = graph(vec![reference("alias", "missing")]);
+        let mut result = AnalysisResult::default();
+        validate_references(&graph, &mut result);
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.errors[0]
+            .message
+            .contains("Unresolved reference '@string/missing' in 'alias'"));
+        assert!(result.errors[0].message.contains("values.xml"));
+    }
+
+    #[test]
+    fn unresolved_reference_suggests_a_close_key() {
+        let graph = graph(vec![
+            ParsedResource::string("auth/title", "Sign in"),
+            reference("header", "auth/tilte"),
+            reference("footer", "billing/total"),
+        ]);
+        let mut result = AnalysisResult::default();
+        validate_references(&graph, &mut result);
+        let messages: Vec<&str> =
+            result.errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Unresolved reference '@string/billing/total' in 'footer' (values.xml)",
+                "Unresolved reference '@string/auth/tilte' in 'header' (values.xml); did you mean '@auth/title'?",
+            ]
+        );
+    }
+
+    #[test]
+    fn edit_distance_counts_characters() {
+        assert_eq!(edit_distance("title", "title"), 0);
+        assert_eq!(edit_distance("tilte", "title"), 2);
+        assert_eq!(edit_distance("café", "cafe"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn reports_cycle_once() {
+        let graph =
+            graph(vec![reference("a", "b"), reference("b", "a")]);
+        let mut result = AnalysisResult::default();
+        validate_references(&graph, &mut result);
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.errors[0].message.contains("a -> b -> a"));
+    }
+
+    #[test]
+    fn reports_kind_mismatch() {
+        let graph = graph(vec![
+            ParsedResource::bool("enabled", true),
+            reference("label", "enabled"),
+        ]);
+        let mut result = AnalysisResult::default();
+        validate_references(&graph, &mut result);
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.errors[0].message.contains("Bool"));
+    }
+}
@@ -0,0 +1,150 @@
+//! Regions marked with `<!-- region: name -->` comments: unbalanced
+//! markers, and lint levels scoped to one region.
+
+use crate::generator::ir::ResourceGraph;
+use crate::generator::parsing::REGION;
+
+use super::{
+    codes, AnalysisError, AnalysisResult, AnalysisWarning, ValidationOptions,
+};
+
+/// One warning per region marker without its counterpart
+pub(super) fn validate_regions(
+    graph: &ResourceGraph,
+    result: &mut AnalysisResult,
+) {
+    for marker in graph.unbalanced_regions() {
+        result.warnings.push(AnalysisWarning::new(
+            codes::UNBALANCED_REGION,
+            format!(
+                "Unbalanced region marker at {}:{}: {}",
+                marker.origin.file.display(),
+                marker.line,
+                marker.message
+            ),
+            None,
+        ));
+    }
+}
+
+/// Promotes the warnings about resources in a region whose code is denied
+/// there, and drops those allowed there; the global levels apply after
+pub(super) fn apply_region_levels(
+    graph: &ResourceGraph,
+    options: &ValidationOptions,
+    result: &mut AnalysisResult,
+) {
+    if options.deny_in_regions.is_empty() && options.allow_in_regions.is_empty()
+    {
+        return;
+    }
+    let listed = |pairs: &[(String, String)], region: &str, code: &str| {
+        pairs.iter().any(|(r, c)| r == region && c == code)
+    };
+    let warnings = std::mem::take(&mut result.warnings);
+    for warning in warnings {
+        let region = warning
+            .key
+            .as_ref()
+            .and_then(|key| graph.get(key))
+            .and_then(|node| node.attributes.get(REGION));
+        let Some(region) = region else {
+            result.warnings.push(warning);
+            continue;
+        };
+        if listed(&options.deny_in_regions, region, warning.code) {
+            result.errors.push(AnalysisError::new(
+                warning.code,
+                warning.message,
+                warning.key,
+            ));
+        } else if !listed(&options.allow_in_regions, region, warning.code) {
+            result.warnings.push(warning);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::analysis::validate_with_options;
+    use crate::generator::input::RawResourceFile;
+    use crate::generator::ir::ResourceGraphBuilder;
+    use crate::generator::parsing::{parse_raw_files, ParseOptions};
+    use std::path::PathBuf;
+
+    fn graph(xml: &str) -> ResourceGraph {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            xml.to_string(),
+            false,
+        );
+        let files = parse_raw_files(&[raw], &ParseOptions::default()).unwrap();
+        ResourceGraphBuilder::from_parsed_files(&files)
+    }
+
+    #[test]
+    fn reports_unbalanced_markers() {
+        let graph = graph("<resources>\n<!-- endregion -->\n</resources>");
+        let result = validate_with_options(&graph, Default::default());
+        let warnings: Vec<(&str, &str)> = result
+            .warnings
+            .iter()
+            .map(|warning| (warning.code, warning.message.as_str()))
+            .collect();
+        assert_eq!(
+            warnings,
+            [(
+                codes::UNBALANCED_REGION,
+                "Unbalanced region marker at values.xml:2: `endregion` \
+                 without an open region"
+            )]
+        );
+    }
+
+    #[test]
+    fn levels_apply_to_one_region() {
+        let graph = graph(
+            r#"<resources>
+                <!-- region: legacy -->
+                <string name="old_token">x</string>
+                <!-- endregion -->
+                <!-- region: checkout -->
+                <string name="pay_token">x</string>
+                <!-- endregion -->
+                <string name="api_token">x</string>
+            </resources>"#,
+        );
+        let warned = |result: &AnalysisResult| {
+            let mut keys: Vec<String> = result
+                .warnings
+                .iter()
+                .filter_map(|warning| warning.key.as_ref())
+                .map(|key| key.full_name())
+                .collect();
+            keys.sort();
+            keys
+        };
+        let result = validate_with_options(&graph, Default::default());
+        assert_eq!(warned(&result), ["api_token", "old_token", "pay_token"]);
+
+        let options = ValidationOptions {
+            allow_in_regions: vec![(
+                "legacy".to_string(),
+                codes::POSSIBLE_SECRET.to_string(),
+            )],
+            deny_in_regions: vec![(
+                "checkout".to_string(),
+                codes::POSSIBLE_SECRET.to_string(),
+            )],
+            ..Default::default()
+        };
+        let result = validate_with_options(&graph, options);
+        assert_eq!(warned(&result), ["api_token"]);
+        assert_eq!(result.errors.len(), 1, "{:?}", result.errors);
+        assert_eq!(
+            result.errors[0].key.as_ref().map(|key| key.full_name()),
+            Some("pay_token".to_string())
+        );
+    }
+}
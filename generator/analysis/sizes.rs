@@ -0,0 +1,246 @@
+//! Values too large to be literals.
+//!
+//! A value becomes a literal in the generated code, and rustc holds every
+//! literal in memory, several times over, on each incremental build: a
+//! 2 MB JSON document in a `<string>` builds, but slowly and at a cost in
+//! memory. Values over `ValidationOptions::max_value_bytes` are warnings
+//! suggesting a file of their own, embedded with `include_str!` or
+//! `include_bytes!`; values over `hard_max_value_bytes` are errors. Arrays
+//! count the sum of their items.
+
+use crate::generator::ir::{
+    ArrayItem, ResourceGraph, ResourceKey, ResourceNode, ResourceValue,
+};
+
+use super::{
+    codes, interpolation, AnalysisError, AnalysisResult, AnalysisWarning,
+};
+
+/// Default for [`super::ValidationOptions::max_value_bytes`]: 64 KiB
+pub const DEFAULT_MAX_VALUE_BYTES: usize = 64 << 10;
+
+/// Default for [`super::ValidationOptions::hard_max_value_bytes`]: 4 MiB
+pub const DEFAULT_HARD_MAX_VALUE_BYTES: usize = 4 << 20;
+
+/// What to do instead, at the end of both messages
+const SUGGESTION: &str = "move it to a file of its own and embed it with include_str! or include_bytes!";
+
+/// One warning per value over `max`, or one error when over `hard_max`;
+/// `None` lifts a limit
+pub(super) fn validate_value_sizes(
+    graph: &ResourceGraph,
+    max: Option<usize>,
+    hard_max: Option<usize>,
+    result: &mut AnalysisResult,
+) {
+    let smallest = match (max, hard_max) {
+        (Some(max), Some(hard_max)) => max.min(hard_max),
+        (Some(limit), None) | (None, Some(limit)) => limit,
+        (None, None) => return,
+    };
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first() else {
+            continue;
+        };
+        let bytes = value_bytes(graph, key, node);
+        if bytes <= smallest {
+            continue;
+        }
+        let described = format!(
+            "'{}' ({}) is {}",
+            key.full_name(),
+            node.origin.file.display(),
+            readable(bytes)
+        );
+        match hard_max {
+            Some(limit) if bytes > limit => {
+                result.errors.push(AnalysisError::new(
+                    codes::VALUE_TOO_LARGE,
+                    format!(
+                        "{described}, over the hard_max_value_bytes of {}; {SUGGESTION}",
+                        readable(limit)
+                    ),
+                    Some(key.clone()),
+                ));
+            }
+            _ => result.warnings.push(AnalysisWarning::new(
+                codes::LARGE_VALUE,
+                format!(
+                    "{described}, over the max_value_bytes of {}, which slows rustc down; {SUGGESTION}",
+                    readable(smallest)
+                ),
+                Some(key.clone()),
+            )),
+        }
+    }
+}
+
+/// Bytes the value adds to the generated code: UTF-8 bytes of text, the
+/// size of the other array items. References count nothing, their target
+/// is checked on its own.
+fn value_bytes(
+    graph: &ResourceGraph,
+    key: &ResourceKey,
+    node: &ResourceNode,
+) -> usize {
+    match &node.value {
+        ResourceValue::String(text) => text.len(),
+        ResourceValue::Interpolated(_) => {
+            // Unresolvable values are reported by the reference checks
+            interpolation::resolve_text(graph, key).map_or(0, |text| text.len())
+        }
+        ResourceValue::Template { text, .. } => text.len(),
+        ResourceValue::MimeMap(pairs) => pairs
+            .iter()
+            .map(|(extension, mime)| extension.len() + mime.len())
+            .sum(),
+        ResourceValue::Array(items) => items.iter().map(item_bytes).sum(),
+        ResourceValue::ArrayRows(rows) => {
+            rows.iter().flatten().map(item_bytes).sum()
+        }
+        _ => 0,
+    }
+}
+
+fn item_bytes(item: &ArrayItem) -> usize {
+    match item {
+        ArrayItem::String(text) => text.len(),
+        ArrayItem::Int(_) => size_of::<i64>(),
+        ArrayItem::Float(_) => size_of::<f64>(),
+        ArrayItem::Duration(_) => size_of::<std::time::Duration>(),
+        ArrayItem::Size(_) => size_of::<u64>(),
+        ArrayItem::Reference(_) => 0,
+    }
+}
+
+/// `2 MiB`, `64.5 KiB`, or `12 bytes`, with the byte count when rounded
+fn readable(bytes: usize) -> String {
+    for (unit, size) in [("MiB", 1 << 20), ("KiB", 1 << 10)] {
+        if bytes < size {
+            continue;
+        }
+        if bytes.is_multiple_of(size) {
+            return format!("{} {unit}", bytes / size);
+        }
+        let tenths = bytes * 10 / size;
+        return format!("{}.{} {unit} ({bytes} bytes)", tenths / 10, tenths % 10);
+    }
+    format!("{bytes} bytes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::analysis::testing;
+    use crate::generator::input::RawResourceFile;
+    use crate::generator::ir::ResourceGraphBuilder;
+    use crate::generator::parsing::{
+        parse_raw_files, ParsedResource, ParsedResourceFile,
+    };
+    use std::path::PathBuf;
+
+    fn check(resources: Vec<ParsedResource>) -> AnalysisResult {
+        testing::run_pass(resources, |graph, result| {
+            validate_value_sizes(
+                graph,
+                Some(DEFAULT_MAX_VALUE_BYTES),
+                Some(DEFAULT_HARD_MAX_VALUE_BYTES),
+                result,
+            )
+        })
+    }
+
+    fn codes(result: &AnalysisResult) -> Vec<(&str, String)> {
+        let warnings = result.warnings.iter().map(|warning| {
+            (warning.code, warning.key.as_ref().unwrap().full_name())
+        });
+        let errors = result.errors.iter().map(|error| {
+            (error.code, error.key.as_ref().unwrap().full_name())
+        });
+        let mut codes: Vec<_> = warnings.chain(errors).collect();
+        codes.sort();
+        codes
+    }
+
+    #[test]
+    fn warns_over_the_limit_and_fails_over_the_hard_limit() {
+        let text = |bytes: usize| "x".repeat(bytes);
+        let result = check(vec![
+            ParsedResource::string("at_limit", text(DEFAULT_MAX_VALUE_BYTES)),
+            ParsedResource::string("over_limit", text(DEFAULT_MAX_VALUE_BYTES + 1)),
+            ParsedResource::string(
+                "at_hard_limit",
+                text(DEFAULT_HARD_MAX_VALUE_BYTES),
+            ),
+            ParsedResource::string(
+                "over_hard_limit",
+                text(DEFAULT_HARD_MAX_VALUE_BYTES + 1),
+            ),
+        ]);
+        assert_eq!(
+            codes(&result),
+            [
+                (codes::LARGE_VALUE, "at_hard_limit".to_string()),
+                (codes::LARGE_VALUE, "over_limit".to_string()),
+                (codes::VALUE_TOO_LARGE, "over_hard_limit".to_string()),
+            ]
+        );
+        let over_limit = result
+            .warnings
+            .iter()
+            .find(|warning| warning.message.starts_with("'over_limit'"))
+            .unwrap();
+        assert_eq!(
+            over_limit.message,
+            "'over_limit' (values.xml) is 64.0 KiB (65537 bytes), over the max_value_bytes of 64 KiB, which slows rustc down; move it to a file of its own and embed it with include_str! or include_bytes!"
+        );
+        assert_eq!(
+            result.errors[0].message,
+            "'over_hard_limit' (values.xml) is 4.0 MiB (4194305 bytes), over the hard_max_value_bytes of 4 MiB; move it to a file of its own and embed it with include_str! or include_bytes!"
+        );
+    }
+
+    #[test]
+    fn arrays_count_their_items() {
+        let half = "x".repeat(DEFAULT_MAX_VALUE_BYTES / 2);
+        let xml = format!(
+            r#"<resources>
+                <string-array name="fits"><item>{half}</item><item>{half}</item></string-array>
+                <string-array name="spills"><item>{half}</item><item>{half}</item><item>x</item></string-array>
+            </resources>"#
+        );
+        let raw = RawResourceFile::new(PathBuf::from("values.xml"), xml, false);
+        let parsed = parse_raw_files(&[raw], &Default::default()).unwrap();
+        let resources = parsed.into_iter().flat_map(|file| file.resources).collect();
+        let result = check(resources);
+        assert_eq!(
+            codes(&result),
+            [(codes::LARGE_VALUE, "spills".to_string())]
+        );
+    }
+
+    #[test]
+    fn limits_can_be_lifted() {
+        let file = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![ParsedResource::string(
+                "big",
+                "x".repeat(DEFAULT_HARD_MAX_VALUE_BYTES + 1),
+            )],
+        );
+        let graph = ResourceGraphBuilder::from_parsed_files(&[file]);
+        let mut result = AnalysisResult::default();
+        validate_value_sizes(&graph, None, None, &mut result);
+        assert!(result.is_empty());
+        validate_value_sizes(&graph, None, Some(1 << 30), &mut result);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn sizes_read_naturally() {
+        assert_eq!(readable(12), "12 bytes");
+        assert_eq!(readable(64 << 10), "64 KiB");
+        assert_eq!(readable(2_500_000), "2.3 MiB (2500000 bytes)");
+    }
+}
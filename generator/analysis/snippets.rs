@@ -0,0 +1,218 @@
+//! XML excerpts for diagnostics, in the style of rustc:
+//!
+//! ```text
+//!  --> res/values.xml:3:5
+//!   |
+//! 3 |     <date name="launch">2025-02-30</date>
+//!   |     ^^^^^
+//! ```
+//!
+//! Resources do not record their line yet, so the element is located by
+//! its `name` attribute in the file as it was read from disk. Profile
+//! preprocessing rewrites the XML, so its output cannot be quoted.
+
+use std::path::Path;
+
+use crate::generator::input::RawResourceFile;
+use crate::generator::ir::{ResourceGraph, ResourceKey};
+
+use super::AnalysisResult;
+
+/// Widest excerpt shown; longer lines are cut around the element
+const MAX_LINE_WIDTH: usize = 100;
+
+/// Fills in the snippet of every diagnostic whose resource can be found,
+/// and the file of every such warning
+pub(crate) fn attach_snippets(
+    graph: &ResourceGraph,
+    files: &[RawResourceFile],
+    result: &mut AnalysisResult,
+) {
+    for error in &mut result.errors {
+        if let Some(key) = &error.key {
+            error.snippet = snippet(graph, files, key);
+        }
+    }
+    for warning in &mut result.warnings {
+        if let Some(key) = &warning.key {
+            warning.file =
+                origin_file(graph, key).map(Path::to_path_buf);
+            warning.snippet = snippet(graph, files, key);
+        }
+    }
+}
+
+fn snippet(
+    graph: &ResourceGraph,
+    files: &[RawResourceFile],
+    key: &ResourceKey,
+) -> Option<String> {
+    let path = origin_file(graph, key)?;
+    let file = files.iter().find(|file| file.path == path)?;
+    let offset = locate(&file.source, key)?;
+    Some(render(path, &file.source, offset))
+}
+
+/// File declaring `key`, including resources rejected by their type
+fn origin_file<'a>(
+    graph: &'a ResourceGraph,
+    key: &ResourceKey,
+) -> Option<&'a Path> {
+    if let Some(node) = graph.get(key) {
+        return Some(&node.origin.file);
+    }
+    graph
+        .rejected()
+        .iter()
+        .find(|rejected| &rejected.key == key)
+        .map(|rejected| rejected.origin.file.as_path())
+}
+
+/// Byte offset of the `<` starting the element declaring `key`. Enclosing
+/// namespaces are skipped first when they can be found, so the same name
+/// in an earlier namespace is not picked up.
+fn locate(source: &str, key: &ResourceKey) -> Option<usize> {
+    let mut from = 0;
+    for namespace in &key.namespace {
+        if let Some(at) = find_name(&source[from..], namespace) {
+            from += at;
+        }
+    }
+    let at = find_name(&source[from..], &key.name)
+        .map(|at| from + at)
+        .or_else(|| find_name(source, &key.name))?;
+    source[..at].rfind('<')
+}
+
+/// Offset of the first `name="..."` (or `name='...'`) attribute with this
+/// value, possibly prefixed (`res:name="..."`)
+fn find_name(text: &str, name: &str) -> Option<usize> {
+    [format!("name=\"{name}\""), format!("name='{name}'")]
+        .iter()
+        .filter_map(|pattern| {
+            text.match_indices(pattern.as_str())
+                .map(|(at, _)| at)
+                .find(|&at| {
+                    text[..at]
+                        .chars()
+                        .next_back()
+                        .is_some_and(|c| c.is_whitespace() || c == ':')
+                })
+        })
+        .min()
+}
+
+/// The line holding `offset`, with carets under the element's `<tag`
+fn render(path: &Path, source: &str, offset: usize) -> String {
+    let line_start = source[..offset].rfind('\n').map_or(0, |at| at + 1);
+    let line_end = source[offset..]
+        .find('\n')
+        .map_or(source.len(), |at| offset + at);
+    let line = source[line_start..line_end].trim_end_matches('\r');
+    let line_number = source[..line_start].matches('\n').count() + 1;
+    let column = source[line_start..offset].chars().count();
+    let tag_width = source[offset + 1..line_end]
+        .find(|c: char| c.is_whitespace() || matches!(c, '>' | '/'))
+        .map_or(1, |len| source[offset..offset + 1 + len].chars().count());
+
+    let (excerpt, caret_column) = excerpt(line, column);
+    // Tabs stay tabs so the carets line up however they are displayed
+    let indent: String = excerpt
+        .chars()
+        .take(caret_column)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let gutter = " ".repeat(line_number.to_string().len());
+    format!(
+        "{gutter}--> {}:{line_number}:{}\n\
+         {gutter} |\n\
+         {line_number} | {excerpt}\n\
+         {gutter} | {indent}{}",
+        path.display(),
+        column + 1,
+        "^".repeat(tag_width)
+    )
+}
+
+/// At most [`MAX_LINE_WIDTH`] characters of `line` around `column`, with
+/// `...` marking what was cut, and the column within the excerpt
+fn excerpt(line: &str, column: usize) -> (String, usize) {
+    let chars: Vec<char> = line.chars().collect();
+    if chars.len() <= MAX_LINE_WIDTH {
+        return (line.to_string(), column);
+    }
+    let start = column
+        .saturating_sub(MAX_LINE_WIDTH / 3)
+        .min(chars.len() - MAX_LINE_WIDTH);
+    let end = start + MAX_LINE_WIDTH;
+    let mut text: String = chars[start..end].iter().collect();
+    let mut caret_column = column - start;
+    if start > 0 {
+        text.insert_str(0, "...");
+        caret_column += 3;
+    }
+    if end < chars.len() {
+        text.push_str("...");
+    }
+    (text, caret_column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn locate_and_render(source: &str, key: &str) -> String {
+        let offset = locate(source, &ResourceKey::from_path(key)).unwrap();
+        render(&PathBuf::from("res/values.xml"), source, offset)
+    }
+
+    #[test]
+    fn renders_line_with_carets_under_the_tag() {
+        let source = "<resources>\n    \
+                      <string name=\"title\">Hi</string>\n    \
+                      <date name=\"launch\">2025-02-30</date>\n\
+                      </resources>\n";
+        assert_eq!(
+            locate_and_render(source, "launch"),
+            " --> res/values.xml:3:5\n  \
+             |\n\
+             3 |     <date name=\"launch\">2025-02-30</date>\n  \
+             |     ^^^^^"
+        );
+    }
+
+    #[test]
+    fn prefers_the_name_inside_the_namespace() {
+        let source = "<resources>\n\
+                      <string name='title'>A</string>\n\
+                      <ns name=\"ui\">\n\
+                      \t<string name='title'>B</string>\n\
+                      </ns>\n\
+                      </resources>";
+        let rendered = locate_and_render(source, "ui/title");
+        assert!(rendered.contains("values.xml:4:2"), "{rendered}");
+        assert!(rendered.ends_with("| \t^^^^^^^"), "{rendered}");
+        // `subtitle` or `data-name` are not the `name` attribute
+        let source = "<x subname=\"a\"/>\n<string name=\"a\"/>";
+        assert!(locate_and_render(source, "a").contains(":2:1"));
+        let source = "<r:resources>\n<r:bool r:name=\"a\"/>";
+        assert!(locate_and_render(source, "a").ends_with("| ^^^^^^^"));
+    }
+
+    #[test]
+    fn cuts_long_lines_around_the_element() {
+        let source = format!(
+            "<resources>{}<int name=\"n\">1</int>{}</resources>",
+            " ".repeat(150),
+            " ".repeat(150)
+        );
+        let rendered = locate_and_render(&source, "n");
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert!(lines[2].starts_with("1 | ..."), "{rendered}");
+        assert!(lines[2].ends_with("..."), "{rendered}");
+        assert_eq!(lines[2].chars().count(), "1 | ".len() + 106);
+        let caret = lines[3].find('^').unwrap();
+        assert_eq!(&lines[2][caret..caret + 4], "<int");
+    }
+}
@@ -0,0 +1,435 @@
+//! Template calls (`{@footer}` in a template body).
+//!
+//! A call of a template without parameters is inlined: the callee's text
+//! replaces it, with its braces escaped (`{{`) when the caller becomes a
+//! `format!` string. A call of a template with parameters stays a call, and the
+//! callee's parameters are appended to the caller's, renamed after the
+//! callee (`footer_name`) when the caller already has one of that name.
+//! Callees are resolved first, so calls nest; a call that loops back is a
+//! cycle.
+
+use crate::generator::ir::types::{
+    count_placeholders, param_identifier, template_calls,
+};
+use crate::generator::ir::{
+    ResourceGraph, ResourceKey, ResourceKind, ResourceNode, ResourceValue,
+    TemplateParam,
+};
+
+use super::references;
+use super::{codes, AnalysisError, AnalysisResult};
+
+/// Why the calls of a template could not be resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CallIssue {
+    /// `caller` calls `path`, which names no resource
+    Unresolved { caller: ResourceKey, path: String },
+    /// `caller` calls `callee`, which is not a template
+    NotTemplate {
+        caller: ResourceKey,
+        callee: ResourceKey,
+    },
+    /// `caller` calls `callee`, which takes positional arguments (`%1$s`)
+    Positional {
+        caller: ResourceKey,
+        callee: ResourceKey,
+    },
+    /// Calls loop back (chain includes the repeated key)
+    Cycle(Vec<ResourceKey>),
+}
+
+/// Whether the body of `node` calls another template
+pub(crate) fn has_calls(node: &ResourceNode) -> bool {
+    matches!(&node.value, ResourceValue::Template { text, .. }
+        if !template_calls(text).is_empty())
+}
+
+/// Keys of the templates the body of `node`, at `key`, calls directly
+pub(crate) fn called_keys(
+    key: &ResourceKey,
+    node: &ResourceNode,
+) -> Vec<ResourceKey> {
+    let ResourceValue::Template { text, .. } = &node.value else {
+        return Vec::new();
+    };
+    template_calls(text)
+        .iter()
+        .filter_map(|call| callee_key(key, &call.path))
+        .collect()
+}
+
+/// Text and parameters of the template at `key` with its calls resolved:
+/// the calls of templates without parameters inlined, the others written
+/// `{@path(args)}` with the arguments appended to the parameters
+pub fn resolve_calls(
+    graph: &ResourceGraph,
+    key: &ResourceKey,
+) -> Result<(String, Vec<TemplateParam>), CallIssue> {
+    resolve_with_chain(graph, key, &mut Vec::new())
+}
+
+fn resolve_with_chain(
+    graph: &ResourceGraph,
+    key: &ResourceKey,
+    chain: &mut Vec<ResourceKey>,
+) -> Result<(String, Vec<TemplateParam>), CallIssue> {
+    let is_cycle = chain.contains(key);
+    chain.push(key.clone());
+    if is_cycle {
+        return Err(CallIssue::Cycle(chain.clone()));
+    }
+    let Some(ResourceValue::Template { text, params }) =
+        graph.get(key).map(|node| &node.value)
+    else {
+        chain.pop();
+        return Ok((String::new(), Vec::new()));
+    };
+
+    let mut text = text.clone();
+    let mut params = params.clone();
+    // Inlined last, once the parameters tell whether braces need escaping
+    let mut inlined: Vec<(String, String)> = Vec::new();
+    for call in template_calls(&text.clone()) {
+        let repeated = inlined.iter().any(|(source, _)| *source == call.source);
+        if repeated || !text.contains(&call.source) {
+            // Repeated call, already resolved
+            continue;
+        }
+        let callee = callee_key(key, &call.path).ok_or_else(|| {
+            CallIssue::Unresolved {
+                caller: key.clone(),
+                path: call.path.clone(),
+            }
+        })?;
+        let node =
+            graph.get(&callee).ok_or_else(|| CallIssue::Unresolved {
+                caller: key.clone(),
+                path: call.path.clone(),
+            })?;
+        if node.kind != ResourceKind::Template {
+            return Err(CallIssue::NotTemplate {
+                caller: key.clone(),
+                callee,
+            });
+        }
+        let (callee_text, callee_params) =
+            resolve_with_chain(graph, &callee, chain)?;
+        if callee_params.is_empty() {
+            if count_placeholders(&callee_text) > 0 {
+                return Err(CallIssue::Positional {
+                    caller: key.clone(),
+                    callee,
+                });
+            }
+            inlined.push((call.source, callee_text));
+            continue;
+        }
+        let mut args = Vec::new();
+        for param in callee_params {
+            let name = free_name(&params, &callee.name, &param.name);
+            args.push(name.clone());
+            params.push(TemplateParam { name, ..param });
+        }
+        let resolved = format!(
+            "{{@{}({})}}",
+            callee.full_name(),
+            args.join(",")
+        );
+        text = text.replace(&call.source, &resolved);
+    }
+    let formatted = !params.is_empty() || count_placeholders(&text) > 0;
+    for (source, callee_text) in inlined {
+        let callee_text = if formatted {
+            callee_text.replace('{', "{{").replace('}', "}}")
+        } else {
+            callee_text
+        };
+        text = text.replace(&source, &callee_text);
+    }
+    chain.pop();
+    Ok((text, params))
+}
+
+/// Key of the template `path` names from a template at `caller`: absolute,
+/// or relative to its namespace with `./` and `../`
+fn callee_key(caller: &ResourceKey, path: &str) -> Option<ResourceKey> {
+    if !(path.starts_with("./") || path.starts_with("../")) {
+        return Some(ResourceKey::from_path(path));
+    }
+    let mut segments = caller.namespace.clone();
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                segments.pop()?;
+            }
+            name => segments.push(name.to_string()),
+        }
+    }
+    let name = segments.pop()?;
+    Some(ResourceKey::new(segments, name))
+}
+
+/// `; did you mean '{@mail/footer}'?` when a template close to the
+/// missing callee exists
+fn suggested_callee(
+    graph: &ResourceGraph,
+    caller: &ResourceKey,
+    path: &str,
+) -> String {
+    callee_key(caller, path)
+        .and_then(|callee| references::nearest_key(graph, &callee))
+        .filter(|key| {
+            graph.get(key).is_some_and(|n| n.kind == ResourceKind::Template)
+        })
+        .map(|key| format!("; did you mean '{{@{}}}'?", key.full_name()))
+        .unwrap_or_default()
+}
+
+/// `name`, or `<callee>_<name>` (then with a number) when a parameter
+/// already has its identifier
+fn free_name(params: &[TemplateParam], callee: &str, name: &str) -> String {
+    let taken = |candidate: &str| {
+        let ident = param_identifier(candidate);
+        params.iter().any(|p| param_identifier(&p.name) == ident)
+    };
+    if !taken(name) {
+        return name.to_string();
+    }
+    let renamed = format!("{callee}_{name}");
+    let mut candidate = renamed.clone();
+    let mut suffix = 2;
+    while taken(&candidate) {
+        candidate = format!("{renamed}_{suffix}");
+        suffix += 1;
+    }
+    candidate
+}
+
+/// Validates the calls of every template in the graph
+pub(super) fn validate_template_calls(
+    graph: &ResourceGraph,
+    result: &mut AnalysisResult,
+) {
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first() else {
+            continue;
+        };
+        if !has_calls(node) {
+            continue;
+        }
+        if let Some(error) = check_calls(graph, key) {
+            result.errors.push(error);
+        }
+        check_gated_callees(graph, key, node, result);
+    }
+}
+
+/// Reports the issue of `key`'s own calls; those of its callees are
+/// reported with them
+fn check_calls(graph: &ResourceGraph, key: &ResourceKey) -> Option<AnalysisError> {
+    let file = |key: &ResourceKey| {
+        graph
+            .get(key)
+            .map(|node| node.origin.file.display().to_string())
+            .unwrap_or_default()
+    };
+    let (code, message) = match resolve_calls(graph, key).err()? {
+        CallIssue::Unresolved { caller, path } if &caller == key => (
+            codes::UNRESOLVED_REFERENCE,
+            format!(
+                "Template '{}' calls '{{@{path}}}', but no template has that name ({}){}",
+                key.full_name(),
+                file(key),
+                suggested_callee(graph, key, &path)
+            ),
+        ),
+        CallIssue::NotTemplate { caller, callee } if &caller == key => (
+            codes::KIND_MISMATCH,
+            format!(
+                "Template '{}' calls '{}', which is not a template ({})",
+                key.full_name(),
+                callee.full_name(),
+                file(key)
+            ),
+        ),
+        CallIssue::Positional { caller, callee } if &caller == key => (
+            codes::KIND_MISMATCH,
+            format!(
+                "Template '{}' calls '{}', whose positional placeholders (%1$s) cannot be passed on; give it named <template> parameters ({})",
+                key.full_name(),
+                callee.full_name(),
+                file(key)
+            ),
+        ),
+        CallIssue::Cycle(chain) => {
+            // Report a cycle once, from its smallest key
+            let last = chain.last()?;
+            let start = chain.iter().position(|k| k == last)?;
+            let cycle = &chain[start..];
+            if cycle.iter().min()? != key {
+                return None;
+            }
+            let path: Vec<String> =
+                cycle.iter().map(ResourceKey::full_name).collect();
+            (
+                codes::REFERENCE_CYCLE,
+                format!(
+                    "Template call cycle detected: {} ({})",
+                    path.join(" -> "),
+                    file(key)
+                ),
+            )
+        }
+        _ => return None,
+    };
+    Some(AnalysisError::new(code, message, Some(key.clone())))
+}
+
+/// A call must not need features its caller does not have, as a reference
+fn check_gated_callees(
+    graph: &ResourceGraph,
+    key: &ResourceKey,
+    node: &ResourceNode,
+    result: &mut AnalysisResult,
+) {
+    let ResourceValue::Template { text, .. } = &node.value else {
+        return;
+    };
+    let available = graph.required_features(key);
+    for call in template_calls(text) {
+        let Some(callee) = callee_key(key, &call.path) else {
+            continue;
+        };
+        let missing: Vec<String> = graph
+            .required_features(&callee)
+            .into_iter()
+            .filter(|feature| !available.contains(feature))
+            .map(|feature| format!("'{feature}'"))
+            .collect();
+        if missing.is_empty() || graph.get(&callee).is_none() {
+            continue;
+        }
+        result.errors.push(AnalysisError::new(
+            codes::GATED_REFERENCE,
+            format!(
+                "Template '{}' ({}) calls '{}', which only exists with feature {}; move the call under the same feature or the callee out of it",
+                key.full_name(),
+                node.origin.file.display(),
+                callee.full_name(),
+                missing.join(", ")
+            ),
+            Some(key.clone()),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::analysis::testing::graph;
+    use crate::generator::parsing::{
+        ParsedResource, ResourceKind as ParsedKind, ScalarValue,
+        TemplateParam as ParsedParam,
+    };
+
+    fn template(name: &str, text: &str, params: &[&str]) -> ParsedResource {
+        ParsedResource {
+            name: name.to_string(),
+            kind: ParsedKind::Template,
+            value: ScalarValue::Template {
+                text: text.to_string(),
+                params: params
+                    .iter()
+                    .map(|name| ParsedParam {
+                        name: name.to_string(),
+                        value: ScalarValue::Text(String::new()),
+                        order: None,
+                    })
+                    .collect(),
+            },
+            attributes: Default::default(),
+        }
+    }
+
+    fn errors(graph: &ResourceGraph) -> Vec<String> {
+        let mut result = AnalysisResult::default();
+        validate_template_calls(graph, &mut result);
+        result.errors.into_iter().map(|error| error.message).collect()
+    }
+
+    #[test]
+    fn inlines_templates_without_parameters() {
+        let graph = graph(vec![
+            template("mail/signature", "The team", &[]),
+            template("mail/footer", "-- {@./signature}", &[]),
+            template("mail/full", "Hi {name}\n{@mail/footer}", &["name"]),
+        ]);
+        let key = ResourceKey::from_path("mail/full");
+        let (text, params) = resolve_calls(&graph, &key).unwrap();
+        assert_eq!(text, "Hi {name}\n-- The team");
+        assert_eq!(params.len(), 1);
+        assert!(errors(&graph).is_empty());
+    }
+
+    #[test]
+    fn escapes_inlined_braces_in_formatted_callers() {
+        let graph = graph(vec![
+            template("braces", "Use {x} or {{braces}}", &[]),
+            template("bye", "Bye {name}", &[]),
+            template("dear", "Dear {name}. {@braces} {@bye}", &["name"]),
+            template("plain", "{@braces} {@bye}", &[]),
+        ]);
+        let resolve = |path| resolve_calls(&graph, &ResourceKey::from_path(path));
+        assert_eq!(
+            resolve("dear").unwrap().0,
+            "Dear {name}. Use {{x}} or {{{{braces}}}} Bye {{name}}"
+        );
+        // A caller without parameters stays a plain constant
+        assert_eq!(
+            resolve("plain").unwrap().0,
+            "Use {x} or {{braces}} Bye {name}"
+        );
+    }
+
+    #[test]
+    fn appends_callee_parameters_renaming_collisions() {
+        let graph = graph(vec![
+            template("footer", "{name} at {company}", &["name", "company"]),
+            template("email", "Hi {name}. {@footer} {@footer}", &["name"]),
+        ]);
+        let key = ResourceKey::from_path("email");
+        let (text, params) = resolve_calls(&graph, &key).unwrap();
+        assert_eq!(
+            text,
+            "Hi {name}. {@footer(footer_name,company)} {@footer(footer_name,company)}"
+        );
+        let names: Vec<&str> = params.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["name", "footer_name", "company"]);
+    }
+
+    #[test]
+    fn reports_unknown_callees_and_cycles() {
+        let graph = graph(vec![
+            template("a", "{@b}", &[]),
+            template("b", "{@a}", &[]),
+            template("c", "{@missing}", &[]),
+            ParsedResource::string("title", "Title"),
+            template("d", "{@title}", &[]),
+        ]);
+        let errors = errors(&graph);
+        assert_eq!(errors.len(), 3, "{errors:?}");
+        assert_eq!(
+            errors[0],
+            "Template call cycle detected: a -> b -> a (values.xml)"
+        );
+        assert_eq!(
+            errors[1],
+            "Template 'c' calls '{@missing}', but no template has that name (values.xml)"
+        );
+        assert_eq!(
+            errors[2],
+            "Template 'd' calls 'title', which is not a template (values.xml)"
+        );
+    }
+}
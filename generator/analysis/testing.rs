@@ -0,0 +1,50 @@
+//! Fixtures shared by the tests of the analysis passes and of generation
+
+use std::path::PathBuf;
+
+use crate::generator::ir::{ResourceGraph, ResourceGraphBuilder};
+use crate::generator::parsing::{
+    ParsedResource, ParsedResourceFile, ResourceKind, ScalarValue,
+};
+
+use super::AnalysisResult;
+
+/// The graph of `resources`, all in one `values.xml`
+pub(crate) fn graph(resources: Vec<ParsedResource>) -> ResourceGraph {
+    let file =
+        ParsedResourceFile::new(PathBuf::from("values.xml"), false, resources);
+    ResourceGraphBuilder::from_parsed_files(&[file])
+}
+
+/// Runs `pass` on the [`graph`] of `resources`
+pub(super) fn run_pass(
+    resources: Vec<ParsedResource>,
+    pass: impl FnOnce(&ResourceGraph, &mut AnalysisResult),
+) -> AnalysisResult {
+    let graph = graph(resources);
+    let mut result = AnalysisResult::default();
+    pass(&graph, &mut result);
+    result
+}
+
+/// The messages of the warnings of [`run_pass`]
+pub(super) fn warnings_for(
+    resources: Vec<ParsedResource>,
+    pass: impl FnOnce(&ResourceGraph, &mut AnalysisResult),
+) -> Vec<String> {
+    run_pass(resources, pass)
+        .warnings
+        .into_iter()
+        .map(|warning| warning.message)
+        .collect()
+}
+
+/// `<color name="{name}">{value}</color>`
+pub(super) fn color(name: &str, value: &str) -> ParsedResource {
+    ParsedResource {
+        name: name.to_string(),
+        kind: ResourceKind::Color,
+        value: ScalarValue::Color(value.to_string()),
+        attributes: Default::default(),
+    }
+}
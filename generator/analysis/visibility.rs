@@ -0,0 +1,198 @@
+//! `visibility="pub|crate|super"` on resources and `<ns>` elements.
+//!
+//! Values other than `pub`, `crate`, and `super` are errors, reported once
+//! per value and file since namespaces pass theirs on to every resource
+//! inside. A public resource referencing a restricted one is a warning: its
+//! value is inlined, so the restricted value becomes public through it.
+
+use std::collections::BTreeSet;
+
+use crate::generator::ir::{
+    ArrayItem, InterpolationPart, ResourceGraph, ResourceKey, ResourceValue,
+    Visibility,
+};
+
+use super::{codes, AnalysisError, AnalysisResult, AnalysisWarning};
+
+pub(super) fn validate_visibility(
+    graph: &ResourceGraph,
+    result: &mut AnalysisResult,
+) {
+    report_invalid_values(graph, result);
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first() else {
+            continue;
+        };
+        if graph.effective_visibility(key) != Visibility::Public {
+            continue;
+        }
+        for target in referenced_keys(&node.value) {
+            let target_visibility = graph.effective_visibility(target);
+            if target_visibility == Visibility::Public
+                || graph.get_all(target).is_none()
+            {
+                continue;
+            }
+            result.warnings.push(AnalysisWarning::new(
+                codes::VISIBILITY_LEAK,
+                format!(
+                    "Public resource '{}' ({}) references '{}', which is {}; its value becomes public through '{}'",
+                    key.full_name(),
+                    node.origin.file.display(),
+                    target.full_name(),
+                    target_visibility.keyword(),
+                    key.full_name()
+                ),
+                Some(key.clone()),
+            ));
+        }
+    }
+}
+
+fn report_invalid_values(graph: &ResourceGraph, result: &mut AnalysisResult) {
+    let mut invalid = BTreeSet::new();
+    for declared in graph.namespace_visibilities() {
+        invalid.insert((
+            declared.origin.file.clone(),
+            declared.visibility.clone(),
+        ));
+    }
+    for node in graph.nodes().values().flatten() {
+        if let Some(value) = node.attributes.get("visibility") {
+            invalid.insert((node.origin.file.clone(), value.clone()));
+        }
+    }
+    for (file, value) in invalid {
+        if Visibility::parse(&value).is_some() {
+            continue;
+        }
+        result.errors.push(AnalysisError::new(
+            codes::INVALID_VISIBILITY,
+            format!(
+                "Invalid visibility '{value}' in {}: expected pub, crate, or super",
+                file.display()
+            ),
+            None,
+        ));
+    }
+}
+
+/// Keys a value inlines, shared with the feature checks
+pub(super) fn referenced_keys(value: &ResourceValue) -> Vec<&ResourceKey> {
+    match value {
+        ResourceValue::Reference { target, .. } => vec![target],
+        ResourceValue::Interpolated(parts) => parts
+            .iter()
+            .filter_map(|part| match part {
+                InterpolationPart::Reference { target, .. } => Some(target),
+                InterpolationPart::Text(_) => None,
+            })
+            .collect(),
+        ResourceValue::Array(items) => item_references(items).collect(),
+        ResourceValue::ArrayRows(rows) => {
+            rows.iter().flat_map(|row| item_references(row)).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn item_references(
+    items: &[ArrayItem],
+) -> impl Iterator<Item = &ResourceKey> {
+    items.iter().filter_map(|item| match item {
+        ArrayItem::Reference(target) => Some(target),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::input::RawResourceFile;
+    use crate::generator::ir::ResourceGraphBuilder;
+    use crate::generator::parsing::parse_raw_files;
+    use std::path::PathBuf;
+
+    fn graph(xml: &str) -> ResourceGraph {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            xml.to_string(),
+            false,
+        );
+        let parsed = parse_raw_files(&[raw], &Default::default()).unwrap();
+        ResourceGraphBuilder::from_parsed_files(&parsed)
+    }
+
+    fn validate(xml: &str) -> AnalysisResult {
+        let mut result = AnalysisResult::default();
+        validate_visibility(&graph(xml), &mut result);
+        result
+    }
+
+    #[test]
+    fn namespaces_pass_their_visibility_on() {
+        let graph = graph(
+            r#"<resources>
+                <ns name="internal" visibility="crate">
+                    <string name="salt">abc</string>
+                    <string name="shown" visibility="pub">x</string>
+                    <ns name="deeper" visibility="super">
+                        <string name="key">k</string>
+                    </ns>
+                </ns>
+                <string name="title">T</string>
+            </resources>"#,
+        );
+        let effective =
+            |path| graph.effective_visibility(&ResourceKey::from_path(path));
+        assert_eq!(effective("internal/salt"), Visibility::Crate);
+        // Its own `pub` does not escape the pub(crate) module
+        assert_eq!(effective("internal/shown"), Visibility::Crate);
+        assert_eq!(effective("internal/deeper/key"), Visibility::Super);
+        assert_eq!(effective("title"), Visibility::Public);
+        assert_eq!(
+            graph.namespace_visibility(&["internal".to_string()]),
+            Visibility::Crate
+        );
+    }
+
+    #[test]
+    fn warns_when_public_resource_references_restricted_one() {
+        let result = validate(
+            r#"<resources>
+                <string name="salt" visibility="crate">abc</string>
+                <string name="alias">@string/salt</string>
+                <string name="label">Salt: @string/salt</string>
+                <string name="private_alias" visibility="crate">@string/salt</string>
+            </resources>"#,
+        );
+        assert!(result.errors.is_empty());
+        assert_eq!(result.warnings.len(), 2);
+        assert!(result.warnings[0].message.starts_with(
+            "Public resource 'alias' (values.xml) references 'salt', which is pub(crate)"
+        ));
+        assert!(result.warnings[1].message.contains("'label'"));
+    }
+
+    #[test]
+    fn invalid_values_are_reported_once_per_file() {
+        let result = validate(
+            r#"<resources>
+                <ns name="internal" visibility="private">
+                    <string name="a">a</string>
+                    <string name="b">b</string>
+                </ns>
+                <string name="c" visibility="pub(crate)">c</string>
+            </resources>"#,
+        );
+        let messages: Vec<&str> =
+            result.errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Invalid visibility 'private' in values.xml: expected pub, crate, or super",
+                "Invalid visibility 'pub(crate)' in values.xml: expected pub, crate, or super",
+            ]
+        );
+    }
+}
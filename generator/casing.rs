@@ -0,0 +1,303 @@
+//! Case of the generated names: constants, namespace modules, and
+//! template functions.
+//!
+//! The configuration is passed down to every emitter (type handlers
+//! included, through [`ResourceType::emit_rust_named`]), which names its
+//! items with [`NamingConfig::const_identifier`],
+//! [`NamingConfig::module_identifier`], and [`NamingConfig::fn_identifier`].
+//! Analysis gets the same configuration, so the names it checks for
+//! collisions are the names the emitters write.
+//!
+//! [`ResourceType::emit_rust_named`]: crate::generator::ir::types::ResourceType::emit_rust_named
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::generator::ir::{ResourceGraph, ResourceKind};
+use crate::generator::utils::{pascal_case, sanitize_identifier};
+
+/// How a name is cased, after [`sanitize_identifier`] replaced the
+/// characters a Rust name cannot hold
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Case {
+    /// `app_name`, `appName` → `APP_NAME`, `APPNAME`: every letter upper
+    /// case, word breaks left as written
+    Upper,
+    /// `app_name`, `appName` → `AppName`
+    Pascal,
+    /// `app_name`, `appName` → `app_name`
+    Snake,
+    /// As written: `appName` → `appName`
+    Preserve,
+}
+
+impl Case {
+    /// `name` in this case. Applying it again to its own output with a
+    /// suffix (`APP_NAME` + `_len`) gives the same as applying it once
+    /// (`app_name_len`), so companion items follow their main one.
+    pub fn apply(self, name: &str) -> String {
+        let name = sanitize_identifier(name);
+        match self {
+            Self::Upper => name.to_uppercase(),
+            Self::Pascal => pascal_case(&name),
+            Self::Snake => snake_case(&name),
+            Self::Preserve => name,
+        }
+    }
+}
+
+impl fmt::Display for Case {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Upper => "upper",
+            Self::Pascal => "pascal",
+            Self::Snake => "snake",
+            Self::Preserve => "preserve",
+        })
+    }
+}
+
+impl FromStr for Case {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "upper" => Ok(Self::Upper),
+            "pascal" => Ok(Self::Pascal),
+            "snake" => Ok(Self::Snake),
+            "preserve" => Ok(Self::Preserve),
+            other => Err(format!(
+                "unknown case '{other}', expected upper, pascal, snake, or \
+                 preserve"
+            )),
+        }
+    }
+}
+
+/// Case of each kind of generated name. The default is the historical
+/// output: `UPPER` constants, modules and functions as written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NamingConfig {
+    /// Constants and statics, companions included (`APP_NAME_LEN`)
+    pub const_case: Case,
+    /// Namespace modules (`r::error_codes`)
+    pub module_case: Case,
+    /// Template functions and their builders (`greet_args`)
+    pub fn_case: Case,
+}
+
+impl Default for NamingConfig {
+    fn default() -> Self {
+        Self {
+            const_case: Case::Upper,
+            module_case: Case::Preserve,
+            fn_case: Case::Preserve,
+        }
+    }
+}
+
+impl NamingConfig {
+    /// The constant generated for an XML name, in the constant case
+    /// (`not-found` → `NOT_FOUND` by default)
+    pub fn const_identifier(&self, name: &str) -> String {
+        self.const_case.apply(name)
+    }
+
+    /// Companion constant of `const_name` (`DEVICE_NAME` and `len` →
+    /// `DEVICE_NAME_LEN`), in the same case
+    pub fn companion_identifier(
+        &self,
+        const_name: &str,
+        suffix: &str,
+    ) -> String {
+        self.const_identifier(&format!("{const_name}_{suffix}"))
+    }
+
+    /// Function accompanying a resource (`mime_types` and `for` →
+    /// `mime_types_for`), in the function case; under [`Case::Preserve`]
+    /// it is lower case, as these functions always were
+    pub fn companion_fn(&self, name: &str, suffix: &str) -> String {
+        let name = format!("{name}_{suffix}");
+        match self.fn_case {
+            Case::Preserve => sanitize_identifier(&name).to_lowercase(),
+            case => case.apply(&name),
+        }
+    }
+
+    /// The module generated for a namespace, in the module case
+    pub fn module_identifier(&self, name: &str) -> String {
+        self.module_case.apply(name)
+    }
+
+    /// The function generated for a template, in the function case
+    pub fn fn_identifier(&self, name: &str) -> String {
+        self.fn_case.apply(name)
+    }
+
+    /// Lints the names generated for `graph` trip under this
+    /// configuration, for an `#[allow]` on the root module; `None` when
+    /// they follow the Rust conventions. Under [`Case::Preserve`] it
+    /// depends on the names as written (`MyNs` is not snake case).
+    pub(crate) fn allowed_lints(
+        &self,
+        graph: &ResourceGraph,
+    ) -> Option<&'static str> {
+        let lower = |name: String| name.chars().any(char::is_lowercase);
+        let upper = |name: String| name.chars().any(char::is_uppercase);
+        let keys = || graph.nodes().iter();
+        let constants = match self.const_case {
+            Case::Upper => false,
+            Case::Preserve => keys().any(|(key, nodes)| {
+                !is_template(nodes) && lower(self.const_identifier(&key.name))
+            }),
+            Case::Pascal | Case::Snake => true,
+        };
+        let modules = match self.module_case {
+            Case::Snake => false,
+            Case::Preserve => keys().any(|(key, _)| {
+                key.namespace
+                    .iter()
+                    .any(|name| upper(self.module_identifier(name)))
+            }),
+            Case::Upper | Case::Pascal => true,
+        };
+        let functions = match self.fn_case {
+            Case::Snake => false,
+            Case::Preserve => keys().any(|(key, nodes)| {
+                is_template(nodes) && upper(self.fn_identifier(&key.name))
+            }),
+            Case::Upper | Case::Pascal => true,
+        };
+        match (constants, modules || functions) {
+            (true, true) => Some("non_upper_case_globals, non_snake_case"),
+            (true, false) => Some("non_upper_case_globals"),
+            (false, true) => Some("non_snake_case"),
+            (false, false) => None,
+        }
+    }
+}
+
+/// Whether the first definition of a key is a template, named as a
+/// function
+fn is_template(nodes: &[crate::generator::ir::ResourceNode]) -> bool {
+    nodes
+        .first()
+        .is_some_and(|node| node.kind == ResourceKind::Template)
+}
+
+/// `snake_case`: a `_` before each upper case letter following a lower
+/// case letter or a digit, then everything lower case
+fn snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        let breaks = previous.is_some_and(|p| {
+            p.is_lowercase() || p.is_ascii_digit()
+        });
+        if c.is_uppercase() && breaks {
+            snake.push('_');
+        }
+        snake.extend(c.to_lowercase());
+        previous = Some(c);
+    }
+    snake
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cases_names() {
+        let names = ["app_name", "appName", "error-codes", "HTTPServer"];
+        let cased = |case: Case| -> Vec<String> {
+            names.iter().map(|name| case.apply(name)).collect()
+        };
+        assert_eq!(
+            cased(Case::Upper),
+            ["APP_NAME", "APPNAME", "ERROR_CODES", "HTTPSERVER"]
+        );
+        assert_eq!(
+            cased(Case::Pascal),
+            ["AppName", "AppName", "ErrorCodes", "HTTPServer"]
+        );
+        assert_eq!(
+            cased(Case::Snake),
+            ["app_name", "app_name", "error_codes", "httpserver"]
+        );
+        assert_eq!(
+            cased(Case::Preserve),
+            ["app_name", "appName", "error_codes", "HTTPServer"]
+        );
+    }
+
+    #[test]
+    fn companions_follow_their_main_name() {
+        for case in [Case::Upper, Case::Pascal, Case::Snake, Case::Preserve]
+        {
+            for name in ["app_name", "appName", "error-codes"] {
+                assert_eq!(
+                    case.apply(&format!("{}_len", case.apply(name))),
+                    case.apply(&format!("{name}_len")),
+                    "{case} {name}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn names_items_in_the_configured_case() {
+        let pascal = NamingConfig {
+            const_case: Case::Pascal,
+            fn_case: Case::Snake,
+            ..NamingConfig::default()
+        };
+        assert_eq!(pascal.const_identifier("app_name"), "AppName");
+        assert_eq!(pascal.companion_identifier("AppName", "len"), "AppNameLen");
+        assert_eq!(pascal.fn_identifier("greetUser"), "greet_user");
+        let default = NamingConfig::default();
+        assert_eq!(default.const_identifier("app_name"), "APP_NAME");
+        assert_eq!(default.companion_fn("mimeTypes", "for"), "mimetypes_for");
+        assert_eq!("Snake".parse::<Case>(), Ok(Case::Snake));
+        assert!("kebab".parse::<Case>().is_err());
+    }
+
+    #[test]
+    fn preserved_names_allow_the_lints_they_trip() {
+        use crate::generator::ir::ResourceGraphBuilder;
+        use crate::generator::parsing::{ParsedResource, ParsedResourceFile};
+        use std::path::PathBuf;
+
+        let graph = |names: &[&str]| {
+            let resources = names
+                .iter()
+                .map(|name| ParsedResource::string(*name, "x"))
+                .collect();
+            let file = ParsedResourceFile::new(
+                PathBuf::from("values.xml"),
+                false,
+                resources,
+            );
+            ResourceGraphBuilder::from_parsed_files(&[file])
+        };
+        let preserve = NamingConfig::default();
+        assert_eq!(preserve.allowed_lints(&graph(&["auth/TITLE"])), None);
+        assert_eq!(
+            preserve.allowed_lints(&graph(&["MyNs/TITLE"])),
+            Some("non_snake_case")
+        );
+        let constants = NamingConfig {
+            const_case: Case::Preserve,
+            ..NamingConfig::default()
+        };
+        assert_eq!(
+            constants.allowed_lints(&graph(&["my_ns/title"])),
+            Some("non_upper_case_globals")
+        );
+        let snake = NamingConfig {
+            module_case: Case::Snake,
+            ..NamingConfig::default()
+        };
+        assert_eq!(snake.allowed_lints(&graph(&["MyNs/TITLE"])), None);
+    }
+}
@@ -0,0 +1,124 @@
+//! What changed between two versions of the resources (`r-resources
+//! diff`): keys added, removed, or whose winning definition changed, so a
+//! review sees the resources rather than the XML around them.
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use crate::generator::ir::{ResourceGraph, ResourceKey, ResourceNode};
+
+use super::markdown::value_text;
+
+/// One key that differs between two graphs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceChange {
+    /// Key as written in XML (`auth/title`)
+    pub key: String,
+    /// Kind and value before (`string "Sign in"`); `None` when added
+    pub before: Option<String>,
+    /// Kind and value after; `None` when removed
+    pub after: Option<String>,
+}
+
+/// Keys of `before` and `after` whose first definition differs in kind,
+/// value, or attributes, sorted by key
+pub fn resource_diff(
+    before: &ResourceGraph,
+    after: &ResourceGraph,
+) -> Vec<ResourceChange> {
+    let keys: BTreeSet<&ResourceKey> =
+        before.nodes().keys().chain(after.nodes().keys()).collect();
+    let mut changes = Vec::new();
+    for key in keys {
+        let old = before.get(key);
+        let new = after.get(key);
+        if old == new {
+            continue;
+        }
+        changes.push(ResourceChange {
+            key: key.full_name(),
+            before: old.map(describe),
+            after: new.map(describe),
+        });
+    }
+    changes
+}
+
+/// `changes` as text, one line per key: `+` added, `-` removed, `~`
+/// changed
+pub fn diff_report(changes: &[ResourceChange]) -> String {
+    if changes.is_empty() {
+        return "No resource changed\n".to_string();
+    }
+    let width = changes.iter().map(|c| c.key.len()).max().unwrap_or(0);
+    let count = match changes.len() {
+        1 => "1 resource".to_string(),
+        n => format!("{n} resources"),
+    };
+    let mut report = format!("{count} changed:\n");
+    for change in changes {
+        let (sign, text) = match (&change.before, &change.after) {
+            (None, Some(after)) => ('+', after.clone()),
+            (Some(before), None) => ('-', before.clone()),
+            (Some(before), Some(after)) if before == after => {
+                ('~', format!("{after} (attributes changed)"))
+            }
+            (Some(before), Some(after)) => ('~', format!("{before} → {after}")),
+            (None, None) => continue,
+        };
+        let _ = writeln!(report, "    {sign} {:width$}  {text}", change.key);
+    }
+    report
+}
+
+/// Kind and value of `node`, the value quoted on one line
+fn describe(node: &ResourceNode) -> String {
+    format!("{} {:?}", node.kind.tag(), value_text(node))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::analysis::testing::graph;
+    use crate::generator::parsing::ParsedResource;
+
+    #[test]
+    fn reports_added_removed_and_changed_keys() {
+        let before = graph(vec![
+            ParsedResource::string("auth/title", "Sign in"),
+            ParsedResource::string("auth/legacy", "Old"),
+            ParsedResource::string("port", "8080"),
+            ParsedResource::string("same", "Same"),
+        ]);
+        let mut documented = ParsedResource::string("same", "Same");
+        documented
+            .attributes
+            .insert("doc".to_string(), "Now documented".to_string());
+        let after = graph(vec![
+            ParsedResource::string("auth/title", "Log in"),
+            ParsedResource::string("auth/subtitle", "Welcome"),
+            ParsedResource::number("port", "8080", None),
+            documented,
+        ]);
+        let changes = resource_diff(&before, &after);
+        let keys: Vec<&str> = changes.iter().map(|c| c.key.as_str()).collect();
+        // Top-level keys first, as everywhere else
+        assert_eq!(
+            keys,
+            ["port", "same", "auth/legacy", "auth/subtitle", "auth/title"]
+        );
+        assert_eq!(
+            diff_report(&changes),
+            "5 resources changed:\n\
+             \x20   ~ port           string \"8080\" → number \"8080\"\n\
+             \x20   ~ same           string \"Same\" (attributes changed)\n\
+             \x20   - auth/legacy    string \"Old\"\n\
+             \x20   + auth/subtitle  string \"Welcome\"\n\
+             \x20   ~ auth/title     string \"Sign in\" → string \"Log in\"\n"
+        );
+        assert_eq!(
+            diff_report(&resource_diff(&after, &after)),
+            "No resource changed\n"
+        );
+    }
+}
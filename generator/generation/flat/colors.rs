@@ -0,0 +1,106 @@
+//! `r::colors`: a table from resource keys to typed colors, for code that
+//! only knows the key at runtime (themes, config files).
+
+use crate::generator::ir::types::parse_hex_color;
+use crate::generator::ir::{ResourceKind, ResourceValue, Visibility};
+use std::fmt::Write as _;
+
+use super::emitter::{emitted_node, is_gated, visible_as, GenerationContext};
+
+/// Name of the generated module, which no top-level namespace may take
+pub(super) const COLORS: &str = "colors";
+
+/// Emits `pub mod colors` with every public hex color of the runtime
+/// resources; references take the color they point to
+pub(super) fn emit_colors(
+    code: &mut String,
+    ctx: &GenerationContext<'_>,
+    indent: usize,
+) {
+    let mut entries = Vec::new();
+    for (key, nodes) in ctx.graph.nodes() {
+        let Some(first) = nodes.first() else {
+            continue;
+        };
+        // The table is public and always compiled, so it must not reach
+        // restricted or feature-gated resources
+        if first.origin.is_test
+            || !visible_as(ctx, key, Visibility::Public)
+            || is_gated(ctx, key)
+        {
+            continue;
+        }
+        let Some(node) = emitted_node(ctx, key, first) else {
+            continue;
+        };
+        let ResourceValue::Color(value) = &node.value else {
+            continue;
+        };
+        if node.kind != ResourceKind::Color {
+            continue;
+        }
+        if let Some(argb) = parse_hex_color(value) {
+            entries.push((key.full_name(), argb));
+        }
+    }
+    // `by_name` binary searches on the byte order of the keys
+    entries.sort();
+    code.push_str(&colors_module(&entries, indent));
+}
+
+fn colors_module(entries: &[(String, [u8; 4])], indent: usize) -> String {
+    let pad = " ".repeat(indent);
+    let mut code = format!(
+        "{pad}/// Typed colors by resource key.\n\
+         {pad}///\n\
+         {pad}/// Keys are the full namespaced path (`ui/colors/primary`) \
+         and are\n\
+         {pad}/// matched case-sensitively; only hex colors are listed.\n\
+         {pad}pub mod {COLORS} {{\n\
+         {pad}    /// Every color, sorted by key\n\
+         {pad}    pub const ALL: &[(&str, r_resources::Color)] = &[\n"
+    );
+    for (key, [a, r, g, b]) in entries {
+        let _ = writeln!(
+            code,
+            "{pad}        ({key:?}, \
+             r_resources::Color::new({r}, {g}, {b}, {a})),"
+        );
+    }
+    let _ = writeln!(
+        code,
+        "{pad}    ];\n\
+         \n\
+         {pad}    /// The color declared under `name`, e.g. \
+         `\"ui/colors/primary\"`\n\
+         {pad}    #[must_use]\n\
+         {pad}    pub fn by_name(name: &str) -> Option<r_resources::Color> {{\n\
+         {pad}        ALL.binary_search_by(|(key, _)| (*key).cmp(name))\n\
+         {pad}            .ok()\n\
+         {pad}            .map(|index| ALL[index].1)\n\
+         {pad}    }}\n\
+         {pad}}}"
+    );
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_entries_in_order_and_searches_them() {
+        let entries = vec![
+            ("accent".to_string(), [255, 255, 87, 34]),
+            ("ui/colors/primary".to_string(), [128, 51, 102, 255]),
+        ];
+        let code = colors_module(&entries, 4);
+        let accent = "            (\"accent\", \
+                      r_resources::Color::new(255, 87, 34, 255)),\n";
+        let primary = "            (\"ui/colors/primary\", \
+                       r_resources::Color::new(51, 102, 255, 128)),\n";
+        assert!(code.contains(&format!("{accent}{primary}")), "{code}");
+        assert!(code.contains("    pub mod colors {\n"), "{code}");
+        assert!(code.contains("ALL.binary_search_by"), "{code}");
+    }
+}
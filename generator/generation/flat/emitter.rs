@@ -1,127 +1,619 @@
 //! Code emission for flat module generation
 
-use crate::generator::analysis::AnalysisWarning;
-use crate::generator::ir::{ResourceGraph, ResourceKey, ResourceNode, TypeRegistry};
-use crate::generator::utils::sanitize_identifier;
+use crate::generator::analysis::{
+    arrays, codes, interpolation, references, template_calls,
+    AnalysisWarning,
+};
+use crate::generator::ir::types::{EmittedItem, ResourceType};
+use crate::generator::ir::{
+    ResourceGraph, ResourceKey, ResourceNode, ResourceValue, TypeRegistry,
+    Visibility,
+};
+use crate::generator::casing::NamingConfig;
+use crate::generator::utils::{doc_comment, inner_doc_comment};
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt::Write as _;
 
+use super::colors::emit_colors;
+use super::error_enums::emit_error_enum;
+use super::interning::StringPool;
+use super::json::emit_to_json;
+use super::markers::Marker;
+use super::kinds::{emit_kind_imports, emit_kind_modules, in_kind_module};
+use super::meta::emit_meta;
+use super::naming::{has_prelude_namespace, prelude, PRELUDE};
 use super::tree::{build_namespace_tree, sort_namespace_tree, NamespaceNode};
+use crate::generator::generation::inputs::slash_path;
+use crate::generator::generation::GenerationOptions;
 
 /// Context for code generation
-struct GenerationContext<'a> {
-    graph: &'a ResourceGraph,
-    registry: &'a TypeRegistry,
-    duplicate_info: &'a HashMap<ResourceKey, String>,
+pub(super) struct GenerationContext<'a> {
+    pub(super) graph: &'a ResourceGraph,
+    pub(super) registry: &'a TypeRegistry,
+    pub(super) duplicate_info: &'a HashMap<ResourceKey, String>,
+    /// Values emitted once and named by their constants; empty unless
+    /// `GenerationOptions::intern_strings`
+    pub(super) strings: &'a StringPool,
+    /// Whether constants get a marker type; see [`Marker`]
+    pub(super) markers: bool,
+    /// `GenerationOptions::group_by_origin`
+    pub(super) group_by_origin: bool,
+    /// `GenerationOptions::naming`
+    pub(super) naming: NamingConfig,
+    /// Whether resources are defined in the kind modules and re-exported
+    /// by the root one; see [`super::kinds`]
+    pub(super) kind_modules: bool,
 }
 
 /// Parameters for emitting a single resource
-struct ResourceEmitParams<'a> {
-    key: &'a ResourceKey,
-    node: &'a ResourceNode,
-    warning_message: Option<&'a String>,
-    indent: usize,
+pub(super) struct ResourceEmitParams<'a> {
+    pub(super) key: &'a ResourceKey,
+    pub(super) node: &'a ResourceNode,
+    pub(super) warning_message: Option<&'a String>,
+    pub(super) indent: usize,
+    /// Path from the module of the resource to the root module, where
+    /// the interned statics are (`super::super::`)
+    pub(super) statics: &'a str,
 }
 
-/// Generates the `r` module with nested namespace structure
+/// Generates the `r` module with nested namespace structure, holding every
+/// resource that does not come from the test resources.
+///
+/// Returns the code and the warnings raised while generating it.
 pub fn generate_r_module(
     graph: &ResourceGraph,
     registry: &TypeRegistry,
     warnings: &[AnalysisWarning],
-) -> String {
-    let mut tree = build_namespace_tree(graph);
+    options: &GenerationOptions,
+) -> (String, Vec<AnalysisWarning>) {
+    let mut tree = build_namespace_tree(graph, false);
     sort_namespace_tree(&mut tree);
+    let duplicate_info = duplicate_notes(graph, warnings);
+    let ctx = GenerationContext {
+        graph,
+        registry,
+        duplicate_info: &duplicate_info,
+        strings: &StringPool::default(),
+        markers: false,
+        group_by_origin: false,
+        naming: options.naming,
+        kind_modules: options.emit_kind_modules,
+    };
 
-    // Build a map of keys to their warning messages (for duplicate info)
-    let duplicate_info: HashMap<_, _> = warnings
-        .iter()
-        .filter_map(|w| {
-            w.key.as_ref().map(|k| (k.clone(), w.message.clone()))
-        })
-        .collect();
+    let strings = if options.intern_strings {
+        StringPool::collect(&tree, &ctx)
+    } else {
+        StringPool::default()
+    };
+    let ctx = GenerationContext {
+        strings: &strings,
+        markers: options.emit_resource_markers,
+        group_by_origin: options.group_by_origin,
+        ..ctx
+    };
 
+    let root = options.root_module.as_str();
+    let mut code = root_module_start(root, &ctx.naming, graph);
+    strings.emit_statics(&mut code, ctx.kind_modules);
+    emit_namespace_tree(&mut code, &tree, &ctx, &[], 4);
+    if options.emit_to_json {
+        emit_to_json(&mut code, &tree, &ctx, 4);
+    }
+    if options.emit_meta {
+        emit_meta(&mut code, &tree, graph, 4);
+    }
+    if options.emit_lookup {
+        emit_colors(&mut code, &ctx, 4);
+    }
+    let prelude_warnings = emit_root_prelude(&mut code, root, &tree, &ctx);
+    code.push_str("}\n");
+    if ctx.kind_modules {
+        emit_kind_modules(&mut code, &tree, &ctx, root);
+    }
+    (code, prelude_warnings)
+}
+
+/// Generates the `r_tests` module (`<root>_tests`) from the test
+/// resources, laid out like `r`. `None` when there are no test resources.
+pub fn generate_r_tests_module(
+    graph: &ResourceGraph,
+    registry: &TypeRegistry,
+    warnings: &[AnalysisWarning],
+    options: &GenerationOptions,
+) -> Option<(String, Vec<AnalysisWarning>)> {
+    let mut tree = build_namespace_tree(graph, true);
+    if tree.is_empty() {
+        return None;
+    }
+    sort_namespace_tree(&mut tree);
+    let duplicate_info = duplicate_notes(graph, warnings);
     let ctx = GenerationContext {
         graph,
         registry,
         duplicate_info: &duplicate_info,
+        strings: &StringPool::default(),
+        markers: false,
+        group_by_origin: options.group_by_origin,
+        naming: options.naming,
+        kind_modules: false,
     };
 
-    let mut code = String::from("\npub mod r {\n    use std::str::FromStr;\n");
-    emit_namespace_tree(&mut code, &tree, &ctx, 4);
+    let tests_root = format!("{}_tests", options.root_module);
+    let mut code = root_module_start(&tests_root, &ctx.naming, graph);
+    emit_namespace_tree(&mut code, &tree, &ctx, &[], 4);
+    let prelude_warnings =
+        emit_root_prelude(&mut code, &tests_root, &tree, &ctx);
     code.push_str("}\n");
-    code
+    Some((code, prelude_warnings))
+}
+
+/// `pub mod <root> {`, allowing the case lints `naming` trips on the
+/// names in `graph`
+fn root_module_start(
+    root: &str,
+    naming: &NamingConfig,
+    graph: &ResourceGraph,
+) -> String {
+    match naming.allowed_lints(graph) {
+        Some(lints) => format!("\n#[allow({lints})]\npub mod {root} {{\n"),
+        None => format!("\npub mod {root} {{\n"),
+    }
 }
 
+/// Maps duplicated keys to their deprecation notes; other warnings (e.g.
+/// secrets) must not deprecate the constant. Notes leave out file paths so
+/// the generated code does not depend on the checkout.
+pub(super) fn duplicate_notes(
+    graph: &ResourceGraph,
+    warnings: &[AnalysisWarning],
+) -> HashMap<ResourceKey, String> {
+    let mut duplicate_info = HashMap::new();
+    for warning in warnings {
+        let Some(key) = warning.key.as_ref() else {
+            continue;
+        };
+        let is_duplicate = warning.code == codes::DUPLICATE_KEY
+            || warning.code == codes::TYPE_CONFLICT;
+        if is_duplicate && graph.has_duplicates(key) {
+            duplicate_info.entry(key.clone()).or_insert_with(|| {
+                format!(
+                    "Duplicate resource key '{}' defined in multiple files",
+                    key.full_name()
+                )
+            });
+        }
+    }
+    duplicate_info
+}
+
+/// Recurses once per namespace level, like the other tree walks here;
+/// analysis keeps the depth to `ValidationOptions::max_namespace_depth`
 fn emit_namespace_tree(
     code: &mut String,
     node: &NamespaceNode,
     ctx: &GenerationContext<'_>,
+    namespace: &[String],
     indent: usize,
 ) {
     let pad = " ".repeat(indent);
     for (ns_name, child) in &node.children {
+        let mut child_namespace = namespace.to_vec();
+        child_namespace.push(ns_name.clone());
+        for feature in ctx.graph.features_of_namespace(&child_namespace) {
+            let _ = writeln!(code, "{pad}#[cfg(feature = {feature:?})]");
+        }
         let _ = writeln!(
             code,
-            "{}pub mod {} {{",
+            "{}{} mod {} {{",
             pad,
-            sanitize_identifier(ns_name)
+            ctx.graph.namespace_visibility(&child_namespace).keyword(),
+            ctx.naming.module_identifier(ns_name)
         );
-        emit_namespace_tree(code, child, ctx, indent + 4);
+        if let Some(doc) = ctx.graph.namespace_doc(&child_namespace) {
+            code.push_str(&inner_doc_comment(&format!("{pad}    "), &doc));
+        }
+        emit_namespace_tree(code, child, ctx, &child_namespace, indent + 4);
+        emit_error_enum(code, child, ctx, &child_namespace, indent + 4);
+        if !has_prelude_namespace(child, &ctx.naming) {
+            // A re-export nobody outside the crate can see is linted as
+            // unused
+            let restricted = (1..=child_namespace.len()).any(|depth| {
+                ctx.graph.namespace_visibility(&child_namespace[..depth])
+                    != Visibility::Public
+            });
+            let allow = if restricted {
+                "clippy::excessive_nesting, unused_imports"
+            } else {
+                "clippy::excessive_nesting"
+            };
+            let _ = writeln!(
+                code,
+                "{pad}    #[allow({allow})]\n\
+                 {pad}    pub mod {PRELUDE} {{\n\
+                 {pad}        pub use super::*;\n\
+                 {pad}    }}"
+            );
+        }
         let _ = writeln!(code, "{}}}", pad);
     }
 
-    for key in &node.resource_keys {
-        if let Some(all_nodes) = ctx.graph.get_all(key) {
-            let warning_message = ctx.duplicate_info.get(key);
-
-            // Only emit the first node (priority), duplicates are ignored but warned
-            if let Some(first_node) = all_nodes.first() {
-                let params = ResourceEmitParams {
-                    key,
-                    node: first_node,
-                    warning_message,
-                    indent,
-                };
-                emit_resource(code, &params, ctx);
+    emit_kind_imports(code, node, ctx, namespace, indent);
+    let statics = "super::".repeat(namespace.len());
+    let mut origin = None;
+    for key in emission_order(node, ctx) {
+        if in_kind_module(ctx, key) {
+            continue;
+        }
+        // Only emit the first node (priority), duplicates are ignored but warned
+        let Some(first_node) = ctx.graph.get_all(key).and_then(|nodes| nodes.first())
+        else {
+            continue;
+        };
+        if ctx.group_by_origin {
+            let file = slash_path(&first_node.origin.file);
+            if origin.as_ref() != Some(&file) {
+                let _ = writeln!(code, "{pad}// from {file}");
+                origin = Some(file);
             }
-            // Note: Duplicate nodes are not generated, only the first one is kept
         }
+        let params = ResourceEmitParams {
+            key,
+            node: first_node,
+            warning_message: ctx.duplicate_info.get(key),
+            indent,
+            statics: &statics,
+        };
+        emit_resource(code, &params, ctx);
+        // Note: Duplicate nodes are not generated, only the first one is kept
+    }
+}
+
+/// The resources of a namespace module in the order they are emitted: by
+/// name, or by origin file and then name with
+/// `GenerationOptions::group_by_origin`
+fn emission_order<'a>(
+    node: &'a NamespaceNode,
+    ctx: &GenerationContext<'_>,
+) -> Vec<&'a ResourceKey> {
+    let mut keys: Vec<&ResourceKey> = node.resource_keys.iter().collect();
+    if ctx.group_by_origin {
+        keys.sort_by_cached_key(|key| {
+            let file = ctx
+                .graph
+                .get_all(key)
+                .and_then(|nodes| nodes.first())
+                .map(|node| slash_path(&node.origin.file));
+            (file, key.name.clone())
+        });
     }
+    keys
 }
 
-fn emit_resource(
+pub(super) fn emit_resource(
     code: &mut String,
     params: &ResourceEmitParams<'_>,
     ctx: &GenerationContext<'_>,
 ) {
     let pad = " ".repeat(params.indent);
 
-    // Add warning annotation for duplicates with file information
-    if let Some(warning) = params.warning_message {
-        // Extract just the relevant part of the warning for the note
-        let note = if warning.len() > 100 {
-            format!("{}...", &warning[..100])
-        } else {
-            warning.clone()
-        };
-        code.push_str(&format!(
-            "{pad}#[deprecated(note = \"{}\")]\n",
-            note.replace('"', "\\\"")
-        ));
+    // Add warning annotation for duplicates
+    if let Some(note) = params.warning_message {
+        code.push_str(&deprecated_attribute(&pad, note));
         // Add allow for dead_code with a message
         code.push_str(&format!(
             "{pad}#[allow(dead_code)] // WARNING: Duplicate resource - only first definition is used\n"
         ));
     }
 
-    // Find the type handler by matching ResourceKind
-    for ty in ctx.registry.all() {
-        if ty.resource_kind() == params.node.kind {
-            if let Some(rust_code) = ty.emit_rust(params.key, params.node, params.indent) {
-                code.push_str(&rust_code);
+    let Some(node) = emitted_node(ctx, params.key, params.node) else {
+        return;
+    };
+    let Some(ty) = handler_for(ctx, &node) else {
+        return;
+    };
+    if let Some(rust_code) =
+        ty.emit_rust_named(params.key, &node, params.indent, &ctx.naming)
+    {
+        let mut rust_code =
+            ctx.strings.intern(rust_code, &node, params.statics);
+        let marker = ctx
+            .markers
+            .then(|| {
+                Marker::of(params.key, &node, ty, &rust_code, &ctx.naming)
+            })
+            .flatten();
+        if let Some(marker) = marker {
+            let deprecated = params.warning_message.is_some();
+            marker.emit(&mut rust_code, params.key, params.indent, deprecated);
+        }
+        // The declared documentation and visibility, not the ones of a
+        // referenced resource
+        if let Some(doc) = params.node.doc() {
+            code.push_str(&doc_comment(&pad, doc));
+            // A paragraph of its own above the documentation of the type
+            if rust_code.trim_start().starts_with("///") {
+                code.push_str(&doc_comment(&pad, ""));
+            }
+        }
+        let visibility = params.node.visibility();
+        code.push_str(&with_visibility(&rust_code, params.indent, visibility));
+    }
+}
+
+/// `#[deprecated]` with `note` as a Rust string literal, so quotes,
+/// backslashes, and newlines in it cannot end the attribute
+fn deprecated_attribute(pad: &str, note: &str) -> String {
+    format!("{pad}#[deprecated(note = {note:?})]\n")
+}
+
+/// Replaces `pub` on the items a handler emitted at `indent` (nested code,
+/// like function bodies, is deeper and left alone)
+fn with_visibility(code: &str, indent: usize, visibility: Visibility) -> String {
+    if visibility == Visibility::Public {
+        return code.to_string();
+    }
+    let item_prefix = format!("{}pub ", " ".repeat(indent));
+    let mut rewritten = String::with_capacity(code.len());
+    for line in code.split_inclusive('\n') {
+        match line.strip_prefix(&item_prefix) {
+            Some(item) => {
+                let _ = write!(
+                    rewritten,
+                    "{}{} {item}",
+                    " ".repeat(indent),
+                    visibility.keyword()
+                );
             }
-            return;
+            None => rewritten.push_str(line),
+        }
+    }
+    rewritten
+}
+
+/// Whether the resource only exists with some cargo feature enabled
+pub(super) fn is_gated(ctx: &GenerationContext<'_>, key: &ResourceKey) -> bool {
+    !ctx.graph.required_features(key).is_empty()
+}
+
+/// Whether the resource, and every namespace containing it, is visible at
+/// least as widely as `visibility`
+pub(super) fn visible_as(
+    ctx: &GenerationContext<'_>,
+    key: &ResourceKey,
+    visibility: Visibility,
+) -> bool {
+    ctx.graph.effective_visibility(key) <= visibility
+}
+
+/// The node as it is emitted: references take the value of the resource
+/// they point to, with the attributes declared on the reference winning
+/// over the target's (`max-len`, `as-bytes`), interpolated strings are
+/// inlined as plain strings, and array items referencing a resource take
+/// its value. `None` if it cannot be resolved (reported by analysis).
+pub(super) fn emitted_node<'a>(
+    ctx: &GenerationContext<'a>,
+    key: &ResourceKey,
+    node: &'a ResourceNode,
+) -> Option<Cow<'a, ResourceNode>> {
+    let ResourceValue::Reference { .. } = &node.value else {
+        return resolved_node(ctx, key, node);
+    };
+    let target = references::resolve(ctx.graph, key).ok()?;
+    let mut resolved = resolved_node(ctx, key, target)?.into_owned();
+    resolved.attributes.extend(node.attributes.clone());
+    Some(Cow::Owned(resolved))
+}
+
+/// `node`, which is not a reference, with its template calls, array item
+/// references, and interpolations resolved
+fn resolved_node<'a>(
+    ctx: &GenerationContext<'a>,
+    key: &ResourceKey,
+    node: &'a ResourceNode,
+) -> Option<Cow<'a, ResourceNode>> {
+    if template_calls::has_calls(node) {
+        let (text, params) = template_calls::resolve_calls(ctx.graph, key).ok()?;
+        return Some(Cow::Owned(ResourceNode {
+            value: ResourceValue::Template { text, params },
+            ..node.clone()
+        }));
+    }
+    if arrays::has_item_references(&node.value) {
+        let value = arrays::resolve_array(ctx.graph, node).ok()?;
+        return Some(Cow::Owned(ResourceNode {
+            value,
+            ..node.clone()
+        }));
+    }
+    if !matches!(node.value, ResourceValue::Interpolated(_)) {
+        return Some(Cow::Borrowed(node));
+    }
+    let text = interpolation::resolve_text(ctx.graph, key).ok()?;
+    Some(Cow::Owned(ResourceNode {
+        value: ResourceValue::String(text),
+        ..node.clone()
+    }))
+}
+
+/// Finds the type handler by matching ResourceKind
+pub(super) fn handler_for<'a>(
+    ctx: &GenerationContext<'a>,
+    node: &ResourceNode,
+) -> Option<&'a dyn ResourceType> {
+    ctx.registry
+        .all()
+        .iter()
+        .find(|ty| ty.resource_kind() == node.kind)
+        .map(|ty| ty.as_ref())
+}
+
+/// Emits `<root>::prelude` (`r` or `r_tests`), see [`prelude`]. Returns
+/// warnings listing the names left out because they are defined in
+/// several places.
+fn emit_root_prelude(
+    code: &mut String,
+    root: &str,
+    tree: &NamespaceNode,
+    ctx: &GenerationContext<'_>,
+) -> Vec<AnalysisWarning> {
+    if has_prelude_namespace(tree, &ctx.naming) {
+        return vec![AnalysisWarning::new(
+            codes::PRELUDE_CONFLICT,
+            format!(
+                "Namespace 'prelude' hides the generated {root}::prelude module; rename it to get the prelude"
+            ),
+            None,
+        )];
+    }
+    let prelude = prelude(root, tree, ctx);
+    code.push_str("    pub mod prelude {\n        pub use super::*;\n");
+    for export in prelude.exports {
+        let _ = writeln!(code, "        pub use super::{export};");
+    }
+    code.push_str("    }\n");
+    prelude
+        .conflicts
+        .into_iter()
+        .map(|(paths, names)| {
+            AnalysisWarning::new(
+                codes::PRELUDE_CONFLICT,
+                format!(
+                    "Left out of {root}::prelude because they are defined in several namespaces ({}): {}. Import them from their modules instead",
+                    paths.join(", "),
+                    names.join(", ")
+                ),
+                None,
+            )
+        })
+        .collect()
+}
+
+/// Generates `mod __r_resources_smoke` (`__<root>_resources_smoke`): one
+/// test that touches every constant and static and calls every function
+/// with dummy arguments, so a resource emitted in the wrong place fails the
+/// consumer's `cargo test`. `r_tests` is covered when `with_tests` says it
+/// is in the same artifact.
+pub fn generate_smoke_tests(
+    graph: &ResourceGraph,
+    registry: &TypeRegistry,
+    root: &str,
+    with_tests: bool,
+    naming: &NamingConfig,
+) -> String {
+    let duplicate_info = HashMap::new();
+    let ctx = GenerationContext {
+        graph,
+        registry,
+        duplicate_info: &duplicate_info,
+        strings: &StringPool::default(),
+        markers: false,
+        group_by_origin: false,
+        naming: *naming,
+        kind_modules: false,
+    };
+
+    let mut code = format!(
+        "\n#[cfg(test)]\n\
+         mod __{root}_resources_smoke {{\n\
+         \x20   #[test]\n\
+         \x20   #[allow(deprecated)] // Duplicated resources are deprecated\n\
+         \x20   // Types are named without their lifetimes (`GreetArgs`)\n\
+         \x20   #[allow(elided_lifetimes_in_paths)]\n\
+         \x20   fn every_resource_is_reachable() {{\n",
+    );
+    let mut modules = vec![(false, format!("super::{root}"))];
+    if with_tests {
+        modules.push((true, format!("super::{root}_tests")));
+    }
+    for (tests, module) in &modules {
+        let mut tree = build_namespace_tree(graph, *tests);
+        sort_namespace_tree(&mut tree);
+        emit_smoke_calls(&mut code, &tree, &ctx, module);
+    }
+    code.push_str("    }\n}\n");
+    code
+}
+
+fn emit_smoke_calls(
+    code: &mut String,
+    node: &NamespaceNode,
+    ctx: &GenerationContext<'_>,
+    module: &str,
+) {
+    for key in &node.resource_keys {
+        let Some(first) = ctx.graph.get_all(key).and_then(<[_]>::first)
+        else {
+            continue;
+        };
+        // `pub(super)` items are out of reach of the smoke module, and
+        // feature-gated ones may not exist
+        if !visible_as(ctx, key, Visibility::Crate) || is_gated(ctx, key) {
+            continue;
+        }
+        let Some(resolved) = emitted_node(ctx, key, first) else {
+            continue;
+        };
+        let Some(ty) = handler_for(ctx, &resolved) else {
+            continue;
+        };
+        for item in ty.emitted_items(key, &resolved, &ctx.naming) {
+            let _ = writeln!(
+                code,
+                "        let _ = std::hint::black_box({});",
+                smoke_expression(module, &item)
+            );
+        }
+    }
+    for (ns_name, child) in &node.children {
+        let child_module = format!(
+            "{module}::{}",
+            ctx.naming.module_identifier(ns_name)
+        );
+        emit_smoke_calls(code, child, ctx, &child_module);
+    }
+}
+
+fn smoke_expression(module: &str, item: &EmittedItem) -> String {
+    match item {
+        EmittedItem::Const(name) => format!("&{module}::{name}"),
+        EmittedItem::Lazy(name) => format!("&*{module}::{name}"),
+        EmittedItem::Type(name) => {
+            format!("std::any::type_name::<{module}::{name}>()")
         }
+        EmittedItem::Function { name, params } => {
+            let args: Vec<&str> =
+                params.iter().map(|ty| dummy_argument(ty)).collect();
+            format!("{module}::{name}({})", args.join(", "))
+        }
+    }
+}
+
+/// Placeholder value for a parameter of Rust type `ty`
+fn dummy_argument(ty: &str) -> &'static str {
+    match ty {
+        "&str" => "\"\"",
+        "bool" => "false",
+        "f32" | "f64" => "0.0",
+        "r_resources::BigDecimal" => "r_resources::BigDecimal::from(0)",
+        // Argument structs of template builders
+        ty if ty.starts_with(char::is_uppercase) => "Default::default()",
+        _ => "0",
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deprecation_notes_are_escaped() {
+        assert_eq!(
+            deprecated_attribute("    ", "Key 'a\\b' is \"duplicated\"\nhere"),
+            concat!(
+                r#"    #[deprecated(note = "Key 'a\\b' is \"duplicated\"\nhere")]"#,
+                "\n"
+            )
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! Error enums: a namespace declared with `<ns name="error"
+//! error-enum="ApiError">` gets `pub enum ApiError` in its module, one
+//! variant per message string and `_code` number pair, with the message as
+//! its `Display`. Pairs are checked during analysis, so a pair that cannot
+//! be resolved here is left out rather than reported twice.
+
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+
+use crate::generator::analysis::error_enums::{error_code, error_pairs};
+use crate::generator::ir::ResourceValue;
+use crate::generator::utils::{doc_comment, pascal_case};
+
+use super::emitter::{emitted_node, GenerationContext};
+use super::tree::NamespaceNode;
+
+/// One variant: its identifier, message, and code
+struct Variant {
+    ident: String,
+    message: String,
+    code: i64,
+}
+
+/// Emits the error enum of the module generated for `namespace`, if it
+/// declares one, at `indent`
+pub(super) fn emit_error_enum(
+    code: &mut String,
+    node: &NamespaceNode,
+    ctx: &GenerationContext<'_>,
+    namespace: &[String],
+    indent: usize,
+) {
+    let Some(type_name) = ctx.graph.error_enum_of(namespace) else {
+        return;
+    };
+    let variants = variants(node, ctx, namespace);
+    if variants.is_empty() {
+        return;
+    }
+    let pad = " ".repeat(indent);
+
+    let _ = write!(
+        code,
+        "{pad}/// Errors of this module, one per message string and `_code` number\n\
+         {pad}#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]\n\
+         {pad}pub enum {type_name} {{\n"
+    );
+    for variant in &variants {
+        code.push_str(&doc_comment(
+            &format!("{pad}    "),
+            &format!("{}: {}", variant.code, variant.message),
+        ));
+        let _ = writeln!(code, "{pad}    {},", variant.ident);
+    }
+    let all: Vec<String> = variants
+        .iter()
+        .map(|variant| format!("Self::{}", variant.ident))
+        .collect();
+    let _ = write!(
+        code,
+        "{pad}}}\n\
+         {pad}impl {type_name} {{\n\
+         {pad}    /// Every variant, in key order\n\
+         {pad}    pub const ALL: &'static [Self] = &[{}];\n\
+         {pad}\n\
+         {pad}    /// The message string of the error\n\
+         {pad}    #[must_use]\n\
+         {pad}    pub const fn message(&self) -> &'static str {{\n\
+         {pad}        match self {{\n",
+        all.join(", ")
+    );
+    for variant in &variants {
+        let _ = writeln!(
+            code,
+            "{pad}            Self::{} => \"{}\",",
+            variant.ident,
+            variant.message.escape_debug()
+        );
+    }
+    let _ = write!(
+        code,
+        "{pad}        }}\n\
+         {pad}    }}\n\
+         {pad}\n\
+         {pad}    /// The code number of the error\n\
+         {pad}    #[must_use]\n\
+         {pad}    pub const fn code(&self) -> i64 {{\n\
+         {pad}        match self {{\n"
+    );
+    for variant in &variants {
+        let _ = writeln!(
+            code,
+            "{pad}            Self::{} => {},",
+            variant.ident, variant.code
+        );
+    }
+    let _ = write!(
+        code,
+        "{pad}        }}\n\
+         {pad}    }}\n\
+         {pad}\n\
+         {pad}    /// The error with `code`, the first in key order when several\n\
+         {pad}    /// share it\n\
+         {pad}    #[must_use]\n\
+         {pad}    pub const fn from_code(code: i64) -> Option<Self> {{\n\
+         {pad}        match code {{\n"
+    );
+    // A code shared by several errors maps to the first; later arms would
+    // be unreachable
+    let mut seen = BTreeSet::new();
+    for variant in variants.iter().filter(|v| seen.insert(v.code)) {
+        let _ = writeln!(
+            code,
+            "{pad}            {} => Some(Self::{}),",
+            variant.code, variant.ident
+        );
+    }
+    let _ = writeln!(
+        code,
+        "{pad}            _ => None,\n\
+         {pad}        }}\n\
+         {pad}    }}\n\
+         {pad}}}\n\
+         {pad}impl std::fmt::Display for {type_name} {{\n\
+         {pad}    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{\n\
+         {pad}        f.write_str(self.message())\n\
+         {pad}    }}\n\
+         {pad}}}\n\
+         {pad}impl std::error::Error for {type_name} {{}}"
+    );
+}
+
+/// Pairs whose message is in this module (not the test resources of the
+/// same namespace, or the other way around), with their resolved values
+fn variants(
+    node: &NamespaceNode,
+    ctx: &GenerationContext<'_>,
+    namespace: &[String],
+) -> Vec<Variant> {
+    let (pairs, _) = error_pairs(ctx.graph, namespace);
+    pairs
+        .into_iter()
+        .filter(|pair| node.resource_keys.contains(pair.message))
+        .filter_map(|pair| {
+            let resolve = |key| {
+                let first = ctx.graph.get_all(key)?.first()?;
+                emitted_node(ctx, key, first)
+            };
+            let ResourceValue::String(message) = &resolve(pair.message)?.value
+            else {
+                return None;
+            };
+            let code = error_code(&resolve(pair.code)?.value)?;
+            Some(Variant {
+                ident: pascal_case(&pair.message.name),
+                message: message.clone(),
+                code,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,241 @@
+//! String interning: a value written verbatim by several `<string>`
+//! resources (a brand name, `OK`, a URL) is emitted once, as a private
+//! static at the top of the root module, and each constant names it:
+//!
+//! ```rust
+//! pub mod r {
+//!     #[allow(dead_code)]
+//!     static __S0: &str = "OK";
+//!     pub const CONFIRM: &str = __S0;
+//!     pub mod dialog {
+//!         pub const ACCEPT: &str = super::__S0;
+//!     }
+//! }
+//! ```
+//!
+//! The constants keep their type and value; only the literal is shared.
+//! Values are compared as emitted (references and interpolations
+//! resolved), byte for byte, so values that merely look alike are never
+//! merged.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use crate::generator::casing::NamingConfig;
+use crate::generator::ir::{
+    ResourceGraph, ResourceKind, ResourceNode, ResourceValue, TypeRegistry,
+};
+
+use super::emitter::{emitted_node, GenerationContext};
+use super::tree::{build_namespace_tree, NamespaceNode};
+
+/// Prefix of the statics holding the interned values
+const STATIC_PREFIX: &str = "__S";
+
+/// What interning saved in one root module
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InternedStrings {
+    /// Values emitted once instead of at every constant
+    pub values: usize,
+    /// Constants naming one of these values instead of repeating it
+    pub constants: usize,
+}
+
+/// Values shared by two or more string constants, with the index of the
+/// static holding each; empty when interning is off
+#[derive(Debug, Default)]
+pub(super) struct StringPool {
+    statics: BTreeMap<String, usize>,
+    constants: usize,
+}
+
+impl StringPool {
+    /// Pool of the string values `tree` emits more than once
+    pub(super) fn collect(
+        tree: &NamespaceNode,
+        ctx: &GenerationContext<'_>,
+    ) -> Self {
+        let mut counts = BTreeMap::new();
+        count_values(tree, ctx, &mut counts);
+        let mut pool = Self::default();
+        for (value, count) in counts {
+            if count < 2 {
+                continue;
+            }
+            pool.constants += count;
+            let index = pool.statics.len();
+            pool.statics.insert(value, index);
+        }
+        pool
+    }
+
+    pub(super) fn stats(&self) -> InternedStrings {
+        InternedStrings {
+            values: self.statics.len(),
+            constants: self.constants,
+        }
+    }
+
+    /// The statics, at the top of the root module; `pub(super)` when the
+    /// kind modules next to it name them
+    pub(super) fn emit_statics(&self, code: &mut String, shared: bool) {
+        let visibility = if shared { "pub(super) " } else { "" };
+        let mut statics: Vec<(&String, &usize)> = self.statics.iter().collect();
+        statics.sort_by_key(|(_, index)| **index);
+        for (value, index) in statics {
+            let _ = writeln!(
+                code,
+                "    #[allow(dead_code)]\n    \
+                 {visibility}static {STATIC_PREFIX}{index}: &str = \"{}\";",
+                value.escape_debug()
+            );
+        }
+    }
+
+    /// `code`, as emitted for `node` in a module reaching the root one
+    /// through `statics` (`super::super::`), with its literal replaced by
+    /// the static holding it
+    pub(super) fn intern(
+        &self,
+        code: String,
+        node: &ResourceNode,
+        statics: &str,
+    ) -> String {
+        let Some(value) = string_value(node) else {
+            return code;
+        };
+        let Some(index) = self.statics.get(value) else {
+            return code;
+        };
+        let literal = format!(": &str = \"{}\";", value.escape_debug());
+        let path = format!(": &str = {statics}{STATIC_PREFIX}{index};");
+        code.replacen(&literal, &path, 1)
+    }
+}
+
+/// Interning stats of the `r` module generated from `graph`
+pub(crate) fn interned_strings(
+    graph: &ResourceGraph,
+    registry: &TypeRegistry,
+    naming: &NamingConfig,
+) -> InternedStrings {
+    let duplicate_info = Default::default();
+    let empty = StringPool::default();
+    let ctx = GenerationContext {
+        graph,
+        registry,
+        duplicate_info: &duplicate_info,
+        strings: &empty,
+        markers: false,
+        group_by_origin: false,
+        naming: *naming,
+        kind_modules: false,
+    };
+    let tree = build_namespace_tree(graph, false);
+    StringPool::collect(&tree, &ctx).stats()
+}
+
+/// The value of a plain string constant
+fn string_value(node: &ResourceNode) -> Option<&str> {
+    match (&node.kind, &node.value) {
+        (ResourceKind::String, ResourceValue::String(value)) => Some(value),
+        _ => None,
+    }
+}
+
+/// Recurses once per namespace level, like the namespace tree itself
+fn count_values(
+    node: &NamespaceNode,
+    ctx: &GenerationContext<'_>,
+    counts: &mut BTreeMap<String, usize>,
+) {
+    for key in &node.resource_keys {
+        let Some(first) = ctx.graph.get_all(key).and_then(|n| n.first())
+        else {
+            continue;
+        };
+        let Some(emitted) = emitted_node(ctx, key, first) else {
+            continue;
+        };
+        if let Some(value) = string_value(&emitted) {
+            *counts.entry(value.to_string()).or_default() += 1;
+        }
+    }
+    for child in node.children.values() {
+        count_values(child, ctx, counts);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::analysis::testing::graph;
+    use crate::generator::generation::flat::generate_r_module;
+    use crate::generator::generation::GenerationOptions;
+    use crate::generator::parsing::ParsedResource;
+
+    fn generate(graph: &ResourceGraph) -> String {
+        let options = GenerationOptions {
+            intern_strings: true,
+            ..Default::default()
+        };
+        generate_r_module(graph, &TypeRegistry::default(), &[], &options).0
+    }
+
+    #[test]
+    fn shares_repeated_values_through_statics() {
+        let graph = graph(vec![
+            ParsedResource::string("confirm", "OK"),
+            ParsedResource::string("dialog/accept", "OK"),
+            ParsedResource::string("brand", "Acme \"Pro\""),
+            ParsedResource::string("about/brand", "Acme \"Pro\""),
+        ]);
+        let code = generate(&graph);
+        assert!(
+            code.contains("    static __S0: &str = \"Acme \\\"Pro\\\"\";\n"),
+            "{code}"
+        );
+        assert!(code.contains("    static __S1: &str = \"OK\";\n"), "{code}");
+        assert!(code.contains("pub const CONFIRM: &str = __S1;"), "{code}");
+        assert!(
+            code.contains("pub const ACCEPT: &str = super::__S1;"),
+            "{code}"
+        );
+        assert!(
+            code.contains("pub const BRAND: &str = super::__S0;"),
+            "{code}"
+        );
+        assert_eq!(
+            interned_strings(
+                &graph,
+                &TypeRegistry::default(),
+                &NamingConfig::default()
+            ),
+            InternedStrings {
+                values: 2,
+                constants: 4
+            }
+        );
+    }
+
+    #[test]
+    fn never_merges_distinct_values() {
+        let graph = graph(vec![
+            ParsedResource::string("ok", "OK"),
+            ParsedResource::string("ok_lower", "ok"),
+            ParsedResource::string("ok_padded", "OK "),
+            ParsedResource::string("yes", "Yes"),
+        ]);
+        let code = generate(&graph);
+        assert!(!code.contains("__S"), "{code}");
+        assert!(code.contains("pub const OK_PADDED: &str = \"OK \";"));
+        assert_eq!(
+            interned_strings(
+                &graph,
+                &TypeRegistry::default(),
+                &NamingConfig::default()
+            ),
+            InternedStrings::default()
+        );
+    }
+}
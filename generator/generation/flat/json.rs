@@ -0,0 +1,178 @@
+//! `r::to_json()`: the scalar resources as a JSON document built at compile
+//! time, so the generated crate needs no serializer.
+
+use crate::generator::ir::types::{is_raw, number_literal};
+use crate::generator::ir::{ArrayItem, ResourceValue};
+use std::fmt::Write as _;
+
+use super::emitter::{emitted_node, GenerationContext};
+use super::tree::NamespaceNode;
+
+/// Emits `pub fn to_json() -> String` at `indent`
+pub(super) fn emit_to_json(
+    code: &mut String,
+    tree: &NamespaceNode,
+    ctx: &GenerationContext<'_>,
+    indent: usize,
+) {
+    let pad = " ".repeat(indent);
+    let json = json_document(tree, ctx, &[]);
+    let _ = writeln!(
+        code,
+        "{pad}/// Every scalar resource as a JSON object nested by namespace\n\
+         {pad}#[must_use]\n\
+         {pad}pub fn to_json() -> String {{\n\
+         {pad}    String::from({json:?})\n\
+         {pad}}}"
+    );
+}
+
+/// Namespaces become objects, arrays become arrays, and resources keep
+/// their XML names. Templates, enums, `<raw>` constants, and feature-gated
+/// namespaces are left out; references and interpolations hold their
+/// resolved value.
+pub(super) fn json_document(
+    node: &NamespaceNode,
+    ctx: &GenerationContext<'_>,
+    namespace: &[String],
+) -> String {
+    let mut members = Vec::new();
+    for key in &node.resource_keys {
+        let Some(first) = ctx.graph.get_all(key).and_then(<[_]>::first)
+        else {
+            continue;
+        };
+        let Some(resolved) = emitted_node(ctx, key, first) else {
+            continue;
+        };
+        if is_raw(&resolved) {
+            continue;
+        }
+        if let Some(value) = json_value(&resolved.value) {
+            members.push(format!("{}:{value}", json_string(&key.name)));
+        }
+    }
+    for (ns_name, child) in &node.children {
+        let mut child_namespace = namespace.to_vec();
+        child_namespace.push(ns_name.clone());
+        // The string would ship the gated values in every build
+        if !ctx.graph.features_of_namespace(&child_namespace).is_empty() {
+            continue;
+        }
+        let object = json_document(child, ctx, &child_namespace);
+        members.push(format!("{}:{object}", json_string(ns_name)));
+    }
+    format!("{{{}}}", members.join(","))
+}
+
+fn json_value(value: &ResourceValue) -> Option<String> {
+    let json = match value {
+        ResourceValue::String(text) | ResourceValue::Color(text) => {
+            json_string(text)
+        }
+        ResourceValue::Cron { expression, .. } => json_string(expression),
+        ResourceValue::Size { bytes, .. } => bytes.to_string(),
+        ResourceValue::DateTime { raw, .. }
+        | ResourceValue::Dimension { raw, .. } => json_string(raw),
+        ResourceValue::Date { year, month, day } => {
+            json_string(&format!("{year:04}-{month:02}-{day:02}"))
+        }
+        ResourceValue::Version {
+            major,
+            minor,
+            patch,
+        } => json_string(&format!("{major}.{minor}.{patch}")),
+        ResourceValue::Char(c) => json_string(&c.to_string()),
+        ResourceValue::Bool(b) => b.to_string(),
+        ResourceValue::Number(number) => json_number(&number_literal(number)),
+        ResourceValue::Percent { ratio, .. } => json_number(&ratio.to_string()),
+        ResourceValue::MimeMap(entries) => {
+            let members: Vec<String> = entries
+                .iter()
+                .map(|(ext, mime)| {
+                    format!("{}:{}", json_string(ext), json_string(mime))
+                })
+                .collect();
+            format!("{{{}}}", members.join(","))
+        }
+        ResourceValue::Array(items) => json_array(items),
+        ResourceValue::ArrayRows(rows) => {
+            let rows: Vec<String> =
+                rows.iter().map(|row| json_array(row)).collect();
+            format!("[{}]", rows.join(","))
+        }
+        ResourceValue::Enum { .. }
+        | ResourceValue::Template { .. }
+        | ResourceValue::Reference { .. }
+        | ResourceValue::Interpolated(_) => return None,
+    };
+    Some(json)
+}
+
+fn json_array(items: &[ArrayItem]) -> String {
+    let items: Vec<String> = items
+        .iter()
+        .map(|item| match item {
+            ArrayItem::String(text) => json_string(text),
+            ArrayItem::Int(value) => value.to_string(),
+            ArrayItem::Float(value) => json_number(&value.to_string()),
+            ArrayItem::Duration(duration) => {
+                json_string(&format!("{duration:?}"))
+            }
+            ArrayItem::Size(bytes) => bytes.to_string(),
+            // Only reachable for a node `emitted_node` did not resolve
+            ArrayItem::Reference(target) => {
+                json_string(&format!("@{}", target.full_name()))
+            }
+        })
+        .collect();
+    format!("[{}]", items.join(","))
+}
+
+/// Rust literal as a JSON number; quoted if JSON cannot represent it
+fn json_number(literal: &str) -> String {
+    let cleaned = literal.trim_start_matches('+').replace('_', "");
+    if cleaned.parse::<f64>().is_ok_and(f64::is_finite) {
+        cleaned
+    } else {
+        json_string(literal)
+    }
+}
+
+pub(super) fn json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_strings() {
+        assert_eq!(json_string("a \"b\"\n\\"), r#""a \"b\"\n\\""#);
+        assert_eq!(json_string("\u{1}é"), "\"\\u0001é\"");
+    }
+
+    #[test]
+    fn numbers_stay_numbers_when_json_allows() {
+        assert_eq!(json_number("1_000"), "1000");
+        assert_eq!(json_number("2.5e3"), "2.5e3");
+        assert_eq!(json_number("inf"), "\"inf\"");
+    }
+}
@@ -0,0 +1,383 @@
+//! Per-kind modules (`string`, `color`, ...) for code written against the
+//! legacy layout, where resources were grouped by kind first:
+//! `string::auth::TITLE` instead of `r::auth::TITLE`.
+//!
+//! The kind modules sit next to the root module and define the items,
+//! mirroring the namespace tree; each namespace module of the root then
+//! re-exports them with a `pub use` glob per kind, so both paths name the
+//! same item and no literal is emitted twice:
+//!
+//! ```rust
+//! pub mod r {
+//!     pub mod auth {
+//!         pub use super::super::string::auth::*;
+//!     }
+//! }
+//! pub mod string {
+//!     pub mod auth {
+//!         pub const TITLE: &str = "Sign in";
+//!     }
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! `pub(super)` resources are private to their parent namespace, which a
+//! re-export cannot reach, so they stay in the root module.
+
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+
+use crate::generator::ir::types::KEYWORDS;
+use crate::generator::ir::{ResourceKey, ResourceKind, Visibility};
+use crate::generator::utils::sanitize_identifier;
+
+use super::emitter::{
+    emit_resource, emitted_node, handler_for, GenerationContext,
+    ResourceEmitParams,
+};
+use super::tree::NamespaceNode;
+
+/// Module holding the resources of `kind`: its tag (`string`, `color`),
+/// with `-` as `_` (`mime_map`), `<item>_array` for arrays, and keywords
+/// as raw identifiers (`r#enum`)
+pub(super) fn kind_module(kind: &ResourceKind) -> String {
+    let name = match kind {
+        ResourceKind::String => "string",
+        ResourceKind::Number => "number",
+        ResourceKind::Bool => "bool",
+        ResourceKind::Color => "color",
+        ResourceKind::Char => "char",
+        ResourceKind::Percent => "percent",
+        ResourceKind::Cron => "cron",
+        ResourceKind::Size => "size",
+        ResourceKind::DateTime => "datetime",
+        ResourceKind::Date => "date",
+        ResourceKind::Version => "version",
+        ResourceKind::MimeMap => "mime_map",
+        ResourceKind::Enum => "enum",
+        ResourceKind::Url => "url",
+        ResourceKind::Dimension => "dimension",
+        ResourceKind::Template => "template",
+        ResourceKind::Array(item) => {
+            return sanitize_identifier(&format!("{item}_array"));
+        }
+        ResourceKind::Custom(tag) => tag,
+    };
+    let name = sanitize_identifier(name);
+    if KEYWORDS.contains(&name.as_str()) {
+        format!("r#{name}")
+    } else {
+        name
+    }
+}
+
+/// One resource as defined in its kind module
+struct KindEntry {
+    module: String,
+    visibility: Visibility,
+}
+
+/// The kind module defining the resource at `key`. `None` for resources
+/// that stay in the root module: unresolved ones, ones without items, and
+/// `pub(super)` ones, which are private to their parent namespace
+fn kind_entry(
+    ctx: &GenerationContext<'_>,
+    key: &ResourceKey,
+) -> Option<KindEntry> {
+    let first = ctx.graph.get_all(key)?.first()?;
+    let visibility = ctx.graph.effective_visibility(key);
+    if visibility == Visibility::Super {
+        return None;
+    }
+    // A reference has the kind, and the items, of what it points to
+    let node = emitted_node(ctx, key, first)?;
+    let items =
+        handler_for(ctx, &node)?.emitted_items(key, &node, &ctx.naming);
+    if items.is_empty() {
+        return None;
+    }
+    Some(KindEntry {
+        module: kind_module(&node.kind),
+        visibility,
+    })
+}
+
+/// Whether the resource at `key` is defined in a kind module, and only
+/// re-exported by the root module
+pub(super) fn in_kind_module(
+    ctx: &GenerationContext<'_>,
+    key: &ResourceKey,
+) -> bool {
+    ctx.kind_modules && kind_entry(ctx, key).is_some()
+}
+
+/// Emits, in the namespace module of `node` at `indent`, a `pub use` glob
+/// of each kind module defining resources of that namespace. The glob is
+/// `pub(crate)` when none of them is public, as a `pub` one would then
+/// re-export nothing.
+pub(super) fn emit_kind_imports(
+    code: &mut String,
+    node: &NamespaceNode,
+    ctx: &GenerationContext<'_>,
+    namespace: &[String],
+    indent: usize,
+) {
+    if !ctx.kind_modules {
+        return;
+    }
+    let pad = " ".repeat(indent);
+    let entries: Vec<KindEntry> = node
+        .resource_keys
+        .iter()
+        .filter_map(|key| kind_entry(ctx, key))
+        .collect();
+    let modules: BTreeSet<&str> =
+        entries.iter().map(|entry| entry.module.as_str()).collect();
+    // Out of the namespace modules, then out of the root module
+    let path = "super::".repeat(namespace.len() + 1);
+    for module in modules {
+        let public = entries.iter().any(|entry| {
+            entry.module == module && entry.visibility == Visibility::Public
+        });
+        let mut glob = format!("{path}{module}");
+        for part in namespace {
+            glob.push_str("::");
+            glob.push_str(&ctx.naming.module_identifier(part));
+        }
+        if public {
+            let _ = writeln!(code, "{pad}pub use {glob}::*;");
+        } else {
+            // Nothing outside the crate can see it, so it may go unused
+            let _ = writeln!(
+                code,
+                "{pad}#[allow(unused_imports)]
+{pad}pub(crate) use {glob}::*;"
+            );
+        }
+    }
+}
+
+/// Emits, next to the root module `root`, a `pub mod` per kind found in
+/// `tree`, each mirroring the namespaces that hold resources of that kind
+/// and defining them
+pub(super) fn emit_kind_modules(
+    code: &mut String,
+    tree: &NamespaceNode,
+    ctx: &GenerationContext<'_>,
+    root: &str,
+) {
+    let mut modules = BTreeSet::new();
+    collect_modules(tree, ctx, &mut modules);
+    let lints = ctx.naming.allowed_lints(ctx.graph);
+    for module in modules {
+        let allow = match lints {
+            Some(lints) => format!("clippy::excessive_nesting, {lints}"),
+            None => "clippy::excessive_nesting".to_string(),
+        };
+        let _ = writeln!(
+            code,
+            "\n/// Every `{}` resource, under its namespace; `{root}` \
+             re-exports them\n\
+             #[allow({allow})]\n\
+             pub mod {module} {{",
+            module.trim_start_matches("r#")
+        );
+        let module = KindModule {
+            name: &module,
+            root,
+        };
+        emit_kind_tree(code, tree, ctx, &module, &[]);
+        code.push_str("}\n");
+    }
+}
+
+fn collect_modules(
+    node: &NamespaceNode,
+    ctx: &GenerationContext<'_>,
+    modules: &mut BTreeSet<String>,
+) {
+    let entries = node.resource_keys.iter().filter_map(|key| {
+        kind_entry(ctx, key).map(|entry| entry.module)
+    });
+    modules.extend(entries);
+    for child in node.children.values() {
+        collect_modules(child, ctx, modules);
+    }
+}
+
+fn has_kind(
+    node: &NamespaceNode,
+    ctx: &GenerationContext<'_>,
+    module: &str,
+) -> bool {
+    node.resource_keys
+        .iter()
+        .filter_map(|key| kind_entry(ctx, key))
+        .any(|entry| entry.module == module)
+        || node
+            .children
+            .values()
+            .any(|child| has_kind(child, ctx, module))
+}
+
+/// A kind module being emitted, next to the root module `root`
+struct KindModule<'a> {
+    name: &'a str,
+    root: &'a str,
+}
+
+/// Recurses once per namespace level, like the namespace tree itself
+fn emit_kind_tree(
+    code: &mut String,
+    node: &NamespaceNode,
+    ctx: &GenerationContext<'_>,
+    module: &KindModule<'_>,
+    namespace: &[String],
+) {
+    // Nested in the kind module and each namespace
+    let indent = 4 * (namespace.len() + 1);
+    let pad = " ".repeat(indent);
+    // The interned statics are in the root module, next to the kind one
+    let up = "super::".repeat(namespace.len() + 1);
+    let statics = format!("{up}{}::", module.root);
+    for key in &node.resource_keys {
+        let Some(entry) = kind_entry(ctx, key) else {
+            continue;
+        };
+        if entry.module != module.name {
+            continue;
+        }
+        let Some(first) = ctx.graph.get_all(key).and_then(<[_]>::first)
+        else {
+            continue;
+        };
+        let params = ResourceEmitParams {
+            key,
+            node: first,
+            warning_message: ctx.duplicate_info.get(key),
+            indent,
+            statics: &statics,
+        };
+        emit_resource(code, &params, ctx);
+    }
+    for (ns_name, child) in &node.children {
+        if !has_kind(child, ctx, module.name) {
+            continue;
+        }
+        let mut child_namespace = namespace.to_vec();
+        child_namespace.push(ns_name.clone());
+        for feature in ctx.graph.features_of_namespace(&child_namespace) {
+            let _ = writeln!(code, "{pad}#[cfg(feature = {feature:?})]");
+        }
+        let _ = writeln!(
+            code,
+            "{pad}{} mod {} {{",
+            ctx.graph.namespace_visibility(&child_namespace).keyword(),
+            ctx.naming.module_identifier(ns_name)
+        );
+        emit_kind_tree(code, child, ctx, module, &child_namespace);
+        let _ = writeln!(code, "{pad}}}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::generation::flat::generate_r_module;
+    use crate::generator::generation::GenerationOptions;
+    use crate::generator::ir::{ResourceGraphBuilder, TypeRegistry};
+    use crate::generator::parsing::{ParsedResource, ParsedResourceFile};
+    use std::path::PathBuf;
+
+    fn generate(resources: Vec<ParsedResource>) -> String {
+        let file = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            resources,
+        );
+        let graph = ResourceGraphBuilder::from_parsed_files(&[file]);
+        let options = GenerationOptions {
+            emit_kind_modules: true,
+            ..Default::default()
+        };
+        generate_r_module(&graph, &TypeRegistry::default(), &[], &options).0
+    }
+
+    #[test]
+    fn names_modules_after_kinds() {
+        assert_eq!(kind_module(&ResourceKind::MimeMap), "mime_map");
+        assert_eq!(kind_module(&ResourceKind::Enum), "r#enum");
+        let array = ResourceKind::Array("string".to_string());
+        assert_eq!(kind_module(&array), "string_array");
+        let custom = ResourceKind::Custom("shader".to_string());
+        assert_eq!(kind_module(&custom), "shader");
+    }
+
+    #[test]
+    fn defines_each_kind_under_its_namespaces() {
+        let code = generate(vec![
+            ParsedResource::string("app_name", "Demo"),
+            ParsedResource::string("auth/title", "Sign in"),
+            ParsedResource::bool("auth/remember", true),
+            ParsedResource::number("ui/grid/columns", "4", None),
+        ]);
+        let string = "pub mod string {\n\
+                      \x20   pub const APP_NAME: &str = \"Demo\";\n\
+                      \x20   pub mod auth {\n\
+                      \x20       pub const TITLE: &str = \"Sign in\";\n\
+                      \x20   }\n\
+                      }\n";
+        assert!(code.contains(string), "{code}");
+        let number = "    pub mod ui {\n\
+                      \x20       pub mod grid {\n\
+                      \x20           pub const COLUMNS: i64 = 4;\n";
+        assert!(code.contains(number), "{code}");
+        assert!(code.contains("\npub mod bool {\n"), "{code}");
+        // Only the namespaces holding the kind are mirrored
+        let bool_module = code.split("\npub mod bool {\n").nth(1).unwrap();
+        let end = bool_module.find("\n}\n").unwrap();
+        let bool_module = &bool_module[..end];
+        assert!(!bool_module.contains("pub mod ui"), "{code}");
+    }
+
+    #[test]
+    fn root_re_exports_the_kind_modules() {
+        let code = generate(vec![
+            ParsedResource::string("app_name", "Demo"),
+            ParsedResource::string("auth/title", "Sign in"),
+            ParsedResource::bool("auth/remember", true),
+        ]);
+        let root = code.split("\npub mod string {").next().unwrap();
+        assert!(root.contains("    pub use super::string::*;\n"), "{code}");
+        let auth = "    pub mod auth {\n\
+                    \x20       pub use super::super::bool::auth::*;\n\
+                    \x20       pub use super::super::string::auth::*;\n";
+        assert!(root.contains(auth), "{code}");
+        // The literals are only in the kind modules
+        assert!(!root.contains("\"Sign in\""), "{code}");
+    }
+
+    #[test]
+    fn keeps_restricted_visibility_and_leaves_super_in_the_root() {
+        let restricted = |name: &str, visibility: &str| {
+            let mut resource = ParsedResource::string(name, "x");
+            resource
+                .attributes
+                .insert("visibility".to_string(), visibility.to_string());
+            resource
+        };
+        let code = generate(vec![
+            restricted("internal", "crate"),
+            restricted("private", "super"),
+        ]);
+        let internal = "pub mod string {\n\
+                        \x20   pub(crate) const INTERNAL: &str = \"x\";\n\
+                        }\n";
+        assert!(code.contains(internal), "{code}");
+        let import = "    #[allow(unused_imports)]\n\
+                      \x20   pub(crate) use super::string::*;\n";
+        assert!(code.contains(import), "{code}");
+        let root = code.split("\npub mod string {").next().unwrap();
+        assert!(root.contains("pub(super) const PRIVATE"), "{code}");
+    }
+}
@@ -0,0 +1,222 @@
+//! Resource markers: with `GenerationOptions::emit_resource_markers`, each
+//! constant gets a zero-sized type implementing `r_resources::Resource`,
+//! so code can take a resource generically and log its key with its value:
+//!
+//! ```rust
+//! pub mod r {
+//!     pub mod auth {
+//!         pub const TITLE: &str = "Sign in";
+//!         /// Marker of `auth/title`, see `r_resources::Resource`
+//!         #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+//!         #[allow(dead_code)]
+//!         pub struct Title;
+//!         impl r_resources::Resource for Title {
+//!             const KEY: &'static str = "auth/title";
+//!             type Value = &'static str;
+//!             const VALUE: Self::Value = TITLE;
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Markers are generated for every constant, so one left unused, e.g. of
+//! a crate-visible resource, does not warn. Templates are functions and
+//! lazily initialized statics are not constants, so neither has a marker. A marker is a unit struct, which
+//! takes its name in both the type and the value namespace of its module;
+//! a clash with another item there fails generation.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use crate::generator::analysis::{codes, AnalysisError};
+use crate::generator::casing::NamingConfig;
+use crate::generator::ir::types::{EmittedItem, ResourceType};
+use crate::generator::ir::{
+    ResourceGraph, ResourceKey, ResourceKind, ResourceNode, TypeRegistry,
+};
+use crate::generator::utils::{pascal_case, sanitize_identifier};
+
+use super::emitter::{emitted_node, handler_for, GenerationContext};
+use super::interning::StringPool;
+
+/// The marker of one constant
+pub(super) struct Marker {
+    name: String,
+    constant: String,
+    /// Type of the constant, with `'static` on its references
+    ty: String,
+}
+
+impl Marker {
+    /// The marker of the resource a handler emitted as `code`, if it is a
+    /// constant
+    pub(super) fn of(
+        key: &ResourceKey,
+        node: &ResourceNode,
+        handler: &dyn ResourceType,
+        code: &str,
+        naming: &NamingConfig,
+    ) -> Option<Self> {
+        if node.kind == ResourceKind::Template {
+            return None;
+        }
+        let first =
+            handler.emitted_items(key, node, naming).into_iter().next()?;
+        let EmittedItem::Const(constant) = first else {
+            return None;
+        };
+        let ty = static_type(const_type(code, &constant)?);
+        let name = pascal_case(&sanitize_identifier(&key.name));
+        if !name.starts_with(|c: char| c.is_alphabetic()) {
+            return None;
+        }
+        Some(Self { name, constant, ty })
+    }
+
+    /// The struct and its `Resource` impl; `deprecated` when the constant
+    /// is, so naming it does not warn
+    pub(super) fn emit(
+        &self,
+        code: &mut String,
+        key: &ResourceKey,
+        indent: usize,
+        deprecated: bool,
+    ) {
+        let pad = " ".repeat(indent);
+        let Self { name, constant, ty } = self;
+        let full_name = key.full_name();
+        let allow = if deprecated {
+            format!("{pad}#[allow(deprecated)]\n")
+        } else {
+            String::new()
+        };
+        let _ = write!(
+            code,
+            "{pad}/// Marker of `{}`, see `r_resources::Resource`\n\
+             {pad}#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]\n\
+             {pad}#[allow(dead_code)]\n\
+             {pad}pub struct {name};\n\
+             {allow}\
+             {pad}impl r_resources::Resource for {name} {{\n\
+             {pad}    const KEY: &'static str = {full_name:?};\n\
+             {pad}    type Value = {ty};\n\
+             {pad}    const VALUE: Self::Value = {constant};\n\
+             {pad}}}\n",
+            full_name.escape_default()
+        );
+    }
+}
+
+/// The type declared for `constant` in `code`: `&str` in
+/// `pub const TITLE: &str = "Sign in";`
+fn const_type<'a>(code: &'a str, constant: &str) -> Option<&'a str> {
+    let declaration = format!(" const {constant}: ");
+    let start = code.find(&declaration)? + declaration.len();
+    let rest = &code[start..];
+    Some(&rest[..rest.find(" = ")?])
+}
+
+/// `ty` with `'static` on its elided lifetimes, which an associated type
+/// cannot leave out: `&[&str]` → `&'static [&'static str]`
+fn static_type(ty: &str) -> String {
+    let mut static_ty = String::with_capacity(ty.len() + 8);
+    let mut chars = ty.chars().peekable();
+    while let Some(c) = chars.next() {
+        static_ty.push(c);
+        if c == '&' && chars.peek() != Some(&'\'') {
+            static_ty.push_str("'static ");
+        }
+    }
+    static_ty
+}
+
+/// One error per marker named like another item of its module: a
+/// constant, function, or type of any resource there, a namespace module,
+/// or another marker
+pub(crate) fn marker_errors(
+    graph: &ResourceGraph,
+    registry: &TypeRegistry,
+    naming: &NamingConfig,
+) -> Vec<AnalysisError> {
+    let duplicate_info = Default::default();
+    let strings = StringPool::default();
+    let ctx = GenerationContext {
+        graph,
+        registry,
+        duplicate_info: &duplicate_info,
+        strings: &strings,
+        markers: true,
+        group_by_origin: false,
+        naming: *naming,
+        kind_modules: false,
+    };
+    // Module path → item name → what takes it
+    let mut taken: BTreeMap<Vec<String>, BTreeMap<String, String>> =
+        BTreeMap::new();
+    let mut markers = Vec::new();
+    for (key, nodes) in graph.nodes() {
+        let Some(first) = nodes.first().filter(|n| !n.origin.is_test) else {
+            continue;
+        };
+        let Some(node) = emitted_node(&ctx, key, first) else {
+            continue;
+        };
+        let Some(handler) = handler_for(&ctx, &node) else {
+            continue;
+        };
+        for depth in 1..=key.namespace.len() {
+            let module = naming.module_identifier(&key.namespace[depth - 1]);
+            let owner = format!("the namespace '{}'", key.namespace[..depth].join("/"));
+            taken
+                .entry(key.namespace[..depth - 1].to_vec())
+                .or_default()
+                .insert(module, owner);
+        }
+        let items = taken.entry(key.namespace.clone()).or_default();
+        for item in handler.emitted_items(key, &node, naming) {
+            let owner = format!("an item of '{}'", key.full_name());
+            items.insert(item.name().to_string(), owner);
+        }
+        let code = handler
+            .emit_rust_named(key, &node, 0, naming)
+            .unwrap_or_default();
+        markers.extend(
+            Marker::of(key, &node, handler, &code, naming)
+                .map(|marker| (key, marker)),
+        );
+    }
+
+    let mut errors = Vec::new();
+    for (key, marker) in markers {
+        let items = taken.entry(key.namespace.clone()).or_default();
+        let owner = format!("the marker of '{}'", key.full_name());
+        let Some(other) = items.insert(marker.name.clone(), owner) else {
+            continue;
+        };
+        errors.push(AnalysisError::new(
+            codes::NAME_COLLISION,
+            format!(
+                "The marker type `{}` of '{}' is named like {other}; rename one of them or turn `emit_resource_markers` off",
+                marker.name,
+                key.full_name()
+            ),
+            Some(key.clone()),
+        ));
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_constant_type() {
+        let code = "    pub const NAMES: &[&str] = &[\"a\"];\n";
+        assert_eq!(const_type(code, "NAMES"), Some("&[&str]"));
+        assert_eq!(const_type(code, "NAME"), None);
+        assert_eq!(static_type("&[&str]"), "&'static [&'static str]");
+        assert_eq!(static_type("&'static str"), "&'static str");
+        assert_eq!(static_type("r_resources::Color"), "r_resources::Color");
+    }
+}
@@ -0,0 +1,88 @@
+//! `r::meta`: resource counts and namespace names, computed from the graph
+//! at emission for startup logs and sanity checks.
+
+use std::fmt::Write;
+
+use crate::generator::ir::{ResourceGraph, ResourceKind};
+
+use super::tree::NamespaceNode;
+
+/// Name of the generated module, which no top-level namespace may take
+pub(super) const META: &str = "meta";
+
+pub(super) fn emit_meta(
+    code: &mut String,
+    tree: &NamespaceNode,
+    graph: &ResourceGraph,
+    indent: usize,
+) {
+    let pad = " ".repeat(indent);
+    let (total, strings) = count_resources(tree, graph);
+    let namespaces: Vec<String> = tree
+        .children
+        .keys()
+        .map(|name| format!("{name:?}"))
+        .collect();
+    let _ = writeln!(
+        code,
+        "{pad}/// Counts and namespaces of the resources in this module, as \
+         declared (feature-gated ones included)\n\
+         {pad}pub mod {META} {{\n\
+         {pad}    /// Every resource, nested namespaces included\n\
+         {pad}    pub const TOTAL_RESOURCES: usize = {total};\n\
+         {pad}    /// `<string>` resources among them\n\
+         {pad}    pub const STRING_COUNT: usize = {strings};\n\
+         {pad}    /// Top-level namespaces, sorted\n\
+         {pad}    pub const NAMESPACES: &[&str] = &[{}];\n\
+         {pad}}}",
+        namespaces.join(", ")
+    );
+}
+
+/// Resources below `node`, and how many of them are strings
+fn count_resources(
+    node: &NamespaceNode,
+    graph: &ResourceGraph,
+) -> (usize, usize) {
+    let strings = node
+        .resource_keys
+        .iter()
+        .filter(|key| {
+            graph
+                .get(key)
+                .is_some_and(|node| node.kind == ResourceKind::String)
+        })
+        .count();
+    node.children.values().fold(
+        (node.resource_keys.len(), strings),
+        |(total, strings), child| {
+            let (child_total, child_strings) = count_resources(child, graph);
+            (total + child_total, strings + child_strings)
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::analysis::testing::graph;
+    use crate::generator::generation::flat::tree::build_namespace_tree;
+    use crate::generator::parsing::ParsedResource;
+
+    #[test]
+    fn emits_counts_and_top_level_namespaces() {
+        let graph = graph(vec![
+            ParsedResource::string("title", "Demo"),
+            ParsedResource::number("max_items", "10", None),
+            ParsedResource::string("ui/labels/ok", "OK"),
+            ParsedResource::bool("auth/enabled", true),
+        ]);
+        let tree = build_namespace_tree(&graph, false);
+        let mut code = String::new();
+        emit_meta(&mut code, &tree, &graph, 0);
+        assert!(code.contains("pub const TOTAL_RESOURCES: usize = 4;"));
+        assert!(code.contains("pub const STRING_COUNT: usize = 2;"));
+        let namespaces = "pub const NAMESPACES: &[&str] = &[\"auth\", \"ui\"];";
+        assert!(code.contains(namespaces), "{code}");
+    }
+}
@@ -19,8 +19,38 @@
 //! }
 //! ```
 
+mod colors;
 mod emitter;
+mod error_enums;
+mod interning;
+mod json;
+mod kinds;
+mod markers;
+mod meta;
+mod naming;
+#[allow(dead_code)] // Used by the library and CLI, not by build.rs
+mod paths;
+mod signatures;
+mod stats;
 mod tree;
 
-pub use emitter::generate_r_module;
+pub use emitter::{
+    generate_r_module, generate_r_tests_module, generate_smoke_tests,
+};
+pub(crate) use interning::interned_strings;
+pub(crate) use markers::marker_errors;
+pub use interning::InternedStrings;
+#[allow(unused_imports)] // Used by the markdown overview, not by build.rs
+pub(crate) use naming::module_path;
+#[allow(unused_imports)] // Public API, re-exported from the crate root
+pub use naming::{const_path_for, module_path_for};
+#[allow(unused_imports)] // Public API, used by the CLI
+pub use paths::{paths_json, paths_table, resource_paths, ResourcePaths};
+pub(crate) use signatures::template_signatures;
+pub use signatures::{
+    reordered_parameters, signatures_manifest, TemplateSignature,
+};
+pub(crate) use stats::resource_stats;
+pub use stats::{stats_table, ResourceStats, StatsRow};
+pub(crate) use tree::reserved_namespace_errors;
 
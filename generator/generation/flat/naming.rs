@@ -0,0 +1,262 @@
+//! Names the generated code gives to resources: module paths, the items
+//! each resource becomes, and what `<root>::prelude` re-exports. The
+//! emitter and the path report both go through here, so the report cannot
+//! disagree with the code.
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::generator::ir::types::EmittedItem;
+use crate::generator::ir::{ResourceKey, Visibility};
+use crate::generator::casing::NamingConfig;
+
+use super::emitter::{
+    emitted_node, handler_for, is_gated, visible_as, GenerationContext,
+};
+use super::tree::NamespaceNode;
+
+/// Name of the re-export module generated in `r` and in every namespace
+pub(super) const PRELUDE: &str = "prelude";
+
+/// Root module of a plan that does not set `root_module`
+const DEFAULT_ROOT: &str = "r";
+
+/// `r::auth::errors` for the namespace `auth/errors` under `root`, with
+/// the modules named by `naming`
+pub(crate) fn module_path<S: AsRef<str>>(
+    root: &str,
+    namespace: &[S],
+    naming: &NamingConfig,
+) -> String {
+    let mut path = root.to_string();
+    for part in namespace {
+        path.push_str("::");
+        path.push_str(&naming.module_identifier(part.as_ref()));
+    }
+    path
+}
+
+/// The module generated for an XML namespace under the default `r` root:
+/// `["auth", "error-codes"]` → `r::auth::error_codes`, and `[]` → `r`.
+/// Each part goes through [`sanitize_identifier`], then takes the module
+/// case of the default [`NamingConfig`] (as written).
+///
+/// [`sanitize_identifier`]: crate::generator::utils::sanitize_identifier
+/// [`NamingConfig`]: crate::generator::casing::NamingConfig
+#[allow(dead_code)] // Public API, not used by build.rs
+pub fn module_path_for(namespace: &[&str]) -> String {
+    module_path(DEFAULT_ROOT, namespace, &NamingConfig::default())
+}
+
+/// The constant generated for an XML key under the default `r` root, as
+/// the emitter writes it: `auth/error-codes/not-found` →
+/// `r::auth::error_codes::NOT_FOUND`. The key is split on `/`, the last
+/// part named like the other parts by [`sanitize_identifier`] and then
+/// upper-cased, as the default [`NamingConfig`] says. Templates
+/// and enums generate functions and types instead, named differently.
+///
+/// [`sanitize_identifier`]: crate::generator::utils::sanitize_identifier
+/// [`NamingConfig`]: crate::generator::casing::NamingConfig
+#[allow(dead_code)] // Public API, not used by build.rs
+pub fn const_path_for(key: &str) -> String {
+    let key = ResourceKey::from_path(key);
+    let naming = NamingConfig::default();
+    format!(
+        "{}::{}",
+        module_path(DEFAULT_ROOT, &key.namespace, &naming),
+        naming.const_identifier(&key.name)
+    )
+}
+
+/// Items generated for the resource at `key`
+pub(super) fn emitted_items(
+    ctx: &GenerationContext<'_>,
+    key: &ResourceKey,
+) -> Vec<EmittedItem> {
+    let Some(first) = ctx.graph.get_all(key).and_then(<[_]>::first) else {
+        return Vec::new();
+    };
+    let Some(node) = emitted_node(ctx, key, first) else {
+        return Vec::new();
+    };
+    handler_for(ctx, &node)
+        .map(|ty| ty.emitted_items(key, &node, &ctx.naming))
+        .unwrap_or_default()
+}
+
+/// Names of [`emitted_items`]
+pub(super) fn item_names(
+    ctx: &GenerationContext<'_>,
+    key: &ResourceKey,
+) -> Vec<String> {
+    emitted_items(ctx, key)
+        .iter()
+        .map(|item| item.name().to_string())
+        .collect()
+}
+
+/// A `<ns name="prelude">` would clash with the generated prelude module
+pub(super) fn has_prelude_namespace(
+    node: &NamespaceNode,
+    naming: &NamingConfig,
+) -> bool {
+    node.children
+        .keys()
+        .any(|name| naming.module_identifier(name) == PRELUDE)
+}
+
+/// What `<root>::prelude` re-exports besides `super::*`
+pub(super) struct Prelude {
+    /// Nested items whose name is unique across the tree, as module paths
+    /// relative to the root (`auth::TITLE`)
+    pub(super) exports: Vec<String>,
+    /// Names left out, grouped by the modules defining them (the root
+    /// first when it defines the name too)
+    pub(super) conflicts: BTreeMap<Vec<String>, Vec<String>>,
+}
+
+/// The prelude of `tree`: the root items and namespace modules come from
+/// the glob, plus every nested item whose name is unique across the tree
+/// (duplicated keys stay reachable through their module)
+pub(super) fn prelude(
+    root: &str,
+    tree: &NamespaceNode,
+    ctx: &GenerationContext<'_>,
+) -> Prelude {
+    let root_names: HashSet<String> = tree
+        .resource_keys
+        .iter()
+        .flat_map(|key| item_names(ctx, key))
+        .collect();
+    let mut nested = BTreeMap::new();
+    for (ns_name, child) in &tree.children {
+        let path = vec![ctx.naming.module_identifier(ns_name)];
+        collect_nested_items(child, ctx, &path, &mut nested);
+    }
+
+    // Conflicting names grouped by the modules defining them, so parallel
+    // trees (e.g. one namespace per locale) give one warning
+    let mut prelude = Prelude {
+        exports: Vec::new(),
+        conflicts: BTreeMap::new(),
+    };
+    for (name, mut paths) in nested {
+        if paths.len() == 1 && !root_names.contains(&name) {
+            prelude.exports.push(format!("{}::{name}", paths[0]));
+            continue;
+        }
+        if root_names.contains(&name) {
+            paths.insert(0, root.to_string());
+        }
+        prelude.conflicts.entry(paths).or_default().push(name);
+    }
+    prelude
+}
+
+/// Maps each item name below `node` to the module paths defining it
+fn collect_nested_items(
+    node: &NamespaceNode,
+    ctx: &GenerationContext<'_>,
+    path: &[String],
+    items: &mut BTreeMap<String, Vec<String>>,
+) {
+    let module = path.join("::");
+    for key in &node.resource_keys {
+        // Re-exporting a deprecated duplicate would warn in the consumer,
+        // `pub use` cannot re-export a restricted item, and a gated one
+        // may not exist
+        if ctx.duplicate_info.contains_key(key)
+            || !visible_as(ctx, key, Visibility::Public)
+            || is_gated(ctx, key)
+        {
+            continue;
+        }
+        for name in item_names(ctx, key) {
+            items.entry(name).or_default().push(module.clone());
+        }
+    }
+    for (ns_name, child) in &node.children {
+        let mut child_path = path.to_vec();
+        child_path.push(ctx.naming.module_identifier(ns_name));
+        collect_nested_items(child, ctx, &child_path, items);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::generation::flat::generate_r_module;
+    use crate::generator::generation::flat::paths::resource_paths;
+    use crate::generator::generation::GenerationOptions;
+    use crate::generator::ir::{ResourceGraphBuilder, TypeRegistry};
+    use crate::generator::parsing::{ParsedResource, ParsedResourceFile};
+    use crate::generator::utils::sanitize_identifier;
+    use std::path::PathBuf;
+
+    #[test]
+    fn module_paths_use_sanitized_namespaces() {
+        let namespace = vec!["auth".to_string(), "error-codes".to_string()];
+        let naming = NamingConfig::default();
+        assert_eq!(
+            module_path("r", &namespace, &naming),
+            "r::auth::error_codes"
+        );
+        assert_eq!(module_path::<&str>("r_tests", &[], &naming), "r_tests");
+        assert_eq!(
+            module_path_for(&["auth", "error-codes"]),
+            "r::auth::error_codes"
+        );
+        assert_eq!(module_path_for(&[]), "r");
+    }
+
+    /// The helpers predict what the emitter writes, on names that need
+    /// sanitizing
+    #[test]
+    fn helpers_agree_with_the_emitter() {
+        let keys = [
+            "app_name",
+            "app.name.short",
+            "auth/error-codes/not-found",
+            "ui/Prix TTC/total",
+            "ui/labels/café",
+            "v2/api--key",
+            "a/b/c/d/deep_key",
+            "mixedCase/someValue",
+        ];
+        let resources = keys
+            .iter()
+            .map(|key| ParsedResource::string(*key, "x"))
+            .collect();
+        let file = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            resources,
+        );
+        let graph = ResourceGraphBuilder::from_parsed_files(&[file]);
+        let registry = TypeRegistry::default();
+        let naming = NamingConfig::default();
+        let report = resource_paths(&graph, &registry, &[], "r", &naming);
+        let (code, _) = generate_r_module(
+            &graph,
+            &registry,
+            &[],
+            &GenerationOptions::default(),
+        );
+        assert_eq!(report.len(), keys.len());
+        for paths in &report {
+            let expected = const_path_for(&paths.key);
+            assert_eq!(paths.paths, [expected.as_str()], "{}", paths.key);
+
+            let namespace: Vec<&str> = paths.key.split('/').collect();
+            let (name, namespace) = namespace.split_last().unwrap();
+            let module = module_path_for(namespace);
+            assert!(expected.starts_with(&format!("{module}::")));
+            // Each module and the constant appear in the code as written
+            for part in module.split("::").skip(1) {
+                assert!(code.contains(&format!("pub mod {part} {{")), "{part}");
+            }
+            let constant = sanitize_identifier(name).to_uppercase();
+            let declaration = format!("pub const {constant}: &str");
+            assert!(code.contains(&declaration), "{declaration}\n{code}");
+        }
+    }
+}
@@ -0,0 +1,258 @@
+//! Rust paths generated for every resource key, for people who know the
+//! XML key and look for the constant (`auth/title` → `r::auth::TITLE`).
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+
+use crate::generator::analysis::AnalysisWarning;
+use crate::generator::ir::types::EmittedItem;
+use crate::generator::ir::{ResourceGraph, TypeRegistry};
+use crate::generator::casing::NamingConfig;
+
+use super::emitter::{duplicate_notes, GenerationContext};
+use super::interning::StringPool;
+use super::json::json_string;
+use super::naming::{
+    emitted_items, has_prelude_namespace, module_path, prelude, PRELUDE,
+};
+use super::tree::build_namespace_tree;
+
+/// Rust paths generated for one resource key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourcePaths {
+    /// Key as written in XML (`auth/title`)
+    pub key: String,
+    /// Items generated for it (`r::auth::TITLE`); functions show their
+    /// parameter types (`r::greet(&str, u32)`)
+    pub paths: Vec<String>,
+    /// The same items through `r::prelude`, where they are re-exported
+    pub aliases: Vec<String>,
+}
+
+/// Paths of every resource in `graph`, sorted by key, as generated under
+/// `root` (test resources under `<root>_tests`) and named by `naming`.
+/// `warnings` are those of the analysis, which decide the duplicates left
+/// out of the prelude.
+pub fn resource_paths(
+    graph: &ResourceGraph,
+    registry: &TypeRegistry,
+    warnings: &[AnalysisWarning],
+    root: &str,
+    naming: &NamingConfig,
+) -> Vec<ResourcePaths> {
+    let duplicate_info = duplicate_notes(graph, warnings);
+    let ctx = GenerationContext {
+        graph,
+        registry,
+        duplicate_info: &duplicate_info,
+        strings: &StringPool::default(),
+        markers: false,
+        group_by_origin: false,
+        naming: *naming,
+        kind_modules: false,
+    };
+    let tests_root = format!("{root}_tests");
+    // Items re-exported by each prelude, relative to its root
+    let mut preludes = HashMap::new();
+    for (is_test, root) in [(false, root), (true, tests_root.as_str())] {
+        let tree = build_namespace_tree(graph, is_test);
+        if has_prelude_namespace(&tree, naming) {
+            continue;
+        }
+        let exports: HashSet<String> =
+            prelude(root, &tree, &ctx).exports.into_iter().collect();
+        preludes.insert(is_test, exports);
+    }
+
+    let mut report = Vec::new();
+    for (key, nodes) in graph.nodes() {
+        let Some(first) = nodes.first() else {
+            continue;
+        };
+        let is_test = first.origin.is_test;
+        let root = if is_test { tests_root.as_str() } else { root };
+        let module = module_path(root, &key.namespace, naming);
+        let relative_module: Vec<String> = key
+            .namespace
+            .iter()
+            .map(|ns| naming.module_identifier(ns))
+            .collect();
+        let relative_module = relative_module.join("::");
+        let mut paths = Vec::new();
+        let mut aliases = Vec::new();
+        for item in emitted_items(&ctx, key) {
+            let name = item.name();
+            paths.push(format!("{module}::{}", signature(&item)));
+            // The glob re-exports root items, the rest are listed by path
+            let exported = preludes.get(&is_test).is_some_and(|exports| {
+                key.namespace.is_empty()
+                    || exports.contains(&format!("{relative_module}::{name}"))
+            });
+            if exported {
+                aliases
+                    .push(format!("{root}::{PRELUDE}::{}", signature(&item)));
+            }
+        }
+        report.push(ResourcePaths {
+            key: key.full_name(),
+            paths,
+            aliases,
+        });
+    }
+    report
+}
+
+/// Name of `item`, with the parameter types of a function
+fn signature(item: &EmittedItem) -> String {
+    match item {
+        EmittedItem::Function { name, params } => {
+            format!("{name}({})", params.join(", "))
+        }
+        _ => item.name().to_string(),
+    }
+}
+
+/// Two columns: each key and its paths, aliases last
+pub fn paths_table(report: &[ResourcePaths]) -> String {
+    let width = report
+        .iter()
+        .map(|paths| paths.key.len())
+        .max()
+        .unwrap_or(0)
+        .max("Key".len());
+    let mut table = format!("{:width$}  Rust path\n", "Key");
+    for paths in report {
+        let all: Vec<&str> = paths
+            .paths
+            .iter()
+            .chain(&paths.aliases)
+            .map(String::as_str)
+            .collect();
+        let _ = writeln!(table, "{:width$}  {}", paths.key, all.join(", "));
+    }
+    table
+}
+
+/// A JSON array with one `{"key", "paths", "aliases"}` object per key
+pub fn paths_json(report: &[ResourcePaths]) -> String {
+    let list = |items: &[String]| {
+        let items: Vec<String> =
+            items.iter().map(|item| json_string(item)).collect();
+        format!("[{}]", items.join(", "))
+    };
+    let objects: Vec<String> = report
+        .iter()
+        .map(|paths| {
+            format!(
+                "  {{\"key\": {}, \"paths\": {}, \"aliases\": {}}}",
+                json_string(&paths.key),
+                list(&paths.paths),
+                list(&paths.aliases)
+            )
+        })
+        .collect();
+    if objects.is_empty() {
+        return "[]\n".to_string();
+    }
+    format!("[\n{}\n]\n", objects.join(",\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::generation::flat::generate_r_module;
+    use crate::generator::generation::GenerationOptions;
+    use crate::generator::ir::ResourceGraphBuilder;
+    use crate::generator::parsing::{ParsedResource, ParsedResourceFile};
+    use std::path::PathBuf;
+
+    fn graph() -> ResourceGraph {
+        let file = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![
+                ParsedResource::string("app_name", "Demo"),
+                ParsedResource::string("auth/title", "Sign in"),
+                ParsedResource::string("home/title", "Home"),
+                ParsedResource::string("ui/error-codes/not_found", "404"),
+            ],
+        );
+        ResourceGraphBuilder::from_parsed_files(&[file])
+    }
+
+    #[test]
+    fn reports_module_paths_and_prelude_aliases() {
+        let report = resource_paths(
+            &graph(),
+            &TypeRegistry::default(),
+            &[],
+            "r",
+            &NamingConfig::default(),
+        );
+        let keys: Vec<&str> =
+            report.iter().map(|paths| paths.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            [
+                "app_name",
+                "auth/title",
+                "home/title",
+                "ui/error-codes/not_found"
+            ]
+        );
+        assert_eq!(report[0].paths, ["r::APP_NAME"]);
+        assert_eq!(report[0].aliases, ["r::prelude::APP_NAME"]);
+        // `TITLE` is defined twice, so the prelude leaves it out
+        assert_eq!(report[1].paths, ["r::auth::TITLE"]);
+        assert!(report[1].aliases.is_empty());
+        assert_eq!(report[3].paths, ["r::ui::error_codes::NOT_FOUND"]);
+        assert_eq!(report[3].aliases, ["r::prelude::NOT_FOUND"]);
+    }
+
+    #[test]
+    fn aliases_match_the_generated_prelude() {
+        let graph = graph();
+        let registry = TypeRegistry::default();
+        let (code, _) = generate_r_module(
+            &graph,
+            &registry,
+            &[],
+            &GenerationOptions::default(),
+        );
+        let naming = NamingConfig::default();
+        let report = resource_paths(&graph, &registry, &[], "r", &naming);
+        let nested = report.iter().flat_map(|paths| {
+            paths.paths.iter().filter_map(move |path| {
+                let relative = path.strip_prefix("r::")?;
+                let (_, name) = relative.rsplit_once("::")?;
+                Some((paths, relative, name))
+            })
+        });
+        for (paths, relative, name) in nested {
+            let export = format!("pub use super::{relative};");
+            let alias = format!("r::prelude::{name}");
+            let aliased = paths.aliases.contains(&alias);
+            assert_eq!(code.contains(&export), aliased, "{relative}\n{code}");
+        }
+    }
+
+    #[test]
+    fn formats_a_table_and_json() {
+        let report = vec![ResourcePaths {
+            key: "auth/title".to_string(),
+            paths: vec!["r::auth::TITLE".to_string()],
+            aliases: vec!["r::prelude::TITLE".to_string()],
+        }];
+        assert_eq!(
+            paths_table(&report),
+            "Key         Rust path\n\
+             auth/title  r::auth::TITLE, r::prelude::TITLE\n"
+        );
+        assert_eq!(
+            paths_json(&report),
+            "[\n  {\"key\": \"auth/title\", \"paths\": [\"r::auth::TITLE\"], \
+             \"aliases\": [\"r::prelude::TITLE\"]}\n]\n"
+        );
+        assert_eq!(paths_json(&[]), "[]\n");
+    }
+}
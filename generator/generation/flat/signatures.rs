@@ -0,0 +1,222 @@
+//! Template signatures: the parameters of every generated template
+//! function, in order, written next to the generated code as
+//! `r_signatures.txt`:
+//!
+//! ```text
+//! r::mail::welcome(name: &str, count: i64)
+//! ```
+//!
+//! Swapping two parameters of the same type still compiles and swaps the
+//! values at every call site, so the next build compares against the file
+//! it finds and warns about templates whose parameters were reordered.
+
+use crate::generator::ir::types::{param_identifier, param_rust_type};
+use crate::generator::ir::{ResourceGraph, ResourceValue, TypeRegistry};
+use crate::generator::casing::NamingConfig;
+
+use super::emitter::{emitted_node, GenerationContext};
+use super::naming::module_path;
+
+/// A template function and its parameters, in signature order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateSignature {
+    /// `r::mail::welcome`
+    pub path: String,
+    /// Parameter identifiers and their Rust types
+    pub params: Vec<(String, String)>,
+}
+
+impl std::fmt::Display for TemplateSignature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<String> = self
+            .params
+            .iter()
+            .map(|(name, ty)| format!("{name}: {ty}"))
+            .collect();
+        write!(f, "{}({})", self.path, params.join(", "))
+    }
+}
+
+/// Signatures of the templates with named parameters generated under
+/// `root`, sorted by key, test resources left out. Calls of other
+/// templates are resolved, so their parameters are included.
+pub(crate) fn template_signatures(
+    graph: &ResourceGraph,
+    registry: &TypeRegistry,
+    root: &str,
+    naming: &NamingConfig,
+) -> Vec<TemplateSignature> {
+    let duplicate_info = Default::default();
+    let strings = Default::default();
+    let ctx = GenerationContext {
+        graph,
+        registry,
+        duplicate_info: &duplicate_info,
+        strings: &strings,
+        markers: false,
+        group_by_origin: false,
+        naming: *naming,
+        kind_modules: false,
+    };
+    let mut signatures = Vec::new();
+    for (key, nodes) in graph.nodes() {
+        let Some(first) = nodes.first().filter(|n| !n.origin.is_test) else {
+            continue;
+        };
+        let Some(emitted) = emitted_node(&ctx, key, first) else {
+            continue;
+        };
+        let ResourceValue::Template { params, .. } = &emitted.value else {
+            continue;
+        };
+        if params.is_empty() {
+            continue;
+        }
+        signatures.push(TemplateSignature {
+            path: format!(
+                "{}::{}",
+                module_path(root, &key.namespace, naming),
+                naming.fn_identifier(&key.name)
+            ),
+            params: params
+                .iter()
+                .map(|p| {
+                    (
+                        param_identifier(&p.name),
+                        param_rust_type(&p.value).to_string(),
+                    )
+                })
+                .collect(),
+        });
+    }
+    signatures
+}
+
+/// `signatures` as the contents of `r_signatures.txt`, one per line
+pub fn signatures_manifest(signatures: &[TemplateSignature]) -> String {
+    signatures.iter().map(|s| format!("{s}\n")).collect()
+}
+
+/// One message per template of `current` that takes the same parameters
+/// as in the `previous` manifest, in another order
+pub fn reordered_parameters(
+    previous: &str,
+    current: &[TemplateSignature],
+) -> Vec<String> {
+    let mut messages = Vec::new();
+    for line in previous.lines() {
+        let Some((path, names)) = parse_line(line) else {
+            continue;
+        };
+        let Some(signature) = current.iter().find(|s| s.path == path) else {
+            continue;
+        };
+        let now: Vec<&str> =
+            signature.params.iter().map(|(n, _)| n.as_str()).collect();
+        let mut sorted_before = names.clone();
+        let mut sorted_now = now.clone();
+        sorted_before.sort_unstable();
+        sorted_now.sort_unstable();
+        if names == now || sorted_before != sorted_now {
+            continue;
+        }
+        messages.push(format!(
+            "Parameters of template '{path}' were reordered since the last build: ({}) is now ({}); call sites passing arguments of the same type get them swapped. Give the parameters an `order` to keep it stable",
+            names.join(", "),
+            now.join(", ")
+        ));
+    }
+    messages
+}
+
+/// `r::mail::welcome(name: &str, count: i64)` → the path and the
+/// parameter names
+fn parse_line(line: &str) -> Option<(&str, Vec<&str>)> {
+    let (path, rest) = line.trim().split_once('(')?;
+    let params = rest.strip_suffix(')')?;
+    let names = params
+        .split(", ")
+        .filter(|param| !param.is_empty())
+        .map(|param| param.split_once(':').map_or(param, |(n, _)| n).trim())
+        .collect();
+    Some((path, names))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::ResourceGraphBuilder;
+    use crate::generator::parsing::{
+        ParsedResource, ParsedResourceFile, ResourceKind as ParsedKind,
+        ScalarValue, TemplateParam,
+    };
+    use std::path::PathBuf;
+
+    fn signatures(params: &[(&str, ScalarValue)]) -> Vec<TemplateSignature> {
+        let template = ParsedResource {
+            name: "mail/welcome".to_string(),
+            kind: ParsedKind::Template,
+            value: ScalarValue::Template {
+                text: "Hi {name}, {count} new".to_string(),
+                params: params
+                    .iter()
+                    .map(|(name, value)| TemplateParam {
+                        name: name.to_string(),
+                        value: value.clone(),
+                        order: None,
+                    })
+                    .collect(),
+            },
+            attributes: Default::default(),
+        };
+        let file = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![template, ParsedResource::string("title", "Title")],
+        );
+        let graph = ResourceGraphBuilder::from_parsed_files(&[file]);
+        template_signatures(
+            &graph,
+            &TypeRegistry::new(),
+            "r",
+            &NamingConfig::default(),
+        )
+    }
+
+    #[test]
+    fn lists_template_functions_with_their_parameters() {
+        let count = || ScalarValue::Number {
+            value: String::new(),
+            explicit_type: Some("u32".to_string()),
+        };
+        let current = signatures(&[
+            ("name", ScalarValue::Text(String::new())),
+            ("count", count()),
+        ]);
+        assert_eq!(
+            signatures_manifest(&current),
+            "r::mail::welcome(name: &str, count: u32)\n"
+        );
+        assert!(reordered_parameters(&signatures_manifest(&current), &current)
+            .is_empty());
+
+        let swapped = signatures(&[
+            ("count", count()),
+            ("name", ScalarValue::Text(String::new())),
+        ]);
+        let messages =
+            reordered_parameters(&signatures_manifest(&current), &swapped);
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].starts_with(
+            "Parameters of template 'r::mail::welcome' were reordered since the last build: (name, count) is now (count, name)"
+        ));
+
+        // Other changes already break the call sites
+        let renamed = signatures(&[
+            ("count", count()),
+            ("user", ScalarValue::Text(String::new())),
+        ]);
+        assert!(reordered_parameters(&signatures_manifest(&current), &renamed)
+            .is_empty());
+    }
+}
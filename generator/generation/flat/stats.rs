@@ -0,0 +1,319 @@
+//! Size report: how many bytes of static data the resources of a root
+//! module contribute, by namespace and kind, e.g. before shipping an
+//! embedded build:
+//!
+//! ```text
+//! Namespace  Kind          Items  Bytes
+//! (root)     string            1      4
+//! auth       number            1      2
+//! auth       string            1      7
+//! auth       string-array      1     12
+//! auth       template          1      9
+//! Total                        5     34
+//! ```
+//!
+//! Text is counted as its UTF-8 length once references and
+//! interpolations are resolved (strings, colors, cron expressions, array
+//! and mime-map items, template bodies); other values as the size of the
+//! Rust type they are emitted as (`u16` is 2 bytes, an untyped integer
+//! 8). Companions like `_LEN` and the code of the accessors are left out,
+//! so the figures are a lower bound of what lands in the binary.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use crate::generator::casing::NamingConfig;
+use crate::generator::ir::{
+    ArrayItem, NumberType, NumberValue, ResourceGraph, ResourceValue,
+    TypeRegistry,
+};
+
+use super::emitter::{emitted_node, GenerationContext};
+use super::tree::{build_namespace_tree, NamespaceNode};
+
+/// Static data of the resources of one kind in one namespace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatsRow {
+    /// Root module the namespace is in (`r`)
+    pub root: String,
+    /// Namespace as written in XML (`auth/login`); empty at the root
+    pub namespace: String,
+    /// Tag of the resources (`string`, `string-array`)
+    pub kind: String,
+    pub items: usize,
+    pub bytes: usize,
+}
+
+/// Static data the resources contribute, one row per root module,
+/// namespace, and kind, namespaces depth-first and kinds sorted
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResourceStats {
+    pub rows: Vec<StatsRow>,
+}
+
+#[allow(dead_code)] // Public API, used by the CLI and not by build.rs
+impl ResourceStats {
+    /// Resources counted, all rows together
+    #[must_use]
+    pub fn items(&self) -> usize {
+        self.rows.iter().map(|row| row.items).sum()
+    }
+
+    /// Bytes counted, all rows together
+    #[must_use]
+    pub fn bytes(&self) -> usize {
+        self.rows.iter().map(|row| row.bytes).sum()
+    }
+
+    /// Items and bytes of each kind, across namespaces
+    #[must_use]
+    pub fn by_kind(&self) -> BTreeMap<&str, (usize, usize)> {
+        let mut kinds: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+        for row in &self.rows {
+            let (items, bytes) = kinds.entry(&row.kind).or_default();
+            *items += row.items;
+            *bytes += row.bytes;
+        }
+        kinds
+    }
+}
+
+/// Stats of the `root` module generated from `graph`, test resources
+/// left out
+pub(crate) fn resource_stats(
+    graph: &ResourceGraph,
+    registry: &TypeRegistry,
+    root: &str,
+    naming: &NamingConfig,
+) -> ResourceStats {
+    let duplicate_info = Default::default();
+    let strings = Default::default();
+    let ctx = GenerationContext {
+        graph,
+        registry,
+        duplicate_info: &duplicate_info,
+        strings: &strings,
+        markers: false,
+        group_by_origin: false,
+        naming: *naming,
+        kind_modules: false,
+    };
+    let tree = build_namespace_tree(graph, false);
+    let mut rows = Vec::new();
+    collect_rows(&tree, &ctx, root, &mut rows);
+    ResourceStats { rows }
+}
+
+fn collect_rows(
+    node: &NamespaceNode,
+    ctx: &GenerationContext<'_>,
+    root: &str,
+    rows: &mut Vec<StatsRow>,
+) {
+    // Kind → (items, bytes)
+    let mut kinds: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+    let mut namespace = None;
+    for key in &node.resource_keys {
+        let Some(first) = ctx.graph.get_all(key).and_then(|n| n.first())
+        else {
+            continue;
+        };
+        let Some(emitted) = emitted_node(ctx, key, first) else {
+            continue;
+        };
+        namespace.get_or_insert_with(|| key.namespace.join("/"));
+        let (items, bytes) =
+            kinds.entry(emitted.kind.tag()).or_default();
+        *items += 1;
+        *bytes += value_bytes(&emitted.value);
+    }
+    for (kind, (items, bytes)) in kinds {
+        rows.push(StatsRow {
+            root: root.to_string(),
+            namespace: namespace.clone().unwrap_or_default(),
+            kind,
+            items,
+            bytes,
+        });
+    }
+    for child in node.children.values() {
+        collect_rows(child, ctx, root, rows);
+    }
+}
+
+/// Bytes of static data `value` is emitted as, see the module docs
+fn value_bytes(value: &ResourceValue) -> usize {
+    match value {
+        ResourceValue::String(text) | ResourceValue::Color(text) => text.len(),
+        ResourceValue::Cron { expression, .. } => expression.len(),
+        ResourceValue::Template { text, .. } => text.len(),
+        ResourceValue::Number(number) => number_bytes(number),
+        ResourceValue::Bool(_) => 1,
+        ResourceValue::Char(_) => 4,
+        ResourceValue::Percent { .. } | ResourceValue::Dimension { .. } => 4,
+        ResourceValue::Size { .. } => 8,
+        // Seconds and nanoseconds
+        ResourceValue::DateTime { .. } => 12,
+        ResourceValue::Date { .. } => 6,
+        ResourceValue::Version { .. } => 24,
+        ResourceValue::MimeMap(entries) => entries
+            .iter()
+            .map(|(extension, mime)| extension.len() + mime.len())
+            .sum(),
+        ResourceValue::Array(items) => items.iter().map(item_bytes).sum(),
+        ResourceValue::ArrayRows(rows) => {
+            rows.iter().flatten().map(item_bytes).sum()
+        }
+        // Variant names, returned by `as_str()`
+        ResourceValue::Enum { variants, .. } => {
+            variants.iter().map(|variant| variant.name.len()).sum()
+        }
+        // Resolved before counting
+        ResourceValue::Reference { .. } | ResourceValue::Interpolated(_) => 0,
+    }
+}
+
+fn number_bytes(number: &NumberValue) -> usize {
+    match number {
+        NumberValue::Int(_) | NumberValue::Float(_) => 8,
+        NumberValue::BigDecimal(literal) => literal.len(),
+        NumberValue::Typed { ty, .. } => match ty {
+            NumberType::I8 | NumberType::U8 => 1,
+            NumberType::I16 | NumberType::U16 => 2,
+            NumberType::I32 | NumberType::U32 | NumberType::F32 => 4,
+            NumberType::I64 | NumberType::U64 | NumberType::F64 => 8,
+        },
+    }
+}
+
+fn item_bytes(item: &ArrayItem) -> usize {
+    match item {
+        ArrayItem::String(text) => text.len(),
+        ArrayItem::Int(_) | ArrayItem::Float(_) | ArrayItem::Size(_) => 8,
+        ArrayItem::Duration(_) => 16,
+        // Resolved before counting
+        ArrayItem::Reference(_) => 0,
+    }
+}
+
+/// `stats` as an aligned table, one line per row, then the totals
+#[allow(dead_code)] // Used by the library and CLI, not by build.rs
+pub fn stats_table(stats: &ResourceStats) -> String {
+    let several_roots =
+        stats.rows.iter().any(|row| row.root != stats.rows[0].root);
+    let namespace = |row: &StatsRow| {
+        let name = if row.namespace.is_empty() {
+            "(root)"
+        } else {
+            row.namespace.as_str()
+        };
+        if several_roots {
+            format!("{}::{name}", row.root)
+        } else {
+            name.to_string()
+        }
+    };
+    let names: Vec<String> = stats.rows.iter().map(namespace).collect();
+    let width = names
+        .iter()
+        .map(String::len)
+        .max()
+        .unwrap_or(0)
+        .max("Namespace".len());
+    let kind_width = stats
+        .rows
+        .iter()
+        .map(|row| row.kind.len())
+        .max()
+        .unwrap_or(0)
+        .max("Kind".len());
+    let mut table =
+        format!("{:width$}  {:kind_width$}  Items  Bytes\n", "Namespace", "Kind");
+    for (row, name) in stats.rows.iter().zip(&names) {
+        let _ = writeln!(
+            table,
+            "{name:width$}  {:kind_width$}  {:>5}  {:>5}",
+            row.kind, row.items, row.bytes
+        );
+    }
+    let _ = writeln!(
+        table,
+        "{:width$}  {:kind_width$}  {:>5}  {:>5}",
+        "Total",
+        "",
+        stats.items(),
+        stats.bytes()
+    );
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::ResourceGraphBuilder;
+    use crate::generator::parsing::{
+        ParsedResource, ParsedResourceFile, ResourceKind, ScalarValue,
+    };
+    use std::path::PathBuf;
+
+    fn stats(resources: Vec<ParsedResource>) -> ResourceStats {
+        let file = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            resources,
+        );
+        let graph = ResourceGraphBuilder::from_parsed_files(&[file]);
+        resource_stats(
+            &graph,
+            &TypeRegistry::new(),
+            "r",
+            &NamingConfig::default(),
+        )
+    }
+
+    #[test]
+    fn sums_bytes_by_namespace_and_kind() {
+        let stats = stats(vec![
+            ParsedResource::string("app_name", "Demo"),
+            ParsedResource::string("tagline", "Fast"),
+            ParsedResource::string("auth/title", "Sign in"),
+            ParsedResource::number("auth/port", "8080", Some("u16".into())),
+            ParsedResource::number("auth/retries", "3", None),
+            ParsedResource::bool("auth/remember", true),
+            // Counted with the value it points to
+            ParsedResource {
+                name: "auth/brand".to_string(),
+                kind: ResourceKind::String,
+                value: ScalarValue::Reference {
+                    kind: Some("string".to_string()),
+                    path: "app_name".to_string(),
+                },
+                attributes: Default::default(),
+            },
+        ]);
+
+        let rows: Vec<(&str, &str, usize, usize)> = stats
+            .rows
+            .iter()
+            .map(|row| {
+                (row.namespace.as_str(), row.kind.as_str(), row.items, row.bytes)
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                ("", "string", 2, 8),
+                ("auth", "bool", 1, 1),
+                ("auth", "number", 2, 10),
+                ("auth", "string", 2, 11),
+            ]
+        );
+        assert_eq!((stats.items(), stats.bytes()), (7, 30));
+        assert_eq!(stats.by_kind()["string"], (4, 19));
+
+        let table = stats_table(&stats);
+        assert!(table.starts_with("Namespace  Kind    Items  Bytes\n"), "{table}");
+        assert!(table.contains("(root)     string      2      8\n"), "{table}");
+        assert!(table.ends_with("Total                  7     30\n"), "{table}");
+    }
+}
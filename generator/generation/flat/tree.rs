@@ -1,19 +1,36 @@
 //! Namespace tree construction and sorting
 
+use crate::generator::analysis::{codes, AnalysisError};
+use crate::generator::generation::GenerationOptions;
 use crate::generator::ir::{ResourceGraph, ResourceKey};
+use crate::generator::casing::NamingConfig;
 use std::collections::BTreeMap;
 
+use super::colors::COLORS;
+use super::meta::META;
+
+/// Namespace of the builtin resources (`r::build::VERSION`)
+const BUILD: &str = "build";
+
 #[derive(Default)]
 pub(super) struct NamespaceNode {
     pub(super) children: BTreeMap<String, NamespaceNode>,
     pub(super) resource_keys: Vec<ResourceKey>,
 }
 
-/// Builds a namespace tree from the resource graph
-pub(super) fn build_namespace_tree(graph: &ResourceGraph) -> NamespaceNode {
+/// Builds a namespace tree from the resource graph, with either the runtime
+/// resources (`r`) or the test-only ones (`r_tests`)
+pub(super) fn build_namespace_tree(
+    graph: &ResourceGraph,
+    tests: bool,
+) -> NamespaceNode {
     let mut root = NamespaceNode::default();
     // Only use the first node for each key (primary), duplicates are handled separately
-    for key in graph.nodes().keys() {
+    for (key, nodes) in graph.nodes() {
+        let is_test = nodes.first().is_some_and(|node| node.origin.is_test);
+        if is_test != tests {
+            continue;
+        }
         let mut current = &mut root;
         for ns_part in &key.namespace {
             current = current
@@ -26,6 +43,12 @@ pub(super) fn build_namespace_tree(graph: &ResourceGraph) -> NamespaceNode {
     root
 }
 
+impl NamespaceNode {
+    pub(super) fn is_empty(&self) -> bool {
+        self.resource_keys.is_empty() && self.children.is_empty()
+    }
+}
+
 /// Sorts the namespace tree recursively
 pub(super) fn sort_namespace_tree(node: &mut NamespaceNode) {
     node.resource_keys.sort_by(|a, b| a.name.cmp(&b.name));
@@ -34,3 +57,220 @@ pub(super) fn sort_namespace_tree(node: &mut NamespaceNode) {
     }
 }
 
+
+/// A module the generated code has next to the top-level namespaces, and
+/// the option generating it (`None` when it always is)
+struct ReservedModule {
+    name: String,
+    option: Option<&'static str>,
+}
+
+/// Every name a top-level namespace may not take with `options`: the root
+/// modules themselves (`r::r` would be mistaken for `r` once the prelude
+/// is glob-imported), and each enabled helper module. `prelude` is not
+/// here: a namespace taking it only costs the prelude, with a warning.
+fn reserved_modules(
+    graph: &ResourceGraph,
+    options: &GenerationOptions,
+) -> Vec<ReservedModule> {
+    let root = &options.root_module;
+    let mut modules = vec![
+        ReservedModule {
+            name: root.clone(),
+            option: None,
+        },
+        ReservedModule {
+            name: format!("{root}_tests"),
+            option: None,
+        },
+    ];
+    let helpers = [
+        (META, "emit_meta", options.emit_meta),
+        (COLORS, "emit_lookup", options.emit_lookup),
+    ];
+    for (name, option, enabled) in helpers {
+        if enabled {
+            modules.push(ReservedModule {
+                name: name.to_string(),
+                option: Some(option),
+            });
+        }
+    }
+    // The builtin resources are only there with a build version
+    let has_builtin = graph
+        .nodes()
+        .values()
+        .flatten()
+        .any(|node| node.origin.is_builtin());
+    if has_builtin {
+        modules.push(ReservedModule {
+            name: BUILD.to_string(),
+            option: Some("build_version"),
+        });
+    }
+    modules
+}
+
+/// A top-level namespace named like a module generated next to it (`meta`,
+/// `colors`, the root itself) would clash with it, so
+/// each such module reserves its name. Test resources are not affected,
+/// since `r_tests` has none of the helper modules.
+pub(crate) fn reserved_namespace_errors(
+    graph: &ResourceGraph,
+    options: &GenerationOptions,
+) -> Vec<AnalysisError> {
+    reserved_modules(graph, options)
+        .iter()
+        .filter_map(|module| {
+            reserved_namespace_error(graph, module, &options.naming)
+        })
+        .collect()
+}
+
+fn reserved_namespace_error(
+    graph: &ResourceGraph,
+    module: &ReservedModule,
+    naming: &NamingConfig,
+) -> Option<AnalysisError> {
+    let (key, node) = graph.nodes().iter().find_map(|(key, nodes)| {
+        // The builtin `build` resources are the module itself
+        let node = nodes.iter().find(|node| !node.origin.is_builtin())?;
+        let reserved = !node.origin.is_test
+            && key
+                .namespace
+                .first()
+                .is_some_and(|name| naming.module_identifier(name) == module.name);
+        reserved.then_some((key, node))
+    })?;
+    let namespace = &key.namespace[0];
+    let file = node.origin.file.display();
+    let name = &module.name;
+    let message = match module.option {
+        Some(option) => format!(
+            "Namespace '{namespace}' ({file}) is reserved for the generated `{name}` module while `{option}` is on; rename the namespace or turn `{option}` off"
+        ),
+        None => format!(
+            "Namespace '{namespace}' ({file}) is reserved, since `{name}` is a generated root module; rename the namespace"
+        ),
+    };
+    Some(AnalysisError::new(
+        codes::RESERVED_NAMESPACE,
+        message,
+        Some(key.clone()),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::{ResourceGraphBuilder, ResourceOrigin};
+    use crate::generator::parsing::{ParsedResource, ParsedResourceFile};
+    use std::path::PathBuf;
+
+    fn errors_for(
+        resources: Vec<ParsedResource>,
+        options: &GenerationOptions,
+    ) -> Vec<AnalysisError> {
+        let file = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            resources,
+        );
+        let graph = ResourceGraphBuilder::from_parsed_files(&[file]);
+        reserved_namespace_errors(&graph, options)
+    }
+
+    #[test]
+    fn top_level_generated_module_names_are_reserved() {
+        let options = GenerationOptions {
+            emit_meta: true,
+            ..Default::default()
+        };
+        let nested = ParsedResource::string("ui/meta/title", "Nested is fine");
+        assert!(errors_for(vec![nested], &options).is_empty());
+
+        let resources = vec![
+            ParsedResource::string("meta/version", "1"),
+            ParsedResource::string("colors/note", "Only reserved with lookup"),
+        ];
+        let errors = errors_for(resources.clone(), &options);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, codes::RESERVED_NAMESPACE);
+        assert!(errors[0].message.contains("'meta' (values.xml)"));
+
+        let options = GenerationOptions {
+            emit_lookup: true,
+            ..Default::default()
+        };
+        let errors = errors_for(resources, &options);
+        assert_eq!(errors.len(), 1);
+        let message = &errors[0].message;
+        assert!(message.contains("`colors` module while `emit_lookup`"));
+    }
+
+    #[test]
+    fn root_module_names_are_always_reserved() {
+        let options = GenerationOptions::default();
+        let nested = ParsedResource::string("ui/r/title", "Nested is fine");
+        assert!(errors_for(vec![nested], &options).is_empty());
+
+        let errors = errors_for(
+            vec![ParsedResource::string("r_tests/title", "Clash")],
+            &options,
+        );
+        assert_eq!(errors.len(), 1);
+        let message = &errors[0].message;
+        assert!(message.contains("'r_tests' (values.xml)"), "{message}");
+        assert!(message.contains("`r_tests` is a generated root"));
+
+        // The names follow the root module
+        let options = GenerationOptions {
+            root_module: "res".to_string(),
+            ..Default::default()
+        };
+        let resources = vec![
+            ParsedResource::string("r/title", "Fine under res"),
+            ParsedResource::string("res/title", "Clash"),
+        ];
+        let errors = errors_for(resources, &options);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.contains("'res' (values.xml)"));
+    }
+
+    #[test]
+    fn build_namespace_is_reserved_with_a_build_version() {
+        let user = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![ParsedResource::string("build/channel", "beta")],
+        );
+        let options = GenerationOptions::default();
+        let files = std::slice::from_ref(&user);
+        let graph = ResourceGraphBuilder::from_parsed_files(files);
+        assert!(reserved_namespace_errors(&graph, &options).is_empty());
+
+        let builtin = ParsedResourceFile::new(
+            PathBuf::from(ResourceOrigin::BUILTIN),
+            false,
+            vec![ParsedResource::string("build/version", "1.4.2")],
+        );
+        let graph = ResourceGraphBuilder::from_parsed_files(&[builtin, user]);
+        let errors = reserved_namespace_errors(&graph, &options);
+        assert_eq!(errors.len(), 1);
+        let message = &errors[0].message;
+        assert!(message.contains("'build' (values.xml)"), "{message}");
+        assert!(message.contains("turn `build_version` off"), "{message}");
+    }
+
+    #[test]
+    fn kind_modules_reserve_nothing_in_the_root() {
+        let options = GenerationOptions {
+            emit_kind_modules: true,
+            ..Default::default()
+        };
+        // `string` is next to `r`, not in it
+        let resources =
+            vec![ParsedResource::string("string/title", "r::string::TITLE")];
+        assert!(errors_for(resources, &options).is_empty());
+    }
+}
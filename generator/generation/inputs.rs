@@ -0,0 +1,101 @@
+//! The resource files an artifact was generated from, listed in a comment
+//! at its top:
+//!
+//! ```text
+//! // Resource files of `r` (profile: debug), in priority order:
+//! //   1. res/strings.xml
+//! //   2. res/values.xml
+//! //   3. res/tests/values.xml (test)
+//! ```
+//!
+//! Paths are the ones diagnostics use, relative to the resource
+//! directory's parent with `/` separators, so the comment is the same on
+//! every machine.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::generator::parsing::ParsedResourceFile;
+
+/// A resource file as loaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputFile {
+    /// `res/values.xml`
+    pub path: String,
+    /// Position in load order, from 1; when files define the same key,
+    /// the lowest wins
+    pub order: usize,
+    pub is_test: bool,
+}
+
+impl InputFile {
+    /// The files of `parsed`, in the order they were loaded
+    pub(crate) fn list(parsed: &[ParsedResourceFile]) -> Vec<Self> {
+        parsed
+            .iter()
+            .enumerate()
+            .map(|(index, file)| Self {
+                path: slash_path(&file.path),
+                order: index + 1,
+                is_test: file.is_test,
+            })
+            .collect()
+    }
+}
+
+/// The comment listing `inputs`, ending with an empty line
+pub(crate) fn inputs_header(
+    root_module: &str,
+    profile: &str,
+    inputs: &[InputFile],
+) -> String {
+    let mut header =
+        format!("// Resource files of `{root_module}` (profile: {profile})");
+    if inputs.is_empty() {
+        header.push_str(": none\n");
+        return header;
+    }
+    header.push_str(", in priority order:\n");
+    for input in inputs {
+        let test = if input.is_test { " (test)" } else { "" };
+        let _ = writeln!(header, "//   {}. {}{test}", input.order, input.path);
+    }
+    header
+}
+
+/// `path` with `/` between its components, whatever the platform
+pub(super) fn slash_path(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn lists_files_in_load_order() {
+        let file = |path: &str, is_test| {
+            ParsedResourceFile::new(PathBuf::from(path), is_test, Vec::new())
+        };
+        let inputs = InputFile::list(&[
+            file("res/strings.xml", false),
+            file("res/values.xml", false),
+            file("res/tests/values.xml", true),
+        ]);
+        assert_eq!(
+            inputs_header("r", "release", &inputs),
+            "// Resource files of `r` (profile: release), in priority order:\n\
+             //   1. res/strings.xml\n\
+             //   2. res/values.xml\n\
+             //   3. res/tests/values.xml (test)\n"
+        );
+        assert_eq!(
+            inputs_header("r_bench", "debug", &[]),
+            "// Resource files of `r_bench` (profile: debug): none\n"
+        );
+    }
+}
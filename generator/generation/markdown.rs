@@ -0,0 +1,343 @@
+//! Markdown overview of the resources, one table per namespace, for people
+//! who read the resources without reading the XML.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use crate::generator::casing::NamingConfig;
+use crate::generator::ir::types::{number_literal, param_rust_type};
+use crate::generator::ir::{
+    ArrayItem, InterpolationPart, ResourceGraph, ResourceNode, ResourceValue,
+};
+use crate::generator::parsing::REGION;
+
+use super::flat::module_path;
+
+/// Longest value shown in a table cell, in characters
+const MAX_VALUE_CHARS: usize = 80;
+
+/// Renders every resource as markdown tables with columns key, kind, value,
+/// source file, and doc. Namespaces and keys are sorted, so the output only
+/// changes when the resources do. Values are shown as written (references
+/// are not resolved) and truncated to keep the tables readable. Resources
+/// in a `<!-- region: ... -->` get a table of their own under their
+/// namespace, headed by the region name.
+pub fn emit_markdown(graph: &ResourceGraph) -> String {
+    // Rows of each namespace by region, resources outside one first
+    let mut tables: BTreeMap<&[String], BTreeMap<Option<&str>, Vec<String>>> =
+        BTreeMap::new();
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first() else {
+            continue;
+        };
+        let sources: Vec<String> = nodes
+            .iter()
+            .map(|node| escape_cell(&node.origin.file.display().to_string()))
+            .collect();
+        let doc = node
+            .doc()
+            .map(|doc| format!(" {}", escape_cell(doc)))
+            .unwrap_or_default();
+        let row = format!(
+            "| `{}` | {} | {} | {} |{doc} |",
+            key.name,
+            node.kind.tag(),
+            value_cell(node),
+            sources.join("<br>")
+        );
+        let region = node.attributes.get(REGION).map(String::as_str);
+        tables
+            .entry(&key.namespace)
+            .or_default()
+            .entry(region)
+            .or_default()
+            .push(row);
+    }
+
+    let mut markdown = String::from("# Resources\n");
+    for (namespace, regions) in tables {
+        let module = module_path("r", namespace, &NamingConfig::default());
+        let _ = writeln!(markdown, "\n## `{module}`");
+        if let Some(doc) = graph.namespace_doc(namespace) {
+            let _ = writeln!(markdown, "\n{doc}");
+        }
+        for (region, rows) in regions {
+            if let Some(region) = region {
+                let _ = writeln!(markdown, "\n### {region}");
+            }
+            let _ = writeln!(
+                markdown,
+                "\n| Key | Kind | Value | Source | Doc |\n\
+                 | --- | --- | --- | --- | --- |\n{}",
+                rows.join("\n")
+            );
+        }
+    }
+    markdown
+}
+
+fn value_cell(node: &ResourceNode) -> String {
+    escape_cell(&value_text(node))
+}
+
+/// Value of `node` as written, truncated to [`MAX_VALUE_CHARS`]
+pub(crate) fn value_text(node: &ResourceNode) -> String {
+    let text = match &node.value {
+        ResourceValue::String(text) | ResourceValue::Color(text) => {
+            text.clone()
+        }
+        ResourceValue::Number(number) => number_literal(number),
+        ResourceValue::Bool(b) => b.to_string(),
+        ResourceValue::Char(c) => c.to_string(),
+        ResourceValue::Percent { raw, .. } => raw.clone(),
+        ResourceValue::Cron { expression, .. } => expression.clone(),
+        ResourceValue::Size { raw, .. }
+        | ResourceValue::DateTime { raw, .. }
+        | ResourceValue::Dimension { raw, .. } => raw.clone(),
+        ResourceValue::Date { year, month, day } => {
+            format!("{year:04}-{month:02}-{day:02}")
+        }
+        ResourceValue::Version {
+            major,
+            minor,
+            patch,
+        } => format!("{major}.{minor}.{patch}"),
+        ResourceValue::MimeMap(entries) => entries
+            .iter()
+            .map(|(ext, mime)| format!("{ext} → {mime}"))
+            .collect::<Vec<_>>()
+            .join(", "),
+        ResourceValue::Array(items) => array_cell(items),
+        ResourceValue::ArrayRows(rows) => rows
+            .iter()
+            .map(|row| array_cell(row))
+            .collect::<Vec<_>>()
+            .join("; "),
+        ResourceValue::Enum { variants, .. } => variants
+            .iter()
+            .map(|variant| match variant.discriminant {
+                Some(value) => format!("{} = {value}", variant.name),
+                None => variant.name.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        ResourceValue::Template { text, params } => {
+            let params: Vec<String> = params
+                .iter()
+                .map(|p| format!("{}: {}", p.name, param_rust_type(&p.value)))
+                .collect();
+            // The parameter list is never truncated
+            return format!("`({})` {}", params.join(", "), truncate(text));
+        }
+        ResourceValue::Reference { target, .. } => {
+            format!("@{}", target.full_name())
+        }
+        ResourceValue::Interpolated(parts) => parts
+            .iter()
+            .map(|part| match part {
+                InterpolationPart::Text(text) => text.clone(),
+                InterpolationPart::Reference { target, .. } => {
+                    format!("@{}", target.full_name())
+                }
+            })
+            .collect(),
+    };
+    truncate(&text)
+}
+
+fn array_cell(items: &[ArrayItem]) -> String {
+    items
+        .iter()
+        .map(|item| match item {
+            ArrayItem::String(text) => text.clone(),
+            ArrayItem::Int(value) => value.to_string(),
+            ArrayItem::Float(value) => value.to_string(),
+            ArrayItem::Duration(duration) => format!("{duration:?}"),
+            ArrayItem::Size(bytes) => format!("{bytes} B"),
+            ArrayItem::Reference(target) => format!("@{}", target.full_name()),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn truncate(text: &str) -> String {
+    if text.chars().count() <= MAX_VALUE_CHARS {
+        return text.to_string();
+    }
+    let mut truncated: String =
+        text.chars().take(MAX_VALUE_CHARS - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Pipes would end the cell and newlines the row
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::ResourceGraphBuilder;
+    use crate::generator::parsing::{
+        ParsedNamespaceDoc, ParsedResource, ParsedResourceFile,
+        ResourceKind as ParsedKind, ScalarValue, TemplateParam,
+    };
+    use std::path::PathBuf;
+
+    #[test]
+    fn renders_one_table_per_namespace() {
+        let file = ParsedResourceFile::new(
+            PathBuf::from("res/values.xml"),
+            false,
+            vec![
+                ParsedResource::string("auth/title", "Login"),
+                ParsedResource::string("app_name", "a | b\nc"),
+                ParsedResource::bool("auth/remember", true),
+            ],
+        );
+        let graph = ResourceGraphBuilder::from_parsed_files(&[file]);
+
+        assert_eq!(
+            emit_markdown(&graph),
+            "# Resources\n\
+             \n\
+             ## `r`\n\
+             \n\
+             | Key | Kind | Value | Source | Doc |\n\
+             | --- | --- | --- | --- | --- |\n\
+             | `app_name` | string | a \\| b<br>c | res/values.xml | |\n\
+             \n\
+             ## `r::auth`\n\
+             \n\
+             | Key | Kind | Value | Source | Doc |\n\
+             | --- | --- | --- | --- | --- |\n\
+             | `remember` | bool | true | res/values.xml | |\n\
+             | `title` | string | Login | res/values.xml | |\n"
+        );
+    }
+
+    #[test]
+    fn shows_template_parameters_and_raw_text() {
+        let template = ParsedResource {
+            name: "greet".to_string(),
+            kind: ParsedKind::Template,
+            value: ScalarValue::Template {
+                text: "Hello {name}, you have {count} messages".to_string(),
+                params: vec![
+                    TemplateParam {
+                        name: "name".to_string(),
+                        value: ScalarValue::Text(String::new()),
+                        order: None,
+                    },
+                    TemplateParam {
+                        name: "count".to_string(),
+                        value: ScalarValue::Number {
+                            value: String::new(),
+                            explicit_type: Some("u32".to_string()),
+                        },
+                        order: None,
+                    },
+                ],
+            },
+            attributes: Default::default(),
+        };
+        let file = ParsedResourceFile::new(
+            PathBuf::from("res/values.xml"),
+            false,
+            vec![template],
+        );
+        let graph = ResourceGraphBuilder::from_parsed_files(&[file]);
+
+        assert!(emit_markdown(&graph).contains(
+            "| `greet` | template | `(name: &str, count: u32)` Hello {name}, you have {count} messages |"
+        ));
+    }
+
+    #[test]
+    fn shows_resource_and_namespace_docs() {
+        let mut cta = ParsedResource::string("auth/cta", "Go");
+        cta.attributes
+            .insert("doc".to_string(), "Main button | label".to_string());
+        let mut file = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![cta],
+        );
+        file.namespace_docs.push(ParsedNamespaceDoc {
+            namespace: vec!["auth".to_string()],
+            doc: "Sign-in screens".to_string(),
+        });
+        let graph = ResourceGraphBuilder::from_parsed_files(&[file]);
+
+        let markdown = emit_markdown(&graph);
+        assert!(
+            markdown.contains("## `r::auth`\n\nSign-in screens\n\n| Key"),
+            "{markdown}"
+        );
+        assert!(markdown.contains(
+            "| `cta` | string | Go | values.xml | Main button \\| label |"
+        ));
+    }
+
+    #[test]
+    fn truncates_long_values() {
+        let long = "x".repeat(100);
+        let truncated = truncate(&long);
+        assert_eq!(truncated.chars().count(), MAX_VALUE_CHARS);
+        assert!(truncated.ends_with('…'));
+        assert_eq!(truncate("short"), "short");
+        // A multi-byte character right at the cut is kept or dropped whole
+        let accented = format!("{}éyy", "x".repeat(MAX_VALUE_CHARS - 2));
+        assert_eq!(
+            truncate(&accented),
+            format!("{}é…", "x".repeat(MAX_VALUE_CHARS - 2))
+        );
+        let emoji = format!("{}🎉🎉", "x".repeat(MAX_VALUE_CHARS - 1));
+        assert_eq!(
+            truncate(&emoji),
+            format!("{}…", "x".repeat(MAX_VALUE_CHARS - 1))
+        );
+    }
+
+    #[test]
+    fn regions_get_a_table_of_their_own() {
+        let in_region = |name: &str, region: &str| {
+            let mut resource = ParsedResource::string(name, "x");
+            resource
+                .attributes
+                .insert(REGION.to_string(), region.to_string());
+            resource
+        };
+        let file = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![
+                in_region("pay", "Checkout flow"),
+                ParsedResource::string("title", "x"),
+                in_region("cards/visa", "Checkout flow"),
+                in_region("about", "About"),
+            ],
+        );
+        let graph = ResourceGraphBuilder::from_parsed_files(&[file]);
+        let header = "| Key | Kind | Value | Source | Doc |\n\
+                      | --- | --- | --- | --- | --- |\n";
+        let row = |key: &str| {
+            format!("| `{key}` | string | x | values.xml | |\n")
+        };
+        assert_eq!(
+            emit_markdown(&graph),
+            format!(
+                "# Resources\n\n## `r`\n\n{header}{}\n### About\n\n\
+                 {header}{}\n### Checkout flow\n\n{header}{}\n\
+                 ## `r::cards`\n\n### Checkout flow\n\n{header}{}",
+                row("title"),
+                row("about"),
+                row("pay"),
+                row("visa")
+            )
+        );
+    }
+}
@@ -4,57 +4,281 @@
 //! Currently supports:
 //! - Flat module generation (`r::` namespace structure)
 //!
+//! - Markdown documentation of the resources (`emit_markdown`)
+//! - The Rust paths generated for each key (`resource_paths`)
+//! - The resources using a key (`uses_report`)
+//! - What changed between two versions of the resources (`resource_diff`)
+//!
 //! Future generators can be added (e.g., hierarchical, JSON export, etc.)
 
+#[allow(dead_code)] // Used by the CLI, not by build.rs
+mod diff;
 mod flat;
+mod inputs;
+#[allow(dead_code)] // Used by the library and CLI, not by build.rs
+mod markdown;
+#[cfg(any(test, feature = "test-utils"))]
+mod snapshot;
+#[allow(dead_code)] // Used by the CLI, not by build.rs
+mod uses;
+
+use std::borrow::Cow;
+use std::fmt::Write;
+use std::path::Path;
 
 use crate::generator::analysis::{self, AnalysisError};
+use crate::generator::casing::NamingConfig;
 use crate::generator::ir::{ResourceGraph, TypeRegistry};
+use crate::generator::utils::pascal_case;
+
+#[allow(unused_imports)] // Public API, re-exported from the crate root
+pub use flat::{const_path_for, module_path_for};
+pub use flat::InternedStrings;
+#[allow(unused_imports)] // Public API, used by the CLI
+pub use flat::{stats_table, ResourceStats, StatsRow};
+pub use flat::{reordered_parameters, signatures_manifest, TemplateSignature};
+pub use inputs::InputFile;
+pub(crate) use inputs::inputs_header;
+#[allow(unused_imports)] // Public API, used by the CLI
+pub use flat::{paths_json, paths_table, resource_paths, ResourcePaths};
+#[allow(unused_imports)] // Public API, used by the CLI
+pub use markdown::emit_markdown;
+#[allow(unused_imports)] // Public API, used by the CLI
+pub use uses::{dependents, uses_report, Dependent};
+#[allow(unused_imports)] // Public API, used by the CLI
+pub use diff::{diff_report, resource_diff, ResourceChange};
+#[cfg(any(test, feature = "test-utils"))]
+#[allow(unused_imports)] // Public API, may be used by consumers
+pub use snapshot::assert_artifact_matches;
 
 pub struct OutputArtifacts {
     pub rust: String,
-    pub warnings: Vec<String>,
+    /// `pub mod r_tests` on its own, for `r_tests_generated.rs`; `None`
+    /// without test resources
+    pub tests_rust: Option<String>,
+    /// Warnings of the analysis and of the generation, with their code,
+    /// key, and file; [`OutputArtifacts::rendered_warnings`] formats them
+    pub warnings: Vec<analysis::AnalysisWarning>,
+    /// String values shared by interning; zero unless
+    /// `GenerationOptions::intern_strings`
+    pub interned_strings: InternedStrings,
+    /// Bytes of static data the resources contribute, by namespace and
+    /// kind; test resources left out
+    pub stats: ResourceStats,
+    /// Resource files the main artifact was generated from, in priority
+    /// order; listed at its top unless `BuildPlan::emit_inputs` is off
+    pub inputs: Vec<InputFile>,
+    /// Template functions and their parameters in order, written to
+    /// `r_signatures.txt` so the next build can spot reorderings
+    pub signatures: Vec<TemplateSignature>,
+}
+
+impl OutputArtifacts {
+    /// The warnings as printed, `[RR0001] Duplicate key ...`
+    #[allow(dead_code)] // Public API, may be used by consumers
+    pub fn rendered_warnings(&self) -> Vec<String> {
+        self.warnings.iter().map(ToString::to_string).collect()
+    }
+
+    /// Generated code and warnings in a form stable across machines, for
+    /// snapshot tests.
+    ///
+    /// Absolute paths of existing files are reduced to `[path]/<file name>`,
+    /// line endings and trailing whitespace are normalized, and warnings are
+    /// sorted. The generator emits no timestamps, so nothing else varies.
+    #[allow(dead_code)] // Public API for snapshot tests
+    pub fn normalized(&self) -> String {
+        let mut warnings: Vec<String> = self
+            .rendered_warnings()
+            .iter()
+            .map(|warning| normalize_paths(warning))
+            .collect();
+        warnings.sort();
+
+        let mut text = String::from("// --- rust ---\n");
+        for line in self.rust.lines() {
+            text.push_str(normalize_paths(line).trim_end());
+            text.push('\n');
+        }
+        text.push_str("// --- warnings ---\n");
+        for warning in warnings {
+            text.push_str(&warning);
+            text.push('\n');
+        }
+        text
+    }
+}
+
+/// Replaces absolute paths of existing files with `[path]/<file name>`
+fn normalize_paths(text: &str) -> String {
+    let is_path_char = |c: char| {
+        c.is_alphanumeric() || matches!(c, '/' | '\\' | '.' | '_' | '-' | ':')
+    };
+    let mut normalized = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let len = rest.find(|c| !is_path_char(c)).unwrap_or(rest.len());
+        if len == 0 {
+            normalized.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        normalized.push_str(&normalize_path_token(&rest[..len]));
+        rest = &rest[len..];
+    }
+    normalized
+}
+
+fn normalize_path_token(token: &str) -> Cow<'_, str> {
+    // A path ending a sentence keeps its period
+    let path = token.trim_end_matches('.');
+    let candidate = Path::new(path);
+    let file_name = candidate.file_name().map(|name| name.to_string_lossy());
+    match file_name {
+        Some(name) if candidate.is_absolute() && candidate.exists() => {
+            Cow::Owned(format!("[path]/{name}{}", &token[path.len()..]))
+        }
+        _ => Cow::Borrowed(token),
+    }
+}
+
+/// Generation options
+#[derive(Debug, Clone)]
+pub struct GenerationOptions {
+    /// If true, a `#[cfg(test)]` module touching every resource is appended
+    pub emit_smoke_tests: bool,
+    /// If true, `r::to_json()` returns the scalar resources as JSON
+    pub emit_to_json: bool,
+    /// If true, `r::meta` holds resource counts and namespace names
+    pub emit_meta: bool,
+    /// If true, lookup tables from resource keys to values are emitted,
+    /// starting with `r::colors::by_name`
+    pub emit_lookup: bool,
+    /// If true, `string`, `color`, ... next to `r` define every resource
+    /// of their kind under its namespace path, and `r` re-exports them
+    pub emit_kind_modules: bool,
+    /// If true, each constant gets a zero-sized marker type implementing
+    /// `r_resources::Resource`
+    pub emit_resource_markers: bool,
+    /// Case of the generated constants, modules, and functions
+    pub naming: NamingConfig,
+    /// If true, a string value shared by several constants is emitted once,
+    /// as a private static the constants name
+    pub intern_strings: bool,
+    /// If true, the items of each namespace module are ordered by origin
+    /// file, then name, with a comment naming each file
+    pub group_by_origin: bool,
+    /// If true, the `r_tests` module is also part of the main artifact
+    pub embed_test_resources: bool,
+    /// Name of the root module; every other top-level item is derived from
+    /// it so two artifacts with different roots do not collide
+    pub root_module: String,
+}
+
+impl Default for GenerationOptions {
+    fn default() -> Self {
+        Self {
+            emit_smoke_tests: false,
+            emit_to_json: false,
+            emit_meta: false,
+            emit_lookup: false,
+            emit_kind_modules: false,
+            emit_resource_markers: false,
+            naming: NamingConfig::default(),
+            intern_strings: false,
+            group_by_origin: false,
+            embed_test_resources: false,
+            root_module: "r".to_string(),
+        }
+    }
 }
 
 pub fn emit(
     graph: &ResourceGraph,
     analysis_warnings: &[analysis::AnalysisWarning],
+    registry: &TypeRegistry,
+    options: GenerationOptions,
 ) -> Result<OutputArtifacts, Vec<AnalysisError>> {
-    let registry = TypeRegistry::default();
+    let mut errors = flat::reserved_namespace_errors(graph, &options);
+    if options.emit_resource_markers {
+        errors.extend(flat::marker_errors(graph, registry, &options.naming));
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    let root = options.root_module.as_str();
     let mut rust_code = String::new();
 
-    // Generate main R struct
-    rust_code.push_str(
+    // Generate main R struct, named after the root module (`r` → `R`)
+    let root_struct = pascal_case(root);
+    let _ = write!(
+        rust_code,
         r#"
-pub struct R;
+pub struct {root_struct};
 
-impl Default for R {
-    fn default() -> Self {
+impl Default for {root_struct} {{
+    fn default() -> Self {{
         Self::new()
-    }
-}
+    }}
+}}
 
-impl R {
+impl {root_struct} {{
     #[must_use]
-    pub const fn new() -> Self {
+    pub const fn new() -> Self {{
         Self
-    }
-}
+    }}
+}}
 "#,
     );
 
     // Generate flat r:: module with duplicate warnings
-    rust_code.push_str(&flat::generate_r_module(
+    let (module, generation_warnings) =
+        flat::generate_r_module(graph, registry, analysis_warnings, &options);
+    rust_code.push_str(&module);
+    let mut warnings = analysis_warnings.to_vec();
+    warnings.extend(generation_warnings);
+
+    let tests_module = flat::generate_r_tests_module(
         graph,
-        &registry,
+        registry,
         analysis_warnings,
-    ));
+        &options,
+    );
+    let tests_rust = tests_module.map(|(code, tests_warnings)| {
+        warnings.extend(tests_warnings);
+        code
+    });
+    let embed_tests = options.embed_test_resources && tests_rust.is_some();
+    if let Some(code) = tests_rust.as_ref() {
+        if embed_tests {
+            rust_code.push_str(code);
+        }
+    }
+    if options.emit_smoke_tests {
+        rust_code.push_str(&flat::generate_smoke_tests(
+            graph,
+            registry,
+            root,
+            embed_tests,
+            &options.naming,
+        ));
+    }
 
+    let interned_strings = if options.intern_strings {
+        flat::interned_strings(graph, registry, &options.naming)
+    } else {
+        InternedStrings::default()
+    };
+    let stats = flat::resource_stats(graph, registry, root, &options.naming);
+    let signatures =
+        flat::template_signatures(graph, registry, root, &options.naming);
     Ok(OutputArtifacts {
         rust: rust_code,
-        warnings: analysis_warnings
-            .iter()
-            .map(|w| w.message.clone())
-            .collect(),
+        tests_rust,
+        warnings,
+        interned_strings,
+        stats,
+        inputs: Vec::new(),
+        signatures,
     })
 }
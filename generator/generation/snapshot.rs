@@ -0,0 +1,170 @@
+//! Golden-file snapshots of generated artifacts (`test-utils` feature).
+
+use std::path::Path;
+
+use super::OutputArtifacts;
+
+/// Lines of unchanged context shown around each change
+const CONTEXT: usize = 3;
+
+/// Compares `artifacts.normalized()` with the snapshot at `path`.
+///
+/// With `UPDATE_SNAPSHOTS=1` the snapshot is (re)written instead. Panics with
+/// a unified diff when they differ, or when the snapshot does not exist.
+#[allow(dead_code)] // Public API for snapshot tests
+pub fn assert_artifact_matches(path: &Path, artifacts: &OutputArtifacts) {
+    let actual = artifacts.normalized();
+    if std::env::var("UPDATE_SNAPSHOTS").is_ok_and(|v| v == "1") {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .expect("failed to create snapshot directory");
+        }
+        std::fs::write(path, actual).expect("failed to write snapshot");
+        return;
+    }
+    let Ok(expected) = std::fs::read_to_string(path) else {
+        panic!(
+            "snapshot {} does not exist; run with UPDATE_SNAPSHOTS=1 to create it",
+            path.display()
+        );
+    };
+    if expected != actual {
+        panic!(
+            "snapshot {} does not match (run with UPDATE_SNAPSHOTS=1 to accept):\n{}",
+            path.display(),
+            unified_diff(&expected, &actual)
+        );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Line<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Line diff in unified format (`@@ -a,b +c,d @@` hunks)
+fn unified_diff(expected: &str, actual: &str) -> String {
+    let lines = diff_lines(expected, actual);
+    let changed: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Line::Same(_)))
+        .map(|(index, _)| index)
+        .collect();
+
+    let mut output = String::from("--- expected\n+++ actual\n");
+    let mut hunk_start = 0;
+    while hunk_start < changed.len() {
+        // Changes closer than twice the context share a hunk
+        let mut hunk_end = hunk_start;
+        while hunk_end + 1 < changed.len()
+            && changed[hunk_end + 1] - changed[hunk_end] <= 2 * CONTEXT
+        {
+            hunk_end += 1;
+        }
+        let first = changed[hunk_start].saturating_sub(CONTEXT);
+        let last = (changed[hunk_end] + CONTEXT).min(lines.len() - 1);
+        write_hunk(&mut output, &lines, first, last);
+        hunk_start = hunk_end + 1;
+    }
+    output
+}
+
+fn write_hunk(
+    output: &mut String,
+    lines: &[Line<'_>],
+    first: usize,
+    last: usize,
+) {
+    let counts = |lines: &[Line<'_>], removed: bool| {
+        lines
+            .iter()
+            .filter(|line| match line {
+                Line::Same(_) => true,
+                Line::Removed(_) => removed,
+                Line::Added(_) => !removed,
+            })
+            .count()
+    };
+    let old_start = counts(&lines[..first], true) + 1;
+    let new_start = counts(&lines[..first], false) + 1;
+    let hunk = &lines[first..=last];
+    output.push_str(&format!(
+        "@@ -{old_start},{} +{new_start},{} @@\n",
+        counts(hunk, true),
+        counts(hunk, false)
+    ));
+    for line in hunk {
+        let (prefix, text) = match line {
+            Line::Same(text) => (' ', text),
+            Line::Removed(text) => ('-', text),
+            Line::Added(text) => ('+', text),
+        };
+        output.push(prefix);
+        output.push_str(text);
+        output.push('\n');
+    }
+}
+
+/// Longest-common-subsequence line diff
+fn diff_lines<'a>(expected: &'a str, actual: &'a str) -> Vec<Line<'a>> {
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+
+    // lcs[i][j]: length of the LCS of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(Line::Same(old[i]));
+            (i, j) = (i + 1, j + 1);
+        } else if i < old.len()
+            && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1])
+        {
+            lines.push(Line::Removed(old[i]));
+            i += 1;
+        } else {
+            lines.push(Line::Added(new[j]));
+            j += 1;
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unified_diff_shows_changes_with_context() {
+        let expected = "a\nb\nc\nd\ne\nf\ng\nh\n";
+        let actual = "a\nb\nc\nD\ne\nf\ng\nh\ni\n";
+        assert_eq!(
+            unified_diff(expected, actual),
+            "--- expected\n+++ actual\n\
+             @@ -1,8 +1,9 @@\n a\n b\n c\n-d\n+D\n e\n f\n g\n h\n+i\n"
+        );
+    }
+
+    #[test]
+    fn distant_changes_get_separate_hunks() {
+        let expected = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+        let actual = "x\n2\n3\n4\n5\n6\n7\n8\n9\ny\n";
+        let diff = unified_diff(expected, actual);
+        assert!(diff.contains("@@ -1,4 +1,4 @@\n-1\n+x\n 2\n 3\n 4\n"));
+        assert!(diff.contains("@@ -7,4 +7,4 @@\n 7\n 8\n 9\n-10\n+y\n"));
+    }
+}
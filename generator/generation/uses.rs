@@ -0,0 +1,177 @@
+//! What uses a resource (`r-resources uses auth/title`): every key whose
+//! value references, interpolates, lists, or calls it, directly or through
+//! another user, so a resource can be deleted knowing what breaks.
+
+use std::collections::VecDeque;
+use std::fmt::Write;
+
+use crate::generator::analysis::references::nearest_key;
+use crate::generator::ir::{ResourceGraph, ResourceKey};
+
+/// A resource depending on the one asked about
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependent {
+    pub key: ResourceKey,
+    /// File of its first definition
+    pub file: String,
+    /// The resource it names, itself a dependent; `None` when it names
+    /// the resource asked about
+    pub through: Option<ResourceKey>,
+}
+
+/// Resources depending on `key`: the direct ones first, then those
+/// depending on them, each listed once. `graph` must be indexed, as the
+/// pipeline's is.
+pub fn dependents(graph: &ResourceGraph, key: &ResourceKey) -> Vec<Dependent> {
+    let mut found: Vec<Dependent> = Vec::new();
+    let mut queue = VecDeque::from([key.clone()]);
+    while let Some(used) = queue.pop_front() {
+        let new: Vec<&ResourceKey> = graph
+            .dependents_of(&used)
+            .iter()
+            .filter(|user| *user != key && !found.iter().any(|d| &d.key == *user))
+            .collect();
+        for user in new {
+            found.push(Dependent {
+                key: user.clone(),
+                file: graph
+                    .get(user)
+                    .map(|node| node.origin.file.display().to_string())
+                    .unwrap_or_default(),
+                through: (&used != key).then(|| used.clone()),
+            });
+            queue.push_back(user.clone());
+        }
+    }
+    found
+}
+
+/// The dependents of `key` as text, one per line; an error naming the
+/// closest key when `key` does not exist
+pub fn uses_report(
+    graph: &ResourceGraph,
+    key: &ResourceKey,
+) -> Result<String, String> {
+    let name = key.full_name();
+    if graph.get(key).is_none() {
+        let hint = nearest_key(graph, key)
+            .map(|near| format!("; did you mean '{}'?", near.full_name()))
+            .unwrap_or_default();
+        return Err(format!("no resource '{name}'{hint}"));
+    }
+    let found = dependents(graph, key);
+    if found.is_empty() {
+        return Ok(format!("'{name}' is not used by any resource\n"));
+    }
+    let width = found
+        .iter()
+        .map(|d| d.key.full_name().len())
+        .max()
+        .unwrap_or(0);
+    let count = match found.len() {
+        1 => "1 resource".to_string(),
+        n => format!("{n} resources"),
+    };
+    let mut report = format!("'{name}' is used by {count}:\n");
+    for dependent in &found {
+        let _ = write!(
+            report,
+            "    {:width$}  {}",
+            dependent.key.full_name(),
+            dependent.file
+        );
+        if let Some(through) = &dependent.through {
+            let _ = write!(report, "  (through {})", through.full_name());
+        }
+        report.push('\n');
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::analysis::references::index_dependents;
+    use crate::generator::input::RawResourceFile;
+    use crate::generator::ir::ResourceGraphBuilder;
+    use crate::generator::parsing::parse_raw_files;
+    use std::path::PathBuf;
+
+    fn graph(files: &[(&str, &str)]) -> ResourceGraph {
+        let raw: Vec<RawResourceFile> = files
+            .iter()
+            .map(|(path, xml)| {
+                RawResourceFile::new(
+                    PathBuf::from(path),
+                    xml.to_string(),
+                    false,
+                )
+            })
+            .collect();
+        let parsed = parse_raw_files(&raw, &Default::default()).unwrap();
+        let mut graph = ResourceGraphBuilder::from_parsed_files(&parsed);
+        index_dependents(&mut graph);
+        graph
+    }
+
+    #[test]
+    fn lists_direct_and_indirect_users() {
+        let graph = graph(&[
+            (
+                "auth.xml",
+                r#"<resources><ns name="auth">
+                    <string name="title">Sign in</string>
+                    <string name="header">@string/auth/title</string>
+                    <string name="hint">Use @string/auth/header to continue</string>
+                    <string-array name="labels"><item>@string/auth/title</item></string-array>
+                </ns></resources>"#,
+            ),
+            (
+                "mail.xml",
+                r#"<resources>
+                    <template name="footer">{@welcome} (auth)</template>
+                    <template name="welcome">Welcome!</template>
+                    <string name="other">Unrelated</string>
+                </resources>"#,
+            ),
+        ]);
+        let title = ResourceKey::from_path("auth/title");
+        assert_eq!(
+            graph.dependents_of(&title),
+            [
+                ResourceKey::from_path("auth/header"),
+                ResourceKey::from_path("auth/labels"),
+            ]
+        );
+        assert_eq!(
+            uses_report(&graph, &title).unwrap(),
+            "'auth/title' is used by 3 resources:\n    auth/header  auth.xml\n    auth/labels  auth.xml\n    auth/hint    auth.xml  (through auth/header)\n"
+        );
+
+        let welcome = ResourceKey::from_path("welcome");
+        assert_eq!(
+            graph.dependents_of(&welcome),
+            [ResourceKey::from_path("footer")]
+        );
+        assert_eq!(
+            uses_report(&graph, &ResourceKey::from_path("other")).unwrap(),
+            "'other' is not used by any resource\n"
+        );
+    }
+
+    #[test]
+    fn missing_keys_suggest_the_closest_one() {
+        let graph = graph(&[(
+            "values.xml",
+            r#"<resources><ns name="auth"><string name="title">A</string></ns></resources>"#,
+        )]);
+        assert_eq!(
+            uses_report(&graph, &ResourceKey::from_path("auth/tilte")),
+            Err("no resource 'auth/tilte'; did you mean 'auth/title'?".to_string())
+        );
+        assert_eq!(
+            uses_report(&graph, &ResourceKey::from_path("billing")),
+            Err("no resource 'billing'".to_string())
+        );
+    }
+}
@@ -0,0 +1,155 @@
+//! Legacy encodings: files are read as bytes and transcoded to UTF-8
+//! before parsing, following the encoding their XML declaration names
+//! (`<?xml version="1.0" encoding="ISO-8859-1"?>`), or
+//! `BuildPlan::default_encoding` without one.
+//!
+//! UTF-8 (with or without a byte order mark), ASCII, ISO-8859-1, and
+//! Windows-1252 are understood. As browsers do, ISO-8859-1 is read as
+//! Windows-1252, its superset: files labelled Latin-1 often hold `€` or
+//! curly quotes in the 0x80–0x9F range.
+
+/// Why the bytes of a file could not be read as text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum DecodeError {
+    /// The declared or default encoding is not one of the supported ones
+    Unknown(String),
+    /// The file is read as UTF-8 but is not; `offset` is the first byte
+    /// that is not
+    InvalidUtf8 { offset: usize },
+}
+
+/// An encoding a resource file may be written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    Utf8,
+    Ascii,
+    Windows1252,
+}
+
+impl Encoding {
+    /// The encoding named `label`, ignoring case: `UTF-8`, `latin1`,
+    /// `ISO-8859-1`, `cp1252`, ...
+    fn from_label(label: &str) -> Option<Self> {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "utf-8" | "utf8" => Some(Self::Utf8),
+            "us-ascii" | "ascii" => Some(Self::Ascii),
+            "iso-8859-1" | "iso8859-1" | "iso_8859-1" | "latin1"
+            | "latin-1" | "l1" | "cp819" | "windows-1252" | "cp1252"
+            | "x-cp1252" => Some(Self::Windows1252),
+            _ => None,
+        }
+    }
+}
+
+/// Characters of Windows-1252 bytes 0x80–0x9F; the five bytes it leaves
+/// undefined keep their C1 control character
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{81}', '\u{201A}', '\u{192}', '\u{201E}', '\u{2026}',
+    '\u{2020}', '\u{2021}', '\u{2C6}', '\u{2030}', '\u{160}', '\u{2039}',
+    '\u{152}', '\u{8D}', '\u{17D}', '\u{8F}', '\u{90}', '\u{2018}',
+    '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{2DC}', '\u{2122}', '\u{161}', '\u{203A}', '\u{153}', '\u{9D}',
+    '\u{17E}', '\u{178}',
+];
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// `bytes` as UTF-8 text, transcoded from the encoding their declaration
+/// names, else from `default_encoding`, else taken as UTF-8
+pub(super) fn decode(
+    bytes: Vec<u8>,
+    default_encoding: Option<&str>,
+) -> Result<String, DecodeError> {
+    if bytes.starts_with(b"\xFF\xFE") || bytes.starts_with(b"\xFE\xFF") {
+        return Err(DecodeError::Unknown("UTF-16".to_string()));
+    }
+    let has_bom = bytes.starts_with(UTF8_BOM);
+    // A byte order mark is the file's own say, so it outranks both
+    let label = declared_encoding(&bytes).or(default_encoding);
+    let encoding = match label {
+        Some(label) if !has_bom => Encoding::from_label(label)
+            .ok_or_else(|| DecodeError::Unknown(label.to_string()))?,
+        _ => Encoding::Utf8,
+    };
+    match encoding {
+        Encoding::Utf8 | Encoding::Ascii => {
+            let mut text = String::from_utf8(bytes).map_err(|err| {
+                DecodeError::InvalidUtf8 {
+                    offset: err.utf8_error().valid_up_to(),
+                }
+            })?;
+            if has_bom {
+                text.drain(..'\u{FEFF}'.len_utf8());
+            }
+            Ok(text)
+        }
+        Encoding::Windows1252 => Ok(bytes
+            .iter()
+            .map(|&byte| match byte {
+                0x80..=0x9F => WINDOWS_1252_HIGH[usize::from(byte - 0x80)],
+                _ => char::from(byte),
+            })
+            .collect()),
+    }
+}
+
+/// The `encoding` of the XML declaration opening `bytes`, if any. The
+/// declaration is ASCII in every supported encoding, so it is read before
+/// knowing which one applies.
+fn declared_encoding(bytes: &[u8]) -> Option<&str> {
+    let bytes = bytes.strip_prefix(UTF8_BOM).unwrap_or(bytes);
+    let rest = bytes.strip_prefix(b"<?xml")?;
+    let end = rest.windows(2).position(|pair| pair == b"?>")?;
+    let declaration = std::str::from_utf8(&rest[..end]).ok()?;
+    let (_, value) = declaration.split_once("encoding")?;
+    let value = value.trim_start().strip_prefix('=')?.trim_start();
+    let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let value = &value[1..];
+    Some(&value[..value.find(quote)?])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transcodes_declared_and_default_encodings() {
+        let latin1 =
+            b"<?xml version=\"1.0\" encoding='ISO-8859-1'?><s>Caf\xE9 \x80</s>";
+        assert_eq!(
+            decode(latin1.to_vec(), None).unwrap(),
+            "<?xml version=\"1.0\" encoding='ISO-8859-1'?><s>Café €</s>"
+        );
+        assert_eq!(
+            decode(b"<s>\xE0 bient\xF4t</s>".to_vec(), Some("windows-1252")),
+            Ok("<s>à bientôt</s>".to_string())
+        );
+        // The declaration wins over the default
+        let utf8 = "<?xml version=\"1.0\" encoding=\"UTF-8\"?><s>é</s>";
+        assert_eq!(
+            decode(utf8.as_bytes().to_vec(), Some("latin1")),
+            Ok(utf8.to_string())
+        );
+        assert_eq!(
+            decode(b"\xEF\xBB\xBF<s>\xC3\xA9</s>".to_vec(), Some("latin1")),
+            Ok("<s>é</s>".to_string())
+        );
+    }
+
+    #[test]
+    fn reports_unknown_encodings_and_invalid_utf8() {
+        let shift_jis = b"<?xml version=\"1.0\" encoding=\"Shift_JIS\"?><s/>";
+        assert_eq!(
+            decode(shift_jis.to_vec(), None),
+            Err(DecodeError::Unknown("Shift_JIS".to_string()))
+        );
+        assert_eq!(
+            decode(b"\xFF\xFE<\0".to_vec(), None),
+            Err(DecodeError::Unknown("UTF-16".to_string()))
+        );
+        assert_eq!(
+            decode(b"<s>Caf\xE9</s>".to_vec(), None),
+            Err(DecodeError::InvalidUtf8 { offset: 6 })
+        );
+    }
+}
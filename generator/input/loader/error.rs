@@ -2,11 +2,37 @@ use std::fmt;
 use std::io;
 use std::path::PathBuf;
 
+use super::profile::PreprocessError;
+
 #[derive(Debug)]
 pub enum LoaderError {
     MissingDirectory(PathBuf),
     Io { path: PathBuf, source: io::Error },
+    /// A symlink in the resource directory whose target cannot be read
+    Symlink {
+        path: PathBuf,
+        target: PathBuf,
+        source: io::Error,
+    },
     NoXmlFilesFound { searched: PathBuf },
+    /// The file declares an encoding (or `BuildPlan::default_encoding`
+    /// names one) that cannot be read
+    UnknownEncoding { path: PathBuf, encoding: String },
+    /// The file is read as UTF-8 but is not; `offset` is the first byte
+    /// that is not
+    InvalidUtf8 { path: PathBuf, offset: usize },
+    /// The file is not well-formed, so `profile` attributes could not be
+    /// applied
+    Preprocess {
+        path: PathBuf,
+        source: PreprocessError,
+    },
+    /// A `BuildPlan::tag_aliases` entry names a tag no type handles
+    UnknownAliasTarget { alias: String, target: String },
+    /// `BuildPlan::root_module` is not a lowercase Rust identifier
+    InvalidRootModule(String),
+    /// A `BuildPlan::extra_sets` name cannot become its own root module
+    InvalidExtraSet { name: String, reason: &'static str },
 }
 
 impl fmt::Display for LoaderError {
@@ -26,6 +52,18 @@ impl fmt::Display for LoaderError {
                     path.display()
                 )
             }
+            Self::Symlink {
+                path,
+                target,
+                source,
+            } => {
+                write!(
+                    f,
+                    "failed to read '{}' (symlink to '{}'): {source}",
+                    path.display(),
+                    target.display()
+                )
+            }
             Self::NoXmlFilesFound { searched } => {
                 write!(
                     f,
@@ -33,6 +71,46 @@ impl fmt::Display for LoaderError {
                     searched.display()
                 )
             }
+            Self::UnknownEncoding { path, encoding } => {
+                write!(
+                    f,
+                    "'{}' is encoded as '{encoding}', which is not supported; \
+                     use UTF-8, ISO-8859-1, or Windows-1252",
+                    path.display()
+                )
+            }
+            Self::InvalidUtf8 { path, offset } => {
+                write!(
+                    f,
+                    "'{}' is not valid UTF-8 (byte {offset}); declare its \
+                     encoding, e.g. <?xml version=\"1.0\" \
+                     encoding=\"ISO-8859-1\"?>, or set \
+                     BuildPlan::default_encoding",
+                    path.display()
+                )
+            }
+            Self::Preprocess { path, source } => {
+                write!(
+                    f,
+                    "cannot filter '{}' by profile: {source}",
+                    path.display()
+                )
+            }
+            Self::UnknownAliasTarget { alias, target } => {
+                write!(
+                    f,
+                    "tag alias '{alias}' points to unknown type '{target}'"
+                )
+            }
+            Self::InvalidRootModule(name) => {
+                write!(
+                    f,
+                    "root module '{name}' is not a lowercase Rust identifier"
+                )
+            }
+            Self::InvalidExtraSet { name, reason } => {
+                write!(f, "extra resource set '{name}' {reason}")
+            }
         }
     }
 }
@@ -40,7 +118,10 @@ impl fmt::Display for LoaderError {
 impl std::error::Error for LoaderError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            Self::Io { source, .. } => Some(source),
+            Self::Io { source, .. } | Self::Symlink { source, .. } => {
+                Some(source)
+            }
+            Self::Preprocess { source, .. } => Some(source),
             _ => None,
         }
     }
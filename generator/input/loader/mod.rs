@@ -1,81 +1,216 @@
+mod encoding;
 mod error;
 mod profile;
 mod raw_file;
 mod scan;
 
 pub use error::LoaderError;
+pub use profile::ProfileSkip;
 pub use raw_file::RawResourceFile;
 
 use std::fs;
 use std::path::Path;
 
 use super::BuildPlan;
-use scan::collect_xml_files;
+use crate::generator::ir::types::KEYWORDS;
+use encoding::{decode, DecodeError};
+use scan::{collect_xml_files, ScanSkip, ScannedFile};
 
 /// Loads every XML file defined in the build plan, applying profile preprocessing.
+///
+/// Files are named relative to the directory holding the resource root
+/// (`res/values.xml`, `res/tests/values.xml`), so diagnostics tell apart
+/// files of the same name without printing the whole checkout path. Files
+/// outside that directory keep their full path.
+#[allow(dead_code)] // Public API, may be used by consumers
 pub fn load_resources(
     plan: &BuildPlan,
 ) -> Result<Vec<RawResourceFile>, LoaderError> {
-    let mut files = load_directory(
+    load_resources_with_notes(plan).map(|(files, _)| files)
+}
+
+/// Like [`load_resources`], with a note for each `.xml` entry left out:
+/// hidden files, dangling symlinks, and entries that are not regular files
+pub fn load_resources_with_notes(
+    plan: &BuildPlan,
+) -> Result<(Vec<RawResourceFile>, Vec<String>), LoaderError> {
+    check_root_module(&plan.root_module)?;
+    check_extra_sets(plan)?;
+    check_tag_aliases(plan)?;
+    let base = plan.resources_dir.parent();
+    let (mut files, mut skipped) = load_directory(
         &plan.resources_dir,
         false,
-        &plan.profile,
-        true, /* strict */
+        plan,
+        !plan.allow_missing_resources,
     )?;
 
     if let Some(tests_dir) = &plan.tests_resources_dir {
         if tests_dir.exists() {
-            let mut test_files = load_directory(
-                tests_dir,
-                true,
-                &plan.profile,
-                false, /* not strict */
+            let (mut test_files, mut test_skipped) = load_directory(
+                tests_dir, true, plan, false, /* not strict */
             )?;
             files.append(&mut test_files);
+            skipped.append(&mut test_skipped);
         }
     }
 
-    Ok(files)
+    let relative = |path: &Path| {
+        base.and_then(|base| path.strip_prefix(base).ok())
+            .unwrap_or(path)
+            .to_path_buf()
+    };
+    for file in &mut files {
+        file.path = relative(&file.path);
+    }
+    let notes = skipped
+        .iter()
+        .map(|skip| skip.note(&relative(&skip.path)))
+        .collect();
+    Ok((files, notes))
 }
 
+/// Warning for a lenient plan whose resource directory is missing or has no
+/// XML files; `None` when there is something to load
+pub fn missing_resources_warning(plan: &BuildPlan) -> Option<String> {
+    let dir = &plan.resources_dir;
+    if !dir.exists() {
+        return Some(format!(
+            "Resource directory '{}' does not exist; generating an empty R",
+            dir.display()
+        ));
+    }
+    match collect_xml_files(dir, plan.include_hidden_files) {
+        Ok(scan) if scan.files.is_empty() => Some(format!(
+            "No XML files found in '{}'; generating an empty R",
+            dir.display()
+        )),
+        _ => None,
+    }
+}
+
+/// Aliases must point to a tag some registered type handles
+fn check_tag_aliases(plan: &BuildPlan) -> Result<(), LoaderError> {
+    let registry = plan.type_registry();
+    for (alias, target) in &plan.tag_aliases {
+        if registry.find_by_xml_tag(target).is_none() {
+            return Err(LoaderError::UnknownAliasTarget {
+                alias: alias.clone(),
+                target: target.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// The root module name must be usable as is: `snake_case` starting with a
+/// letter, and not a keyword
+pub(crate) fn check_root_module(name: &str) -> Result<(), LoaderError> {
+    let valid = name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_')
+        && !KEYWORDS.contains(&name);
+    if valid {
+        Ok(())
+    } else {
+        Err(LoaderError::InvalidRootModule(name.to_string()))
+    }
+}
+
+/// Each extra set needs a root module of its own: `<root>_<name>` must be
+/// a valid name, and neither the test module nor another set's
+fn check_extra_sets(plan: &BuildPlan) -> Result<(), LoaderError> {
+    for (index, (name, _)) in plan.extra_sets.iter().enumerate() {
+        let reason = if name == "tests" {
+            Some("would take the root module of the test resources")
+        } else if plan.extra_sets[..index].iter().any(|(n, _)| n == name) {
+            Some("is listed twice")
+        } else if name.is_empty()
+            || check_root_module(&format!("{}_{name}", plan.root_module))
+                .is_err()
+        {
+            Some("is not a lowercase Rust identifier")
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            return Err(LoaderError::InvalidExtraSet {
+                name: name.clone(),
+                reason,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// The files of `dir`, and the `.xml` entries the scan left out
 fn load_directory(
     dir: &Path,
     is_test: bool,
-    profile: &str,
+    plan: &BuildPlan,
     strict: bool,
-) -> Result<Vec<RawResourceFile>, LoaderError> {
+) -> Result<(Vec<RawResourceFile>, Vec<ScanSkip>), LoaderError> {
     if !dir.exists() {
         if strict {
             return Err(LoaderError::MissingDirectory(
                 dir.to_path_buf(),
             ));
         }
-        return Ok(Vec::new());
+        return Ok((Vec::new(), Vec::new()));
     }
 
-    let xml_paths = collect_xml_files(dir)?;
-    if xml_paths.is_empty() {
-        if strict {
-            return Err(LoaderError::NoXmlFilesFound {
-                searched: dir.to_path_buf(),
-            });
-        }
-        return Ok(Vec::new());
+    let scan = collect_xml_files(dir, plan.include_hidden_files)?;
+    if scan.files.is_empty() && strict {
+        return Err(LoaderError::NoXmlFilesFound {
+            searched: dir.to_path_buf(),
+        });
     }
 
-    let mut loaded = Vec::with_capacity(xml_paths.len());
-    for path in xml_paths {
-        let raw = fs::read_to_string(&path).map_err(|source| {
-            LoaderError::Io {
+    let mut loaded = Vec::with_capacity(scan.files.len());
+    for ScannedFile { path, target } in scan.files {
+        let bytes = fs::read(&path).map_err(|source| match target {
+            Some(target) => LoaderError::Symlink {
+                path: path.clone(),
+                target,
+                source,
+            },
+            None => LoaderError::Io {
                 path: path.clone(),
                 source,
-            }
+            },
         })?;
-        let filtered = profile::preprocess_xml(&raw, profile);
-        loaded.push(RawResourceFile::new(path, filtered, is_test));
+        let raw = decode(bytes, plan.default_encoding.as_deref()).map_err(
+            |err| match err {
+                DecodeError::Unknown(encoding) => {
+                    LoaderError::UnknownEncoding {
+                        path: path.clone(),
+                        encoding,
+                    }
+                }
+                DecodeError::InvalidUtf8 { offset } => {
+                    LoaderError::InvalidUtf8 {
+                        path: path.clone(),
+                        offset,
+                    }
+                }
+            },
+        )?;
+        let preprocessed =
+            profile::preprocess_xml(&raw, &plan.profile).map_err(|source| {
+                LoaderError::Preprocess {
+                    path: path.clone(),
+                    source,
+                }
+            })?;
+        loaded.push(
+            RawResourceFile::new(path, preprocessed.xml, is_test)
+                .with_source(raw)
+                .with_skipped(preprocessed.skipped),
+        );
     }
 
-    Ok(loaded)
+    Ok((loaded, scan.skipped))
 }
 
 #[cfg(test)]
@@ -100,6 +235,66 @@ mod tests {
         assert!(matches!(err, LoaderError::MissingDirectory(_)));
     }
 
+    #[test]
+    fn lenient_plan_loads_nothing_from_missing_or_empty_dir() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        let mut plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        plan.allow_missing_resources = true;
+
+        assert!(load_resources(&plan).unwrap().is_empty());
+        let warning = missing_resources_warning(&plan).unwrap();
+        assert!(warning.contains("does not exist"));
+
+        write_file(&res_dir.join("notes.txt"), "not xml");
+        assert!(load_resources(&plan).unwrap().is_empty());
+        let warning = missing_resources_warning(&plan).unwrap();
+        assert!(warning.starts_with("No XML files found"));
+
+        write_file(&res_dir.join("values.xml"), "<resources/>");
+        assert!(missing_resources_warning(&plan).is_none());
+    }
+
+    #[test]
+    fn fails_on_alias_to_unknown_type() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(&res_dir.join("values.xml"), "<resources/>");
+        let mut plan = BuildPlan::new(res_dir, None, "debug");
+        plan.tag_aliases = vec![
+            ("message".to_string(), "string".to_string()),
+            ("count".to_string(), "nubmer".to_string()),
+        ];
+
+        let err = load_resources(&plan).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "tag alias 'count' points to unknown type 'nubmer'"
+        );
+    }
+
+    #[test]
+    fn extra_sets_need_a_root_module_of_their_own() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(&res_dir.join("values.xml"), "<resources/>");
+        let mut plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let set = |name: &str| (name.to_string(), res_dir.join(name));
+
+        plan.extra_sets = vec![set("bench"), set("fixtures")];
+        assert!(load_resources(&plan).is_ok());
+        plan.extra_sets = vec![set("bench"), set("bench")];
+        let err = load_resources(&plan).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "extra resource set 'bench' is listed twice"
+        );
+        plan.extra_sets = vec![set("Bench")];
+        let err = load_resources(&plan).err().unwrap();
+        let message = err.to_string();
+        assert!(message.ends_with("is not a lowercase Rust identifier"));
+    }
+
     #[test]
     fn load_files_and_apply_profile_filtering() {
         let tmp = tempdir().unwrap();
@@ -149,6 +344,66 @@ mod tests {
         assert!(test_file.contents.contains("test_only"));
     }
 
+    #[test]
+    fn files_are_named_relative_to_the_resource_root_parent() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(&res_dir.join("values.xml"), "<resources/>");
+        write_file(&res_dir.join("tests").join("values.xml"), "<resources/>");
+        let elsewhere = tempdir().unwrap();
+        write_file(&elsewhere.path().join("extra.xml"), "<resources/>");
+
+        let tests_dir = res_dir.join("tests");
+        let plan = BuildPlan::new(res_dir, Some(tests_dir), "debug");
+        let paths: Vec<_> = load_resources(&plan)
+            .unwrap()
+            .into_iter()
+            .map(|file| file.path)
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                Path::new("res").join("values.xml"),
+                Path::new("res").join("tests").join("values.xml"),
+            ]
+        );
+
+        // A tests directory outside keeps its full path
+        let outside = elsewhere.path().to_path_buf();
+        let plan = BuildPlan::new(tmp.path().join("res"), Some(outside), "x");
+        let files = load_resources(&plan).unwrap();
+        assert_eq!(files[1].path, elsewhere.path().join("extra.xml"));
+    }
+
+    #[test]
+    fn malformed_file_fails_instead_of_skipping_profile_filtering() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"
+<resources>
+    <string name="api_key" profile="debug">dev-secret</string>
+    <string name="title">Demo</strin>
+</resources>
+"#,
+        );
+        // Lenient plans fail too: unfiltered, the debug secret would ship
+        let mut plan = BuildPlan::new(res_dir, None, "release");
+        plan.allow_missing_resources = true;
+
+        let err = load_resources(&plan).err().unwrap();
+        let LoaderError::Preprocess { path, source } = &err else {
+            panic!("expected a preprocessing error, got {err}");
+        };
+        let contents = fs::read_to_string(path).unwrap();
+        let at = usize::try_from(source.position).unwrap();
+        assert!(contents[at..].starts_with("</strin>"), "{err}");
+        let message = err.to_string();
+        assert!(message.starts_with("cannot filter '"), "{message}");
+        assert!(message.contains("values.xml' by profile: XML error at byte"));
+    }
+
     #[test]
     fn missing_tests_dir_is_ignored() {
         let tmp = tempdir().unwrap();
@@ -1,190 +1,243 @@
 /// Environment/profile preprocessing for resources
-use quick_xml::events::Event;
-use quick_xml::Reader;
-use std::fmt::Write as _;
+use std::path::Path;
 
-/// Preprocess XML: remove any element that has a profile attribute not matching the current profile
-/// This runs before parsing, so the parser receives only relevant nodes
-pub fn preprocess_xml(xml: &str, current_profile: &str) -> String {
-    let mut reader = Reader::from_str(xml);
-    reader.config_mut().trim_text(true);
-
-    let mut buf = Vec::new();
-    let mut out = String::new();
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, Writer};
 
-    // Track whether we are currently skipping a subtree due to mismatched profile
-    let mut skip_depth: usize = 0;
+/// XML that could not be read while filtering by profile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreprocessError {
+    /// Byte offset of the error in the file
+    pub position: u64,
+    pub message: String,
+}
 
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(e)) => {
-                handle_start_event(
-                    &e,
-                    current_profile,
-                    &mut skip_depth,
-                    &mut out,
-                );
-            }
-            Ok(Event::Empty(e)) => {
-                handle_empty_event(
-                    &e,
-                    current_profile,
-                    skip_depth,
-                    &mut out,
-                );
-            }
-            Ok(Event::Text(e)) => {
-                handle_text_event(&e, skip_depth, &mut out)
-            }
-            Ok(Event::End(e)) => {
-                handle_end_event(&e, &mut skip_depth, &mut out)
-            }
-            Ok(Event::Eof) => break,
-            Ok(
-                Event::Comment(_)
-                | Event::Decl(_)
-                | Event::CData(_)
-                | Event::PI(_)
-                | Event::DocType(_)
-                | Event::GeneralRef(_),
-            ) => {
-                // ignore
-            }
-            Err(_) => {
-                // If preprocessing fails, return original xml to avoid hard failure
-                return xml.to_string();
-            }
-        }
-        buf.clear();
+impl std::fmt::Display for PreprocessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "XML error at byte {}: {}", self.position, self.message)
     }
-    out
 }
 
-#[inline]
-fn extract_profile_attr_start(
-    e: &quick_xml::events::BytesStart,
-) -> Option<String> {
-    for attr in e.attributes().flatten() {
-        if attr.key.as_ref() == b"profile" {
-            return Some(
-                String::from_utf8_lossy(&attr.value).to_string(),
-            );
-        }
-    }
-    None
+impl std::error::Error for PreprocessError {}
+
+/// Element left out because its `profile` attribute names another profile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileSkip {
+    /// Tag of the element (`string`, `ns`)
+    pub element: String,
+    /// Its `name` attribute, when it has one
+    pub name: Option<String>,
+    /// Profile the element was written for
+    pub profile: String,
 }
 
-#[inline]
-fn extract_profile_attr_empty(
-    e: &quick_xml::events::BytesStart,
-) -> Option<String> {
-    for attr in e.attributes().flatten() {
-        if attr.key.as_ref() == b"profile" {
-            return Some(
-                String::from_utf8_lossy(&attr.value).to_string(),
-            );
-        }
+impl ProfileSkip {
+    /// `<string> 'debug_endpoint' (res/values.xml) skipped (profile="debug",
+    /// active profile release)`
+    pub fn note(&self, file: &Path, active_profile: &str) -> String {
+        let name = self
+            .name
+            .as_ref()
+            .map(|name| format!(" '{name}'"))
+            .unwrap_or_default();
+        format!(
+            "<{}>{name} ({}) skipped (profile={:?}, active profile {active_profile})",
+            self.element,
+            file.display(),
+            self.profile
+        )
     }
-    None
 }
 
-#[inline]
-fn handle_start_event(
-    e: &quick_xml::events::BytesStart,
-    current_profile: &str,
-    skip_depth: &mut usize,
-    out: &mut String,
-) {
-    let profile_attr = extract_profile_attr_start(e);
-    if *skip_depth > 0 {
-        *skip_depth += 1;
-    } else if let Some(p) = profile_attr.as_deref() {
-        if p != current_profile {
-            *skip_depth = 1; // start skipping this subtree
-        }
-    }
-    if *skip_depth == 0 {
-        write_start_tag(out, e);
-    }
+/// XML kept for the current profile, and the elements taken out of it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preprocessed {
+    pub xml: String,
+    /// Outermost skipped elements, in document order; their content is not
+    /// listed
+    pub skipped: Vec<ProfileSkip>,
 }
 
-#[inline]
-fn handle_empty_event(
-    e: &quick_xml::events::BytesStart,
+/// Preprocess XML: remove any element that has a profile attribute not matching the current profile
+/// This runs before parsing, so the parser receives only relevant nodes.
+/// Everything else (text, entities, comments, CDATA, the declaration) is
+/// copied through as written.
+/// Malformed XML is an error: passing it through unfiltered would let
+/// elements meant for another profile into this build.
+pub fn preprocess_xml(
+    xml: &str,
     current_profile: &str,
-    skip_depth: usize,
-    out: &mut String,
-) {
-    if skip_depth == 0 {
-        let profile_attr = extract_profile_attr_empty(e);
-        if let Some(p) = profile_attr.as_deref() {
-            if p == current_profile {
-                write_empty_tag(out, e);
-            } else {
-                // skip this empty element
+) -> Result<Preprocessed, PreprocessError> {
+    let mut reader = Reader::from_str(xml);
+    let mut writer = Writer::new(Vec::with_capacity(xml.len()));
+    let mut skipped = Vec::new();
+
+    // Depth inside a subtree skipped due to a mismatched profile
+    let mut skip_depth: usize = 0;
+
+    loop {
+        let event = reader.read_event().map_err(|err| PreprocessError {
+            position: reader.error_position(),
+            message: err.to_string(),
+        })?;
+        match &event {
+            Event::Eof => break,
+            Event::Start(_) if skip_depth > 0 => {
+                skip_depth += 1;
+                continue;
+            }
+            Event::End(_) if skip_depth > 0 => {
+                skip_depth -= 1;
+                continue;
+            }
+            _ if skip_depth > 0 => continue,
+            Event::Start(e) | Event::Empty(e) => {
+                if let Some(skip) = profile_skip(e, current_profile) {
+                    // The content of a start tag goes with it
+                    skip_depth = usize::from(matches!(event, Event::Start(_)));
+                    skipped.push(skip);
+                    continue;
+                }
             }
-        } else {
-            write_empty_tag(out, e);
+            _ => {}
         }
+        // Writing into a Vec cannot fail
+        let _ = writer.write_event(event);
     }
+    Ok(Preprocessed {
+        xml: String::from_utf8_lossy(&writer.into_inner()).into_owned(),
+        skipped,
+    })
 }
 
-#[inline]
-fn handle_text_event(
-    e: &quick_xml::events::BytesText,
-    skip_depth: usize,
-    out: &mut String,
-) {
-    if skip_depth == 0 {
-        out.push_str(&String::from_utf8_lossy(e));
+/// The skip of an element whose `profile` attribute names another profile;
+/// `None` when it has no such attribute or names this profile
+fn profile_skip(
+    e: &BytesStart<'_>,
+    current_profile: &str,
+) -> Option<ProfileSkip> {
+    let attribute = |key: &[u8]| {
+        e.attributes()
+            .flatten()
+            .find(|attr| attr.key.local_name().as_ref() == key)
+    };
+    let profile = attribute(b"profile")?;
+    if profile.value.as_ref() == current_profile.as_bytes() {
+        return None;
     }
+    let text = |attr: quick_xml::events::attributes::Attribute<'_>| {
+        attr.unescape_value().map_or_else(
+            |_| String::from_utf8_lossy(&attr.value).into_owned(),
+            |value| value.into_owned(),
+        )
+    };
+    Some(ProfileSkip {
+        element: String::from_utf8_lossy(e.local_name().as_ref())
+            .into_owned(),
+        name: attribute(b"name").map(text),
+        profile: text(profile),
+    })
 }
 
-#[inline]
-fn handle_end_event(
-    e: &quick_xml::events::BytesEnd,
-    skip_depth: &mut usize,
-    out: &mut String,
-) {
-    if *skip_depth > 0 {
-        *skip_depth -= 1;
-    } else {
-        write_end_tag(out, e);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_everything_but_other_profiles() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- Strings shown on the landing page -->
+<resources>
+    <string name="intro"><![CDATA[<b>Welcome</b> & enjoy]]></string>
+    <string name="api" profile="debug">http://localhost<!-- dev --></string>
+    <string name="api" profile='release'>https://example.com</string>
+    <bool name="verbose" profile="debug"/>
+    <?custom instruction?>
+    <string name="legal">Fish &amp; Chips</string>
+</resources>
+"#;
+        let filtered = preprocess_xml(xml, "release").unwrap().xml;
+        let debug_api = concat!(
+            r#"<string name="api" profile="debug">"#,
+            "http://localhost<!-- dev --></string>",
+        );
+        let expected = xml
+            .replace(debug_api, "")
+            .replace(r#"<bool name="verbose" profile="debug"/>"#, "");
+        assert_eq!(filtered, expected);
     }
-}
 
-fn write_start_tag(
-    out: &mut String,
-    e: &quick_xml::events::BytesStart,
-) {
-    let name_binding = e.name();
-    let name = String::from_utf8_lossy(name_binding.as_ref());
-    let _ = write!(out, "<{name}");
-    for attr in e.attributes().flatten() {
-        let key = String::from_utf8_lossy(attr.key.as_ref());
-        let val = String::from_utf8_lossy(&attr.value);
-        let _ = write!(out, " {key}=\"{val}\"");
+    #[test]
+    fn file_without_profiles_round_trips() {
+        let xml = "<?xml version=\"1.0\"?>\r\n<resources>\
+                   <char name=\"space\"> </char>\
+                   <![CDATA[ stray ]]><!---->\
+                   </resources>";
+        assert_eq!(preprocess_xml(xml, "debug").unwrap().xml, xml);
     }
-    out.push('>');
-}
 
-fn write_empty_tag(
-    out: &mut String,
-    e: &quick_xml::events::BytesStart,
-) {
-    let name_binding = e.name();
-    let name = String::from_utf8_lossy(name_binding.as_ref());
-    let _ = write!(out, "<{name}");
-    for attr in e.attributes().flatten() {
-        let key = String::from_utf8_lossy(attr.key.as_ref());
-        let val = String::from_utf8_lossy(&attr.value);
-        let _ = write!(out, " {key}=\"{val}\"");
+    #[test]
+    fn attributes_keep_their_escapes_and_quotes() {
+        let xml = concat!(
+            r#"<resources><string name="x" profile="debug">dev</string>"#,
+            r#"<string name="q" note="say &quot;hi&quot; &amp; go">Q</string>"#,
+            r#"<string name="s" note='say "hi"' profile="release"/>"#,
+            "</resources>",
+        );
+        let filtered = preprocess_xml(xml, "release").unwrap().xml;
+        assert_eq!(
+            filtered,
+            xml.replace(r#"<string name="x" profile="debug">dev</string>"#, "")
+        );
     }
-    out.push_str("/>");
-}
 
-fn write_end_tag(out: &mut String, e: &quick_xml::events::BytesEnd) {
-    let name_binding = e.name();
-    let name = String::from_utf8_lossy(name_binding.as_ref());
-    let _ = write!(out, "</{name}>");
+    #[test]
+    fn nested_subtrees_are_skipped_whole() {
+        let xml = concat!(
+            r#"<resources><ns name="dev" profile="debug">"#,
+            r#"<ns name="x"><string name="a"/></ns><![CDATA[x]]></ns>"#,
+            r#"<string name="b">B</string></resources>"#,
+        );
+        assert_eq!(
+            preprocess_xml(xml, "release").unwrap().xml,
+            r#"<resources><string name="b">B</string></resources>"#
+        );
+    }
+
+    #[test]
+    fn records_the_outermost_skipped_elements() {
+        let xml = concat!(
+            r#"<resources><ns name="dev" profile="debug">"#,
+            r#"<string name="a" profile="debug"/></ns>"#,
+            r#"<bool name="trace" profile="debug &amp; ci">true</bool>"#,
+            r#"<string name="b" profile="release">B</string>"#,
+            r#"<item profile="debug"/></resources>"#,
+        );
+        let skipped = preprocess_xml(xml, "release").unwrap().skipped;
+        let skip = |element: &str, name: Option<&str>, profile: &str| {
+            ProfileSkip {
+                element: element.to_string(),
+                name: name.map(str::to_string),
+                profile: profile.to_string(),
+            }
+        };
+        assert_eq!(
+            skipped,
+            [
+                skip("ns", Some("dev"), "debug"),
+                skip("bool", Some("trace"), "debug & ci"),
+                skip("item", None, "debug"),
+            ]
+        );
+        assert_eq!(
+            skipped[0].note(Path::new("res/values.xml"), "release"),
+            "<ns> 'dev' (res/values.xml) skipped (profile=\"debug\", \
+             active profile release)"
+        );
+        assert_eq!(
+            skipped[2].note(Path::new("values.xml"), "release"),
+            "<item> (values.xml) skipped (profile=\"debug\", active \
+             profile release)"
+        );
+    }
 }
@@ -1,11 +1,18 @@
 use std::path::PathBuf;
 
+use super::profile::ProfileSkip;
+
 /// Raw resource file loaded from disk and preprocessed for the selected profile.
 #[derive(Debug, Clone)]
 pub struct RawResourceFile {
     pub path: PathBuf,
     pub contents: String,
     pub is_test: bool,
+    /// File as read from disk, before preprocessing; diagnostics quote it
+    /// so their line numbers match the editor
+    pub source: String,
+    /// Elements preprocessing left out for another profile
+    pub skipped: Vec<ProfileSkip>,
 }
 
 impl RawResourceFile {
@@ -16,8 +23,24 @@ impl RawResourceFile {
     ) -> Self {
         Self {
             path,
+            source: contents.clone(),
             contents,
             is_test,
+            skipped: Vec::new(),
         }
     }
+
+    /// Keeps `source` as the original text of a preprocessed file
+    #[must_use]
+    pub fn with_source(mut self, source: String) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Records the elements preprocessing left out of `contents`
+    #[must_use]
+    pub fn with_skipped(mut self, skipped: Vec<ProfileSkip>) -> Self {
+        self.skipped = skipped;
+        self
+    }
 }
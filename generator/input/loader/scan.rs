@@ -1,32 +1,245 @@
+use std::collections::HashSet;
 use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
 
 use super::LoaderError;
 
+/// XML files of a resource directory, and the entries left out
+#[derive(Debug, Default)]
+pub(super) struct Scan {
+    pub(super) files: Vec<ScannedFile>,
+    pub(super) skipped: Vec<ScanSkip>,
+}
+
+/// An XML file to load; symlinks keep their target for error messages
+#[derive(Debug)]
+pub(super) struct ScannedFile {
+    pub(super) path: PathBuf,
+    pub(super) target: Option<PathBuf>,
+}
+
+/// An `.xml` entry the scan did not load, and why
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct ScanSkip {
+    pub(super) path: PathBuf,
+    pub(super) reason: String,
+}
+
+impl ScanSkip {
+    fn new(path: &Path, reason: impl Into<String>) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            reason: reason.into(),
+        }
+    }
+
+    /// `res/.#values.xml skipped: hidden file (...)`, with `path` as the
+    /// loader names files
+    pub(super) fn note(&self, path: &Path) -> String {
+        format!("{} skipped: {}", path.display(), self.reason)
+    }
+}
+
+/// The `.xml` entries of `dir`, sorted. Symlinks to regular files are
+/// followed; hidden files (leading `.`, like editor lock files) unless
+/// `include_hidden`, dangling symlinks, other non-regular entries, and a
+/// second path to an already listed file are skipped.
 pub(super) fn collect_xml_files(
     dir: &Path,
-) -> Result<Vec<PathBuf>, LoaderError> {
-    let mut files = Vec::new();
+    include_hidden: bool,
+) -> Result<Scan, LoaderError> {
     let entries =
         fs::read_dir(dir).map_err(|source| LoaderError::Io {
             path: dir.to_path_buf(),
             source,
         })?;
-
+    let mut paths = Vec::new();
     for entry in entries {
         let entry = entry.map_err(|source| LoaderError::Io {
             path: dir.to_path_buf(),
             source,
         })?;
         let path = entry.path();
-        if !path.is_file() {
+        if path.extension().is_some_and(|ext| ext == "xml") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut scan = Scan::default();
+    // Files already listed, so two symlinks to one file load it once
+    let mut visited = HashSet::new();
+    for path in paths {
+        let hidden = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        if hidden && !include_hidden {
+            scan.skipped.push(ScanSkip::new(
+                &path,
+                "hidden file (set `BuildPlan::include_hidden_files` to load \
+                 it)",
+            ));
             continue;
         }
-        if path.extension().is_some_and(|ext| ext == "xml") {
-            files.push(path);
+        let Some(file) = scan_entry(&path, &mut scan.skipped)? else {
+            continue;
+        };
+        let canonical = fs::canonicalize(&path).unwrap_or(path.clone());
+        if !visited.insert(canonical.clone()) {
+            scan.skipped.push(ScanSkip::new(
+                &path,
+                format!("same file as '{}'", canonical.display()),
+            ));
+            continue;
         }
+        scan.files.push(file);
     }
+    Ok(scan)
+}
 
-    files.sort();
-    Ok(files)
+/// `path` as a file to load, or `None` (with a skip) when it is not a
+/// regular file or a symlink to one
+fn scan_entry(
+    path: &Path,
+    skipped: &mut Vec<ScanSkip>,
+) -> Result<Option<ScannedFile>, LoaderError> {
+    let metadata =
+        fs::symlink_metadata(path).map_err(|source| LoaderError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+    if !metadata.file_type().is_symlink() {
+        if !metadata.is_file() {
+            skipped.push(ScanSkip::new(path, "not a regular file"));
+            return Ok(None);
+        }
+        return Ok(Some(ScannedFile {
+            path: path.to_path_buf(),
+            target: None,
+        }));
+    }
+
+    let target = fs::read_link(path).map_err(|source| LoaderError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    match fs::metadata(path) {
+        Ok(metadata) if metadata.is_file() => Ok(Some(ScannedFile {
+            path: path.to_path_buf(),
+            target: Some(target),
+        })),
+        Ok(_) => {
+            let reason = format!(
+                "symlink to '{}', which is not a regular file",
+                target.display()
+            );
+            skipped.push(ScanSkip::new(path, reason));
+            Ok(None)
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            let reason = format!(
+                "symlink to '{}', which does not exist",
+                target.display()
+            );
+            skipped.push(ScanSkip::new(path, reason));
+            Ok(None)
+        }
+        // A cycle of symlinks ends here too, named by the OS error
+        Err(source) => Err(LoaderError::Symlink {
+            path: path.to_path_buf(),
+            target,
+            source,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn names(paths: impl IntoIterator<Item = PathBuf>) -> Vec<String> {
+        paths
+            .into_iter()
+            .map(|path| {
+                path.file_name().unwrap().to_string_lossy().into_owned()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn skips_hidden_files_unless_included() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("values.xml"), "<resources/>").unwrap();
+        fs::write(dir.path().join(".draft.xml"), "<resources/>").unwrap();
+        fs::create_dir(dir.path().join("folder.xml")).unwrap();
+
+        let scan = collect_xml_files(dir.path(), false).unwrap();
+        let files = scan.files.into_iter().map(|file| file.path);
+        assert_eq!(names(files), ["values.xml"]);
+        let reasons: Vec<&str> =
+            scan.skipped.iter().map(|skip| skip.reason.as_str()).collect();
+        assert!(reasons[0].starts_with("hidden file"), "{reasons:?}");
+        assert_eq!(reasons[1], "not a regular file");
+
+        let scan = collect_xml_files(dir.path(), true).unwrap();
+        let files = scan.files.into_iter().map(|file| file.path);
+        assert_eq!(names(files), [".draft.xml", "values.xml"]);
+    }
+
+    // Creating symlinks needs privileges on Windows
+    #[cfg(unix)]
+    #[test]
+    fn follows_symlinks_to_files_and_skips_the_rest() {
+        use std::os::unix::fs::symlink;
+
+        let shared = tempdir().unwrap();
+        let shared_file = shared.path().join("colors.xml");
+        fs::write(&shared_file, "<resources/>").unwrap();
+        let dir = tempdir().unwrap();
+        let res = dir.path();
+        symlink(&shared_file, res.join("colors.xml")).unwrap();
+        symlink(&shared_file, res.join("palette.xml")).unwrap();
+        symlink("gone.txt", res.join("dangling.xml")).unwrap();
+        symlink(shared.path(), res.join("shared.xml")).unwrap();
+
+        let scan = collect_xml_files(res, false).unwrap();
+        assert_eq!(scan.files.len(), 1);
+        assert_eq!(scan.files[0].target.as_ref(), Some(&shared_file));
+        let notes: Vec<String> = scan
+            .skipped
+            .iter()
+            .map(|skip| skip.note(Path::new(skip.path.file_name().unwrap())))
+            .collect();
+        let shared = shared.path().display();
+        assert_eq!(
+            notes,
+            [
+                "dangling.xml skipped: symlink to 'gone.txt', which does not \
+                 exist"
+                    .to_string(),
+                format!(
+                    "palette.xml skipped: same file as '{}'",
+                    fs::canonicalize(&shared_file).unwrap().display()
+                ),
+                format!(
+                    "shared.xml skipped: symlink to '{shared}', which is not \
+                     a regular file"
+                ),
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlink_cycles_fail_naming_the_link_and_its_target() {
+        use std::os::unix::fs::symlink;
+
+        let dir = tempdir().unwrap();
+        symlink("loop.xml", dir.path().join("loop.xml")).unwrap();
+        let err = collect_xml_files(dir.path(), false).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("loop.xml' (symlink to 'loop.xml')"));
+    }
 }
@@ -0,0 +1,778 @@
+//! Configuration from the consumer's `Cargo.toml`:
+//!
+//! ```toml
+//! [package.metadata.r-resources]
+//! duplicates = "error"
+//! profile-default = "staging"
+//! include-tests = true
+//! root-module = "assets"
+//! deny = ["RR0060"]
+//! ```
+//!
+//! Unlike the `R_RESOURCES_*` variables, the table is versioned with the
+//! crate, so every developer and CI builds with the same settings; a
+//! variable that is set still wins, for one-off experiments. Only this
+//! table is read, by a small reader covering what it needs (strings,
+//! multi-line ones included, booleans, integers, arrays of them, and the
+//! table written inline or with dotted keys, `r-resources.deny = [...]`
+//! under `[package.metadata]`),
+//! so the generator does not depend on a TOML crate. `cargo r-resources`
+//! gets the table from `cargo metadata` instead, as
+//! [`ManifestConfig::from_entries`].
+
+use std::path::Path;
+
+/// Header of the table, as written in `Cargo.toml`
+pub const METADATA_TABLE: &str = "[package.metadata.r-resources]";
+
+/// Keys leading to the table, however it is written
+const TABLE_PATH: [&str; 3] = ["package", "metadata", "r-resources"];
+
+/// Keys this version reads
+const KEYS: &[&str] = &[
+    "res-dir",
+    "tests-dir",
+    "root-module",
+    "profile-default",
+    "include-tests",
+    "file-as-namespace",
+    "duplicates",
+    "deny",
+    "allow",
+    "max-warnings",
+];
+
+/// The `[package.metadata.r-resources]` table; everything is unset when
+/// the manifest has none
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ManifestConfig {
+    /// Resource directory, relative to the manifest (default `res`)
+    pub res_dir: Option<String>,
+    /// Test resources, relative to the resource directory
+    /// (`R_RESOURCES_TESTS_DIR`)
+    pub tests_dir: Option<String>,
+    /// `BuildPlan::root_module`
+    pub root_module: Option<String>,
+    /// Profile matched by `profile` attributes in place of Cargo's `debug`
+    /// or `release` (`R_RESOURCES_PROFILE`)
+    pub profile_default: Option<String>,
+    /// Put `r_tests` in `r_generated.rs` (`R_RESOURCES_INCLUDE_TESTS`)
+    pub include_tests: Option<bool>,
+    /// `BuildPlan::file_as_namespace`
+    pub file_as_namespace: Option<bool>,
+    /// `duplicates = "error"` or `"warning"`
+    /// (`R_RESOURCES_DUPLICATES_AS_ERRORS`)
+    pub duplicates_as_errors: Option<bool>,
+    /// Codes turned into errors (`R_RESOURCES_DENY`)
+    pub deny: Vec<String>,
+    /// Codes silenced (`R_RESOURCES_ALLOW`)
+    pub allow: Vec<String>,
+    /// `R_RESOURCES_MAX_WARNINGS`
+    pub max_warnings: Option<usize>,
+    /// Keys of the table this version does not read, in file order
+    pub unknown_keys: Vec<String>,
+}
+
+impl ManifestConfig {
+    /// Reads the table of the manifest at `path`
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|err| format!("failed to read '{}': {err}", path.display()))?;
+        Self::parse(&text)
+            .map_err(|err| format!("{} in '{}'", err, path.display()))
+    }
+
+    /// Parses the table out of the text of a `Cargo.toml`
+    pub fn parse(manifest: &str) -> Result<Self, String> {
+        Self::from_entries(table_entries(manifest)?)
+    }
+
+    /// The configuration of the `key = value` pairs of the table, parsed
+    /// by another reader
+    pub fn from_entries(
+        entries: impl IntoIterator<Item = (String, ManifestValue)>,
+    ) -> Result<Self, String> {
+        let mut config = Self::default();
+        for (key, value) in entries {
+            let invalid = |expected: &str| {
+                format!("{METADATA_TABLE}: {key} must be {expected}")
+            };
+            match key.as_str() {
+                "res-dir" => config.res_dir = Some(value.text(invalid)?),
+                "tests-dir" => config.tests_dir = Some(value.text(invalid)?),
+                "root-module" => {
+                    config.root_module = Some(value.text(invalid)?)
+                }
+                "profile-default" => {
+                    config.profile_default = Some(value.text(invalid)?)
+                }
+                "include-tests" => {
+                    config.include_tests = Some(value.flag(invalid)?)
+                }
+                "file-as-namespace" => {
+                    config.file_as_namespace = Some(value.flag(invalid)?)
+                }
+                "duplicates" => {
+                    config.duplicates_as_errors =
+                        match value.text(invalid)?.as_str() {
+                            "error" => Some(true),
+                            "warning" => Some(false),
+                            _ => return Err(invalid("\"error\" or \"warning\"")),
+                        }
+                }
+                "deny" => config.deny = value.codes(invalid)?,
+                "allow" => config.allow = value.codes(invalid)?,
+                "max-warnings" => {
+                    config.max_warnings = Some(value.count(invalid)?)
+                }
+                _ => config.unknown_keys.push(key),
+            }
+        }
+        Ok(config)
+    }
+
+    /// The value of the `R_RESOURCES_*` variable `name` this table stands
+    /// for, written as the variable would be; `None` when the table leaves
+    /// it unset. [`build`](crate::generator::build) looks a variable up
+    /// here when the environment does not set it.
+    pub fn var(&self, name: &str) -> Option<String> {
+        let list = |codes: &[String]| {
+            (!codes.is_empty()).then(|| codes.join(","))
+        };
+        match name {
+            "R_RESOURCES_TESTS_DIR" => self.tests_dir.clone(),
+            "R_RESOURCES_PROFILE" => self.profile_default.clone(),
+            "R_RESOURCES_INCLUDE_TESTS" => {
+                self.include_tests.filter(|on| *on).map(|_| "1".into())
+            }
+            "R_RESOURCES_DUPLICATES_AS_ERRORS" => self
+                .duplicates_as_errors
+                .map(|on| if on { "1" } else { "0" }.to_string()),
+            "R_RESOURCES_DENY" => list(&self.deny),
+            "R_RESOURCES_ALLOW" => list(&self.allow),
+            "R_RESOURCES_MAX_WARNINGS" => {
+                self.max_warnings.map(|count| count.to_string())
+            }
+            _ => None,
+        }
+    }
+
+    /// Fails on keys this version does not read, for tools that can be
+    /// stricter than a build
+    #[allow(dead_code)] // Used by `cargo r-resources`
+    pub fn check_known_keys(&self) -> Result<(), String> {
+        if self.unknown_keys.is_empty() {
+            return Ok(());
+        }
+        Err(format!(
+            "{METADATA_TABLE}: unknown keys {} (known keys: {})",
+            self.unknown_keys.join(", "),
+            KEYS.join(", ")
+        ))
+    }
+
+    /// `cargo:warning` text for the keys not read, `None` without any
+    pub fn unknown_keys_warning(&self) -> Option<String> {
+        (!self.unknown_keys.is_empty()).then(|| {
+            format!(
+                "{METADATA_TABLE}: unknown keys {} are ignored (known keys: {})",
+                self.unknown_keys.join(", "),
+                KEYS.join(", ")
+            )
+        })
+    }
+}
+
+/// A value of the table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManifestValue {
+    String(String),
+    Bool(bool),
+    Integer(i64),
+    Array(Vec<ManifestValue>),
+    /// An inline table, `{ key = value, ... }`
+    Table(Vec<(String, ManifestValue)>),
+}
+
+impl ManifestValue {
+    fn text(self, invalid: impl Fn(&str) -> String) -> Result<String, String> {
+        match self {
+            ManifestValue::String(text) => Ok(text),
+            _ => Err(invalid("a string")),
+        }
+    }
+
+    fn flag(self, invalid: impl Fn(&str) -> String) -> Result<bool, String> {
+        match self {
+            ManifestValue::Bool(on) => Ok(on),
+            _ => Err(invalid("true or false")),
+        }
+    }
+
+    fn count(self, invalid: impl Fn(&str) -> String) -> Result<usize, String> {
+        match self {
+            ManifestValue::Integer(count) => {
+                usize::try_from(count).map_err(|_| invalid("a count"))
+            }
+            _ => Err(invalid("a count")),
+        }
+    }
+
+    fn codes(
+        self,
+        invalid: impl Fn(&str) -> String,
+    ) -> Result<Vec<String>, String> {
+        let ManifestValue::Array(items) = self else {
+            return Err(invalid("a list of codes"));
+        };
+        items
+            .into_iter()
+            .map(|item| match item {
+                ManifestValue::String(code) => Ok(code),
+                _ => Err(invalid("a list of codes")),
+            })
+            .collect()
+    }
+}
+
+/// `key = value` pairs of the table, in file order, whether written under
+/// its header, inline (`r-resources = { ... }` under `[package.metadata]`),
+/// or with dotted keys. A value may span lines while an array or a
+/// multi-line string (`"""`, `'''`) is open.
+fn table_entries(
+    manifest: &str,
+) -> Result<Vec<(String, ManifestValue)>, String> {
+    let mut entries = Vec::new();
+    let mut table = Vec::new();
+    let mut lines = manifest.lines().enumerate();
+    while let Some((index, line)) = lines.next() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if trimmed.starts_with('[') {
+            table = header_path(trimmed);
+            continue;
+        }
+        let at = |message: String| format!("{message} at line {}", index + 1);
+        let Some((key, value)) = trimmed.split_once('=') else {
+            if relative_key(&table).is_some() {
+                return Err(at(format!("{METADATA_TABLE}: expected `key = value`")));
+            }
+            continue;
+        };
+        let mut path = table.clone();
+        path.extend(key_path(key));
+        let key = relative_key(&path);
+        // Read to the end of the value even when it is not ours, so the
+        // lines of an array are not taken for keys or headers
+        let mut text = value.to_string();
+        while let Some(open) = unclosed(&text) {
+            match (lines.next(), &key) {
+                (Some((_, next)), _) => {
+                    text.push('\n');
+                    text.push_str(next);
+                }
+                (None, Some(key)) => {
+                    return Err(at(format!(
+                        "{METADATA_TABLE}: unclosed {open} for {key}"
+                    )))
+                }
+                // Cargo reports the broken manifest
+                (None, None) => return Ok(entries),
+            }
+        }
+        let Some(key) = key else {
+            continue;
+        };
+        let mut reader = Reader {
+            chars: text.chars().collect(),
+            at: 0,
+        };
+        let value = reader
+            .value()
+            .and_then(|value| reader.end().map(|()| value))
+            .map_err(|err| at(format!("{METADATA_TABLE}: {key}: {err}")))?;
+        match value {
+            ManifestValue::Table(inline) if key.is_empty() => {
+                entries.extend(inline)
+            }
+            value => entries.push((key, value)),
+        }
+    }
+    Ok(entries)
+}
+
+/// The key of `path` within the table, `""` for the table itself; `None`
+/// outside of it
+fn relative_key(path: &[String]) -> Option<String> {
+    let inside = path.len() >= TABLE_PATH.len()
+        && path.iter().zip(TABLE_PATH).all(|(key, part)| key == part);
+    inside.then(|| path[TABLE_PATH.len()..].join("."))
+}
+
+/// Keys of a table header, `[package.metadata."r-resources"]` (or
+/// `[[bin]]`)
+fn header_path(line: &str) -> Vec<String> {
+    let header = line.split(']').next().unwrap_or_default();
+    key_path(header.trim_start_matches('['))
+}
+
+/// Parts of a dotted key, spacing and quoting aside
+fn key_path(key: &str) -> Vec<String> {
+    key.split('.')
+        .map(|part| part.trim().trim_matches(['"', '\'']).to_string())
+        .collect()
+}
+
+/// What `text` leaves open: a multi-line string, or a `[` or `{` outside
+/// strings and comments; `None` once the value is complete
+fn unclosed(text: &str) -> Option<&'static str> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut depth = 0i32;
+    // The quote character, and whether it is tripled
+    let mut quote: Option<(char, bool)> = None;
+    let mut comment = false;
+    let mut at = 0;
+    while let Some(&c) = chars.get(at) {
+        let tripled = chars.get(at..at + 3) == Some(&[c, c, c][..]);
+        at += 1;
+        if comment {
+            comment = c != '\n';
+            continue;
+        }
+        match quote {
+            Some(('"', _)) if c == '\\' => at += 1,
+            Some((open, true)) if c == open && tripled => {
+                quote = None;
+                at += 2;
+            }
+            Some((open, false)) if c == open || c == '\n' => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' if tripled => {
+                    quote = Some((c, true));
+                    at += 2;
+                }
+                '"' | '\'' => quote = Some((c, false)),
+                '[' | '{' => depth += 1,
+                ']' | '}' => depth -= 1,
+                '#' => comment = true,
+                _ => {}
+            },
+        }
+    }
+    match quote {
+        Some((_, true)) => Some("string"),
+        _ if depth > 0 => Some("array"),
+        _ => None,
+    }
+}
+
+/// Reads one TOML value
+struct Reader {
+    chars: Vec<char>,
+    at: usize,
+}
+
+impl Reader {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.at).copied()
+    }
+
+    /// Skips whitespace, line breaks, and comments
+    fn skip_blank(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == '#' {
+                self.skip_comment();
+            } else if c.is_whitespace() {
+                self.at += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.at += 1;
+        }
+    }
+
+    fn skip_comment(&mut self) {
+        while self.peek().is_some_and(|c| c != '\n') {
+            self.at += 1;
+        }
+    }
+
+    /// Nothing but blanks and comments left
+    fn end(&mut self) -> Result<(), String> {
+        self.skip_blank();
+        match self.peek() {
+            None => Ok(()),
+            Some(c) => Err(format!("unexpected '{c}' after the value")),
+        }
+    }
+
+    fn value(&mut self) -> Result<ManifestValue, String> {
+        self.skip_blank();
+        match self.peek() {
+            Some('"') => self.basic_string().map(ManifestValue::String),
+            Some('\'') => self.literal_string().map(ManifestValue::String),
+            Some('[') => self.array(),
+            Some('{') => self.inline_table(),
+            Some(_) => self.bare(),
+            None => Err("missing value".to_string()),
+        }
+    }
+
+    /// Whether three `quote`s start a multi-line string here, skipping
+    /// them and the line break right after
+    fn triple_quote(&mut self, quote: char) -> bool {
+        let tripled = self.chars.get(self.at..self.at + 3)
+            == Some(&[quote, quote, quote][..]);
+        if tripled {
+            self.at += 3;
+            if self.peek() == Some('\n') {
+                self.at += 1;
+            }
+        }
+        tripled
+    }
+
+    /// Whether `quote` (three of them when `multi_line`) closes the string
+    /// here, skipping it
+    fn closing(&mut self, quote: char, multi_line: bool) -> bool {
+        let len = if multi_line { 3 } else { 1 };
+        let closed = self.chars.get(self.at..self.at + len)
+            == Some(&[quote, quote, quote][..len]);
+        if closed {
+            self.at += len;
+        }
+        closed
+    }
+
+    fn basic_string(&mut self) -> Result<String, String> {
+        let multi_line = self.triple_quote('"');
+        if !multi_line {
+            self.at += 1;
+        }
+        let mut text = String::new();
+        while !self.closing('"', multi_line) {
+            let c = self.peek().ok_or("unterminated string")?;
+            self.at += 1;
+            match c {
+                // A backslash ending a line trims the blanks after it
+                '\\' if multi_line
+                    && self.peek().is_some_and(char::is_whitespace) =>
+                {
+                    self.skip_whitespace()
+                }
+                '\\' => {
+                    let escaped = self.peek().ok_or("unterminated string")?;
+                    self.at += 1;
+                    text.push(unescape(escaped)?);
+                }
+                '\n' if !multi_line => {
+                    return Err("unterminated string".to_string())
+                }
+                c => text.push(c),
+            }
+        }
+        Ok(text)
+    }
+
+    fn literal_string(&mut self) -> Result<String, String> {
+        let multi_line = self.triple_quote('\'');
+        if !multi_line {
+            self.at += 1;
+        }
+        let mut text = String::new();
+        while !self.closing('\'', multi_line) {
+            let c = self.peek().ok_or("unterminated string")?;
+            self.at += 1;
+            if c == '\n' && !multi_line {
+                return Err("unterminated string".to_string());
+            }
+            text.push(c);
+        }
+        Ok(text)
+    }
+
+    fn array(&mut self) -> Result<ManifestValue, String> {
+        self.at += 1;
+        let mut items = Vec::new();
+        loop {
+            self.skip_blank();
+            if self.peek() == Some(']') {
+                self.at += 1;
+                return Ok(ManifestValue::Array(items));
+            }
+            items.push(self.value()?);
+            self.skip_blank();
+            match self.peek() {
+                Some(',') => self.at += 1,
+                Some(']') => {}
+                _ => return Err("expected ',' or ']' in array".to_string()),
+            }
+        }
+    }
+
+    fn inline_table(&mut self) -> Result<ManifestValue, String> {
+        self.at += 1;
+        let mut entries = Vec::new();
+        loop {
+            self.skip_blank();
+            if self.peek() == Some('}') {
+                self.at += 1;
+                return Ok(ManifestValue::Table(entries));
+            }
+            let key = self.key()?;
+            self.skip_blank();
+            if self.peek() != Some('=') {
+                return Err(format!("expected '=' after {key}"));
+            }
+            self.at += 1;
+            entries.push((key, self.value()?));
+            self.skip_blank();
+            match self.peek() {
+                Some(',') => self.at += 1,
+                Some('}') => {}
+                _ => return Err("expected ',' or '}' in table".to_string()),
+            }
+        }
+    }
+
+    /// A key of an inline table, dotted keys joined with `.`
+    fn key(&mut self) -> Result<String, String> {
+        let mut parts = Vec::new();
+        loop {
+            self.skip_blank();
+            let part = match self.peek() {
+                Some('"') => self.basic_string()?,
+                Some('\'') => self.literal_string()?,
+                _ => self.bare_key()?,
+            };
+            parts.push(part);
+            self.skip_blank();
+            if self.peek() != Some('.') {
+                return Ok(parts.join("."));
+            }
+            self.at += 1;
+        }
+    }
+
+    fn bare_key(&mut self) -> Result<String, String> {
+        let start = self.at;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_alphanumeric() || matches!(c, '_' | '-'))
+        {
+            self.at += 1;
+        }
+        if start == self.at {
+            return Err("missing key".to_string());
+        }
+        Ok(self.chars[start..self.at].iter().collect())
+    }
+
+    /// `true`, `false`, or an integer
+    fn bare(&mut self) -> Result<ManifestValue, String> {
+        let start = self.at;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '+'))
+        {
+            self.at += 1;
+        }
+        let word: String = self.chars[start..self.at].iter().collect();
+        match word.as_str() {
+            "true" => Ok(ManifestValue::Bool(true)),
+            "false" => Ok(ManifestValue::Bool(false)),
+            _ => word
+                .replace('_', "")
+                .parse()
+                .map(ManifestValue::Integer)
+                .map_err(|_| format!("unsupported value '{word}'")),
+        }
+    }
+}
+
+/// The character `\c` stands for in a basic string
+fn unescape(c: char) -> Result<char, String> {
+    match c {
+        'n' => Ok('\n'),
+        't' => Ok('\t'),
+        '"' => Ok('"'),
+        '\\' => Ok('\\'),
+        other => Err(format!("unsupported escape '\\{other}'")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_metadata_table_only() {
+        let config = ManifestConfig::parse(
+            r#"
+[package]
+name = "app"
+root-module = "ignored"
+
+[package.metadata.r-resources]
+duplicates = "error"   # fail on any duplicate
+profile-default = 'staging'
+include-tests = true
+root-module = "assets"
+deny = [
+    "RR0060", # secrets
+    "RR0001",
+]
+max-warnings = 1_0
+colour = "blue"
+
+[dependencies]
+deny = "ignored"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            ManifestConfig {
+                root_module: Some("assets".to_string()),
+                profile_default: Some("staging".to_string()),
+                include_tests: Some(true),
+                duplicates_as_errors: Some(true),
+                deny: vec!["RR0060".to_string(), "RR0001".to_string()],
+                max_warnings: Some(10),
+                unknown_keys: vec!["colour".to_string()],
+                ..Default::default()
+            }
+        );
+        assert_eq!(config.var("R_RESOURCES_DENY").as_deref(), Some("RR0060,RR0001"));
+        assert_eq!(config.var("R_RESOURCES_DUPLICATES_AS_ERRORS").as_deref(), Some("1"));
+        assert_eq!(config.var("R_RESOURCES_PROFILE").as_deref(), Some("staging"));
+        assert_eq!(config.var("R_RESOURCES_ALLOW"), None);
+        assert!(config
+            .unknown_keys_warning()
+            .unwrap()
+            .starts_with("[package.metadata.r-resources]: unknown keys colour are ignored"));
+    }
+
+    #[test]
+    fn reads_the_table_inline_or_with_dotted_keys() {
+        let expected = ManifestConfig {
+            profile_default: Some("staging".to_string()),
+            deny: vec!["RR0060".to_string()],
+            max_warnings: Some(3),
+            ..Default::default()
+        };
+        let inline = ManifestConfig::parse(
+            r#"
+[package.metadata]
+docs.rs = { all-features = true }
+r-resources = { profile-default = "staging", deny = ["RR0060"], max-warnings = 3 }
+"#,
+        )
+        .unwrap();
+        assert_eq!(inline, expected);
+        let dotted = ManifestConfig::parse(
+            r#"
+[package]
+name = "app"
+metadata.r-resources.deny = [
+    "RR0060",
+]
+
+[package.metadata]
+r-resources.profile-default = "staging"
+"r-resources".max-warnings = 3
+"#,
+        )
+        .unwrap();
+        assert_eq!(dotted, expected);
+        // A subtable is not read, so its keys are reported as unknown
+        let nested = ManifestConfig::parse(
+            "[package.metadata.r-resources.staging]\ndeny = []\n",
+        )
+        .unwrap();
+        assert_eq!(nested.unknown_keys, ["staging.deny"]);
+    }
+
+    #[test]
+    fn arrays_of_other_tables_are_skipped_whole() {
+        let config = ManifestConfig::parse(
+            r#"
+[features]
+default = [
+    "std",
+]
+[package.metadata.r-resources]
+root-module = "assets"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.root_module.as_deref(), Some("assets"));
+        assert!(config.unknown_keys.is_empty());
+    }
+
+    #[test]
+    fn multi_line_strings_are_read_whole() {
+        let config = ManifestConfig::parse(
+            r#"
+[package]
+description = """
+[package.metadata.r-resources]
+root-module = "wrong"
+"""
+[package.metadata.r-resources]
+root-module = '''
+assets'''
+res-dir = """\
+    resources \
+    """
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.root_module.as_deref(), Some("assets"));
+        assert_eq!(config.res_dir.as_deref(), Some("resources "));
+
+        let unclosed = ManifestConfig::parse(
+            "[package.metadata.r-resources]\nres-dir = '''\nres\n",
+        );
+        assert_eq!(
+            unclosed.unwrap_err(),
+            "[package.metadata.r-resources]: unclosed string for res-dir at line 2"
+        );
+    }
+
+    #[test]
+    fn missing_table_leaves_everything_unset() {
+        let config =
+            ManifestConfig::parse("[package]\nname = \"app\"\n").unwrap();
+        assert_eq!(config, ManifestConfig::default());
+        assert_eq!(config.unknown_keys_warning(), None);
+    }
+
+    #[test]
+    fn rejects_values_of_the_wrong_type() {
+        let parse = |body: &str| {
+            ManifestConfig::parse(&format!("{METADATA_TABLE}\n{body}\n"))
+                .unwrap_err()
+        };
+        assert_eq!(
+            parse("duplicates = \"fatal\""),
+            "[package.metadata.r-resources]: duplicates must be \"error\" or \"warning\""
+        );
+        assert_eq!(
+            parse("include-tests = \"yes\""),
+            "[package.metadata.r-resources]: include-tests must be true or false"
+        );
+        assert_eq!(
+            parse("deny = [\"RR0001\""),
+            "[package.metadata.r-resources]: unclosed array for deny at line 2"
+        );
+    }
+}
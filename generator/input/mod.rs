@@ -4,20 +4,133 @@
 //! - Scanning directories for XML resource files
 //! - Profile-based preprocessing (filtering resources by build profile)
 //! - Test resource discovery (from `res/tests/` directory)
+//! - The `[package.metadata.r-resources]` table of the consumer's manifest
 //!
 //! The output is a list of `RawResourceFile` objects ready for parsing.
 
 pub mod loader;
+pub mod manifest;
 
-pub use loader::{load_resources, LoaderError, RawResourceFile};
+pub use loader::{
+    load_resources_with_notes, missing_resources_warning, LoaderError,
+    RawResourceFile,
+};
+#[allow(unused_imports)] // Public API, may be used by consumers
+pub use loader::load_resources;
+#[allow(unused_imports)] // Public API, may be used by consumers
+pub use loader::ProfileSkip;
+pub(crate) use loader::check_root_module;
+pub use manifest::ManifestConfig;
+#[allow(unused_imports)] // Public API, used by `cargo r-resources`
+pub use manifest::ManifestValue;
 
+use std::sync::Arc;
+
+use crate::generator::casing::NamingConfig;
+use crate::generator::ir::types::{ResourceType, TypeRegistry};
+
+#[derive(Clone)]
 pub struct BuildPlan {
     /// Root directory that contains runtime resources (default: `res/`).
     pub resources_dir: std::path::PathBuf,
-    /// Optional directory for test-only resources.
+    /// Optional directory for test-only resources, generated as `r_tests`.
     pub tests_resources_dir: Option<std::path::PathBuf>,
+    /// Also put `r_tests` in the main artifact; it is always available on
+    /// its own as `OutputArtifacts::tests_rust`.
+    pub embed_test_resources: bool,
     /// Cargo profile (debug/release) captured for preprocessing.
     pub profile: String,
+    /// Also generate a `#[cfg(test)]` module touching every resource, so the
+    /// consumer's `cargo test` checks the whole generated surface.
+    pub emit_smoke_tests: bool,
+    /// Also generate `r::to_json()`, returning every scalar resource as a
+    /// JSON object nested by namespace (built at compile time, no serde).
+    pub emit_to_json: bool,
+    /// Also generate `r::meta` with `TOTAL_RESOURCES`, `STRING_COUNT`, and
+    /// the sorted top-level `NAMESPACES`; a `meta` namespace is then an
+    /// error.
+    pub emit_meta: bool,
+    /// Also generate lookup tables from resource keys to values, for now
+    /// `r::colors::by_name("ui/colors/primary")`; a `colors` namespace is
+    /// then an error.
+    pub emit_lookup: bool,
+    /// Also generate one module per resource kind next to the root module
+    /// (`string`, `color`, `string_array`), each mirroring the namespace
+    /// tree and defining its resources, for code written against the
+    /// legacy layout; the root module re-exports them, so
+    /// `string::auth::TITLE` is `r::auth::TITLE`.
+    pub emit_kind_modules: bool,
+    /// Also generate, next to each constant, a zero-sized marker type
+    /// implementing `r_resources::Resource` (`r::auth::Title` for
+    /// `r::auth::TITLE`), so code can take a resource generically and read
+    /// its key with its value
+    pub emit_resource_markers: bool,
+    /// Emit a string value written by several resources once, as a hidden
+    /// static their constants name, so the artifact carries it once
+    pub intern_strings: bool,
+    /// Order the items of each namespace module by origin file, then name,
+    /// with a `// from res/auth.xml` line where the file changes, so a
+    /// review of the committed artifact shows which file a change came
+    /// from; by default items are in name order
+    pub group_by_origin: bool,
+    /// Case of the generated constants, namespace modules, and template
+    /// functions; the default keeps `UPPER_CASE` constants and the other
+    /// names as written
+    pub naming: NamingConfig,
+    /// Project-specific resource types, registered after the built-in ones.
+    pub custom_types: Vec<Arc<dyn ResourceType>>,
+    /// Extra tag names for existing types, as `(alias, tag)` pairs (e.g.
+    /// `("message", "string")`). The target must be a built-in tag or one
+    /// of the custom types' tags.
+    pub tag_aliases: Vec<(String, String)>,
+    /// Values replacing those of the XML, as `(key, text)` pairs (e.g.
+    /// `("brand/name", "Globex")`), the text written as between the tags.
+    /// Applied to the main resources before analysis; a key matching none,
+    /// or a resource without a single value, fails the build.
+    pub overrides: Vec<(String, String)>,
+    /// Namespace each file's resources under its file stem (`auth.xml` →
+    /// `r::auth`). `values*.xml` files and locale suffixes are left out.
+    pub file_as_namespace: bool,
+    /// Generate an empty `R` (with a warning) when `resources_dir` is
+    /// missing or has no XML files, instead of failing.
+    pub allow_missing_resources: bool,
+    /// Load `.xml` files whose name starts with `.`; by default they are
+    /// skipped (with a note), as editors leave such lock and backup files
+    pub include_hidden_files: bool,
+    /// Encoding of files without an XML declaration naming one (e.g.
+    /// `"ISO-8859-1"` for legacy translations); `None` reads them as
+    /// UTF-8. A declared encoding always wins.
+    pub default_encoding: Option<String>,
+    /// Crate version exposed as the builtin `r::build::VERSION`, which
+    /// resources can reference as `@build/version`. `None` leaves the
+    /// `build` namespace out.
+    pub build_version: Option<String>,
+    /// Name of the generated root module (default `r`). The struct becomes
+    /// its PascalCase form (`R`), the test module `<root>_tests`, and the
+    /// smoke tests `__<root>_resources_smoke`, so artifacts with different
+    /// roots can be included side by side.
+    pub root_module: String,
+    /// Auxiliary resource sets, as `(name, directory)` pairs (e.g.
+    /// `("bench", "res/bench")`). Each is built on its own into the root
+    /// module `<root>_<name>` (`r_bench`), like test resources: its keys
+    /// never clash with the main ones and references do not cross sets.
+    pub extra_sets: Vec<(String, std::path::PathBuf)>,
+    /// Generate the named-argument variants (`<name>_args()` builder and
+    /// `<name>_with(Args { .. })`) for every template with parameters, as
+    /// if each had `style="builder"`; `style="plain"` opts one out.
+    pub template_builders: bool,
+    /// Print the analysis notes along with the warnings, e.g. which
+    /// elements a `profile` attribute left out of this build
+    pub verbose: bool,
+    /// List the loaded resource files, in priority order and with the
+    /// profile, in a comment at the top of the artifact; turn it off to
+    /// keep the artifact free of file names
+    pub emit_inputs: bool,
+    /// Run the whole pipeline but leave `OUT_DIR` alone, reporting what
+    /// would be written instead. Building never writes; this tells
+    /// [`crate::generator::build`] and [`crate::generator::emit_artifacts`]
+    /// not to either.
+    pub dry_run: bool,
 }
 
 impl BuildPlan {
@@ -30,7 +143,98 @@ impl BuildPlan {
         Self {
             resources_dir,
             tests_resources_dir,
+            embed_test_resources: true,
             profile: profile.into(),
+            emit_smoke_tests: false,
+            emit_to_json: false,
+            emit_meta: false,
+            emit_lookup: false,
+            emit_kind_modules: false,
+            emit_resource_markers: false,
+            intern_strings: false,
+            group_by_origin: false,
+            naming: NamingConfig::default(),
+            custom_types: Vec::new(),
+            tag_aliases: Vec::new(),
+            overrides: Vec::new(),
+            file_as_namespace: false,
+            allow_missing_resources: false,
+            include_hidden_files: false,
+            default_encoding: None,
+            build_version: None,
+            root_module: "r".to_string(),
+            extra_sets: Vec::new(),
+            template_builders: false,
+            verbose: false,
+            emit_inputs: true,
+            dry_run: false,
+        }
+    }
+
+    /// This plan with [`Self::dry_run`] set
+    #[allow(dead_code)] // Public API, may be used by consumers
+    #[must_use]
+    pub fn dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
+    /// Adds resource types for tags the crate does not know about, e.g. a
+    /// `<shader name="...">` handler. Resources written with one of a type's
+    /// `xml_tags` are validated, built, and emitted by that type.
+    #[allow(dead_code)] // Public API, may be used by consumers
+    #[must_use]
+    pub fn with_custom_types(
+        mut self,
+        types: Vec<Box<dyn ResourceType>>,
+    ) -> Self {
+        self.custom_types.extend(types.into_iter().map(Arc::from));
+        self
+    }
+
+    /// Plan building the extra set `name` from `dir`: same types and
+    /// settings, rooted at `<root>_<name>`, without test resources, the
+    /// `build` namespace, or further sets
+    pub(crate) fn extra_set_plan(
+        &self,
+        name: &str,
+        dir: &std::path::Path,
+    ) -> Self {
+        Self {
+            resources_dir: dir.to_path_buf(),
+            tests_resources_dir: None,
+            embed_test_resources: false,
+            profile: self.profile.clone(),
+            emit_smoke_tests: self.emit_smoke_tests,
+            emit_to_json: self.emit_to_json,
+            emit_meta: self.emit_meta,
+            emit_lookup: self.emit_lookup,
+            emit_kind_modules: self.emit_kind_modules,
+            emit_resource_markers: self.emit_resource_markers,
+            intern_strings: self.intern_strings,
+            group_by_origin: self.group_by_origin,
+            naming: self.naming,
+            custom_types: self.custom_types.clone(),
+            tag_aliases: self.tag_aliases.clone(),
+            // Keys name main resources
+            overrides: Vec::new(),
+            file_as_namespace: self.file_as_namespace,
+            // A set is listed on purpose, so it must have resources
+            allow_missing_resources: false,
+            include_hidden_files: self.include_hidden_files,
+            default_encoding: self.default_encoding.clone(),
+            build_version: None,
+            root_module: format!("{}_{name}", self.root_module),
+            extra_sets: Vec::new(),
+            template_builders: self.template_builders,
+            verbose: self.verbose,
+            emit_inputs: self.emit_inputs,
+            dry_run: self.dry_run,
         }
     }
+
+    /// Built-in types plus [`Self::custom_types`]
+    pub fn type_registry(&self) -> TypeRegistry {
+        TypeRegistry::with_custom_types(&self.custom_types)
+    }
 }
@@ -1,7 +1,14 @@
-use crate::generator::parsing::ParsedResourceFile;
-
-use super::model::{ResourceGraph, ResourceKey};
-use super::types::TypeRegistry;
+use crate::generator::parsing::{
+    InterpolationPart as ParsedPart, ParsedResource, ParsedResourceFile,
+    ResourceKind as ParsedKind, ScalarValue,
+};
+
+use super::model::{
+    Assertion, ErrorEnum, InterpolationPart, LocaleRequirement, NamespaceDeclaration, NamespaceDoc, NamespaceFeature,
+    NamespaceVisibility, RejectedResource, ResourceGraph, ResourceKey,
+    ResourceNode, ResourceOrigin, ResourceValue, UnbalancedRegion,
+};
+use super::types::{ResourceType, TypeRegistry};
 
 #[derive(Default)]
 pub struct ResourceGraphBuilder {
@@ -10,11 +17,11 @@ pub struct ResourceGraphBuilder {
 }
 
 impl ResourceGraphBuilder {
+    #[allow(dead_code)] // Used in tests and public API
     pub fn new() -> Self {
         Self::default()
     }
 
-    #[allow(dead_code)] // Reserved for future use
     pub fn with_registry(registry: TypeRegistry) -> Self {
         Self {
             graph: ResourceGraph::default(),
@@ -22,17 +29,76 @@ impl ResourceGraphBuilder {
         }
     }
 
+    #[allow(dead_code)] // Used in tests and public API
     pub fn from_parsed_files(
         files: &[ParsedResourceFile],
     ) -> ResourceGraph {
-        let mut builder = Self::new();
+        Self::new().build(files)
+    }
+
+    /// Ingests `files` with this builder's registry
+    pub fn build(mut self, files: &[ParsedResourceFile]) -> ResourceGraph {
         for file in files {
-            builder.ingest_file(file);
+            self.ingest_file(file);
         }
-        builder.graph
+        self.graph
     }
 
     fn ingest_file(&mut self, file: &ParsedResourceFile) {
+        for assertion in &file.assertions {
+            self.graph.add_assertion(Assertion {
+                expr: assertion.expr.clone(),
+                namespace: assertion.namespace.clone(),
+                origin: ResourceOrigin::new(file.path.clone(), file.is_test),
+            });
+        }
+        for requirement in &file.locale_requirements {
+            self.graph.add_locale_requirement(LocaleRequirement {
+                namespace: requirement.namespace.clone(),
+                locales: requirement.locales.clone(),
+            });
+        }
+        for declared in &file.namespace_visibilities {
+            self.graph.add_namespace_visibility(NamespaceVisibility {
+                namespace: declared.namespace.clone(),
+                visibility: declared.visibility.clone(),
+                origin: ResourceOrigin::new(file.path.clone(), file.is_test),
+            });
+        }
+        for declared in &file.namespace_features {
+            self.graph.add_namespace_feature(NamespaceFeature {
+                namespace: declared.namespace.clone(),
+                feature: declared.feature.clone(),
+                origin: ResourceOrigin::new(file.path.clone(), file.is_test),
+            });
+        }
+        for declared in &file.namespace_docs {
+            self.graph.add_namespace_doc(NamespaceDoc {
+                namespace: declared.namespace.clone(),
+                doc: declared.doc.clone(),
+            });
+        }
+        for declared in &file.namespace_declarations {
+            self.graph.add_namespace_declaration(NamespaceDeclaration {
+                namespace: declared.namespace.clone(),
+                extend: declared.extend,
+                origin: ResourceOrigin::new(file.path.clone(), file.is_test),
+            });
+        }
+        for declared in &file.error_enums {
+            self.graph.add_error_enum(ErrorEnum {
+                namespace: declared.namespace.clone(),
+                name: declared.name.clone(),
+                origin: ResourceOrigin::new(file.path.clone(), file.is_test),
+            });
+        }
+        for marker in &file.unbalanced_regions {
+            self.graph.add_unbalanced_region(UnbalancedRegion {
+                line: marker.line,
+                message: marker.message.clone(),
+                origin: ResourceOrigin::new(file.path.clone(), file.is_test),
+            });
+        }
         for resource in &file.resources {
             let key = ResourceKey::from_path(&resource.name);
             let origin = super::ResourceOrigin::new(
@@ -40,37 +106,147 @@ impl ResourceGraphBuilder {
                 file.is_test,
             );
 
-            // Map ParsedKind to type name
-            let type_name = match resource.kind {
-                crate::generator::parsing::ResourceKind::String => {
-                    "string"
-                }
-                crate::generator::parsing::ResourceKind::Number => {
-                    "number"
-                }
-                crate::generator::parsing::ResourceKind::Bool => {
-                    "bool"
-                }
-                crate::generator::parsing::ResourceKind::Color => {
-                    "color"
-                }
-                crate::generator::parsing::ResourceKind::Template => {
-                    "template"
-                }
-            };
-
-            let Some(ty) = self.registry.find_by_name(type_name) else {
+            let Some(ty) = handler_for(&self.registry, &resource.kind)
+            else {
                 continue;
             };
-            let Some(node) = ty.build_node(resource, origin) else {
+            if let Err(message) = ty.validate(resource) {
+                self.graph.reject(RejectedResource {
+                    key,
+                    origin,
+                    message,
+                });
                 continue;
+            }
+            let node = match &resource.value {
+                ScalarValue::Reference { .. } => self.build_reference(
+                    resource,
+                    ty.resource_kind(),
+                    origin.clone(),
+                ),
+                _ => ty
+                    .build_node(resource, origin.clone())
+                    .ok_or_else(|| {
+                        format!("content is not a valid {}", ty.name())
+                    })
+                    .and_then(|node| {
+                        self.with_interpolated_kinds(resource, node)
+                    }),
             };
+            // A resource that cannot be built is reported, not dropped:
+            // a missing constant would only surface in the crate using it
+            let mut node = match node {
+                Ok(node) => node,
+                Err(message) => {
+                    self.graph.reject(RejectedResource {
+                        key,
+                        origin,
+                        message,
+                    });
+                    continue;
+                }
+            };
+            node.attributes = resource.attributes.clone();
             let is_duplicate = self.graph.insert(key, node);
             if is_duplicate {
                 // Duplicate detected - will be reported as warning in analysis
             }
         }
     }
+
+    /// References are type-agnostic: the node keeps the declared kind and the
+    /// target is resolved during analysis
+    fn build_reference(
+        &self,
+        resource: &ParsedResource,
+        declared_kind: super::ResourceKind,
+        origin: ResourceOrigin,
+    ) -> Result<ResourceNode, String> {
+        let ScalarValue::Reference { kind, path } = &resource.value else {
+            return Err("expected a reference".to_string());
+        };
+        Ok(ResourceNode {
+            kind: declared_kind,
+            value: ResourceValue::Reference {
+                target: ResourceKey::from_path(path),
+                kind: self.reference_kind(kind.as_deref(), path)?,
+            },
+            origin,
+            attributes: Default::default(),
+        })
+    }
+
+    /// Fills in the kinds named by the references of an interpolated
+    /// string (`@bool/flag`), which handlers leave out since only the
+    /// registry knows the types
+    fn with_interpolated_kinds(
+        &self,
+        resource: &ParsedResource,
+        mut node: ResourceNode,
+    ) -> Result<ResourceNode, String> {
+        let (ScalarValue::Interpolated(parsed), ResourceValue::Interpolated(parts)) =
+            (&resource.value, &mut node.value)
+        else {
+            return Ok(node);
+        };
+        for (parsed, part) in parsed.iter().zip(parts.iter_mut()) {
+            if let (
+                ParsedPart::Reference { kind: Some(name), path },
+                InterpolationPart::Reference { kind, .. },
+            ) = (parsed, part)
+            {
+                *kind = self.reference_kind(Some(name), path)?;
+            }
+        }
+        Ok(node)
+    }
+
+    /// Kind of the type named in a reference, `None` when it names none
+    fn reference_kind(
+        &self,
+        name: Option<&str>,
+        path: &str,
+    ) -> Result<Option<super::ResourceKind>, String> {
+        let Some(name) = name else {
+            return Ok(None);
+        };
+        let ty = self.registry.find_by_name(name).ok_or_else(|| {
+            format!("unknown type '{name}' in reference '{path}'")
+        })?;
+        Ok(Some(ty.resource_kind()))
+    }
+}
+
+/// Type handler for a parsed kind; custom tags are matched against the
+/// `xml_tags` of the registered types
+fn handler_for<'a>(
+    registry: &'a TypeRegistry,
+    kind: &ParsedKind,
+) -> Option<&'a dyn ResourceType> {
+    let type_name = match kind {
+        ParsedKind::String => "string",
+        ParsedKind::Number => "number",
+        ParsedKind::Bool => "bool",
+        ParsedKind::Color => "color",
+        ParsedKind::Char => "char",
+        ParsedKind::Percent => "percent",
+        ParsedKind::Cron => "cron",
+        ParsedKind::Size => "size",
+        ParsedKind::DateTime => "datetime",
+        ParsedKind::Date => "date",
+        ParsedKind::Version => "version",
+        ParsedKind::Dimension => "dimension",
+        ParsedKind::MimeMap => "mime-map",
+        ParsedKind::Enum => "enum",
+        ParsedKind::Array(item) => {
+            return registry.find_by_xml_tag(&format!("{item}-array"));
+        }
+        ParsedKind::Template => "template",
+        ParsedKind::Custom(tag) => {
+            return registry.find_by_xml_tag(tag);
+        }
+    };
+    registry.find_by_name(type_name)
 }
 
 #[cfg(test)]
@@ -94,6 +270,7 @@ mod tests {
                 name: "auth/title".to_string(),
                 kind: ParsedKind::String,
                 value: ScalarValue::Text("Login".to_string()),
+                attributes: Default::default(),
             }],
         );
 
@@ -125,11 +302,13 @@ mod tests {
                         value: "3".to_string(),
                         explicit_type: None,
                     },
+                    attributes: Default::default(),
                 },
                 ParsedResource {
                     name: "enabled".to_string(),
                     kind: ParsedKind::Bool,
                     value: ScalarValue::Bool(true),
+                    attributes: Default::default(),
                 },
             ],
         );
@@ -168,6 +347,7 @@ mod tests {
                 name: "app_name".to_string(),
                 kind: ParsedKind::String,
                 value: ScalarValue::Text("MyApp".to_string()),
+                attributes: Default::default(),
             }],
         );
         let file2 = ParsedResourceFile::new(
@@ -177,6 +357,7 @@ mod tests {
                 name: "version".to_string(),
                 kind: ParsedKind::String,
                 value: ScalarValue::Text("1.0.0".to_string()),
+                attributes: Default::default(),
             }],
         );
 
@@ -193,6 +374,7 @@ mod tests {
                 name: "title".to_string(),
                 kind: ParsedKind::String,
                 value: ScalarValue::Text("First".to_string()),
+                attributes: Default::default(),
             }],
         );
         let file2 = ParsedResourceFile::new(
@@ -202,6 +384,7 @@ mod tests {
                 name: "title".to_string(),
                 kind: ParsedKind::String,
                 value: ScalarValue::Text("Second".to_string()),
+                attributes: Default::default(),
             }],
         );
 
@@ -232,6 +415,7 @@ mod tests {
                 name: "primary_color".to_string(),
                 kind: ParsedKind::Color,
                 value: ScalarValue::Color("#FF0000".to_string()),
+                attributes: Default::default(),
             }],
         );
 
@@ -257,11 +441,13 @@ mod tests {
                     name: "auth/title".to_string(),
                     kind: ParsedKind::String,
                     value: ScalarValue::Text("Login".to_string()),
+                    attributes: Default::default(),
                 },
                 ParsedResource {
                     name: "auth/error/message".to_string(),
                     kind: ParsedKind::String,
                     value: ScalarValue::Text("Invalid".to_string()),
+                    attributes: Default::default(),
                 },
             ],
         );
@@ -297,6 +483,7 @@ mod tests {
                 name: "test_string".to_string(),
                 kind: ParsedKind::String,
                 value: ScalarValue::Text("Test".to_string()),
+                attributes: Default::default(),
             }],
         );
 
@@ -320,9 +507,32 @@ mod tests {
     }
 
     #[test]
-    fn ignores_invalid_resources() {
-        // Create a file with a resource that can't be built
-        // (e.g., invalid number format)
+    fn records_resources_rejected_by_validation() {
+        let parsed = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![ParsedResource {
+                name: "ui/zoom".to_string(),
+                kind: ParsedKind::Percent,
+                value: ScalarValue::Percent {
+                    value: "150%".to_string(),
+                    allow_over_100: false,
+                },
+                attributes: Default::default(),
+            }],
+        );
+
+        let graph = ResourceGraphBuilder::from_parsed_files(&[parsed]);
+        assert_eq!(graph.nodes().len(), 0);
+        let rejected = graph.rejected();
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0].key, ResourceKey::from_path("ui/zoom"));
+        assert_eq!(rejected[0].origin.file, PathBuf::from("values.xml"));
+        assert!(rejected[0].message.contains("over 100%"));
+    }
+
+    #[test]
+    fn reports_invalid_resources() {
         let parsed = ParsedResourceFile::new(
             PathBuf::from("values.xml"),
             false,
@@ -331,6 +541,7 @@ mod tests {
                     name: "valid_string".to_string(),
                     kind: ParsedKind::String,
                     value: ScalarValue::Text("Valid".to_string()),
+                    attributes: Default::default(),
                 },
                 ParsedResource {
                     name: "invalid_number".to_string(),
@@ -339,19 +550,45 @@ mod tests {
                         value: "not_a_number".to_string(),
                         explicit_type: None,
                     },
+                    attributes: Default::default(),
                 },
             ],
         );
 
         let graph = ResourceGraphBuilder::from_parsed_files(&[parsed]);
-        // Only valid resource should be added
         assert_eq!(graph.nodes().len(), 1);
-        
-        let key = ResourceKey::from_path("valid_string");
-        assert!(graph.get(&key).is_some());
-        
+        assert!(graph.get(&ResourceKey::from_path("valid_string")).is_some());
+
         let invalid_key = ResourceKey::from_path("invalid_number");
         assert!(graph.get(&invalid_key).is_none());
+        let [rejected] = graph.rejected() else {
+            panic!("expected one rejection: {:?}", graph.rejected());
+        };
+        assert_eq!(rejected.key, invalid_key);
+        assert!(rejected.origin.file.ends_with("values.xml"));
+        assert!(rejected.message.contains("not_a_number"), "{rejected:?}");
+    }
+
+    #[test]
+    fn reports_resources_their_handler_cannot_build() {
+        // Content that passes validation but pairs the wrong value with the
+        // kind, as a custom reader or handler might produce
+        let parsed = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![ParsedResource {
+                name: "accent".to_string(),
+                kind: ParsedKind::Color,
+                value: ScalarValue::Bool(true),
+                attributes: Default::default(),
+            }],
+        );
+        let graph = ResourceGraphBuilder::from_parsed_files(&[parsed]);
+        assert!(graph.nodes().is_empty());
+        let [rejected] = graph.rejected() else {
+            panic!("expected one rejection: {:?}", graph.rejected());
+        };
+        assert_eq!(rejected.message, "content is not a valid color");
     }
 
     #[test]
@@ -364,6 +601,7 @@ mod tests {
                     name: "app_name".to_string(),
                     kind: ParsedKind::String,
                     value: ScalarValue::Text("MyApp".to_string()),
+                    attributes: Default::default(),
                 },
                 ParsedResource {
                     name: "max_retries".to_string(),
@@ -372,16 +610,19 @@ mod tests {
                         value: "3".to_string(),
                         explicit_type: None,
                     },
+                    attributes: Default::default(),
                 },
                 ParsedResource {
                     name: "enabled".to_string(),
                     kind: ParsedKind::Bool,
                     value: ScalarValue::Bool(true),
+                    attributes: Default::default(),
                 },
                 ParsedResource {
                     name: "primary_color".to_string(),
                     kind: ParsedKind::Color,
                     value: ScalarValue::Color("#FF0000".to_string()),
+                    attributes: Default::default(),
                 },
             ],
         );
@@ -424,6 +665,7 @@ mod tests {
                 name: "title".to_string(),
                 kind: ParsedKind::String,
                 value: ScalarValue::Text("Hello".to_string()),
+                attributes: Default::default(),
             }],
         );
 
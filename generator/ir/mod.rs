@@ -10,11 +10,15 @@ pub mod types;
 
 pub use builder::ResourceGraphBuilder;
 pub use model::{
-    ResourceGraph, ResourceKey, ResourceKind, ResourceNode,
-    ResourceOrigin, ResourceValue,
+    Assertion, InterpolationPart, ResourceGraph, ResourceKey, ResourceKind,
+    ResourceNode, ResourceOrigin, ResourceValue, Visibility,
 };
 pub use types::TypeRegistry;
 
 // Re-export commonly used types from model (for advanced usage)
 #[allow(unused_imports)] // Public API, may be used by consumers
-pub use model::{NumberType, NumberValue};
+pub use model::{
+    ArrayItem, EnumVariant, ErrorEnum, LocaleRequirement, NamespaceDeclaration, NamespaceDoc,
+    NamespaceFeature, NamespaceVisibility, NumberType, NumberValue,
+    TemplateParam, UnbalancedRegion,
+};
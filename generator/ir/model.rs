@@ -1,9 +1,21 @@
 use std::collections::BTreeMap;
-use std::path::PathBuf;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 #[derive(Debug, Default)]
 pub struct ResourceGraph {
     nodes: BTreeMap<ResourceKey, Vec<ResourceNode>>, // Multiple nodes per key to track duplicates
+    rejected: Vec<RejectedResource>, // Resources whose content failed type validation
+    assertions: Vec<Assertion>, // <assert> invariants, checked during analysis
+    locale_requirements: Vec<LocaleRequirement>, // <ns require-locales="...">
+    namespace_visibilities: Vec<NamespaceVisibility>, // <ns visibility="...">
+    namespace_features: Vec<NamespaceFeature>, // <ns feature="...">
+    namespace_docs: Vec<NamespaceDoc>, // <ns doc="...">
+    namespace_declarations: Vec<NamespaceDeclaration>, // Every <ns>, with its `extend`
+    error_enums: Vec<ErrorEnum>, // <ns error-enum="...">
+    unbalanced_regions: Vec<UnbalancedRegion>, // Region comments without their counterpart
+    dependents: BTreeMap<ResourceKey, Vec<ResourceKey>>, // Reverse references, indexed before analysis
 }
 
 impl ResourceGraph {
@@ -18,6 +30,26 @@ impl ResourceGraph {
         is_duplicate
     }
 
+    /// Moves everything in `other` into this graph. Its nodes come after
+    /// the ones already here, so a key defined in both is a duplicate whose
+    /// first definition is this graph's, as for a later XML file.
+    pub fn merge(&mut self, other: ResourceGraph) {
+        for (key, nodes) in other.nodes {
+            self.nodes.entry(key).or_default().extend(nodes);
+        }
+        self.rejected.extend(other.rejected);
+        self.assertions.extend(other.assertions);
+        self.locale_requirements.extend(other.locale_requirements);
+        self.namespace_visibilities
+            .extend(other.namespace_visibilities);
+        self.namespace_features.extend(other.namespace_features);
+        self.namespace_docs.extend(other.namespace_docs);
+        self.namespace_declarations
+            .extend(other.namespace_declarations);
+        self.error_enums.extend(other.error_enums);
+        self.unbalanced_regions.extend(other.unbalanced_regions);
+    }
+
     pub fn nodes(&self) -> &BTreeMap<ResourceKey, Vec<ResourceNode>> {
         &self.nodes
     }
@@ -28,6 +60,22 @@ impl ResourceGraph {
         self.nodes.get(key).and_then(|nodes| nodes.first())
     }
 
+    /// Keys whose value references, interpolates, or calls `key`, in key
+    /// order. Empty until the pipeline indexes the graph for analysis
+    /// (`analysis::references::index_dependents`).
+    #[allow(dead_code)] // Used by the CLI and tooling
+    pub fn dependents_of(&self, key: &ResourceKey) -> &[ResourceKey] {
+        self.dependents.get(key).map_or(&[], Vec::as_slice)
+    }
+
+    /// Replaces the reverse-reference index
+    pub fn set_dependents(
+        &mut self,
+        dependents: BTreeMap<ResourceKey, Vec<ResourceKey>>,
+    ) {
+        self.dependents = dependents;
+    }
+
     /// Get all nodes for a key (including duplicates)
     pub fn get_all(
         &self,
@@ -36,11 +84,269 @@ impl ResourceGraph {
         self.nodes.get(key).map(|v| v.as_slice())
     }
 
+    /// Records a resource whose content was rejected by its type handler
+    pub fn reject(&mut self, rejected: RejectedResource) {
+        self.rejected.push(rejected);
+    }
+
+    pub fn rejected(&self) -> &[RejectedResource] {
+        &self.rejected
+    }
+
     /// Check if a key has duplicates
-    #[allow(dead_code)] // Reserved for future use
     pub fn has_duplicates(&self, key: &ResourceKey) -> bool {
         self.nodes.get(key).is_some_and(|nodes| nodes.len() > 1)
     }
+
+    pub fn add_assertion(&mut self, assertion: Assertion) {
+        self.assertions.push(assertion);
+    }
+
+    pub fn assertions(&self) -> &[Assertion] {
+        &self.assertions
+    }
+
+    pub fn add_locale_requirement(&mut self, requirement: LocaleRequirement) {
+        self.locale_requirements.push(requirement);
+    }
+
+    pub fn locale_requirements(&self) -> &[LocaleRequirement] {
+        &self.locale_requirements
+    }
+
+    pub fn add_namespace_visibility(&mut self, declared: NamespaceVisibility) {
+        self.namespace_visibilities.push(declared);
+    }
+
+    pub fn namespace_visibilities(&self) -> &[NamespaceVisibility] {
+        &self.namespace_visibilities
+    }
+
+    /// Visibility of the module generated for `namespace`: the most
+    /// restrictive one declared for it, `pub` if none (or none valid) is
+    pub fn namespace_visibility(&self, namespace: &[String]) -> Visibility {
+        self.namespace_visibilities
+            .iter()
+            .filter(|declared| declared.namespace == namespace)
+            .filter_map(|declared| Visibility::parse(&declared.visibility))
+            .max()
+            .unwrap_or_default()
+    }
+
+    pub fn add_namespace_feature(&mut self, declared: NamespaceFeature) {
+        self.namespace_features.push(declared);
+    }
+
+    pub fn namespace_features(&self) -> &[NamespaceFeature] {
+        &self.namespace_features
+    }
+
+    pub fn add_namespace_doc(&mut self, declared: NamespaceDoc) {
+        self.namespace_docs.push(declared);
+    }
+
+    /// Inner documentation of the module generated for `namespace`: every
+    /// distinct `doc` declared for it, in file order, a blank line apart
+    pub fn namespace_doc(&self, namespace: &[String]) -> Option<String> {
+        let mut docs: Vec<&str> = Vec::new();
+        for declared in &self.namespace_docs {
+            let doc = declared.doc.trim();
+            if declared.namespace == namespace
+                && !doc.is_empty()
+                && !docs.contains(&doc)
+            {
+                docs.push(doc);
+            }
+        }
+        (!docs.is_empty()).then(|| docs.join("\n\n"))
+    }
+
+    pub fn add_namespace_declaration(&mut self, declared: NamespaceDeclaration) {
+        self.namespace_declarations.push(declared);
+    }
+
+    /// Every `<ns>` of every file, in file order
+    pub fn namespace_declarations(&self) -> &[NamespaceDeclaration] {
+        &self.namespace_declarations
+    }
+
+    pub fn add_error_enum(&mut self, declared: ErrorEnum) {
+        self.error_enums.push(declared);
+    }
+
+    pub fn error_enums(&self) -> &[ErrorEnum] {
+        &self.error_enums
+    }
+
+    /// Name of the error enum generated in the module of `namespace`: the
+    /// first one declared for it
+    pub fn error_enum_of(&self, namespace: &[String]) -> Option<&str> {
+        self.error_enums
+            .iter()
+            .find(|declared| declared.namespace == namespace)
+            .map(|declared| declared.name.as_str())
+    }
+
+    pub fn add_unbalanced_region(&mut self, marker: UnbalancedRegion) {
+        self.unbalanced_regions.push(marker);
+    }
+
+    pub fn unbalanced_regions(&self) -> &[UnbalancedRegion] {
+        &self.unbalanced_regions
+    }
+
+    /// Cargo features gating the module generated for `namespace` itself
+    /// (not its parents), sorted and deduplicated
+    pub fn features_of_namespace(&self, namespace: &[String]) -> Vec<&str> {
+        let mut features: Vec<&str> = self
+            .namespace_features
+            .iter()
+            .filter(|declared| declared.namespace == namespace)
+            .map(|declared| declared.feature.as_str())
+            .collect();
+        features.sort_unstable();
+        features.dedup();
+        features
+    }
+
+    /// Every cargo feature needed for the resource at `key` to exist: those
+    /// of all the namespaces containing it
+    pub fn required_features(&self, key: &ResourceKey) -> Vec<&str> {
+        let mut features: Vec<&str> = (1..=key.namespace.len())
+            .flat_map(|depth| {
+                self.features_of_namespace(&key.namespace[..depth])
+            })
+            .collect();
+        features.sort_unstable();
+        features.dedup();
+        features
+    }
+
+    /// How widely the resource at `key` can be reached: the most
+    /// restrictive of its own visibility and its namespaces' modules
+    pub fn effective_visibility(&self, key: &ResourceKey) -> Visibility {
+        let own = self.get_all(key).and_then(<[_]>::first).map_or(
+            Visibility::Public,
+            ResourceNode::visibility,
+        );
+        (1..=key.namespace.len())
+            .map(|depth| self.namespace_visibility(&key.namespace[..depth]))
+            .fold(own, Visibility::max)
+    }
+}
+
+/// Resource left out of the graph because its content is invalid for its type
+#[derive(Debug, Clone)]
+pub struct RejectedResource {
+    pub key: ResourceKey,
+    pub origin: ResourceOrigin,
+    pub message: String,
+}
+
+/// Invariant declared with `<assert expr="..."/>`
+#[derive(Debug, Clone)]
+pub struct Assertion {
+    pub expr: String,
+    /// Namespace the assertion was declared in; identifiers resolve from here
+    pub namespace: Vec<String>,
+    pub origin: ResourceOrigin,
+}
+
+/// Locales whose namespaces below `namespace` must define the same keys,
+/// declared with `<ns require-locales="en,fr">`
+#[derive(Debug, Clone)]
+pub struct LocaleRequirement {
+    pub namespace: Vec<String>,
+    pub locales: Vec<String>,
+}
+
+/// Visibility declared with `<ns visibility="...">`
+#[derive(Debug, Clone)]
+pub struct NamespaceVisibility {
+    pub namespace: Vec<String>,
+    /// Value as written, validated during analysis
+    pub visibility: String,
+    pub origin: ResourceOrigin,
+}
+
+/// Cargo feature gating a namespace, declared with `<ns feature="...">`
+#[derive(Debug, Clone)]
+pub struct NamespaceFeature {
+    pub namespace: Vec<String>,
+    pub feature: String,
+    pub origin: ResourceOrigin,
+}
+
+/// Documentation of a namespace module, declared with `<ns doc="...">`
+#[derive(Debug, Clone)]
+pub struct NamespaceDoc {
+    pub namespace: Vec<String>,
+    pub doc: String,
+}
+
+/// An `<ns>` element; `extend="true"` continues a namespace another file
+/// opens
+#[derive(Debug, Clone)]
+pub struct NamespaceDeclaration {
+    pub namespace: Vec<String>,
+    pub extend: bool,
+    pub origin: ResourceOrigin,
+}
+
+/// Enum generated from the message and code pairs of a namespace, declared
+/// with `<ns error-enum="...">`
+#[derive(Debug, Clone)]
+pub struct ErrorEnum {
+    pub namespace: Vec<String>,
+    /// Type name as written, validated during analysis
+    pub name: String,
+    pub origin: ResourceOrigin,
+}
+
+/// `<!-- region: name -->` or `<!-- endregion -->` comment without its
+/// counterpart
+#[derive(Debug, Clone)]
+pub struct UnbalancedRegion {
+    /// Line of the marker in `origin.file`
+    pub line: usize,
+    pub message: String,
+    pub origin: ResourceOrigin,
+}
+
+/// Visibility of a generated item or module, from the `visibility`
+/// attribute. Ordered from the most to the least visible.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub enum Visibility {
+    /// `pub` (the default)
+    #[default]
+    Public,
+    /// `pub(crate)`
+    Crate,
+    /// `pub(super)`
+    Super,
+}
+
+impl Visibility {
+    /// Parses the attribute value: `pub`, `crate`, or `super`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "pub" => Some(Self::Public),
+            "crate" => Some(Self::Crate),
+            "super" => Some(Self::Super),
+            _ => None,
+        }
+    }
+
+    /// Rust visibility keyword
+    pub fn keyword(self) -> &'static str {
+        match self {
+            Self::Public => "pub",
+            Self::Crate => "pub(crate)",
+            Self::Super => "pub(super)",
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -50,7 +356,7 @@ pub struct ResourceKey {
 }
 
 impl ResourceKey {
-    #[allow(dead_code)] // Reserved for future use
+    #[allow(dead_code)] // Public API, may be used by consumers
     pub fn new(
         namespace: Vec<String>,
         name: impl Into<String>,
@@ -82,20 +388,108 @@ impl ResourceKey {
     }
 }
 
+/// Nodes compare and hash by kind, value, and attributes. The origin is
+/// left out, so the same resource read from two files is equal; compare
+/// `origin` as well when where it comes from matters.
 #[derive(Debug, Clone)]
 pub struct ResourceNode {
     pub kind: ResourceKind,
     pub value: ResourceValue,
     pub origin: ResourceOrigin,
+    /// Attributes of the resource tag other than `name` (e.g. `allow-secret`)
+    pub attributes: BTreeMap<String, String>,
+}
+
+impl ResourceNode {
+    /// Node without attributes, e.g. for a resource built in a build script
+    /// rather than read from XML. `value` must be the shape `kind`'s type
+    /// builds (`ResourceValue::String` for `ResourceKind::String`); it is
+    /// emitted as is, without the checks done on XML content.
+    #[allow(dead_code)] // Public API, may be used by consumers
+    pub fn new(
+        kind: ResourceKind,
+        value: ResourceValue,
+        origin: ResourceOrigin,
+    ) -> Self {
+        Self {
+            kind,
+            value,
+            origin,
+            attributes: BTreeMap::new(),
+        }
+    }
+
+    /// Sets an attribute as if written on the resource tag (`visibility`,
+    /// `allow-secret`, ...)
+    #[allow(dead_code)] // Public API, may be used by consumers
+    #[must_use]
+    pub fn with_attribute(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.attributes.insert(name.into(), value.into());
+        self
+    }
+
+    /// True when the attribute is set to `"true"`
+    pub fn has_flag(&self, name: &str) -> bool {
+        self.attributes.get(name).is_some_and(|value| value == "true")
+    }
+
+    /// Visibility from the `visibility` attribute (set on the resource or
+    /// inherited from its `<ns>`); `pub` when absent or invalid, invalid
+    /// values being reported by analysis
+    pub fn visibility(&self) -> Visibility {
+        self.attributes
+            .get("visibility")
+            .and_then(|value| Visibility::parse(value))
+            .unwrap_or_default()
+    }
+
+    /// Documentation from the `doc` attribute, if set and not blank
+    pub fn doc(&self) -> Option<&str> {
+        self.attributes
+            .get("doc")
+            .map(|doc| doc.trim())
+            .filter(|doc| !doc.is_empty())
+    }
+}
+
+impl PartialEq for ResourceNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && self.value == other.value
+            && self.attributes == other.attributes
+    }
+}
+
+impl Eq for ResourceNode {}
+
+impl Hash for ResourceNode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.kind.hash(state);
+        self.value.hash(state);
+        self.attributes.hash(state);
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[allow(dead_code)] // Some variants reserved for future use
 pub enum ResourceKind {
     String,
     Number,
     Bool,
     Color,
+    Char,
+    Percent,
+    Cron,
+    Size,
+    DateTime,
+    Date,
+    Version,
+    MimeMap,
+    Enum,
     Url,
     Dimension,
     Array(String),
@@ -103,8 +497,37 @@ pub enum ResourceKind {
     Custom(String),
 }
 
+impl ResourceKind {
+    /// Tag of the kind as written in XML (`string-array`), and in
+    /// references (`@string/...`)
+    #[must_use]
+    pub fn tag(&self) -> String {
+        let name = match self {
+            Self::String => "string",
+            Self::Number => "number",
+            Self::Bool => "bool",
+            Self::Color => "color",
+            Self::Char => "char",
+            Self::Percent => "percent",
+            Self::Cron => "cron",
+            Self::Size => "size",
+            Self::DateTime => "datetime",
+            Self::Date => "date",
+            Self::Version => "version",
+            Self::MimeMap => "mime-map",
+            Self::Enum => "enum",
+            Self::Url => "url",
+            Self::Dimension => "dimension",
+            Self::Template => "template",
+            Self::Array(item) => return format!("{item}-array"),
+            Self::Custom(tag) => tag,
+        };
+        name.to_string()
+    }
+}
+
 /// Represents the explicit Rust type requested via `<number type="...">`
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum NumberType {
     I8,
     I16,
@@ -134,9 +557,19 @@ impl NumberType {
             Self::F64 => "f64",
         }
     }
+
+    /// `f32` or `f64`
+    #[must_use]
+    pub fn is_float(self) -> bool {
+        matches!(self, Self::F32 | Self::F64)
+    }
 }
 
-/// Represents a parsed numeric value
+/// Represents a parsed numeric value.
+///
+/// Values are compared as written, so `Int(1)` and `Float(1.0)` differ;
+/// compare `as_f64_lossy()` to compare magnitudes. Unlike `f64`, a NaN
+/// float equals itself, so values can be map keys.
 #[derive(Debug, Clone)]
 pub enum NumberValue {
     /// Fits into i64
@@ -149,26 +582,323 @@ pub enum NumberValue {
     Typed { literal: String, ty: NumberType },
 }
 
-#[derive(Debug, Clone)]
+impl NumberValue {
+    /// The nearest `f64`; BigDecimals lose the digits beyond its precision
+    #[allow(dead_code)] // Public API, may be used by consumers
+    #[must_use]
+    pub fn as_f64_lossy(&self) -> f64 {
+        match self {
+            Self::Int(value) => *value as f64,
+            Self::Float(value) => *value,
+            Self::BigDecimal(literal) | Self::Typed { literal, .. } => {
+                literal.parse().unwrap_or(f64::NAN)
+            }
+        }
+    }
+
+    /// The value when it is a whole number that fits in an `i64`, whatever
+    /// its type: `Float(3.0)` is `Some(3)`, `Float(2.5)` and a `u64` above
+    /// `i64::MAX` are `None`
+    #[allow(dead_code)] // Public API, may be used by consumers
+    #[must_use]
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::Int(value) => Some(*value),
+            Self::Float(value) => float_as_i64(*value),
+            Self::Typed { literal, ty } if ty.is_float() => {
+                float_as_i64(literal.parse().ok()?)
+            }
+            Self::Typed { literal, .. } => literal.parse().ok(),
+            Self::BigDecimal(literal) => {
+                let value = bigdecimal::BigDecimal::from_str(literal).ok()?;
+                if !value.is_integer() {
+                    return None;
+                }
+                bigdecimal::ToPrimitive::to_i64(&value)
+            }
+        }
+    }
+}
+
+/// `value` as an `i64` when it has no fraction and is in range
+fn float_as_i64(value: f64) -> Option<i64> {
+    // -2^63 is exact as an f64, 2^63 is the first value out of range
+    let limit = -(i64::MIN as f64);
+    let in_range = (-limit..limit).contains(&value);
+    (value.fract() == 0.0 && in_range).then_some(value as i64)
+}
+
+/// Bits identifying a float for `Eq` and `Hash`: `-0.0` is `0.0`, and every
+/// NaN is the same NaN
+fn float_bits(value: f64) -> u64 {
+    if value == 0.0 {
+        0
+    } else if value.is_nan() {
+        f64::NAN.to_bits()
+    } else {
+        value.to_bits()
+    }
+}
+
+impl PartialEq for NumberValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Int(a), Self::Int(b)) => a == b,
+            (Self::Float(a), Self::Float(b)) => {
+                float_bits(*a) == float_bits(*b)
+            }
+            (Self::BigDecimal(a), Self::BigDecimal(b)) => a == b,
+            (
+                Self::Typed { literal, ty },
+                Self::Typed {
+                    literal: other_literal,
+                    ty: other_ty,
+                },
+            ) => literal == other_literal && ty == other_ty,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for NumberValue {}
+
+impl Hash for NumberValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Self::Int(value) => value.hash(state),
+            Self::Float(value) => float_bits(*value).hash(state),
+            Self::BigDecimal(literal) => literal.hash(state),
+            Self::Typed { literal, ty } => (literal, ty).hash(state),
+        }
+    }
+}
+
+/// Values compare field by field, numbers and array items as described on
+/// [`NumberValue`]; percentages and dimensions are finite once built.
+#[derive(Debug, Clone, PartialEq)]
 pub enum ResourceValue {
     String(String),
     Number(NumberValue),
     Bool(bool),
     Color(String),
+    Char(char),
+    /// Percentage normalized to a ratio (`37.5%` → 0.375), with its original text
+    Percent { ratio: f64, raw: String },
+    /// Cron expression as written, and its 6-field form used for the schedule
+    Cron { expression: String, schedule: String },
+    /// Byte count, with the text it was written as (`25MB`)
+    Size { bytes: u64, raw: String },
+    /// Seconds and nanoseconds since the Unix epoch, with the RFC 3339 text
+    /// it was written as
+    DateTime {
+        timestamp: i64,
+        nanos: u32,
+        raw: String,
+    },
+    /// Calendar date, already checked against the length of the month
+    Date { year: i32, month: u8, day: u8 },
+    /// `MAJOR.MINOR.PATCH`
+    Version { major: u64, minor: u64, patch: u64 },
+    /// Length with the `DimensionUnit` variant of its unit (`Dp`), and the
+    /// text it was written as (`16dp`)
+    Dimension {
+        value: f32,
+        unit: String,
+        raw: String,
+    },
+    /// `(extension, MIME type)` pairs, in declaration order
+    MimeMap(Vec<(String, String)>),
+    /// Items in the order they are emitted: declaration order, or sorted
+    /// for `sorted="true"`
+    Array(Vec<ArrayItem>),
+    /// Rows of a two-dimensional array, in declaration order; rows may
+    /// differ in length
+    ArrayRows(Vec<Vec<ArrayItem>>),
+    /// Variants in declaration order; `repr` is the integer type backing
+    /// them (`u8`), in which case every variant has a discriminant
+    Enum {
+        repr: Option<String>,
+        variants: Vec<EnumVariant>,
+    },
     Template {
         text: String,
         params: Vec<TemplateParam>,
     },
-    // TODO: add arrays, references, etc.
+    /// Reference to another resource (`@string/app_name`), resolved at emission.
+    /// `kind` is the kind named in the reference, if any.
+    Reference {
+        target: ResourceKey,
+        kind: Option<ResourceKind>,
+    },
+    /// String with embedded references, flattened to text at emission
+    Interpolated(Vec<InterpolationPart>),
+}
+
+impl ResourceValue {
+    /// Kind of resource this shape of value is built for. `None` when the
+    /// value alone does not tell: a reference that names no kind, or an
+    /// array without a literal item. Custom types reuse these shapes, so
+    /// `ResourceNode::kind` stays the authority.
+    #[allow(dead_code)] // Public API, may be used by consumers
+    #[must_use]
+    pub fn kind(&self) -> Option<ResourceKind> {
+        let kind = match self {
+            Self::String(_) | Self::Interpolated(_) => ResourceKind::String,
+            Self::Number(_) => ResourceKind::Number,
+            Self::Bool(_) => ResourceKind::Bool,
+            Self::Color(_) => ResourceKind::Color,
+            Self::Char(_) => ResourceKind::Char,
+            Self::Percent { .. } => ResourceKind::Percent,
+            Self::Cron { .. } => ResourceKind::Cron,
+            Self::Size { .. } => ResourceKind::Size,
+            Self::DateTime { .. } => ResourceKind::DateTime,
+            Self::Date { .. } => ResourceKind::Date,
+            Self::Version { .. } => ResourceKind::Version,
+            Self::Dimension { .. } => ResourceKind::Dimension,
+            Self::MimeMap(_) => ResourceKind::MimeMap,
+            Self::Enum { .. } => ResourceKind::Enum,
+            Self::Template { .. } => ResourceKind::Template,
+            Self::Reference { kind, .. } => return kind.clone(),
+            Self::Array(items) => return array_kind(items),
+            Self::ArrayRows(rows) => return array_kind(rows.iter().flatten()),
+        };
+        Some(kind)
+    }
+}
+
+/// `ResourceKind::Array` named after the first literal item
+fn array_kind<'a>(
+    items: impl IntoIterator<Item = &'a ArrayItem>,
+) -> Option<ResourceKind> {
+    let item = items.into_iter().find_map(|item| match item {
+        ArrayItem::String(_) => Some("string"),
+        ArrayItem::Int(_) => Some("int"),
+        ArrayItem::Float(_) => Some("float"),
+        ArrayItem::Duration(_) => Some("duration"),
+        ArrayItem::Size(_) => Some("size"),
+        ArrayItem::Reference(_) => None,
+    })?;
+    Some(ResourceKind::Array(item.to_string()))
+}
+
+impl Eq for ResourceValue {}
+
+impl Hash for ResourceValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Self::String(text) | Self::Color(text) => text.hash(state),
+            Self::Number(number) => number.hash(state),
+            Self::Bool(value) => value.hash(state),
+            Self::Char(value) => value.hash(state),
+            Self::Percent { ratio, raw } => {
+                (float_bits(*ratio), raw).hash(state);
+            }
+            Self::Cron {
+                expression,
+                schedule,
+            } => (expression, schedule).hash(state),
+            Self::Size { bytes, raw } => (bytes, raw).hash(state),
+            Self::DateTime {
+                timestamp,
+                nanos,
+                raw,
+            } => (timestamp, nanos, raw).hash(state),
+            Self::Date { year, month, day } => (year, month, day).hash(state),
+            Self::Version {
+                major,
+                minor,
+                patch,
+            } => (major, minor, patch).hash(state),
+            Self::Dimension { value, unit, raw } => {
+                (float_bits(f64::from(*value)), unit, raw).hash(state);
+            }
+            Self::MimeMap(pairs) => pairs.hash(state),
+            Self::Array(items) => items.hash(state),
+            Self::ArrayRows(rows) => rows.hash(state),
+            Self::Enum { repr, variants } => (repr, variants).hash(state),
+            Self::Template { text, params } => (text, params).hash(state),
+            Self::Reference { target, kind } => (target, kind).hash(state),
+            Self::Interpolated(parts) => parts.hash(state),
+        }
+    }
 }
 
+/// Item of a `<string-array>`, `<int-array>`, `<float-array>`,
+/// `<duration-array>`, or `<size-array>`; floats compare like
+/// [`NumberValue::Float`]
 #[derive(Debug, Clone)]
+pub enum ArrayItem {
+    String(String),
+    Int(i64),
+    Float(f64),
+    Duration(std::time::Duration),
+    /// Bytes
+    Size(u64),
+    /// `<item>@string/api/error</item>`, inlined once resolved
+    Reference(ResourceKey),
+}
+
+impl PartialEq for ArrayItem {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::String(a), Self::String(b)) => a == b,
+            (Self::Int(a), Self::Int(b)) => a == b,
+            (Self::Float(a), Self::Float(b)) => {
+                float_bits(*a) == float_bits(*b)
+            }
+            (Self::Duration(a), Self::Duration(b)) => a == b,
+            (Self::Size(a), Self::Size(b)) => a == b,
+            (Self::Reference(a), Self::Reference(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for ArrayItem {}
+
+impl Hash for ArrayItem {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Self::String(text) => text.hash(state),
+            Self::Int(value) => value.hash(state),
+            Self::Float(value) => float_bits(*value).hash(state),
+            Self::Duration(value) => value.hash(state),
+            Self::Size(value) => value.hash(state),
+            Self::Reference(key) => key.hash(state),
+        }
+    }
+}
+
+/// Item of an `<enum>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EnumVariant {
+    /// Item text as written (`low`), returned by the generated `as_str()`
+    pub name: String,
+    pub discriminant: Option<i128>,
+}
+
+/// Piece of an interpolated string
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InterpolationPart {
+    Text(String),
+    /// Embedded reference; `kind` is the kind named in it, if any, checked
+    /// against the target during analysis
+    Reference {
+        target: ResourceKey,
+        kind: Option<ResourceKind>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TemplateParam {
     pub name: String,
     pub value: TemplateParamValue, // Store parameter type information
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum TemplateParamValue {
     String,
     Number { explicit_type: Option<String> }, // Store explicit_type for numbers (e.g., "bigdecimal", "i32")
@@ -176,7 +906,7 @@ pub enum TemplateParamValue {
     Color,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[allow(dead_code)] // Fields reserved for future use (line, profile, is_test)
 pub struct ResourceOrigin {
     pub file: PathBuf,
@@ -186,6 +916,12 @@ pub struct ResourceOrigin {
 }
 
 impl ResourceOrigin {
+    /// File of the resources generated from the plan rather than read
+    /// (`r::build::VERSION`)
+    pub(crate) const BUILTIN: &'static str = "<builtin>";
+
+    /// `file` is only used in diagnostics, so resources built in code can
+    /// name where they come from (`<database>`)
     pub fn new(file: PathBuf, is_test: bool) -> Self {
         Self {
             file,
@@ -194,9 +930,16 @@ impl ResourceOrigin {
             is_test,
         }
     }
+
+    /// Whether the resource was generated from the plan, see
+    /// [`Self::BUILTIN`]
+    pub fn is_builtin(&self) -> bool {
+        self.file == Path::new(Self::BUILTIN)
+    }
 }
 
 #[cfg(test)]
+#[allow(clippy::approx_constant)] // 3.14 is a readable fixture, not PI
 mod tests {
     use super::*;
 
@@ -215,6 +958,7 @@ mod tests {
             kind: ResourceKind::String,
             value: ResourceValue::String("value".to_string()),
             origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
         };
 
         let is_duplicate = graph.insert(key.clone(), node);
@@ -231,11 +975,13 @@ mod tests {
             kind: ResourceKind::String,
             value: ResourceValue::String("first".to_string()),
             origin: ResourceOrigin::new(PathBuf::from("test1.xml"), false),
+            attributes: Default::default(),
         };
         let node2 = ResourceNode {
             kind: ResourceKind::String,
             value: ResourceValue::String("second".to_string()),
             origin: ResourceOrigin::new(PathBuf::from("test2.xml"), false),
+            attributes: Default::default(),
         };
 
         let is_dup1 = graph.insert(key.clone(), node1);
@@ -254,11 +1000,13 @@ mod tests {
             kind: ResourceKind::String,
             value: ResourceValue::String("first".to_string()),
             origin: ResourceOrigin::new(PathBuf::from("test1.xml"), false),
+            attributes: Default::default(),
         };
         let node2 = ResourceNode {
             kind: ResourceKind::String,
             value: ResourceValue::String("second".to_string()),
             origin: ResourceOrigin::new(PathBuf::from("test2.xml"), false),
+            attributes: Default::default(),
         };
 
         graph.insert(key.clone(), node1);
@@ -271,6 +1019,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn graph_merge_appends_after_existing_nodes() {
+        let string = |value: &str, file: &str| {
+            ResourceNode::new(
+                ResourceKind::String,
+                ResourceValue::String(value.to_string()),
+                ResourceOrigin::new(PathBuf::from(file), false),
+            )
+        };
+        let shared = ResourceKey::from_path("ui/title");
+        let mut graph = ResourceGraph::default();
+        graph.insert(shared.clone(), string("xml", "values.xml"));
+
+        let mut extra = ResourceGraph::default();
+        extra.insert(shared.clone(), string("db", "<database>"));
+        extra.insert(
+            ResourceKey::from_path("db/version"),
+            string("42", "<database>").with_attribute("visibility", "crate"),
+        );
+        extra.add_locale_requirement(LocaleRequirement {
+            namespace: vec!["db".to_string()],
+            locales: vec!["en".to_string()],
+        });
+        graph.merge(extra);
+
+        assert!(graph.has_duplicates(&shared));
+        let ResourceValue::String(first) = &graph.get(&shared).unwrap().value
+        else {
+            panic!("expected a string");
+        };
+        assert_eq!(first, "xml");
+        let version = graph.get(&ResourceKey::from_path("db/version")).unwrap();
+        assert_eq!(version.visibility(), Visibility::Crate);
+        assert_eq!(graph.locale_requirements().len(), 1);
+    }
+
     #[test]
     fn graph_get_all_returns_all_nodes() {
         let mut graph = ResourceGraph::default();
@@ -279,11 +1063,13 @@ mod tests {
             kind: ResourceKind::String,
             value: ResourceValue::String("first".to_string()),
             origin: ResourceOrigin::new(PathBuf::from("test1.xml"), false),
+            attributes: Default::default(),
         };
         let node2 = ResourceNode {
             kind: ResourceKind::String,
             value: ResourceValue::String("second".to_string()),
             origin: ResourceOrigin::new(PathBuf::from("test2.xml"), false),
+            attributes: Default::default(),
         };
 
         graph.insert(key.clone(), node1);
@@ -309,11 +1095,13 @@ mod tests {
             kind: ResourceKind::String,
             value: ResourceValue::String("first".to_string()),
             origin: ResourceOrigin::new(PathBuf::from("test1.xml"), false),
+            attributes: Default::default(),
         };
         let node2 = ResourceNode {
             kind: ResourceKind::String,
             value: ResourceValue::String("second".to_string()),
             origin: ResourceOrigin::new(PathBuf::from("test2.xml"), false),
+            attributes: Default::default(),
         };
 
         graph.insert(key.clone(), node1);
@@ -610,4 +1398,101 @@ mod tests {
             _ => panic!("expected Typed"),
         }
     }
+
+    fn hash_of(value: &impl Hash) -> u64 {
+        use std::collections::hash_map::DefaultHasher;
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn number_value_accessors() {
+        assert_eq!(NumberValue::Int(-7).as_i64(), Some(-7));
+        assert_eq!(NumberValue::Float(3.0).as_i64(), Some(3));
+        assert_eq!(NumberValue::Float(2.5).as_i64(), None);
+        assert_eq!(NumberValue::Float(1e19).as_i64(), None);
+        let big = NumberValue::BigDecimal("12345678901234567890.5".into());
+        assert_eq!(big.as_i64(), None);
+        assert!((big.as_f64_lossy() - 1.2345678901234567e19).abs() < 1e4);
+        let whole = NumberValue::BigDecimal("42.000000000000000000".into());
+        assert_eq!(whole.as_i64(), Some(42));
+        let typed = |literal: &str, ty| NumberValue::Typed {
+            literal: literal.to_string(),
+            ty,
+        };
+        assert_eq!(typed("250", NumberType::U8).as_i64(), Some(250));
+        assert_eq!(typed("1e3", NumberType::F32).as_i64(), Some(1000));
+        let above = typed("18446744073709551615", NumberType::U64);
+        assert_eq!(above.as_i64(), None);
+        assert_eq!(above.as_f64_lossy(), 18_446_744_073_709_551_615.0);
+        assert_eq!(typed("0.5", NumberType::F64).as_f64_lossy(), 0.5);
+    }
+
+    #[test]
+    fn values_compare_and_hash_by_content() {
+        let mut seen = std::collections::HashMap::new();
+        seen.insert(ResourceValue::Number(NumberValue::Float(f64::NAN)), 1);
+        seen.insert(ResourceValue::Number(NumberValue::Float(0.0)), 2);
+        seen.insert(ResourceValue::String("Hi".to_string()), 3);
+        let nan = ResourceValue::Number(NumberValue::Float(f64::NAN));
+        assert_eq!(seen.get(&nan), Some(&1));
+        let negative_zero = ResourceValue::Number(NumberValue::Float(-0.0));
+        assert_eq!(seen.get(&negative_zero), Some(&2));
+        let zero = hash_of(&NumberValue::Float(0.0));
+        assert_eq!(hash_of(&NumberValue::Float(-0.0)), zero);
+        assert_ne!(NumberValue::Int(1), NumberValue::Float(1.0));
+
+        let items = |value| ResourceValue::Array(vec![ArrayItem::Float(value)]);
+        assert_eq!(items(1.5), items(1.5));
+        assert_ne!(items(1.5), items(2.5));
+    }
+
+    #[test]
+    fn nodes_compare_without_their_origin() {
+        let node = |file: &str| {
+            ResourceNode::new(
+                ResourceKind::Color,
+                ResourceValue::Color("#fff".to_string()),
+                ResourceOrigin::new(PathBuf::from(file), false),
+            )
+        };
+        assert_eq!(node("a.xml"), node("b.xml"));
+        assert_eq!(hash_of(&node("a.xml")), hash_of(&node("b.xml")));
+        assert_ne!(node("a.xml").origin, node("b.xml").origin);
+        let restricted = node("a.xml").with_attribute("visibility", "crate");
+        assert_ne!(node("a.xml"), restricted);
+    }
+
+    #[test]
+    fn value_kinds() {
+        let kind = |value: ResourceValue| value.kind();
+        assert_eq!(
+            kind(ResourceValue::Interpolated(Vec::new())),
+            Some(ResourceKind::String)
+        );
+        assert_eq!(
+            kind(ResourceValue::Date {
+                year: 2025,
+                month: 2,
+                day: 28
+            }),
+            Some(ResourceKind::Date)
+        );
+        let reference = |kind| ResourceValue::Reference {
+            target: ResourceKey::from_path("a"),
+            kind,
+        };
+        assert_eq!(kind(reference(None)), None);
+        assert_eq!(
+            kind(reference(Some(ResourceKind::Bool))),
+            Some(ResourceKind::Bool)
+        );
+        let rows = ResourceValue::ArrayRows(vec![
+            vec![ArrayItem::Reference(ResourceKey::from_path("a"))],
+            vec![ArrayItem::Int(1)],
+        ]);
+        assert_eq!(kind(rows), Some(ResourceKind::Array("int".to_string())));
+        assert_eq!(kind(ResourceValue::Array(Vec::new())), None);
+    }
 }
@@ -0,0 +1,558 @@
+use crate::generator::casing::NamingConfig;
+use crate::generator::ir::types::duration::{duration_expr, parse_duration};
+use crate::generator::ir::types::size::{parse_size, size_literal};
+use crate::generator::ir::types::{EmittedItem, ResourceType};
+use crate::generator::ir::{
+    ArrayItem, ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
+    ResourceValue,
+};
+use crate::generator::parsing::{
+    ArrayItem as ParsedItem, ParsedResource, ScalarValue,
+};
+use std::fmt::Write as _;
+
+/// `<string-array name="langs"><item>en</item></string-array>` (and the
+/// `int`/`float`/`duration`/`size` forms): a `&[T]` slice plus a
+/// `LANGS_LEN` const. String
+/// arrays with `sorted="true"` are sorted at build time and get a
+/// `langs_contains(s)` lookup using `binary_search`.
+///
+/// With `<row>` children the array is two-dimensional: `[[T; W]; H]` when
+/// every row has the same length, `&[&[T]]` otherwise. Items may be
+/// references, which are inlined once resolved (see `analysis::arrays`).
+pub struct ArrayType {
+    tags: &'static [&'static str],
+    /// Item type as in `ResourceKind::Array`
+    item: &'static str,
+    rust_type: &'static str,
+}
+
+pub const STRING_ARRAY: ArrayType = ArrayType {
+    tags: &["string-array"],
+    item: "string",
+    rust_type: "&str",
+};
+
+pub const INT_ARRAY: ArrayType = ArrayType {
+    tags: &["int-array"],
+    item: "int",
+    rust_type: "i64",
+};
+
+pub const FLOAT_ARRAY: ArrayType = ArrayType {
+    tags: &["float-array"],
+    item: "float",
+    rust_type: "f64",
+};
+
+/// Items parsed like `100ms`, emitted as const `Duration` constructors
+pub const DURATION_ARRAY: ArrayType = ArrayType {
+    tags: &["duration-array"],
+    item: "duration",
+    rust_type: "core::time::Duration",
+};
+
+/// Items parsed like `25MB`, emitted in bytes
+pub const SIZE_ARRAY: ArrayType = ArrayType {
+    tags: &["size-array"],
+    item: "size",
+    rust_type: "u64",
+};
+
+impl ResourceType for ArrayType {
+    fn name(&self) -> &'static str {
+        self.tags[0]
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        self.tags
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::Array(self.item.to_string())
+    }
+
+    fn validate(&self, parsed: &ParsedResource) -> Result<(), String> {
+        match &parsed.value {
+            ScalarValue::Array(items) => {
+                if is_sorted(parsed) && self.item != "string" {
+                    return Err(format!(
+                        "sorted=\"true\" is only supported on string-array, \
+                         not {}",
+                        self.name()
+                    ));
+                }
+                self.parse_items(items).map(drop)
+            }
+            ScalarValue::ArrayRows { rows, stray_items } => {
+                self.validate_rows(parsed, rows, stray_items)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let items = match &parsed.value {
+            ScalarValue::Array(items) => items,
+            ScalarValue::ArrayRows { rows, .. } => {
+                let rows = rows
+                    .iter()
+                    .map(|row| self.parse_items(row))
+                    .collect::<Result<_, _>>()
+                    .ok()?;
+                return Some(ResourceNode {
+                    kind: self.resource_kind(),
+                    value: ResourceValue::ArrayRows(rows),
+                    origin,
+                    attributes: Default::default(),
+                });
+            }
+            _ => return None,
+        };
+        let mut items = self.parse_items(items).ok()?;
+        if is_sorted(parsed) {
+            sort_items(&mut items);
+        }
+        Some(ResourceNode {
+            kind: self.resource_kind(),
+            value: ResourceValue::Array(items),
+            origin,
+            attributes: Default::default(),
+        })
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        self.emit_rust_named(key, node, indent, &NamingConfig::default())
+    }
+
+    fn emit_rust_named(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+        naming: &NamingConfig,
+    ) -> Option<String> {
+        let items = match &node.value {
+            ResourceValue::Array(items) => items,
+            ResourceValue::ArrayRows(rows) => {
+                return self.emit_rows(key, rows, indent, naming);
+            }
+            _ => return None,
+        };
+        let pad = " ".repeat(indent);
+        let const_name = naming.const_identifier(&key.name);
+        let len = naming.companion_identifier(&const_name, "len");
+        let sorted = node.has_flag("sorted");
+
+        let literals: Vec<String> =
+            items.iter().map(item_literal).collect::<Option<_>>()?;
+        let mut code = String::new();
+        if sorted {
+            let _ = writeln!(
+                code,
+                "{pad}/// Sorted at build time (byte order), so the order may differ from the XML"
+            );
+        }
+        let _ = writeln!(
+            code,
+            "{pad}pub const {const_name}: &[{}] = &[{}];\n\
+             {pad}pub const {len}: usize = {};",
+            self.rust_type,
+            literals.join(", "),
+            items.len()
+        );
+        if sorted {
+            let _ = writeln!(
+                code,
+                "{pad}/// Whether `s` is in [`{const_name}`] (case-sensitive)\n\
+                 {pad}#[must_use]\n\
+                 {pad}pub fn {}(s: &str) -> bool {{\n\
+                 {pad}    {const_name}.binary_search(&s).is_ok()\n\
+                 {pad}}}",
+                naming.companion_fn(&key.name, "contains")
+            );
+        }
+        Some(code)
+    }
+
+    fn emitted_items(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        naming: &NamingConfig,
+    ) -> Vec<EmittedItem> {
+        let const_name = naming.const_identifier(&key.name);
+        let mut items = vec![
+            EmittedItem::Const(const_name.clone()),
+            EmittedItem::Const(naming.companion_identifier(&const_name, "len")),
+        ];
+        if node.has_flag("sorted") {
+            items.push(EmittedItem::Function {
+                name: naming.companion_fn(&key.name, "contains"),
+                params: vec!["&str".to_string()],
+            });
+        }
+        items
+    }
+}
+
+impl ArrayType {
+    fn validate_rows(
+        &self,
+        parsed: &ParsedResource,
+        rows: &[Vec<ParsedItem>],
+        stray_items: &[ParsedItem],
+    ) -> Result<(), String> {
+        if is_sorted(parsed) {
+            return Err(
+                "sorted=\"true\" is not supported on arrays with rows"
+                    .to_string(),
+            );
+        }
+        if !stray_items.is_empty() {
+            return Err(format!(
+                "{} item(s) outside any <row>; once an array uses rows, \
+                 every item must be inside one",
+                stray_items.len()
+            ));
+        }
+        for (index, row) in rows.iter().enumerate() {
+            self.parse_items(row)
+                .map_err(|message| format!("row {index}, {message}"))?;
+        }
+        Ok(())
+    }
+
+    /// Parses every item, naming the first bad one by index
+    fn parse_items(
+        &self,
+        items: &[ParsedItem],
+    ) -> Result<Vec<ArrayItem>, String> {
+        items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                self.parse_item(item)
+                    .map_err(|message| format!("item {index}: {message}"))
+            })
+            .collect()
+    }
+
+    /// Fixed-size `[[T; W]; H]` when all rows have the same length, with
+    /// `&[&[T]]` as the fallback for ragged rows
+    fn emit_rows(
+        &self,
+        key: &ResourceKey,
+        rows: &[Vec<ArrayItem>],
+        indent: usize,
+        naming: &NamingConfig,
+    ) -> Option<String> {
+        let pad = " ".repeat(indent);
+        let const_name = naming.const_identifier(&key.name);
+        let len = naming.companion_identifier(&const_name, "len");
+        let lengths: Vec<usize> = rows.iter().map(Vec::len).collect();
+        let uniform = lengths.windows(2).all(|pair| pair[0] == pair[1]);
+        let rows: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                let items: Vec<String> =
+                    row.iter().map(item_literal).collect::<Option<_>>()?;
+                Some(items.join(", "))
+            })
+            .collect::<Option<_>>()?;
+
+        let mut code = String::new();
+        let (ty, literal) = if uniform {
+            let rows: Vec<String> =
+                rows.iter().map(|row| format!("[{row}]")).collect();
+            (
+                format!(
+                    "[[{}; {}]; {}]",
+                    self.rust_type,
+                    lengths.first().copied().unwrap_or(0),
+                    rows.len()
+                ),
+                format!("[{}]", rows.join(", ")),
+            )
+        } else {
+            let lengths: Vec<String> =
+                lengths.iter().map(ToString::to_string).collect();
+            let _ = writeln!(
+                code,
+                "{pad}/// Ragged: rows have different lengths ({})",
+                lengths.join(", ")
+            );
+            let rows: Vec<String> =
+                rows.iter().map(|row| format!("&[{row}]")).collect();
+            (
+                format!("&[&[{}]]", self.rust_type),
+                format!("&[{}]", rows.join(", ")),
+            )
+        };
+        let _ = writeln!(
+            code,
+            "{pad}pub const {const_name}: {ty} = {literal};\n\
+             {pad}pub const {len}: usize = {};",
+            rows.len()
+        );
+        Some(code)
+    }
+
+    fn parse_item(&self, item: &ParsedItem) -> Result<ArrayItem, String> {
+        let (kind, path) = match item {
+            ParsedItem::Text(text) => return literal_item(self.item, text),
+            ParsedItem::Reference { kind, path } => (kind, path),
+        };
+        let expected = match self.item {
+            "string" => "string",
+            "int" | "float" => "number",
+            _ => {
+                return Err(format!(
+                    "references are not supported in a {}",
+                    self.name()
+                ))
+            }
+        };
+        match kind {
+            Some(kind) if kind != expected => Err(format!(
+                "'@{kind}/{path}' cannot be an item of a {}",
+                self.name()
+            )),
+            _ => Ok(ArrayItem::Reference(ResourceKey::from_path(path))),
+        }
+    }
+}
+
+/// Literal item of an array of `item_type` (`"int"`), from its text
+pub fn literal_item(item_type: &str, text: &str) -> Result<ArrayItem, String> {
+    match item_type {
+        "int" => text.parse().map(ArrayItem::Int).map_err(|_| {
+            format!("'{text}' is not an integer that fits in i64")
+        }),
+        "float" => match text.parse::<f64>() {
+            Ok(value) if value.is_finite() => Ok(ArrayItem::Float(value)),
+            _ => Err(format!("'{text}' is not a finite number")),
+        },
+        "duration" => parse_duration(text).map(ArrayItem::Duration),
+        "size" => parse_size(text).map(ArrayItem::Size),
+        _ => Ok(ArrayItem::String(text.to_string())),
+    }
+}
+
+/// Order of a `sorted="true"` array: stable, and in the byte order
+/// `binary_search` relies on
+pub fn sort_items(items: &mut [ArrayItem]) {
+    items.sort_by(|a, b| match (a, b) {
+        (ArrayItem::String(a), ArrayItem::String(b)) => a.cmp(b),
+        _ => std::cmp::Ordering::Equal,
+    });
+}
+
+
+fn is_sorted(parsed: &ParsedResource) -> bool {
+    parsed.attributes.get("sorted").is_some_and(|value| value == "true")
+}
+
+/// `None` for a reference, which must be resolved before emission
+fn item_literal(item: &ArrayItem) -> Option<String> {
+    Some(match item {
+        ArrayItem::String(text) => format!("{text:?}"),
+        ArrayItem::Int(value) => value.to_string(),
+        // `{:?}` keeps the decimal point (`1.0`, not `1`)
+        ArrayItem::Float(value) => format!("{value:?}"),
+        ArrayItem::Duration(duration) => duration_expr(*duration),
+        ArrayItem::Size(bytes) => size_literal(*bytes),
+        ArrayItem::Reference(_) => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::parsing::ResourceKind as AstResourceKind;
+    use std::path::PathBuf;
+
+    fn parsed(item: &str, items: &[&str], sorted: bool) -> ParsedResource {
+        let mut attributes = std::collections::BTreeMap::new();
+        if sorted {
+            attributes.insert("sorted".to_string(), "true".to_string());
+        }
+        ParsedResource {
+            name: "langs".to_string(),
+            kind: AstResourceKind::Array(item.to_string()),
+            value: ScalarValue::Array(
+                items.iter().map(|&item| ParsedItem::from(item)).collect(),
+            ),
+            attributes,
+        }
+    }
+
+    fn emit(ty: &ArrayType, parsed: &ParsedResource) -> String {
+        let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
+        let mut node = ty.build_node(parsed, origin).unwrap();
+        node.attributes = parsed.attributes.clone();
+        let key = ResourceKey::from_path("langs");
+        ty.emit_rust(&key, &node, 0).unwrap()
+    }
+
+    #[test]
+    fn test_emit_string_array_with_len() {
+        let code =
+            emit(&STRING_ARRAY, &parsed("string", &["en", "fr"], false));
+        assert!(
+            code.contains("pub const LANGS: &[&str] = &[\"en\", \"fr\"];")
+        );
+        assert!(code.contains("pub const LANGS_LEN: usize = 2;"));
+        assert!(!code.contains("langs_contains"));
+    }
+
+    #[test]
+    fn test_emit_sorted_string_array() {
+        let code = emit(
+            &STRING_ARRAY,
+            &parsed("string", &["fr", "de", "en", "de"], true),
+        );
+        assert!(code.contains(
+            "pub const LANGS: &[&str] = &[\"de\", \"de\", \"en\", \"fr\"];"
+        ));
+        assert!(code.contains("/// Sorted at build time (byte order)"));
+        assert!(code.contains("pub fn langs_contains(s: &str) -> bool {"));
+        assert!(code.contains("LANGS.binary_search(&s).is_ok()"));
+    }
+
+    #[test]
+    fn test_emit_numeric_arrays() {
+        let code = emit(&INT_ARRAY, &parsed("int", &["1", "-2"], false));
+        assert!(code.contains("pub const LANGS: &[i64] = &[1, -2];"));
+
+        let code =
+            emit(&FLOAT_ARRAY, &parsed("float", &["1", "0.5"], false));
+        assert!(code.contains("pub const LANGS: &[f64] = &[1.0, 0.5];"));
+    }
+
+    #[test]
+    fn test_emit_duration_and_size_arrays() {
+        let code = emit(
+            &DURATION_ARRAY,
+            &parsed("duration", &["100ms", "1s"], false),
+        );
+        assert!(code.contains(
+            "pub const LANGS: &[core::time::Duration] = \
+             &[core::time::Duration::from_millis(100), \
+             core::time::Duration::from_secs(1)];"
+        ));
+
+        let code =
+            emit(&SIZE_ARRAY, &parsed("size", &["25MiB", "1KB"], false));
+        assert!(
+            code.contains("pub const LANGS: &[u64] = &[26_214_400, 1_000];")
+        );
+
+        let err = SIZE_ARRAY
+            .validate(&parsed("size", &["1KB", "lots"], false))
+            .unwrap_err();
+        assert!(err.starts_with("item 1: 'lots' is not a size"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_reports_item_index() {
+        let err = INT_ARRAY
+            .validate(&parsed("int", &["1", "two"], false))
+            .unwrap_err();
+        assert_eq!(err, "item 1: 'two' is not an integer that fits in i64");
+
+        let err = FLOAT_ARRAY
+            .validate(&parsed("float", &["inf"], false))
+            .unwrap_err();
+        assert!(err.starts_with("item 0:"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_rejects_sorted_numeric_arrays() {
+        let err = INT_ARRAY
+            .validate(&parsed("int", &["2", "1"], true))
+            .unwrap_err();
+        assert!(err.contains("only supported on string-array"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_reference_kind() {
+        let mut langs = parsed("string", &["en"], false);
+        let ScalarValue::Array(items) = &mut langs.value else {
+            unreachable!();
+        };
+        items.push(ParsedItem::Reference {
+            kind: Some("number".to_string()),
+            path: "max".to_string(),
+        });
+        let err = STRING_ARRAY.validate(&langs).unwrap_err();
+        assert_eq!(
+            err,
+            "item 1: '@number/max' cannot be an item of a string-array"
+        );
+    }
+
+    fn rows(rows: &[&[&str]], stray_items: &[&str]) -> ParsedResource {
+        let to_items =
+            |items: &[&str]| items.iter().map(|&item| item.into()).collect();
+        ParsedResource {
+            name: "level".to_string(),
+            kind: AstResourceKind::Array("int".to_string()),
+            value: ScalarValue::ArrayRows {
+                rows: rows.iter().map(|row| to_items(row)).collect(),
+                stray_items: to_items(stray_items),
+            },
+            attributes: Default::default(),
+        }
+    }
+
+    fn emit_level(parsed: &ParsedResource) -> String {
+        let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
+        let node = INT_ARRAY.build_node(parsed, origin).unwrap();
+        let key = ResourceKey::from_path("level");
+        INT_ARRAY.emit_rust(&key, &node, 0).unwrap()
+    }
+
+    #[test]
+    fn test_emit_uniform_rows_as_fixed_size() {
+        let code = emit_level(&rows(&[&["0", "1"], &["2", "3"]], &[]));
+        assert!(code.contains(
+            "pub const LEVEL: [[i64; 2]; 2] = [[0, 1], [2, 3]];"
+        ));
+        assert!(code.contains("pub const LEVEL_LEN: usize = 2;"));
+        assert!(!code.contains("Ragged"));
+    }
+
+    #[test]
+    fn test_emit_ragged_rows_as_slices() {
+        let code = emit_level(&rows(&[&["0", "1"], &["2"], &[]], &[]));
+        assert!(code
+            .contains("/// Ragged: rows have different lengths (2, 1, 0)"));
+        assert!(code
+            .contains("pub const LEVEL: &[&[i64]] = &[&[0, 1], &[2], &[]];"));
+        assert!(code.contains("pub const LEVEL_LEN: usize = 3;"));
+    }
+
+    #[test]
+    fn test_validate_rows() {
+        let err = INT_ARRAY
+            .validate(&rows(&[&["0"], &["1", "x"]], &[]))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            "row 1, item 1: 'x' is not an integer that fits in i64"
+        );
+
+        let err = INT_ARRAY.validate(&rows(&[&["0"]], &["1"])).unwrap_err();
+        assert!(err.starts_with("1 item(s) outside any <row>"), "{err}");
+    }
+}
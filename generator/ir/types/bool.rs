@@ -1,10 +1,10 @@
+use crate::generator::casing::NamingConfig;
 use crate::generator::ir::types::ResourceType;
 use crate::generator::ir::{
     ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
     ResourceValue,
 };
 use crate::generator::parsing::{ParsedResource, ScalarValue};
-use crate::generator::utils::sanitize_identifier;
 
 pub struct BoolType;
 
@@ -21,6 +21,15 @@ impl ResourceType for BoolType {
         ResourceKind::Bool
     }
 
+    fn validate(&self, parsed: &ParsedResource) -> Result<(), String> {
+        match &parsed.value {
+            ScalarValue::Text(text) => Err(format!(
+                "'{text}' is not a bool (expected true or false)"
+            )),
+            _ => Ok(()),
+        }
+    }
+
     fn build_node(
         &self,
         parsed: &ParsedResource,
@@ -31,6 +40,7 @@ impl ResourceType for BoolType {
                 kind: ResourceKind::Bool,
                 value: ResourceValue::Bool(*value),
                 origin,
+                attributes: Default::default(),
             })
         } else {
             None
@@ -42,11 +52,20 @@ impl ResourceType for BoolType {
         key: &ResourceKey,
         node: &ResourceNode,
         indent: usize,
+    ) -> Option<String> {
+        self.emit_rust_named(key, node, indent, &NamingConfig::default())
+    }
+
+    fn emit_rust_named(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+        naming: &NamingConfig,
     ) -> Option<String> {
         if let ResourceValue::Bool(value) = &node.value {
             let pad = " ".repeat(indent);
-            let const_name =
-                sanitize_identifier(&key.name).to_uppercase();
+            let const_name = naming.const_identifier(&key.name);
             Some(format!(
                 "{pad}pub const {const_name}: bool = {value};\n"
             ))
@@ -94,6 +113,7 @@ mod tests {
             name: "enabled".to_string(),
             kind: AstResourceKind::Bool,
             value: ScalarValue::Bool(true),
+            attributes: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -112,6 +132,7 @@ mod tests {
             name: "disabled".to_string(),
             kind: AstResourceKind::Bool,
             value: ScalarValue::Bool(false),
+            attributes: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -128,6 +149,7 @@ mod tests {
             name: "not_bool".to_string(),
             kind: AstResourceKind::Bool,
             value: ScalarValue::Text("not a bool".to_string()),
+            attributes: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -143,6 +165,7 @@ mod tests {
             name: "not_bool".to_string(),
             kind: AstResourceKind::Color,
             value: ScalarValue::Color("#FF0000".to_string()),
+            attributes: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -162,6 +185,7 @@ mod tests {
             kind: ModelResourceKind::Bool,
             value: ResourceValue::Bool(true),
             origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
         };
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
@@ -180,6 +204,7 @@ mod tests {
             kind: ModelResourceKind::Bool,
             value: ResourceValue::Bool(false),
             origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
         };
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
@@ -198,6 +223,7 @@ mod tests {
             kind: ModelResourceKind::Bool,
             value: ResourceValue::Color("#FF0000".to_string()),
             origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
         };
 
         let result = handler.emit_rust(&key, &node, 4);
@@ -216,6 +242,7 @@ mod tests {
             kind: ModelResourceKind::Bool,
             value: ResourceValue::Bool(true),
             origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
         };
 
         let result = handler.emit_rust(&key, &node, 8).unwrap();
@@ -235,6 +262,7 @@ mod tests {
             kind: ModelResourceKind::Bool,
             value: ResourceValue::Bool(true),
             origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
         };
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
@@ -261,6 +289,7 @@ mod tests {
                 kind: ModelResourceKind::Bool,
                 value: ResourceValue::Bool(true),
                 origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+                attributes: Default::default(),
             };
 
             let result = handler.emit_rust(&key, &node, 4).unwrap();
@@ -287,6 +316,7 @@ mod tests {
                 name: name.to_string(),
                 kind: AstResourceKind::Bool,
                 value: ScalarValue::Bool(value),
+                attributes: Default::default(),
             };
             let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -311,6 +341,7 @@ mod tests {
                 name: name.to_string(),
                 kind: AstResourceKind::Bool,
                 value: ScalarValue::Bool(value),
+                attributes: Default::default(),
             };
             let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
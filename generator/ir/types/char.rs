@@ -0,0 +1,118 @@
+use crate::generator::casing::NamingConfig;
+use crate::generator::ir::types::ResourceType;
+use crate::generator::ir::{
+    ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
+    ResourceValue,
+};
+use crate::generator::parsing::{ParsedResource, ScalarValue};
+
+pub struct CharType;
+
+impl ResourceType for CharType {
+    fn name(&self) -> &'static str {
+        "char"
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        &["char"]
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::Char
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let ScalarValue::Char(value) = &parsed.value else {
+            return None;
+        };
+        Some(ResourceNode {
+            kind: ResourceKind::Char,
+            value: ResourceValue::Char(*value),
+            origin,
+            attributes: Default::default(),
+        })
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        self.emit_rust_named(key, node, indent, &NamingConfig::default())
+    }
+
+    fn emit_rust_named(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+        naming: &NamingConfig,
+    ) -> Option<String> {
+        let ResourceValue::Char(value) = &node.value else {
+            return None;
+        };
+        let pad = " ".repeat(indent);
+        let const_name = naming.const_identifier(&key.name);
+        let escaped = value.escape_debug();
+        Some(format!("{pad}pub const {const_name}: char = '{escaped}';\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::parsing::ResourceKind as AstResourceKind;
+    use std::path::PathBuf;
+
+    fn emit(value: char) -> String {
+        let key = ResourceKey::from_path("sep");
+        let node = ResourceNode {
+            kind: ResourceKind::Char,
+            value: ResourceValue::Char(value),
+            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
+        };
+        CharType.emit_rust(&key, &node, 0).unwrap()
+    }
+
+    #[test]
+    fn test_build_node_char() {
+        let parsed = ParsedResource {
+            name: "path_sep".to_string(),
+            kind: AstResourceKind::Char,
+            value: ScalarValue::Char('/'),
+            attributes: Default::default(),
+        };
+        let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
+
+        let node = CharType.build_node(&parsed, origin).unwrap();
+        assert_eq!(node.kind, ResourceKind::Char);
+        assert!(matches!(node.value, ResourceValue::Char('/')));
+    }
+
+    #[test]
+    fn test_build_node_wrong_type() {
+        let parsed = ParsedResource::string("path_sep", "/");
+        let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
+        assert!(CharType.build_node(&parsed, origin).is_none());
+    }
+
+    #[test]
+    fn test_emit_rust_plain_char() {
+        assert_eq!(emit('/'), "pub const SEP: char = '/';\n");
+        assert_eq!(emit('é'), "pub const SEP: char = 'é';\n");
+    }
+
+    #[test]
+    fn test_emit_rust_escapes_char_literal() {
+        assert!(emit('\'').contains("= '\\'';"));
+        assert!(emit('\\').contains("= '\\\\';"));
+        assert!(emit('\n').contains("= '\\n';"));
+        assert!(emit('\t').contains("= '\\t';"));
+    }
+}
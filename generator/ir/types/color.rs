@@ -1,11 +1,14 @@
-use crate::generator::ir::types::ResourceType;
+use crate::generator::casing::NamingConfig;
+use crate::generator::ir::types::{EmittedItem, ResourceType};
 use crate::generator::ir::{
     ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
     ResourceValue,
 };
 use crate::generator::parsing::{ParsedResource, ScalarValue};
-use crate::generator::utils::sanitize_identifier;
 
+/// `<color name="primary">#3366FF</color>`: the text as a `&str` const, plus
+/// `PRIMARY_COLOR: r_resources::Color` when the value is a hex color
+/// (`#RGB`, `#ARGB`, `#RRGGBB`, or `#AARRGGBB`).
 pub struct ColorType;
 
 impl ResourceType for ColorType {
@@ -31,6 +34,7 @@ impl ResourceType for ColorType {
                 kind: ResourceKind::Color,
                 value: ResourceValue::Color(value.clone()),
                 origin,
+                attributes: Default::default(),
             })
         } else {
             None
@@ -42,17 +46,80 @@ impl ResourceType for ColorType {
         key: &ResourceKey,
         node: &ResourceNode,
         indent: usize,
+    ) -> Option<String> {
+        self.emit_rust_named(key, node, indent, &NamingConfig::default())
+    }
+
+    fn emit_rust_named(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+        naming: &NamingConfig,
     ) -> Option<String> {
         if let ResourceValue::Color(value) = &node.value {
             let pad = " ".repeat(indent);
-            let const_name =
-                sanitize_identifier(&key.name).to_uppercase();
+            let const_name = naming.const_identifier(&key.name);
             let escaped = value.escape_debug();
-            Some(format!("{pad}pub const {const_name}: &str = \"{escaped}\";\n"))
+            let mut code = format!(
+                "{pad}pub const {const_name}: &str = \"{escaped}\";\n"
+            );
+            if let Some([a, r, g, b]) = parse_hex_color(value) {
+                let color = naming.companion_identifier(&const_name, "color");
+                code.push_str(&format!(
+                    "{pad}/// [`{const_name}`] as a typed color\n\
+                     {pad}pub const {color}: r_resources::Color = \
+                     r_resources::Color::new({r}, {g}, {b}, {a});\n"
+                ));
+            }
+            Some(code)
         } else {
             None
         }
     }
+
+    fn emitted_items(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        naming: &NamingConfig,
+    ) -> Vec<EmittedItem> {
+        let const_name = naming.const_identifier(&key.name);
+        let mut items = vec![EmittedItem::Const(const_name.clone())];
+        if matches!(&node.value, ResourceValue::Color(value)
+            if parse_hex_color(value).is_some())
+        {
+            let color = naming.companion_identifier(&const_name, "color");
+            items.push(EmittedItem::Const(color));
+        }
+        items
+    }
+}
+
+/// Parses `#RGB`, `#ARGB`, `#RRGGBB`, and `#AARRGGBB` (Android ordering) into
+/// `[a, r, g, b]`; the short forms repeat each digit and alpha defaults to
+/// opaque. Anything else (`rgb(...)`, names) has no typed form.
+pub(crate) fn parse_hex_color(value: &str) -> Option<[u8; 4]> {
+    let hex = value.trim().strip_prefix('#')?;
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digits: Vec<u8> = match hex.len() {
+        3 | 4 => hex
+            .chars()
+            .map(|c| c.to_digit(16).map(|d| d as u8 * 17))
+            .collect::<Option<_>>()?,
+        6 | 8 => (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+            .collect::<Option<_>>()?,
+        _ => return None,
+    };
+    match digits[..] {
+        [r, g, b] => Some([255, r, g, b]),
+        [a, r, g, b] => Some([a, r, g, b]),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
@@ -93,6 +160,7 @@ mod tests {
             name: "primary_color".to_string(),
             kind: AstResourceKind::Color,
             value: ScalarValue::Color("#FF0000".to_string()),
+            attributes: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -114,6 +182,7 @@ mod tests {
             name: "bg_color".to_string(),
             kind: AstResourceKind::Color,
             value: ScalarValue::Color("rgb(255, 0, 0)".to_string()),
+            attributes: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -132,6 +201,7 @@ mod tests {
             name: "text_color".to_string(),
             kind: AstResourceKind::Color,
             value: ScalarValue::Color("red".to_string()),
+            attributes: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -150,6 +220,7 @@ mod tests {
             name: "not_color".to_string(),
             kind: AstResourceKind::Color,
             value: ScalarValue::Text("not a color".to_string()),
+            attributes: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -165,6 +236,7 @@ mod tests {
             name: "not_color".to_string(),
             kind: AstResourceKind::Bool,
             value: ScalarValue::Bool(true),
+            attributes: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -184,6 +256,7 @@ mod tests {
             kind: ModelResourceKind::Color,
             value: ResourceValue::Color("#FF0000".to_string()),
             origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
         };
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
@@ -191,6 +264,47 @@ mod tests {
         assert!(result.contains("#FF0000"));
     }
 
+    #[test]
+    fn test_parse_hex_color() {
+        assert_eq!(parse_hex_color("#3366FF"), Some([255, 0x33, 0x66, 0xFF]));
+        assert_eq!(
+            parse_hex_color("#803366ff"),
+            Some([0x80, 0x33, 0x66, 0xFF])
+        );
+        assert_eq!(parse_hex_color("#36F"), Some([255, 0x33, 0x66, 0xFF]));
+        assert_eq!(parse_hex_color("#836F"), Some([0x88, 0x33, 0x66, 0xFF]));
+        assert_eq!(parse_hex_color("#12345"), None);
+        assert_eq!(parse_hex_color("#GG0000"), None);
+        assert_eq!(parse_hex_color("rgb(255, 0, 0)"), None);
+        assert_eq!(parse_hex_color("red"), None);
+    }
+
+    #[test]
+    fn test_emit_rust_hex_color_has_typed_form() {
+        let key = ResourceKey::from_path("primary");
+        let node = ResourceNode {
+            kind: ModelResourceKind::Color,
+            value: ResourceValue::Color("#803366FF".to_string()),
+            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
+        };
+
+        assert_eq!(
+            ColorType.emit_rust(&key, &node, 0).unwrap(),
+            "pub const PRIMARY: &str = \"#803366FF\";\n\
+             /// [`PRIMARY`] as a typed color\n\
+             pub const PRIMARY_COLOR: r_resources::Color = \
+             r_resources::Color::new(51, 102, 255, 128);\n"
+        );
+        assert_eq!(
+            ColorType.emitted_items(&key, &node, &NamingConfig::default()),
+            vec![
+                EmittedItem::Const("PRIMARY".to_string()),
+                EmittedItem::Const("PRIMARY_COLOR".to_string()),
+            ]
+        );
+    }
+
     // Test emit_rust with different color formats
     #[test]
     fn test_emit_rust_rgb_color() {
@@ -203,11 +317,13 @@ mod tests {
             kind: ModelResourceKind::Color,
             value: ResourceValue::Color("rgb(255, 0, 0)".to_string()),
             origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
         };
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
         assert!(result.contains("pub const BG_COLOR: &str"));
         assert!(result.contains("rgb(255, 0, 0)"));
+        assert!(!result.contains("r_resources::Color"));
     }
 
     // Test emit_rust with named color
@@ -222,6 +338,7 @@ mod tests {
             kind: ModelResourceKind::Color,
             value: ResourceValue::Color("blue".to_string()),
             origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
         };
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
@@ -241,6 +358,7 @@ mod tests {
             kind: ModelResourceKind::Color,
             value: ResourceValue::Color("#FF\"test\"".to_string()),
             origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
         };
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
@@ -264,6 +382,7 @@ mod tests {
             kind: ModelResourceKind::Color,
             value: ResourceValue::Bool(true),
             origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
         };
 
         let result = handler.emit_rust(&key, &node, 4);
@@ -282,6 +401,7 @@ mod tests {
             kind: ModelResourceKind::Color,
             value: ResourceValue::Color("#000000".to_string()),
             origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
         };
 
         let result = handler.emit_rust(&key, &node, 8).unwrap();
@@ -301,6 +421,7 @@ mod tests {
             kind: ModelResourceKind::Color,
             value: ResourceValue::Color("#FF0000".to_string()),
             origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
         };
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
@@ -320,6 +441,7 @@ mod tests {
             kind: ModelResourceKind::Color,
             value: ResourceValue::Color("".to_string()),
             origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
         };
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
@@ -0,0 +1,192 @@
+use crate::generator::casing::NamingConfig;
+use crate::generator::ir::types::{EmittedItem, ResourceType};
+use crate::generator::ir::{
+    ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
+    ResourceValue,
+};
+use crate::generator::parsing::{ParsedResource, ScalarValue};
+
+/// `<cron name="nightly_cleanup">0 3 * * *</cron>`: the expression as a string
+/// const plus a `_SCHEDULE` accessor, validated at build time.
+///
+/// Requires the `cron` feature; without it the tag is rejected with an
+/// explanation instead of being dropped.
+pub struct CronType;
+
+impl ResourceType for CronType {
+    fn name(&self) -> &'static str {
+        "cron"
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        &["cron"]
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::Cron
+    }
+
+    fn validate(&self, parsed: &ParsedResource) -> Result<(), String> {
+        let ScalarValue::Cron(expression) = &parsed.value else {
+            return Ok(());
+        };
+        parse_schedule(expression).map(|_| ())
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let ScalarValue::Cron(expression) = &parsed.value else {
+            return None;
+        };
+        let schedule = parse_schedule(expression).ok()?;
+        Some(ResourceNode {
+            kind: ResourceKind::Cron,
+            value: ResourceValue::Cron {
+                expression: expression.clone(),
+                schedule,
+            },
+            origin,
+            attributes: Default::default(),
+        })
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        self.emit_rust_named(key, node, indent, &NamingConfig::default())
+    }
+
+    fn emit_rust_named(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+        naming: &NamingConfig,
+    ) -> Option<String> {
+        let ResourceValue::Cron {
+            expression,
+            schedule,
+        } = &node.value
+        else {
+            return None;
+        };
+        let pad = " ".repeat(indent);
+        let const_name = naming.const_identifier(&key.name);
+        let expression = expression.escape_debug();
+        let schedule = schedule.escape_debug();
+        let lazy = naming.companion_identifier(&const_name, "schedule");
+        Some(format!(
+            "{pad}pub const {const_name}: &str = \"{expression}\";\n\
+             {pad}pub static {lazy}: std::sync::LazyLock<r_resources::cron::Schedule> = std::sync::LazyLock::new(|| {{\n\
+             {pad}    <r_resources::cron::Schedule as std::str::FromStr>::from_str(\"{schedule}\").expect(\"cron expression validated at build time\")\n\
+             {pad}}});\n"
+        ))
+    }
+
+    fn emitted_items(
+        &self,
+        key: &ResourceKey,
+        _node: &ResourceNode,
+        naming: &NamingConfig,
+    ) -> Vec<EmittedItem> {
+        let const_name = naming.const_identifier(&key.name);
+        vec![
+            EmittedItem::Const(const_name.clone()),
+            EmittedItem::Lazy(naming.companion_identifier(&const_name, "schedule")),
+        ]
+    }
+}
+
+/// Validates an expression and returns its 6-field form (seconds first).
+#[cfg(feature = "cron")]
+fn parse_schedule(expression: &str) -> Result<String, String> {
+    use std::str::FromStr;
+
+    let schedule = with_seconds_field(expression)?;
+    ::cron::Schedule::from_str(&schedule).map_err(|err| {
+        format!("invalid cron expression '{expression}': {err}")
+    })?;
+    Ok(schedule)
+}
+
+#[cfg(not(feature = "cron"))]
+fn parse_schedule(_expression: &str) -> Result<String, String> {
+    Err("<cron> resources require the `cron` feature of r-resources"
+        .to_string())
+}
+
+/// The `cron` crate expects a leading seconds field; 5-field expressions
+/// run at second 0.
+#[cfg(feature = "cron")]
+fn with_seconds_field(expression: &str) -> Result<String, String> {
+    let fields: Vec<&str> = expression.split_whitespace().collect();
+    match fields.len() {
+        5 => Ok(format!("0 {}", fields.join(" "))),
+        6 => Ok(fields.join(" ")),
+        count => Err(format!(
+            "invalid cron expression '{expression}': expected 5 or 6 fields, found {count}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::parsing::ResourceKind as AstResourceKind;
+
+    fn parsed(expression: &str) -> ParsedResource {
+        ParsedResource {
+            name: "nightly_cleanup".to_string(),
+            kind: AstResourceKind::Cron,
+            value: ScalarValue::Cron(expression.to_string()),
+            attributes: Default::default(),
+        }
+    }
+
+    #[cfg(not(feature = "cron"))]
+    #[test]
+    fn test_validate_requires_feature() {
+        let err = CronType.validate(&parsed("0 3 * * *")).unwrap_err();
+        assert!(err.contains("`cron` feature"), "{err}");
+    }
+
+    #[cfg(feature = "cron")]
+    #[test]
+    fn test_five_and_six_field_forms() {
+        assert_eq!(parse_schedule("0 3 * * *"), Ok("0 0 3 * * *".into()));
+        assert_eq!(
+            parse_schedule("30 0 3 * * Mon"),
+            Ok("30 0 3 * * Mon".into())
+        );
+    }
+
+    #[cfg(feature = "cron")]
+    #[test]
+    fn test_validate_rejects_invalid_expressions() {
+        let err = CronType.validate(&parsed("0 3 * *")).unwrap_err();
+        assert!(err.contains("expected 5 or 6 fields, found 4"), "{err}");
+        let err = CronType.validate(&parsed("0 25 * * *")).unwrap_err();
+        assert!(err.contains("invalid cron expression '0 25 * * *'"));
+    }
+
+    #[cfg(feature = "cron")]
+    #[test]
+    fn test_emit_rust() {
+        let origin = ResourceOrigin::new("test.xml".into(), false);
+        let node =
+            CronType.build_node(&parsed("0 3 * * *"), origin).unwrap();
+        let key = ResourceKey::from_path("nightly_cleanup");
+        let code = CronType.emit_rust(&key, &node, 0).unwrap();
+        assert!(code.contains(
+            "pub const NIGHTLY_CLEANUP: &str = \"0 3 * * *\";"
+        ));
+        assert!(code.contains("pub static NIGHTLY_CLEANUP_SCHEDULE"));
+        assert!(code.contains("from_str(\"0 0 3 * * *\")"));
+    }
+}
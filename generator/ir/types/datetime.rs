@@ -0,0 +1,432 @@
+use crate::generator::casing::NamingConfig;
+use crate::generator::ir::types::{EmittedItem, ResourceType};
+use crate::generator::ir::{
+    ResourceKey, ResourceKind, ResourceNode, ResourceOrigin, ResourceValue,
+};
+use crate::generator::parsing::{ParsedResource, ScalarValue};
+use crate::generator::utils::doc_comment;
+
+/// `<datetime name="promo_ends">2025-12-31T23:59:59Z</datetime>`: RFC 3339,
+/// emitted as an `i64` Unix timestamp plus a `_RFC3339` const holding the
+/// original text. With the `chrono` feature, a `_UTC` accessor returns the
+/// `chrono::DateTime<Utc>`.
+pub struct DateTimeType;
+
+/// `<date name="launch">2025-06-01</date>`: an `r_resources::Date`.
+pub struct DateType;
+
+impl ResourceType for DateTimeType {
+    fn name(&self) -> &'static str {
+        "datetime"
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        &["datetime"]
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::DateTime
+    }
+
+    fn validate(&self, parsed: &ParsedResource) -> Result<(), String> {
+        let ScalarValue::DateTime(text) = &parsed.value else {
+            return Ok(());
+        };
+        parse_rfc3339(text).map(|_| ())
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let ScalarValue::DateTime(text) = &parsed.value else {
+            return None;
+        };
+        let (timestamp, nanos) = parse_rfc3339(text).ok()?;
+        Some(ResourceNode {
+            kind: ResourceKind::DateTime,
+            value: ResourceValue::DateTime {
+                timestamp,
+                nanos,
+                raw: text.clone(),
+            },
+            origin,
+            attributes: Default::default(),
+        })
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        self.emit_rust_named(key, node, indent, &NamingConfig::default())
+    }
+
+    fn emit_rust_named(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+        naming: &NamingConfig,
+    ) -> Option<String> {
+        let ResourceValue::DateTime {
+            timestamp,
+            nanos,
+            raw,
+        } = &node.value
+        else {
+            return None;
+        };
+        let pad = " ".repeat(indent);
+        let const_name = naming.const_identifier(&key.name);
+        let rfc3339 = naming.companion_identifier(&const_name, "rfc3339");
+        let mut code = format!(
+            "{}{pad}pub const {const_name}: i64 = {};\n\
+             {pad}/// Original text of [`{const_name}`]\n\
+             {pad}pub const {rfc3339}: &str = \"{}\";\n",
+            doc_comment(&pad, &format!("{raw} (Unix timestamp, seconds)")),
+            timestamp_literal(*timestamp),
+            raw.escape_debug()
+        );
+        if cfg!(feature = "chrono") {
+            let utc = naming.companion_identifier(&const_name, "utc");
+            code.push_str(&format!(
+                "{pad}pub static {utc}: std::sync::LazyLock<r_resources::chrono::DateTime<r_resources::chrono::Utc>> = std::sync::LazyLock::new(|| {{\n\
+                 {pad}    r_resources::chrono::DateTime::from_timestamp({const_name}, {nanos}).expect(\"datetime validated at build time\")\n\
+                 {pad}}});\n"
+            ));
+        }
+        Some(code)
+    }
+
+    fn emitted_items(
+        &self,
+        key: &ResourceKey,
+        _node: &ResourceNode,
+        naming: &NamingConfig,
+    ) -> Vec<EmittedItem> {
+        let const_name = naming.const_identifier(&key.name);
+        let mut items = vec![
+            EmittedItem::Const(const_name.clone()),
+            EmittedItem::Const(naming.companion_identifier(&const_name, "rfc3339")),
+        ];
+        if cfg!(feature = "chrono") {
+            let utc = naming.companion_identifier(&const_name, "utc");
+            items.push(EmittedItem::Lazy(utc));
+        }
+        items
+    }
+}
+
+impl ResourceType for DateType {
+    fn name(&self) -> &'static str {
+        "date"
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        &["date"]
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::Date
+    }
+
+    fn validate(&self, parsed: &ParsedResource) -> Result<(), String> {
+        let ScalarValue::Date(text) = &parsed.value else {
+            return Ok(());
+        };
+        parse_date(text).map(|_| ())
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let ScalarValue::Date(text) = &parsed.value else {
+            return None;
+        };
+        let (year, month, day) = parse_date(text).ok()?;
+        Some(ResourceNode {
+            kind: ResourceKind::Date,
+            value: ResourceValue::Date { year, month, day },
+            origin,
+            attributes: Default::default(),
+        })
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        self.emit_rust_named(key, node, indent, &NamingConfig::default())
+    }
+
+    fn emit_rust_named(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+        naming: &NamingConfig,
+    ) -> Option<String> {
+        let ResourceValue::Date { year, month, day } = &node.value else {
+            return None;
+        };
+        let pad = " ".repeat(indent);
+        let const_name = naming.const_identifier(&key.name);
+        Some(format!(
+            "{pad}/// {year:04}-{month:02}-{day:02}\n\
+             {pad}pub const {const_name}: r_resources::Date = \
+             r_resources::Date::new({year}, {month}, {day});\n"
+        ))
+    }
+
+    fn emitted_items(
+        &self,
+        key: &ResourceKey,
+        _node: &ResourceNode,
+        naming: &NamingConfig,
+    ) -> Vec<EmittedItem> {
+        vec![EmittedItem::Const(
+            naming.const_identifier(&key.name),
+        )]
+    }
+}
+
+/// `YYYY-MM-DD`, checked against the length of the month
+fn parse_date(text: &str) -> Result<(i32, u8, u8), String> {
+    let invalid = || format!("'{text}' is not a date (expected YYYY-MM-DD)");
+    let bytes = text.as_bytes();
+    if bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return Err(invalid());
+    }
+    let year = digits(&text[0..4]).ok_or_else(invalid)?;
+    let month = digits(&text[5..7]).ok_or_else(invalid)?;
+    let day = digits(&text[8..10]).ok_or_else(invalid)?;
+    if !(1..=12).contains(&month) {
+        return Err(format!("'{text}' has month {month}, expected 1 to 12"));
+    }
+    let last_day = days_in_month(year, month);
+    if !(1..=last_day).contains(&day) {
+        return Err(format!(
+            "'{text}' has day {day}, but that month has {last_day} days"
+        ));
+    }
+    Ok((year as i32, month as u8, day as u8))
+}
+
+/// RFC 3339 date-time (`2025-12-31T23:59:59Z`, `2025-06-01 08:00:00.5+02:00`)
+/// as seconds since the Unix epoch and the nanoseconds past that second
+fn parse_rfc3339(text: &str) -> Result<(i64, u32), String> {
+    let invalid = || {
+        format!(
+            "'{text}' is not an RFC 3339 date-time \
+             (expected e.g. 2025-12-31T23:59:59Z)"
+        )
+    };
+    // Everything below slices by byte position
+    if !text.is_ascii()
+        || text.len() < 20
+        || !matches!(text.as_bytes()[10], b'T' | b't' | b' ')
+    {
+        return Err(invalid());
+    }
+    let (date, time) = (&text[..10], &text[11..]);
+    let (year, month, day) = parse_date(date)?;
+
+    let bytes = time.as_bytes();
+    if bytes.len() < 9 || bytes[2] != b':' || bytes[5] != b':' {
+        return Err(invalid());
+    }
+    let hour = digits(&time[0..2]).ok_or_else(invalid)?;
+    let minute = digits(&time[3..5]).ok_or_else(invalid)?;
+    let second = digits(&time[6..8]).ok_or_else(invalid)?;
+    if hour > 23 || minute > 59 || second > 59 {
+        return Err(format!(
+            "'{text}' has a time out of range (leap seconds are not supported)"
+        ));
+    }
+
+    let mut rest = &time[8..];
+    let mut nanos = 0;
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let end = fraction
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(fraction.len());
+        if end == 0 || end > 9 {
+            return Err(invalid());
+        }
+        nanos = digits(&fraction[..end]).ok_or_else(invalid)?
+            * 10u32.pow(9 - end as u32);
+        rest = &fraction[end..];
+    }
+    let offset = parse_offset(rest).ok_or_else(invalid)?;
+
+    let days = days_from_civil(i64::from(year), month, day);
+    let seconds = days * 86_400
+        + i64::from(hour) * 3_600
+        + i64::from(minute) * 60
+        + i64::from(second)
+        - offset;
+    Ok((seconds, nanos))
+}
+
+/// `Z` or `±HH:MM`, as seconds east of UTC
+fn parse_offset(text: &str) -> Option<i64> {
+    if text.eq_ignore_ascii_case("z") {
+        return Some(0);
+    }
+    let sign = match text.as_bytes().first()? {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let (hours, minutes) = text[1..].split_once(':')?;
+    if hours.len() != 2 || minutes.len() != 2 {
+        return None;
+    }
+    let (hours, minutes) = (digits(hours)?, digits(minutes)?);
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    Some(sign * i64::from(hours * 3_600 + minutes * 60))
+}
+
+/// Value of a run of ASCII digits
+fn digits(text: &str) -> Option<u32> {
+    if text.is_empty() || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    let leap = year.is_multiple_of(4)
+        && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+    match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days from 1970-01-01 to the given proleptic Gregorian date
+fn days_from_civil(year: i64, month: u8, day: u8) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5
+            + i64::from(day)
+            - 1;
+    let day_of_era =
+        year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Timestamp with `_` every three digits, like other generated integers
+fn timestamp_literal(timestamp: i64) -> String {
+    let grouped = super::size::size_literal(timestamp.unsigned_abs());
+    if timestamp < 0 {
+        format!("-{grouped}")
+    } else {
+        grouped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::parsing::ResourceKind as AstResourceKind;
+    use std::path::PathBuf;
+
+    fn emit(ty: &dyn ResourceType, value: ScalarValue) -> String {
+        let kind = match value {
+            ScalarValue::Date(_) => AstResourceKind::Date,
+            _ => AstResourceKind::DateTime,
+        };
+        let parsed = ParsedResource {
+            name: "promo_ends".to_string(),
+            kind,
+            value,
+            attributes: Default::default(),
+        };
+        let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
+        let node = ty.build_node(&parsed, origin).unwrap();
+        let key = ResourceKey::from_path("promo_ends");
+        ty.emit_rust(&key, &node, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_rfc3339() {
+        assert_eq!(
+            parse_rfc3339("2025-12-31T23:59:59Z"),
+            Ok((1_767_225_599, 0))
+        );
+        assert_eq!(parse_rfc3339("1970-01-01T00:00:00Z"), Ok((0, 0)));
+        assert_eq!(
+            parse_rfc3339("1970-01-01 02:00:00.25+02:00"),
+            Ok((0, 250_000_000))
+        );
+        assert_eq!(parse_rfc3339("1969-12-31T23:59:59z"), Ok((-1, 0)));
+        assert_eq!(
+            parse_rfc3339("2024-02-29T12:00:00-05:30"),
+            Ok((1_709_227_800, 0))
+        );
+    }
+
+    #[test]
+    fn test_parse_rfc3339_rejects_bad_input() {
+        for text in [
+            "2025-12-31",
+            "2025-12-31T23:59:59",
+            "2025-12-31T24:00:00Z",
+            "2025-12-31T23:59:60Z",
+            "2025-02-29T00:00:00Z",
+            "2025-12-31T23:59:59+2:00",
+            "2025-12-31T23:59:59.Z",
+            "2025-12-31T23:59:5é+00:00",
+        ] {
+            assert!(parse_rfc3339(text).is_err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(parse_date("2025-06-01"), Ok((2025, 6, 1)));
+        assert_eq!(parse_date("2000-02-29"), Ok((2000, 2, 29)));
+        let err = parse_date("1900-02-29").unwrap_err();
+        assert_eq!(err, "'1900-02-29' has day 29, but that month has 28 days");
+        assert!(parse_date("2025-13-01").unwrap_err().contains("month 13"));
+        assert!(parse_date("2025-6-1").is_err());
+    }
+
+    #[test]
+    fn test_emit_datetime() {
+        let code = emit(
+            &DateTimeType,
+            ScalarValue::DateTime("2025-12-31T23:59:59Z".to_string()),
+        );
+        assert!(code.contains("pub const PROMO_ENDS: i64 = 1_767_225_599;"));
+        assert!(code.contains(
+            "pub const PROMO_ENDS_RFC3339: &str = \"2025-12-31T23:59:59Z\";"
+        ));
+        assert_eq!(code.contains("PROMO_ENDS_UTC"), cfg!(feature = "chrono"));
+    }
+
+    #[test]
+    fn test_emit_date() {
+        let code = emit(&DateType, ScalarValue::Date("2025-06-01".into()));
+        assert!(code.contains(
+            "pub const PROMO_ENDS: r_resources::Date = \
+             r_resources::Date::new(2025, 6, 1);"
+        ));
+    }
+}
@@ -0,0 +1,178 @@
+//! Lengths with a density unit (`16dp`, `14sp`, `1px`).
+
+use crate::generator::casing::NamingConfig;
+use crate::generator::ir::types::{EmittedItem, ResourceType};
+use crate::generator::ir::{
+    ResourceKey, ResourceKind, ResourceNode, ResourceOrigin, ResourceValue,
+};
+use crate::generator::parsing::{ParsedResource, ScalarValue};
+use crate::generator::utils::doc_comment;
+
+use super::number::format_float32;
+
+/// Suffixes and the `r_resources::DimensionUnit` variant they stand for;
+/// `dip` is Android's older spelling of `dp`
+const DIMENSION_UNITS: &[(&str, &str)] =
+    &[("dp", "Dp"), ("dip", "Dp"), ("sp", "Sp"), ("px", "Px")];
+
+/// `<dimension name="padding">16dp</dimension>`: an
+/// `r_resources::Dimension`, documented with the original text.
+pub struct DimensionType;
+
+impl ResourceType for DimensionType {
+    fn name(&self) -> &'static str {
+        "dimension"
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        &["dimension"]
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::Dimension
+    }
+
+    fn validate(&self, parsed: &ParsedResource) -> Result<(), String> {
+        let ScalarValue::Dimension(text) = &parsed.value else {
+            return Ok(());
+        };
+        parse_dimension(text).map(|_| ())
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let ScalarValue::Dimension(text) = &parsed.value else {
+            return None;
+        };
+        let (value, unit) = parse_dimension(text).ok()?;
+        Some(ResourceNode {
+            kind: ResourceKind::Dimension,
+            value: ResourceValue::Dimension {
+                value,
+                unit: unit.to_string(),
+                raw: text.clone(),
+            },
+            origin,
+            attributes: Default::default(),
+        })
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        self.emit_rust_named(key, node, indent, &NamingConfig::default())
+    }
+
+    fn emit_rust_named(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+        naming: &NamingConfig,
+    ) -> Option<String> {
+        let ResourceValue::Dimension { value, unit, raw } = &node.value else {
+            return None;
+        };
+        let pad = " ".repeat(indent);
+        let const_name = naming.const_identifier(&key.name);
+        Some(format!(
+            "{}{pad}pub const {const_name}: r_resources::Dimension = \
+             r_resources::Dimension::new({}, \
+             r_resources::DimensionUnit::{unit});\n",
+            doc_comment(&pad, raw),
+            format_float32(*value)
+        ))
+    }
+
+    fn emitted_items(
+        &self,
+        key: &ResourceKey,
+        _node: &ResourceNode,
+        naming: &NamingConfig,
+    ) -> Vec<EmittedItem> {
+        vec![EmittedItem::Const(
+            naming.const_identifier(&key.name),
+        )]
+    }
+}
+
+/// `16dp` as its value and the `DimensionUnit` variant of its unit. The
+/// number is a plain decimal and may be negative (`-4dp` margins).
+fn parse_dimension(text: &str) -> Result<(f32, &'static str), String> {
+    let split = text
+        .find(|c: char| c.is_ascii_alphabetic())
+        .unwrap_or(text.len());
+    let (number, suffix) = text.split_at(split);
+    let Some(&(_, unit)) =
+        DIMENSION_UNITS.iter().find(|(name, _)| *name == suffix)
+    else {
+        return Err(format!(
+            "'{text}' is not a dimension (expected a number followed by \
+             dp, dip, sp or px)"
+        ));
+    };
+    let digits = number.strip_prefix('-').unwrap_or(number);
+    let plain = !digits.is_empty()
+        && !digits.starts_with('.')
+        && !digits.ends_with('.')
+        && digits.bytes().filter(|&b| b == b'.').count() <= 1
+        && digits.bytes().all(|b| b.is_ascii_digit() || b == b'.');
+    let value = number
+        .parse::<f32>()
+        .ok()
+        .filter(|value| plain && value.is_finite())
+        .ok_or_else(|| format!("'{text}' has an invalid number '{number}'"))?;
+    Ok((value, unit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::parsing::ResourceKind as AstResourceKind;
+    use std::path::PathBuf;
+
+    fn parsed(value: &str) -> ParsedResource {
+        ParsedResource {
+            name: "padding".to_string(),
+            kind: AstResourceKind::Dimension,
+            value: ScalarValue::Dimension(value.to_string()),
+            attributes: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_emit_dimension() {
+        let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
+        let node = DimensionType.build_node(&parsed("16dp"), origin).unwrap();
+        let key = ResourceKey::from_path("padding");
+        let code = DimensionType.emit_rust(&key, &node, 0).unwrap();
+        assert_eq!(
+            code,
+            "/// 16dp\npub const PADDING: r_resources::Dimension = \
+             r_resources::Dimension::new(16.0, \
+             r_resources::DimensionUnit::Dp);\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_dimension() {
+        assert_eq!(parse_dimension("16dp"), Ok((16.0, "Dp")));
+        assert_eq!(parse_dimension("1.5dip"), Ok((1.5, "Dp")));
+        assert_eq!(parse_dimension("14sp"), Ok((14.0, "Sp")));
+        assert_eq!(parse_dimension("-4px"), Ok((-4.0, "Px")));
+        for text in ["16", "dp", "16 dp", "16DP", "16pt", ".5dp", "1.dp"] {
+            assert!(parse_dimension(text).is_err(), "{text}");
+        }
+        for text in ["+1dp", "1e3dp", "1.2.3sp", "1e39px"] {
+            assert!(parse_dimension(text).is_err(), "{text}");
+        }
+        let err = DimensionType.validate(&parsed("12pt")).unwrap_err();
+        assert!(err.contains("dp, dip, sp or px"), "{err}");
+    }
+}
@@ -0,0 +1,81 @@
+//! Durations with unit suffixes (`100ms`, `1.5s`, `2h`).
+
+use std::time::Duration;
+
+use super::size::parse_scaled;
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+/// Suffixes and their length in nanoseconds
+const DURATION_UNITS: &[(&str, u128)] = &[
+    ("ns", 1),
+    ("us", 1_000),
+    ("µs", 1_000),
+    ("ms", 1_000_000),
+    ("s", NANOS_PER_SEC),
+    ("m", 60 * NANOS_PER_SEC),
+    ("h", 3_600 * NANOS_PER_SEC),
+    ("d", 86_400 * NANOS_PER_SEC),
+];
+
+/// Duration in `text`; a fraction is accepted when it comes out to whole
+/// nanoseconds (`1.5s`, not `1.5ns`)
+pub fn parse_duration(text: &str) -> Result<Duration, String> {
+    let nanos = parse_scaled(text, DURATION_UNITS)
+        .map_err(|reason| format!("'{text}' is not a duration: {reason}"))?;
+    let secs = u64::try_from(nanos / NANOS_PER_SEC)
+        .map_err(|_| format!("'{text}' does not fit in a Duration"))?;
+    Ok(Duration::new(secs, (nanos % NANOS_PER_SEC) as u32))
+}
+
+/// Const expression for `duration`, with the coarsest exact constructor
+/// (`core::time::Duration::from_millis(100)`)
+pub fn duration_expr(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let nanos = duration.subsec_nanos();
+    // The finer constructors take a u64, which very long durations overflow
+    let millis = u64::try_from(duration.as_millis()).ok();
+    let micros = u64::try_from(duration.as_micros()).ok();
+    let (constructor, args) = match (nanos, millis, micros) {
+        (0, _, _) => ("from_secs", secs.to_string()),
+        (nanos, Some(millis), _) if nanos.is_multiple_of(1_000_000) => {
+            ("from_millis", millis.to_string())
+        }
+        (nanos, _, Some(micros)) if nanos.is_multiple_of(1_000) => {
+            ("from_micros", micros.to_string())
+        }
+        _ => ("new", format!("{secs}, {nanos}")),
+    };
+    format!("core::time::Duration::{constructor}({args})")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration_units() {
+        assert_eq!(parse_duration("100ms"), Ok(Duration::from_millis(100)));
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("2h"), Ok(Duration::from_secs(7200)));
+        assert_eq!(parse_duration("250µs"), Ok(Duration::from_micros(250)));
+    }
+
+    #[test]
+    fn test_parse_duration_rejects_bad_input() {
+        let err = parse_duration("5x").unwrap_err();
+        assert!(err.starts_with("'5x' is not a duration"), "{err}");
+        assert!(parse_duration("1.5ns").is_err());
+        assert!(parse_duration("100").is_err());
+    }
+
+    #[test]
+    fn test_duration_expr_uses_coarsest_constructor() {
+        let expr = |text| duration_expr(parse_duration(text).unwrap());
+        assert_eq!(expr("5s"), "core::time::Duration::from_secs(5)");
+        assert_eq!(expr("1.5s"), "core::time::Duration::from_millis(1500)");
+        assert_eq!(expr("3us"), "core::time::Duration::from_micros(3)");
+        assert_eq!(expr("1s"), "core::time::Duration::from_secs(1)");
+        assert_eq!(expr("7ns"), "core::time::Duration::new(0, 7)");
+    }
+}
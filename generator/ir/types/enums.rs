@@ -0,0 +1,392 @@
+use crate::generator::casing::NamingConfig;
+use crate::generator::ir::types::{EmittedItem, ResourceType};
+use crate::generator::ir::{
+    EnumVariant, ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
+    ResourceValue,
+};
+use crate::generator::parsing::{EnumItem, ParsedResource, ScalarValue};
+use crate::generator::utils::pascal_case;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+/// `<enum name="priority"><item>low</item><item>high</item></enum>`: a
+/// `Priority` enum with one variant per item, `ALL`, and `as_str()`.
+/// With `repr="u8"` (any integer type) every item needs a `value`, and the
+/// enum gets `#[repr(u8)]`, `From<Priority> for u8`, and `TryFrom<u8>`.
+pub struct EnumType;
+
+/// Integer types an enum can be backed by, with their range
+const REPR_TYPES: &[(&str, i128, i128)] = &[
+    ("u8", 0, u8::MAX as i128),
+    ("u16", 0, u16::MAX as i128),
+    ("u32", 0, u32::MAX as i128),
+    ("u64", 0, u64::MAX as i128),
+    ("i8", i8::MIN as i128, i8::MAX as i128),
+    ("i16", i16::MIN as i128, i16::MAX as i128),
+    ("i32", i32::MIN as i128, i32::MAX as i128),
+    ("i64", i64::MIN as i128, i64::MAX as i128),
+];
+
+impl ResourceType for EnumType {
+    fn name(&self) -> &'static str {
+        "enum"
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        &["enum"]
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::Enum
+    }
+
+    fn validate(&self, parsed: &ParsedResource) -> Result<(), String> {
+        let ScalarValue::Enum { repr, items } = &parsed.value else {
+            return Ok(());
+        };
+        if items.is_empty() {
+            return Err("enum has no <item>".to_string());
+        }
+        let range = match repr {
+            Some(repr) => Some(repr_range(repr).ok_or_else(|| {
+                format!(
+                    "unsupported repr '{repr}': expected u8, u16, u32, u64, i8, i16, i32, or i64"
+                )
+            })?),
+            None => None,
+        };
+        let mut variants = HashMap::new();
+        let mut discriminants = HashMap::new();
+        for item in items {
+            let variant = variant_name(item)?;
+            if let Some(other) = variants.insert(variant.clone(), &item.name) {
+                return Err(format!(
+                    "items '{other}' and '{}' both become variant '{variant}'",
+                    item.name
+                ));
+            }
+            let Some(value) = discriminant(item, repr.as_deref(), range)?
+            else {
+                continue;
+            };
+            if let Some(other) = discriminants.insert(value, &item.name) {
+                return Err(format!(
+                    "items '{other}' and '{}' share the discriminant {value}",
+                    item.name
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let ScalarValue::Enum { repr, items } = &parsed.value else {
+            return None;
+        };
+        let variants = items
+            .iter()
+            .map(|item| EnumVariant {
+                name: item.name.clone(),
+                discriminant: item
+                    .value
+                    .as_deref()
+                    .and_then(|value| value.trim().parse().ok()),
+            })
+            .collect();
+        Some(ResourceNode {
+            kind: ResourceKind::Enum,
+            value: ResourceValue::Enum {
+                repr: repr.clone(),
+                variants,
+            },
+            origin,
+            attributes: Default::default(),
+        })
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        let ResourceValue::Enum { repr, variants } = &node.value else {
+            return None;
+        };
+        let pad = " ".repeat(indent);
+        let type_name = pascal_case(&key.name);
+        let idents: Vec<String> =
+            variants.iter().map(|v| pascal_case(&v.name)).collect();
+
+        let mut code = format!(
+            "{pad}#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]\n"
+        );
+        if let Some(repr) = repr {
+            let _ = writeln!(code, "{pad}#[repr({repr})]");
+        }
+        let _ = writeln!(code, "{pad}pub enum {type_name} {{");
+        for (ident, variant) in idents.iter().zip(variants) {
+            match variant.discriminant {
+                Some(value) => {
+                    let _ = writeln!(code, "{pad}    {ident} = {value},");
+                }
+                None => {
+                    let _ = writeln!(code, "{pad}    {ident},");
+                }
+            }
+        }
+        let all: Vec<String> =
+            idents.iter().map(|ident| format!("Self::{ident}")).collect();
+        let _ = write!(
+            code,
+            "{pad}}}\n\
+             {pad}impl {type_name} {{\n\
+             {pad}    /// Every variant, in declaration order\n\
+             {pad}    pub const ALL: &'static [Self] = &[{}];\n\
+             {pad}\n\
+             {pad}    /// The item as written in the resource file\n\
+             {pad}    #[must_use]\n\
+             {pad}    pub const fn as_str(self) -> &'static str {{\n\
+             {pad}        match self {{\n",
+            all.join(", ")
+        );
+        for (ident, variant) in idents.iter().zip(variants) {
+            let _ = writeln!(
+                code,
+                "{pad}            Self::{ident} => \"{}\",",
+                variant.name.escape_debug()
+            );
+        }
+        let _ = writeln!(
+            code,
+            "{pad}        }}\n\
+             {pad}    }}\n\
+             {pad}}}"
+        );
+        if let Some(repr) = repr {
+            emit_conversions(&mut code, &type_name, repr, variants, &pad);
+        }
+        Some(code)
+    }
+
+    fn emitted_items(
+        &self,
+        key: &ResourceKey,
+        _node: &ResourceNode,
+        _naming: &NamingConfig,
+    ) -> Vec<EmittedItem> {
+        vec![EmittedItem::Type(pascal_case(&key.name))]
+    }
+}
+
+/// `From<Enum> for repr` and `TryFrom<repr> for Enum`, whose error is the
+/// unknown discriminant
+fn emit_conversions(
+    code: &mut String,
+    type_name: &str,
+    repr: &str,
+    variants: &[EnumVariant],
+    pad: &str,
+) {
+    let _ = write!(
+        code,
+        "{pad}impl From<{type_name}> for {repr} {{\n\
+         {pad}    fn from(value: {type_name}) -> Self {{\n\
+         {pad}        value as {repr}\n\
+         {pad}    }}\n\
+         {pad}}}\n\
+         {pad}impl TryFrom<{repr}> for {type_name} {{\n\
+         {pad}    /// The value, when no variant has it as discriminant\n\
+         {pad}    type Error = {repr};\n\
+         {pad}\n\
+         {pad}    fn try_from(value: {repr}) -> Result<Self, Self::Error> {{\n\
+         {pad}        match value {{\n"
+    );
+    for variant in variants {
+        if let Some(value) = variant.discriminant {
+            let _ = writeln!(
+                code,
+                "{pad}            {value} => Ok(Self::{}),",
+                pascal_case(&variant.name)
+            );
+        }
+    }
+    let _ = writeln!(
+        code,
+        "{pad}            other => Err(other),\n\
+         {pad}        }}\n\
+         {pad}    }}\n\
+         {pad}}}"
+    );
+}
+
+fn repr_range(repr: &str) -> Option<(i128, i128)> {
+    REPR_TYPES
+        .iter()
+        .find(|(name, _, _)| *name == repr)
+        .map(|&(_, min, max)| (min, max))
+}
+
+fn variant_name(item: &EnumItem) -> Result<String, String> {
+    if item.name.is_empty() {
+        return Err("enum item without text".to_string());
+    }
+    let variant = pascal_case(&item.name);
+    if !variant.starts_with(|c: char| c.is_alphabetic()) {
+        return Err(format!(
+            "item '{}' must start with a letter to become a variant",
+            item.name
+        ));
+    }
+    Ok(variant)
+}
+
+/// The item's discriminant, checked against the repr type; items of an
+/// enum without `repr` have none
+fn discriminant(
+    item: &EnumItem,
+    repr: Option<&str>,
+    range: Option<(i128, i128)>,
+) -> Result<Option<i128>, String> {
+    let name = &item.name;
+    let (value, repr, (min, max)) = match (&item.value, repr, range) {
+        (None, None, _) => return Ok(None),
+        (Some(value), None, _) => {
+            return Err(format!(
+                "item '{name}' has value '{value}' but the enum has no `repr`"
+            ))
+        }
+        (None, Some(repr), _) => {
+            return Err(format!(
+                "item '{name}' has no value; every item of an enum with repr=\"{repr}\" needs one"
+            ))
+        }
+        (Some(value), Some(repr), Some(range)) => (value.trim(), repr, range),
+        (Some(_), Some(_), None) => return Ok(None),
+    };
+    let parsed: i128 = value.parse().map_err(|_| {
+        format!("value '{value}' of item '{name}' is not an integer")
+    })?;
+    if parsed < min || parsed > max {
+        return Err(format!(
+            "value {parsed} of item '{name}' does not fit in {repr} ({min}..={max})"
+        ));
+    }
+    Ok(Some(parsed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::parsing::ResourceKind as AstResourceKind;
+    use std::path::PathBuf;
+
+    fn parsed(
+        repr: Option<&str>,
+        items: &[(&str, Option<&str>)],
+    ) -> ParsedResource {
+        ParsedResource {
+            name: "priority".to_string(),
+            kind: AstResourceKind::Enum,
+            value: ScalarValue::Enum {
+                repr: repr.map(str::to_string),
+                items: items
+                    .iter()
+                    .map(|(name, value)| EnumItem {
+                        name: name.to_string(),
+                        value: value.map(str::to_string),
+                    })
+                    .collect(),
+            },
+            attributes: Default::default(),
+        }
+    }
+
+    fn emit(parsed: &ParsedResource) -> String {
+        let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
+        let node = EnumType.build_node(parsed, origin).unwrap();
+        let key = ResourceKey::from_path("priority");
+        EnumType.emit_rust(&key, &node, 0).unwrap()
+    }
+
+    #[test]
+    fn test_emit_plain_enum() {
+        let code =
+            emit(&parsed(None, &[("low", None), ("very-high", None)]));
+
+        assert!(code
+            .contains("pub enum Priority {\n    Low,\n    VeryHigh,\n}"));
+        assert!(!code.contains("#[repr"));
+        assert!(code.contains(
+            "pub const ALL: &'static [Self] = &[Self::Low, Self::VeryHigh];"
+        ));
+        assert!(code.contains("Self::VeryHigh => \"very-high\","));
+        assert!(!code.contains("TryFrom"));
+    }
+
+    #[test]
+    fn test_emit_repr_enum() {
+        let code = emit(&parsed(
+            Some("u8"),
+            &[("low", Some("0")), ("high", Some("10"))],
+        ));
+
+        assert!(code.contains(
+            "#[repr(u8)]\npub enum Priority {\n    Low = 0,\n    High = 10,\n}"
+        ));
+        assert!(code.contains("impl From<Priority> for u8 {"));
+        assert!(code.contains("impl TryFrom<u8> for Priority {"));
+        assert!(code.contains(
+            "            10 => Ok(Self::High),\n            other => Err(other),"
+        ));
+    }
+
+    #[test]
+    fn test_validate_rejects_discriminant_collisions() {
+        let err = EnumType
+            .validate(&parsed(
+                Some("u8"),
+                &[("low", Some("0")), ("none", Some("0"))],
+            ))
+            .unwrap_err();
+        assert_eq!(err, "items 'low' and 'none' share the discriminant 0");
+    }
+
+    #[test]
+    fn test_validate_rejects_values_outside_repr() {
+        let err = EnumType
+            .validate(&parsed(Some("u8"), &[("huge", Some("300"))]))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            "value 300 of item 'huge' does not fit in u8 (0..=255)"
+        );
+
+        let err = EnumType
+            .validate(&parsed(Some("i8"), &[("cold", Some("-129"))]))
+            .unwrap_err();
+        assert!(err.contains("does not fit in i8 (-128..=127)"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_rejects_inconsistent_items() {
+        let low_twice = [("low", None), ("Low", None)];
+        let cases = [
+            (parsed(None, &[]), "no <item>"),
+            (parsed(Some("f32"), &[("a", Some("1"))]), "unsupported repr"),
+            (parsed(Some("u8"), &[("a", None)]), "item 'a' has no value"),
+            (parsed(None, &[("a", Some("1"))]), "has no `repr`"),
+            (parsed(Some("u8"), &[("a", Some("x"))]), "is not an integer"),
+            (parsed(None, &low_twice), "both become variant 'Low'"),
+            (parsed(None, &[("1st", None)]), "must start with a letter"),
+        ];
+        for (enum_resource, expected) in cases {
+            let err = EnumType.validate(&enum_resource).unwrap_err();
+            assert!(err.contains(expected), "{err}");
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use crate::generator::casing::NamingConfig;
+use crate::generator::ir::types::{EmittedItem, ResourceType};
+use crate::generator::ir::{
+    ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
+    ResourceValue,
+};
+use crate::generator::parsing::{MimeEntry, ParsedResource, ScalarValue};
+use std::collections::HashSet;
+use std::fmt::Write as _;
+
+/// `<mime-map name="upload_types"><entry ext="png">image/png</entry></mime-map>`:
+/// a `&[(&str, &str)]` table plus a case-insensitive `upload_types_for(ext)` lookup.
+pub struct MimeMapType;
+
+impl ResourceType for MimeMapType {
+    fn name(&self) -> &'static str {
+        "mime-map"
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        &["mime-map"]
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::MimeMap
+    }
+
+    fn validate(&self, parsed: &ParsedResource) -> Result<(), String> {
+        let ScalarValue::MimeMap(entries) = &parsed.value else {
+            return Ok(());
+        };
+        let mut seen = HashSet::new();
+        for entry in entries {
+            validate_entry(entry)?;
+            if !seen.insert(entry.ext.as_str()) {
+                return Err(format!(
+                    "duplicate extension '{}' in mime-map",
+                    entry.ext
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let ScalarValue::MimeMap(entries) = &parsed.value else {
+            return None;
+        };
+        let pairs = entries
+            .iter()
+            .map(|entry| (entry.ext.clone(), entry.mime.clone()))
+            .collect();
+        Some(ResourceNode {
+            kind: ResourceKind::MimeMap,
+            value: ResourceValue::MimeMap(pairs),
+            origin,
+            attributes: Default::default(),
+        })
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        self.emit_rust_named(key, node, indent, &NamingConfig::default())
+    }
+
+    fn emit_rust_named(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+        naming: &NamingConfig,
+    ) -> Option<String> {
+        let ResourceValue::MimeMap(pairs) = &node.value else {
+            return None;
+        };
+        let pad = " ".repeat(indent);
+        let const_name = naming.const_identifier(&key.name);
+        let fn_name = naming.companion_fn(&key.name, "for");
+
+        let mut code = format!("{pad}pub const {const_name}: &[(&str, &str)] = &[\n");
+        for (ext, mime) in pairs {
+            let _ = writeln!(
+                code,
+                "{pad}    (\"{}\", \"{}\"),",
+                ext.escape_debug(),
+                mime.escape_debug()
+            );
+        }
+        let _ = write!(
+            code,
+            "{pad}];\n\
+             {pad}/// MIME type registered for `ext` in [`{const_name}`] (case-insensitive)\n\
+             {pad}pub fn {fn_name}(ext: &str) -> Option<&'static str> {{\n\
+             {pad}    {const_name}\n\
+             {pad}        .iter()\n\
+             {pad}        .find(|(known, _)| known.eq_ignore_ascii_case(ext))\n\
+             {pad}        .map(|(_, mime)| *mime)\n\
+             {pad}}}\n"
+        );
+        Some(code)
+    }
+
+    fn emitted_items(
+        &self,
+        key: &ResourceKey,
+        _node: &ResourceNode,
+        naming: &NamingConfig,
+    ) -> Vec<EmittedItem> {
+        vec![
+            EmittedItem::Const(naming.const_identifier(&key.name)),
+            EmittedItem::Function {
+                name: naming.companion_fn(&key.name, "for"),
+                params: vec!["&str".to_string()],
+            },
+        ]
+    }
+}
+
+fn validate_entry(entry: &MimeEntry) -> Result<(), String> {
+    let ext = &entry.ext;
+    if ext.is_empty() {
+        return Err(format!(
+            "mime-map entry '{}' is missing its `ext` attribute",
+            entry.mime
+        ));
+    }
+    let valid_ext = ext
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !valid_ext {
+        return Err(format!(
+            "extension '{ext}' must be lowercase, without a leading dot"
+        ));
+    }
+    if !is_mime_type(&entry.mime) {
+        return Err(format!(
+            "'{}' (extension '{ext}') is not a MIME type of the form type/subtype",
+            entry.mime
+        ));
+    }
+    Ok(())
+}
+
+/// `type/subtype`, both made of RFC 6838 name characters
+fn is_mime_type(value: &str) -> bool {
+    let is_name = |part: &str| {
+        !part.is_empty()
+            && part.chars().all(|c| {
+                c.is_ascii_alphanumeric()
+                    || matches!(c, '!' | '#' | '$' | '&' | '-' | '^' | '_' | '.' | '+')
+            })
+    };
+    value
+        .split_once('/')
+        .is_some_and(|(ty, subtype)| is_name(ty) && is_name(subtype))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::parsing::ResourceKind as AstResourceKind;
+    use std::path::PathBuf;
+
+    fn parsed(entries: &[(&str, &str)]) -> ParsedResource {
+        ParsedResource {
+            name: "upload_types".to_string(),
+            kind: AstResourceKind::MimeMap,
+            value: ScalarValue::MimeMap(
+                entries
+                    .iter()
+                    .map(|(ext, mime)| MimeEntry {
+                        ext: ext.to_string(),
+                        mime: mime.to_string(),
+                    })
+                    .collect(),
+            ),
+            attributes: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_validate_accepts_valid_map() {
+        let map = parsed(&[
+            ("png", "image/png"),
+            ("svg", "image/svg+xml"),
+            ("docx", "application/vnd.openxmlformats-officedocument.wordprocessingml.document"),
+        ]);
+        assert!(MimeMapType.validate(&map).is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_extensions() {
+        for ext in [".png", "PNG", ""] {
+            let err = MimeMapType
+                .validate(&parsed(&[(ext, "image/png")]))
+                .unwrap_err();
+            assert!(err.contains("ext"), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_mime_types() {
+        for mime in ["image", "image/", "/png", "image/png; x=1", "a/b/c"] {
+            let err = MimeMapType
+                .validate(&parsed(&[("png", mime)]))
+                .unwrap_err();
+            assert!(err.contains("type/subtype"), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_duplicate_extensions() {
+        let map = parsed(&[("jpg", "image/jpeg"), ("jpg", "image/jpg")]);
+        let err = MimeMapType.validate(&map).unwrap_err();
+        assert!(err.contains("duplicate extension 'jpg'"), "{err}");
+    }
+
+    #[test]
+    fn test_emit_rust() {
+        let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
+        let map = parsed(&[("png", "image/png"), ("jpg", "image/jpeg")]);
+        let node = MimeMapType.build_node(&map, origin).unwrap();
+        let key = ResourceKey::from_path("upload_types");
+        let code = MimeMapType.emit_rust(&key, &node, 0).unwrap();
+
+        assert!(code.contains("pub const UPLOAD_TYPES: &[(&str, &str)] = &[\n"));
+        assert!(code.contains("    (\"png\", \"image/png\"),\n    (\"jpg\", \"image/jpeg\"),\n];"));
+        assert!(code.contains(
+            "pub fn upload_types_for(ext: &str) -> Option<&'static str> {"
+        ));
+        assert!(code.contains("eq_ignore_ascii_case(ext)"));
+    }
+}
@@ -2,48 +2,136 @@
 //!
 //! Pour ajouter un nouveau type, créez simplement un fichier `ir/types/your_type.rs`
 //! et implémentez le trait `ResourceType`.
+mod array;
 mod bool;
+mod char;
 mod color;
+mod cron;
+mod datetime;
+mod dimension;
+mod duration;
+mod enums;
+mod mime_map;
 mod number;
+mod percent;
+mod raw;
+mod size;
 mod string;
 mod template;
+mod version;
 
+pub use array::{literal_item, sort_items};
+pub(crate) use color::parse_hex_color;
+pub use number::number_literal;
+pub(crate) use number::rust_type as number_rust_type;
+pub(crate) use raw::is_raw;
+pub use template::param_rust_type;
+pub(crate) use template::{
+    count_placeholders, param_identifier, placeholders, template_calls,
+    Placeholder, KEYWORDS, STYLE as TEMPLATE_STYLE,
+    STYLE_BUILDER as TEMPLATE_STYLE_BUILDER,
+};
+
+use std::sync::Arc;
+
+use crate::generator::casing::NamingConfig;
 use crate::generator::ir::{
     ResourceKey, ResourceNode, ResourceOrigin,
 };
 use crate::generator::parsing::ParsedResource;
 
-/// Trait that each resource type must implement
+/// Trait that each resource type must implement.
+///
+/// This trait is also the extension point for project-specific tags, see
+/// [`BuildPlan::with_custom_types`](crate::generator::BuildPlan::with_custom_types).
+/// It is semver-stable: new methods only arrive with default implementations.
 pub trait ResourceType: Send + Sync {
     /// Type name (e.g., "string", "number", "bool")
     fn name(&self) -> &'static str;
 
-    /// XML tags that correspond to this type (e.g., ["string"], ["number", "int", "float"])
-    #[allow(dead_code)] // Reserved for future use
+    /// XML tags that correspond to this type (e.g., ["string"], ["number", "int", "float"]).
+    /// For custom types, resources written with these tags are routed here.
     fn xml_tags(&self) -> &'static [&'static str];
 
-    /// Returns the ResourceKind corresponding to this type
+    /// Returns the ResourceKind corresponding to this type. Custom types use
+    /// `ResourceKind::Custom` so the emitter can tell them apart.
     fn resource_kind(&self) -> crate::generator::ir::ResourceKind;
 
-    /// Converts a `ParsedResource` into a `ResourceNode`
+    /// Checks the parsed content before `build_node`. Errors are recorded on the
+    /// graph and reported by analysis with the resource's file.
+    fn validate(&self, _parsed: &ParsedResource) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Converts a `ParsedResource` into a `ResourceNode`. `None` is reported
+    /// as an invalid value; `validate` gives a more precise message.
     fn build_node(
         &self,
         parsed: &ParsedResource,
         origin: ResourceOrigin,
     ) -> Option<ResourceNode>;
 
-    /// Generates Rust code for this type
+    /// Generates Rust code for this type, with the default naming
     fn emit_rust(
         &self,
         key: &ResourceKey,
         node: &ResourceNode,
         indent: usize,
     ) -> Option<String>;
+
+    /// Generates Rust code for this type, naming its items in the case of
+    /// `naming` ([`NamingConfig::const_identifier`] and the like). This is
+    /// what the build calls; it defaults to [`emit_rust`](Self::emit_rust).
+    fn emit_rust_named(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+        naming: &NamingConfig,
+    ) -> Option<String> {
+        let _ = naming;
+        self.emit_rust(key, node, indent)
+    }
+
+    /// Items `emit_rust_named` defines for this resource under `naming`,
+    /// used for re-exports and smoke tests. Defaults to the single
+    /// constant.
+    fn emitted_items(
+        &self,
+        key: &ResourceKey,
+        _node: &ResourceNode,
+        naming: &NamingConfig,
+    ) -> Vec<EmittedItem> {
+        vec![EmittedItem::Const(naming.const_identifier(&key.name))]
+    }
+}
+
+/// Item defined by [`ResourceType::emit_rust`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EmittedItem {
+    /// `pub const NAME`
+    Const(String),
+    /// `pub static NAME: LazyLock<_>`, initialized on first access
+    Lazy(String),
+    /// `pub fn name(..)`, with the Rust type of each parameter
+    Function { name: String, params: Vec<String> },
+    /// `pub enum Name` or `pub struct Name`
+    Type(String),
+}
+
+impl EmittedItem {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Const(name) | Self::Lazy(name) | Self::Type(name) => name,
+            Self::Function { name, .. } => name,
+        }
+    }
 }
 
 /// Global registry for resource types
+#[derive(Clone)]
 pub struct TypeRegistry {
-    types: Vec<Box<dyn ResourceType>>,
+    types: Vec<Arc<dyn ResourceType>>,
 }
 
 impl TypeRegistry {
@@ -51,9 +139,16 @@ impl TypeRegistry {
         Self { types: Vec::new() }
     }
 
+    /// Default types followed by `custom` ones
+    pub fn with_custom_types(custom: &[Arc<dyn ResourceType>]) -> Self {
+        let mut registry = Self::default();
+        registry.types.extend(custom.iter().cloned());
+        registry
+    }
+
     /// Registers a new type
     pub fn register(&mut self, ty: Box<dyn ResourceType>) {
-        self.types.push(ty);
+        self.types.push(Arc::from(ty));
     }
 
     /// Finds a type by its name
@@ -68,7 +163,6 @@ impl TypeRegistry {
     }
 
     /// Finds a type by XML tag
-    #[allow(dead_code)] // Public API, reserved for future use
     pub fn find_by_xml_tag(
         &self,
         tag: &str,
@@ -80,7 +174,7 @@ impl TypeRegistry {
     }
 
     /// Returns all registered types
-    pub fn all(&self) -> &[Box<dyn ResourceType>] {
+    pub fn all(&self) -> &[Arc<dyn ResourceType>] {
         &self.types
     }
 }
@@ -92,7 +186,23 @@ impl Default for TypeRegistry {
         registry.register(Box::new(number::NumberTypeHandler));
         registry.register(Box::new(bool::BoolType));
         registry.register(Box::new(color::ColorType));
+        registry.register(Box::new(char::CharType));
+        registry.register(Box::new(percent::PercentType));
+        registry.register(Box::new(cron::CronType));
+        registry.register(Box::new(size::SizeType));
+        registry.register(Box::new(datetime::DateTimeType));
+        registry.register(Box::new(datetime::DateType));
+        registry.register(Box::new(version::VersionType));
+        registry.register(Box::new(dimension::DimensionType));
+        registry.register(Box::new(mime_map::MimeMapType));
+        registry.register(Box::new(enums::EnumType));
+        registry.register(Box::new(array::STRING_ARRAY));
+        registry.register(Box::new(array::INT_ARRAY));
+        registry.register(Box::new(array::FLOAT_ARRAY));
+        registry.register(Box::new(array::DURATION_ARRAY));
+        registry.register(Box::new(array::SIZE_ARRAY));
         registry.register(Box::new(template::TemplateType));
+        registry.register(Box::new(raw::RawType));
         registry
     }
 }
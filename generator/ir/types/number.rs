@@ -1,3 +1,4 @@
+use crate::generator::casing::NamingConfig;
 use crate::generator::ir::model::{NumberType, NumberValue};
 use crate::generator::ir::types::ResourceType;
 use crate::generator::ir::{
@@ -5,7 +6,6 @@ use crate::generator::ir::{
     ResourceValue,
 };
 use crate::generator::parsing::{ParsedResource, ScalarValue};
-use crate::generator::utils::sanitize_identifier;
 use std::str::FromStr;
 
 pub struct NumberTypeHandler;
@@ -23,6 +23,17 @@ impl ResourceType for NumberTypeHandler {
         ResourceKind::Number
     }
 
+    fn validate(&self, parsed: &ParsedResource) -> Result<(), String> {
+        let ScalarValue::Number {
+            value,
+            explicit_type,
+        } = &parsed.value
+        else {
+            return Ok(());
+        };
+        parse_number_value(value, explicit_type.as_deref()).map(|_| ())
+    }
+
     fn build_node(
         &self,
         parsed: &ParsedResource,
@@ -41,6 +52,7 @@ impl ResourceType for NumberTypeHandler {
                 kind: ResourceKind::Number,
                 value: ResourceValue::Number(number_value),
                 origin,
+                attributes: Default::default(),
             })
         } else {
             None
@@ -52,35 +64,37 @@ impl ResourceType for NumberTypeHandler {
         key: &ResourceKey,
         node: &ResourceNode,
         indent: usize,
+    ) -> Option<String> {
+        self.emit_rust_named(key, node, indent, &NamingConfig::default())
+    }
+
+    fn emit_rust_named(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+        naming: &NamingConfig,
     ) -> Option<String> {
         if let ResourceValue::Number(number_value) = &node.value {
             let pad = " ".repeat(indent);
-            let const_name =
-                sanitize_identifier(&key.name).to_uppercase();
+            let const_name = naming.const_identifier(&key.name);
 
             Some(match number_value {
-                NumberValue::Int(i) => format!(
-                    "{pad}pub const {const_name}: i64 = {i};\n"
-                ),
-                NumberValue::Float(f) => {
-                    let formatted = format_float(*f);
-                    format!("{pad}pub const {const_name}: f64 = {formatted};\n")
-                }
                 NumberValue::BigDecimal(raw) => {
                     let literal = escape_literal(raw);
+                    // One shared parse function keeps each static short
                     format!(
-                        "{pad}pub static {const_name}: std::sync::LazyLock<r_resources::BigDecimal> = std::sync::LazyLock::new(|| {{\n\
-                        {pad}    r_resources::BigDecimal::from_str(\"{literal}\").expect(\"valid decimal literal\")\n\
-                        {pad}}});\n"
-                    )
-                }
-                NumberValue::Typed { literal, ty } => {
-                    format!(
-                        "{pad}pub const {const_name}: {} = {};\n",
-                        ty.as_str(),
-                        literal
+                        "{pad}pub static {const_name}: std::sync::LazyLock<r_resources::BigDecimal> = \
+                         std::sync::LazyLock::new(|| \
+                         r_resources::__parse_big_decimal(\"{literal}\"));\n"
                     )
                 }
+                _ => format!(
+                    "{pad}pub const {const_name}: {} = {}{};\n",
+                    rust_type(number_value),
+                    number_literal(number_value),
+                    float_suffix(number_value)
+                ),
             })
         } else {
             None
@@ -88,6 +102,40 @@ impl ResourceType for NumberTypeHandler {
     }
 }
 
+/// Literal text of a number as emitted in generated code (`3`, `2.5`, `1e10`).
+///
+/// Also used wherever a number is rendered as text, e.g. string interpolation.
+pub fn number_literal(value: &NumberValue) -> String {
+    match value {
+        NumberValue::Int(i) => i.to_string(),
+        NumberValue::Float(f) => format_float(*f),
+        NumberValue::BigDecimal(raw) => raw.clone(),
+        NumberValue::Typed { literal, .. } => literal.clone(),
+    }
+}
+
+/// Typed floats keep their literal as written, which may have no `.`
+/// (`1e-7`, `5`); the suffix makes it a float either way
+fn float_suffix(value: &NumberValue) -> &'static str {
+    match value {
+        NumberValue::Typed {
+            ty: ty @ (NumberType::F32 | NumberType::F64),
+            ..
+        } => ty.as_str(),
+        _ => "",
+    }
+}
+
+/// Type of the constant emitted for `value`
+pub(crate) fn rust_type(value: &NumberValue) -> &'static str {
+    match value {
+        NumberValue::Int(_) => "i64",
+        NumberValue::Float(_) => "f64",
+        NumberValue::BigDecimal(_) => "r_resources::BigDecimal",
+        NumberValue::Typed { ty, .. } => ty.as_str(),
+    }
+}
+
 fn parse_number_value(
     text: &str,
     explicit_type: Option<&str>,
@@ -189,16 +237,18 @@ fn parse_explicit_number(
                 |_| format!("'{trimmed}' does not fit in u64"),
             )?
         }
-        NumberType::F32 => {
-            trimmed.parse::<f32>().map(format_float32).map_err(
-                |_| format!("'{trimmed}' is not a valid f32 literal"),
-            )?
-        }
-        NumberType::F64 => {
-            trimmed.parse::<f64>().map(format_float64).map_err(
-                |_| format!("'{trimmed}' is not a valid f64 literal"),
-            )?
-        }
+        NumberType::F32 => match trimmed.parse::<f32>() {
+            Ok(value) if value.is_finite() => {
+                float_literal(trimmed, || format_float32(value))
+            }
+            _ => return Err(format!("'{trimmed}' is not a valid f32 literal")),
+        },
+        NumberType::F64 => match trimmed.parse::<f64>() {
+            Ok(value) if value.is_finite() => {
+                float_literal(trimmed, || format_float64(value))
+            }
+            _ => return Err(format!("'{trimmed}' is not a valid f64 literal")),
+        },
     };
 
     Ok(NumberValue::Typed {
@@ -207,6 +257,37 @@ fn parse_explicit_number(
     })
 }
 
+/// The literal as written when Rust accepts it with a type suffix, so
+/// `3.0000001`, `2.50` or `-0.0` keep their digits; otherwise (`.5`, `+1`,
+/// `1.`) the reformatted value
+fn float_literal(text: &str, reformat: impl FnOnce() -> String) -> String {
+    if is_rust_float_literal(text) {
+        text.to_string()
+    } else {
+        reformat()
+    }
+}
+
+/// `-`? digits (`.` digits)? (`e` [+-]? digits)?
+fn is_rust_float_literal(text: &str) -> bool {
+    let digits = |part: &str| {
+        !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit())
+    };
+    let body = text.strip_prefix('-').unwrap_or(text);
+    let (mantissa, exponent) = match body.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (body, None),
+    };
+    let mantissa_ok = match mantissa.split_once('.') {
+        Some((whole, fraction)) => digits(whole) && digits(fraction),
+        None => digits(mantissa),
+    };
+    let exponent_ok = exponent.is_none_or(|exponent| {
+        digits(exponent.strip_prefix(['+', '-']).unwrap_or(exponent))
+    });
+    mantissa_ok && exponent_ok
+}
+
 fn looks_like_integer(literal: &str) -> bool {
     !(literal.contains('.')
         || literal.contains('e')
@@ -231,10 +312,8 @@ fn count_significant_digits(literal: &str) -> usize {
     
     for ch in literal.chars() {
         match ch {
-            '0'..='9' => {
-                if !in_exponent {
-                    digits += 1;
-                }
+            '0'..='9' if !in_exponent => {
+                digits += 1;
             }
             'e' | 'E' => {
                 in_exponent = true;
@@ -261,7 +340,7 @@ fn format_float(value: f64) -> String {
     }
 }
 
-fn format_float32(value: f32) -> String {
+pub(super) fn format_float32(value: f32) -> String {
     let s = value.to_string();
     if s.contains('.') || s.contains('e') || s.contains('E') {
         s
@@ -284,6 +363,7 @@ fn escape_literal(literal: &str) -> String {
 }
 
 #[cfg(test)]
+#[allow(clippy::approx_constant)] // 3.14 is a readable fixture, not PI
 mod tests {
     use super::*;
     use crate::generator::ir::model::{NumberType, ResourceKind as ModelResourceKind};
@@ -442,6 +522,47 @@ mod tests {
         assert!(matches!(result, NumberValue::Typed { ty: NumberType::F64, .. }));
     }
 
+    #[test]
+    fn test_explicit_floats_keep_their_literal() {
+        let literal = |text: &str, ty: &str| {
+            number_literal(&parse_explicit_number(text, ty).unwrap())
+        };
+        assert_eq!(literal("3.0000001", "f32"), "3.0000001");
+        assert_eq!(literal("2.50", "f64"), "2.50");
+        assert_eq!(literal("1e-7", "f32"), "1e-7");
+        assert_eq!(literal("-6.02E+23", "f64"), "-6.02E+23");
+        assert_eq!(literal("-0.0", "f32"), "-0.0");
+        assert_eq!(literal(" 5 ", "f64"), "5");
+        // Valid for `parse` but not as Rust source
+        assert_eq!(literal(".5", "f32"), "0.5");
+        assert_eq!(literal("+1.5", "f64"), "1.5");
+        assert_eq!(literal("1.", "f64"), "1.0");
+        assert_eq!(literal("-0", "f32"), "-0");
+        for text in ["inf", "NaN", "1e39"] {
+            assert!(parse_explicit_number(text, "f32").is_err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_emit_rust_explicit_float_suffix() {
+        let emit = |text: &str, ty: &str| {
+            let node = ResourceNode {
+                kind: ModelResourceKind::Number,
+                value: ResourceValue::Number(
+                    parse_explicit_number(text, ty).unwrap(),
+                ),
+                origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+                attributes: Default::default(),
+            };
+            let key = ResourceKey::from_path("x");
+            NumberTypeHandler.emit_rust(&key, &node, 0).unwrap()
+        };
+        assert_eq!(emit("1e-7", "f32"), "pub const X: f32 = 1e-7f32;\n");
+        assert_eq!(emit("-0.0", "f64"), "pub const X: f64 = -0.0f64;\n");
+        assert_eq!(emit("2.50", "f64"), "pub const X: f64 = 2.50f64;\n");
+        assert_eq!(emit("7", "i32"), "pub const X: i32 = 7;\n");
+    }
+
     #[test]
     fn test_parse_explicit_unsupported_type() {
         let result = parse_explicit_number("123", "invalid");
@@ -547,6 +668,7 @@ mod tests {
             kind: ModelResourceKind::Number,
             value: ResourceValue::Number(NumberValue::Int(42)),
             origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
         };
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
@@ -565,6 +687,7 @@ mod tests {
             kind: ModelResourceKind::Number,
             value: ResourceValue::Number(NumberValue::Float(3.14)),
             origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
         };
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
@@ -586,14 +709,17 @@ mod tests {
                 "12345678901234567890.123456789".to_string(),
             )),
             origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
         };
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
-        assert!(result.contains("pub static BIG_NUMBER"));
-        assert!(result.contains("LazyLock"));
-        assert!(result.contains("BigDecimal"));
-        assert!(result.contains("12345678901234567890.123456789"));
-        assert!(result.contains("from_str"));
+        assert_eq!(
+            result,
+            "    pub static BIG_NUMBER: \
+             std::sync::LazyLock<r_resources::BigDecimal> = \
+             std::sync::LazyLock::new(|| r_resources::__parse_big_decimal(\
+             \"12345678901234567890.123456789\"));\n"
+        );
     }
 
     // Test emit_rust for Typed
@@ -611,6 +737,7 @@ mod tests {
                 ty: NumberType::I8,
             }),
             origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
         };
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
@@ -628,6 +755,7 @@ mod tests {
                 value: "42".to_string(),
                 explicit_type: None,
             },
+            attributes: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -649,6 +777,7 @@ mod tests {
                 value: "127".to_string(),
                 explicit_type: Some("i8".to_string()),
             },
+            attributes: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -670,6 +799,7 @@ mod tests {
                 value: "not_a_number".to_string(),
                 explicit_type: None,
             },
+            attributes: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
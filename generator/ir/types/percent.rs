@@ -0,0 +1,213 @@
+use crate::generator::casing::NamingConfig;
+use crate::generator::ir::types::number::format_float32;
+use crate::generator::ir::types::{EmittedItem, ResourceType};
+use crate::generator::ir::{
+    ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
+    ResourceValue,
+};
+use crate::generator::parsing::{ParsedResource, ScalarValue};
+use crate::generator::utils::doc_comment;
+
+/// `<percent name="sidebar_width">37.5%</percent>`: an `f32` ratio in `0..=1`,
+/// plus a `_RAW` string const holding the original text.
+pub struct PercentType;
+
+impl ResourceType for PercentType {
+    fn name(&self) -> &'static str {
+        "percent"
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        &["percent"]
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::Percent
+    }
+
+    fn validate(&self, parsed: &ParsedResource) -> Result<(), String> {
+        let ScalarValue::Percent {
+            value,
+            allow_over_100,
+        } = &parsed.value
+        else {
+            return Ok(());
+        };
+        parse_percent(value, *allow_over_100).map(|_| ())
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let ScalarValue::Percent {
+            value,
+            allow_over_100,
+        } = &parsed.value
+        else {
+            return None;
+        };
+        let ratio = parse_percent(value, *allow_over_100).ok()?;
+        Some(ResourceNode {
+            kind: ResourceKind::Percent,
+            value: ResourceValue::Percent {
+                ratio,
+                raw: value.clone(),
+            },
+            origin,
+            attributes: Default::default(),
+        })
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        self.emit_rust_named(key, node, indent, &NamingConfig::default())
+    }
+
+    fn emit_rust_named(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+        naming: &NamingConfig,
+    ) -> Option<String> {
+        let ResourceValue::Percent { ratio, raw } = &node.value else {
+            return None;
+        };
+        let pad = " ".repeat(indent);
+        let const_name = naming.const_identifier(&key.name);
+        let literal = format_float32(*ratio as f32);
+        let escaped = raw.escape_debug();
+        let raw_name = naming.companion_identifier(&const_name, "raw");
+        Some(format!(
+            "{}{pad}pub const {const_name}: f32 = {literal};\n\
+             {pad}/// Original text of [`{const_name}`]\n\
+             {pad}pub const {raw_name}: &str = \"{escaped}\";\n",
+            doc_comment(&pad, raw)
+        ))
+    }
+
+    fn emitted_items(
+        &self,
+        key: &ResourceKey,
+        _node: &ResourceNode,
+        naming: &NamingConfig,
+    ) -> Vec<EmittedItem> {
+        let const_name = naming.const_identifier(&key.name);
+        vec![
+            EmittedItem::Const(const_name.clone()),
+            EmittedItem::Const(naming.companion_identifier(&const_name, "raw")),
+        ]
+    }
+}
+
+/// Parses `37.5%` into the ratio `0.375`.
+///
+/// Values must lie in `0..=100` unless `allow_over_100` is set.
+fn parse_percent(text: &str, allow_over_100: bool) -> Result<f64, String> {
+    let Some(number) = text.trim().strip_suffix('%') else {
+        return Err(format!(
+            "expected a percentage like '37.5%', found '{text}'"
+        ));
+    };
+    let percent = number
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite())
+        .ok_or_else(|| format!("'{text}' is not a valid percentage"))?;
+    if percent < 0.0 {
+        return Err(format!("'{text}' is negative"));
+    }
+    if percent > 100.0 && !allow_over_100 {
+        return Err(format!(
+            "'{text}' is over 100% (set allow-over-100=\"true\" to allow it)"
+        ));
+    }
+    Ok(percent / 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::parsing::ResourceKind as AstResourceKind;
+    use std::path::PathBuf;
+
+    fn parsed(value: &str, allow_over_100: bool) -> ParsedResource {
+        ParsedResource {
+            name: "sidebar_width".to_string(),
+            kind: AstResourceKind::Percent,
+            value: ScalarValue::Percent {
+                value: value.to_string(),
+                allow_over_100,
+            },
+            attributes: Default::default(),
+        }
+    }
+
+    fn origin() -> ResourceOrigin {
+        ResourceOrigin::new(PathBuf::from("test.xml"), false)
+    }
+
+    #[test]
+    fn test_parse_percent() {
+        assert_eq!(parse_percent("37.5%", false), Ok(0.375));
+        assert_eq!(parse_percent("0%", false), Ok(0.0));
+        assert_eq!(parse_percent("100 %", false), Ok(1.0));
+        assert_eq!(parse_percent("150%", true), Ok(1.5));
+    }
+
+    #[test]
+    fn test_parse_percent_rejects_invalid() {
+        assert!(parse_percent("37.5", false)
+            .unwrap_err()
+            .contains("expected a percentage"));
+        assert!(parse_percent("abc%", false)
+            .unwrap_err()
+            .contains("not a valid percentage"));
+        assert!(parse_percent("-5%", true)
+            .unwrap_err()
+            .contains("negative"));
+        assert!(parse_percent("150%", false)
+            .unwrap_err()
+            .contains("allow-over-100"));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(PercentType.validate(&parsed("50%", false)).is_ok());
+        assert!(PercentType.validate(&parsed("120%", false)).is_err());
+        assert!(PercentType.validate(&parsed("120%", true)).is_ok());
+    }
+
+    #[test]
+    fn test_build_and_emit() {
+        let node =
+            PercentType.build_node(&parsed("37.5%", false), origin()).unwrap();
+        assert_eq!(node.kind, ResourceKind::Percent);
+
+        let key = ResourceKey::from_path("sidebar_width");
+        let code = PercentType.emit_rust(&key, &node, 4).unwrap();
+        assert_eq!(
+            code,
+            "    /// 37.5%\n\
+             \x20   pub const SIDEBAR_WIDTH: f32 = 0.375;\n\
+             \x20   /// Original text of [`SIDEBAR_WIDTH`]\n\
+             \x20   pub const SIDEBAR_WIDTH_RAW: &str = \"37.5%\";\n"
+        );
+    }
+
+    #[test]
+    fn test_emit_whole_ratio_keeps_float_literal() {
+        let node =
+            PercentType.build_node(&parsed("100%", false), origin()).unwrap();
+        let key = ResourceKey::from_path("full");
+        let code = PercentType.emit_rust(&key, &node, 0).unwrap();
+        assert!(code.contains("pub const FULL: f32 = 1.0;"));
+    }
+}
@@ -0,0 +1,152 @@
+use crate::generator::casing::NamingConfig;
+use crate::generator::ir::types::ResourceType;
+use crate::generator::ir::{
+    ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
+    ResourceValue,
+};
+use crate::generator::parsing::{ParsedResource, ScalarValue};
+
+/// Tag of verbatim Rust constants
+pub(crate) const RAW_TAG: &str = "raw";
+
+/// Attribute naming the Rust type of the constant
+const TYPE: &str = "type";
+
+/// `<raw name="retry_policy" type="crate::RetryPolicy">crate::RetryPolicy::exponential(3, 250)</raw>`:
+/// a constant of any type, its expression emitted as written.
+///
+/// Nothing is checked beyond the type and expression being there, so an
+/// invalid expression only fails when the consumer crate compiles. Analysis
+/// rejects the tag unless `ValidationOptions::allow_raw` is set.
+pub struct RawType;
+
+impl ResourceType for RawType {
+    fn name(&self) -> &'static str {
+        RAW_TAG
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        &[RAW_TAG]
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::Custom(RAW_TAG.to_string())
+    }
+
+    fn validate(&self, parsed: &ParsedResource) -> Result<(), String> {
+        let has_type = parsed
+            .attributes
+            .get(TYPE)
+            .is_some_and(|ty| !ty.trim().is_empty());
+        if !has_type {
+            return Err(format!(
+                "<{RAW_TAG}> needs a `{TYPE}` attribute naming the Rust type \
+                 of the constant"
+            ));
+        }
+        match &parsed.value {
+            ScalarValue::Text(text) if text.trim().is_empty() => Err(format!(
+                "<{RAW_TAG}> is empty; write the Rust expression of the \
+                 constant"
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let ScalarValue::Text(expression) = &parsed.value else {
+            return None;
+        };
+        Some(ResourceNode {
+            kind: self.resource_kind(),
+            value: ResourceValue::String(expression.trim().to_string()),
+            origin,
+            attributes: Default::default(),
+        })
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        self.emit_rust_named(key, node, indent, &NamingConfig::default())
+    }
+
+    fn emit_rust_named(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+        naming: &NamingConfig,
+    ) -> Option<String> {
+        let ResourceValue::String(expression) = &node.value else {
+            return None;
+        };
+        let ty = node.attributes.get(TYPE)?.trim();
+        let pad = " ".repeat(indent);
+        let const_name = naming.const_identifier(&key.name);
+        Some(format!("{pad}pub const {const_name}: {ty} = {expression};\n"))
+    }
+}
+
+/// Whether `node` is a `<raw>` constant, whose text is Rust code rather
+/// than a value
+pub(crate) fn is_raw(node: &ResourceNode) -> bool {
+    matches!(&node.kind, ResourceKind::Custom(tag) if tag == RAW_TAG)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::parsing::ResourceKind as AstResourceKind;
+    use std::path::PathBuf;
+
+    fn raw(ty: Option<&str>, expression: &str) -> ParsedResource {
+        ParsedResource {
+            name: "retry_policy".to_string(),
+            kind: AstResourceKind::Custom(RAW_TAG.to_string()),
+            value: ScalarValue::Text(expression.to_string()),
+            attributes: ty
+                .map(|ty| (TYPE.to_string(), ty.to_string()))
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_emits_the_expression_verbatim() {
+        let parsed = raw(
+            Some("crate::RetryPolicy"),
+            "crate::RetryPolicy::exponential(3, 250)",
+        );
+        assert!(RawType.validate(&parsed).is_ok());
+        let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
+        let mut node = RawType.build_node(&parsed, origin).unwrap();
+        node.attributes = parsed.attributes.clone();
+        assert!(is_raw(&node));
+        let key = ResourceKey::from_path("retry_policy");
+        assert_eq!(
+            RawType.emit_rust(&key, &node, 4).unwrap(),
+            "    pub const RETRY_POLICY: crate::RetryPolicy = \
+             crate::RetryPolicy::exponential(3, 250);\n"
+        );
+    }
+
+    #[test]
+    fn test_validate_needs_a_type_and_an_expression() {
+        assert!(RawType
+            .validate(&raw(None, "1"))
+            .unwrap_err()
+            .contains("needs a `type` attribute"));
+        assert!(RawType
+            .validate(&raw(Some("u8"), "  "))
+            .unwrap_err()
+            .contains("is empty"));
+    }
+}
@@ -0,0 +1,238 @@
+//! Byte sizes with unit suffixes (`25MB`, `1.5GiB`), also the parser behind
+//! other `<number><unit>` values such as durations.
+
+use crate::generator::casing::NamingConfig;
+use crate::generator::ir::types::{EmittedItem, ResourceType};
+use crate::generator::ir::{
+    ResourceKey, ResourceKind, ResourceNode, ResourceOrigin, ResourceValue,
+};
+use crate::generator::parsing::{ParsedResource, ScalarValue};
+use crate::generator::utils::doc_comment;
+
+/// Decimal (`KB` = 1000 bytes) and binary (`KiB` = 1024 bytes) suffixes
+const SIZE_UNITS: &[(&str, u128)] = &[
+    ("B", 1),
+    ("KB", 1_000),
+    ("MB", 1_000_000),
+    ("GB", 1_000_000_000),
+    ("KiB", 1 << 10),
+    ("MiB", 1 << 20),
+    ("GiB", 1 << 30),
+];
+
+/// `<size name="max_upload">25MB</size>`: a `u64` byte count, documented
+/// with the original text.
+pub struct SizeType;
+
+impl ResourceType for SizeType {
+    fn name(&self) -> &'static str {
+        "size"
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        &["size"]
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::Size
+    }
+
+    fn validate(&self, parsed: &ParsedResource) -> Result<(), String> {
+        let ScalarValue::Size(text) = &parsed.value else {
+            return Ok(());
+        };
+        parse_size(text).map(|_| ())
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let ScalarValue::Size(text) = &parsed.value else {
+            return None;
+        };
+        Some(ResourceNode {
+            kind: ResourceKind::Size,
+            value: ResourceValue::Size {
+                bytes: parse_size(text).ok()?,
+                raw: text.clone(),
+            },
+            origin,
+            attributes: Default::default(),
+        })
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        self.emit_rust_named(key, node, indent, &NamingConfig::default())
+    }
+
+    fn emit_rust_named(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+        naming: &NamingConfig,
+    ) -> Option<String> {
+        let ResourceValue::Size { bytes, raw } = &node.value else {
+            return None;
+        };
+        let pad = " ".repeat(indent);
+        let const_name = naming.const_identifier(&key.name);
+        Some(format!(
+            "{}{pad}pub const {const_name}: u64 = {};\n",
+            doc_comment(&pad, &format!("{raw} (in bytes)")),
+            size_literal(*bytes)
+        ))
+    }
+
+    fn emitted_items(
+        &self,
+        key: &ResourceKey,
+        _node: &ResourceNode,
+        naming: &NamingConfig,
+    ) -> Vec<EmittedItem> {
+        vec![EmittedItem::Const(
+            naming.const_identifier(&key.name),
+        )]
+    }
+}
+
+/// Bytes in `text`; a fraction is accepted when it comes out to whole
+/// bytes (`1.5KiB` is 1536, `1.5B` is an error)
+pub fn parse_size(text: &str) -> Result<u64, String> {
+    let bytes = parse_scaled(text, SIZE_UNITS)
+        .map_err(|reason| format!("'{text}' is not a size: {reason}"))?;
+    u64::try_from(bytes)
+        .map_err(|_| format!("'{text}' does not fit in u64 bytes"))
+}
+
+/// Byte count with `_` every three digits (`26_214_400`)
+pub fn size_literal(bytes: u64) -> String {
+    let digits = bytes.to_string();
+    let mut literal = String::with_capacity(digits.len() * 4 / 3);
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            literal.push('_');
+        }
+        literal.push(digit);
+    }
+    literal
+}
+
+/// `<number><unit>` as a count of the smallest unit (`units[0]`, whose
+/// multiplier is 1). The number is a plain decimal; its fraction must come
+/// out to a whole count.
+pub(super) fn parse_scaled(
+    text: &str,
+    units: &[(&str, u128)],
+) -> Result<u128, String> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let unit = unit.trim();
+    let Some(&(_, multiplier)) = units.iter().find(|(name, _)| *name == unit)
+    else {
+        let names: Vec<&str> = units.iter().map(|(name, _)| *name).collect();
+        return Err(format!(
+            "expected a number followed by one of {}",
+            names.join(", ")
+        ));
+    };
+
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+    if whole.is_empty() && fraction.is_empty() || fraction.contains('.') {
+        return Err(format!("'{number}' is not a number"));
+    }
+    if fraction.len() > 18 {
+        return Err("too many decimal places".to_string());
+    }
+    let too_large = || "value too large".to_string();
+    let whole: u128 = match whole {
+        "" => 0,
+        digits => digits.parse().map_err(|_| too_large())?,
+    };
+    let scale = 10u128.pow(fraction.len() as u32);
+    let fraction: u128 = fraction.parse().unwrap_or(0);
+    let fraction = fraction * multiplier;
+    if !fraction.is_multiple_of(scale) {
+        return Err(format!("not a whole number of {}", units[0].0));
+    }
+    whole
+        .checked_mul(multiplier)
+        .and_then(|value| value.checked_add(fraction / scale))
+        .ok_or_else(too_large)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::parsing::ResourceKind as AstResourceKind;
+    use std::path::PathBuf;
+
+    fn parsed(value: &str) -> ParsedResource {
+        ParsedResource {
+            name: "max_upload".to_string(),
+            kind: AstResourceKind::Size,
+            value: ScalarValue::Size(value.to_string()),
+            attributes: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_emit_size_with_original_text() {
+        let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
+        let node = SizeType.build_node(&parsed("25MiB"), origin).unwrap();
+        let key = ResourceKey::from_path("max_upload");
+        let code = SizeType.emit_rust(&key, &node, 0).unwrap();
+        assert_eq!(
+            code,
+            "/// 25MiB (in bytes)\npub const MAX_UPLOAD: u64 = 26_214_400;\n"
+        );
+    }
+
+    #[test]
+    fn test_validate_size() {
+        assert!(SizeType.validate(&parsed("1.5GiB")).is_ok());
+        let err = SizeType.validate(&parsed("1.5B")).unwrap_err();
+        assert!(err.contains("not a whole number of B"), "{err}");
+        let err = SizeType.validate(&parsed("20EB")).unwrap_err();
+        assert!(err.contains("expected a number followed by"), "{err}");
+    }
+
+    #[test]
+    fn test_parse_size_units() {
+        assert_eq!(parse_size("512B"), Ok(512));
+        assert_eq!(parse_size("25MB"), Ok(25_000_000));
+        assert_eq!(parse_size("25 MiB"), Ok(26_214_400));
+        assert_eq!(parse_size("1.5GiB"), Ok(1_610_612_736));
+        assert_eq!(parse_size("0.5KB"), Ok(500));
+    }
+
+    #[test]
+    fn test_parse_size_rejects_bad_input() {
+        let err = parse_size("1.5B").unwrap_err();
+        assert_eq!(err, "'1.5B' is not a size: not a whole number of B");
+        assert!(parse_size("25").unwrap_err().contains("KiB"));
+        assert!(parse_size("1.2.3MB").is_err());
+        assert!(parse_size("MB").is_err());
+        assert_eq!(
+            parse_size("99999999999GiB").unwrap_err(),
+            "'99999999999GiB' does not fit in u64 bytes"
+        );
+    }
+
+    #[test]
+    fn test_size_literal_groups_digits() {
+        assert_eq!(size_literal(0), "0");
+        assert_eq!(size_literal(512), "512");
+        assert_eq!(size_literal(26_214_400), "26_214_400");
+    }
+}
@@ -1,11 +1,17 @@
-use crate::generator::ir::types::ResourceType;
+use crate::generator::casing::NamingConfig;
+use crate::generator::ir::types::{EmittedItem, ResourceType};
 use crate::generator::ir::{
-    ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
-    ResourceValue,
+    InterpolationPart, ResourceKey, ResourceKind, ResourceNode,
+    ResourceOrigin, ResourceValue,
+};
+use crate::generator::parsing::{
+    InterpolationPart as ParsedPart, ParsedResource, ScalarValue,
 };
-use crate::generator::parsing::{ParsedResource, ScalarValue};
-use crate::generator::utils::sanitize_identifier;
 
+/// `<string name="device_name">Sensor</string>`: a `&str` constant. With
+/// `max-len="32"` (checked by analysis) it also gets `DEVICE_NAME_LEN`, the
+/// length in bytes, and with `as-bytes="true"` a `DEVICE_NAME_BYTES` slice
+/// for code filling fixed-size buffers.
 pub struct StringType;
 
 impl ResourceType for StringType {
@@ -26,15 +32,23 @@ impl ResourceType for StringType {
         parsed: &ParsedResource,
         origin: ResourceOrigin,
     ) -> Option<ResourceNode> {
-        if let ScalarValue::Text(value) = &parsed.value {
-            Some(ResourceNode {
-                kind: ResourceKind::String,
-                value: ResourceValue::String(value.clone()),
-                origin,
-            })
-        } else {
-            None
-        }
+        let value = match &parsed.value {
+            ScalarValue::Text(value) => {
+                ResourceValue::String(value.clone())
+            }
+            ScalarValue::Interpolated(parts) => {
+                ResourceValue::Interpolated(
+                    parts.iter().map(interpolation_part).collect(),
+                )
+            }
+            _ => return None,
+        };
+        Some(ResourceNode {
+            kind: ResourceKind::String,
+            value,
+            origin,
+            attributes: Default::default(),
+        })
     }
 
     fn emit_rust(
@@ -42,15 +56,126 @@ impl ResourceType for StringType {
         key: &ResourceKey,
         node: &ResourceNode,
         indent: usize,
+    ) -> Option<String> {
+        self.emit_rust_named(key, node, indent, &NamingConfig::default())
+    }
+
+    fn emit_rust_named(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+        naming: &NamingConfig,
     ) -> Option<String> {
         if let ResourceValue::String(value) = &node.value {
             let pad = " ".repeat(indent);
-            let const_name =
-                sanitize_identifier(&key.name).to_uppercase();
+            let const_name = naming.const_identifier(&key.name);
             let escaped = value.escape_debug();
-            Some(format!("{pad}pub const {const_name}: &str = \"{escaped}\";\n"))
+            let mut code = format!(
+                "{pad}pub const {const_name}: &str = \"{escaped}\";\n"
+            );
+            if node.attributes.contains_key(MAX_LEN) {
+                let len = naming.companion_identifier(&const_name, "len");
+                code.push_str(&format!(
+                    "{pad}/// Length of [`{const_name}`] in bytes of UTF-8\n\
+                     {pad}pub const {len}: usize = {};\n",
+                    value.len()
+                ));
+            }
+            if node.has_flag(AS_BYTES) {
+                let bytes = naming.companion_identifier(&const_name, "bytes");
+                code.push_str(&format!(
+                    "{pad}/// [`{const_name}`] as UTF-8 bytes\n\
+                     {pad}pub const {bytes}: &[u8] = \
+                     {const_name}.as_bytes();\n"
+                ));
+            }
+            Some(code)
         } else {
             None
         }
     }
+
+    fn emitted_items(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        naming: &NamingConfig,
+    ) -> Vec<EmittedItem> {
+        let const_name = naming.const_identifier(&key.name);
+        let mut items = vec![EmittedItem::Const(const_name.clone())];
+        if node.attributes.contains_key(MAX_LEN) {
+            let len = naming.companion_identifier(&const_name, "len");
+            items.push(EmittedItem::Const(len));
+        }
+        if node.has_flag(AS_BYTES) {
+            let bytes = naming.companion_identifier(&const_name, "bytes");
+            items.push(EmittedItem::Const(bytes));
+        }
+        items
+    }
+}
+
+/// Attribute capping the length in bytes, which also emits `<NAME>_LEN`
+const MAX_LEN: &str = "max-len";
+/// Flag emitting `<NAME>_BYTES`
+const AS_BYTES: &str = "as-bytes";
+
+/// The kind named in a reference is filled in by the graph builder,
+/// which knows the registered types
+fn interpolation_part(part: &ParsedPart) -> InterpolationPart {
+    match part {
+        ParsedPart::Text(text) => InterpolationPart::Text(text.clone()),
+        ParsedPart::Reference { path, .. } => InterpolationPart::Reference {
+            target: ResourceKey::from_path(path),
+            kind: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn emit(value: &str, attributes: &[(&str, &str)]) -> String {
+        let mut parsed = ParsedResource::string("device_name", value);
+        for (name, value) in attributes {
+            parsed
+                .attributes
+                .insert((*name).to_string(), (*value).to_string());
+        }
+        let origin = ResourceOrigin::new(PathBuf::from("values.xml"), false);
+        let mut node = StringType.build_node(&parsed, origin).unwrap();
+        node.attributes = parsed.attributes.clone();
+        let key = ResourceKey::from_path("device_name");
+        let items: Vec<String> = StringType
+            .emitted_items(&key, &node, &NamingConfig::default())
+            .iter()
+            .map(|item| item.name().to_string())
+            .collect();
+        let code = StringType.emit_rust(&key, &node, 0).unwrap();
+        for item in items {
+            assert!(code.contains(&format!("pub const {item}:")), "{code}");
+        }
+        code
+    }
+
+    #[test]
+    fn length_is_counted_in_bytes() {
+        // 11 characters, 13 bytes
+        let code = emit("Capteur été", &[("max-len", "32")]);
+        assert!(code.contains("pub const DEVICE_NAME_LEN: usize = 13;"));
+        assert!(!code.contains("_BYTES"), "{code}");
+        assert!(!emit("Sensor", &[]).contains("_LEN"));
+    }
+
+    #[test]
+    fn bytes_are_emitted_on_request() {
+        let code = emit("Sensor", &[("as-bytes", "true")]);
+        assert!(code.contains(
+            "pub const DEVICE_NAME_BYTES: &[u8] = DEVICE_NAME.as_bytes();"
+        ));
+        assert!(!code.contains("_LEN"), "{code}");
+    }
 }
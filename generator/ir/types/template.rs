@@ -1,11 +1,28 @@
+use crate::generator::casing::NamingConfig;
 use crate::generator::ir::model::{TemplateParam, TemplateParamValue};
-use crate::generator::ir::types::ResourceType;
+use crate::generator::ir::types::{EmittedItem, ResourceType};
 use crate::generator::ir::{
     ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
     ResourceValue,
 };
 use crate::generator::parsing::{ParsedResource, ScalarValue};
-use crate::generator::utils::sanitize_identifier;
+use crate::generator::utils::{pascal_case, sanitize_identifier};
+use std::collections::HashMap;
+
+/// `style` attribute values; `builder` adds named-argument variants next to
+/// the function, `plain` opts out when `BuildPlan::template_builders` is on
+pub(crate) const STYLE: &str = "style";
+pub(crate) const STYLE_BUILDER: &str = "builder";
+const STYLE_PLAIN: &str = "plain";
+
+/// Method finishing the builder, which no parameter may be named after
+const BUILD_METHOD: &str = "build";
+
+/// Between a parameter name and its forms: `{count:plural(item|items)}`
+const PLURAL_PREFIX: &str = ":plural(";
+
+/// Opens a call of another template: `{@footer}`
+const CALL_PREFIX: &str = "{@";
 
 pub struct TemplateType;
 
@@ -22,6 +39,49 @@ impl ResourceType for TemplateType {
         ResourceKind::Template
     }
 
+    /// Parameter names must stay distinct once they are Rust identifiers,
+    /// and the builder's own method name is taken
+    fn validate(&self, parsed: &ParsedResource) -> Result<(), String> {
+        let style = parsed.attributes.get(STYLE).map(String::as_str);
+        if let Some(style) =
+            style.filter(|s| ![STYLE_BUILDER, STYLE_PLAIN].contains(s))
+        {
+            return Err(format!(
+                "style must be '{STYLE_BUILDER}' or '{STYLE_PLAIN}', \
+                 found '{style}'"
+            ));
+        }
+        let ScalarValue::Template { text, params } = &parsed.value else {
+            return Ok(());
+        };
+        if style == Some(STYLE_BUILDER)
+            && params.iter().any(|p| param_identifier(&p.name) == BUILD_METHOD)
+        {
+            return Err(format!(
+                "a parameter named '{BUILD_METHOD}' would clash with the \
+                 builder's `{BUILD_METHOD}()`"
+            ));
+        }
+        let mut seen: HashMap<String, &str> = HashMap::new();
+        for param in params {
+            if param.name.is_empty() {
+                return Err("a parameter has an empty name".to_string());
+            }
+            let ident = param_identifier(&param.name);
+            if let Some(first) = seen.insert(ident.clone(), &param.name) {
+                return Err(format!(
+                    "parameters '{first}' and '{}' both become `{ident}`",
+                    param.name
+                ));
+            }
+        }
+        validate_order(params)?;
+        for segment in plural_segments(text) {
+            validate_plural(&segment, params)?;
+        }
+        Ok(())
+    }
+
     fn build_node(
         &self,
         parsed: &ParsedResource,
@@ -31,31 +91,11 @@ impl ResourceType for TemplateType {
             // Templates with parameters from <template> tags
             ScalarValue::Template { text, params } => {
                 // Convert ast::TemplateParam (with ScalarValue) to model::TemplateParam
-                let model_params: Vec<TemplateParam> = params
-                    .iter()
-                    .map(|p| {
-                        // Convert ScalarValue to TemplateParamValue
-                        let param_value = match &p.value {
-                            crate::generator::parsing::ScalarValue::Text(_) => {
-                                crate::generator::ir::model::TemplateParamValue::String
-                            }
-                            crate::generator::parsing::ScalarValue::Number { explicit_type, .. } => {
-                                crate::generator::ir::model::TemplateParamValue::Number {
-                                    explicit_type: explicit_type.clone(),
-                                }
-                            }
-                            crate::generator::parsing::ScalarValue::Bool(_) => {
-                                crate::generator::ir::model::TemplateParamValue::Bool
-                            }
-                            crate::generator::parsing::ScalarValue::Color(_) => {
-                                crate::generator::ir::model::TemplateParamValue::Color
-                            }
-                            _ => crate::generator::ir::model::TemplateParamValue::String,
-                        };
-                        TemplateParam {
-                            name: p.name.clone(),
-                            value: param_value,
-                        }
+                let model_params: Vec<TemplateParam> = ordered(params)
+                    .into_iter()
+                    .map(|p| TemplateParam {
+                        name: p.name.clone(),
+                        value: param_value_from_scalar(&p.value),
                     })
                     .collect();
                 Some(ResourceNode {
@@ -65,6 +105,7 @@ impl ResourceType for TemplateType {
                         params: model_params,
                     },
                     origin,
+                    attributes: Default::default(),
                 })
             }
             // Templates detected from placeholders in strings
@@ -74,19 +115,16 @@ impl ResourceType for TemplateType {
                     parsed.kind,
                     crate::generator::parsing::ResourceKind::Template
                 ) || contains_template_placeholders(value);
-                
-                if is_template {
-                    Some(ResourceNode {
-                        kind: ResourceKind::Template,
-                        value: ResourceValue::Template {
-                            text: value.clone(),
-                            params: Vec::new(),
-                        },
-                        origin,
-                    })
-                } else {
-                    None
-                }
+
+                is_template.then(|| ResourceNode {
+                    kind: ResourceKind::Template,
+                    value: ResourceValue::Template {
+                        text: value.clone(),
+                        params: Vec::new(),
+                    },
+                    origin,
+                    attributes: Default::default(),
+                })
             }
             _ => None,
         }
@@ -98,167 +136,755 @@ impl ResourceType for TemplateType {
         node: &ResourceNode,
         indent: usize,
     ) -> Option<String> {
-        if let ResourceValue::Template { text, params } = &node.value {
-            let pad = " ".repeat(indent);
-            let func_name = sanitize_identifier(&key.name);
-            
-            // If we have named parameters, use them
-            if !params.is_empty() {
-                // Generate function with named parameters
-                let param_defs: Vec<String> = params
-                    .iter()
-                    .map(|p| {
-                        let rust_type = match &p.value {
-                            TemplateParamValue::String => "&str",
-                            TemplateParamValue::Number { explicit_type } => {
-                                // Use explicit_type if provided, otherwise default to i64
-                                match explicit_type.as_deref() {
-                                    Some("bigdecimal") => "r_resources::BigDecimal",
-                                    Some("i8") => "i8",
-                                    Some("i16") => "i16",
-                                    Some("i32") => "i32",
-                                    Some("i64") => "i64",
-                                    Some("u8") => "u8",
-                                    Some("u16") => "u16",
-                                    Some("u32") => "u32",
-                                    Some("u64") => "u64",
-                                    Some("f32") => "f32",
-                                    Some("f64") => "f64",
-                                    _ => "i64", // Default for numbers
-                                }
-                            }
-                            TemplateParamValue::Bool => "bool",
-                            TemplateParamValue::Color => "&str",
-                        };
-                        format!("{}: {}", sanitize_identifier(&p.name), rust_type)
-                    })
-                    .collect();
-                let params_str = param_defs.join(", ");
-                
-                // Replace {name} with {} in format string
-                let mut format_str = text.clone();
-                for param in params {
-                    format_str = format_str.replace(&format!("{{{}}}", param.name), "{}");
-                }
-                let format_escaped = format_str.escape_debug();
-                
-                // Generate parameter names for format! macro
-                // For BigDecimal and other Display types, we can use them directly in format!
-                let param_names: Vec<String> = params
-                    .iter()
-                    .map(|p| sanitize_identifier(&p.name))
-                    .collect();
-                let param_names_str = param_names.join(", ");
-                
-                Some(format!(
-                    "{pad}pub fn {func_name}({params_str}) -> String {{\n\
-                    {pad}    format!(\"{format_escaped}\", {param_names_str})\n\
-                    {pad}}}\n"
-                ))
-            } else {
-                // No parameters, check for old-style placeholders or treat as constant
-                let placeholder_count = count_placeholders(text);
-                if placeholder_count == 0 {
-                    // No placeholders, treat as regular string (use uppercase for consts)
-                    let escaped = text.escape_debug();
-                    let const_name = func_name.to_uppercase();
-                    Some(format!("{pad}pub const {const_name}: &str = \"{escaped}\";\n"))
-                } else {
-                    // Old-style placeholders %1$s, %2$d, etc.
-                    let params: Vec<String> = (1..=placeholder_count)
-                        .map(|i| format!("arg{i}: &str"))
-                        .collect();
-                    let params_str = params.join(", ");
-                    
-                    // Generate the format string replacement
-                    let mut format_str = text.clone();
-                    for i in 1..=placeholder_count {
-                        // Replace %1$s, %2$s, etc. with {}
-                        format_str = format_str.replace(&format!("%{i}$s"), "{}");
-                        format_str = format_str.replace(&format!("%{i}$d"), "{}");
-                    }
-                    let format_escaped = format_str.escape_debug();
-                    
-                    Some(format!(
-                        "{pad}pub fn {func_name}({params_str}) -> String {{\n\
-                        {pad}    format!(\"{format_escaped}\", {})\n\
-                        {pad}}}\n",
-                        (1..=placeholder_count)
-                            .map(|i| format!("arg{i}"))
-                            .collect::<Vec<_>>()
-                            .join(", ")
-                    ))
-                }
+        self.emit_rust_named(key, node, indent, &NamingConfig::default())
+    }
+
+    fn emit_rust_named(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+        naming: &NamingConfig,
+    ) -> Option<String> {
+        let ResourceValue::Template { text, params } = &node.value
+        else {
+            return None;
+        };
+        let pad = " ".repeat(indent);
+        let func_name = naming.fn_identifier(&key.name);
+
+        // If we have named parameters, use them
+        if !params.is_empty() {
+            let mut code =
+                emit_named_template(&pad, key, text, params, naming);
+            if has_builder(node) {
+                code.push_str(&emit_builder(&pad, &func_name, params, naming));
             }
+            return Some(code);
+        }
+
+        // No parameters, check for old-style placeholders or treat as constant
+        let placeholder_count = count_placeholders(text);
+        if placeholder_count == 0 {
+            // No placeholders, treat as regular string (use uppercase for consts)
+            let escaped = text.escape_debug();
+            let const_name = naming.const_identifier(&key.name);
+            return Some(format!(
+                "{pad}pub const {const_name}: &str = \"{escaped}\";\n"
+            ));
+        }
+        Some(emit_positional_template(
+            &pad,
+            &func_name,
+            text,
+            placeholder_count,
+        ))
+    }
+
+    fn emitted_items(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        naming: &NamingConfig,
+    ) -> Vec<EmittedItem> {
+        let ResourceValue::Template { text, params } = &node.value else {
+            return Vec::new();
+        };
+        let name = naming.fn_identifier(&key.name);
+        let params: Vec<String> = if params.is_empty() {
+            vec!["&str".to_string(); count_placeholders(text)]
         } else {
-            None
+            params
+                .iter()
+                .map(|p| param_rust_type(&p.value).to_string())
+                .collect()
+        };
+        if params.is_empty() {
+            return vec![EmittedItem::Const(naming.const_identifier(&key.name))];
+        }
+        let mut items = vec![EmittedItem::Function {
+            name: name.clone(),
+            params,
+        }];
+        if has_builder(node) {
+            let args_type = args_type_name(&name);
+            items.extend([
+                EmittedItem::Type(args_type.clone()),
+                EmittedItem::Function {
+                    name: naming.fn_identifier(&format!("{name}_args")),
+                    params: Vec::new(),
+                },
+                EmittedItem::Function {
+                    name: naming.fn_identifier(&format!("{name}_with")),
+                    params: vec![args_type],
+                },
+            ]);
         }
+        items
     }
 }
 
-/// Check if a string contains template placeholders (e.g., %1$s, %2$d)
-fn contains_template_placeholders(text: &str) -> bool {
-    // Pattern: % followed by number, $, and type specifier (s, d, etc.)
-    let mut chars = text.chars().peekable();
-    while let Some(ch) = chars.next() {
-        if ch == '%' {
-            // Check if followed by number
-            let mut has_number = false;
-            while let Some(&next) = chars.peek() {
-                if next.is_ascii_digit() {
-                    has_number = true;
-                    chars.next();
-                } else if next == '$' && has_number {
-                    chars.next();
-                    // Check for type specifier
-                    if let Some(&spec) = chars.peek() {
-                        if matches!(spec, 's' | 'd' | 'f' | 'x' | 'X') {
-                            return true;
-                        }
-                    }
-                    break;
-                } else {
-                    break;
-                }
+/// `order` attributes, when given, are distinct positions on every
+/// parameter; a template numbering only some would still depend on where
+/// the others are declared
+fn validate_order(
+    params: &[crate::generator::parsing::TemplateParam],
+) -> Result<(), String> {
+    if params.iter().all(|p| p.order.is_none()) {
+        return Ok(());
+    }
+    if let Some(missing) = params.iter().find(|p| p.order.is_none()) {
+        return Err(format!(
+            "parameter '{}' has no order while others do; give every \
+             parameter one or none",
+            missing.name
+        ));
+    }
+    let mut seen: HashMap<u32, &str> = HashMap::new();
+    for param in params {
+        let order = param.order.as_deref().unwrap_or_default();
+        let Ok(position) = order.trim().parse::<u32>() else {
+            return Err(format!(
+                "parameter '{}' has order '{order}', which is not a \
+                 non-negative integer",
+                param.name
+            ));
+        };
+        if let Some(first) = seen.insert(position, &param.name) {
+            return Err(format!(
+                "parameters '{first}' and '{}' both have order {position}",
+                param.name
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Parameters in signature order: by `order` when given, otherwise as
+/// declared
+fn ordered(
+    params: &[crate::generator::parsing::TemplateParam],
+) -> Vec<&crate::generator::parsing::TemplateParam> {
+    let mut ordered: Vec<_> = params.iter().collect();
+    ordered.sort_by_key(|p| {
+        p.order
+            .as_deref()
+            .and_then(|order| order.trim().parse::<u32>().ok())
+            .unwrap_or(u32::MAX)
+    });
+    ordered
+}
+
+/// Whether named-argument variants are generated: `style="builder"` on a
+/// template with `<template>` parameters
+fn has_builder(node: &ResourceNode) -> bool {
+    let ResourceValue::Template { params, .. } = &node.value else {
+        return false;
+    };
+    !params.is_empty()
+        && node.attributes.get(STYLE).map(String::as_str)
+            == Some(STYLE_BUILDER)
+}
+
+/// `WelcomeMessageArgs` for `welcome_message`
+fn args_type_name(func_name: &str) -> String {
+    format!("{}Args", pascal_case(func_name))
+}
+
+/// Generates the arguments struct of `func_name`, whose setters name each
+/// argument (`welcome_message_args().name("Bob").count(3).build()`), and
+/// `<func_name>_with(Args { .. })` taking it whole
+fn emit_builder(
+    pad: &str,
+    func_name: &str,
+    params: &[TemplateParam],
+    naming: &NamingConfig,
+) -> String {
+    let args_type = args_type_name(func_name);
+    let args_fn = naming.fn_identifier(&format!("{func_name}_args"));
+    let with_fn = naming.fn_identifier(&format!("{func_name}_with"));
+    // Borrowed `&str` arguments need a lifetime on the struct
+    let borrows = params
+        .iter()
+        .any(|p| param_rust_type(&p.value).starts_with('&'));
+    let (lifetime, generics, anonymous) = if borrows {
+        ("'a ", "<'a>", "<'_>")
+    } else {
+        ("", "", "")
+    };
+    let fields: Vec<(String, String)> = params
+        .iter()
+        .map(|p| {
+            let ty = param_rust_type(&p.value);
+            let ty = match ty.strip_prefix('&') {
+                Some(rest) => format!("&{lifetime}{rest}"),
+                None => ty.to_string(),
+            };
+            (param_identifier(&p.name), ty)
+        })
+        .collect();
+
+    let mut code = format!(
+        "{pad}/// Arguments of [`{func_name}`], set by name\n\
+         {pad}#[derive(Debug, Clone, Default)]\n\
+         {pad}pub struct {args_type}{generics} {{\n"
+    );
+    for (field, ty) in &fields {
+        code.push_str(&format!("{pad}    pub {field}: {ty},\n"));
+    }
+    code.push_str(&format!(
+        "{pad}}}\n\n{pad}impl{generics} {args_type}{generics} {{\n"
+    ));
+    for (field, ty) in &fields {
+        code.push_str(&format!(
+            "{pad}    #[must_use]\n\
+             {pad}    pub fn {field}(mut self, {field}: {ty}) -> Self {{\n\
+             {pad}        self.{field} = {field};\n\
+             {pad}        self\n\
+             {pad}    }}\n\n"
+        ));
+    }
+    let call_args: Vec<String> = fields
+        .iter()
+        .map(|(field, _)| format!("self.{field}"))
+        .collect();
+    code.push_str(&format!(
+        "{pad}    /// Formats the template with these arguments\n\
+         {pad}    #[must_use]\n\
+         {pad}    pub fn {BUILD_METHOD}(self) -> String {{\n\
+         {pad}        {func_name}({})\n\
+         {pad}    }}\n\
+         {pad}}}\n\n\
+         {pad}/// Builder for [`{func_name}`], starting from default \
+         arguments\n\
+         {pad}#[must_use]\n\
+         {pad}pub fn {args_fn}{generics}() -> {args_type}{generics} {{\n\
+         {pad}    {args_type}::default()\n\
+         {pad}}}\n\n\
+         {pad}/// [`{func_name}`] with its arguments named\n\
+         {pad}#[must_use]\n\
+         {pad}pub fn {with_fn}(args: {args_type}{anonymous}) \
+         -> String {{\n\
+         {pad}    args.{BUILD_METHOD}()\n\
+         {pad}}}\n",
+        call_args.join(", ")
+    ));
+    code
+}
+
+/// Converts a parsed parameter value into its IR parameter type
+fn param_value_from_scalar(value: &ScalarValue) -> TemplateParamValue {
+    match value {
+        ScalarValue::Text(_) => TemplateParamValue::String,
+        ScalarValue::Number { explicit_type, .. } => {
+            TemplateParamValue::Number {
+                explicit_type: explicit_type.clone(),
             }
         }
+        ScalarValue::Bool(_) => TemplateParamValue::Bool,
+        ScalarValue::Color(_) => TemplateParamValue::Color,
+        _ => TemplateParamValue::String,
     }
-    false
 }
 
-/// Count the number of unique placeholders in a template string
-fn count_placeholders(template: &str) -> usize {
-    use std::collections::HashSet;
-    let mut placeholders = HashSet::new();
-    let mut chars = template.chars().peekable();
-    
-    while let Some(ch) = chars.next() {
-        if ch == '%' {
-            let mut number = String::new();
-            // Collect digits
-            while let Some(&next) = chars.peek() {
-                if next.is_ascii_digit() {
-                    number.push(next);
-                    chars.next();
-                } else if next == '$' && !number.is_empty() {
-                    chars.next();
-                    // Check for type specifier
-                    if let Some(&spec) = chars.peek() {
-                        if matches!(spec, 's' | 'd' | 'f' | 'x' | 'X') {
-                            if let Ok(num) = number.parse::<usize>() {
-                                placeholders.insert(num);
-                            }
-                        }
-                    }
-                    break;
-                } else {
-                    break;
-                }
+/// Returns the Rust type used for a template parameter in the function signature
+pub fn param_rust_type(value: &TemplateParamValue) -> &'static str {
+    match value {
+        TemplateParamValue::String | TemplateParamValue::Color => "&str",
+        TemplateParamValue::Bool => "bool",
+        // Use explicit_type if provided, otherwise default to i64
+        TemplateParamValue::Number { explicit_type } => {
+            match explicit_type.as_deref() {
+                Some("bigdecimal") => "r_resources::BigDecimal",
+                Some("i8") => "i8",
+                Some("i16") => "i16",
+                Some("i32") => "i32",
+                Some("u8") => "u8",
+                Some("u16") => "u16",
+                Some("u32") => "u32",
+                Some("u64") => "u64",
+                Some("f32") => "f32",
+                Some("f64") => "f64",
+                _ => "i64", // Default for numbers
+            }
+        }
+    }
+}
+
+/// Generates a function with named parameters (`<template>` element form)
+fn emit_named_template(
+    pad: &str,
+    key: &ResourceKey,
+    text: &str,
+    params: &[TemplateParam],
+    naming: &NamingConfig,
+) -> String {
+    let func_name = naming.fn_identifier(&key.name);
+    let param_defs: Vec<String> = params
+        .iter()
+        .map(|p| {
+            format!(
+                "{}: {}",
+                param_identifier(&p.name),
+                param_rust_type(&p.value)
+            )
+        })
+        .collect();
+    let params_str = param_defs.join(", ");
+
+    // Plural segments become arguments choosing their form; they are
+    // replaced first, as `{count}` is a prefix of none of them
+    let mut format_str = text.to_string();
+    let mut args: Vec<String> = Vec::new();
+    // Resolved calls of templates with parameters; the others were
+    // inlined, and unresolved ones are reported by analysis
+    for call in template_calls(text) {
+        let Some(call_args) = &call.args else {
+            continue;
+        };
+        let index = format!("{{{}}}", args.len());
+        let count =
+            replace_segment(&mut format_str, &call.source, &index, usize::MAX);
+        if count > 0 {
+            args.push(call_expression(key, &call.path, call_args, naming));
+        }
+    }
+    for segment in plural_segments(text) {
+        let Some(param) = params.iter().find(|p| p.name == segment.param)
+        else {
+            continue;
+        };
+        let index = format!("{{{}}}", args.len());
+        let count =
+            replace_segment(&mut format_str, &segment.source, &index, 1);
+        if count > 0 {
+            args.push(english_plural(param, &segment.forms));
+        }
+    }
+
+    // Replace {name} with its argument's index in the format string, so
+    // placeholders may come in any order or repeat. Parameters only read
+    // by plural segments are not arguments, which format! would reject.
+    // BigDecimal and other Display types are passed as they are.
+    for param in params {
+        let placeholder = format!("{{{}}}", param.name);
+        let index = format!("{{{}}}", args.len());
+        let count =
+            replace_segment(&mut format_str, &placeholder, &index, usize::MAX);
+        if count > 0 {
+            args.push(param_identifier(&param.name));
+        }
+    }
+    let format_escaped = format_str.escape_debug();
+    let param_names_str = args.join(", ");
+
+    format!(
+        "{pad}pub fn {func_name}({params_str}) -> String {{\n\
+        {pad}    format!(\"{format_escaped}\", {param_names_str})\n\
+        {pad}}}\n"
+    )
+}
+
+/// Replaces `segment`, braces included, by `with` in the format string
+/// `text`, at most `limit` times, and returns how many were replaced.
+/// Escaped braces are skipped, so `{{name}}` stays the text `{name}`.
+fn replace_segment(
+    text: &mut String,
+    segment: &str,
+    with: &str,
+    limit: usize,
+) -> usize {
+    let mut replaced = String::with_capacity(text.len());
+    let mut count = 0;
+    let mut rest = text.as_str();
+    while let Some(ch) = rest.chars().next() {
+        let len = if rest.starts_with("{{") || rest.starts_with("}}") {
+            replaced.push_str(&rest[..2]);
+            2
+        } else if count < limit && rest.starts_with(segment) {
+            replaced.push_str(with);
+            count += 1;
+            segment.len()
+        } else {
+            replaced.push(ch);
+            ch.len_utf8()
+        };
+        rest = &rest[len..];
+    }
+    *text = replaced;
+    count
+}
+
+/// Another template called from a template body: `{@footer}` as written,
+/// or `{@mail/footer(name,company)}` once analysis resolved it, naming the
+/// caller's parameters passed on to the callee
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TemplateCall {
+    /// The call as written, braces included
+    pub source: String,
+    /// Path of the callee, absolute or relative (`./footer`, `../footer`)
+    pub path: String,
+    /// Caller parameters passed to the callee, once resolved
+    pub args: Option<Vec<String>>,
+}
+
+/// Every call of another template in `text`, in order and with repeats
+pub(crate) fn template_calls(text: &str) -> Vec<TemplateCall> {
+    let mut calls = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(CALL_PREFIX) {
+        rest = &rest[start..];
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        match parse_call(&rest[CALL_PREFIX.len()..end]) {
+            Some((path, args)) => {
+                calls.push(TemplateCall {
+                    source: rest[..=end].to_string(),
+                    path,
+                    args,
+                });
+                rest = &rest[end + 1..];
             }
+            None => rest = &rest[CALL_PREFIX.len()..],
+        }
+    }
+    calls
+}
+
+/// Reads `mail/footer` or `mail/footer(name,company)`, the inside of a call
+fn parse_call(inner: &str) -> Option<(String, Option<Vec<String>>)> {
+    let (path, args) = match inner.split_once('(') {
+        Some((path, args)) => {
+            let args = args.strip_suffix(')')?;
+            let args: Vec<String> = args
+                .split(',')
+                .map(|arg| arg.trim().to_string())
+                .filter(|arg| !arg.is_empty())
+                .collect();
+            (path, Some(args))
+        }
+        None => (inner, None),
+    };
+    let is_path = !path.is_empty()
+        && !path.ends_with('/')
+        && path
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '/' | '.'));
+    is_path.then(|| (path.to_string(), args))
+}
+
+/// `footer(name)` when the callee is in the caller's module, otherwise its
+/// path from there (`super::mail::footer(name)`)
+fn call_expression(
+    caller: &ResourceKey,
+    path: &str,
+    args: &[String],
+    naming: &NamingConfig,
+) -> String {
+    let callee = ResourceKey::from_path(path);
+    let mut expression = String::new();
+    if callee.namespace != caller.namespace {
+        expression.push_str(&"super::".repeat(caller.namespace.len()));
+        for namespace in &callee.namespace {
+            expression.push_str(&naming.module_identifier(namespace));
+            expression.push_str("::");
+        }
+    }
+    let args: Vec<String> =
+        args.iter().map(|arg| param_identifier(arg)).collect();
+    format!(
+        "{expression}{}({})",
+        naming.fn_identifier(&callee.name),
+        args.join(", ")
+    )
+}
+
+/// Forms chosen by a numeric parameter, written
+/// `{count:plural(message|messages)}` in the template text
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PluralSegment {
+    /// The segment as written, braces included
+    source: String,
+    param: String,
+    forms: Vec<String>,
+}
+
+/// Every plural segment of `text`, in order
+fn plural_segments(text: &str) -> Vec<PluralSegment> {
+    let mut segments = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        if let Some(segment) = parse_plural(&rest[..end]) {
+            segments.push(segment);
+            rest = &rest[end + 1..];
+        }
+    }
+    segments
+}
+
+/// Reads `count:plural(item|items)`, the inside of a plural segment
+fn parse_plural(inner: &str) -> Option<PluralSegment> {
+    let (param, forms) = inner.split_once(PLURAL_PREFIX)?;
+    let forms = forms.strip_suffix(')')?;
+    let is_name = !param.is_empty()
+        && param
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-'));
+    if !is_name {
+        return None;
+    }
+    let forms = if forms.is_empty() {
+        Vec::new()
+    } else {
+        forms.split('|').map(String::from).collect()
+    };
+    Some(PluralSegment {
+        source: format!("{{{inner}}}"),
+        param: param.to_string(),
+        forms,
+    })
+}
+
+/// A plural segment must name a numeric parameter and list one form (used
+/// for every count) or two (`one|other`)
+fn validate_plural(
+    segment: &PluralSegment,
+    params: &[crate::generator::parsing::TemplateParam],
+) -> Result<(), String> {
+    let source = &segment.source;
+    let Some(param) = params.iter().find(|p| p.name == segment.param) else {
+        return Err(format!(
+            "'{source}' refers to '{}', which is not a parameter",
+            segment.param
+        ));
+    };
+    let numeric = match &param.value {
+        ScalarValue::Number { explicit_type, .. } => {
+            explicit_type.as_deref() != Some("bigdecimal")
+        }
+        _ => false,
+    };
+    if !numeric {
+        return Err(format!(
+            "'{source}' needs '{}' to be an integer or float <number>",
+            segment.param
+        ));
+    }
+    match segment.forms.len() {
+        0 => Err(format!("'{source}' has no forms")),
+        1 | 2 => Ok(()),
+        _ => Err(format!(
+            "'{source}' has {} forms, but plurals take one or two \
+             (`one|other`)",
+            segment.forms.len()
+        )),
+    }
+}
+
+/// Expression picking a form with the English rules: the first form for
+/// exactly one, the last for every other count (`0 messages`). Per-locale
+/// CLDR rules would choose among more forms here. A single form still
+/// reads the parameter, which may be used nowhere else in the template.
+fn english_plural(param: &TemplateParam, forms: &[String]) -> String {
+    let (one, other) = match forms {
+        [one, .., other] => (one, other),
+        [form] => {
+            return format!(
+                "{{ let _ = {}; \"{}\" }}",
+                param_identifier(&param.name),
+                form.escape_debug()
+            )
+        }
+        [] => return "\"\"".to_string(),
+    };
+    let unit = match param_rust_type(&param.value) {
+        "f32" | "f64" => "1.0",
+        _ => "1",
+    };
+    format!(
+        "if {} == {unit} {{ \"{}\" }} else {{ \"{}\" }}",
+        param_identifier(&param.name),
+        one.escape_debug(),
+        other.escape_debug()
+    )
+}
+
+/// Rust keywords, which cannot name a parameter as they are
+pub(crate) const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const",
+    "continue", "crate", "do", "dyn", "else", "enum", "extern", "false",
+    "final", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "macro",
+    "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true",
+    "try", "type", "typeof", "unsafe", "unsized", "use", "virtual", "where",
+    "while", "yield",
+];
+
+/// Function parameter for a template parameter: `user-id` becomes
+/// `user_id`, a keyword becomes a raw identifier (`r#type`), and the few
+/// keywords that cannot be raw (`self`, `crate`, ...) get a trailing `_`.
+/// Placeholders keep matching the name as written (`{type}`).
+pub(crate) fn param_identifier(name: &str) -> String {
+    let ident = sanitize_identifier(name);
+    if ident.starts_with(|c: char| c.is_ascii_digit()) || ident == "_" {
+        return format!("_{ident}");
+    }
+    if !KEYWORDS.contains(&ident.as_str()) {
+        return ident;
+    }
+    if matches!(ident.as_str(), "crate" | "self" | "Self" | "super") {
+        format!("{ident}_")
+    } else {
+        format!("r#{ident}")
+    }
+}
+
+/// Generates a function for old-style positional placeholders (%1$s, %2$d, etc.)
+fn emit_positional_template(
+    pad: &str,
+    func_name: &str,
+    text: &str,
+    placeholder_count: usize,
+) -> String {
+    let params: Vec<String> = (1..=placeholder_count)
+        .map(|i| format!("arg{i}: &str"))
+        .collect();
+    let params_str = params.join(", ");
+
+    // Generate the format string replacement
+    let mut format_str = text.to_string();
+    for i in 1..=placeholder_count {
+        // Replace %1$s, %2$s, etc. with {}
+        format_str = format_str.replace(&format!("%{i}$s"), "{}");
+        format_str = format_str.replace(&format!("%{i}$d"), "{}");
+    }
+    let format_escaped = format_str.escape_debug();
+    let args = (1..=placeholder_count)
+        .map(|i| format!("arg{i}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!(
+        "{pad}pub fn {func_name}({params_str}) -> String {{\n\
+        {pad}    format!(\"{format_escaped}\", {args})\n\
+        {pad}}}\n"
+    )
+}
+
+/// A placeholder in resource text, as translations must keep it
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Placeholder {
+    /// `%1$s`: the argument index and its type specifier
+    Positional(usize, char),
+    /// `{name}`, filled by the `<template>` parameter of that name
+    Named(String),
+}
+
+impl std::fmt::Display for Placeholder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Positional(index, spec) => write!(f, "%{index}${spec}"),
+            Self::Named(name) => write!(f, "{{{name}}}"),
         }
     }
-    
-    placeholders.len()
+}
+
+/// Every placeholder in `text`, in order and with repeats: positional ones
+/// (`%1$s`, `%2$d`) and named ones (`{name}`). Plain strings are scanned
+/// the same way as templates, so their translations can be compared.
+pub(crate) fn placeholders(text: &str) -> Vec<Placeholder> {
+    let mut found = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        let placeholder = match ch {
+            '%' => read_positional(&mut chars),
+            '{' => read_named(&mut chars),
+            _ => None,
+        };
+        found.extend(placeholder);
+    }
+    found
+}
+
+/// Reads a positional placeholder (`1$s`) right after a `%` sign.
+///
+/// Returns the placeholder when the digits are followed by `$` and a
+/// supported type specifier.
+fn read_positional(
+    chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+) -> Option<Placeholder> {
+    let mut number = String::new();
+    while let Some(&next) = chars.peek() {
+        if !next.is_ascii_digit() {
+            break;
+        }
+        number.push(next);
+        chars.next();
+    }
+    if number.is_empty() || chars.peek() != Some(&'$') {
+        return None;
+    }
+    chars.next();
+    let spec = *chars.peek()?;
+    if !matches!(spec, 's' | 'd' | 'f' | 'x' | 'X') {
+        return None;
+    }
+    let index = number.parse::<usize>().ok()?;
+    chars.next();
+    Some(Placeholder::Positional(index, spec))
+}
+
+/// Reads a named placeholder (`name}`) right after a `{`; text that is not
+/// a parameter name (`{ }`, `{{`) is left alone
+fn read_named(
+    chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+) -> Option<Placeholder> {
+    let mut name = String::new();
+    while let Some(&next) = chars.peek() {
+        if !(next.is_alphanumeric() || matches!(next, '_' | '-')) {
+            break;
+        }
+        name.push(next);
+        chars.next();
+    }
+    // `{count:plural(...)}` stands for its parameter, whatever the forms
+    if !name.is_empty() && chars.peek() == Some(&':') {
+        let rest: String = chars.clone().take_while(|&c| c != '}').collect();
+        let len = rest.chars().count();
+        let closed = chars.clone().nth(len) == Some('}');
+        if closed && parse_plural(&format!("{name}{rest}")).is_some() {
+            chars.nth(len);
+            return Some(Placeholder::Named(name));
+        }
+    }
+    if name.is_empty() || chars.peek() != Some(&'}') {
+        return None;
+    }
+    chars.next();
+    Some(Placeholder::Named(name))
+}
+
+/// Check if a string contains template placeholders (e.g., %1$s, %2$d)
+fn contains_template_placeholders(text: &str) -> bool {
+    count_placeholders(text) > 0
+}
+
+/// Count the number of unique positional placeholders in a template string
+pub(crate) fn count_placeholders(template: &str) -> usize {
+    use std::collections::HashSet;
+    placeholders(template)
+        .into_iter()
+        .filter_map(|placeholder| match placeholder {
+            Placeholder::Positional(index, _) => Some(index),
+            Placeholder::Named(_) => None,
+        })
+        .collect::<HashSet<_>>()
+        .len()
 }
 
 #[cfg(test)]
@@ -310,6 +936,22 @@ mod tests {
         assert_eq!(count_placeholders("A: %1$s, B: %2$s, C: %3$d"), 3);
         assert_eq!(count_placeholders("Hello World"), 0);
         assert_eq!(count_placeholders("%1$s %1$s"), 1); // Same placeholder twice
+        assert_eq!(count_placeholders("Hi {name}"), 0);
+    }
+
+    #[test]
+    fn test_placeholders() {
+        let found: Vec<String> = placeholders("%2$d of %1$s, {user-id} {x}!")
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(found, ["%2$d", "%1$s", "{user-id}", "{x}"]);
+        let found = placeholders("{n:plural(a|b)} {n:other} {m:plural(a)");
+        assert_eq!(found, [Placeholder::Named("n".to_string())]);
+        assert_eq!(placeholders("%1$s %1$s").len(), 2);
+        for text in ["100%", "%1s", "%$s", "{ }", "{}", "{a b}", "{{"] {
+            assert!(placeholders(text).is_empty(), "{text}");
+        }
     }
 
     // Test build_node with template (detected from placeholders)
@@ -320,6 +962,7 @@ mod tests {
             name: "welcome_message".to_string(),
             kind: AstResourceKind::String, // Templates can be detected in strings
             value: ScalarValue::Text("Hello %1$s!".to_string()),
+            attributes: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -342,6 +985,7 @@ mod tests {
             name: "welcome_message".to_string(),
             kind: AstResourceKind::Template, // Explicit template tag
             value: ScalarValue::Text("Hello %1$s!".to_string()),
+            attributes: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -363,6 +1007,7 @@ mod tests {
             name: "simple_string".to_string(),
             kind: AstResourceKind::String,
             value: ScalarValue::Text("Hello World".to_string()),
+            attributes: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -378,6 +1023,7 @@ mod tests {
             name: "not_template".to_string(),
             kind: AstResourceKind::Bool,
             value: ScalarValue::Bool(true),
+            attributes: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -400,6 +1046,7 @@ mod tests {
                 params: vec![],
             },
             origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
         };
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
@@ -408,6 +1055,36 @@ mod tests {
         assert!(result.contains("format!"));
     }
 
+    // Test emitted_items matches what emit_rust defines
+    #[test]
+    fn test_emitted_items() {
+        let handler = TemplateType;
+        let key = ResourceKey {
+            namespace: vec![],
+            name: "welcome".to_string(),
+        };
+        let node = |text: &str| ResourceNode {
+            kind: ModelResourceKind::Template,
+            value: ResourceValue::Template {
+                text: text.to_string(),
+                params: vec![],
+            },
+            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
+        };
+        assert_eq!(
+            handler.emitted_items(&key, &node("Hello %1$s and %2$s!"), &NamingConfig::default()),
+            [EmittedItem::Function {
+                name: "welcome".to_string(),
+                params: vec!["&str".to_string(); 2],
+            }]
+        );
+        assert_eq!(
+            handler.emitted_items(&key, &node("Hello!"), &NamingConfig::default()),
+            [EmittedItem::Const("WELCOME".to_string())]
+        );
+    }
+
     // Test emit_rust for template with multiple placeholders
     #[test]
     fn test_emit_rust_multiple_placeholders() {
@@ -423,6 +1100,7 @@ mod tests {
                 params: vec![],
             },
             origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
         };
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
@@ -447,6 +1125,7 @@ mod tests {
                 params: vec![],
             },
             origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
         };
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
@@ -466,6 +1145,7 @@ mod tests {
             kind: ModelResourceKind::Template,
             value: ResourceValue::String("Hello".to_string()),
             origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
         };
 
         let result = handler.emit_rust(&key, &node, 4);
@@ -487,6 +1167,7 @@ mod tests {
                 params: vec![],
             },
             origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
         };
 
         let result = handler.emit_rust(&key, &node, 8).unwrap();
@@ -508,10 +1189,327 @@ mod tests {
                 params: vec![],
             },
             origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
         };
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
         assert!(result.contains("pub fn welcome"));
     }
+
+    fn template(text: &str, names: &[&str]) -> ParsedResource {
+        use crate::generator::parsing::TemplateParam as AstTemplateParam;
+        ParsedResource {
+            name: "welcome".to_string(),
+            kind: AstResourceKind::Template,
+            value: ScalarValue::Template {
+                text: text.to_string(),
+                params: names
+                    .iter()
+                    .map(|name| AstTemplateParam {
+                        name: name.to_string(),
+                        value: ScalarValue::Text(String::new()),
+                        order: None,
+                    })
+                    .collect(),
+            },
+            attributes: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_param_identifier() {
+        assert_eq!(param_identifier("user-id"), "user_id");
+        assert_eq!(param_identifier("type"), "r#type");
+        assert_eq!(param_identifier("self"), "self_");
+        assert_eq!(param_identifier("1st"), "_1st");
+        assert_eq!(param_identifier("_"), "__");
+        assert_eq!(param_identifier("types"), "types");
+    }
+
+    #[test]
+    fn test_emit_keyword_params() {
+        let parsed = template("A {type} for {user-id}", &["type", "user-id"]);
+        TemplateType.validate(&parsed).unwrap();
+        let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
+        let node = TemplateType.build_node(&parsed, origin).unwrap();
+        let key = ResourceKey::from_path("welcome");
+        let code = TemplateType.emit_rust(&key, &node, 0).unwrap();
+        assert_eq!(
+            code,
+            "pub fn welcome(r#type: &str, user_id: &str) -> String {\n    \
+             format!(\"A {0} for {1}\", r#type, user_id)\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_escaped_braces_are_not_placeholders() {
+        let mut text = "{{name}} is {name}, {name}}}".to_string();
+        assert_eq!(replace_segment(&mut text, "{name}", "{0}", usize::MAX), 2);
+        assert_eq!(text, "{{name}} is {0}, {0}}}");
+        assert_eq!(replace_segment(&mut text, "{0}", "{1}", 1), 1);
+        assert_eq!(text, "{{name}} is {1}, {0}}}");
+    }
+
+    #[test]
+    fn test_validate_rejects_colliding_params() {
+        let parsed = template("{user-id} {user_id}", &["user-id", "user_id"]);
+        assert_eq!(
+            TemplateType.validate(&parsed).unwrap_err(),
+            "parameters 'user-id' and 'user_id' both become `user_id`"
+        );
+        let parsed = template("{self} {self_}", &["self", "self_"]);
+        assert!(TemplateType.validate(&parsed).is_err());
+        assert!(TemplateType.validate(&template("", &[""])).is_err());
+    }
+
+    #[test]
+    fn test_order_attribute_sets_the_signature() {
+        let with_orders = |orders: &[Option<&str>]| {
+            let mut parsed = template("{name} {count}", &["name", "count"]);
+            let ScalarValue::Template { params, .. } = &mut parsed.value
+            else {
+                unreachable!()
+            };
+            params
+                .iter_mut()
+                .zip(orders)
+                .for_each(|(param, order)| param.order = order.map(String::from));
+            parsed
+        };
+        let parsed = with_orders(&[Some("2"), Some("1")]);
+        assert!(TemplateType.validate(&parsed).is_ok());
+        let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
+        let node = TemplateType.build_node(&parsed, origin).unwrap();
+        let code = TemplateType
+            .emit_rust(&ResourceKey::from_path("welcome"), &node, 0)
+            .unwrap();
+        assert!(code.starts_with("pub fn welcome(count: &str, name: &str)"));
+
+        assert_eq!(
+            TemplateType
+                .validate(&with_orders(&[Some("1"), None]))
+                .unwrap_err(),
+            "parameter 'count' has no order while others do; give every \
+             parameter one or none"
+        );
+        assert_eq!(
+            TemplateType
+                .validate(&with_orders(&[Some("1"), Some("1")]))
+                .unwrap_err(),
+            "parameters 'name' and 'count' both have order 1"
+        );
+        assert!(TemplateType
+            .validate(&with_orders(&[Some("first"), Some("2")]))
+            .unwrap_err()
+            .contains("not a non-negative integer"));
+    }
+
+    #[test]
+    fn test_validate_plural_segments() {
+        use crate::generator::parsing::TemplateParam as AstTemplateParam;
+        let number = |explicit_type: Option<&str>| AstTemplateParam {
+            name: "count".to_string(),
+            value: ScalarValue::Number {
+                value: "0".to_string(),
+                explicit_type: explicit_type.map(String::from),
+            },
+            order: None,
+        };
+        let plural = |text: &str, param: AstTemplateParam| {
+            let mut parsed = template(text, &[]);
+            if let ScalarValue::Template { params, .. } = &mut parsed.value {
+                params.push(param);
+            }
+            TemplateType.validate(&parsed)
+        };
+        let text = "{count} {count:plural(message|messages)}";
+        assert!(plural(text, number(None)).is_ok());
+        assert!(plural(text, number(Some("f32"))).is_ok());
+        assert!(plural("{count:plural(s)}", number(None)).is_ok());
+        assert_eq!(
+            plural("{count:plural()}", number(None)).unwrap_err(),
+            "'{count:plural()}' has no forms"
+        );
+        assert!(plural("{count:plural(a|b|c)}", number(None))
+            .unwrap_err()
+            .contains("has 3 forms"));
+        assert_eq!(
+            plural("{n:plural(a|b)}", number(None)).unwrap_err(),
+            "'{n:plural(a|b)}' refers to 'n', which is not a parameter"
+        );
+        assert!(plural(text, number(Some("bigdecimal"))).is_err());
+        let name = template("{count:plural(a|b)}", &["count"]);
+        assert_eq!(
+            TemplateType.validate(&name).unwrap_err(),
+            "'{count:plural(a|b)}' needs 'count' to be an integer or float \
+             <number>"
+        );
+    }
+
+    #[test]
+    fn test_emit_plural_segments() {
+        let param = |name: &str, explicit_type: Option<&str>| TemplateParam {
+            name: name.to_string(),
+            value: TemplateParamValue::Number {
+                explicit_type: explicit_type.map(String::from),
+            },
+        };
+        let node = ResourceNode {
+            kind: ModelResourceKind::Template,
+            value: ResourceValue::Template {
+                text: "{count} {count:plural(file|files)}, \
+                       {size:plural(MB)} {ratio:plural(pt|pts)}"
+                    .to_string(),
+                params: vec![
+                    param("count", None),
+                    param("size", Some("u32")),
+                    param("ratio", Some("f64")),
+                ],
+            },
+            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: Default::default(),
+        };
+        let key = ResourceKey {
+            namespace: vec![],
+            name: "summary".to_string(),
+        };
+        let code = TemplateType.emit_rust(&key, &node, 0).unwrap();
+        assert_eq!(
+            code,
+            "pub fn summary(count: i64, size: u32, ratio: f64) -> String {\
+             \n    format!(\"{3} {0}, {1} {2}\", \
+             if count == 1 { \"file\" } else { \"files\" }, \
+             { let _ = size; \"MB\" }, \
+             if ratio == 1.0 { \"pt\" } else { \"pts\" }, count)\n}\n"
+        );
+    }
+
+    fn builder_node(style: Option<&str>) -> ResourceNode {
+        let param = |name: &str, value| TemplateParam {
+            name: name.to_string(),
+            value,
+        };
+        ResourceNode {
+            kind: ModelResourceKind::Template,
+            value: ResourceValue::Template {
+                text: "Welcome to {name}, you have {count} messages!"
+                    .to_string(),
+                params: vec![
+                    param("name", TemplateParamValue::String),
+                    param(
+                        "count",
+                        TemplateParamValue::Number {
+                            explicit_type: None,
+                        },
+                    ),
+                ],
+            },
+            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            attributes: style
+                .map(|style| (STYLE.to_string(), style.to_string()))
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_emit_builder_style() {
+        let key = ResourceKey::from_path("welcome_message");
+        let node = builder_node(Some(STYLE_BUILDER));
+        let code = TemplateType.emit_rust(&key, &node, 0).unwrap();
+        for expected in [
+            "pub fn welcome_message(name: &str, count: i64) -> String {",
+            "pub struct WelcomeMessageArgs<'a> {\n    pub name: &'a str,\n    \
+             pub count: i64,\n}",
+            "impl<'a> WelcomeMessageArgs<'a> {",
+            "pub fn name(mut self, name: &'a str) -> Self {",
+            "pub fn count(mut self, count: i64) -> Self {",
+            "pub fn build(self) -> String {\n        \
+             welcome_message(self.name, self.count)\n    }",
+            "pub fn welcome_message_args<'a>() -> WelcomeMessageArgs<'a> {",
+            "pub fn welcome_message_with(args: WelcomeMessageArgs<'_>) \
+             -> String {",
+        ] {
+            assert!(code.contains(expected), "{expected}\n{code}");
+        }
+        assert_eq!(
+            TemplateType.emitted_items(&key, &node, &NamingConfig::default()),
+            [
+                EmittedItem::Function {
+                    name: "welcome_message".to_string(),
+                    params: vec!["&str".to_string(), "i64".to_string()],
+                },
+                EmittedItem::Type("WelcomeMessageArgs".to_string()),
+                EmittedItem::Function {
+                    name: "welcome_message_args".to_string(),
+                    params: Vec::new(),
+                },
+                EmittedItem::Function {
+                    name: "welcome_message_with".to_string(),
+                    params: vec!["WelcomeMessageArgs".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plain_style_emits_only_the_function() {
+        let key = ResourceKey::from_path("welcome_message");
+        for style in [None, Some(STYLE_PLAIN)] {
+            let node = builder_node(style);
+            let code = TemplateType.emit_rust(&key, &node, 0).unwrap();
+            assert!(!code.contains("Args"), "{code}");
+            assert_eq!(TemplateType.emitted_items(&key, &node, &NamingConfig::default()).len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_validate_style() {
+        let mut parsed = template("{name}", &["name"]);
+        parsed
+            .attributes
+            .insert(STYLE.to_string(), "fluent".to_string());
+        assert_eq!(
+            TemplateType.validate(&parsed).unwrap_err(),
+            "style must be 'builder' or 'plain', found 'fluent'"
+        );
+        let mut parsed = template("{build}", &["build"]);
+        TemplateType.validate(&parsed).unwrap();
+        parsed
+            .attributes
+            .insert(STYLE.to_string(), STYLE_BUILDER.to_string());
+        assert!(TemplateType.validate(&parsed).is_err());
+    }
+
+    #[test]
+    fn finds_calls_as_written_and_resolved() {
+        let calls = template_calls("{@footer} {a} {@mail/footer(name,company)} {@}");
+        assert_eq!(
+            calls,
+            [
+                TemplateCall {
+                    source: "{@footer}".to_string(),
+                    path: "footer".to_string(),
+                    args: None,
+                },
+                TemplateCall {
+                    source: "{@mail/footer(name,company)}".to_string(),
+                    path: "mail/footer".to_string(),
+                    args: Some(vec!["name".to_string(), "company".to_string()]),
+                },
+            ]
+        );
+        let caller = ResourceKey::from_path("auth/welcome");
+        let args = ["type".to_string()];
+        let naming = NamingConfig::default();
+        assert_eq!(
+            call_expression(&caller, "mail/footer", &args, &naming),
+            "super::mail::footer(r#type)"
+        );
+        assert_eq!(
+            call_expression(&caller, "auth/footer", &[], &naming),
+            "footer()"
+        );
+    }
 }
 
@@ -0,0 +1,165 @@
+//! `MAJOR.MINOR.PATCH` versions (`1.2.0`).
+
+use crate::generator::casing::NamingConfig;
+use crate::generator::ir::types::{EmittedItem, ResourceType};
+use crate::generator::ir::{
+    ResourceKey, ResourceKind, ResourceNode, ResourceOrigin, ResourceValue,
+};
+use crate::generator::parsing::{ParsedResource, ScalarValue};
+
+/// `<version name="min_supported">1.2.0</version>`: an
+/// `r_resources::Version`, comparable in const code.
+pub struct VersionType;
+
+impl ResourceType for VersionType {
+    fn name(&self) -> &'static str {
+        "version"
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        &["version"]
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::Version
+    }
+
+    fn validate(&self, parsed: &ParsedResource) -> Result<(), String> {
+        let ScalarValue::Version(text) = &parsed.value else {
+            return Ok(());
+        };
+        parse_version(text).map(|_| ())
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let ScalarValue::Version(text) = &parsed.value else {
+            return None;
+        };
+        let (major, minor, patch) = parse_version(text).ok()?;
+        Some(ResourceNode {
+            kind: ResourceKind::Version,
+            value: ResourceValue::Version {
+                major,
+                minor,
+                patch,
+            },
+            origin,
+            attributes: Default::default(),
+        })
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        self.emit_rust_named(key, node, indent, &NamingConfig::default())
+    }
+
+    fn emit_rust_named(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+        naming: &NamingConfig,
+    ) -> Option<String> {
+        let ResourceValue::Version {
+            major,
+            minor,
+            patch,
+        } = &node.value
+        else {
+            return None;
+        };
+        let pad = " ".repeat(indent);
+        let const_name = naming.const_identifier(&key.name);
+        Some(format!(
+            "{pad}/// {major}.{minor}.{patch}\n\
+             {pad}pub const {const_name}: r_resources::Version = \
+             r_resources::Version::new({major}, {minor}, {patch});\n"
+        ))
+    }
+
+    fn emitted_items(
+        &self,
+        key: &ResourceKey,
+        _node: &ResourceNode,
+        naming: &NamingConfig,
+    ) -> Vec<EmittedItem> {
+        vec![EmittedItem::Const(
+            naming.const_identifier(&key.name),
+        )]
+    }
+}
+
+/// `MAJOR.MINOR.PATCH` as in SemVer: three numbers without leading zeros.
+/// Pre-release and build suffixes (`-beta.1`, `+sha`) are rejected, since
+/// `Version` has nowhere to keep them.
+fn parse_version(text: &str) -> Result<(u64, u64, u64), String> {
+    let invalid =
+        || format!("'{text}' is not a version (expected MAJOR.MINOR.PATCH)");
+    if text.contains(['-', '+']) {
+        return Err(format!(
+            "'{text}' has a pre-release or build suffix, which <version> \
+             does not support"
+        ));
+    }
+    let parts: Vec<&str> = text.split('.').collect();
+    let [major, minor, patch] = parts.as_slice() else {
+        return Err(invalid());
+    };
+    let number = |part: &str| {
+        let digits = !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+        if !digits || part.len() > 1 && part.starts_with('0') {
+            return Err(invalid());
+        }
+        part.parse::<u64>()
+            .map_err(|_| format!("'{text}' has a component too large for u64"))
+    };
+    Ok((number(major)?, number(minor)?, number(patch)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::parsing::ResourceKind as AstResourceKind;
+    use std::path::PathBuf;
+
+    fn parsed(value: &str) -> ParsedResource {
+        ParsedResource {
+            name: "min_supported".to_string(),
+            kind: AstResourceKind::Version,
+            value: ScalarValue::Version(value.to_string()),
+            attributes: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_emit_version() {
+        let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
+        let node = VersionType.build_node(&parsed("1.2.0"), origin).unwrap();
+        let key = ResourceKey::from_path("min_supported");
+        let code = VersionType.emit_rust(&key, &node, 0).unwrap();
+        assert_eq!(
+            code,
+            "/// 1.2.0\npub const MIN_SUPPORTED: r_resources::Version = \
+             r_resources::Version::new(1, 2, 0);\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("1.2.0"), Ok((1, 2, 0)));
+        assert_eq!(parse_version("0.10.300"), Ok((0, 10, 300)));
+        for text in ["1.2", "1.2.3.4", "1.02.0", "1..0", "v1.2.0", "1.2.x"] {
+            assert!(parse_version(text).is_err(), "{text}");
+        }
+        let err = VersionType.validate(&parsed("1.0.0-beta.1")).unwrap_err();
+        assert!(err.contains("pre-release or build suffix"), "{err}");
+    }
+}
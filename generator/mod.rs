@@ -13,6 +13,7 @@
 //! maintainable and extensible.
 
 pub mod analysis;
+pub mod casing;
 pub mod generation;
 pub mod input;
 pub mod ir;
@@ -38,7 +39,7 @@ impl std::fmt::Display for BuildError {
             Self::Pipeline(err) => write!(f, "{err}"),
             Self::Analysis(errors) | Self::Generation(errors) => {
                 for err in errors {
-                    writeln!(f, "{err:?}")?;
+                    writeln!(f, "{err}")?;
                 }
                 Ok(())
             }
@@ -62,13 +63,146 @@ pub fn build_with_plan_and_options(
     plan: &BuildPlan,
     validation_options: analysis::ValidationOptions,
 ) -> Result<generation::OutputArtifacts, BuildError> {
-    let pipeline_output =
-        pipeline::build_graph_with_options(plan, validation_options)
-            .map_err(BuildError::Pipeline)?;
+    let pipeline_output = pipeline::build_graph_with_options(
+        plan,
+        validation_options.clone(),
+    )
+    .map_err(BuildError::Pipeline)?;
+    let mut artifacts = emit_pipeline_output(plan, pipeline_output)?;
+    append_extra_sets(plan, &validation_options, &mut artifacts)?;
+    // Once for the whole build, with every set and generation warning
+    check_warning_budget(&validation_options, &artifacts.warnings)?;
+    Ok(artifacts)
+}
+
+/// Fails with `RR0005` when `warnings` exceed `max_warnings`
+fn check_warning_budget(
+    validation_options: &analysis::ValidationOptions,
+    warnings: &[analysis::AnalysisWarning],
+) -> Result<(), BuildError> {
+    let over_budget = validation_options
+        .max_warnings
+        .and_then(|limit| analysis::check_warning_budget(limit, warnings));
+    match over_budget {
+        Some(error) => Err(BuildError::Analysis(vec![error])),
+        None => Ok(()),
+    }
+}
+
+/// Builds the resources of `plan` together with `extra`, resources built
+/// in code (e.g. read from a database by the build script). They are
+/// merged in before analysis: a key also defined in XML is a duplicate of
+/// it, and references work in both directions.
+#[allow(dead_code)] // Public API, may be used by consumers
+pub fn build_with_graph(
+    plan: &BuildPlan,
+    extra: ir::ResourceGraph,
+) -> Result<generation::OutputArtifacts, BuildError> {
+    build_with_graph_and_options(
+        plan,
+        extra,
+        analysis::ValidationOptions::default(),
+    )
+}
+
+/// [`build_with_graph`] with explicit validation options, `max_warnings`
+/// included
+#[allow(dead_code)] // Public API, may be used by consumers
+pub fn build_with_graph_and_options(
+    plan: &BuildPlan,
+    extra: ir::ResourceGraph,
+    validation_options: analysis::ValidationOptions,
+) -> Result<generation::OutputArtifacts, BuildError> {
+    let pipeline_output = pipeline::build_graph_with_extra(
+        plan,
+        validation_options.clone(),
+        extra,
+    )
+    .map_err(BuildError::Pipeline)?;
+    let mut artifacts = emit_pipeline_output(plan, pipeline_output)?;
+    append_extra_sets(plan, &validation_options, &mut artifacts)?;
+    check_warning_budget(&validation_options, &artifacts.warnings)?;
+    Ok(artifacts)
+}
+
+/// Builds resources parsed by another front-end instead of read from XML.
+/// `plan` supplies the type, validation, and generation settings; its
+/// directories, profile, tag aliases, and `file_as_namespace` are unused.
+#[allow(dead_code)] // Public API, may be used by consumers
+pub fn build_from_parsed(
+    files: Vec<parsing::ParsedResourceFile>,
+    plan: &BuildPlan,
+) -> Result<generation::OutputArtifacts, BuildError> {
+    build_from_parsed_with_options(
+        files,
+        plan,
+        analysis::ValidationOptions::default(),
+    )
+}
+
+/// [`build_from_parsed`] with explicit validation options, `max_warnings`
+/// included
+#[allow(dead_code)] // Public API, may be used by consumers
+pub fn build_from_parsed_with_options(
+    files: Vec<parsing::ParsedResourceFile>,
+    plan: &BuildPlan,
+    validation_options: analysis::ValidationOptions,
+) -> Result<generation::OutputArtifacts, BuildError> {
+    let pipeline_output = pipeline::build_graph_from_parsed(
+        plan,
+        files,
+        validation_options.clone(),
+    )
+    .map_err(BuildError::Pipeline)?;
+    let artifacts = emit_pipeline_output(plan, pipeline_output)?;
+    check_warning_budget(&validation_options, &artifacts.warnings)?;
+    Ok(artifacts)
+}
+
+/// Builds each of `plan.extra_sets` into its own root module and appends
+/// it to the main artifact. Every set is validated alone, so duplicates
+/// are only reported within a set.
+fn append_extra_sets(
+    plan: &BuildPlan,
+    validation_options: &analysis::ValidationOptions,
+    artifacts: &mut generation::OutputArtifacts,
+) -> Result<(), BuildError> {
+    for (name, dir) in &plan.extra_sets {
+        let set_plan = plan.extra_set_plan(name, dir);
+        let pipeline_output = pipeline::build_graph_with_options(
+            &set_plan,
+            validation_options.clone(),
+        )
+        .map_err(BuildError::Pipeline)?;
+        let set = emit_pipeline_output(&set_plan, pipeline_output)?;
+        artifacts.rust.push_str(&set.rust);
+        artifacts.warnings.extend(set.warnings);
+        artifacts.interned_strings.values += set.interned_strings.values;
+        artifacts.interned_strings.constants += set.interned_strings.constants;
+        artifacts.stats.rows.extend(set.stats.rows);
+        artifacts.signatures.extend(set.signatures);
+    }
+    Ok(())
+}
+
+/// Prints the warnings (and notes when verbose), then fails on errors or
+/// generates the code
+fn emit_pipeline_output(
+    plan: &BuildPlan,
+    pipeline_output: pipeline::PipelineOutput,
+) -> Result<generation::OutputArtifacts, BuildError> {
+    if plan.verbose {
+        for note in &pipeline_output.analysis_result.notes {
+            eprintln!("note: {note}");
+        }
+    }
 
     // Print warnings
     for warning in &pipeline_output.analysis_result.warnings {
-        eprintln!("warning: {}", warning.message);
+        eprintln!("warning: {warning}");
+        if let Some(snippet) = &warning.snippet {
+            eprintln!("{snippet}");
+        }
     }
 
     // Errors stop the build
@@ -78,69 +212,276 @@ pub fn build_with_plan_and_options(
         ));
     }
 
-    generation::emit(
+    let generation_options = generation::GenerationOptions {
+        emit_smoke_tests: plan.emit_smoke_tests,
+        emit_to_json: plan.emit_to_json,
+        emit_meta: plan.emit_meta,
+        emit_lookup: plan.emit_lookup,
+        emit_kind_modules: plan.emit_kind_modules,
+        emit_resource_markers: plan.emit_resource_markers,
+        intern_strings: plan.intern_strings,
+        group_by_origin: plan.group_by_origin,
+        naming: plan.naming,
+        embed_test_resources: plan.embed_test_resources,
+        root_module: plan.root_module.clone(),
+    };
+    let mut artifacts = generation::emit(
         &pipeline_output.graph,
         &pipeline_output.analysis_result.warnings,
+        &plan.type_registry(),
+        generation_options,
     )
-    .map_err(BuildError::Generation)
+    .map_err(BuildError::Generation)?;
+    if plan.emit_inputs {
+        let header = generation::inputs_header(
+            &plan.root_module,
+            &plan.profile,
+            &pipeline_output.inputs,
+        );
+        artifacts.rust.insert_str(0, &header);
+    }
+    artifacts.inputs = pipeline_output.inputs;
+    let interned = artifacts.interned_strings;
+    if plan.verbose && plan.intern_strings {
+        eprintln!(
+            "note: {} interned {} string values shared by {} constants",
+            plan.root_module, interned.values, interned.constants
+        );
+    }
+    Ok(artifacts)
+}
+
+/// What [`write_generated_code`] did with the file at the given path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WriteOutcome {
+    Written(std::path::PathBuf),
+    /// The file already held the same bytes and was left untouched
+    Unchanged(std::path::PathBuf),
+}
+
+impl WriteOutcome {
+    pub fn path(&self) -> &std::path::Path {
+        match self {
+            Self::Written(path) | Self::Unchanged(path) => path,
+        }
+    }
+}
+
+/// File included by `include_resources!`
+pub const GENERATED_FILE: &str = "r_generated.rs";
+/// File included by `include_test_resources!`
+pub const GENERATED_TESTS_FILE: &str = "r_tests_generated.rs";
+/// Template signatures of the last build, compared by the next one
+pub const SIGNATURES_FILE: &str = "r_signatures.txt";
+/// Compile-time variable holding the path of [`GENERATED_FILE`]. The macros
+/// include the file through it, so a crate whose build script never wrote
+/// the file gets an error naming the fix instead of a failed `include!`.
+pub const GENERATED_FILE_ENV: &str = "R_RESOURCES_GENERATED";
+/// Like [`GENERATED_FILE_ENV`], for [`GENERATED_TESTS_FILE`]
+pub const GENERATED_TESTS_FILE_ENV: &str = "R_RESOURCES_TESTS_GENERATED";
+
+/// Writes the generated code to `out_dir/r_generated.rs`. The directory is
+/// created if needed. Identical content is not rewritten, so the mtime only
+/// moves when the code does; otherwise the file is replaced atomically and
+/// an interrupted build never leaves it truncated.
+pub fn write_generated_code(
+    code: &str,
+    out_dir: &std::path::Path,
+) -> std::io::Result<WriteOutcome> {
+    write_artifact(code, out_dir, GENERATED_FILE)
 }
 
-/// Writes the generated code to `OUT_DIR/r_generated.rs`
-pub fn write_generated_code(code: &str) -> std::io::Result<()> {
+/// Like [`write_generated_code`], for `OutputArtifacts::tests_rust` in
+/// `out_dir/r_tests_generated.rs`
+pub fn write_generated_tests_code(
+    code: &str,
+    out_dir: &std::path::Path,
+) -> std::io::Result<WriteOutcome> {
+    write_artifact(code, out_dir, GENERATED_TESTS_FILE)
+}
+
+fn write_artifact(
+    code: &str,
+    out_dir: &std::path::Path,
+    file_name: &str,
+) -> std::io::Result<WriteOutcome> {
     use std::fs;
-    use std::path::Path;
 
-    let out_dir = std::env::var("OUT_DIR").map_err(|_| {
+    fs::create_dir_all(out_dir)?;
+    let dest_path = out_dir.join(file_name);
+    if fs::read(&dest_path).is_ok_and(|existing| existing == code.as_bytes())
+    {
+        return Ok(WriteOutcome::Unchanged(dest_path));
+    }
+    // Same directory as the destination, so the rename cannot cross
+    // filesystems
+    let tmp_path = out_dir.join(format!("{file_name}.tmp"));
+    if let Err(err) = fs::write(&tmp_path, code)
+        .and_then(|()| fs::rename(&tmp_path, &dest_path))
+    {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(err);
+    }
+    Ok(WriteOutcome::Written(dest_path))
+}
+
+/// [`write_generated_code`] into the `OUT_DIR` of the running build script
+#[allow(dead_code)] // Public API, may be used by consumers
+pub fn write_generated_code_from_env(
+    code: &str,
+) -> std::io::Result<WriteOutcome> {
+    let outcome = write_generated_code(code, &out_dir_from_env()?)?;
+    announce_generated_file(GENERATED_FILE_ENV, outcome.path());
+    Ok(outcome)
+}
+
+/// Tells cargo to set `env_name` to `path` when compiling the crate
+fn announce_generated_file(env_name: &str, path: &std::path::Path) {
+    println!("cargo:rustc-env={env_name}={}", path.display());
+}
+
+fn out_dir_from_env() -> std::io::Result<std::path::PathBuf> {
+    std::env::var_os("OUT_DIR").map(Into::into).ok_or_else(|| {
         std::io::Error::new(
             std::io::ErrorKind::NotFound,
             "OUT_DIR environment variable not set",
         )
-    })?;
-    let dest_path = Path::new(&out_dir).join("r_generated.rs");
-    fs::write(&dest_path, code)
+    })
 }
 
 /// Main build function (equivalent to legacy `codegen::build()`)
 ///
-/// Scans `res/` and generates code in `OUT_DIR/r_generated.rs`
+/// Scans `res/` and generates code in `OUT_DIR/r_generated.rs`. Test
+/// resources are read from `res/tests/`, or from the directory named by
+/// `R_RESOURCES_TESTS_DIR` (relative to `res/`).
+///
+/// Settings also come from the `[package.metadata.r-resources]` table of
+/// the crate's `Cargo.toml` (see [`input::ManifestConfig`]); an
+/// `R_RESOURCES_*` variable that is set wins over the table.
 pub fn build() {
     use std::path::Path;
 
     let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
         .expect("CARGO_MANIFEST_DIR environment variable not set");
-    let res_dir = Path::new(&manifest_dir).join("res");
-    let tests_dir = res_dir.join("tests");
+    let config =
+        input::ManifestConfig::read(&Path::new(&manifest_dir).join("Cargo.toml"))
+            .unwrap_or_else(|err| panic!("{err}"));
+    if let Some(message) = config.unknown_keys_warning() {
+        println!("cargo:warning={message}");
+    }
+    let var = |name: &str| std::env::var(name).ok().or_else(|| config.var(name));
 
-    let include_tests = tests_dir.exists()
-        && (std::env::var("CARGO_CFG_TEST").is_ok()
-            || std::env::var("R_RESOURCES_INCLUDE_TESTS").is_ok());
+    let res_dir = Path::new(&manifest_dir)
+        .join(config.res_dir.as_deref().unwrap_or("res"));
+    let tests_dir = res_dir
+        .join(var("R_RESOURCES_TESTS_DIR").unwrap_or_else(|| "tests".to_string()));
+
+    // Test resources always get their own file for
+    // `include_test_resources!`; they join `r_generated.rs` on request
+    let embed_tests = std::env::var("CARGO_CFG_TEST").is_ok()
+        || var("R_RESOURCES_INCLUDE_TESTS").is_some();
 
     let plan = BuildPlan {
         resources_dir: res_dir,
-        tests_resources_dir: include_tests.then_some(tests_dir),
-        profile: std::env::var("PROFILE")
-            .unwrap_or_else(|_| "debug".to_string()),
+        tests_resources_dir: tests_dir.exists().then_some(tests_dir),
+        embed_test_resources: embed_tests,
+        // A profile of the crate's own, e.g. `staging`, or Cargo's
+        profile: var("R_RESOURCES_PROFILE")
+            .or_else(|| std::env::var("PROFILE").ok())
+            .unwrap_or_else(|| "debug".to_string()),
+        emit_smoke_tests: std::env::var("R_RESOURCES_SMOKE_TESTS")
+            .is_ok_and(|v| v == "1" || v == "true"),
+        emit_to_json: std::env::var("R_RESOURCES_EMIT_JSON")
+            .is_ok_and(|v| v == "1" || v == "true"),
+        emit_meta: std::env::var("R_RESOURCES_EMIT_META")
+            .is_ok_and(|v| v == "1" || v == "true"),
+        emit_lookup: std::env::var("R_RESOURCES_EMIT_LOOKUP")
+            .is_ok_and(|v| v == "1" || v == "true"),
+        emit_kind_modules: std::env::var("R_RESOURCES_KIND_MODULES")
+            .is_ok_and(|v| v == "1" || v == "true"),
+        emit_resource_markers: std::env::var("R_RESOURCES_RESOURCE_MARKERS")
+            .is_ok_and(|v| v == "1" || v == "true"),
+        intern_strings: std::env::var("R_RESOURCES_INTERN_STRINGS")
+            .is_ok_and(|v| v == "1" || v == "true"),
+        group_by_origin: std::env::var("R_RESOURCES_GROUP_BY_ORIGIN")
+            .is_ok_and(|v| v == "1" || v == "true"),
+        naming: naming_from_env(|name| std::env::var(name).ok()),
+        custom_types: Vec::new(),
+        tag_aliases: Vec::new(),
+        file_as_namespace: config.file_as_namespace.unwrap_or(false),
+        // Like the legacy generator: a crate without resources still builds
+        allow_missing_resources: true,
+        include_hidden_files: std::env::var("R_RESOURCES_INCLUDE_HIDDEN")
+            .is_ok_and(|v| v == "1" || v == "true"),
+        default_encoding: std::env::var("R_RESOURCES_DEFAULT_ENCODING").ok(),
+        build_version: std::env::var("R_RESOURCES_BUILD_VERSION")
+            .is_ok_and(|v| v == "1" || v == "true")
+            .then(|| std::env::var("CARGO_PKG_VERSION").ok())
+            .flatten(),
+        root_module: config
+            .root_module
+            .clone()
+            .unwrap_or_else(|| "r".to_string()),
+        extra_sets: Vec::new(),
+        template_builders: std::env::var("R_RESOURCES_TEMPLATE_BUILDERS")
+            .is_ok_and(|v| v == "1" || v == "true"),
+        verbose: std::env::var("R_RESOURCES_VERBOSE")
+            .is_ok_and(|v| v == "1" || v == "true"),
+        emit_inputs: !std::env::var("R_RESOURCES_HIDE_INPUTS")
+            .is_ok_and(|v| v == "1" || v == "true"),
+        dry_run: std::env::var("R_RESOURCES_DRY_RUN")
+            .is_ok_and(|v| v == "1" || v == "true"),
+        overrides: Vec::new(),
     };
 
-    // Check if we should treat duplicates as errors
-    let treat_duplicates_as_errors =
-        std::env::var("R_RESOURCES_DUPLICATES_AS_ERRORS")
-            .map(|v| v == "1" || v == "true")
-            .unwrap_or(false);
+    let validation_options = validation_options_from_env(var);
 
-    let validation_options = analysis::ValidationOptions {
-        treat_duplicates_as_errors,
-    };
+    // Shown by cargo, unlike stderr of a successful build script
+    let no_resources_allowed = validation_options
+        .allow
+        .iter()
+        .any(|code| code == analysis::codes::NO_RESOURCES);
+    if let Some(message) = input::missing_resources_warning(&plan) {
+        if !no_resources_allowed {
+            println!(
+                "cargo:warning=[{}] {message}",
+                analysis::codes::NO_RESOURCES
+            );
+        }
+    }
 
-    match build_with_plan_and_options(&plan, validation_options) {
+    let out_dir = out_dir_from_env().expect("Failed to write generated code");
+    let built = build_with_plan_and_options(&plan, validation_options.clone())
+        .and_then(|mut artifacts| {
+            add_reordered_templates(
+                &mut artifacts,
+                &out_dir,
+                &validation_options,
+            )?;
+            Ok(artifacts)
+        });
+    match built {
         Ok(artifacts) => {
             // Print warnings if any
             for warning in &artifacts.warnings {
-                eprintln!("warning: {warning}");
+                if warning.code == analysis::codes::REORDERED_PARAMETERS {
+                    println!("cargo:warning={warning}");
+                } else {
+                    eprintln!("warning: {warning}");
+                }
             }
-            write_generated_code(&artifacts.rust)
+            emit_artifacts(&plan, &artifacts, &out_dir)
                 .expect("Failed to write generated code");
         }
+        Err(BuildError::Analysis(errors)) => {
+            for error in &errors {
+                eprintln!("error: {error}");
+                if let Some(snippet) = &error.snippet {
+                    eprintln!("{snippet}");
+                }
+            }
+            std::process::exit(1);
+        }
         Err(e) => {
             eprintln!("error: {e}");
             std::process::exit(1);
@@ -148,6 +489,246 @@ pub fn build() {
     }
 }
 
+/// Naming configuration of [`build`]: `R_RESOURCES_CONST_CASE`,
+/// `R_RESOURCES_MODULE_CASE`, and `R_RESOURCES_FN_CASE`, each `upper`,
+/// `pascal`, `snake`, or `preserve`
+fn naming_from_env(
+    var: impl Fn(&str) -> Option<String>,
+) -> casing::NamingConfig {
+    let case = |name: &str, default| match var(name) {
+        Some(v) => v.parse().unwrap_or_else(|err| panic!("{name}: {err}")),
+        None => default,
+    };
+    let default = casing::NamingConfig::default();
+    casing::NamingConfig {
+        const_case: case("R_RESOURCES_CONST_CASE", default.const_case),
+        module_case: case("R_RESOURCES_MODULE_CASE", default.module_case),
+        fn_case: case("R_RESOURCES_FN_CASE", default.fn_case),
+    }
+}
+
+/// Validation options of [`build`], from the variables `var` looks up
+fn validation_options_from_env(
+    var: impl Fn(&str) -> Option<String>,
+) -> analysis::ValidationOptions {
+    let flag = |name| var(name).is_some_and(|v| v == "1" || v == "true");
+
+    // Diagnostic codes to fail on or silence, e.g. `RR0001,RR0060`
+    let codes = |name| {
+        var(name)
+            .map(|v| {
+                v.split(',')
+                    .map(str::trim)
+                    .filter(|code| !code.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    // Codes scoped to a region, e.g. `legacy:RR0060,checkout:RR0031`
+    let region_codes = |name| {
+        let entries: Vec<String> = codes(name);
+        entries
+            .iter()
+            .map(|entry| {
+                let (region, code) = entry.rsplit_once(':').unwrap_or_else(
+                    || panic!("{name} entries must be `region:CODE`"),
+                );
+                (region.trim().to_string(), code.trim().to_string())
+            })
+            .collect()
+    };
+
+    // Deepest nesting allowed, or `none` to lift the limit
+    let max_namespace_depth = match var("R_RESOURCES_MAX_NAMESPACE_DEPTH") {
+        Some(v) if v == "none" => None,
+        Some(v) => Some(v.trim().parse().expect(
+            "R_RESOURCES_MAX_NAMESPACE_DEPTH must be a number or `none`",
+        )),
+        None => Some(analysis::DEFAULT_MAX_NAMESPACE_DEPTH),
+    };
+
+    // Value sizes in bytes, or `none` to lift the limit
+    let bytes = |name: &str, default| match var(name) {
+        Some(v) if v == "none" => None,
+        Some(v) => Some(v.trim().parse().unwrap_or_else(|_| {
+            panic!("{name} must be a number of bytes or `none`")
+        })),
+        None => Some(default),
+    };
+
+    let max_warnings = var("R_RESOURCES_MAX_WARNINGS").map(|v| {
+        v.trim()
+            .parse()
+            .expect("R_RESOURCES_MAX_WARNINGS must be a number")
+    });
+
+    analysis::ValidationOptions {
+        treat_duplicates_as_errors: flag("R_RESOURCES_DUPLICATES_AS_ERRORS"),
+        type_mismatch_duplicates_as_errors: !flag(
+            "R_RESOURCES_NUMBER_TYPE_CONFLICTS_AS_WARNINGS",
+        ),
+        max_namespace_depth,
+        max_value_bytes: bytes(
+            "R_RESOURCES_MAX_VALUE_BYTES",
+            analysis::DEFAULT_MAX_VALUE_BYTES,
+        ),
+        hard_max_value_bytes: bytes(
+            "R_RESOURCES_HARD_MAX_VALUE_BYTES",
+            analysis::DEFAULT_HARD_MAX_VALUE_BYTES,
+        ),
+        require_namespace: flag("R_RESOURCES_REQUIRE_NAMESPACE"),
+        explicit_namespace_extension: flag(
+            "R_RESOURCES_EXPLICIT_NAMESPACE_EXTENSION",
+        ),
+        allow_raw: flag("R_RESOURCES_ALLOW_RAW"),
+        flag_nbsp: flag("R_RESOURCES_FLAG_NBSP"),
+        canonical_colors: flag("R_RESOURCES_CANONICAL_COLORS"),
+        quiet_sanitization: flag("R_RESOURCES_QUIET_SANITIZATION"),
+        // Keys, comma-separated like the codes
+        literal_allow_list: codes("R_RESOURCES_ALLOW_LITERALS"),
+        deny: codes("R_RESOURCES_DENY"),
+        allow: codes("R_RESOURCES_ALLOW"),
+        deny_in_regions: region_codes("R_RESOURCES_DENY_IN_REGION"),
+        allow_in_regions: region_codes("R_RESOURCES_ALLOW_IN_REGION"),
+        max_warnings,
+        ..Default::default()
+    }
+}
+
+/// Writes the artifacts into `out_dir` like [`build`] does, or with
+/// [`BuildPlan::dry_run`] prints the [`dry_run_report`] and leaves the
+/// directory untouched
+pub fn emit_artifacts(
+    plan: &BuildPlan,
+    artifacts: &generation::OutputArtifacts,
+    out_dir: &std::path::Path,
+) -> std::io::Result<()> {
+    if plan.dry_run {
+        eprint!("{}", dry_run_report(artifacts, Some(out_dir)));
+        return Ok(());
+    }
+    write_artifacts(artifacts, out_dir)
+}
+
+/// What writing `artifacts` would do: each file with its path (inside
+/// `out_dir` when known), size, and line count, then the warning count
+pub fn dry_run_report(
+    artifacts: &generation::OutputArtifacts,
+    out_dir: Option<&std::path::Path>,
+) -> String {
+    let mut files = vec![(GENERATED_FILE, &artifacts.rust)];
+    files.extend(
+        artifacts
+            .tests_rust
+            .as_ref()
+            .map(|code| (GENERATED_TESTS_FILE, code)),
+    );
+    let mut report = String::new();
+    for (file_name, code) in files {
+        let path = match out_dir {
+            Some(dir) => dir.join(file_name),
+            None => std::path::PathBuf::from(file_name),
+        };
+        report.push_str(&format!(
+            "r-resources: dry run, would write {} ({} bytes, {} lines)\n",
+            path.display(),
+            code.len(),
+            code.lines().count()
+        ));
+    }
+    report.push_str(&format!(
+        "r-resources: dry run, {} warning(s)\n",
+        artifacts.warnings.len()
+    ));
+    report
+}
+
+/// Templates whose parameters are in another order than in the
+/// [`SIGNATURES_FILE`] of the last build into `out_dir`, as `RR0016`
+/// warnings; none on a first build
+fn reordered_templates(
+    artifacts: &generation::OutputArtifacts,
+    out_dir: &std::path::Path,
+) -> Vec<analysis::AnalysisWarning> {
+    let Ok(previous) = std::fs::read_to_string(out_dir.join(SIGNATURES_FILE))
+    else {
+        return Vec::new();
+    };
+    generation::reordered_parameters(&previous, &artifacts.signatures)
+        .into_iter()
+        .map(|message| {
+            analysis::AnalysisWarning::new(
+                analysis::codes::REORDERED_PARAMETERS,
+                message,
+                None,
+            )
+        })
+        .collect()
+}
+
+/// Adds the [`reordered_templates`] to the warnings of `artifacts`, after
+/// `deny` and `allow`, and checks `max_warnings` again with them
+fn add_reordered_templates(
+    artifacts: &mut generation::OutputArtifacts,
+    out_dir: &std::path::Path,
+    validation_options: &analysis::ValidationOptions,
+) -> Result<(), BuildError> {
+    let mut result = analysis::AnalysisResult {
+        warnings: reordered_templates(artifacts, out_dir),
+        ..Default::default()
+    };
+    validation_options.apply_levels(&mut result);
+    if !result.errors.is_empty() {
+        return Err(BuildError::Analysis(result.errors));
+    }
+    artifacts.warnings.append(&mut result.warnings);
+    check_warning_budget(validation_options, &artifacts.warnings)
+}
+
+/// Writes both generated files into `out_dir`, then the template
+/// signatures. Without test resources a stale `r_tests_generated.rs` is
+/// removed, so `include_test_resources!` fails instead of including old
+/// constants.
+fn write_artifacts(
+    artifacts: &generation::OutputArtifacts,
+    out_dir: &std::path::Path,
+) -> std::io::Result<()> {
+    let outcome = write_generated_code(&artifacts.rust, out_dir)?;
+    announce_generated_file(GENERATED_FILE_ENV, outcome.path());
+    let mut outcomes = vec![outcome];
+    match &artifacts.tests_rust {
+        Some(code) => {
+            let outcome = write_generated_tests_code(code, out_dir)?;
+            announce_generated_file(GENERATED_TESTS_FILE_ENV, outcome.path());
+            outcomes.push(outcome);
+        }
+        None => {
+            let stale = out_dir.join(GENERATED_TESTS_FILE);
+            if stale.exists() {
+                std::fs::remove_file(stale)?;
+            }
+        }
+    }
+    write_artifact(
+        &generation::signatures_manifest(&artifacts.signatures),
+        out_dir,
+        SIGNATURES_FILE,
+    )?;
+    for outcome in outcomes {
+        match outcome {
+            WriteOutcome::Written(path) => {
+                eprintln!("r-resources: wrote {}", path.display());
+            }
+            WriteOutcome::Unchanged(path) => {
+                eprintln!("r-resources: {} is up to date", path.display());
+            }
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -161,6 +742,35 @@ mod tests {
         fs::write(path, contents).unwrap();
     }
 
+    /// Compiles `code` and the `#[test]`s in it with rustc, warnings
+    /// denied, as `dir/<name>`, and runs them
+    fn run_generated_tests(dir: &std::path::Path, name: &str, code: &str) {
+        let lib = dir.join(format!("{name}.rs"));
+        write_file(&lib, code);
+        let binary = dir.join(name);
+        let rustc = std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+        let run = |command: &mut std::process::Command| {
+            let output = command.output().expect("run command");
+            assert!(
+                output.status.success(),
+                "{}{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            );
+        };
+        run(std::process::Command::new(rustc)
+            .args(["--edition", "2021", "--test", "-D", "warnings", "-o"])
+            .arg(&binary)
+            .arg(&lib));
+        run(&mut std::process::Command::new(&binary));
+    }
+
+    fn snapshot_path(name: &str) -> std::path::PathBuf {
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("generator/snapshots")
+            .join(format!("{name}.snap"))
+    }
+
     #[test]
     fn build_with_plan_runs_pipeline_and_generation() {
         let tmp = tempdir().unwrap();
@@ -177,30 +787,17 @@ mod tests {
         let artifacts =
             build_with_plan(&plan).expect("build succeeds");
 
-        // Check that R struct is generated
-        assert!(artifacts.rust.contains("pub struct R"));
-        assert!(artifacts.rust.contains("impl R"));
-
-        // Check that r module is generated
-        assert!(artifacts.rust.contains("pub mod r {"));
-
-        // Check that resources are generated
-        assert!(artifacts.rust.contains("pub const TITLE: &str"));
-        assert!(artifacts.rust.contains("\"Hello\""));
-        assert!(artifacts
-            .rust
-            .contains("pub const MAX_RETRIES: i64"));
-        assert!(artifacts.rust.contains("pub const ENABLED: bool"));
-        assert!(artifacts.rust.contains("= true"));
+        // R struct, r module, and one constant per resource
+        generation::assert_artifact_matches(
+            &snapshot_path("build_with_plan_runs_pipeline_and_generation"),
+            &artifacts,
+        );
     }
 
     #[test]
     fn write_generated_code_creates_file() {
         let tmp = tempdir().unwrap();
-        let out_dir = tmp.path();
-
-        // Simuler OUT_DIR
-        std::env::set_var("OUT_DIR", out_dir);
+        let out_dir = tmp.path().join("out");
 
         let code = r#"
 pub struct R;
@@ -209,17 +806,208 @@ pub mod r {
 }
 "#;
 
-        write_generated_code(code).expect("write succeeds");
-
+        let outcome =
+            write_generated_code(code, &out_dir).expect("write succeeds");
         let generated_file = out_dir.join("r_generated.rs");
-        assert!(generated_file.exists());
+        assert_eq!(outcome, WriteOutcome::Written(generated_file.clone()));
 
         let contents = fs::read_to_string(&generated_file).unwrap();
         assert!(contents.contains("pub struct R"));
         assert!(contents.contains("pub const TEST"));
 
-        // Cleanup
-        std::env::remove_var("OUT_DIR");
+        // Rewriting replaces the file and leaves no temporary behind
+        write_generated_code("pub struct R;", &out_dir).unwrap();
+        assert_eq!(
+            fs::read_to_string(&generated_file).unwrap(),
+            "pub struct R;"
+        );
+        assert_eq!(fs::read_dir(&out_dir).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn build_lists_its_inputs_unless_disabled() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources><string name="title">Demo</string></resources>"#,
+        );
+        write_file(
+            &res_dir.join("auth.xml"),
+            r#"<resources><string name="title">Auth</string></resources>"#,
+        );
+        write_file(
+            &res_dir.join("tests").join("values.xml"),
+            r#"<resources><string name="fixture">x</string></resources>"#,
+        );
+        let tests_dir = res_dir.join("tests");
+        let plan = BuildPlan::new(res_dir, Some(tests_dir), "release");
+        let artifacts = build_with_plan(&plan).unwrap();
+        let input = |path: &str, order, is_test| generation::InputFile {
+            path: path.to_string(),
+            order,
+            is_test,
+        };
+        assert_eq!(
+            artifacts.inputs,
+            [
+                input("res/auth.xml", 1, false),
+                input("res/values.xml", 2, false),
+                input("res/tests/values.xml", 3, true),
+            ]
+        );
+        assert!(artifacts.rust.starts_with(
+            "// Resource files of `r` (profile: release), in priority \
+             order:\n//   1. res/auth.xml\n"
+        ));
+
+        let plan = BuildPlan {
+            emit_inputs: false,
+            ..plan
+        };
+        let artifacts = build_with_plan(&plan).unwrap();
+        assert!(artifacts.rust.starts_with("\npub struct R;"));
+        assert_eq!(artifacts.inputs.len(), 3);
+    }
+
+    #[test]
+    fn dry_run_leaves_out_dir_untouched() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources><string name="title">Demo</string></resources>"#,
+        );
+        write_file(
+            &res_dir.join("tests").join("values.xml"),
+            r#"<resources><string name="fixture">x</string></resources>"#,
+        );
+        let tests_dir = res_dir.join("tests");
+        let plan = BuildPlan::new(res_dir, Some(tests_dir), "debug").dry_run();
+        let artifacts = build_with_plan(&plan).unwrap();
+        let out_dir = tmp.path().join("out");
+        emit_artifacts(&plan, &artifacts, &out_dir).unwrap();
+        assert!(!out_dir.exists());
+
+        let report = dry_run_report(&artifacts, Some(&out_dir));
+        let expected = format!(
+            "r-resources: dry run, would write {} ({} bytes, ",
+            out_dir.join(GENERATED_FILE).display(),
+            artifacts.rust.len()
+        );
+        assert!(report.starts_with(&expected), "{report}");
+        assert!(report.contains(GENERATED_TESTS_FILE), "{report}");
+        assert!(report.ends_with("dry run, 0 warning(s)\n"), "{report}");
+
+        // The same artifacts are written once the plan is not a dry run
+        let plan = BuildPlan {
+            dry_run: false,
+            ..plan
+        };
+        emit_artifacts(&plan, &artifacts, &out_dir).unwrap();
+        assert!(out_dir.join(GENERATED_FILE).exists());
+    }
+
+    #[test]
+    fn reordered_template_parameters_are_reported_on_the_next_build() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        let out_dir = tmp.path().join("out");
+        let build = |params: &str| {
+            write_file(
+                &res_dir.join("values.xml"),
+                &format!(
+                    r#"<resources><ns name="mail">
+                        <template name="welcome">{params}Hi {{name}}, {{count}} new</template>
+                    </ns></resources>"#
+                ),
+            );
+            let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+            build_with_plan(&plan).unwrap()
+        };
+
+        let first = build(r#"<string name="name"/><number name="count"/>"#);
+        assert!(reordered_templates(&first, &out_dir).is_empty());
+        write_artifacts(&first, &out_dir).unwrap();
+        assert_eq!(
+            fs::read_to_string(out_dir.join(SIGNATURES_FILE)).unwrap(),
+            "r::mail::welcome(name: &str, count: i64)\n"
+        );
+
+        // `order` keeps the signature whatever the declaration order
+        let ordered = build(
+            r#"<number name="count" order="2"/><string name="name" order="1"/>"#,
+        );
+        assert!(ordered
+            .rust
+            .contains("pub fn welcome(name: &str, count: i64) -> String"));
+        assert!(reordered_templates(&ordered, &out_dir).is_empty());
+
+        let swapped = build(r#"<number name="count"/><string name="name"/>"#);
+        let warnings = reordered_templates(&swapped, &out_dir);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].code, analysis::codes::REORDERED_PARAMETERS);
+        assert!(warnings[0].message.starts_with(
+            "Parameters of template 'r::mail::welcome' were reordered since the last build: (name, count) is now (count, name)"
+        ));
+
+        // Like every other warning: allowed, denied, and in the budget
+        let add = |options: analysis::ValidationOptions| {
+            let mut artifacts = build(r#"<number name="count"/><string name="name"/>"#);
+            add_reordered_templates(&mut artifacts, &out_dir, &options)
+                .map(|()| artifacts.warnings)
+        };
+        let warnings = add(Default::default()).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].code, analysis::codes::REORDERED_PARAMETERS);
+        let allow = vec![analysis::codes::REORDERED_PARAMETERS.to_string()];
+        let allowed = add(analysis::ValidationOptions {
+            allow: allow.clone(),
+            ..Default::default()
+        });
+        assert!(allowed.unwrap().is_empty());
+        let denied = add(analysis::ValidationOptions {
+            deny: allow,
+            ..Default::default()
+        });
+        let Err(BuildError::Analysis(errors)) = denied else {
+            panic!("denied warning should fail the build");
+        };
+        assert_eq!(errors[0].code, analysis::codes::REORDERED_PARAMETERS);
+        let over_budget = add(analysis::ValidationOptions {
+            max_warnings: Some(0),
+            ..Default::default()
+        });
+        let Err(BuildError::Analysis(errors)) = over_budget else {
+            panic!("the reorder should count in max_warnings");
+        };
+        assert_eq!(errors[0].code, analysis::codes::TOO_MANY_WARNINGS);
+    }
+
+    #[test]
+    fn write_generated_code_skips_identical_content() {
+        let tmp = tempdir().unwrap();
+        let out_dir = tmp.path();
+        let path = out_dir.join("r_generated.rs");
+        write_generated_code("pub struct R;", out_dir).unwrap();
+
+        // An old mtime that a rewrite would certainly move
+        let old = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1000);
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+        let mtime = || fs::metadata(&path).unwrap().modified().unwrap();
+
+        let outcome = write_generated_code("pub struct R;", out_dir).unwrap();
+        assert_eq!(outcome, WriteOutcome::Unchanged(path.clone()));
+        assert_eq!(mtime(), old);
+
+        let outcome = write_generated_code("pub struct R2;", out_dir).unwrap();
+        assert_eq!(outcome, WriteOutcome::Written(path.clone()));
+        assert_ne!(mtime(), old);
     }
 
     #[test]
@@ -238,51 +1026,2914 @@ pub mod r {
         let artifacts = build_with_plan(&plan)
             .expect("build succeeds with warnings");
 
-        // Should have warnings about duplicates
-        assert!(!artifacts.warnings.is_empty());
-        assert!(artifacts
-            .warnings
-            .iter()
-            .any(|w| w.contains("Duplicate")));
+        // A duplicate warning, and only the first definition generated
+        // (priority) with a deprecated annotation
+        generation::assert_artifact_matches(
+            &snapshot_path("build_with_duplicates_generates_warnings"),
+            &artifacts,
+        );
+    }
+
+    #[test]
+    fn test_resources_get_their_own_module_and_artifact() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources><string name="app_name">App</string></resources>"#,
+        );
+        write_file(
+            &res_dir.join("tests/fixtures.xml"),
+            r#"<resources><string name="fixture_user">bob</string></resources>"#,
+        );
+        let tests_dir = Some(res_dir.join("tests"));
+        let mut plan = BuildPlan::new(res_dir, tests_dir, "debug");
+        plan.emit_smoke_tests = true;
 
-        // Code should still be generated with deprecated annotation (only first one)
-        assert!(artifacts.rust.contains("pub const TITLE"));
-        assert!(artifacts.rust.contains("#[deprecated"));
+        let artifacts = build_with_plan(&plan).expect("build succeeds");
+        let tests_rust = artifacts.tests_rust.expect("tests artifact");
+        assert!(tests_rust.starts_with("\npub mod r_tests {"));
+        assert!(tests_rust.contains("pub const FIXTURE_USER: &str = \"bob\""));
+        assert!(!tests_rust.contains("APP_NAME"));
+        assert!(artifacts.rust.contains(&tests_rust));
+        assert!(artifacts.rust.contains("&super::r_tests::FIXTURE_USER"));
+        assert!(!artifacts.rust.contains("&super::r::FIXTURE_USER"));
 
-        // Only the first duplicate should be generated (priority)
-        // The second one should NOT be generated
-        assert!(artifacts.rust.contains("\"First\"")); // First file is priority
-        assert!(!artifacts.rust.contains("TITLE_VALUES1")); // No suffix for duplicates
-        assert!(!artifacts.rust.contains("TITLE_VALUES2")); // No suffix for duplicates
+        plan.embed_test_resources = false;
+        let artifacts = build_with_plan(&plan).expect("build succeeds");
+        assert!(artifacts.tests_rust.is_some());
+        assert!(!artifacts.rust.contains("r_tests"));
+        assert!(!artifacts.rust.contains("FIXTURE_USER"));
+
+        plan.tests_resources_dir = None;
+        let artifacts = build_with_plan(&plan).expect("build succeeds");
+        assert!(artifacts.tests_rust.is_none());
     }
 
     #[test]
-    fn build_with_duplicates_as_errors_fails() {
+    fn visibility_applies_to_items_and_modules() {
         let tmp = tempdir().unwrap();
         let res_dir = tmp.path().join("res");
         write_file(
-            &res_dir.join("values1.xml"),
-            r#"<resources><string name="title">First</string></resources>"#,
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="app_name">App</string>
+                <string name="internal_salt" visibility="crate">s</string>
+                <ns name="internal" visibility="crate">
+                    <color name="accent">#3366FF</color>
+                    <template name="greet" visibility="super">
+                        <string name="who"/>Hi {who}
+                    </template>
+                </ns>
+            </resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir, None, "debug");
+        plan.emit_smoke_tests = true;
+        let artifacts = build_with_plan(&plan).expect("build succeeds");
+        let rust = &artifacts.rust;
+
+        assert!(rust.contains("    pub const APP_NAME: &str"));
+        assert!(rust.contains("    pub(crate) const INTERNAL_SALT: &str"));
+        assert!(rust.contains("    pub(crate) mod internal {"));
+        assert!(rust.contains("        pub(crate) const ACCENT: &str"));
+        assert!(rust.contains(
+            "        pub(crate) const ACCENT_COLOR: r_resources::Color"
+        ));
+        assert!(rust.contains("        pub(super) fn greet("));
+        // Restricted items stay out of the root prelude's `pub use`
+        assert!(!rust.contains("pub use super::internal::ACCENT;"));
+        assert!(rust.contains("&super::r::internal::ACCENT_COLOR"));
+        assert!(!rust.contains("super::r::internal::greet("));
+    }
+
+    #[test]
+    fn feature_gated_namespaces_get_cfg_attributes() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="app_name">App</string>
+                <ns name="premium" feature="premium">
+                    <string name="banner">Gold</string>
+                </ns>
+            </resources>"#,
         );
+        let mut plan = BuildPlan::new(res_dir, None, "debug");
+        plan.emit_smoke_tests = true;
+        plan.emit_to_json = true;
+        let artifacts = build_with_plan(&plan).expect("build succeeds");
+        let rust = &artifacts.rust;
+
+        assert!(rust.contains(
+            "    #[cfg(feature = \"premium\")]\n    pub mod premium {"
+        ));
+        assert!(rust.contains("        pub const BANNER: &str"));
+        // Nothing outside the module may name its items
+        assert!(!rust.contains("super::premium::BANNER"));
+        assert!(!rust.contains("r::premium::BANNER"));
+        assert!(!rust.contains("Gold\\\""));
+    }
+
+    #[test]
+    fn error_enum_pairs_messages_with_codes() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
         write_file(
-            &res_dir.join("values2.xml"),
-            r#"<resources><string name="title">Second</string></resources>"#,
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="app_name">App</string>
+                <ns name="api">
+                    <ns name="error" error-enum="ApiError">
+                        <string name="unauthorized">Please "sign in"</string>
+                        <number name="unauthorized_code" type="u16">401</number>
+                        <string name="token_expired">Session expired</string>
+                        <number name="token_expired_code">401</number>
+                        <string name="not_found">Not found</string>
+                        <number name="not_found_code">404</number>
+                        <number name="retry_after">30</number>
+                    </ns>
+                </ns>
+            </resources>"#,
         );
         let plan = BuildPlan::new(res_dir, None, "debug");
-        let options = analysis::ValidationOptions {
-            treat_duplicates_as_errors: true,
-        };
-        let result = build_with_plan_and_options(&plan, options);
+        let rust = build_with_plan(&plan).unwrap().rust;
+        assert!(rust.contains(
+            "                /// 404: Not found\n                NotFound,\n"
+        ), "{rust}");
 
-        // Should fail with error
-        assert!(result.is_err());
-        if let Err(BuildError::Analysis(errors)) = result {
-            assert!(!errors.is_empty());
-            assert!(errors
+        let checks = r#"
+#[test]
+fn error_enum() {
+    use r::api::error::ApiError;
+    assert_eq!(
+        ApiError::ALL,
+        [ApiError::NotFound, ApiError::TokenExpired, ApiError::Unauthorized]
+    );
+    assert_eq!(ApiError::NotFound.code(), 404);
+    assert_eq!(ApiError::Unauthorized.message(), "Please \"sign in\"");
+    assert_eq!(ApiError::TokenExpired.to_string(), r::api::error::TOKEN_EXPIRED);
+    assert_eq!(ApiError::from_code(404), Some(ApiError::NotFound));
+    // Shared codes map to the first error in key order
+    assert_eq!(ApiError::from_code(401), Some(ApiError::TokenExpired));
+    assert_eq!(ApiError::from_code(500), None);
+    let error: Box<dyn std::error::Error> = Box::new(ApiError::NotFound);
+    assert_eq!(error.to_string(), "Not found");
+}
+"#;
+        run_generated_tests(tmp.path(), "error_enum", &format!("{rust}{checks}"));
+    }
+
+    #[test]
+    fn error_enum_with_incomplete_pair_fails() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <ns name="error" error-enum="AppError">
+                    <string name="offline">Offline</string>
+                    <number name="offline_code">503</number>
+                    <string name="busy">Busy</string>
+                </ns>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("Expected Analysis error");
+        };
+        assert_eq!(errors[0].code, "RR0090");
+        assert!(errors[0].message.starts_with("'error/busy' ("));
+        assert!(errors[0].message.contains(
+            "is the message of an incomplete pair of error-enum 'AppError': number 'busy_code' is missing"
+        ));
+    }
+
+    #[test]
+    fn doc_attributes_become_doc_comments() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <ns name="auth" doc="Sign-in screens.&#10;&#10;Shown before the session exists.">
+                    <string name="cta" doc="Main call-to-action button label">Go</string>
+                    <ns name="errors" doc="Messages under the form">
+                        <template name="locked" doc="Account locked">
+                            <number name="minutes"/>Retry in {minutes} min
+                        </template>
+                    </ns>
+                </ns>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let artifacts = build_with_plan(&plan).expect("build succeeds");
+        let rust = &artifacts.rust;
+
+        assert!(rust.contains(
+            "    pub mod auth {\n        \
+             //! Sign-in screens.\n        \
+             //!\n        \
+             //! Shown before the session exists.\n"
+        ));
+        assert!(rust.contains(
+            "        pub mod errors {\n            \
+             //! Messages under the form\n"
+        ));
+        assert!(rust.contains(
+            "        /// Main call-to-action button label\n        \
+             pub const CTA: &str"
+        ));
+        let locked = rust.find("/// Account locked").unwrap();
+        assert!(rust[locked..]
+            .lines()
+            .find(|line| !line.trim_start().starts_with("///"))
+            .is_some_and(|line| line.contains("pub fn locked(")));
+    }
+
+    #[test]
+    fn build_with_latin1_files_transcodes_them() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        std::fs::create_dir_all(&res_dir).unwrap();
+        std::fs::write(
+            res_dir.join("values.xml"),
+            b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?>\n\
+              <resources><string name=\"menu\">Caf\xE9 cr\xE8me \x80</string></resources>",
+        )
+        .unwrap();
+        // Without a declaration, read with the plan's default encoding
+        std::fs::write(
+            res_dir.join("values_legacy.xml"),
+            b"<resources><string name=\"farewell\">\xC0 bient\xF4t</string></resources>",
+        )
+        .unwrap();
+        let mut plan = BuildPlan::new(res_dir, None, "debug");
+        let Err(BuildError::Pipeline(err)) = build_with_plan(&plan) else {
+            panic!("expected the undeclared Latin-1 file to fail");
+        };
+        let message = err.to_string();
+        assert!(message.contains("values_legacy.xml"), "{message}");
+        assert!(message.contains("default_encoding"), "{message}");
+
+        plan.default_encoding = Some("windows-1252".to_string());
+        let rust = build_with_plan(&plan).unwrap().rust;
+        assert!(rust.contains("pub const MENU: &str = \"Café crème €\";"), "{rust}");
+        assert!(rust.contains("pub const FAREWELL: &str = \"À bientôt\";"), "{rust}");
+    }
+
+    #[test]
+    fn build_with_unknown_encoding_names_it_and_the_file() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<?xml version="1.0" encoding="EBCDIC-US"?><resources/>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+
+        let Err(BuildError::Pipeline(err)) = build_with_plan(&plan) else {
+            panic!("expected the unknown encoding to fail");
+        };
+        let message = err.to_string();
+        assert!(message.contains("res/values.xml"), "{message}");
+        assert!(message.contains("'EBCDIC-US'"), "{message}");
+    }
+
+    #[test]
+    fn build_with_quoted_and_preserved_strings_keeps_whitespace() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="space" quoted="true">" "</string>
+                <string name="footer" xml:space="preserve"><![CDATA[Bye
+]]></string>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+
+        let rust = build_with_plan(&plan).unwrap().rust;
+        assert!(rust.contains("pub const SPACE: &str = \" \";"), "{rust}");
+        assert!(rust.contains("pub const FOOTER: &str = \"Bye\\n\";"), "{rust}");
+    }
+
+    #[test]
+    fn build_with_enums_generates_types() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <enum name="priority" repr="u8">
+                    <item value="0">low</item>
+                    <item value="10">high</item>
+                </enum>
+                <ns name="ui">
+                    <enum name="theme"><item>light</item></enum>
+                </ns>
+            </resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        plan.emit_smoke_tests = true;
+        let artifacts = build_with_plan(&plan).expect("build succeeds");
+        let rust = &artifacts.rust;
+
+        assert!(rust.contains("    #[repr(u8)]\n    pub enum Priority {"));
+        assert!(rust.contains("    impl TryFrom<u8> for Priority {"));
+        assert!(rust.contains("        pub enum Theme {"));
+        assert!(rust.contains("pub use super::ui::Theme;"));
+        assert!(rust.contains("std::any::type_name::<super::r::ui::Theme>()"));
+
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <enum name="priority" repr="u8">
+                    <item value="1">low</item>
+                    <item value="1">lowest</item>
+                </enum>
+            </resources>"#,
+        );
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("colliding discriminants must fail the build");
+        };
+        assert!(errors[0].message.contains("'priority'"));
+        assert!(errors[0]
+            .message
+            .contains("items 'low' and 'lowest' share the discriminant 1"));
+    }
+
+    #[test]
+    fn build_with_arrays_generates_len_and_lookup() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string-array name="langs" sorted="true">
+                    <item>fr</item>
+                    <item>de</item>
+                </string-array>
+                <int-array name="sizes"><item>4</item><item>8</item></int-array>
+                <int-array name="level">
+                    <row><item>0</item><item>1</item></row>
+                    <row><item>1</item><item>0</item></row>
+                </int-array>
+            </resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        plan.emit_smoke_tests = true;
+        let artifacts = build_with_plan(&plan).expect("build succeeds");
+        let rust = &artifacts.rust;
+
+        assert!(
+            rust.contains("pub const LANGS: &[&str] = &[\"de\", \"fr\"];")
+        );
+        assert!(rust.contains("pub const LANGS_LEN: usize = 2;"));
+        assert!(rust.contains("pub fn langs_contains(s: &str) -> bool {"));
+        assert!(rust.contains("pub const SIZES: &[i64] = &[4, 8];"));
+        assert!(!rust.contains("sizes_contains"));
+        assert!(rust.contains(
+            "pub const LEVEL: [[i64; 2]; 2] = [[0, 1], [1, 0]];"
+        ));
+
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <int-array name="sizes"><item>4</item><item>x</item></int-array>
+            </resources>"#,
+        );
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("a bad item must fail the build");
+        };
+        assert!(errors[0].message.contains("'sizes'"));
+        assert!(errors[0].message.contains("item 1: 'x' is not an integer"));
+    }
+
+    #[test]
+    fn build_with_array_item_references_inlines_values() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <ns name="api">
+                    <string name="unauthorized">Please sign in</string>
+                </ns>
+                <string-array name="error_summaries">
+                    <item>@string/api/unauthorized</item>
+                    <item>Custom text</item>
+                </string-array>
+            </resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        plan.emit_to_json = true;
+        let artifacts = build_with_plan(&plan).expect("build succeeds");
+
+        assert!(artifacts.rust.contains(
+            "pub const ERROR_SUMMARIES: &[&str] = \
+             &[\"Please sign in\", \"Custom text\"];"
+        ));
+        assert!(artifacts
+            .rust
+            .contains(r#"[\"Please sign in\",\"Custom text\"]"#));
+
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string-array name="error_summaries">
+                    <item>Custom text</item>
+                    <item>@string/api/missing</item>
+                </string-array>
+            </resources>"#,
+        );
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("an unresolved item must fail the build");
+        };
+        assert!(errors[0].message.contains(
+            "'@api/missing' in item 1 of array 'error_summaries'"
+        ));
+    }
+
+    #[test]
+    fn build_with_duration_array_reports_file_and_index() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <duration-array name="retry_backoff">
+                    <item>100ms</item>
+                    <item>1s</item>
+                </duration-array>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let artifacts = build_with_plan(&plan).expect("build succeeds");
+        assert!(artifacts.rust.contains(
+            "pub const RETRY_BACKOFF: &[core::time::Duration] = \
+             &[core::time::Duration::from_millis(100), \
+             core::time::Duration::from_secs(1)];"
+        ));
+
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <duration-array name="retry_backoff">
+                    <item>100ms</item>
+                    <item>5 seconds</item>
+                </duration-array>
+            </resources>"#,
+        );
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("a bad duration must fail the build");
+        };
+        let message = &errors[0].message;
+        assert!(message.contains("item 1: '5 seconds' is not a duration"));
+        assert!(message.contains("values.xml"), "{message}");
+    }
+
+    #[test]
+    fn build_with_sizes_checks_overflow() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <size name="max_upload">25MB</size>
+                <size name="chunk">1.5KiB</size>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let artifacts = build_with_plan(&plan).expect("build succeeds");
+        assert!(artifacts.rust.contains(
+            "/// 25MB (in bytes)\n    pub const MAX_UPLOAD: u64 = 25_000_000;"
+        ));
+        assert!(artifacts.rust.contains("pub const CHUNK: u64 = 1_536;"));
+
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <size name="max_upload">20000000000GiB</size>
+            </resources>"#,
+        );
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("an overflowing size must fail the build");
+        };
+        assert!(errors[0]
+            .message
+            .contains("'20000000000GiB' does not fit in u64 bytes"));
+    }
+
+    #[test]
+    fn build_with_build_version_exposes_builtin_namespace() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="display_version" ref="@build/version"/>
+                <string name="about">Demo @build/version</string>
+                <version name="min_supported">1.2.0</version>
+            </resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        plan.build_version = Some("1.4.2".to_string());
+        let artifacts = build_with_plan(&plan).expect("build succeeds");
+        let rust = &artifacts.rust;
+        assert!(rust.contains("pub mod build"), "{rust}");
+        assert!(rust.contains("pub const VERSION: &str = \"1.4.2\";"));
+        assert!(rust.contains("pub const DISPLAY_VERSION: &str = \"1.4.2\";"));
+        assert!(rust.contains("pub const ABOUT: &str = \"Demo 1.4.2\";"));
+        assert!(rust.contains(
+            "pub const MIN_SUPPORTED: r_resources::Version = \
+             r_resources::Version::new(1, 2, 0);"
+        ));
+
+        // Opt-in: without a version the reference has nothing to resolve to
+        plan.build_version = None;
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("@build/version without a build version must fail");
+        };
+        assert!(errors
+            .iter()
+            .all(|error| error.code == analysis::codes::UNRESOLVED_REFERENCE));
+    }
+
+    #[test]
+    fn build_keeps_cdata_and_entities_through_profile_filtering() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <!-- Landing page -->
+            <resources>
+                <string name="intro"><![CDATA[<b>Hi</b> & bye]]></string>
+                <string name="legal">Fish &amp; Chips</string>
+                <string name="api" profile="release">https://example.com</string>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let artifacts = build_with_plan(&plan).expect("build succeeds");
+        let rust = &artifacts.rust;
+        assert!(rust.contains(r#"pub const INTRO: &str = "<b>Hi</b> & bye";"#));
+        assert!(rust.contains(r#"pub const LEGAL: &str = "Fish & Chips";"#));
+        assert!(!rust.contains("API"), "{rust}");
+    }
+
+    #[test]
+    fn build_notes_resources_left_out_by_profile() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="debug_endpoint" profile="debug">dev</string>
+                <string name="endpoint">prod</string>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "release");
+        let output = pipeline::build_graph(&plan).expect("pipeline succeeds");
+        assert_eq!(
+            output.analysis_result.notes,
+            ["<string> 'debug_endpoint' (res/values.xml) skipped \
+              (profile=\"debug\", active profile release)"]
+        );
+        assert!(output.analysis_result.warnings.is_empty());
+
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let output = pipeline::build_graph(&plan).expect("pipeline succeeds");
+        assert!(output.analysis_result.notes.is_empty());
+    }
+
+    #[test]
+    fn build_notes_hidden_files_unless_included() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources><string name="title">Home</string></resources>"#,
+        );
+        write_file(
+            &res_dir.join(".#values.xml"),
+            r#"<resources><string name="title">Draft</string></resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir, None, "debug");
+        let output = pipeline::build_graph(&plan).expect("pipeline succeeds");
+        let hidden = std::path::Path::new("res").join(".#values.xml");
+        assert_eq!(
+            output.analysis_result.notes,
+            [format!(
+                "{} skipped: hidden file (set \
+                 `BuildPlan::include_hidden_files` to load it)",
+                hidden.display()
+            )]
+        );
+        assert!(output.analysis_result.errors.is_empty());
+
+        // Loaded, the editor copy clashes with the real file
+        plan.include_hidden_files = true;
+        let output = pipeline::build_graph(&plan).expect("pipeline succeeds");
+        assert!(output.analysis_result.notes.is_empty());
+        assert!(!output.analysis_result.warnings.is_empty());
+    }
+
+    #[test]
+    fn build_reads_escaped_attributes_after_profile_filtering() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="debug_only" profile="debug">dev</string>
+                <ns name="a&amp;b" visibility='crate'>
+                    <string name="q" note="say &quot;hi&quot;">Q</string>
+                    <string name="s" note='say "hi"'>S</string>
+                </ns>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "release");
+        let output = pipeline::build_graph(&plan).expect("pipeline succeeds");
+        let graph = &output.graph;
+        let note = |path: &str| {
+            let node = graph.get(&ir::ResourceKey::from_path(path)).unwrap();
+            node.attributes.get("note").cloned()
+        };
+        assert_eq!(note("a&b/q").as_deref(), Some("say \"hi\""));
+        assert_eq!(note("a&b/s").as_deref(), Some("say \"hi\""));
+        assert!(graph.get(&ir::ResourceKey::from_path("debug_only")).is_none());
+    }
+
+    #[test]
+    fn build_errors_quote_the_original_xml() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        // The release-only string is dropped by preprocessing, which also
+        // reflows the file; the snippet still quotes the file on disk
+        write_file(
+            &res_dir.join("values.xml"),
+            "<resources>\n\
+             <!-- dates -->\n\
+             <string name=\"api\" profile=\"release\">prod</string>\n\
+             <ns name=\"promo\">\n\
+             \x20   <date name=\"launch\">2025-02-30</date>\n\
+             </ns>\n\
+             </resources>\n",
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("an impossible date must fail the build");
+        };
+        let snippet = errors[0].snippet.as_deref().expect("a snippet");
+        let lines: Vec<&str> = snippet.lines().collect();
+        assert!(lines[0].ends_with("values.xml:5:5"), "{snippet}");
+        assert_eq!(lines[2], "5 |     <date name=\"launch\">2025-02-30</date>");
+        assert_eq!(lines[3], "  |     ^^^^^");
+    }
+
+    #[test]
+    fn build_with_dates_rejects_out_of_range_values() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <datetime name="promo_ends">2025-12-31T23:59:59Z</datetime>
+                <date name="launch">2025-06-01</date>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let artifacts = build_with_plan(&plan).expect("build succeeds");
+        assert!(artifacts
+            .rust
+            .contains("pub const PROMO_ENDS: i64 = 1_767_225_599;"));
+        assert!(artifacts.rust.contains(
+            "pub const LAUNCH: r_resources::Date = \
+             r_resources::Date::new(2025, 6, 1);"
+        ));
+
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <date name="launch">2025-02-30</date>
+                <datetime name="promo_ends">2025-12-31 23:59</datetime>
+            </resources>"#,
+        );
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("bad dates must fail the build");
+        };
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|e| e.message.contains("values.xml")));
+        assert!(errors.iter().any(|e| e
+            .message
+            .contains("'2025-02-30' has day 30, but that month has 28 days")));
+        assert!(errors.iter().any(|e| e
+            .message
+            .contains("'2025-12-31 23:59' is not an RFC 3339 date-time")));
+    }
+
+    #[test]
+    fn build_without_resources_dir_depends_on_plan() {
+        let tmp = tempdir().unwrap();
+        let mut plan =
+            BuildPlan::new(tmp.path().join("res"), None, "debug");
+        assert!(matches!(
+            build_with_plan(&plan),
+            Err(BuildError::Pipeline(_))
+        ));
+
+        plan.allow_missing_resources = true;
+        let artifacts = build_with_plan(&plan).expect("empty artifact");
+        assert!(artifacts.rust.contains("pub struct R;"));
+        assert!(artifacts.rust.contains("pub mod r {"));
+        assert_eq!(artifacts.warnings.len(), 1);
+        assert_eq!(artifacts.warnings[0].code, analysis::codes::NO_RESOURCES);
+        assert!(artifacts.warnings[0].message.contains("does not exist"));
+    }
+
+    #[test]
+    fn invisible_characters_are_reported_unless_allowed() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <ns name="checkout">
+                    <string name="pay">Pay&#x200B;now</string>
+                    <string name="family" allow-invisible="true">👩&#x200D;👧</string>
+                    <template name="total"><number name="amount" type="f64"/>Total:&#xA0;{amount}</template>
+                </ns>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let artifacts = build_with_plan(&plan).unwrap();
+        let invisible: Vec<&analysis::AnalysisWarning> = artifacts
+            .warnings
+            .iter()
+            .filter(|w| w.code == analysis::codes::INVISIBLE_CHARACTER)
+            .collect();
+        assert_eq!(invisible.len(), 1, "{:?}", artifacts.warnings);
+        assert_eq!(
+            invisible[0].key,
+            Some(ir::ResourceKey::from_path("checkout/pay"))
+        );
+        assert!(invisible[0]
+            .message
+            .contains("U+200B zero-width space at character 4"));
+
+        let options = analysis::ValidationOptions {
+            flag_nbsp: true,
+            ..Default::default()
+        };
+        let artifacts = build_with_plan_and_options(&plan, options).unwrap();
+        assert!(artifacts.warnings.iter().any(|w| w.message.contains("'checkout/total'")
+            && w.message.contains("U+00A0 no-break space at character 7")));
+    }
+
+    #[test]
+    fn transparent_and_short_colors_are_reported() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("colors.xml"),
+            r##"<resources>
+                <ns name="button">
+                    <color name="background">#00FF5722</color>
+                    <color name="transparent_bg">#00000000</color>
+                    <color name="scrim" allow-transparent="true">#0000</color>
+                    <color name="accent">#F53</color>
+                </ns>
+            </resources>"##,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let color_warnings = |options| {
+            let artifacts =
+                build_with_plan_and_options(&plan, options).unwrap();
+            artifacts
+                .rendered_warnings()
+                .into_iter()
+                .filter(|w| w.contains("Color '"))
+                .collect::<Vec<_>>()
+        };
+
+        let warnings = color_warnings(Default::default());
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+        assert!(warnings[0].starts_with(
+            "[RR0064] Color 'button/background' (res/colors.xml) is '#00FF5722', whose alpha is 0"
+        ));
+
+        let warnings = color_warnings(analysis::ValidationOptions {
+            canonical_colors: true,
+            ..Default::default()
+        });
+        assert_eq!(warnings.len(), 3, "{warnings:?}");
+        assert!(warnings.contains(
+            &"[RR0065] Color 'button/accent' (res/colors.xml) is written '#F53'; write it '#FF5533'".to_string()
+        ));
+        assert!(warnings.iter().any(|w| w.contains("'#0000'; write it '#00000000'")));
+    }
+
+    #[test]
+    fn overrides_replace_values_before_analysis() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r##"<resources>
+                <ns name="brand">
+                    <string name="name">Acme</string>
+                    <color name="primary">#FF5722</color>
+                    <percent name="discount">10%</percent>
+                    <string name="tagline">Made by @string/brand/name</string>
+                </ns>
+            </resources>"##,
+        );
+        let mut plan = BuildPlan::new(res_dir, None, "debug");
+        plan.overrides = vec![
+            ("brand/name".to_string(), "Globex @string/x".to_string()),
+            ("brand/primary".to_string(), "#00336699".to_string()),
+        ];
+
+        let artifacts = build_with_plan(&plan).unwrap();
+        let rust = &artifacts.rust;
+        assert!(rust.contains("pub const NAME: &str = \"Globex @string/x\";"));
+        assert!(rust.contains("pub const TAGLINE: &str = \"Made by Globex @string/x\";"));
+        assert!(!rust.contains("Acme"));
+        assert!(artifacts.warnings.iter().any(|w| {
+            w.code == analysis::codes::TRANSPARENT_COLOR
+                && w.file.as_deref()
+                    == Some(std::path::Path::new(parsing::OVERRIDE_FILE))
+                && w.message.starts_with(
+                    "Color 'brand/primary' (<override>) is '#00336699'",
+                )
+        }));
+
+        plan.overrides = vec![
+            ("brand/nmae".to_string(), "Globex".to_string()),
+            ("brand/discount".to_string(), "abc".to_string()),
+        ];
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan) else {
+            panic!("Expected Analysis error");
+        };
+        assert!(errors.iter().any(|e| e.message
+            == "Override of 'brand/nmae' matches no resource; close keys: brand/name"));
+        assert!(errors.iter().any(|e| e.message.contains("'brand/discount' (<override>)")));
+    }
+
+    #[test]
+    fn overrides_in_gated_namespaces_do_not_warn() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <ns name="beta" feature="beta-ui">
+                    <string name="title">Beta</string>
+                    <string name="banner">Try it</string>
+                </ns>
+            </resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir, None, "debug");
+        plan.overrides = vec![("beta/title".to_string(), "Preview".to_string())];
+        let options = analysis::ValidationOptions {
+            explicit_namespace_extension: true,
+            ..Default::default()
+        };
+
+        let artifacts = build_with_plan_and_options(&plan, options).unwrap();
+        assert!(artifacts.rust.contains("pub const TITLE: &str = \"Preview\";"));
+        assert!(
+            artifacts.warnings.is_empty(),
+            "unexpected warnings: {:?}",
+            artifacts.warnings
+        );
+    }
+
+    #[test]
+    fn values_that_are_also_namespaces_warn_unless_denied() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="api">v2</string>
+                <ns name="api"><string name="base">https://example.com</string></ns>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let artifacts = build_with_plan(&plan).unwrap();
+        let leaf: Vec<&analysis::AnalysisWarning> = artifacts
+            .warnings
+            .iter()
+            .filter(|w| w.code == analysis::codes::LEAF_NAMESPACE)
+            .collect();
+        assert_eq!(leaf.len(), 1, "{:?}", artifacts.warnings);
+        assert!(leaf[0].message.contains("nest it in the namespace as 'api/_self'"));
+        assert!(artifacts.rust.contains("pub const API: &str = \"v2\";"));
+
+        let options = analysis::ValidationOptions {
+            deny: vec![analysis::codes::LEAF_NAMESPACE.to_string()],
+            ..Default::default()
+        };
+        let Err(BuildError::Analysis(errors)) =
+            build_with_plan_and_options(&plan, options)
+        else {
+            panic!("Expected Analysis error");
+        };
+        assert_eq!(errors[0].code, analysis::codes::LEAF_NAMESPACE);
+    }
+
+    #[test]
+    fn build_with_duplicates_as_errors_fails() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values1.xml"),
+            r#"<resources><string name="title">First</string></resources>"#,
+        );
+        write_file(
+            &res_dir.join("values2.xml"),
+            r#"<resources><string name="title">Second</string></resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let options = analysis::ValidationOptions {
+            treat_duplicates_as_errors: true,
+            ..Default::default()
+        };
+        let result = build_with_plan_and_options(&plan, options);
+
+        // Should fail with error
+        assert!(result.is_err());
+        if let Err(BuildError::Analysis(errors)) = result {
+            assert!(!errors.is_empty());
+            assert!(errors
                 .iter()
                 .any(|e| e.message.contains("Duplicate")));
         } else {
             panic!("Expected Analysis error");
         }
     }
+    #[test]
+    fn build_with_graph_merges_extra_resources() {
+        use ir::{ResourceKey, ResourceKind, ResourceNode, ResourceValue};
+
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="title">Demo</string>
+                <string name="footer">Schema @string/db/schema</string>
+            </resources>"#,
+        );
+        let origin = || ir::ResourceOrigin::new("<database>".into(), false);
+        let mut extra = ir::ResourceGraph::default();
+        extra.insert(
+            ResourceKey::from_path("db/schema"),
+            ResourceNode::new(
+                ResourceKind::String,
+                ResourceValue::String("v7".to_string()),
+                origin(),
+            ),
+        );
+        extra.insert(
+            ResourceKey::from_path("db/app_title"),
+            ResourceNode::new(
+                ResourceKind::String,
+                ResourceValue::Reference {
+                    target: ResourceKey::from_path("title"),
+                    kind: None,
+                },
+                origin(),
+            ),
+        );
+        extra.insert(
+            ResourceKey::from_path("title"),
+            ResourceNode::new(
+                ResourceKind::String,
+                ResourceValue::String("Other".to_string()),
+                origin(),
+            ),
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+
+        let artifacts = build_with_graph(&plan, extra).unwrap();
+        let rust = &artifacts.rust;
+        assert!(rust.contains("pub mod db {"));
+        assert!(rust.contains("pub const SCHEMA: &str = \"v7\";"));
+        assert!(rust.contains("pub const APP_TITLE: &str = \"Demo\";"));
+        assert!(rust.contains("pub const FOOTER: &str = \"Schema v7\";"));
+        // The XML definition comes first and wins
+        assert!(rust.contains("pub const TITLE: &str = \"Demo\";"));
+        assert!(artifacts
+            .warnings
+            .iter()
+            .any(|w| w.message.contains("title")
+                && w.message.contains("<database>")));
+    }
+
+    #[test]
+    fn build_with_graph_and_from_parsed_apply_the_warning_budget() {
+        use parsing::{ParsedResource, ParsedResourceFile};
+
+        let options = analysis::ValidationOptions {
+            max_warnings: Some(0),
+            ..Default::default()
+        };
+        let budget_exceeded = |result: Result<_, BuildError>| match result {
+            Err(BuildError::Analysis(errors)) => {
+                errors.iter().any(|e| e.code == "RR0005")
+            }
+            _ => false,
+        };
+
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources><string name="title">Demo</string></resources>"#,
+        );
+        let extra = || {
+            let mut extra = ir::ResourceGraph::default();
+            extra.insert(
+                ir::ResourceKey::from_path("title"),
+                ir::ResourceNode::new(
+                    ir::ResourceKind::String,
+                    ir::ResourceValue::String("Other".to_string()),
+                    ir::ResourceOrigin::new("<database>".into(), false),
+                ),
+            );
+            extra
+        };
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        assert!(build_with_graph(&plan, extra()).is_ok());
+        assert!(budget_exceeded(build_with_graph_and_options(
+            &plan,
+            extra(),
+            options.clone(),
+        )));
+
+        let files = || {
+            vec![ParsedResourceFile::new(
+                "rows.csv".into(),
+                false,
+                vec![
+                    ParsedResource::string("title", "Demo"),
+                    ParsedResource::string("title", "Other"),
+                ],
+            )]
+        };
+        assert!(build_from_parsed(files(), &plan).is_ok());
+        assert!(budget_exceeded(build_from_parsed_with_options(
+            files(),
+            &plan,
+            options,
+        )));
+    }
+
+    #[test]
+    fn build_from_parsed_validates_without_files_on_disk() {
+        use parsing::{ParsedResource, ParsedResourceFile};
+
+        let files = vec![ParsedResourceFile::new(
+            "rows.csv".into(),
+            false,
+            vec![
+                ParsedResource::string("title", "Demo"),
+                ParsedResource::number("limit", "ten", None),
+            ],
+        )];
+        let plan = BuildPlan::new("does-not-exist".into(), None, "debug");
+        let Err(BuildError::Analysis(errors)) = build_from_parsed(files, &plan)
+        else {
+            panic!("expected analysis errors");
+        };
+        assert_eq!(errors.len(), 1);
+        let message = &errors[0].message;
+        assert!(message.contains("'limit' (rows.csv)"), "{message}");
+    }
+
+    #[test]
+    fn build_without_depth_limit_emits_deep_trees() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        let depth = 12;
+        let open: String =
+            (0..depth).map(|i| format!("<ns name=\"n{i}\">")).collect();
+        write_file(
+            &res_dir.join("values.xml"),
+            &format!(
+                "<resources>{open}<string name=\"leaf\">Deep</string>{}\
+                 </resources>",
+                "</ns>".repeat(depth)
+            ),
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan) else {
+            panic!("expected the default limit to reject the key");
+        };
+        assert_eq!(errors[0].code, analysis::codes::NAMESPACE_TOO_DEEP);
+
+        let options = analysis::ValidationOptions {
+            max_namespace_depth: None,
+            ..Default::default()
+        };
+        let rust = build_with_plan_and_options(&plan, options).unwrap().rust;
+        assert!(rust.contains("pub mod n11 {"));
+        assert!(rust.contains("pub const LEAF: &str = \"Deep\";"));
+    }
+
+    #[test]
+    fn build_resolves_relative_references() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <ns name="ui">
+                    <ns name="colors">
+                        <color name="primary">#3366FF</color>
+                        <color name="accent">@./primary</color>
+                    </ns>
+                    <ns name="dimens">
+                        <number name="padding">16</number>
+                        <number name="gutter">@../dimens/padding</number>
+                    </ns>
+                </ns>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let artifacts = build_with_plan(&plan).expect("build succeeds");
+
+        assert!(artifacts
+            .rust
+            .contains("pub const ACCENT: &str = \"#3366FF\";"));
+        assert!(artifacts.rust.contains("pub const GUTTER: i64 = 16;"));
+    }
+
+    #[test]
+    fn build_interpolates_numbers_and_bools() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <number name="max_retries">3</number>
+                <bool name="verbose">false</bool>
+                <ns name="net">
+                    <number name="timeout">2.5</number>
+                    <string name="retry_msg">Retrying up to @number/max_retries times</string>
+                    <string name="summary">@./timeout s, verbose: @bool/verbose</string>
+                </ns>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let artifacts = build_with_plan(&plan).expect("build succeeds");
+
+        assert!(artifacts.rust.contains(
+            "pub const RETRY_MSG: &str = \"Retrying up to 3 times\";"
+        ));
+        assert!(artifacts.rust.contains(
+            "pub const SUMMARY: &str = \"2.5 s, verbose: false\";"
+        ));
+    }
+
+    #[test]
+    fn build_with_template_interpolation_fails() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <template name="greeting">Hello {name}!</template>
+                <string name="banner">Say @template/greeting</string>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("Expected Analysis error");
+        };
+        assert!(errors[0].message.contains("template 'greeting'"));
+    }
+
+    #[test]
+    fn build_with_attribute_templates_emits_functions() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="greeting" template="Hello {name}, you have {count} messages!">
+                    <param name="name" type="string"/>
+                    <param name="count" type="int"/>
+                </string>
+                <template name="farewell"><string name="name"/>Bye {name}</template>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let code = build_with_plan(&plan).unwrap().rust;
+        assert!(code.contains(
+            "pub fn greeting(name: &str, count: i64) -> String {\n        \
+             format!(\"Hello {0}, you have {1} messages!\", name, count)"
+        ));
+        assert!(code.contains("pub fn farewell(name: &str) -> String"));
+    }
+
+    #[test]
+    fn build_with_template_builders_compiles_and_formats() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <ns name="inbox">
+                    <template name="welcome_message">
+                        <string name="name"/>
+                        <number name="count"/>
+                        Welcome to {name}, you have {count} messages!
+                    </template>
+                </ns>
+                <template name="farewell" style="plain">
+                    <string name="name"/>Bye {name}
+                </template>
+                <template name="total"><number name="n"/>{n} items</template>
+            </resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir, None, "debug");
+        plan.template_builders = true;
+        plan.emit_smoke_tests = true;
+        let rust = build_with_plan(&plan).unwrap().rust;
+        assert!(!rust.contains("FarewellArgs"), "{rust}");
+        // Without a borrowed argument the struct needs no lifetime
+        assert!(rust.contains("pub struct TotalArgs {"), "{rust}");
+
+        let checks = r#"
+#[test]
+fn named_arguments() {
+    use r::inbox::*;
+    let expected = "Welcome to Acme, you have 3 messages!";
+    assert_eq!(welcome_message("Acme", 3), expected);
+    let built = welcome_message_args().name("Acme").count(3).build();
+    assert_eq!(built, expected);
+    let args = WelcomeMessageArgs { name: "Acme", count: 3 };
+    assert_eq!(welcome_message_with(args), expected);
+    assert_eq!(r::total_args().n(2).build(), "2 items");
+}
+"#;
+        run_generated_tests(tmp.path(), "templates", &format!("{rust}{checks}"));
+    }
+
+    #[test]
+    fn templates_call_other_templates() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <template name="signature">The Acme team</template>
+                <ns name="mail">
+                    <template name="footer">
+                        <string name="name"/>
+                        <string name="company"/>
+                        Sent to {name} by {company}. {@signature}
+                    </template>
+                    <template name="full_email">
+                        <string name="name"/>
+                        Hello {name}!
+{@./footer}
+                    </template>
+                </ns>
+                <ns name="onboarding">
+                    <template name="welcome"><string name="name"/>{@mail/full_email}</template>
+                </ns>
+                <ns name="letters">
+                    <template name="braces">Use {x} or {{braces}}</template>
+                    <template name="bye">Bye {name}</template>
+                    <template name="dear"><string name="name"/>Dear {name}. {@./braces} {@./bye}</template>
+                </ns>
+            </resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir, None, "debug");
+        plan.emit_smoke_tests = true;
+        let rust = build_with_plan(&plan).unwrap().rust;
+        assert!(rust.contains(
+            "pub fn full_email(name: &str, footer_name: &str, company: &str) -> String {\n            \
+             format!(\"Hello {1}!\\n{0}\", footer(footer_name, company), name)"
+        ), "{rust}");
+        assert!(rust.contains(
+            "super::mail::full_email(full_email_name, footer_name, company)"
+        ));
+        assert!(rust.contains(
+            "format!(\"Dear {0}. Use {{x}} or {{{{braces}}}} Bye {{name}}\", name)"
+        ), "{rust}");
+
+        let checks = r#"
+#[test]
+fn nested_templates() {
+    assert_eq!(
+        r::mail::footer("Ann", "Acme"),
+        "Sent to Ann by Acme. The Acme team"
+    );
+    assert_eq!(
+        r::mail::full_email("Bob", "Ann", "Acme"),
+        "Hello Bob!\nSent to Ann by Acme. The Acme team"
+    );
+    assert_eq!(
+        r::onboarding::welcome("Bob", "Bob", "Ann", "Acme"),
+        "Hello Bob!\nSent to Ann by Acme. The Acme team"
+    );
+}
+
+#[test]
+fn inlined_braces_stay_text() {
+    assert_eq!(r::letters::BRACES, "Use {x} or {{braces}}");
+    assert_eq!(
+        r::letters::dear("Ann"),
+        "Dear Ann. Use {x} or {{braces}} Bye {name}"
+    );
+}
+"#;
+        // `welcome` declares a `name` of its own that it never uses
+        let code = format!("#![allow(unused_variables)]\n{rust}{checks}");
+        run_generated_tests(tmp.path(), "calls", &code);
+    }
+
+    #[test]
+    fn template_call_cycle_fails_with_the_chain() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <template name="header"><string name="a"/>{a} {@footer}</template>
+                <template name="footer">{@header}</template>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("Expected Analysis error");
+        };
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert_eq!(errors[0].code, "RR0011");
+        assert!(errors[0]
+            .message
+            .starts_with("Template call cycle detected: footer -> header -> footer ("));
+    }
+
+    #[test]
+    fn raw_resources_are_emitted_verbatim_once_allowed() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <ns name="net">
+                    <raw name="retry_policy" type="crate::RetryPolicy"><![CDATA[crate::RetryPolicy::exponential(3, 250)]]></raw>
+                    <raw name="backoff_ms" type="&amp;[u32]"><![CDATA[&[100, 200, 400]]]></raw>
+                </ns>
+                <string name="title">Demo</string>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("Expected Analysis error");
+        };
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert_eq!(errors[0].code, "RR0100");
+        assert!(errors[0].message.starts_with("'net/backoff_ms' ("));
+
+        let options = analysis::ValidationOptions {
+            allow_raw: true,
+            ..Default::default()
+        };
+        let mut plan = plan;
+        plan.emit_to_json = true;
+        let artifacts = build_with_plan_and_options(&plan, options).unwrap();
+        let rust = artifacts.rust;
+        assert!(rust.contains(
+            "pub const RETRY_POLICY: crate::RetryPolicy = \
+             crate::RetryPolicy::exponential(3, 250);"
+        ));
+        assert!(rust.contains("pub const BACKOFF_MS: &[u32] = &[100, 200, 400];"));
+
+        let checks = r##"
+#[derive(Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    attempts: u32,
+    base_ms: u32,
+}
+
+impl RetryPolicy {
+    pub const fn exponential(attempts: u32, base_ms: u32) -> Self {
+        Self { attempts, base_ms }
+    }
+}
+
+#[test]
+fn raw_constants() {
+    assert_eq!(r::net::RETRY_POLICY, RetryPolicy::exponential(3, 250));
+    assert_eq!(r::net::BACKOFF_MS, [100, 200, 400]);
+    // Rust code is not a value to export
+    assert_eq!(r::to_json(), r#"{"title":"Demo","net":{}}"#);
+}
+"##;
+        run_generated_tests(tmp.path(), "raw", &format!("{rust}{checks}"));
+    }
+
+    #[test]
+    fn build_with_plural_templates_picks_the_form() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <template name="inbox_summary"><number name="count"/>You have {count} {count:plural(message|messages)}!</template>
+                <template name="progress"><number name="done" type="f64"/>{done} {done:plural(step|steps)} done</template>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let rust = build_with_plan(&plan).unwrap().rust;
+
+        let checks = r#"
+#[test]
+fn plural_forms() {
+    assert_eq!(r::inbox_summary(1), "You have 1 message!");
+    assert_eq!(r::inbox_summary(2), "You have 2 messages!");
+    assert_eq!(r::inbox_summary(0), "You have 0 messages!");
+    assert_eq!(r::progress(1.0), "1 step done");
+    assert_eq!(r::progress(2.5), "2.5 steps done");
+}
+"#;
+        run_generated_tests(tmp.path(), "plurals", &format!("{rust}{checks}"));
+    }
+
+    #[test]
+    fn build_with_plural_without_forms_fails() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <template name="inbox"><number name="count"/>{count:plural()}</template>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let Err(err) = build_with_plan(&plan) else {
+            panic!("a plural without forms must fail the build");
+        };
+        let err = err.to_string();
+        assert!(err.contains("'{count:plural()}' has no forms"), "{err}");
+    }
+
+    #[test]
+    fn build_with_kind_modules_compiles_and_re_exports() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="app_name">Demo</string>
+                <ns name="auth">
+                    <string name="title">Sign in</string>
+                    <string name="heading">@string/auth/title</string>
+                    <bool name="remember">true</bool>
+                    <string-array name="providers">
+                        <item>github</item>
+                        <item>gitlab</item>
+                    </string-array>
+                </ns>
+                <ns name="internal" visibility="crate">
+                    <number name="retries">3</number>
+                </ns>
+            </resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir, None, "debug");
+        plan.emit_kind_modules = true;
+        let rust = build_with_plan(&plan).unwrap().rust;
+        // One definition in the kind module, re-exported by `r`
+        assert_eq!(rust.matches("pub const TITLE:").count(), 1, "{rust}");
+        assert!(rust.contains("\npub mod string {\n"), "{rust}");
+        assert!(
+            rust.contains("pub use super::super::string::auth::*;"),
+            "{rust}"
+        );
+
+        let checks = r#"
+#[test]
+fn kind_paths() {
+    assert_eq!(string::APP_NAME, r::APP_NAME);
+    assert_eq!(string::auth::TITLE, "Sign in");
+    assert_eq!(r::auth::TITLE, "Sign in");
+    assert_eq!(string::auth::HEADING, "Sign in");
+    assert!(bool::auth::REMEMBER);
+    assert!(r::auth::REMEMBER);
+    assert_eq!(string_array::auth::PROVIDERS, ["github", "gitlab"]);
+    assert_eq!(r::prelude::PROVIDERS, ["github", "gitlab"]);
+    assert_eq!(number::internal::RETRIES, 3);
+    assert_eq!(r::internal::RETRIES, 3);
+}
+"#;
+        run_generated_tests(tmp.path(), "kinds", &format!("{rust}{checks}"));
+    }
+
+    #[test]
+    fn group_by_origin_orders_items_by_file() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("auth.xml"),
+            r#"<resources><ns name="auth">
+                <string name="title">Sign in</string>
+                <string name="button">Go</string>
+            </ns></resources>"#,
+        );
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="app">Acme</string>
+                <ns name="auth"><string name="error">Denied</string></ns>
+            </resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir, None, "debug");
+        plan.emit_inputs = false;
+        let by_name = build_with_plan(&plan).unwrap().rust;
+        assert!(!by_name.contains("// from"), "{by_name}");
+        let order = |rust: &str, items: &[&str]| {
+            items
+                .iter()
+                .map(|item| rust.find(item).unwrap_or_else(|| panic!("{item}: {rust}")))
+                .collect::<Vec<_>>()
+                .windows(2)
+                .all(|pair| pair[0] < pair[1])
+        };
+        assert!(order(&by_name, &["BUTTON", "ERROR", "TITLE"]), "{by_name}");
+
+        plan.group_by_origin = true;
+        let grouped = build_with_plan(&plan).unwrap().rust;
+        let auth = "    pub mod auth {
+        // from res/auth.xml
+        pub const BUTTON: &str = \"Go\";
+        pub const TITLE: &str = \"Sign in\";
+        // from res/values.xml
+        pub const ERROR: &str = \"Denied\";
+";
+        assert!(grouped.contains(auth), "{grouped}");
+        assert!(
+            grouped.contains("    }\n    // from res/values.xml\n    pub const APP"),
+            "{grouped}"
+        );
+        // Only the order and the comments differ
+        let items = |rust: &str| {
+            let mut lines: Vec<String> = rust
+                .lines()
+                .filter(|line| !line.trim_start().starts_with("// from"))
+                .map(str::to_string)
+                .collect();
+            lines.sort();
+            lines
+        };
+        assert_eq!(items(&grouped), items(&by_name));
+    }
+
+    #[test]
+    fn build_with_interned_strings_keeps_every_value() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="brand">Acme</string>
+                <string name="ok">OK</string>
+                <string name="ok_lower">ok</string>
+                <ns name="dialog">
+                    <string name="accept">OK</string>
+                    <string name="title" max-len="16" as-bytes="true">Acme</string>
+                    <string name="heading">@string/brand</string>
+                </ns>
+                <ns name="internal" visibility="crate" feature="internal">
+                    <string name="vendor">Acme</string>
+                </ns>
+            </resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir, None, "debug");
+        plan.intern_strings = true;
+        let artifacts = build_with_plan(&plan).unwrap();
+        let rust = artifacts.rust;
+        assert_eq!(rust.matches("\"Acme\"").count(), 1, "{rust}");
+        assert_eq!(
+            artifacts.interned_strings,
+            generation::InternedStrings {
+                values: 2,
+                constants: 6
+            }
+        );
+
+        let checks = r#"
+#[test]
+fn interned_values() {
+    assert_eq!(r::BRAND, "Acme");
+    assert_eq!(r::OK, "OK");
+    assert_eq!(r::OK_LOWER, "ok");
+    assert_eq!(r::dialog::ACCEPT, "OK");
+    assert_eq!(r::dialog::TITLE, "Acme");
+    assert_eq!(r::dialog::TITLE_LEN, 4);
+    assert_eq!(r::dialog::TITLE_BYTES, b"Acme");
+    assert_eq!(r::dialog::HEADING, "Acme");
+}
+"#;
+        run_generated_tests(tmp.path(), "interned", &format!("{rust}{checks}"));
+    }
+
+    #[test]
+    fn build_with_naming_config_compiles_with_the_chosen_case() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="app_name" max-len="16">Demo</string>
+                <percent name="sidebarWidth">37.5%</percent>
+                <string-array name="langs" sorted="true">
+                    <item>fr</item>
+                    <item>en</item>
+                </string-array>
+                <ns name="ErrorCodes">
+                    <number name="not_found">404</number>
+                </ns>
+                <template name="greetUser" style="builder">
+                    <string name="name"/>
+                    Hello {name}
+                </template>
+            </resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir, None, "debug");
+        plan.naming = casing::NamingConfig {
+            const_case: casing::Case::Pascal,
+            module_case: casing::Case::Snake,
+            fn_case: casing::Case::Snake,
+        };
+        plan.emit_smoke_tests = true;
+        plan.emit_kind_modules = true;
+        let rust = build_with_plan(&plan).unwrap().rust;
+        assert!(rust.contains("pub const AppNameLen: usize = 4;"), "{rust}");
+
+        let checks = r#"
+#[test]
+fn cased_names() {
+    assert_eq!(r::AppName, "Demo");
+    assert_eq!(r::AppNameLen, 4);
+    assert_eq!(r::SidebarWidthRaw, "37.5%");
+    assert_eq!(r::LangsLen, 2);
+    assert!(r::langs_contains("fr"));
+    assert_eq!(r::error_codes::NotFound, 404);
+    assert_eq!(r::greet_user("Ada"), "Hello Ada");
+    assert_eq!(r::greet_user_args().name("Ada").build(), "Hello Ada");
+    assert_eq!(string::AppName, "Demo");
+}
+"#;
+        run_generated_tests(tmp.path(), "cased", &format!("{rust}{checks}"));
+    }
+
+    #[test]
+    fn preserved_names_compile_without_warnings() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <ns name="MyNs">
+                    <string name="title">Hello</string>
+                </ns>
+                <template name="greetUser">
+                    <string name="name"/>
+                    Hello {name}
+                </template>
+            </resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir, None, "debug");
+        plan.emit_smoke_tests = true;
+        let rust = build_with_plan(&plan).unwrap().rust;
+        assert!(rust.contains("#[allow(non_snake_case)]"), "{rust}");
+
+        let checks = r#"
+#[test]
+fn preserved_names() {
+    assert_eq!(r::MyNs::TITLE, "Hello");
+    assert_eq!(r::greetUser("Ada"), "Hello Ada");
+}
+"#;
+        run_generated_tests(tmp.path(), "preserved", &format!("{rust}{checks}"));
+    }
+
+    #[test]
+    fn naming_follows_the_environment() {
+        let vars = [
+            ("R_RESOURCES_CONST_CASE", "pascal"),
+            ("R_RESOURCES_FN_CASE", "Snake"),
+        ];
+        let naming = naming_from_env(|name| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| value.to_string())
+        });
+        assert_eq!(
+            naming,
+            casing::NamingConfig {
+                const_case: casing::Case::Pascal,
+                module_case: casing::Case::Preserve,
+                fn_case: casing::Case::Snake,
+            }
+        );
+        assert_eq!(naming_from_env(|_| None), casing::NamingConfig::default());
+    }
+
+    #[test]
+    fn build_with_template_params_sanitizes_names() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources><ns name="shop">
+                <template name="label">
+                    <string name="type"/>
+                    <number name="item-count"/>
+                    {item-count} x {type}
+                </template>
+            </ns></resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let code = build_with_plan(&plan).unwrap().rust;
+        assert!(code.contains(
+            "pub fn label(r#type: &str, item_count: i64) -> String"
+        ));
+        assert!(code.contains(r#"format!("{1} x {0}", r#type, item_count)"#));
+
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources><string name="welcome" template="{user-id}">
+                <param name="user-id"/>
+                <param name="user_id"/>
+            </string></resources>"#,
+        );
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan) else {
+            panic!("Expected Analysis error");
+        };
+        assert!(errors[0].message.contains("'welcome'"));
+        assert!(errors[0].message.contains("values.xml"));
+        assert!(errors[0]
+            .message
+            .contains("'user-id' and 'user_id' both become `user_id`"));
+    }
+
+    #[test]
+    fn build_emits_char_constants() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <char name="path_sep">/</char>
+                <char name="quote">'</char>
+                <char name="tab">\t</char>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let artifacts = build_with_plan(&plan).expect("build succeeds");
+
+        assert!(artifacts.rust.contains("pub const PATH_SEP: char = '/';"));
+        assert!(artifacts.rust.contains("pub const QUOTE: char = '\\'';"));
+        assert!(artifacts.rust.contains("pub const TAB: char = '\\t';"));
+    }
+
+    #[test]
+    fn build_emits_percent_ratios() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <percent name="sidebar_width">37.5%</percent>
+                <percent name="zoom" allow-over-100="true">150%</percent>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let artifacts = build_with_plan(&plan).expect("build succeeds");
+
+        assert!(artifacts
+            .rust
+            .contains("pub const SIDEBAR_WIDTH: f32 = 0.375;"));
+        assert!(artifacts
+            .rust
+            .contains("pub const SIDEBAR_WIDTH_RAW: &str = \"37.5%\";"));
+        assert!(artifacts.rust.contains("pub const ZOOM: f32 = 1.5;"));
+    }
+
+    #[test]
+    fn build_with_percent_over_100_fails() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources><percent name="zoom">150%</percent></resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("Expected Analysis error");
+        };
+        assert!(errors[0].message.contains("'zoom'"));
+        assert!(errors[0].message.contains("values.xml"));
+        assert!(errors[0].message.contains("allow-over-100"));
+    }
+
+    #[test]
+    fn build_with_unbuildable_values_reports_them() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <bool name="dark_mode">yes</bool>
+                <number name="retries" type="u8">300</number>
+                <number name="ratio">1.2.3</number>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("Expected Analysis error");
+        };
+        let messages: Vec<&str> =
+            errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages.len(), 3, "{messages:?}");
+        let invalid = analysis::codes::INVALID_VALUE;
+        assert!(errors.iter().all(|e| e.code == invalid));
+        assert!(messages[0].contains("'dark_mode'"), "{messages:?}");
+        assert!(messages[0].contains("'yes' is not a bool"));
+        assert!(messages[1].contains("'300' does not fit in u8"));
+        assert!(messages[2].contains("'1.2.3'"));
+        assert!(messages.iter().all(|m| m.contains("values.xml")));
+    }
+
+    #[test]
+    fn build_emits_mime_map_lookup() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <mime-map name="upload_types">
+                    <entry ext="png">image/png</entry>
+                    <entry ext="pdf">application/pdf</entry>
+                </mime-map>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let artifacts = build_with_plan(&plan).expect("build succeeds");
+
+        assert!(artifacts.rust.contains("pub const UPLOAD_TYPES: &[(&str, &str)]"));
+        assert!(artifacts.rust.contains("(\"pdf\", \"application/pdf\"),"));
+        assert!(artifacts.rust.contains("pub fn upload_types_for(ext: &str)"));
+    }
+
+    #[test]
+    fn build_with_duplicate_mime_extension_fails() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <mime-map name="upload_types">
+                    <entry ext="jpg">image/jpeg</entry>
+                    <entry ext="jpg">image/jpg</entry>
+                </mime-map>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("Expected Analysis error");
+        };
+        assert!(errors[0].message.contains("'upload_types'"));
+        assert!(errors[0].message.contains("duplicate extension 'jpg'"));
+    }
+
+    #[test]
+    fn build_with_unresolved_reference_fails() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <ns name="ui">
+                    <string name="title">@./missing</string>
+                </ns>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("Expected Analysis error");
+        };
+        assert!(errors[0].message.contains("ui/missing"));
+    }
+
+    #[test]
+    fn build_names_same_named_files_by_directory() {
+        let tmp = tempdir().unwrap();
+        let auth_dir = tmp.path().join("res").join("auth");
+        let billing_dir = tmp.path().join("res").join("billing");
+        write_file(
+            &auth_dir.join("values.xml"),
+            r#"<resources><string name="title">Sign in</string></resources>"#,
+        );
+        write_file(
+            &billing_dir.join("values.xml"),
+            r#"<resources><number name="title">3</number></resources>"#,
+        );
+        let plan = BuildPlan::new(auth_dir, Some(billing_dir), "debug");
+
+        let artifacts = build_with_plan(&plan).unwrap();
+        assert_eq!(artifacts.warnings.len(), 1, "{:?}", artifacts.warnings);
+        let warning = &artifacts.warnings[0].to_string();
+        let auth = std::path::Path::new("auth").join("values.xml");
+        let billing = std::path::Path::new("billing").join("values.xml");
+        assert!(warning.starts_with("[RR0002]"), "{warning}");
+        assert!(
+            warning.contains(&format!("Using '{}'", auth.display())),
+            "{warning}"
+        );
+        assert!(
+            warning.ends_with(&format!("Duplicates in: {}", billing.display())),
+            "{warning}"
+        );
+    }
+
+    #[test]
+    fn validation_options_follow_the_environment() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| (*value).to_string())
+            }
+        };
+        let options = validation_options_from_env(env(&[]));
+        assert!(!options.require_namespace);
+        assert!(!options.explicit_namespace_extension);
+        assert!(!options.allow_raw);
+        assert!(!options.flag_nbsp);
+        assert!(!options.canonical_colors);
+        assert!(!options.treat_duplicates_as_errors);
+        assert!(!options.quiet_sanitization);
+        assert!(options.type_mismatch_duplicates_as_errors);
+        assert_eq!(options.max_warnings, None);
+        assert_eq!(
+            options.max_value_bytes,
+            Some(analysis::DEFAULT_MAX_VALUE_BYTES)
+        );
+        assert_eq!(
+            options.hard_max_value_bytes,
+            Some(analysis::DEFAULT_HARD_MAX_VALUE_BYTES)
+        );
+
+        let options = validation_options_from_env(env(&[
+            ("R_RESOURCES_REQUIRE_NAMESPACE", "1"),
+            ("R_RESOURCES_EXPLICIT_NAMESPACE_EXTENSION", "1"),
+            ("R_RESOURCES_ALLOW_RAW", "true"),
+            ("R_RESOURCES_FLAG_NBSP", "1"),
+            ("R_RESOURCES_CANONICAL_COLORS", "1"),
+            ("R_RESOURCES_QUIET_SANITIZATION", "true"),
+            ("R_RESOURCES_MAX_WARNINGS", " 10"),
+            ("R_RESOURCES_DENY", "RR0082, RR0001"),
+            ("R_RESOURCES_MAX_NAMESPACE_DEPTH", "none"),
+            ("R_RESOURCES_ALLOW_IN_REGION", "Legacy API: RR0060,ui:RR0031"),
+            ("R_RESOURCES_ALLOW_LITERALS", "build/year, zip_code"),
+            ("R_RESOURCES_NUMBER_TYPE_CONFLICTS_AS_WARNINGS", "1"),
+            ("R_RESOURCES_MAX_VALUE_BYTES", "1024"),
+            ("R_RESOURCES_HARD_MAX_VALUE_BYTES", "none"),
+        ]));
+        assert_eq!(options.max_value_bytes, Some(1024));
+        assert_eq!(options.hard_max_value_bytes, None);
+        assert!(!options.type_mismatch_duplicates_as_errors);
+        assert_eq!(options.literal_allow_list, ["build/year", "zip_code"]);
+        assert!(options.require_namespace);
+        assert!(options.explicit_namespace_extension);
+        assert!(options.allow_raw);
+        assert!(options.flag_nbsp);
+        assert!(options.canonical_colors);
+        assert!(options.quiet_sanitization);
+        assert_eq!(options.max_warnings, Some(10));
+        assert_eq!(options.deny, ["RR0082", "RR0001"]);
+        assert_eq!(options.max_namespace_depth, None);
+        let pair = |region: &str, code: &str| {
+            (region.to_string(), code.to_string())
+        };
+        assert_eq!(
+            options.allow_in_regions,
+            [pair("Legacy API", "RR0060"), pair("ui", "RR0031")]
+        );
+        assert!(options.deny_in_regions.is_empty());
+    }
+
+    #[test]
+    fn large_values_warn_and_huge_values_fail() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        let schema = format!("{{\"a\": \"{}\"}}", "x".repeat(2000));
+        write_file(
+            &res_dir.join("values.xml"),
+            &format!(
+                r#"<resources>
+                    <ns name="docs"><string name="schema">{}</string></ns>
+                    <string name="title">Docs</string>
+                </resources>"#,
+                schema.replace('"', "&quot;")
+            ),
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let limits = |max, hard_max| analysis::ValidationOptions {
+            max_value_bytes: Some(max),
+            hard_max_value_bytes: Some(hard_max),
+            ..Default::default()
+        };
+
+        let artifacts =
+            build_with_plan_and_options(&plan, limits(schema.len(), 4096)).unwrap();
+        assert!(artifacts.warnings.is_empty(), "{:?}", artifacts.warnings);
+
+        let artifacts =
+            build_with_plan_and_options(&plan, limits(1024, 4096)).unwrap();
+        let codes: Vec<&str> =
+            artifacts.warnings.iter().map(|warning| warning.code).collect();
+        assert_eq!(codes, [analysis::codes::LARGE_VALUE]);
+        let warning = &artifacts.warnings[0];
+        assert_eq!(warning.key, Some(ir::ResourceKey::from_path("docs/schema")));
+        assert!(
+            warning.message.contains("is 1.9 KiB (2009 bytes), over the max_value_bytes of 1 KiB"),
+            "{}",
+            warning.message
+        );
+
+        let Err(BuildError::Analysis(errors)) =
+            build_with_plan_and_options(&plan, limits(1024, schema.len() - 1))
+        else {
+            panic!("a value over the hard limit must fail the build");
+        };
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert_eq!(errors[0].code, analysis::codes::VALUE_TOO_LARGE);
+        assert!(errors[0].message.contains("values.xml"), "{}", errors[0].message);
+    }
+
+    #[test]
+    fn build_fails_when_warnings_exceed_the_budget() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="a">one</string>
+                <string name="b">one</string>
+                <string name="c">one</string>
+            </resources>"#,
+        );
+        write_file(
+            &res_dir.join("more.xml"),
+            r#"<resources>
+                <string name="a">two</string>
+                <string name="b">two</string>
+                <string name="c">two</string>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let options = |max_warnings| analysis::ValidationOptions {
+            max_warnings: Some(max_warnings),
+            ..Default::default()
+        };
+        let artifacts = build_with_plan_and_options(&plan, options(5));
+        assert_eq!(artifacts.unwrap().warnings.len(), 3);
+
+        let Err(BuildError::Analysis(errors)) =
+            build_with_plan_and_options(&plan, options(2))
+        else {
+            panic!("3 warnings must exceed a limit of 2");
+        };
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, analysis::codes::TOO_MANY_WARNINGS);
+        let message = &errors[0].message;
+        assert!(message.starts_with("3 warnings (limit 2)"), "{message}");
+        assert!(message.contains("\n  [RR0001] Duplicate resource key 'a'"));
+
+        // Extra sets and generation share the budget of the build
+        write_file(
+            &res_dir.join("ui.xml"),
+            r#"<resources>
+                <ns name="api"><string name="title">API</string></ns>
+                <ns name="auth"><string name="title">Login</string></ns>
+            </resources>"#,
+        );
+        let bench_dir = tmp.path().join("bench");
+        write_file(
+            &bench_dir.join("values.xml"),
+            r#"<resources>
+                <number name="rounds">100</number>
+                <number name="rounds">200</number>
+            </resources>"#,
+        );
+        let mut plan = plan;
+        plan.extra_sets = vec![("bench".to_string(), bench_dir)];
+        let artifacts = build_with_plan_and_options(&plan, options(5));
+        assert_eq!(artifacts.unwrap().warnings.len(), 5);
+        let Err(BuildError::Analysis(errors)) =
+            build_with_plan_and_options(&plan, options(4))
+        else {
+            panic!("5 warnings over all sets must exceed a limit of 4");
+        };
+        assert!(errors[0].message.starts_with("5 warnings (limit 4)"));
+    }
+
+    #[test]
+    fn build_with_extra_sets_generates_isolated_roots() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        let bench_dir = res_dir.join("bench");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources><string name="title">App</string></resources>"#,
+        );
+        write_file(
+            &bench_dir.join("values.xml"),
+            r#"<resources>
+                <string name="title">Bench</string>
+                <number name="rounds">100</number>
+                <number name="rounds">200</number>
+            </resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        plan.extra_sets = vec![("bench".to_string(), bench_dir.clone())];
+
+        let artifacts = build_with_plan(&plan).unwrap();
+        // `title` in both sets is not a duplicate, `rounds` twice is
+        assert_eq!(artifacts.warnings.len(), 1, "{:?}", artifacts.warnings);
+        assert!(artifacts.warnings[0].message.contains("'rounds'"));
+        assert!(artifacts.rust.contains("pub struct RBench;"));
+        assert!(artifacts.rust.contains("pub mod r_bench {"));
+        assert!(artifacts.rust.contains("pub const TITLE: &str = \"Bench\";"));
+        assert!(artifacts.rust.contains("pub const TITLE: &str = \"App\";"));
+
+        // References do not reach into the main set
+        write_file(
+            &bench_dir.join("refs.xml"),
+            r#"<resources>
+                <string name="label">@string/missing_in_bench</string>
+            </resources>"#,
+        );
+        write_file(
+            &res_dir.join("more.xml"),
+            r#"<resources>
+                <string name="missing_in_bench">x</string>
+            </resources>"#,
+        );
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan) else {
+            panic!("Expected Analysis error");
+        };
+        assert!(errors[0].message.contains("missing_in_bench"));
+    }
+
+    #[test]
+    fn build_rejects_extra_set_named_tests() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(&res_dir.join("values.xml"), "<resources/>");
+        let mut plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        plan.extra_sets = vec![("tests".to_string(), res_dir.join("t"))];
+        let Err(BuildError::Pipeline(err)) = build_with_plan(&plan) else {
+            panic!("Expected Pipeline error");
+        };
+        assert_eq!(
+            err.to_string(),
+            "extra resource set 'tests' would take the root module of the \
+             test resources"
+        );
+    }
+
+    #[test]
+    fn build_checks_string_lengths_in_bytes() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="device_name" max-len="13" as-bytes="true"
+                    >Capteur été</string>
+                <string name="brand">Acme</string>
+                <string name="label" max-len="8" as-bytes="true">@string/brand</string>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let artifacts = build_with_plan(&plan).unwrap();
+        assert!(artifacts
+            .rust
+            .contains("pub const DEVICE_NAME_LEN: usize = 13;"));
+        assert!(artifacts.rust.contains("pub const DEVICE_NAME_BYTES: &[u8]"));
+        // Declared on the reference, not on its target
+        assert!(artifacts.rust.contains("pub const LABEL_LEN: usize = 4;"));
+        assert!(artifacts.rust.contains("pub const LABEL_BYTES: &[u8]"));
+        assert!(!artifacts.rust.contains("BRAND_LEN"));
+
+        // 11 characters fit in 12, 13 bytes do not
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="device_name" max-len="12">Capteur été</string>
+            </resources>"#,
+        );
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan) else {
+            panic!("Expected Analysis error");
+        };
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert_eq!(errors[0].code, analysis::codes::STRING_TOO_LONG);
+        let expected = "is 13 bytes (11 characters) of UTF-8, over its \
+                        max-len of 12";
+        assert!(errors[0].message.contains(expected), "{errors:?}");
+    }
+
+    #[test]
+    fn build_warns_about_secrets_without_deprecating() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="stripe_api_key">sk_live_51Hx</string>
+                <string name="public_token" allow-secret="true">pk_1</string>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let artifacts = build_with_plan(&plan).unwrap();
+        assert_eq!(artifacts.warnings.len(), 1);
+        assert_eq!(
+            artifacts.warnings[0].code,
+            analysis::codes::POSSIBLE_SECRET
+        );
+        assert!(artifacts.warnings[0].message.contains("'stripe_api_key'"));
+        assert!(!artifacts.rust.contains("#[deprecated"));
+    }
+
+    #[test]
+    fn build_with_false_assertion_fails() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <ns name="db">
+                    <number name="min_pool_size">10</number>
+                    <assert expr="min_pool_size &lt;= max_pool_size"/>
+                </ns>
+            </resources>"#,
+        );
+        write_file(
+            &res_dir.join("limits.xml"),
+            r#"<resources>
+                <ns name="db"><number name="max_pool_size">5</number></ns>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("Expected Analysis error");
+        };
+        let message = &errors[0].message;
+        assert!(message.contains("Assertion `min_pool_size <= max_pool_size` failed"));
+        assert!(message.contains("db/min_pool_size = 10 ("));
+        assert!(message.contains("values.xml"));
+        assert!(message.contains("db/max_pool_size = 5 ("));
+        assert!(message.contains("limits.xml"));
+    }
+
+    #[test]
+    fn build_with_incomplete_required_locales_fails() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <ns name="api" require-locales="en, fr">
+                    <ns name="en">
+                        <string name="unauthorized">Unauthorized</string>
+                        <string name="not_found">Not found</string>
+                    </ns>
+                </ns>
+            </resources>"#,
+        );
+        write_file(
+            &res_dir.join("values-fr.xml"),
+            r#"<resources>
+                <ns name="api"><ns name="fr">
+                    <string name="unauthorized">Non autorisé</string>
+                </ns></ns>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan) else {
+            panic!("Expected Analysis error");
+        };
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0]
+            .message
+            .starts_with("Missing 'api/fr/not_found' for locale 'fr'"));
+        assert!(errors[0].message.contains("values.xml"));
+    }
+
+    #[test]
+    fn build_warns_about_translations_losing_placeholders() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <ns name="ui" require-locales="en,fr">
+                    <ns name="en">
+                        <string name="hello">Hello %1$s</string>
+                        <template name="unread">
+                            <number name="count"/>{count} unread
+                        </template>
+                    </ns>
+                    <ns name="fr">
+                        <string name="hello">Bonjour</string>
+                        <template name="unread">
+                            <number name="count"/>{count} non lus
+                        </template>
+                    </ns>
+                </ns>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let artifacts = build_with_plan(&plan).unwrap();
+        // The other warning is about the prelude, which skips HELLO
+        let mismatches: Vec<&String> = artifacts
+            .warnings
+            .iter()
+            .filter(|warning| {
+                warning.code == analysis::codes::PLACEHOLDER_MISMATCH
+            })
+            .map(|warning| &warning.message)
+            .collect();
+        assert_eq!(mismatches.len(), 1, "{:?}", artifacts.warnings);
+        assert!(mismatches[0].contains("'ui/fr/hello' (locale 'fr', "));
+        assert!(mismatches[0].ends_with("missing %1$s"));
+
+        let options = analysis::ValidationOptions {
+            deny: vec![analysis::codes::PLACEHOLDER_MISMATCH.to_string()],
+            ..Default::default()
+        };
+        let Err(BuildError::Analysis(errors)) =
+            build_with_plan_and_options(&plan, options)
+        else {
+            panic!("Expected Analysis error");
+        };
+        assert_eq!(errors[0].code, analysis::codes::PLACEHOLDER_MISMATCH);
+    }
+
+    #[test]
+    fn build_emits_preludes_and_skips_conflicts() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="app_name">Demo</string>
+                <ns name="api">
+                    <ns name="error">
+                        <string name="unauthorized">Unauthorized</string>
+                    </ns>
+                    <string name="title">API</string>
+                    <percent name="width">40%</percent>
+                </ns>
+                <ns name="auth"><string name="title">Login</string></ns>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let artifacts = build_with_plan(&plan).unwrap();
+        let rust = &artifacts.rust;
+
+        // Every namespace module gets a glob prelude
+        assert_eq!(rust.matches("pub mod prelude {").count(), 4);
+        assert!(rust.contains("pub use super::api::error::UNAUTHORIZED;"));
+        assert!(rust.contains("pub use super::api::WIDTH;"));
+        assert!(rust.contains("pub use super::api::WIDTH_RAW;"));
+        assert!(!rust.contains("pub use super::api::TITLE;"));
+        assert!(!rust.contains("pub use super::auth::TITLE;"));
+        assert_eq!(artifacts.warnings.len(), 1);
+        assert_eq!(
+            artifacts.warnings[0].code,
+            analysis::codes::PRELUDE_CONFLICT
+        );
+        assert!(artifacts.warnings[0].message.contains("(api, auth): TITLE."));
+    }
+
+    #[test]
+    fn build_emits_smoke_tests_when_enabled() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="app_name">Demo</string>
+                <ns name="ui">
+                    <template name="greeting">
+                        <string name="name"/>
+                        <number name="count" type="u8"/>
+                        <bool name="formal"/>
+                        Hello {name} ({count}, {formal})
+                    </template>
+                </ns>
+            </resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir, None, "debug");
+        let artifacts = build_with_plan(&plan).unwrap();
+        assert!(!artifacts.rust.contains("__r_resources_smoke"));
+
+        plan.emit_smoke_tests = true;
+        let rust = build_with_plan(&plan).unwrap().rust;
+        assert!(rust.contains("#[cfg(test)]\nmod __r_resources_smoke {"));
+        assert!(rust.contains("std::hint::black_box(&super::r::APP_NAME);"));
+        assert!(rust.contains(
+            "std::hint::black_box(super::r::ui::greeting(\"\", 0, false));"
+        ));
+    }
+
+    #[test]
+    fn build_with_tag_aliases_treats_aliases_as_targets() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <message name="greeting">Hello</message>
+                <count name="max_items" type="u16">250</count>
+                <message name="farewell">@string/greeting</message>
+            </resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir, None, "debug");
+        plan.tag_aliases = vec![
+            ("message".to_string(), "string".to_string()),
+            ("count".to_string(), "number".to_string()),
+        ];
+
+        let rust = build_with_plan(&plan).unwrap().rust;
+        assert!(rust.contains("pub const GREETING: &str = \"Hello\";"));
+        assert!(rust.contains("pub const MAX_ITEMS: u16 = 250;"));
+        assert!(rust.contains("pub const FAREWELL: &str = \"Hello\";"));
+    }
+
+    #[test]
+    fn build_with_different_roots_compiles_side_by_side() {
+        let tmp = tempdir().unwrap();
+        let mut combined = String::new();
+        for root in ["r", "plugin"] {
+            let res_dir = tmp.path().join(root).join("res");
+            write_file(
+                &res_dir.join("values.xml"),
+                r#"<resources>
+                    <string name="title">Demo</string>
+                    <int name="max_items">10</int>
+                    <ns name="ui">
+                        <template name="greeting">
+                            <string name="name"/>Hi {name}!
+                        </template>
+                        <bool name="compact">true</bool>
+                    </ns>
+                </resources>"#,
+            );
+            write_file(
+                &res_dir.join("tests").join("values.xml"),
+                r#"<resources><string name="fixture">x</string></resources>"#,
+            );
+            let tests_dir = res_dir.join("tests");
+            let mut plan = BuildPlan::new(res_dir, Some(tests_dir), "debug");
+            plan.root_module = root.to_string();
+            plan.emit_smoke_tests = true;
+            plan.emit_to_json = true;
+            plan.emit_meta = true;
+            combined.push_str(&build_with_plan(&plan).unwrap().rust);
+        }
+        assert!(combined.contains("pub struct R;"));
+        assert!(combined.contains("pub struct Plugin;"));
+        assert!(combined.contains("pub mod plugin_tests {"));
+        assert!(combined.contains("mod __plugin_resources_smoke {"));
+        assert!(combined.contains("super::plugin::ui::greeting(\"\")"));
+
+        // Both artifacts in one crate must build, and their smoke tests pass
+        run_generated_tests(tmp.path(), "roots", &combined);
+    }
+
+    #[test]
+    fn build_with_invalid_root_module_fails() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources><string name="title">Demo</string></resources>"#,
+        );
+        for root in ["R", "mod", "1r", "my-res", ""] {
+            let mut plan = BuildPlan::new(res_dir.clone(), None, "debug");
+            plan.root_module = root.to_string();
+            let Err(BuildError::Pipeline(err)) = build_with_plan(&plan) else {
+                panic!("'{root}' should be rejected");
+            };
+            assert!(
+                err.to_string().contains("is not a lowercase Rust identifier"),
+                "{err}"
+            );
+        }
+    }
+
+    #[test]
+    fn build_with_meta_counts_the_resources() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="app_name">Demo</string>
+                <number name="max_items">10</number>
+                <ns name="ui">
+                    <string name="title">@string/app_name</string>
+                    <ns name="buttons"><string name="ok">OK</string></ns>
+                </ns>
+                <ns name="api-v2"><bool name="enabled">true</bool></ns>
+            </resources>"#,
+        );
+        write_file(
+            &res_dir.join("more.xml"),
+            r#"<resources><string name="app_name">Again</string></resources>"#,
+        );
+        write_file(
+            &res_dir.join("tests").join("values.xml"),
+            r#"<resources><string name="fixture">x</string></resources>"#,
+        );
+        let tests_dir = res_dir.join("tests");
+        let mut plan = BuildPlan::new(res_dir, Some(tests_dir), "debug");
+        plan.emit_meta = true;
+
+        let rust = build_with_plan(&plan).unwrap().rust;
+        // The duplicate counts once and the test resource not at all
+        assert!(rust.contains("pub const TOTAL_RESOURCES: usize = 5;"));
+        assert!(rust.contains("pub const STRING_COUNT: usize = 3;"));
+        assert!(rust.contains(
+            "pub const NAMESPACES: &[&str] = &[\"api-v2\", \"ui\"];"
+        ));
+    }
+
+    #[test]
+    fn build_with_meta_rejects_a_meta_namespace() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <ns name="meta"><string name="owner">Team</string></ns>
+            </resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir, None, "debug");
+        assert!(build_with_plan(&plan).is_ok());
+
+        plan.emit_meta = true;
+        let Err(BuildError::Generation(errors)) = build_with_plan(&plan) else {
+            panic!("expected the meta namespace to be rejected");
+        };
+        assert_eq!(errors[0].code, analysis::codes::RESERVED_NAMESPACE);
+    }
+
+    #[test]
+    fn build_with_lookup_lists_hex_colors_only() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <color name="accent">#FF5722</color>
+                <ns name="ui"><ns name="colors">
+                    <color name="primary">#803366FF</color>
+                    <color name="link">@color/accent</color>
+                    <color name="shadow">rgba(0, 0, 0, 0.5)</color>
+                </ns></ns>
+                <ns name="dev" visibility="crate">
+                    <color name="debug">#00FF00</color>
+                </ns>
+            </resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir, None, "debug");
+        plan.emit_lookup = true;
+        let rust = build_with_plan(&plan).unwrap().rust;
+        // The table itself is run by the `lookup` end-to-end fixture
+        assert!(!rust.contains("\"ui/colors/shadow\""), "{rust}");
+        assert!(!rust.contains("\"dev/debug\""), "{rust}");
+    }
+
+    #[test]
+    fn build_with_resource_markers_skips_templates() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="app_name">Demo</string>
+                <ns name="auth">
+                    <string name="title">Sign in</string>
+                    <number name="port" type="u16">8080</number>
+                    <string-array name="providers">
+                        <item>github</item>
+                        <item>gitlab</item>
+                    </string-array>
+                    <template name="greet"><string name="name"/>Hi {name}</template>
+                </ns>
+                <ns name="internal" visibility="crate">
+                    <bool name="debug">true</bool>
+                </ns>
+            </resources>"#,
+        );
+        write_file(
+            &res_dir.join("more.xml"),
+            r#"<resources><string name="app_name">Other</string></resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir, None, "debug");
+        plan.emit_resource_markers = true;
+        let rust = build_with_plan(&plan).unwrap().rust;
+        // The markers are used by the `markers` end-to-end fixture
+        assert!(rust.contains("pub(crate) struct Debug;"), "{rust}");
+        assert!(!rust.contains("struct Greet"), "{rust}");
+    }
+
+    #[test]
+    fn build_with_resource_markers_rejects_clashing_names() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="app_name">Demo</string>
+                <string name="appName">Demo</string>
+                <string name="title">Sign in</string>
+                <ns name="Title"><string name="label">x</string></ns>
+            </resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir, None, "debug");
+        assert!(build_with_plan(&plan).is_ok());
+
+        plan.emit_resource_markers = true;
+        let Err(BuildError::Generation(errors)) = build_with_plan(&plan) else {
+            panic!("expected the clashing markers to be rejected");
+        };
+        let messages: Vec<&str> =
+            errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages.len(), 2, "{messages:?}");
+        assert!(messages.iter().all(|m| m.contains("rename one of them")));
+        assert!(
+            messages.iter().any(|m| m.starts_with("The marker type `AppName`")),
+            "{messages:?}"
+        );
+        assert!(
+            messages
+                .iter()
+                .any(|m| m.contains("`Title` of 'title' is named like the namespace 'Title'")),
+            "{messages:?}"
+        );
+        assert_eq!(errors[0].code, analysis::codes::NAME_COLLISION);
+    }
+
+    #[test]
+    fn build_with_lookup_rejects_a_colors_namespace() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <ns name="colors"><color name="brand">#112233</color></ns>
+            </resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir, None, "debug");
+        assert!(build_with_plan(&plan).is_ok());
+
+        plan.emit_lookup = true;
+        let Err(BuildError::Generation(errors)) = build_with_plan(&plan) else {
+            panic!("expected the colors namespace to be rejected");
+        };
+        assert_eq!(errors[0].code, analysis::codes::RESERVED_NAMESPACE);
+    }
+
+    #[test]
+    fn build_with_file_as_namespace_prefixes_keys() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources><string name="app_name">Demo</string></resources>"#,
+        );
+        write_file(
+            &res_dir.join("auth.xml"),
+            r#"<resources>
+                <string name="title">Login</string>
+                <ns name="errors">
+                    <string name="denied">@../title denied</string>
+                </ns>
+            </resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir, None, "debug");
+        plan.file_as_namespace = true;
+
+        let rust = build_with_plan(&plan).unwrap().rust;
+        assert!(rust.contains("pub const APP_NAME: &str = \"Demo\";"));
+        assert!(rust.contains("pub mod auth {"));
+        assert!(rust.contains("pub mod errors {"));
+        assert!(rust.contains("pub const DENIED: &str = \"Login denied\";"));
+    }
+
+    #[test]
+    fn build_with_file_as_namespace_strips_locale_suffix() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("auth.xml"),
+            r#"<resources><string name="title">Login</string></resources>"#,
+        );
+        write_file(
+            &res_dir.join("auth.pt-BR.xml"),
+            r#"<resources><string name="title">Entrar</string></resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir, None, "debug");
+        plan.file_as_namespace = true;
+
+        // Both files land in `auth`, and the duplicate names the real files
+        let artifacts = build_with_plan(&plan).unwrap();
+        assert!(!artifacts.rust.contains("pt_BR"));
+        assert_eq!(artifacts.warnings.len(), 1);
+        let warning = &artifacts.warnings[0].message;
+        assert!(warning.contains("'auth/title'"));
+        assert!(warning.contains("auth.xml"));
+        assert!(warning.contains("auth.pt-BR.xml"));
+    }
+
+    /// `<shader name="...">file.wgsl</shader>`, emitted as a path constant
+    struct ShaderType;
+
+    impl ir::types::ResourceType for ShaderType {
+        fn name(&self) -> &'static str {
+            "shader"
+        }
+
+        fn xml_tags(&self) -> &'static [&'static str] {
+            &["shader"]
+        }
+
+        fn resource_kind(&self) -> ir::ResourceKind {
+            ir::ResourceKind::Custom("shader".to_string())
+        }
+
+        fn validate(
+            &self,
+            parsed: &parsing::ParsedResource,
+        ) -> Result<(), String> {
+            match &parsed.value {
+                parsing::ScalarValue::Text(path)
+                    if path.ends_with(".wgsl") =>
+                {
+                    Ok(())
+                }
+                _ => Err("expected a .wgsl file".to_string()),
+            }
+        }
+
+        fn build_node(
+            &self,
+            parsed: &parsing::ParsedResource,
+            origin: ir::ResourceOrigin,
+        ) -> Option<ir::ResourceNode> {
+            let parsing::ScalarValue::Text(path) = &parsed.value else {
+                return None;
+            };
+            Some(ir::ResourceNode {
+                kind: self.resource_kind(),
+                value: ir::ResourceValue::String(path.clone()),
+                origin,
+                attributes: Default::default(),
+            })
+        }
+
+        fn emit_rust(
+            &self,
+            key: &ir::ResourceKey,
+            node: &ir::ResourceNode,
+            indent: usize,
+        ) -> Option<String> {
+            let ir::ResourceValue::String(path) = &node.value else {
+                return None;
+            };
+            let name = utils::const_identifier(&key.name);
+            Some(format!(
+                "{}pub const {name}: &str = include_str!({path:?});\n",
+                " ".repeat(indent)
+            ))
+        }
+    }
+
+    #[test]
+    fn build_emits_to_json_when_enabled() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="app_name">Say "hi"</string>
+                <number name="max_retries">3</number>
+                <number name="ratio" type="f32">0.5</number>
+                <bool name="debug">false</bool>
+                <ns name="ui">
+                    <color name="accent">#FF5722</color>
+                    <char name="separator">/</char>
+                    <percent name="zoom">37.5%</percent>
+                    <string name="title">@string/app_name</string>
+                    <template name="greet"><string name="who"/>Hi {who}</template>
+                </ns>
+            </resources>"#,
+        );
+        let mut plan = BuildPlan::new(res_dir, None, "debug");
+        let rust = build_with_plan(&plan).unwrap().rust;
+        assert!(!rust.contains("fn to_json"));
+
+        plan.emit_to_json = true;
+        let rust = build_with_plan(&plan).unwrap().rust;
+        let expected = concat!(
+            r#"{"app_name":"Say \"hi\"","debug":false,"max_retries":3,"#,
+            r##""ratio":0.5,"ui":{"accent":"#FF5722","separator":"/","##,
+            r#""title":"Say \"hi\"","zoom":0.375}}"#
+        );
+        assert!(rust.contains("pub fn to_json() -> String {"));
+        assert!(rust.contains(&format!("String::from({expected:?})")));
+    }
+
+    #[test]
+    fn build_with_custom_type_emits_custom_tags() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <ns name="gfx">
+                    <shader name="blur">shaders/blur.wgsl</shader>
+                </ns>
+                <string name="title">Demo</string>
+            </resources>"#,
+        );
+
+        // Without the handler, the unknown tag is ignored
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let rust = build_with_plan(&plan).unwrap().rust;
+        assert!(!rust.contains("BLUR"));
+
+        let plan = BuildPlan::new(res_dir, None, "debug")
+            .with_custom_types(vec![Box::new(ShaderType)]);
+        let rust = build_with_plan(&plan).unwrap().rust;
+        assert!(rust.contains(
+            "pub const BLUR: &str = include_str!(\"shaders/blur.wgsl\");"
+        ));
+        assert!(rust.contains("pub use super::gfx::BLUR;"));
+        assert!(rust.contains("pub const TITLE: &str = \"Demo\";"));
+    }
+
+    #[test]
+    fn build_with_custom_type_reports_rejected_values() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources><shader name="blur">blur.glsl</shader></resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug")
+            .with_custom_types(vec![Box::new(ShaderType)]);
+
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan) else {
+            panic!("Expected Analysis error");
+        };
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.contains("'blur'"));
+        assert!(errors[0].message.contains("expected a .wgsl file"));
+    }
 }
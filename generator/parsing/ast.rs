@@ -1,13 +1,25 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
+/// Resources of one source file, as handed to the IR stage
 #[derive(Debug, Clone)]
 pub struct ParsedResourceFile {
     pub path: PathBuf,
     pub is_test: bool,
     pub resources: Vec<ParsedResource>,
+    pub assertions: Vec<ParsedAssertion>,
+    pub locale_requirements: Vec<ParsedLocaleRequirement>,
+    pub namespace_visibilities: Vec<ParsedNamespaceVisibility>,
+    pub namespace_features: Vec<ParsedNamespaceFeature>,
+    pub namespace_docs: Vec<ParsedNamespaceDoc>,
+    pub namespace_declarations: Vec<ParsedNamespaceDeclaration>,
+    pub error_enums: Vec<ParsedErrorEnum>,
+    pub unbalanced_regions: Vec<ParsedUnbalancedRegion>,
 }
 
 impl ParsedResourceFile {
+    /// File without assertions or namespace declarations. `path` is the
+    /// one shown in diagnostics; `is_test` puts the resources in `r_tests`.
     pub fn new(
         path: PathBuf,
         is_test: bool,
@@ -17,18 +29,98 @@ impl ParsedResourceFile {
             path,
             is_test,
             resources,
+            assertions: Vec::new(),
+            locale_requirements: Vec::new(),
+            namespace_visibilities: Vec::new(),
+            namespace_features: Vec::new(),
+            namespace_docs: Vec::new(),
+            namespace_declarations: Vec::new(),
+            error_enums: Vec::new(),
+            unbalanced_regions: Vec::new(),
         }
     }
 }
 
+/// `<assert expr="min_pool_size <= max_pool_size"/>`, evaluated during analysis
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedAssertion {
+    /// Expression as written (empty if the `expr` attribute is missing)
+    pub expr: String,
+    /// Namespace enclosing the assertion, used to resolve its identifiers
+    pub namespace: Vec<String>,
+}
+
+/// `<ns name="api" require-locales="en,fr">`: every key under one of the
+/// locale namespaces (`api/en`, `api/fr`) must exist under all of them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedLocaleRequirement {
+    /// Namespace carrying the attribute
+    pub namespace: Vec<String>,
+    pub locales: Vec<String>,
+}
+
+/// `<ns name="internal" visibility="crate">`: the visibility of the generated
+/// module, also inherited by the resources inside
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedNamespaceVisibility {
+    pub namespace: Vec<String>,
+    /// Value as written (`pub`, `crate`, or `super`; validated in analysis)
+    pub visibility: String,
+}
+
+/// `<ns name="premium" feature="premium">`: the generated module only exists
+/// when the consumer crate enables the cargo feature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedNamespaceFeature {
+    pub namespace: Vec<String>,
+    pub feature: String,
+}
+
+/// `<ns name="auth" doc="Sign-in screens">`: inner documentation of the
+/// generated module
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedNamespaceDoc {
+    pub namespace: Vec<String>,
+    pub doc: String,
+}
+
+/// Every `<ns name="auth">` of the file; `extend="true"` marks it as the
+/// continuation of a namespace another file opens
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedNamespaceDeclaration {
+    pub namespace: Vec<String>,
+    pub extend: bool,
+}
+
+/// `<ns name="error" error-enum="ApiError">`: an enum generated from the
+/// message and code pairs of the namespace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedErrorEnum {
+    pub namespace: Vec<String>,
+    /// Type name as written, validated in analysis
+    pub name: String,
+}
+
+/// `<!-- region: name -->` or `<!-- endregion -->` marker without its
+/// counterpart
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedUnbalancedRegion {
+    /// Line of the marker in the file as read from disk
+    pub line: usize,
+    pub message: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParsedResource {
     pub name: String,
     pub kind: ResourceKind,
     pub value: ScalarValue,
+    /// Attributes of the resource tag other than `name` (e.g. `allow-secret`)
+    pub attributes: BTreeMap<String, String>,
 }
 
 impl ParsedResource {
+    /// `<string>`; `name` may be a path (`ui/title`) to namespace it
     pub fn string(
         name: impl Into<String>,
         value: impl Into<String>,
@@ -37,9 +129,12 @@ impl ParsedResource {
             name: name.into(),
             kind: ResourceKind::String,
             value: ScalarValue::Text(value.into()),
+            attributes: BTreeMap::new(),
         }
     }
 
+    /// `<number>` with its text as written; `explicit_type` is the
+    /// `type` attribute (`"u16"`), `None` to infer it from the value
     pub fn number(
         name: impl Into<String>,
         value: impl Into<String>,
@@ -52,14 +147,17 @@ impl ParsedResource {
                 value: value.into(),
                 explicit_type,
             },
+            attributes: BTreeMap::new(),
         }
     }
 
+    /// `<bool>`
     pub fn bool(name: impl Into<String>, value: bool) -> Self {
         Self {
             name: name.into(),
             kind: ResourceKind::Bool,
             value: ScalarValue::Bool(value),
+            attributes: BTreeMap::new(),
         }
     }
 }
@@ -70,8 +168,22 @@ pub enum ResourceKind {
     Number,
     Bool,
     Color,
+    Char,
+    Percent,
+    Cron,
+    Size,
+    DateTime,
+    Date,
+    Version,
+    Dimension,
+    MimeMap,
+    Enum,
+    /// `<string-array>`, `<int-array>`, ..., with the item type (`"string"`)
+    Array(String),
     Template,
-    // TODO: array, etc.
+    /// Tag without a built-in type (`<shader name="...">`), handled by a
+    /// custom `ResourceType` registered for that tag
+    Custom(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -83,16 +195,99 @@ pub enum ScalarValue {
     },
     Bool(bool),
     Color(String),
+    /// Single Unicode scalar value, with escapes already decoded
+    Char(char),
+    /// Percentage text (`37.5%`), validated by the percent handler
+    Percent {
+        value: String,
+        allow_over_100: bool, // `allow-over-100="true"`
+    },
+    /// Cron expression, validated by the cron handler
+    Cron(String),
+    /// Byte size with a unit (`25MB`), validated by the size handler
+    Size(String),
+    /// RFC 3339 date-time, validated by the datetime handler
+    DateTime(String),
+    /// `YYYY-MM-DD` date, validated by the date handler
+    Date(String),
+    /// `MAJOR.MINOR.PATCH`, validated by the version handler
+    Version(String),
+    /// Length with a unit (`16dp`), validated by the dimension handler
+    Dimension(String),
+    /// `<entry ext="png">image/png</entry>` pairs, validated by the mime-map handler
+    MimeMap(Vec<MimeEntry>),
+    /// `<item>`s of an array, validated by the array handler
+    Array(Vec<ArrayItem>),
+    /// `<row>`s of a two-dimensional array; `stray_items` are `<item>`s
+    /// outside any row, which the array handler rejects
+    ArrayRows {
+        rows: Vec<Vec<ArrayItem>>,
+        stray_items: Vec<ArrayItem>,
+    },
+    /// `<item>` children of an `<enum>`, validated by the enum handler
+    Enum {
+        repr: Option<String>, // `repr="u8"`
+        items: Vec<EnumItem>,
+    },
     Template {
         text: String,
         params: Vec<TemplateParam>,
     },
+    /// Reference to another resource (`@string/app_name`), always stored as an
+    /// absolute path. `kind` is `None` for relative references (`@./name`).
+    Reference {
+        kind: Option<String>,
+        path: String,
+    },
+    /// String with embedded references (`Retrying @number/max_retries times`)
+    Interpolated(Vec<InterpolationPart>),
+}
+
+/// Piece of an interpolated string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InterpolationPart {
+    Text(String),
+    /// Embedded reference, with an absolute path like `ScalarValue::Reference`
+    Reference {
+        kind: Option<String>,
+        path: String,
+    },
+}
+
+/// `<item>` of an array: its text, or a reference when the whole text is one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArrayItem {
+    Text(String),
+    /// Reference with an absolute path, like `ScalarValue::Reference`
+    Reference {
+        kind: Option<String>,
+        path: String,
+    },
+}
+
+impl From<&str> for ArrayItem {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MimeEntry {
+    pub ext: String, // Empty when the `ext` attribute is missing
+    pub mime: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumItem {
+    pub name: String,          // Text of the `<item>`
+    pub value: Option<String>, // `value` attribute, the discriminant
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TemplateParam {
     pub name: String,
     pub value: ScalarValue, // Use ScalarValue to represent the parameter (reuses existing parsing logic)
+    pub order: Option<String>, // `order` attribute, the position in the signature
 }
 
 impl ScalarValue {
@@ -103,7 +298,19 @@ impl ScalarValue {
             Self::Number { value, .. } => Some(value.as_str()),
             Self::Bool(_) => None,
             Self::Color(_) => None,
+            Self::Char(_) => None,
+            Self::Percent { value, .. } => Some(value.as_str()),
+            Self::Cron(value) | Self::Size(value) => Some(value.as_str()),
+            Self::DateTime(value) | Self::Date(value) => Some(value.as_str()),
+            Self::Version(value) | Self::Dimension(value) => {
+                Some(value.as_str())
+            }
+            Self::MimeMap(_) => None,
+            Self::Enum { .. } => None,
+            Self::Array(_) | Self::ArrayRows { .. } => None,
             Self::Template { text, .. } => Some(text.as_str()),
+            Self::Reference { .. } => None,
+            Self::Interpolated(_) => None,
         }
     }
 
@@ -133,6 +340,22 @@ impl ScalarValue {
         }
     }
 
+    #[allow(dead_code)] // Used in tests
+    pub fn as_char(&self) -> Option<char> {
+        match self {
+            Self::Char(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    #[allow(dead_code)] // Used in tests
+    pub fn as_reference(&self) -> Option<&str> {
+        match self {
+            Self::Reference { path, .. } => Some(path.as_str()),
+            _ => None,
+        }
+    }
+
     #[allow(dead_code)] // Used in tests
     pub fn as_bool(&self) -> Option<bool> {
         match self {
@@ -3,6 +3,17 @@ use std::path::PathBuf;
 #[derive(Debug)]
 pub enum ParserError {
     Xml { path: PathBuf, message: String },
+    InvalidReference {
+        path: PathBuf,
+        reference: String,
+        message: String,
+    },
+    /// Resource content that is not valid for its type
+    InvalidValue {
+        path: PathBuf,
+        name: String,
+        message: String,
+    },
 }
 
 impl std::fmt::Display for ParserError {
@@ -14,6 +25,24 @@ impl std::fmt::Display for ParserError {
             Self::Xml { path, message } => {
                 write!(f, "{}: {message}", path.display())
             }
+            Self::InvalidReference {
+                path,
+                reference,
+                message,
+            } => write!(
+                f,
+                "{}: invalid reference '{reference}': {message}",
+                path.display()
+            ),
+            Self::InvalidValue {
+                path,
+                name,
+                message,
+            } => write!(
+                f,
+                "{}: invalid value for '{name}': {message}",
+                path.display()
+            ),
         }
     }
 }
@@ -6,18 +6,41 @@
 
 mod ast;
 mod error;
+mod overrides;
 mod reader;
 
 pub use ast::{
-    ParsedResource, ParsedResourceFile, ResourceKind, ScalarValue,
+    ArrayItem, EnumItem, InterpolationPart, MimeEntry, ParsedResource,
+    ParsedResourceFile, ResourceKind, ScalarValue,
+};
+#[allow(unused_imports)] // Public API, may be used by consumers
+pub use ast::{
+    ParsedAssertion, ParsedErrorEnum, ParsedLocaleRequirement,
+    ParsedNamespaceDeclaration, ParsedNamespaceDoc, ParsedNamespaceFeature, ParsedNamespaceVisibility,
+    ParsedUnbalancedRegion, TemplateParam,
 };
 pub use error::ParserError;
+pub use overrides::{apply_overrides, OverrideIssue, OVERRIDE_FILE};
+pub(crate) use reader::REGION;
 
 use crate::generator::input::RawResourceFile;
 
+/// Reader settings taken from the build plan
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ParseOptions<'a> {
+    /// Extra tag names for built-in ones (`message` → `string`)
+    pub tag_aliases: &'a [(String, String)],
+    /// Namespace each file's resources under its file stem
+    pub file_as_namespace: bool,
+}
+
 /// Parse a list of preprocessed raw files into structured resources.
 pub fn parse_raw_files(
     raw_files: &[RawResourceFile],
+    options: &ParseOptions<'_>,
 ) -> Result<Vec<ParsedResourceFile>, ParserError> {
-    raw_files.iter().map(reader::parse_single_file).collect()
+    raw_files
+        .iter()
+        .map(|raw| reader::parse_single_file(raw, options))
+        .collect()
 }
@@ -0,0 +1,263 @@
+//! Values replaced by the build plan (`BuildPlan::overrides`), e.g. the
+//! brand name of a white-label build.
+//!
+//! An override gives the full key and the new text, as it would be written
+//! between the tags. The text is turned into a value of the resource's type,
+//! which then validates it as usual, so `#GGG` for a color or `abc` for a
+//! number fails the build like it would in XML. Overridden definitions move
+//! to a file of their own, [`OVERRIDE_FILE`], so diagnostics about them do
+//! not point at XML that no longer holds the value. Only main resources are
+//! overridden; test resources keep their values.
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use super::{ParsedResource, ParsedResourceFile, ResourceKind, ScalarValue};
+
+/// File of the overridden resources in diagnostics
+pub const OVERRIDE_FILE: &str = "<override>";
+
+/// Why an override could not be applied
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OverrideIssue {
+    /// No main resource has the key
+    Missing(String),
+    /// The resource has no single value (a template, array, enum, or
+    /// mime map)
+    Unsupported { key: String, kind: String },
+    /// The text is not a value of the resource's type
+    Invalid { key: String, message: String },
+}
+
+/// Replaces the value of every main resource named in `overrides` (later
+/// overrides of a key win) and moves those resources to an
+/// [`OVERRIDE_FILE`] appended to `files`
+pub fn apply_overrides(
+    overrides: &[(String, String)],
+    files: &mut Vec<ParsedResourceFile>,
+) -> Vec<OverrideIssue> {
+    let mut issues = Vec::new();
+    let mut applied = BTreeSet::new();
+    for (key, text) in overrides {
+        let matching: Vec<&mut ParsedResource> = files
+            .iter_mut()
+            .filter(|file| !file.is_test)
+            .flat_map(|file| file.resources.iter_mut())
+            .filter(|resource| resource.name == *key)
+            .collect();
+        if matching.is_empty() {
+            issues.push(OverrideIssue::Missing(key.clone()));
+            continue;
+        }
+        for resource in matching {
+            match overridden_value(resource, text) {
+                Ok(value) => {
+                    resource.value = value;
+                    applied.insert(key.clone());
+                }
+                Err(issue) => issues.push(issue),
+            }
+        }
+    }
+    if applied.is_empty() {
+        return issues;
+    }
+    let mut moved = Vec::new();
+    for file in files.iter_mut().filter(|file| !file.is_test) {
+        let (overridden, kept) = std::mem::take(&mut file.resources)
+            .into_iter()
+            .partition(|resource| applied.contains(&resource.name));
+        file.resources = kept;
+        moved.extend::<Vec<ParsedResource>>(overridden);
+    }
+    files.push(ParsedResourceFile::new(
+        PathBuf::from(OVERRIDE_FILE),
+        false,
+        moved,
+    ));
+    issues
+}
+
+/// `text` as a value of the type of `resource`, written as in XML
+fn overridden_value(
+    resource: &ParsedResource,
+    text: &str,
+) -> Result<ScalarValue, OverrideIssue> {
+    let trimmed = text.trim().to_string();
+    let value = match &resource.kind {
+        // Taken literally: `@string/...` is not a reference here
+        ResourceKind::String => ScalarValue::Text(text.to_string()),
+        ResourceKind::Number => ScalarValue::Number {
+            value: trimmed,
+            explicit_type: match &resource.value {
+                ScalarValue::Number { explicit_type, .. } => {
+                    explicit_type.clone()
+                }
+                _ => None,
+            },
+        },
+        // Kept as text when invalid so the bool type reports it
+        ResourceKind::Bool => trimmed
+            .parse()
+            .map_or(ScalarValue::Text(trimmed), ScalarValue::Bool),
+        ResourceKind::Color => ScalarValue::Color(trimmed),
+        ResourceKind::Char => {
+            let mut chars = text.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => ScalarValue::Char(c),
+                _ => {
+                    return Err(OverrideIssue::Invalid {
+                        key: resource.name.clone(),
+                        message: format!(
+                            "'{text}' is {} characters, a <char> holds exactly one",
+                            text.chars().count()
+                        ),
+                    })
+                }
+            }
+        }
+        ResourceKind::Percent => ScalarValue::Percent {
+            value: trimmed,
+            allow_over_100: matches!(
+                resource.value,
+                ScalarValue::Percent {
+                    allow_over_100: true,
+                    ..
+                }
+            ),
+        },
+        ResourceKind::Cron => ScalarValue::Cron(trimmed),
+        ResourceKind::Size => ScalarValue::Size(trimmed),
+        ResourceKind::DateTime => ScalarValue::DateTime(trimmed),
+        ResourceKind::Date => ScalarValue::Date(trimmed),
+        ResourceKind::Version => ScalarValue::Version(trimmed),
+        ResourceKind::Dimension => ScalarValue::Dimension(trimmed),
+        ResourceKind::Custom(_) => ScalarValue::Text(trimmed),
+        ResourceKind::MimeMap
+        | ResourceKind::Enum
+        | ResourceKind::Array(_)
+        | ResourceKind::Template => {
+            return Err(OverrideIssue::Unsupported {
+                key: resource.name.clone(),
+                kind: kind_name(&resource.kind),
+            })
+        }
+    };
+    Ok(value)
+}
+
+fn kind_name(kind: &ResourceKind) -> String {
+    match kind {
+        ResourceKind::MimeMap => "mime-map".to_string(),
+        ResourceKind::Enum => "enum".to_string(),
+        ResourceKind::Array(item) => format!("{item}-array"),
+        _ => "template".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files() -> Vec<ParsedResourceFile> {
+        let mut offset = ParsedResource::number("ui/offset", "4", None);
+        offset.value = ScalarValue::Number {
+            value: "4".to_string(),
+            explicit_type: Some("u8".to_string()),
+        };
+        let template = ParsedResource {
+            name: "greet".to_string(),
+            kind: ResourceKind::Template,
+            value: ScalarValue::Template {
+                text: "Hi".to_string(),
+                params: Vec::new(),
+            },
+            attributes: Default::default(),
+        };
+        vec![
+            ParsedResourceFile::new(
+                PathBuf::from("values.xml"),
+                false,
+                vec![
+                    ParsedResource::string("brand/name", "Acme"),
+                    offset,
+                    ParsedResource::bool("beta", false),
+                    template,
+                ],
+            ),
+            ParsedResourceFile::new(
+                PathBuf::from("tests/values.xml"),
+                true,
+                vec![ParsedResource::string("brand/name", "Test")],
+            ),
+        ]
+    }
+
+    fn overrides(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn replaces_values_and_moves_them_to_the_override_file() {
+        let mut files = files();
+        let issues = apply_overrides(
+            &overrides(&[
+                ("brand/name", "Globex"),
+                ("ui/offset", " 8 "),
+                ("beta", "true"),
+                ("brand/name", "Initech"),
+            ]),
+            &mut files,
+        );
+        assert!(issues.is_empty(), "{issues:?}");
+        assert_eq!(files.len(), 3);
+        let names: Vec<&str> =
+            files[0].resources.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["greet"]);
+        // Test resources keep their value
+        assert_eq!(
+            files[1].resources[0].value,
+            ScalarValue::Text("Test".to_string())
+        );
+
+        let overridden = &files[2];
+        assert_eq!(overridden.path, PathBuf::from(OVERRIDE_FILE));
+        assert_eq!(
+            overridden.resources[0].value,
+            ScalarValue::Text("Initech".to_string())
+        );
+        assert_eq!(
+            overridden.resources[1].value,
+            ScalarValue::Number {
+                value: "8".to_string(),
+                explicit_type: Some("u8".to_string()),
+            }
+        );
+        assert_eq!(overridden.resources[2].value, ScalarValue::Bool(true));
+    }
+
+    #[test]
+    fn reports_what_cannot_be_overridden() {
+        let mut files = files();
+        let issues = apply_overrides(
+            &overrides(&[("brand/title", "Globex"), ("greet", "Hello")]),
+            &mut files,
+        );
+        assert_eq!(
+            issues,
+            [
+                OverrideIssue::Missing("brand/title".to_string()),
+                OverrideIssue::Unsupported {
+                    key: "greet".to_string(),
+                    kind: "template".to_string(),
+                },
+            ]
+        );
+        // Nothing applied, nothing moved
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].resources.len(), 4);
+    }
+}
@@ -0,0 +1,107 @@
+//! Character literals for `<char>` resources.
+//!
+//! Content is taken verbatim (whitespace is significant) after XML entity decoding,
+//! then Rust-style escapes are decoded: `\n`, `\t`, `\r`, `\0`, `\\`, `\'`, `\"` and
+//! `\u{1F600}`.
+
+/// Parses `<char>` content into exactly one Unicode scalar value.
+pub(super) fn parse_char(text: &str) -> Result<char, String> {
+    let decoded = decode_escapes(text)?;
+    let mut chars = decoded.chars();
+    match (chars.next(), chars.next()) {
+        (Some(value), None) => Ok(value),
+        _ => Err(format!(
+            "expected exactly one character, found {} in {text:?}",
+            decoded.chars().count()
+        )),
+    }
+}
+
+fn decode_escapes(text: &str) -> Result<String, String> {
+    let mut decoded = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            decoded.push(c);
+            continue;
+        }
+        let escaped = match chars.next() {
+            // A lone trailing backslash stands for itself
+            None => '\\',
+            Some('n') => '\n',
+            Some('t') => '\t',
+            Some('r') => '\r',
+            Some('0') => '\0',
+            Some(c @ ('\\' | '\'' | '"')) => c,
+            Some('u') => decode_unicode(&mut chars)?,
+            Some(other) => {
+                return Err(format!("unknown escape '\\{other}'"))
+            }
+        };
+        decoded.push(escaped);
+    }
+    Ok(decoded)
+}
+
+/// Decodes the `{XXXX}` part of a `\u{XXXX}` escape
+fn decode_unicode(chars: &mut std::str::Chars<'_>) -> Result<char, String> {
+    let rest = chars.as_str();
+    let Some(body) = rest.strip_prefix('{') else {
+        return Err("expected '{' after '\\u'".to_string());
+    };
+    let Some((hex, after)) = body.split_once('}') else {
+        return Err("unterminated '\\u{' escape".to_string());
+    };
+    let value = u32::from_str_radix(hex, 16)
+        .ok()
+        .filter(|_| (1..=6).contains(&hex.len()))
+        .ok_or_else(|| format!("invalid unicode escape '\\u{{{hex}}}'"))?;
+    let decoded = char::from_u32(value).ok_or_else(|| {
+        format!("'\\u{{{hex}}}' is not a Unicode scalar value")
+    })?;
+    *chars = after.chars();
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_characters() {
+        assert_eq!(parse_char("/"), Ok('/'));
+        assert_eq!(parse_char(" "), Ok(' '));
+        assert_eq!(parse_char("😀"), Ok('😀'));
+        assert_eq!(parse_char("\\"), Ok('\\'));
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(parse_char("\\n"), Ok('\n'));
+        assert_eq!(parse_char("\\t"), Ok('\t'));
+        assert_eq!(parse_char("\\'"), Ok('\''));
+        assert_eq!(parse_char("\\\\"), Ok('\\'));
+        assert_eq!(parse_char("\\u{1F600}"), Ok('😀'));
+        assert_eq!(parse_char("\\u{e9}"), Ok('é'));
+    }
+
+    #[test]
+    fn wrong_length_reports_count() {
+        let err = parse_char("ab").unwrap_err();
+        assert!(err.contains("found 2"), "{err}");
+        let err = parse_char("\\u{41}\\n").unwrap_err();
+        assert!(err.contains("found 2"), "{err}");
+        assert!(parse_char("").unwrap_err().contains("found 0"));
+    }
+
+    #[test]
+    fn invalid_escapes() {
+        assert!(parse_char("\\q").unwrap_err().contains("unknown escape"));
+        assert!(parse_char("\\u{D800}")
+            .unwrap_err()
+            .contains("not a Unicode scalar value"));
+        assert!(parse_char("\\u{zz}").is_err());
+        assert!(parse_char("\\u{41").is_err());
+        assert!(parse_char("\\u41").is_err());
+    }
+}
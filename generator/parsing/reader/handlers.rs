@@ -1,24 +1,99 @@
-use quick_xml::events::{BytesEnd, BytesStart, BytesText};
+use quick_xml::events::{BytesEnd, BytesStart};
 
-use crate::generator::parsing::ast::{ParsedResource, TemplateParam};
+use crate::generator::parsing::ast::{
+    ArrayItem, EnumItem, MimeEntry, ParsedAssertion, ParsedErrorEnum, ParsedLocaleRequirement,
+    ParsedNamespaceDeclaration, ParsedNamespaceDoc, ParsedNamespaceFeature, ParsedNamespaceVisibility, ParsedResource,
+    ResourceKind,
+    ScalarValue, TemplateParam,
+};
 
+use super::chars::parse_char;
+use super::references::{
+    parse_interpolation, parse_reference, ReferenceError,
+};
 use super::state::ParseState;
-use super::utils::{attr_value, text_to_string, to_string};
+use super::utils::{attr_value, resource_attributes, to_string};
+
+/// Invalid content found while reading a resource's text
+#[derive(Debug)]
+pub(super) enum TextError {
+    Reference(ReferenceError),
+    Value { name: String, message: String },
+}
+
+impl From<ReferenceError> for TextError {
+    fn from(err: ReferenceError) -> Self {
+        Self::Reference(err)
+    }
+}
 
 pub(super) fn handle_start(
     state: &mut ParseState,
     e: &BytesStart<'_>,
-) {
-    let tag = to_string(e.name().as_ref());
+) -> Result<(), TextError> {
+    let tag = state.canonical_tag(to_string(e.local_name().as_ref()));
     
     state.current_tag = tag.clone();
 
     if tag == "ns" {
         if let Some(ns_name) = attr_value(e, b"name") {
             state.namespace_stack.push(ns_name);
+            push_namespace_declaration(state, e);
+            push_locale_requirement(state, e);
+            push_namespace_visibility(state, e);
+            push_namespace_feature(state, e);
+            push_namespace_doc(state, e);
+            push_error_enum(state, e);
         }
         state.current_name = None;
-        return;
+        return Ok(());
+    }
+
+    if tag == "assert" {
+        state.assertions.push(ParsedAssertion {
+            expr: attr_value(e, b"expr").unwrap_or_default(),
+            namespace: state.namespace_stack.clone(),
+        });
+        state.current_name = None;
+        return Ok(());
+    }
+
+    // Mime-map entries belong to the enclosing <mime-map> resource
+    if state.in_mime_map && tag == "entry" {
+        state.mime_entry_ext =
+            attr_value(e, b"ext").unwrap_or_default();
+        state.mime_entry_text.clear();
+        return Ok(());
+    }
+    if tag == "mime-map" {
+        state.in_mime_map = true;
+        state.mime_entries.clear();
+    }
+
+    // Likewise for array and enum items
+    if state.in_array && tag == "item" {
+        state.array_item_text.clear();
+        return Ok(());
+    }
+    if state.in_array && tag == "row" {
+        state.in_array_row = true;
+        state.array_rows.push(Vec::new());
+        return Ok(());
+    }
+    if array_item_type(&tag).is_some() {
+        state.in_array = true;
+        state.array_items.clear();
+        state.array_rows.clear();
+    }
+    if state.in_enum && tag == "item" {
+        state.enum_item_value = attr_value(e, b"value");
+        state.enum_item_text.clear();
+        return Ok(());
+    }
+    if tag == "enum" {
+        state.in_enum = true;
+        state.enum_repr = attr_value(e, b"repr");
+        state.enum_items.clear();
     }
 
     // Initialize template state FIRST (before processing parameters)
@@ -49,10 +124,29 @@ pub(super) fn handle_start(
         None
     };
 
+    // Attribute form: `<string name="greeting" template="Hello {name}!">`
+    // with `<param name="name" type="string"/>` children
+    if tag == "string" && !state.in_template {
+        if let Some(text) = attr_value(e, b"template") {
+            state.in_template = true;
+            state.template_from_attribute = true;
+            state.template_params.clear();
+            state.template_text = text;
+            state.current_attributes = resource_attributes(e);
+            state.current_attributes.remove("template");
+            state.current_name = param_name;
+            return Ok(());
+        }
+    }
+    if state.in_template && tag == "param" {
+        return push_template_param(state, e);
+    }
+
     // Handle template parameters: if we're inside a template, treat standard tags as parameters
     // Reuse existing parsing logic by creating ScalarValue directly from attributes
     if state.in_template && tag != "template" {
-        if let Some(param_name_str) = &param_name {
+        // Parameters are named as their placeholders, not namespaced
+        if let Some(param_name_str) = &name_attr {
             let param_value = match tag.as_str() {
                 "string" => Some(crate::generator::parsing::ScalarValue::Text(String::new())), // Empty for params
                 "number" | "int" | "float" => {
@@ -70,94 +164,465 @@ pub(super) fn handle_start(
                 state.template_params.push(TemplateParam {
                     name: param_name_str.clone(),
                     value,
+                    order: attr_value(e, b"order"),
                 });
                 // Reset current_tag to "template" so text is captured correctly
                 state.current_tag = "template".to_string();
                 // Don't set current_name for template parameters
-                return;
+                return Ok(());
             }
         }
     }
 
     // Set state for normal resource processing
+    state.current_attributes = resource_attributes(e);
     state.current_number_type = number_type;
+    state.current_allow_over_100 = tag == "percent"
+        && attr_value(e, b"allow-over-100").as_deref() == Some("true");
     state.current_name = param_name;
+    Ok(())
+}
+
+/// `<param name="count" type="int"/>` of a template; a missing `type` is a
+/// string
+fn push_template_param(
+    state: &mut ParseState,
+    e: &BytesStart<'_>,
+) -> Result<(), TextError> {
+    // Text after the parameter still belongs to an element-form body
+    if !state.template_from_attribute {
+        state.current_tag = "template".to_string();
+    }
+    let Some(name) = attr_value(e, b"name") else {
+        return Ok(());
+    };
+    let ty = attr_value(e, b"type").unwrap_or_else(|| "string".to_string());
+    let value = match ty.as_str() {
+        "string" => ScalarValue::Text(String::new()),
+        "bool" => ScalarValue::Bool(false),
+        "color" => ScalarValue::Color(String::new()),
+        "int" | "number" | "float" | "i8" | "i16" | "i32" | "i64" | "u8"
+        | "u16" | "u32" | "u64" | "f32" | "f64" | "bigdecimal" => {
+            let explicit_type = match ty.as_str() {
+                "int" | "number" => None,
+                "float" => Some("f64".to_string()),
+                _ => Some(ty.clone()),
+            };
+            ScalarValue::Number {
+                value: String::new(),
+                explicit_type,
+            }
+        }
+        _ => {
+            return Err(TextError::Value {
+                name: state.current_name.clone().unwrap_or_default(),
+                message: format!(
+                    "parameter '{name}' has unsupported type '{ty}'"
+                ),
+            })
+        }
+    };
+    state.template_params.push(TemplateParam {
+        name,
+        value,
+        order: attr_value(e, b"order"),
+    });
+    Ok(())
+}
+
+/// Records `require-locales="en,fr"` on the namespace just entered
+fn push_locale_requirement(state: &mut ParseState, e: &BytesStart<'_>) {
+    let Some(list) = attr_value(e, b"require-locales") else {
+        return;
+    };
+    let locales = list
+        .split(',')
+        .map(str::trim)
+        .filter(|locale| !locale.is_empty())
+        .map(str::to_string)
+        .collect();
+    state.locale_requirements.push(ParsedLocaleRequirement {
+        namespace: state.namespace_stack.clone(),
+        locales,
+    });
+}
+
+/// Records `visibility="crate"` on the namespace just entered; resources
+/// below inherit it until the namespace ends
+fn push_namespace_visibility(state: &mut ParseState, e: &BytesStart<'_>) {
+    let Some(visibility) = attr_value(e, b"visibility") else {
+        return;
+    };
+    state
+        .visibility_stack
+        .push((state.namespace_stack.len(), visibility.clone()));
+    state.namespace_visibilities.push(ParsedNamespaceVisibility {
+        namespace: state.namespace_stack.clone(),
+        visibility,
+    });
 }
 
+/// Records `feature="premium"` on the namespace just entered
+fn push_namespace_feature(state: &mut ParseState, e: &BytesStart<'_>) {
+    let Some(feature) = attr_value(e, b"feature") else {
+        return;
+    };
+    state.namespace_features.push(ParsedNamespaceFeature {
+        namespace: state.namespace_stack.clone(),
+        feature,
+    });
+}
+
+/// Records the namespace just entered, continuing another file's when it
+/// has `extend="true"`
+fn push_namespace_declaration(state: &mut ParseState, e: &BytesStart<'_>) {
+    state.namespace_declarations.push(ParsedNamespaceDeclaration {
+        namespace: state.namespace_stack.clone(),
+        extend: attr_value(e, b"extend").as_deref() == Some("true"),
+    });
+}
+
+/// Records `doc="..."` on the namespace just entered
+fn push_namespace_doc(state: &mut ParseState, e: &BytesStart<'_>) {
+    let Some(doc) = attr_value(e, b"doc") else {
+        return;
+    };
+    state.namespace_docs.push(ParsedNamespaceDoc {
+        namespace: state.namespace_stack.clone(),
+        doc,
+    });
+}
+
+/// Records `error-enum="ApiError"` on the namespace just entered
+fn push_error_enum(state: &mut ParseState, e: &BytesStart<'_>) {
+    let Some(name) = attr_value(e, b"error-enum") else {
+        return;
+    };
+    state.error_enums.push(ParsedErrorEnum {
+        namespace: state.namespace_stack.clone(),
+        name,
+    });
+}
+
+/// Handles a run of text (with entities already decoded) inside the current tag
 pub(super) fn handle_text(
     state: &mut ParseState,
-    text: &BytesText<'_>,
-) -> Option<ParsedResource> {
+    text: &str,
+) -> Result<Option<ParsedResource>, TextError> {
     // If we're inside a template, only accumulate text that's directly inside the template tag
+    // Runs are joined as written, so the text around parameter tags keeps
+    // its spacing; the body is trimmed once, when the template ends.
     if state.in_template {
         if state.current_tag == "template" {
-            let trimmed = text_to_string(text).trim().to_string();
-            if !trimmed.is_empty() {
-                state.template_text.push_str(&trimmed);
-                state.template_text.push(' ');
-            }
+            state.template_text.push_str(text);
         }
         // Don't create resources from parameter tags inside templates (they're already handled in handle_start)
-        return None;
+        return Ok(None);
     }
-    
-    if let Some(name) = &state.current_name {
-        let trimmed = text_to_string(text).trim().to_string();
-        if trimmed.is_empty() {
-            return None;
+
+    if state.in_mime_map {
+        if state.current_tag == "entry" {
+            state.mime_entry_text.push_str(text.trim());
         }
+        return Ok(None);
+    }
 
-        match state.current_tag.as_str() {
-            "string" => {
-                return Some(ParsedResource::string(name, trimmed))
+    if state.in_array {
+        if state.current_tag == "item" {
+            state.array_item_text.push_str(text.trim());
+            // Checked here, where a bad reference can still be reported
+            parse_reference(&state.array_item_text, &state.namespace_stack)?;
+        }
+        return Ok(None);
+    }
+
+    if state.in_enum {
+        if state.current_tag == "item" {
+            state.enum_item_text.push_str(text.trim());
+        }
+        return Ok(None);
+    }
+
+    let Some(name) = &state.current_name else {
+        return Ok(None);
+    };
+    // Whitespace is significant in a character literal
+    if state.current_tag == "char" {
+        return char_resource(name.clone(), text).map(Some);
+    }
+    if state.current_tag == "string" {
+        if let Some(exact) = exact_string_text(state, name, text)? {
+            return Ok(Some(string_resource(name.clone(), exact, state)?));
+        }
+    }
+    let trimmed = text.trim().to_string();
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
+
+    // References (`@string/name`, `@./name`) are normalized to absolute paths here,
+    // while the namespace stack is known
+    if let Some(kind) = referencing_kind(&state.current_tag) {
+        let reference =
+            parse_reference(&trimmed, &state.namespace_stack)?;
+        if let Some(value) = reference {
+            return Ok(Some(ParsedResource {
+                name: name.clone(),
+                kind,
+                value,
+                attributes: Default::default(),
+            }));
+        }
+    }
+
+    match state.current_tag.as_str() {
+        "string" => {
+            return Ok(Some(string_resource(name.clone(), trimmed, state)?))
+        }
+        "number" | "int" | "float" => {
+            return Ok(Some(ParsedResource::number(
+                name,
+                trimmed,
+                state.current_number_type.clone(),
+            )));
+        }
+        "bool" => {
+            if let Ok(b) = trimmed.parse::<bool>() {
+                return Ok(Some(ParsedResource::bool(name, b)));
             }
-            "number" | "int" | "float" => {
-                return Some(ParsedResource::number(
-                    name,
+            // Kept as text so the bool type reports it with the file
+            return Ok(Some(ParsedResource {
+                name: name.clone(),
+                kind: ResourceKind::Bool,
+                value: ScalarValue::Text(trimmed),
+                attributes: Default::default(),
+            }));
+        }
+        "cron" => {
+            return Ok(Some(ParsedResource {
+                name: name.clone(),
+                kind: ResourceKind::Cron,
+                value: ScalarValue::Cron(trimmed),
+                attributes: Default::default(),
+            }));
+        }
+        "size" => {
+            return Ok(Some(ParsedResource {
+                name: name.clone(),
+                kind: ResourceKind::Size,
+                value: ScalarValue::Size(trimmed),
+                attributes: Default::default(),
+            }));
+        }
+        "datetime" => {
+            return Ok(Some(ParsedResource {
+                name: name.clone(),
+                kind: ResourceKind::DateTime,
+                value: ScalarValue::DateTime(trimmed),
+                attributes: Default::default(),
+            }));
+        }
+        "date" => {
+            return Ok(Some(ParsedResource {
+                name: name.clone(),
+                kind: ResourceKind::Date,
+                value: ScalarValue::Date(trimmed),
+                attributes: Default::default(),
+            }));
+        }
+        "version" => {
+            return Ok(Some(ParsedResource {
+                name: name.clone(),
+                kind: ResourceKind::Version,
+                value: ScalarValue::Version(trimmed),
+                attributes: Default::default(),
+            }));
+        }
+        "dimension" => {
+            return Ok(Some(ParsedResource {
+                name: name.clone(),
+                kind: ResourceKind::Dimension,
+                value: ScalarValue::Dimension(trimmed),
+                attributes: Default::default(),
+            }));
+        }
+        "percent" => {
+            return Ok(Some(ParsedResource {
+                name: name.clone(),
+                kind: ResourceKind::Percent,
+                value: ScalarValue::Percent {
+                    value: trimmed,
+                    allow_over_100: state.current_allow_over_100,
+                },
+                attributes: Default::default(),
+            }));
+        }
+        "color" => {
+            return Ok(Some(ParsedResource {
+                name: name.clone(),
+                kind: crate::generator::parsing::ResourceKind::Color,
+                value: crate::generator::parsing::ScalarValue::Color(
                     trimmed,
-                    state.current_number_type.clone(),
-                ));
-            }
-            "bool" => {
-                if let Ok(b) = trimmed.parse::<bool>() {
-                    return Some(ParsedResource::bool(name, b));
-                }
-            }
-            "color" => {
-                return Some(ParsedResource {
-                    name: name.clone(),
-                    kind:
-                        crate::generator::parsing::ResourceKind::Color,
-                    value:
-                        crate::generator::parsing::ScalarValue::Color(
-                            trimmed,
-                        ),
-                });
-            }
-            "template" => {
-                // Accumulate text for templates (may be called multiple times)
-                state.template_text.push_str(&trimmed);
-                state.template_text.push(' ');
-            }
-            _ => {}
+                ),
+                attributes: Default::default(),
+            }));
+        }
+        "template" => {
+            // Accumulate text for templates (may be called multiple times)
+            state.template_text.push_str(text);
         }
+        tag if !is_builtin_tag(tag) => {
+            return Ok(Some(ParsedResource {
+                name: name.clone(),
+                kind: ResourceKind::Custom(tag.to_string()),
+                value: ScalarValue::Text(trimmed),
+                attributes: Default::default(),
+            }));
+        }
+        _ => {}
+    }
+    Ok(None)
+}
+
+/// Tags understood by the reader itself; any other tag with a `name` is
+/// parsed as a custom resource
+fn is_builtin_tag(tag: &str) -> bool {
+    matches!(
+        tag,
+        "resources" | "ns" | "assert" | "string" | "number" | "int"
+            | "float" | "bool" | "color" | "char" | "percent" | "cron"
+            | "size" | "datetime" | "date" | "version" | "dimension"
+            | "mime-map" | "entry"
+            | "enum" | "item" | "template" | "param"
+    ) || array_item_type(tag).is_some()
+}
+
+/// Item type of an array tag (`"string"` for `<string-array>`)
+fn array_item_type(tag: &str) -> Option<&'static str> {
+    match tag {
+        "string-array" => Some("string"),
+        "int-array" => Some("int"),
+        "float-array" => Some("float"),
+        "duration-array" => Some("duration"),
+        "size-array" => Some("size"),
+        _ => None,
+    }
+}
+
+/// Builds a string resource, splitting embedded references into an
+/// interpolated value
+fn string_resource(
+    name: String,
+    text: String,
+    state: &ParseState,
+) -> Result<ParsedResource, ReferenceError> {
+    let Some(value) =
+        parse_interpolation(&text, &state.namespace_stack)?
+    else {
+        return Ok(ParsedResource::string(name, text));
+    };
+    Ok(ParsedResource {
+        name,
+        kind: ResourceKind::String,
+        value,
+        attributes: Default::default(),
+    })
+}
+
+/// Text of a `<string>` kept as written rather than trimmed: all of it
+/// with `xml:space="preserve"`, or what is between the double quotes
+/// wrapping it with `quoted="true"`; `None` without either attribute
+fn exact_string_text(
+    state: &ParseState,
+    name: &str,
+    text: &str,
+) -> Result<Option<String>, TextError> {
+    let attribute =
+        |key: &str| state.current_attributes.get(key).map(String::as_str);
+    if attribute("space") == Some("preserve") {
+        return Ok(Some(text.to_string()));
+    }
+    if attribute("quoted") != Some("true") {
+        return Ok(None);
+    }
+    let trimmed = text.trim();
+    let inner = trimmed
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .filter(|_| trimmed.len() >= 2);
+    match inner {
+        Some(inner) => Ok(Some(inner.to_string())),
+        None => Err(TextError::Value {
+            name: name.to_string(),
+            message: format!(
+                "quoted=\"true\" expects the value between double quotes, found '{trimmed}'"
+            ),
+        }),
+    }
+}
+
+fn char_resource(
+    name: String,
+    text: &str,
+) -> Result<ParsedResource, TextError> {
+    match parse_char(text) {
+        Ok(value) => Ok(ParsedResource {
+            name,
+            kind: ResourceKind::Char,
+            value: ScalarValue::Char(value),
+            attributes: Default::default(),
+        }),
+        Err(message) => Err(TextError::Value { name, message }),
+    }
+}
+
+/// Resource kind declared by a tag whose value may be a reference
+fn referencing_kind(tag: &str) -> Option<ResourceKind> {
+    match tag {
+        "string" => Some(ResourceKind::String),
+        "number" | "int" | "float" => Some(ResourceKind::Number),
+        "bool" => Some(ResourceKind::Bool),
+        "color" => Some(ResourceKind::Color),
+        _ => None,
     }
-    None
 }
 
 pub(super) fn handle_end(
     state: &mut ParseState,
     e: &BytesEnd<'_>,
 ) -> Option<ParsedResource> {
-    let tag = to_string(e.name().as_ref());
+    let tag = state.canonical_tag(to_string(e.local_name().as_ref()));
 
     if tag == "ns" {
         state.namespace_stack.pop();
+        let depth = state.namespace_stack.len();
+        state.visibility_stack.retain(|(declared_at, _)| *declared_at <= depth);
         return None;
     }
 
+    if state.in_mime_map && matches!(tag.as_str(), "entry" | "mime-map") {
+        return end_mime_map_tag(state, &tag);
+    }
+    if state.in_array
+        && (matches!(tag.as_str(), "item" | "row")
+            || array_item_type(&tag).is_some())
+    {
+        return end_array_tag(state, &tag);
+    }
+    if state.in_enum && matches!(tag.as_str(), "item" | "enum") {
+        return end_enum_tag(state, &tag);
+    }
+
+    if state.in_template && tag == "param" {
+        return None;
+    }
     // Finalize template when closing tag is encountered
-    if tag == "template" {
+    let closes_template = if state.template_from_attribute {
+        tag == "string"
+    } else {
+        tag == "template"
+    };
+    if closes_template {
         let name = state.current_name.clone();
         if let Some(name) = name {
             let text = state.template_text.trim().to_string();
@@ -165,6 +630,7 @@ pub(super) fn handle_end(
             
             // Reset template state
             state.in_template = false;
+            state.template_from_attribute = false;
             state.template_params.clear();
             state.template_text.clear();
             state.current_name = None;
@@ -176,24 +642,30 @@ pub(super) fn handle_end(
                     text,
                     params,
                 },
+                attributes: Default::default(),
             });
         }
     }
     
     // When inside a template, don't process closing tags of parameter tags as resources
     // (they're already handled in handle_start)
-    if state.in_template {
-        if matches!(tag.as_str(), "string" | "number" | "int" | "float" | "bool" | "color") {
-            // These are template parameters, not resources - just clear current_name
-            state.current_name = None;
-            return None;
-        }
+    if state.in_template
+        && matches!(
+            tag.as_str(),
+            "string" | "number" | "int" | "float" | "bool" | "color"
+        )
+    {
+        // These are template parameters, not resources - just clear current_name
+        state.current_name = None;
+        return None;
     }
 
     if matches!(
         tag.as_str(),
-        "string" | "number" | "int" | "float" | "bool" | "color" | "template"
-    ) {
+        "string" | "number" | "int" | "float" | "bool" | "color" | "char"
+            | "percent" | "cron" | "template"
+    ) || !is_builtin_tag(&tag)
+    {
         state.current_name = None;
     }
     if matches!(tag.as_str(), "number" | "int" | "float") {
@@ -202,3 +674,113 @@ pub(super) fn handle_end(
     state.current_tag.clear();
     None
 }
+
+/// Whether the tag just started is a collection or one of its children,
+/// which a self-closing form has to end right away: `<mime-map
+/// name="x"/>` is an empty map, not one swallowing the rest of the file
+pub(super) fn ends_when_empty(state: &ParseState) -> bool {
+    let tag = state.current_tag.as_str();
+    (state.in_mime_map && matches!(tag, "entry" | "mime-map"))
+        || (state.in_array
+            && (matches!(tag, "item" | "row") || array_item_type(tag).is_some()))
+        || (state.in_enum && matches!(tag, "item" | "enum"))
+}
+
+/// Closes an `<entry>` (recording it) or the `<mime-map>` itself
+fn end_mime_map_tag(
+    state: &mut ParseState,
+    tag: &str,
+) -> Option<ParsedResource> {
+    if tag == "entry" {
+        state.mime_entries.push(MimeEntry {
+            ext: std::mem::take(&mut state.mime_entry_ext),
+            mime: std::mem::take(&mut state.mime_entry_text),
+        });
+        state.current_tag = "mime-map".to_string();
+        return None;
+    }
+
+    state.in_mime_map = false;
+    state.current_tag.clear();
+    let entries = std::mem::take(&mut state.mime_entries);
+    Some(ParsedResource {
+        name: state.current_name.take()?,
+        kind: ResourceKind::MimeMap,
+        value: ScalarValue::MimeMap(entries),
+        attributes: Default::default(),
+    })
+}
+
+/// Closes an `<item>` (recording it) or the `<enum>` itself
+fn end_enum_tag(state: &mut ParseState, tag: &str) -> Option<ParsedResource> {
+    if tag == "item" {
+        state.enum_items.push(EnumItem {
+            name: std::mem::take(&mut state.enum_item_text),
+            value: state.enum_item_value.take(),
+        });
+        state.current_tag = "enum".to_string();
+        return None;
+    }
+
+    state.in_enum = false;
+    state.current_tag.clear();
+    let items = std::mem::take(&mut state.enum_items);
+    Some(ParsedResource {
+        name: state.current_name.take()?,
+        kind: ResourceKind::Enum,
+        value: ScalarValue::Enum {
+            repr: state.enum_repr.take(),
+            items,
+        },
+        attributes: Default::default(),
+    })
+}
+
+/// Closes an array `<item>` (recording it in the current row, if any), a
+/// `<row>`, or the array itself
+fn end_array_tag(state: &mut ParseState, tag: &str) -> Option<ParsedResource> {
+    state.current_tag.clear();
+    let Some(item_type) = array_item_type(tag) else {
+        if tag == "row" {
+            state.in_array_row = false;
+            return None;
+        }
+        let text = std::mem::take(&mut state.array_item_text);
+        let item = array_item(text, &state.namespace_stack);
+        match state.array_rows.last_mut() {
+            Some(row) if state.in_array_row => row.push(item),
+            _ => state.array_items.push(item),
+        }
+        return None;
+    };
+
+    state.in_array = false;
+    state.in_array_row = false;
+    let items = std::mem::take(&mut state.array_items);
+    let rows = std::mem::take(&mut state.array_rows);
+    let value = if rows.is_empty() {
+        ScalarValue::Array(items)
+    } else {
+        ScalarValue::ArrayRows {
+            rows,
+            stray_items: items,
+        }
+    };
+    Some(ParsedResource {
+        name: state.current_name.take()?,
+        kind: ResourceKind::Array(item_type.to_string()),
+        value,
+        attributes: Default::default(),
+    })
+}
+
+/// An item whose whole text is a reference (`@string/api/error`) keeps it,
+/// with relative paths resolved; anything else is literal text
+fn array_item(text: String, namespace: &[String]) -> ArrayItem {
+    match parse_reference(&text, namespace) {
+        Ok(Some(ScalarValue::Reference { kind, path })) => {
+            ArrayItem::Reference { kind, path }
+        }
+        _ => ArrayItem::Text(text),
+    }
+}
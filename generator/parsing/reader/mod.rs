@@ -1,66 +1,209 @@
 //! Streaming XML reader that converts [`RawResourceFile`] into parsed resources.
 
+mod chars;
 mod handlers;
+mod references;
+mod regions;
 mod state;
 mod utils;
 
-use quick_xml::events::Event;
+use std::path::Path;
+
+use quick_xml::events::{BytesStart, Event};
 use quick_xml::Reader;
 
 use crate::generator::input::RawResourceFile;
 
-use super::ast::ParsedResourceFile;
+use super::ast::{ParsedResource, ParsedResourceFile};
 use super::error::ParserError;
-use handlers::{handle_end, handle_start, handle_text};
+use super::ParseOptions;
+pub(crate) use regions::REGION;
+use handlers::{
+    ends_when_empty, handle_end, handle_start, handle_text, TextError,
+};
+use references::parse_reference;
 use state::ParseState;
+use utils::{attr_value, file_namespace, resolve_entity, text_to_string};
 
 pub(super) fn parse_single_file(
     raw: &RawResourceFile,
+    options: &ParseOptions<'_>,
 ) -> Result<ParsedResourceFile, ParserError> {
+    // Text is trimmed per resource once entities are merged back in,
+    // so `a &amp; b` keeps its spaces
     let mut reader = Reader::from_str(&raw.contents);
-    reader.config_mut().trim_text(true);
 
     let mut buf = Vec::new();
-    let mut state = ParseState::default();
+    let mut state = ParseState {
+        tag_aliases: options.tag_aliases.iter().cloned().collect(),
+        ..Default::default()
+    };
+    // Explicit <ns> elements nest inside the file namespace
+    if options.file_as_namespace {
+        state.namespace_stack.extend(file_namespace(&raw.path));
+    }
     let mut resources = Vec::new();
 
     loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(e)) => handle_start(&mut state, &e),
-            Ok(Event::Empty(e)) => {
-                // Handle self-closing tags like <param name="..." type="..."/>
-                handle_start(&mut state, &e);
-            }
-            Ok(Event::Text(e)) => {
-                if let Some(res) = handle_text(&mut state, &e) {
-                    resources.push(res);
-                }
-            }
-            Ok(Event::End(e)) => {
-                if let Some(res) = handle_end(&mut state, &e) {
-                    resources.push(res);
-                }
-            }
-            Ok(Event::Eof) => break,
-            Err(err) => {
-                return Err(ParserError::Xml {
+        let event =
+            reader.read_event_into(&mut buf).map_err(|err| {
+                ParserError::Xml {
                     path: raw.path.clone(),
                     message: format!(
                         "XML error at byte {}: {err}",
                         reader.buffer_position()
                     ),
-                });
+                }
+            })?;
+        match event {
+            Event::Text(e) => {
+                state.pending_text.push_str(&text_to_string(&e));
+            }
+            // Character data is text, taken as written
+            Event::CData(e) => {
+                state.pending_text.push_str(&String::from_utf8_lossy(&e));
+            }
+            Event::GeneralRef(e) => {
+                let text = resolve_entity(&e).map_err(|message| {
+                    ParserError::Xml {
+                        path: raw.path.clone(),
+                        message,
+                    }
+                })?;
+                state.pending_text.push_str(&text);
+            }
+            Event::Start(e) => {
+                resources.extend(flush_text(&mut state, &raw.path)?);
+                handle_start(&mut state, &e)
+                    .map_err(|err| text_error(&raw.path, err))?;
+            }
+            // Self-closing tags like <param name="..." type="..."/>
+            Event::Empty(e) => {
+                resources.extend(flush_text(&mut state, &raw.path)?);
+                resources.extend(empty_element(&mut state, &e, &raw.path)?);
             }
+            Event::End(e) => {
+                resources.extend(flush_text(&mut state, &raw.path)?);
+                let ended = handle_end(&mut state, &e);
+                resources
+                    .extend(ended.map(|res| state.attach_attributes(res)));
+            }
+            Event::Comment(e) => {
+                let end = usize::try_from(reader.buffer_position())
+                    .unwrap_or(usize::MAX);
+                let comment = String::from_utf8_lossy(&e);
+                regions::handle_comment(&mut state, raw, &comment, end);
+            }
+            Event::Eof => break,
             _ => {}
         }
         buf.clear();
     }
+    regions::close_open_regions(&mut state);
+
+    let mut file =
+        ParsedResourceFile::new(raw.path.clone(), raw.is_test, resources);
+    file.assertions = std::mem::take(&mut state.assertions);
+    file.locale_requirements =
+        std::mem::take(&mut state.locale_requirements);
+    file.namespace_visibilities =
+        std::mem::take(&mut state.namespace_visibilities);
+    file.namespace_features = std::mem::take(&mut state.namespace_features);
+    file.namespace_docs = std::mem::take(&mut state.namespace_docs);
+    file.namespace_declarations =
+        std::mem::take(&mut state.namespace_declarations);
+    file.error_enums = std::mem::take(&mut state.error_enums);
+    file.unbalanced_regions = std::mem::take(&mut state.unbalanced_regions);
+    Ok(file)
+}
+
+/// Hands the text accumulated since the last tag to the current resource.
+/// A `<char>` gets it even when there is none, since no text is not one
+/// character either.
+fn flush_text(
+    state: &mut ParseState,
+    path: &Path,
+) -> Result<Option<ParsedResource>, ParserError> {
+    let open_char =
+        state.current_tag == "char" && state.current_name.is_some();
+    if state.pending_text.is_empty() && !open_char {
+        return Ok(None);
+    }
+    let text = std::mem::take(&mut state.pending_text);
+    let resource =
+        handle_text(state, &text).map_err(|err| text_error(path, err))?;
+    Ok(resource.map(|res| state.attach_attributes(res)))
+}
+
+fn text_error(path: &Path, err: TextError) -> ParserError {
+    match err {
+        TextError::Reference(err) => ParserError::InvalidReference {
+            path: path.to_path_buf(),
+            reference: err.reference,
+            message: err.message,
+        },
+        TextError::Value { name, message } => ParserError::InvalidValue {
+            path: path.to_path_buf(),
+            name,
+            message,
+        },
+    }
+}
+
+/// A self-closing tag. A template it opens (`<string name="x"
+/// template="Hi"/>`) has no parameters and no end tag, so it ends here,
+/// like a collection or collection item.
+fn empty_element(
+    state: &mut ParseState,
+    e: &BytesStart<'_>,
+    path: &Path,
+) -> Result<Option<ParsedResource>, ParserError> {
+    let opens_template = !state.in_template;
+    handle_start(state, e).map_err(|err| text_error(path, err))?;
+    if (opens_template && state.in_template) || ends_when_empty(state) {
+        let ended = handle_end(state, &e.to_end());
+        return Ok(ended.map(|res| state.attach_attributes(res)));
+    }
+    let reference = attr_value(e, b"ref");
+    // Not the whitespace after the tag
+    if state.current_tag == "char" && reference.is_none() {
+        let resource = flush_text(state, path);
+        state.current_name = None;
+        return resource;
+    }
+    ref_resource(state, reference, path)
+}
 
-    Ok(ParsedResourceFile::new(
-        raw.path.clone(),
-        raw.is_test,
-        resources,
-    ))
+/// `<string name="..." ref="@build/version"/>`: the resource takes the
+/// referenced value, as if the reference were its text
+fn ref_resource(
+    state: &mut ParseState,
+    reference: Option<String>,
+    path: &Path,
+) -> Result<Option<ParsedResource>, ParserError> {
+    let Some(reference) = reference else {
+        return Ok(None);
+    };
+    if state.current_name.is_none() {
+        return Ok(None);
+    }
+    let invalid = |reference, message| ParserError::InvalidReference {
+        path: path.to_path_buf(),
+        reference,
+        message,
+    };
+    match parse_reference(&reference, &state.namespace_stack) {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            let message = "ref must be a reference like @string/name";
+            return Err(invalid(reference, message.to_string()));
+        }
+        Err(err) => return Err(invalid(err.reference, err.message)),
+    }
+    state.pending_text = reference;
+    let resource = flush_text(state, path);
+    state.current_name = None;
+    resource
 }
 
 #[cfg(test)]
@@ -68,8 +211,9 @@ mod tests {
     use std::path::PathBuf;
 
     use crate::generator::input::RawResourceFile;
+    use crate::generator::parsing::{ArrayItem, ResourceKind, ScalarValue};
 
-    use super::parse_single_file;
+    use super::{file_namespace, parse_single_file, ParseOptions, Path};
 
     #[test]
     fn parse_basic_string() {
@@ -79,7 +223,7 @@ mod tests {
             false,
         );
 
-        let file = parse_single_file(&raw).unwrap();
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
         assert_eq!(file.resources.len(), 1);
         assert_eq!(file.resources[0].name, "app_name");
     }
@@ -99,11 +243,61 @@ mod tests {
             false,
         );
 
-        let file = parse_single_file(&raw).unwrap();
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
         assert_eq!(file.resources.len(), 1);
         assert_eq!(file.resources[0].name, "auth/title");
     }
 
+    #[test]
+    fn parse_prefixed_tags_and_attributes() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("export.xml"),
+            r#"
+<res:resources xmlns:res="urn:example:res">
+    <res:ns res:name="auth">
+        <res:string name="title" xmlns:x="urn:x" x:visibility="crate">Login</res:string>
+        <res:bool res:name="remember">true</res:bool>
+    </res:ns>
+</res:resources>
+"#
+            .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
+        assert_eq!(file.resources.len(), 2);
+        let title = &file.resources[0];
+        assert_eq!(title.name, "auth/title");
+        assert_eq!(title.kind, ResourceKind::String);
+        assert_eq!(title.value, ScalarValue::Text("Login".into()));
+        // The `xmlns:x` declaration is not an attribute of the resource
+        assert_eq!(
+            title.attributes.keys().collect::<Vec<_>>(),
+            ["visibility"]
+        );
+        assert_eq!(file.resources[1].name, "auth/remember");
+        assert_eq!(file.resources[1].value, ScalarValue::Bool(true));
+    }
+
+    #[test]
+    fn parse_default_namespaced_document() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources xmlns="http://schemas.example.com/res">
+                <string name="app_name" xmlns="urn:other">Demo</string>
+                <number name="max_items">10</number>
+            </resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
+        assert_eq!(file.resources.len(), 2);
+        assert_eq!(file.resources[0].name, "app_name");
+        assert!(file.resources[0].attributes.is_empty());
+        assert_eq!(file.resources[1].kind, ResourceKind::Number);
+    }
+
     #[test]
     fn parse_numbers_and_bools() {
         let raw = RawResourceFile::new(
@@ -121,7 +315,7 @@ mod tests {
             false,
         );
 
-        let file = parse_single_file(&raw).unwrap();
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
         assert_eq!(file.resources.len(), 5);
 
         let max_retries = file
@@ -170,7 +364,7 @@ mod tests {
             false,
         );
 
-        let file = parse_single_file(&raw).unwrap();
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
         assert_eq!(file.resources.len(), 4);
 
         let small = file
@@ -202,7 +396,7 @@ mod tests {
             false,
         );
 
-        let file = parse_single_file(&raw).unwrap();
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
         assert_eq!(file.resources.len(), 2);
 
         let primary = file
@@ -241,7 +435,7 @@ mod tests {
             false,
         );
 
-        let file = parse_single_file(&raw).unwrap();
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
         assert_eq!(file.resources.len(), 1);
 
         let template = file
@@ -270,6 +464,119 @@ mod tests {
         }
     }
 
+    #[test]
+    fn template_text_keeps_its_spacing() {
+        let template_text = |xml: &str| {
+            let raw = RawResourceFile::new(
+                PathBuf::from("values.xml"),
+                format!("<resources>{xml}</resources>"),
+                false,
+            );
+            let file = parse_single_file(&raw, &Default::default()).unwrap();
+            match &file.resources[0].value {
+                crate::generator::parsing::ScalarValue::Template {
+                    text,
+                    ..
+                } => text.clone(),
+                other => panic!("Expected Template value, got {other:?}"),
+            }
+        };
+        let multi_line = r#"
+    <template name="greet">
+        <string name="name"/>
+        Hello
+          {name},  welcome!
+    </template>"#;
+        assert_eq!(
+            template_text(multi_line),
+            "Hello\n          {name},  welcome!"
+        );
+        // Parameters between runs of text leave no extra space behind
+        let inline =
+            r#"<template name="t">Hi <string name="n"/>{n}!</template>"#;
+        assert_eq!(template_text(inline), "Hi {n}!");
+        let cdata = concat!(
+            r#"<template name="t"><string name="n"/>"#,
+            "\n  <![CDATA[<b>{n}</b>  &]]> &amp; more\n</template>",
+        );
+        assert_eq!(template_text(cdata), "<b>{n}</b>  & & more");
+    }
+
+    #[test]
+    fn parse_template_attribute_form() {
+        use crate::generator::parsing::ScalarValue;
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"
+<resources>
+    <ns name="ui">
+        <string name="greeting" template="Hello {name}, you have {count} messages!" visibility="crate">
+            <param name="name" type="string"/>
+            <param name="count" type="u32"></param>
+            <param name="ratio" type="float"/>
+            <param name="label"/>
+        </string>
+        <string name="motd" template="Welcome!"/>
+        <string name="title">Inbox</string>
+    </ns>
+</resources>
+"#
+            .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
+        assert_eq!(file.resources.len(), 3);
+        let greeting = &file.resources[0];
+        assert_eq!(greeting.name, "ui/greeting");
+        assert_eq!(
+            greeting.kind,
+            crate::generator::parsing::ResourceKind::Template
+        );
+        // `template` is consumed; other attributes are kept
+        assert_eq!(greeting.attributes.len(), 1);
+        assert_eq!(greeting.attributes["visibility"], "crate");
+        let ScalarValue::Template { text, params } = &greeting.value else {
+            panic!("Expected Template value, got {:?}", greeting.value);
+        };
+        assert_eq!(text, "Hello {name}, you have {count} messages!");
+        let names: Vec<&str> = params.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["name", "count", "ratio", "label"]);
+        assert!(matches!(params[0].value, ScalarValue::Text(_)));
+        let number_type = |value: &ScalarValue| match value {
+            ScalarValue::Number { explicit_type, .. } => explicit_type.clone(),
+            other => panic!("Expected Number value, got {other:?}"),
+        };
+        assert_eq!(number_type(&params[1].value).as_deref(), Some("u32"));
+        assert_eq!(number_type(&params[2].value).as_deref(), Some("f64"));
+        assert!(matches!(params[3].value, ScalarValue::Text(_)));
+
+        let motd = &file.resources[1];
+        assert_eq!(motd.name, "ui/motd");
+        assert!(matches!(
+            &motd.value,
+            ScalarValue::Template { text, params }
+                if text == "Welcome!" && params.is_empty()
+        ));
+        assert_eq!(file.resources[2].value.as_text(), Some("Inbox"));
+    }
+
+    #[test]
+    fn template_param_with_unknown_type_is_an_error() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources><string name="t" template="{w}">
+                <param name="w" type="widget"/>
+            </string></resources>"#
+                .into(),
+            false,
+        );
+        let err = parse_single_file(&raw, &Default::default()).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("'w' has unsupported type 'widget'"));
+        assert!(message.contains("values.xml"), "{message}");
+    }
+
     #[test]
     fn parse_template_with_explicit_type() {
         let raw = RawResourceFile::new(
@@ -287,7 +594,7 @@ mod tests {
             false,
         );
 
-        let file = parse_single_file(&raw).unwrap();
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
         assert_eq!(file.resources.len(), 1);
 
         let template = file
@@ -307,4 +614,678 @@ mod tests {
             panic!("Expected Template value");
         }
     }
+    #[test]
+    fn parse_relative_references() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"
+<resources>
+    <ns name="ui">
+        <ns name="colors">
+            <color name="primary">#3366FF</color>
+            <color name="accent">@./primary</color>
+            <string name="padding_label">@../dimens/padding</string>
+            <string name="title">@string/app_name</string>
+        </ns>
+    </ns>
+</resources>
+"#
+            .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
+        let find = |name: &str| {
+            file.resources
+                .iter()
+                .find(|r| r.name == name)
+                .unwrap()
+                .value
+                .clone()
+        };
+        assert_eq!(
+            find("ui/colors/accent").as_reference(),
+            Some("ui/colors/primary")
+        );
+        assert_eq!(
+            find("ui/colors/padding_label").as_reference(),
+            Some("ui/dimens/padding")
+        );
+        assert_eq!(
+            find("ui/colors/title").as_reference(),
+            Some("app_name")
+        );
+    }
+
+    #[test]
+    fn parse_reference_escaping_root_fails() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"
+<resources>
+    <ns name="ui">
+        <string name="title">@../../app_name</string>
+    </ns>
+</resources>
+"#
+            .into(),
+            false,
+        );
+
+        let err = parse_single_file(&raw, &Default::default()).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("values.xml"));
+        assert!(message.contains("@../../app_name"));
+    }
+
+    #[test]
+    fn parse_cdata_as_text() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+                <string name="intro"><![CDATA[<b>Welcome</b> & enjoy]]></string>
+                <string name="mixed">a &amp; <![CDATA[<b>]]></string>
+            </resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
+        let text = |index: usize| file.resources[index].value.as_text();
+        assert_eq!(text(0), Some("<b>Welcome</b> & enjoy"));
+        assert_eq!(text(1), Some("a & <b>"));
+    }
+
+    #[test]
+    fn parse_strings_keeping_their_whitespace() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+                <string name="space" quoted="true">" "</string>
+                <string name="sep" quoted="true">
+                    " | "
+                </string>
+                <string name="empty" quoted="true">""</string>
+                <string name="line" xml:space="preserve"><![CDATA[Done.
+]]></string>
+                <string name="padded" xml:space="preserve">  a &amp; b </string>
+                <string name="trimmed"> " | " </string>
+            </resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
+        let text = |index: usize| file.resources[index].value.as_text();
+        assert_eq!(text(0), Some(" "));
+        assert_eq!(text(1), Some(" | "));
+        assert_eq!(text(2), Some(""));
+        assert_eq!(text(3), Some("Done.\n"));
+        assert_eq!(text(4), Some("  a & b "));
+        assert_eq!(text(5), Some("\" | \""));
+    }
+
+    #[test]
+    fn parse_quoted_string_without_quotes_fails() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources><string name="sep" quoted="true"> | </string></resources>"#
+                .into(),
+            false,
+        );
+
+        let message = parse_single_file(&raw, &Default::default()).unwrap_err().to_string();
+        assert!(message.contains("values.xml"), "{message}");
+        assert!(message.contains("'sep'"), "{message}");
+        assert!(message.contains("between double quotes"), "{message}");
+    }
+
+    #[test]
+    fn parse_ref_attribute() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"
+<resources>
+    <ns name="ui">
+        <string name="display_version" ref="@build/version"/>
+        <string name="title" ref="@./display_version"/>
+    </ns>
+    <param name="ignored"/>
+</resources>
+"#
+            .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
+        assert_eq!(file.resources.len(), 2);
+        assert_eq!(file.resources[0].name, "ui/display_version");
+        assert_eq!(
+            file.resources[0].value.as_reference(),
+            Some("build/version")
+        );
+        assert_eq!(
+            file.resources[1].value.as_reference(),
+            Some("ui/display_version")
+        );
+
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources><string name="x" ref="1.0"/></resources>"#.into(),
+            false,
+        );
+        let err = parse_single_file(&raw, &Default::default()).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("values.xml"), "{message}");
+        assert!(message.contains("ref must be a reference"), "{message}");
+    }
+
+    #[test]
+    fn parse_chars_with_entities_and_escapes() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"
+<resources>
+    <char name="path_sep">/</char>
+    <char name="space"> </char>
+    <char name="less_than">&lt;</char>
+    <char name="smiley">&#x1F600;</char>
+    <char name="newline">\n</char>
+    <char name="emoji">\u{1F600}</char>
+</resources>
+"#
+            .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
+        let chars: Vec<Option<char>> =
+            file.resources.iter().map(|r| r.value.as_char()).collect();
+        assert_eq!(
+            chars,
+            [
+                Some('/'),
+                Some(' '),
+                Some('<'),
+                Some('😀'),
+                Some('\n'),
+                Some('😀')
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_multi_character_char_fails() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources><char name="sep">::</char></resources>"#
+                .into(),
+            false,
+        );
+
+        let message = parse_single_file(&raw, &Default::default()).unwrap_err().to_string();
+        assert!(message.contains("values.xml"), "{message}");
+        assert!(message.contains("'sep'"), "{message}");
+        assert!(message.contains("found 2"), "{message}");
+    }
+
+    #[test]
+    fn parse_empty_char_fails() {
+        for xml in [
+            r#"<resources><char name="sep"></char></resources>"#,
+            "<resources>\n    <char name=\"sep\"/>\n    <string name=\"after\">x</string>\n</resources>",
+        ] {
+            let raw = RawResourceFile::new(
+                PathBuf::from("values.xml"),
+                xml.into(),
+                false,
+            );
+            let message = parse_single_file(&raw, &Default::default())
+                .unwrap_err()
+                .to_string();
+            assert!(message.contains("'sep'"), "{message}");
+            assert!(message.contains("exactly one character, found 0"), "{message}");
+        }
+    }
+
+    #[test]
+    fn parse_entities_inside_strings() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources><string name="terms">Terms &amp; Conditions</string></resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
+        assert_eq!(file.resources.len(), 1);
+        assert_eq!(
+            file.resources[0].value.as_text(),
+            Some("Terms & Conditions")
+        );
+    }
+
+    #[test]
+    fn parse_mime_map_entries() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"
+<resources>
+    <ns name="upload">
+        <mime-map name="types">
+            <entry ext="png">image/png</entry>
+            <entry ext="svg">image/svg+xml</entry>
+        </mime-map>
+        <string name="label">Upload</string>
+    </ns>
+</resources>
+"#
+            .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
+        assert_eq!(file.resources.len(), 2);
+        assert_eq!(file.resources[0].name, "upload/types");
+        let crate::generator::parsing::ScalarValue::MimeMap(entries) =
+            &file.resources[0].value
+        else {
+            panic!("expected mime-map, got {:?}", file.resources[0]);
+        };
+        let pairs: Vec<(&str, &str)> = entries
+            .iter()
+            .map(|e| (e.ext.as_str(), e.mime.as_str()))
+            .collect();
+        assert_eq!(pairs, [("png", "image/png"), ("svg", "image/svg+xml")]);
+        assert_eq!(file.resources[1].name, "upload/label");
+    }
+
+    #[test]
+    fn parse_self_closing_mime_map_as_empty() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <mime-map name="none"/>
+    <mime-map name="types"><entry ext="bin"/></mime-map>
+    <string name="after">x</string>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
+        let names: Vec<&str> =
+            file.resources.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["none", "types", "after"]);
+        assert_eq!(file.resources[0].value, ScalarValue::MimeMap(Vec::new()));
+        let ScalarValue::MimeMap(entries) = &file.resources[1].value else {
+            panic!("expected mime-map, got {:?}", file.resources[1]);
+        };
+        assert_eq!(entries.len(), 1);
+        assert_eq!((entries[0].ext.as_str(), entries[0].mime.as_str()), ("bin", ""));
+    }
+
+    #[test]
+    fn parse_assertions_with_their_namespace() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"
+<resources>
+    <assert expr="debug == false"/>
+    <ns name="db">
+        <number name="max_pool_size">20</number>
+        <assert expr="min_pool_size &lt;= max_pool_size &amp;&amp; max_pool_size > 0"/>
+    </ns>
+</resources>
+"#
+            .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
+        assert_eq!(file.resources.len(), 1);
+        assert_eq!(file.assertions.len(), 2);
+        assert_eq!(file.assertions[0].expr, "debug == false");
+        assert!(file.assertions[0].namespace.is_empty());
+        assert_eq!(
+            file.assertions[1].expr,
+            "min_pool_size <= max_pool_size && max_pool_size > 0"
+        );
+        assert_eq!(file.assertions[1].namespace, ["db"]);
+    }
+
+    #[test]
+    fn parse_unknown_tags_as_custom_resources() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"
+<resources>
+    <ns name="gfx">
+        <shader name="blur" stage="fragment">blur.wgsl</shader>
+    </ns>
+    <string name="title">Demo</string>
+</resources>
+"#
+            .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
+        assert_eq!(file.resources.len(), 2);
+        let shader = &file.resources[0];
+        assert_eq!(shader.name, "gfx/blur");
+        assert_eq!(shader.kind, ResourceKind::Custom("shader".to_string()));
+        assert_eq!(shader.value, ScalarValue::Text("blur.wgsl".to_string()));
+        assert_eq!(shader.attributes["stage"], "fragment");
+        assert_eq!(file.resources[1].name, "title");
+    }
+
+    #[test]
+    fn file_namespace_uses_stem_without_locale() {
+        let ns = |name: &str| file_namespace(Path::new(name));
+        assert_eq!(ns("res/auth.xml").as_deref(), Some("auth"));
+        assert_eq!(ns("res/user-settings.xml").as_deref(), Some("user_settings"));
+        assert_eq!(ns("res/auth.fr.xml").as_deref(), Some("auth"));
+        assert_eq!(ns("res/auth.pt-BR.xml").as_deref(), Some("auth"));
+        assert_eq!(ns("res/app.config.xml").as_deref(), Some("app_config"));
+        assert_eq!(ns("res/values.xml"), None);
+        assert_eq!(ns("res/values-fr.xml"), None);
+        assert_eq!(ns("res/values.en_US.xml"), None);
+    }
+
+    #[test]
+    fn parse_with_file_as_namespace_nests_ns_elements() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("res/auth.xml"),
+            r#"<resources>
+    <string name="title">Login</string>
+    <ns name="errors"><string name="denied">Denied</string></ns>
+    <assert expr="true"/>
+</resources>"#
+                .into(),
+            false,
+        );
+        let options = ParseOptions {
+            file_as_namespace: true,
+            ..Default::default()
+        };
+
+        let file = parse_single_file(&raw, &options).unwrap();
+        assert_eq!(file.resources[0].name, "auth/title");
+        assert_eq!(file.resources[1].name, "auth/errors/denied");
+        assert_eq!(file.assertions[0].namespace, ["auth"]);
+    }
+
+    #[test]
+    fn parse_namespace_visibility_is_inherited() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <ns name="internal" visibility="crate">
+        <string name="salt">abc</string>
+        <string name="shown" visibility="pub">x</string>
+        <template name="greet"><string name="who"/>Hi {who}</template>
+    </ns>
+    <string name="title">T</string>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
+        let visibility = |i: usize| {
+            file.resources[i].attributes.get("visibility").cloned()
+        };
+        assert_eq!(visibility(0).as_deref(), Some("crate"));
+        assert_eq!(visibility(1).as_deref(), Some("pub"));
+        assert_eq!(visibility(2).as_deref(), Some("crate"));
+        assert_eq!(visibility(3), None);
+        assert_eq!(file.namespace_visibilities.len(), 1);
+        assert_eq!(file.namespace_visibilities[0].namespace, ["internal"]);
+        assert_eq!(file.namespace_visibilities[0].visibility, "crate");
+    }
+
+    #[test]
+    fn parse_namespace_feature() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <ns name="premium" feature="premium">
+        <string name="banner">Gold</string>
+        <ns name="beta" feature="beta-ui">
+            <string name="key">k</string>
+        </ns>
+    </ns>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
+        assert_eq!(file.resources[1].name, "premium/beta/key");
+        assert_eq!(file.namespace_features.len(), 2);
+        assert_eq!(file.namespace_features[0].namespace, ["premium"]);
+        assert_eq!(file.namespace_features[0].feature, "premium");
+        assert_eq!(file.namespace_features[1].namespace, ["premium", "beta"]);
+        assert_eq!(file.namespace_features[1].feature, "beta-ui");
+    }
+
+    #[test]
+    fn parse_namespace_and_resource_docs() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <ns name="auth" doc="Sign-in screens &amp; errors">
+        <string name="cta" doc="Main call-to-action button label">Go</string>
+    </ns>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
+        assert_eq!(file.namespace_docs.len(), 1);
+        assert_eq!(file.namespace_docs[0].namespace, ["auth"]);
+        assert_eq!(file.namespace_docs[0].doc, "Sign-in screens & errors");
+        assert_eq!(
+            file.resources[0].attributes["doc"],
+            "Main call-to-action button label"
+        );
+    }
+
+    #[test]
+    fn parse_enum_items() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <enum name="priority" repr="u8">
+        <item value="0">low</item>
+        <item value="10">high</item>
+    </enum>
+    <string name="after">x</string>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
+        assert_eq!(file.resources.len(), 2);
+        let crate::generator::parsing::ScalarValue::Enum { repr, items } =
+            &file.resources[0].value
+        else {
+            panic!("expected enum, got {:?}", file.resources[0]);
+        };
+        assert_eq!(repr.as_deref(), Some("u8"));
+        let items: Vec<(&str, Option<&str>)> = items
+            .iter()
+            .map(|item| (item.name.as_str(), item.value.as_deref()))
+            .collect();
+        assert_eq!(items, [("low", Some("0")), ("high", Some("10"))]);
+        assert_eq!(file.resources[1].name, "after");
+    }
+
+    #[test]
+    fn parse_self_closing_enums_and_items() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <enum name="empty"/>
+    <enum name="level" repr="u8"><item value="2"/><item value="3">high</item></enum>
+    <string name="after">x</string>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
+        let names: Vec<&str> =
+            file.resources.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["empty", "level", "after"]);
+        let ScalarValue::Enum { items, .. } = &file.resources[0].value else {
+            panic!("expected enum, got {:?}", file.resources[0]);
+        };
+        assert!(items.is_empty());
+        let ScalarValue::Enum { items, .. } = &file.resources[1].value else {
+            panic!("expected enum, got {:?}", file.resources[1]);
+        };
+        // Recorded without a name, so the enum type reports it
+        let items: Vec<(&str, Option<&str>)> = items
+            .iter()
+            .map(|item| (item.name.as_str(), item.value.as_deref()))
+            .collect();
+        assert_eq!(items, [("", Some("2")), ("high", Some("3"))]);
+    }
+
+    #[test]
+    fn parse_array_items() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <string-array name="langs" sorted="true">
+        <item>fr</item>
+        <item>Tom &amp; Jerry</item>
+    </string-array>
+    <int-array name="sizes"><item>1</item><item>2</item></int-array>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
+        assert_eq!(file.resources.len(), 2);
+        let langs = &file.resources[0];
+        assert_eq!(langs.kind, ResourceKind::Array("string".to_string()));
+        assert_eq!(
+            langs.value,
+            crate::generator::parsing::ScalarValue::Array(vec![
+                "fr".into(),
+                "Tom & Jerry".into(),
+            ])
+        );
+        assert_eq!(langs.attributes.get("sorted").unwrap(), "true");
+        assert_eq!(file.resources[1].kind, ResourceKind::Array("int".into()));
+    }
+
+    #[test]
+    fn parse_array_rows() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <int-array name="level">
+        <row><item>0</item><item>1</item></row>
+        <row><item>2</item></row>
+    </int-array>
+    <int-array name="flat"><item>3</item></int-array>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
+        assert_eq!(file.resources.len(), 2);
+        assert_eq!(
+            file.resources[0].value,
+            ScalarValue::ArrayRows {
+                rows: vec![
+                    vec!["0".into(), "1".into()],
+                    vec!["2".into()],
+                ],
+                stray_items: Vec::new(),
+            }
+        );
+        assert_eq!(
+            file.resources[1].value,
+            ScalarValue::Array(vec!["3".into()])
+        );
+    }
+
+    #[test]
+    fn parse_self_closing_arrays_and_items() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <string-array name="none"/>
+    <int-array name="nothing"/>
+    <string-array name="blanks"><item/><item></item><item>x</item></string-array>
+    <int-array name="grid"><row/><row><item>1</item></row></int-array>
+    <string name="after">x</string>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
+        let names: Vec<&str> =
+            file.resources.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["none", "nothing", "blanks", "grid", "after"]);
+        assert_eq!(file.resources[0].value, ScalarValue::Array(Vec::new()));
+        assert_eq!(file.resources[1].kind, ResourceKind::Array("int".into()));
+        assert_eq!(file.resources[1].value, ScalarValue::Array(Vec::new()));
+        assert_eq!(
+            file.resources[2].value,
+            ScalarValue::Array(vec!["".into(), "".into(), "x".into()])
+        );
+        assert_eq!(
+            file.resources[3].value,
+            ScalarValue::ArrayRows {
+                rows: vec![Vec::new(), vec!["1".into()]],
+                stray_items: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_array_item_references() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <ns name="api">
+        <string-array name="errors">
+            <item>@string/api/unauthorized</item>
+            <item>@./not_found</item>
+            <item>See @string/help</item>
+            <item>me@example.com</item>
+        </string-array>
+    </ns>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
+        let reference =
+            |kind: Option<&str>, path: &str| ArrayItem::Reference {
+                kind: kind.map(ToString::to_string),
+                path: path.to_string(),
+            };
+        assert_eq!(
+            file.resources[0].value,
+            ScalarValue::Array(vec![
+                reference(Some("string"), "api/unauthorized"),
+                reference(None, "api/not_found"),
+                "See @string/help".into(),
+                "me@example.com".into(),
+            ])
+        );
+    }
 }
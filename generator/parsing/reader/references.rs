@@ -0,0 +1,287 @@
+//! Reference syntax (`@type/path`, `@./name`, `@../path`) recognized in resource values.
+//!
+//! Relative references are resolved against the namespace stack of the reader so that
+//! the rest of the pipeline only ever sees absolute paths. References embedded in a
+//! longer string (`Retrying up to @number/max_retries times`) become an interpolated
+//! value.
+
+use crate::generator::parsing::{InterpolationPart, ScalarValue};
+
+/// Resource kinds that may prefix an absolute reference (`@string/...`)
+const REFERENCE_KINDS: &[&str] =
+    &["string", "number", "int", "float", "bool", "color", "template"];
+
+/// Namespace of the builtin resources, referenced by path (`@build/version`)
+const BUILTIN_NAMESPACE: &str = "build";
+
+/// Invalid reference found while reading a value
+#[derive(Debug)]
+pub(super) struct ReferenceError {
+    pub(super) reference: String,
+    pub(super) message: String,
+}
+
+/// Parses a whole value as a reference.
+///
+/// Returns `Ok(None)` when the text is not a reference and should be kept as-is.
+pub(super) fn parse_reference(
+    text: &str,
+    namespace: &[String],
+) -> Result<Option<ScalarValue>, ReferenceError> {
+    let Some(body) = text.strip_prefix('@') else {
+        return Ok(None);
+    };
+    if body.chars().any(char::is_whitespace) {
+        return Ok(None);
+    }
+
+    if body.starts_with("./") || body.starts_with("../") {
+        let path = resolve_relative(body, namespace).map_err(|message| {
+            ReferenceError {
+                reference: text.to_string(),
+                message,
+            }
+        })?;
+        return Ok(Some(ScalarValue::Reference { kind: None, path }));
+    }
+
+    let Some((kind, path)) = body.split_once('/') else {
+        return Ok(None);
+    };
+    if kind == BUILTIN_NAMESPACE && !path.is_empty() {
+        return Ok(Some(ScalarValue::Reference {
+            kind: None,
+            path: body.to_string(),
+        }));
+    }
+    if !REFERENCE_KINDS.contains(&kind) || path.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(ScalarValue::Reference {
+        kind: Some(normalize_kind(kind).to_string()),
+        path: path.to_string(),
+    }))
+}
+
+/// Splits a string into text and embedded references.
+///
+/// Returns `Ok(None)` when the text contains no reference. A token only counts as a
+/// reference when `@` starts a word, so e-mail addresses are left untouched.
+pub(super) fn parse_interpolation(
+    text: &str,
+    namespace: &[String],
+) -> Result<Option<ScalarValue>, ReferenceError> {
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut rest = text;
+
+    while let Some(at) = find_reference_start(rest) {
+        let token = reference_token(&rest[at..]);
+        literal.push_str(&rest[..at]);
+        rest = &rest[at + token.len()..];
+
+        let Some(ScalarValue::Reference { kind, path }) =
+            parse_reference(token, namespace)?
+        else {
+            literal.push_str(token);
+            continue;
+        };
+        if !literal.is_empty() {
+            parts.push(InterpolationPart::Text(std::mem::take(
+                &mut literal,
+            )));
+        }
+        parts.push(InterpolationPart::Reference { kind, path });
+    }
+
+    if parts.is_empty() {
+        return Ok(None);
+    }
+    literal.push_str(rest);
+    if !literal.is_empty() {
+        parts.push(InterpolationPart::Text(literal));
+    }
+    Ok(Some(ScalarValue::Interpolated(parts)))
+}
+
+/// Byte offset of the next `@` that starts a word
+fn find_reference_start(text: &str) -> Option<usize> {
+    text.char_indices()
+        .find(|&(index, c)| {
+            c == '@'
+                && !text[..index]
+                    .chars()
+                    .next_back()
+                    .is_some_and(char::is_alphanumeric)
+        })
+        .map(|(index, _)| index)
+}
+
+/// The `@...` token at the start of `text`, without trailing punctuation
+fn reference_token(text: &str) -> &str {
+    let end = text[1..]
+        .find(|c: char| {
+            !(c.is_alphanumeric() || matches!(c, '_' | '-' | '/' | '.'))
+        })
+        .map_or(text.len(), |offset| offset + 1);
+    let token = text[..end].trim_end_matches(['.', '/', '-']);
+    if token.len() > 1 {
+        token
+    } else {
+        &text[..1]
+    }
+}
+
+/// Resolves `./name` or `../path` against the current namespace stack.
+fn resolve_relative(
+    body: &str,
+    namespace: &[String],
+) -> Result<String, String> {
+    let last = body.rsplit('/').next().unwrap_or_default();
+    if matches!(last, "" | "." | "..") {
+        return Err("reference does not name a resource".to_string());
+    }
+
+    let mut segments: Vec<&str> =
+        namespace.iter().map(String::as_str).collect();
+    for part in body.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                segments.pop().ok_or_else(|| {
+                    "reference escapes above the root namespace"
+                        .to_string()
+                })?;
+            }
+            name => segments.push(name),
+        }
+    }
+    Ok(segments.join("/"))
+}
+
+/// Legacy `int`/`float` tags share the `number` kind
+fn normalize_kind(kind: &str) -> &str {
+    match kind {
+        "int" | "float" => "number",
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ns(parts: &[&str]) -> Vec<String> {
+        parts.iter().map(|p| p.to_string()).collect()
+    }
+
+    fn reference_path(value: Option<ScalarValue>) -> String {
+        match value {
+            Some(ScalarValue::Reference { path, .. }) => path,
+            other => panic!("expected reference, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn absolute_reference_keeps_path_and_kind() {
+        let value =
+            parse_reference("@string/ui/colors/primary", &ns(&["x"]))
+                .unwrap();
+        assert_eq!(
+            value,
+            Some(ScalarValue::Reference {
+                kind: Some("string".to_string()),
+                path: "ui/colors/primary".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn builtin_reference_has_no_kind() {
+        let value = parse_reference("@build/version", &ns(&["ui"])).unwrap();
+        assert_eq!(
+            value,
+            Some(ScalarValue::Reference {
+                kind: None,
+                path: "build/version".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn current_namespace_reference() {
+        let value =
+            parse_reference("@./primary", &ns(&["ui", "colors"]))
+                .unwrap();
+        assert_eq!(reference_path(value), "ui/colors/primary");
+    }
+
+    #[test]
+    fn parent_namespace_reference() {
+        let value = parse_reference(
+            "@../dimens/padding",
+            &ns(&["ui", "colors"]),
+        )
+        .unwrap();
+        assert_eq!(reference_path(value), "ui/dimens/padding");
+    }
+
+    #[test]
+    fn escaping_above_root_is_an_error() {
+        let err =
+            parse_reference("@../../title", &ns(&["ui"])).unwrap_err();
+        assert_eq!(err.reference, "@../../title");
+        assert!(err.message.contains("escapes above the root"));
+    }
+
+    #[test]
+    fn interpolation_splits_text_and_references() {
+        let value = parse_interpolation(
+            "Retrying up to @number/max_retries times.",
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            value,
+            Some(ScalarValue::Interpolated(vec![
+                InterpolationPart::Text("Retrying up to ".to_string()),
+                InterpolationPart::Reference {
+                    kind: Some("number".to_string()),
+                    path: "max_retries".to_string(),
+                },
+                InterpolationPart::Text(" times.".to_string()),
+            ]))
+        );
+    }
+
+    #[test]
+    fn interpolation_resolves_relative_references() {
+        let value =
+            parse_interpolation("Hi @./name!", &ns(&["ui"])).unwrap();
+        let Some(ScalarValue::Interpolated(parts)) = value else {
+            panic!("expected interpolation, got {value:?}");
+        };
+        assert_eq!(
+            parts[1],
+            InterpolationPart::Reference {
+                kind: None,
+                path: "ui/name".to_string(),
+            }
+        );
+        assert_eq!(parts[2], InterpolationPart::Text("!".to_string()));
+    }
+
+    #[test]
+    fn interpolation_ignores_plain_at_signs() {
+        for text in ["Plain text", "mail me@string/x", "@ home", "@team"] {
+            assert_eq!(parse_interpolation(text, &[]).unwrap(), None);
+        }
+    }
+
+    #[test]
+    fn plain_text_is_not_a_reference() {
+        assert_eq!(parse_reference("Hello", &[]).unwrap(), None);
+        assert_eq!(parse_reference("@handle", &[]).unwrap(), None);
+        assert_eq!(parse_reference("@team/handle", &[]).unwrap(), None);
+    }
+}
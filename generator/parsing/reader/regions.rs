@@ -0,0 +1,199 @@
+//! Region comments grouping the resources of a file:
+//!
+//! ```xml
+//! <!-- region: checkout -->
+//! <string name="pay">Pay now</string>
+//! <!-- endregion -->
+//! ```
+//!
+//! Resources between the markers get the attribute `region="checkout"`,
+//! unless they set one themselves. Regions nest, and a resource belongs to
+//! the innermost one. `<!-- endregion: checkout -->` may name the region
+//! it closes.
+
+use crate::generator::input::RawResourceFile;
+use crate::generator::parsing::ast::ParsedUnbalancedRegion;
+
+use super::state::ParseState;
+
+/// Attribute holding the region of a resource
+pub(crate) const REGION: &str = "region";
+
+/// Region comment, by the name it opens or closes
+enum Marker<'a> {
+    Start(&'a str),
+    End(Option<&'a str>),
+}
+
+/// The marker a comment holds; other comments, and `region:` without a
+/// name, are left alone
+fn marker(comment: &str) -> Option<Marker<'_>> {
+    let comment = comment.trim();
+    if let Some(name) = comment.strip_prefix("region:") {
+        let name = name.trim();
+        return (!name.is_empty()).then_some(Marker::Start(name));
+    }
+    let rest = comment.strip_prefix("endregion")?;
+    if rest.trim().is_empty() {
+        return Some(Marker::End(None));
+    }
+    let name = rest.strip_prefix(':')?.trim();
+    Some(Marker::End((!name.is_empty()).then_some(name)))
+}
+
+/// Opens or closes a region for the comment ending at byte `end` of the
+/// preprocessed XML
+pub(super) fn handle_comment(
+    state: &mut ParseState,
+    raw: &RawResourceFile,
+    comment: &str,
+    end: usize,
+) {
+    let Some(marker) = marker(comment) else {
+        return;
+    };
+    let line = source_line(raw, comment, end);
+    match marker {
+        Marker::Start(name) => {
+            state.region_stack.push((name.to_string(), line));
+        }
+        Marker::End(name) => close_region(state, name, line),
+    }
+}
+
+fn close_region(state: &mut ParseState, name: Option<&str>, line: usize) {
+    let message = match (state.region_stack.pop(), name) {
+        (None, _) => "`endregion` without an open region".to_string(),
+        (Some((open, _)), Some(name)) if name != open => {
+            format!("`endregion: {name}` closes region '{open}'")
+        }
+        _ => return,
+    };
+    state
+        .unbalanced_regions
+        .push(ParsedUnbalancedRegion { line, message });
+}
+
+/// Reports the regions still open at the end of the file
+pub(super) fn close_open_regions(state: &mut ParseState) {
+    for (name, line) in std::mem::take(&mut state.region_stack) {
+        state.unbalanced_regions.push(ParsedUnbalancedRegion {
+            line,
+            message: format!("region '{name}' is never closed"),
+        });
+    }
+}
+
+/// Line of the comment in the file as read from disk. Profile filtering
+/// may have removed lines above it, so the comment is found again in the
+/// source by the number of identical comments before it; failing that, its
+/// line in the preprocessed XML is used.
+fn source_line(raw: &RawResourceFile, comment: &str, end: usize) -> usize {
+    let text = format!("<!--{comment}-->");
+    let contents = raw.contents.get(..end).unwrap_or(&raw.contents);
+    let before = contents.matches(&text).count().saturating_sub(1);
+    let (file, at) = match raw.source.match_indices(&text).nth(before) {
+        Some((at, _)) => (raw.source.as_str(), at),
+        None => (contents, contents.rfind(&text).unwrap_or(0)),
+    };
+    file[..at].matches('\n').count() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::generator::parsing::reader::parse_single_file;
+
+    fn parse(xml: &str) -> crate::generator::parsing::ParsedResourceFile {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            xml.to_string(),
+            false,
+        );
+        parse_single_file(&raw, &Default::default()).unwrap()
+    }
+
+    fn regions(xml: &str) -> Vec<(String, Option<String>)> {
+        parse(xml)
+            .resources
+            .into_iter()
+            .map(|res| (res.name, res.attributes.get(REGION).cloned()))
+            .collect()
+    }
+
+    #[test]
+    fn resources_take_the_innermost_region() {
+        let found = regions(
+            r#"<resources>
+                <string name="title">Shop</string>
+                <!-- region: checkout -->
+                <string name="pay">Pay</string>
+                <!--region:Payment cards-->
+                <ns name="cards"><string name="visa">Visa</string></ns>
+                <!-- endregion: Payment cards -->
+                <string name="total" region="summary">Total</string>
+                <!-- endregion -->
+                <!-- Checkout flow -->
+                <string name="thanks">Thanks</string>
+            </resources>"#,
+        );
+        let region = |name: &str| Some(name.to_string());
+        assert_eq!(
+            found,
+            [
+                ("title".to_string(), None),
+                ("pay".to_string(), region("checkout")),
+                ("cards/visa".to_string(), region("Payment cards")),
+                ("total".to_string(), region("summary")),
+                ("thanks".to_string(), None),
+            ]
+        );
+        assert!(parse("<resources/>").unbalanced_regions.is_empty());
+    }
+
+    #[test]
+    fn unbalanced_markers_are_reported_with_their_line() {
+        let file = parse(
+            "<resources>\n\
+             <!-- endregion -->\n\
+             <!-- region: a -->\n\
+             <!-- region: b -->\n\
+             <!-- endregion: a -->\n\
+             </resources>",
+        );
+        let found: Vec<(usize, &str)> = file
+            .unbalanced_regions
+            .iter()
+            .map(|marker| (marker.line, marker.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (2, "`endregion` without an open region"),
+                (5, "`endregion: a` closes region 'b'"),
+                (3, "region 'a' is never closed"),
+            ]
+        );
+    }
+
+    #[test]
+    fn lines_are_those_of_the_source() {
+        let source = "<resources>\n\
+                      <string name=\"dev\" profile=\"debug\">\n\
+                      x\n\
+                      </string>\n\
+                      <!-- region: a -->\n\
+                      </resources>";
+        let contents = "<resources>\n\n<!-- region: a -->\n</resources>";
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            contents.to_string(),
+            false,
+        )
+        .with_source(source.to_string());
+        let file = parse_single_file(&raw, &Default::default()).unwrap();
+        assert_eq!(file.unbalanced_regions[0].line, 5);
+    }
+}
@@ -1,10 +1,79 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::generator::parsing::ast::{
+    ParsedAssertion, ParsedErrorEnum, ParsedLocaleRequirement,
+    ParsedNamespaceDeclaration, ParsedNamespaceDoc,
+    ParsedNamespaceFeature, ParsedNamespaceVisibility, ParsedResource, ParsedUnbalancedRegion,
+};
+
+use super::regions::REGION;
+
 #[derive(Default)]
 pub(super) struct ParseState {
     pub(super) current_tag: String,
     pub(super) current_name: Option<String>,
+    pub(super) current_attributes: BTreeMap<String, String>, // Attributes of the current resource tag
     pub(super) namespace_stack: Vec<String>,
     pub(super) current_number_type: Option<String>, // For <number type="...">
+    pub(super) current_allow_over_100: bool, // For <percent allow-over-100="true">
     pub(super) template_params: Vec<crate::generator::parsing::ast::TemplateParam>, // For <template><param>
     pub(super) template_text: String, // Accumulated text for templates
     pub(super) in_template: bool, // Track if we're inside a <template> tag
+    pub(super) template_from_attribute: bool, // <string template="..."> rather than <template>
+    pub(super) in_mime_map: bool, // Track if we're inside a <mime-map> tag
+    pub(super) mime_entries: Vec<crate::generator::parsing::MimeEntry>, // For <mime-map><entry>
+    pub(super) mime_entry_ext: String, // `ext` of the current <entry>
+    pub(super) mime_entry_text: String, // Text of the current <entry>
+    pub(super) in_array: bool, // Track if we're inside a <string-array>, <int-array>, ...
+    pub(super) array_items: Vec<crate::generator::parsing::ArrayItem>, // For <string-array><item>
+    pub(super) array_item_text: String, // Text of the current array <item>
+    pub(super) in_array_row: bool, // Track if we're inside an array <row>
+    pub(super) array_rows: Vec<Vec<crate::generator::parsing::ArrayItem>>, // For <int-array><row><item>
+    pub(super) in_enum: bool, // Track if we're inside an <enum> tag
+    pub(super) enum_repr: Option<String>, // For <enum repr="...">
+    pub(super) enum_items: Vec<crate::generator::parsing::EnumItem>, // For <enum><item>
+    pub(super) enum_item_value: Option<String>, // `value` of the current <item>
+    pub(super) enum_item_text: String, // Text of the current <item>
+    pub(super) pending_text: String, // Text and decoded entities since the last tag
+    pub(super) assertions: Vec<ParsedAssertion>, // <assert expr="..."/> found so far
+    pub(super) locale_requirements: Vec<ParsedLocaleRequirement>, // <ns require-locales="...">
+    pub(super) tag_aliases: HashMap<String, String>, // Alias -> tag it stands for
+    pub(super) namespace_visibilities: Vec<ParsedNamespaceVisibility>, // <ns visibility="...">
+    pub(super) namespace_features: Vec<ParsedNamespaceFeature>, // <ns feature="...">
+    pub(super) namespace_docs: Vec<ParsedNamespaceDoc>, // <ns doc="...">
+    pub(super) namespace_declarations: Vec<ParsedNamespaceDeclaration>, // Every <ns>, with its `extend`
+    pub(super) error_enums: Vec<ParsedErrorEnum>, // <ns error-enum="...">
+    pub(super) visibility_stack: Vec<(usize, String)>, // Enclosing <ns visibility>, with the namespace depth declaring it
+    pub(super) region_stack: Vec<(String, usize)>, // Open <!-- region: ... --> comments, with their line
+    pub(super) unbalanced_regions: Vec<ParsedUnbalancedRegion>, // Region markers without their counterpart
+}
+
+impl ParseState {
+    /// Tag name as the reader understands it, with aliases applied
+    pub(super) fn canonical_tag(&self, tag: String) -> String {
+        self.tag_aliases.get(&tag).cloned().unwrap_or(tag)
+    }
+
+    /// Stamps the attributes of the current resource tag onto its resource;
+    /// without its own `visibility` or `region`, it inherits the enclosing
+    /// namespace's and region's
+    pub(super) fn attach_attributes(
+        &self,
+        mut resource: ParsedResource,
+    ) -> ParsedResource {
+        resource.attributes = self.current_attributes.clone();
+        if let Some((_, visibility)) = self.visibility_stack.last() {
+            resource
+                .attributes
+                .entry("visibility".to_string())
+                .or_insert_with(|| visibility.clone());
+        }
+        if let Some((region, _)) = self.region_stack.last() {
+            resource
+                .attributes
+                .entry(REGION.to_string())
+                .or_insert_with(|| region.clone());
+        }
+        resource
+    }
 }
@@ -1,15 +1,51 @@
-use quick_xml::events::{BytesStart, BytesText};
+use std::collections::BTreeMap;
+use std::path::Path;
 
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::attributes::Attribute;
+use quick_xml::events::{BytesRef, BytesStart, BytesText};
+
+use crate::generator::utils::sanitize_identifier;
+
+/// Value of an attribute, with entities (`&lt;`, `&amp;`) decoded. Names
+/// are matched without their prefix, like tags (`res:name` is `name`).
 pub(super) fn attr_value(
     e: &BytesStart<'_>,
     name: &[u8],
 ) -> Option<String> {
-    for attr in e.attributes().flatten() {
-        if attr.key.as_ref() == name {
-            return Some(to_string(attr.value.as_ref()));
-        }
-    }
-    None
+    let attr = resource_attrs(e)
+        .find(|attr| attr.key.local_name().as_ref() == name)?;
+    Some(match attr.unescape_value() {
+        Ok(value) => value.into_owned(),
+        Err(_) => to_string(attr.value.as_ref()),
+    })
+}
+
+/// All attributes of a resource tag except `name`, by their unprefixed
+/// names and decoded like [`attr_value`]
+pub(super) fn resource_attributes(
+    e: &BytesStart<'_>,
+) -> BTreeMap<String, String> {
+    resource_attrs(e)
+        .filter(|attr| attr.key.local_name().as_ref() != b"name")
+        .map(|attr| {
+            let value = match attr.unescape_value() {
+                Ok(value) => value.into_owned(),
+                Err(_) => to_string(attr.value.as_ref()),
+            };
+            (to_string(attr.key.local_name().as_ref()), value)
+        })
+        .collect()
+}
+
+/// Well-formed attributes, leaving out `xmlns` and `xmlns:*`
+/// declarations, which only bind prefixes
+fn resource_attrs<'a>(
+    e: &'a BytesStart<'_>,
+) -> impl Iterator<Item = Attribute<'a>> {
+    e.attributes()
+        .flatten()
+        .filter(|attr| attr.key.as_namespace_binding().is_none())
 }
 
 pub(super) fn to_string(bytes: &[u8]) -> String {
@@ -19,3 +55,43 @@ pub(super) fn to_string(bytes: &[u8]) -> String {
 pub(super) fn text_to_string(text: &BytesText<'_>) -> String {
     String::from_utf8_lossy(text.as_ref()).to_string()
 }
+
+/// Text of an entity reference (`&amp;`, `&#x1F600;`)
+pub(super) fn resolve_entity(entity: &BytesRef<'_>) -> Result<String, String> {
+    if let Some(c) = entity.resolve_char_ref().map_err(|e| e.to_string())? {
+        return Ok(c.to_string());
+    }
+    let name = to_string(entity);
+    resolve_predefined_entity(&name)
+        .map(str::to_string)
+        .ok_or_else(|| format!("unknown entity '&{name};'"))
+}
+
+/// Namespace derived from a file name for `file_as_namespace`: the sanitized
+/// stem without its locale suffix (`auth.pt-BR.xml` → `auth`). `values*.xml`
+/// files stay at the root.
+pub(super) fn file_namespace(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_str()?;
+    let stem = match stem.rsplit_once('.') {
+        Some((base, suffix)) if is_locale_tag(suffix) => base,
+        _ => stem,
+    };
+    if stem.is_empty() || stem.starts_with("values") {
+        return None;
+    }
+    Some(sanitize_identifier(stem))
+}
+
+/// `fr`, `fil`, `pt-BR`, `en_US`
+fn is_locale_tag(tag: &str) -> bool {
+    let (language, region) = match tag.split_once(['-', '_']) {
+        Some((language, region)) => (language, Some(region)),
+        None => (tag, None),
+    };
+    let language_ok = (2..=3).contains(&language.len())
+        && language.chars().all(|c| c.is_ascii_lowercase());
+    let region_ok = region.is_none_or(|region| {
+        region.len() == 2 && region.chars().all(|c| c.is_ascii_uppercase())
+    });
+    language_ok && region_ok
+}
@@ -4,16 +4,23 @@
 //! input → parsing → IR → analysis → output
 
 use crate::generator::analysis;
+use crate::generator::generation::InputFile;
 use crate::generator::input::{self, BuildPlan};
-use crate::generator::ir::{ResourceGraph, ResourceGraphBuilder};
+use crate::generator::ir::types::{TEMPLATE_STYLE, TEMPLATE_STYLE_BUILDER};
+use crate::generator::ir::{
+    ResourceGraph, ResourceGraphBuilder, ResourceOrigin,
+};
 use crate::generator::parsing;
 
 pub struct PipelineOutput {
     pub graph: ResourceGraph,
     pub analysis_result: analysis::AnalysisResult,
+    /// Files the graph was built from, in load order; resources built in
+    /// code have none
+    pub inputs: Vec<InputFile>,
 }
 
-#[allow(dead_code)] // Reserved for future use
+#[allow(dead_code)] // Used by the CLI
 pub fn build_graph(
     plan: &BuildPlan,
 ) -> Result<PipelineOutput, PipelineError> {
@@ -27,17 +34,139 @@ pub fn build_graph_with_options(
     plan: &BuildPlan,
     validation_options: analysis::ValidationOptions,
 ) -> Result<PipelineOutput, PipelineError> {
-    let raw_files = input::load_resources(plan)?;
-    let parsed_files = parsing::parse_raw_files(&raw_files)?;
-    let graph =
-        ResourceGraphBuilder::from_parsed_files(&parsed_files);
-    let analysis_result =
-        analysis::validate_with_options(&graph, validation_options);
-
-    Ok(PipelineOutput {
+    build_graph_with_extra(plan, validation_options, ResourceGraph::default())
+}
+
+/// Like [`build_graph_with_options`], with `extra` merged into the graph
+/// read from XML before analysis, so its resources are checked for
+/// duplicates, can reference and be referenced, and are emitted with the
+/// rest
+pub fn build_graph_with_extra(
+    plan: &BuildPlan,
+    validation_options: analysis::ValidationOptions,
+    extra: ResourceGraph,
+) -> Result<PipelineOutput, PipelineError> {
+    let (raw_files, loader_notes) = input::load_resources_with_notes(plan)?;
+    let parsed_files = parsing::parse_raw_files(
+        &raw_files,
+        &parsing::ParseOptions {
+            tag_aliases: &plan.tag_aliases,
+            file_as_namespace: plan.file_as_namespace,
+        },
+    )?;
+    let mut output = analyze_parsed(
+        plan,
+        parsed_files,
+        &raw_files,
+        validation_options.clone(),
+        extra,
+    );
+    output.analysis_result.notes.extend(loader_notes);
+    if plan.allow_missing_resources {
+        let mut missing = analysis::AnalysisResult::default();
+        missing.warnings.extend(input::missing_resources_warning(plan).map(
+            |message| {
+                analysis::AnalysisWarning::new(
+                    analysis::codes::NO_RESOURCES,
+                    message,
+                    None,
+                )
+            },
+        ));
+        validation_options.apply_levels(&mut missing);
+        output.analysis_result.warnings.append(&mut missing.warnings);
+        output.analysis_result.errors.append(&mut missing.errors);
+    }
+    Ok(output)
+}
+
+/// Runs the stages after parsing on files produced by another front-end
+/// (e.g. a CSV reader in a build script): the loader and the XML reader
+/// are skipped, so only the plan's type, validation, and generation
+/// settings apply. Diagnostics have no XML snippet to quote.
+#[allow(dead_code)] // Public API, may be used by consumers
+pub fn build_graph_from_parsed(
+    plan: &BuildPlan,
+    files: Vec<parsing::ParsedResourceFile>,
+    validation_options: analysis::ValidationOptions,
+) -> Result<PipelineOutput, PipelineError> {
+    input::check_root_module(&plan.root_module)?;
+    Ok(analyze_parsed(
+        plan,
+        files,
+        &[],
+        validation_options,
+        ResourceGraph::default(),
+    ))
+}
+
+/// Builds the graph from parsed files plus `extra`, then validates it
+fn analyze_parsed(
+    plan: &BuildPlan,
+    mut parsed_files: Vec<parsing::ParsedResourceFile>,
+    raw_files: &[input::RawResourceFile],
+    validation_options: analysis::ValidationOptions,
+    extra: ResourceGraph,
+) -> PipelineOutput {
+    let inputs = InputFile::list(&parsed_files);
+    let override_issues =
+        parsing::apply_overrides(&plan.overrides, &mut parsed_files);
+    parsed_files.extend(builtin_file(plan));
+    if plan.template_builders {
+        default_template_style(&mut parsed_files);
+    }
+    let mut graph =
+        ResourceGraphBuilder::with_registry(plan.type_registry())
+            .build(&parsed_files);
+    graph.merge(extra);
+    analysis::references::index_dependents(&mut graph);
+    // Names are checked as the plan's naming configuration writes them
+    let mut analysis_result = analysis::validate_with_naming(
+        &graph,
+        validation_options,
+        &plan.naming,
+    );
+    analysis::report_override_issues(
+        &graph,
+        &override_issues,
+        &mut analysis_result,
+    );
+    analysis::attach_snippets(&graph, raw_files, &mut analysis_result);
+    analysis_result.notes.extend(raw_files.iter().flat_map(|file| {
+        file.skipped
+            .iter()
+            .map(|skip| skip.note(&file.path, &plan.profile))
+    }));
+    PipelineOutput {
         graph,
         analysis_result,
-    })
+        inputs,
+    }
+}
+
+/// Gives `style="builder"` to every template not choosing a style itself
+fn default_template_style(files: &mut [parsing::ParsedResourceFile]) {
+    let templates = files
+        .iter_mut()
+        .flat_map(|file| &mut file.resources)
+        .filter(|resource| resource.kind == parsing::ResourceKind::Template);
+    for template in templates {
+        template
+            .attributes
+            .entry(TEMPLATE_STYLE.to_string())
+            .or_insert_with(|| TEMPLATE_STYLE_BUILDER.to_string());
+    }
+}
+
+/// Resources generated from the plan rather than read from XML
+/// (`r::build::VERSION`), as if declared in a file of their own
+fn builtin_file(plan: &BuildPlan) -> Option<parsing::ParsedResourceFile> {
+    let version = plan.build_version.as_ref()?;
+    Some(parsing::ParsedResourceFile::new(
+        ResourceOrigin::BUILTIN.into(),
+        false,
+        vec![parsing::ParsedResource::string("build/version", version)],
+    ))
 }
 
 #[derive(Debug)]
@@ -1,8 +1,16 @@
 //! Utility functions for code generation.
 
-/// Sanitizes an identifier to be a valid Rust identifier
+use crate::generator::casing;
+
+/// The identifier generated code uses for an XML name or namespace.
 ///
-/// Replaces non-alphanumeric characters (except underscores) with underscores
+/// Each character that is neither alphanumeric (Unicode letters and
+/// digits included) nor `_` becomes one `_`: `error-codes` →
+/// `error_codes`, `app.name` → `app_name`, `a--b` → `a__b`, `prix
+/// ttc` → `prix_ttc`. Case is kept. Modules use the result as is;
+/// constants are the result in upper case (`not-found` → `NOT_FOUND`),
+/// see [`crate::generator::generation::const_path_for`]. Keywords and
+/// leading digits are left alone, so `type` or `2fa` still needs renaming.
 pub fn sanitize_identifier(s: &str) -> String {
     s.chars()
         .map(|c| {
@@ -15,6 +23,59 @@ pub fn sanitize_identifier(s: &str) -> String {
         .collect()
 }
 
+/// The constant generated for an XML name under the default
+/// [`NamingConfig`](casing::NamingConfig): [`sanitize_identifier`] in
+/// upper case (`not-found` → `NOT_FOUND`). Emitters given a configuration
+/// name their constants with
+/// [`NamingConfig::const_identifier`](casing::NamingConfig::const_identifier)
+/// instead, so analysis can tell authors what they get.
+#[allow(dead_code)] // Public API, not used by build.rs
+pub fn const_identifier(name: &str) -> String {
+    casing::NamingConfig::default().const_identifier(name)
+}
+
+/// `///` lines at `pad` showing `text` as written. `\r` is dropped and
+/// every line of `text` gets a comment line of its own, so a value cannot
+/// end the comment and spill into the generated code.
+pub fn doc_comment(pad: &str, text: &str) -> String {
+    comment_lines(pad, "///", text)
+}
+
+/// `//!` lines at `pad`, like [`doc_comment`], documenting the enclosing
+/// module
+pub fn inner_doc_comment(pad: &str, text: &str) -> String {
+    comment_lines(pad, "//!", text)
+}
+
+fn comment_lines(pad: &str, marker: &str, text: &str) -> String {
+    text.replace('\r', "")
+        .split('\n')
+        .map(|line| {
+            if line.is_empty() {
+                format!("{pad}{marker}\n")
+            } else {
+                format!("{pad}{marker} {line}\n")
+            }
+        })
+        .collect()
+}
+
+/// `UpperCamelCase` form of a resource or item name, for type and variant
+/// names: `very-high` and `very_high` both become `VeryHigh`
+pub fn pascal_case(s: &str) -> String {
+    s.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<String>>()
+        .concat()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -25,5 +86,29 @@ mod tests {
         assert_eq!(sanitize_identifier("app.name"), "app_name");
         assert_eq!(sanitize_identifier("my_var"), "my_var");
         assert_eq!(sanitize_identifier("test123"), "test123");
+        assert_eq!(sanitize_identifier("a--b"), "a__b");
+        assert_eq!(sanitize_identifier("Prix TTC"), "Prix_TTC");
+        assert_eq!(sanitize_identifier("café"), "café");
+    }
+
+    #[test]
+    fn doc_comment_gives_each_line_its_own_comment() {
+        assert_eq!(doc_comment("    ", "12px"), "    /// 12px\n");
+        assert_eq!(
+            doc_comment("", "say \"hi\"\r\n\n*/ //! fn x() {}"),
+            "/// say \"hi\"\n///\n/// */ //! fn x() {}\n"
+        );
+        assert_eq!(
+            inner_doc_comment("    ", "Sign-in\n\nscreens"),
+            "    //! Sign-in\n    //!\n    //! screens\n"
+        );
+    }
+
+    #[test]
+    fn test_pascal_case() {
+        assert_eq!(pascal_case("priority"), "Priority");
+        assert_eq!(pascal_case("very-high"), "VeryHigh");
+        assert_eq!(pascal_case("in_progress"), "InProgress");
+        assert_eq!(pascal_case("HTTP status"), "HTTPStatus");
     }
 }
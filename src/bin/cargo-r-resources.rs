@@ -0,0 +1,459 @@
+//! `cargo r-resources`: the `r-resources` commands run on the packages of
+//! a Cargo workspace, each with its own configuration.
+//!
+//! ```text
+//! cargo r-resources validate [-p <package>] [--manifest-path <path>] [--release] [--verbose]
+//! cargo r-resources stats [-p <package>] ...
+//! cargo r-resources diff [-p <package>] ... [--base HEAD]
+//! cargo r-resources docs [-p <package>] ... [--output RESOURCES.md]
+//! ```
+//!
+//! Packages are found with `cargo metadata`. Each one is configured by the
+//! `[package.metadata.r-resources]` table of its manifest, with the keys
+//! `build()` reads, so its resource directory is `res/` next to the manifest
+//! unless the table says otherwise; without `-p`, every member with a
+//! resource directory is processed and the command fails if any of them
+//! does. The commands themselves are those of `r-resources`, in
+//! [`commands`].
+
+mod commands;
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+
+use r_resources::generator::analysis::ValidationOptions;
+use r_resources::generator::input::manifest::METADATA_TABLE;
+use r_resources::generator::input::{ManifestConfig, ManifestValue};
+use r_resources::BuildPlan;
+use serde_json::Value;
+
+const USAGE: &str = "\
+usage: cargo r-resources <command> [options]
+
+commands:
+    validate    Build the resources without writing anything, reporting
+                warnings, errors, and what would be generated
+    stats       Report the bytes of static data the resources contribute
+    diff        List the resources added, removed, or changed since a git
+                revision (`--base`, default HEAD)
+    docs        Write a markdown overview of the resources
+
+options:
+    -p, --package <name>     Package to process (default: every workspace
+                             member with a resource directory)
+    --manifest-path <path>   Cargo.toml of the workspace or package
+    --release                Build for the release profile
+    --output <file>          Write to a file instead of stdout
+    --base <rev>             `diff` baseline: a commit, branch, or tag
+                             (default: HEAD)
+    --verbose                Also print notes, e.g. elements left out for
+                             another profile
+
+[package.metadata.r-resources] keys:
+    res-dir             Resource directory, relative to the manifest
+                        (default: res)
+    tests-dir           Test resources, relative to res-dir (default: tests)
+    root-module         Root module name (default: r)
+    profile-default     Profile to build, overriding --release
+    include-tests       Put the test resources in r_generated.rs
+    file-as-namespace   Put each file's resources in a namespace of its name
+    duplicates          \"error\" to fail on duplicate keys (default: \"warning\")
+    deny, allow         Diagnostic codes to fail on or silence
+    max-warnings        Warnings allowed before the build fails";
+
+/// Parsed command line
+struct Args {
+    command: String,
+    package: Option<String>,
+    manifest_path: Option<PathBuf>,
+    release: bool,
+    output: Option<PathBuf>,
+    /// Git revision `diff` compares against
+    base: String,
+    verbose: bool,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let command = args.next().ok_or("missing command")?;
+    let mut parsed = Args {
+        command,
+        package: None,
+        manifest_path: None,
+        release: false,
+        output: None,
+        base: "HEAD".to_string(),
+        verbose: false,
+    };
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{arg} needs a value"));
+        match arg.as_str() {
+            "-p" | "--package" => parsed.package = Some(value()?),
+            "--manifest-path" => {
+                parsed.manifest_path = Some(PathBuf::from(value()?))
+            }
+            "--release" => parsed.release = true,
+            "--output" => parsed.output = Some(PathBuf::from(value()?)),
+            "--base" => parsed.base = value()?,
+            "--verbose" => parsed.verbose = true,
+            _ => return Err(format!("unknown option '{arg}'")),
+        }
+    }
+    Ok(parsed)
+}
+
+/// A workspace member with resources, and how to build them
+struct Package {
+    name: String,
+    plan: BuildPlan,
+    options: ValidationOptions,
+}
+
+/// The packages of the workspace (or the one named by `-p`) that have a
+/// resource directory
+fn packages(args: &Args) -> Result<Vec<Package>, String> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut command = Command::new(cargo);
+    command.args(["metadata", "--format-version", "1", "--no-deps"]);
+    if let Some(path) = &args.manifest_path {
+        command.arg("--manifest-path").arg(path);
+    }
+    let output = command
+        .output()
+        .map_err(|err| format!("failed to run cargo metadata: {err}"))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    let metadata: Value = serde_json::from_slice(&output.stdout)
+        .map_err(|err| format!("unreadable cargo metadata: {err}"))?;
+    let members = metadata["packages"].as_array().cloned().unwrap_or_default();
+    if let Some(name) = &args.package {
+        let member = members
+            .iter()
+            .find(|member| member["name"] == name.as_str())
+            .ok_or(format!("no package '{name}' in the workspace"))?;
+        let package = package(member, args)?;
+        if !package.plan.resources_dir.is_dir() {
+            return Err(format!(
+                "package '{name}' has no resource directory '{}'",
+                package.plan.resources_dir.display()
+            ));
+        }
+        return Ok(vec![package]);
+    }
+    let mut found = Vec::new();
+    for member in &members {
+        let package = package(member, args)?;
+        if package.plan.resources_dir.is_dir() {
+            found.push(package);
+        }
+    }
+    if found.is_empty() {
+        return Err("no workspace member has a resource directory".to_string());
+    }
+    Ok(found)
+}
+
+/// The plan of one `cargo metadata` package, from the
+/// `[package.metadata.r-resources]` table of its manifest
+fn package(member: &Value, args: &Args) -> Result<Package, String> {
+    let name = member["name"].as_str().unwrap_or_default().to_string();
+    let manifest = Path::new(member["manifest_path"].as_str().unwrap_or_default());
+    let dir = manifest.parent().unwrap_or(Path::new("."));
+    let config =
+        manifest_config(member).map_err(|message| format!("{name}: {message}"))?;
+
+    let res_dir = dir.join(config.res_dir.as_deref().unwrap_or("res"));
+    let tests_dir = res_dir.join(config.tests_dir.as_deref().unwrap_or("tests"));
+    let profile = config.profile_default.clone().unwrap_or_else(|| {
+        if args.release { "release" } else { "debug" }.to_string()
+    });
+    let mut plan = BuildPlan::new(
+        res_dir,
+        tests_dir.is_dir().then_some(tests_dir),
+        profile,
+    )
+    .dry_run();
+    plan.verbose = args.verbose;
+    plan.embed_test_resources = config.include_tests.unwrap_or(false);
+    plan.file_as_namespace = config.file_as_namespace.unwrap_or(false);
+    if let Some(root) = config.root_module {
+        plan.root_module = root;
+    }
+    let options = ValidationOptions {
+        treat_duplicates_as_errors: config.duplicates_as_errors.unwrap_or(false),
+        deny: config.deny,
+        allow: config.allow,
+        max_warnings: config.max_warnings,
+        ..Default::default()
+    };
+    Ok(Package {
+        name,
+        plan,
+        options,
+    })
+}
+
+/// The `[package.metadata.r-resources]` table of a `cargo metadata`
+/// package, as Cargo read it (inline and dotted forms included). Unlike a
+/// build, unknown keys fail: a typo here is caught before it is committed.
+fn manifest_config(member: &Value) -> Result<ManifestConfig, String> {
+    let table = match &member["metadata"]["r-resources"] {
+        Value::Null => return Ok(ManifestConfig::default()),
+        Value::Object(table) => table,
+        _ => return Err(format!("{METADATA_TABLE} must be a table")),
+    };
+    let entries = table
+        .iter()
+        .map(|(key, value)| {
+            let value = manifest_value(value).ok_or_else(|| {
+                format!("{METADATA_TABLE}: unsupported value for {key}")
+            })?;
+            Ok((key.clone(), value))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let config = ManifestConfig::from_entries(entries)?;
+    config.check_known_keys()?;
+    Ok(config)
+}
+
+/// `value` as the manifest reader represents it; `None` for what a TOML
+/// table cannot hold as a setting (fractional numbers)
+fn manifest_value(value: &Value) -> Option<ManifestValue> {
+    match value {
+        Value::String(text) => Some(ManifestValue::String(text.clone())),
+        Value::Bool(on) => Some(ManifestValue::Bool(*on)),
+        Value::Number(number) => number.as_i64().map(ManifestValue::Integer),
+        Value::Array(items) => items
+            .iter()
+            .map(manifest_value)
+            .collect::<Option<_>>()
+            .map(ManifestValue::Array),
+        Value::Object(table) => table
+            .iter()
+            .map(|(key, value)| Some((key.clone(), manifest_value(value)?)))
+            .collect::<Option<_>>()
+            .map(ManifestValue::Table),
+        Value::Null => None,
+    }
+}
+
+/// Output of the command for one package
+fn run(args: &Args, package: &Package) -> Result<String, String> {
+    let Package { plan, options, .. } = package;
+    match args.command.as_str() {
+        "validate" | "check" => commands::check(plan, options.clone()),
+        "stats" => commands::stats(plan, options.clone()),
+        "diff" => commands::diff(plan, &args.base),
+        "docs" => commands::docs(plan, plan.verbose),
+        other => Err(format!("unknown command '{other}'")),
+    }
+}
+
+fn main() -> ExitCode {
+    // Cargo passes the subcommand name first: `cargo-r-resources r-resources ...`
+    let mut args = std::env::args().skip(1).peekable();
+    if args.peek().is_some_and(|arg| arg == "r-resources") {
+        args.next();
+    }
+    let args = match parse_args(args) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    match args.command.as_str() {
+        "help" | "--help" | "-h" => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        "validate" | "check" | "stats" | "diff" | "docs" => {}
+        other => {
+            eprintln!("error: unknown command '{other}'\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    }
+    let packages = match packages(&args) {
+        Ok(packages) => packages,
+        Err(message) => {
+            eprintln!("error: {message}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let several = packages.len() > 1;
+    let mut text = String::new();
+    let mut failed = Vec::new();
+    for package in &packages {
+        if several {
+            eprintln!("r-resources: {}", package.name);
+        }
+        match run(&args, package) {
+            Ok(output) if several => {
+                text.push_str(&format!("# {}\n\n{output}\n", package.name));
+            }
+            Ok(output) => text.push_str(&output),
+            Err(message) => {
+                eprintln!("error: {}: {message}", package.name);
+                failed.push(package.name.as_str());
+            }
+        }
+    }
+    if let Err(message) = commands::write_output(args.output.as_deref(), &text) {
+        eprintln!("error: {message}");
+        return ExitCode::FAILURE;
+    }
+    if failed.is_empty() {
+        return ExitCode::SUCCESS;
+    }
+    eprintln!(
+        "error: {} of {} packages failed: {}",
+        failed.len(),
+        packages.len(),
+        failed.join(", ")
+    );
+    ExitCode::FAILURE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Result<Args, String> {
+        parse_args(line.split_whitespace().map(String::from))
+    }
+
+    fn write(path: &Path, text: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, text).unwrap();
+    }
+
+    #[test]
+    fn parses_the_command_and_its_options() {
+        let parsed =
+            args("validate -p app --manifest-path ws/Cargo.toml --release --verbose")
+                .unwrap();
+        assert_eq!(parsed.command, "validate");
+        assert_eq!(parsed.package.as_deref(), Some("app"));
+        assert_eq!(parsed.manifest_path, Some(PathBuf::from("ws/Cargo.toml")));
+        assert!(parsed.release && parsed.verbose);
+        assert_eq!(parsed.output, None);
+        assert_eq!(parsed.base, "HEAD");
+        assert_eq!(args("diff --base main").unwrap().base, "main");
+
+        assert_eq!(args("").err().as_deref(), Some("missing command"));
+        assert_eq!(args("docs --output").err().as_deref(), Some("--output needs a value"));
+        assert_eq!(args("stats --all").err().as_deref(), Some("unknown option '--all'"));
+    }
+
+    #[test]
+    fn diffs_the_resources_against_a_revision() {
+        let tmp = tempfile::tempdir().unwrap();
+        let res = tmp.path().join("res");
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@t"])
+                .arg("-C")
+                .arg(tmp.path())
+                .args(args)
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {args:?}");
+        };
+        write(
+            &res.join("values.xml"),
+            "<resources><string name=\"title\">Sign in</string>\
+             <string name=\"legacy\">Old</string></resources>",
+        );
+        write(
+            &res.join("auth/values.xml"),
+            "<resources><color name=\"accent\">#ff0000</color></resources>",
+        );
+        git(&["init", "-q"]);
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "resources"]);
+        write(
+            &res.join("values.xml"),
+            "<resources><string name=\"title\">Log in</string>\
+             <string name=\"subtitle\">Welcome</string></resources>",
+        );
+
+        let plan = BuildPlan::new(res.clone(), None, "debug");
+        assert_eq!(
+            commands::diff(&plan, "HEAD").unwrap(),
+            "3 resources changed:\n\
+             \x20   - legacy    string \"Old\"\n\
+             \x20   + subtitle  string \"Welcome\"\n\
+             \x20   ~ title     string \"Sign in\" → string \"Log in\"\n"
+        );
+        let Err(message) = commands::diff(&plan, "v9") else {
+            panic!("v9 is not a revision");
+        };
+        assert!(message.contains("v9"), "{message}");
+    }
+
+    #[test]
+    fn resolves_the_members_with_resources() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        write(
+            &root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"app\", \"core\"]\nresolver = \"2\"\n",
+        );
+        write(
+            &root.join("app/Cargo.toml"),
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n\
+             [package.metadata]\nr-resources = { res-dir = \"assets\", root-module = \"ui\" }\n",
+        );
+        write(&root.join("app/src/lib.rs"), "");
+        write(&root.join("app/assets/values.xml"), "<resources/>");
+        write(
+            &root.join("core/Cargo.toml"),
+            "[package]\nname = \"core\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+        );
+        write(&root.join("core/src/lib.rs"), "");
+        let manifest = root.join("Cargo.toml");
+        let args = |package: Option<&str>, release: bool| Args {
+            command: "validate".to_string(),
+            package: package.map(String::from),
+            manifest_path: Some(manifest.clone()),
+            release,
+            output: None,
+            base: "HEAD".to_string(),
+            verbose: false,
+        };
+
+        let found = packages(&args(None, false)).unwrap();
+        let names: Vec<&str> = found.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["app"]);
+        let plan = &found[0].plan;
+        assert!(plan.resources_dir.ends_with("app/assets"), "{:?}", plan.resources_dir);
+        assert_eq!(plan.root_module, "ui");
+        assert_eq!(plan.profile, "debug");
+
+        let app = packages(&args(Some("app"), true)).unwrap();
+        assert_eq!(app[0].plan.profile, "release");
+        let Err(message) = packages(&args(Some("core"), false)) else {
+            panic!("core has no resources");
+        };
+        assert!(message.starts_with("package 'core' has no resource directory"));
+        let Err(message) = packages(&args(Some("web"), false)) else {
+            panic!("web is not a member");
+        };
+        assert_eq!(message, "no package 'web' in the workspace");
+
+        // A misspelled key fails rather than being ignored
+        write(
+            &root.join("core/Cargo.toml"),
+            "[package]\nname = \"core\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n\
+             [package.metadata.r-resources]\nres-dri = \"assets\"\n",
+        );
+        let Err(message) = packages(&args(None, false)) else {
+            panic!("the unknown key must fail");
+        };
+        assert!(
+            message.starts_with("core: [package.metadata.r-resources]: unknown keys res-dri"),
+            "{message}"
+        );
+    }
+}
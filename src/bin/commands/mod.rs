@@ -0,0 +1,112 @@
+//! Commands shared by `r-resources` and `cargo r-resources`, which differ
+//! in how they find the resources: a directory given on the command line,
+//! or the packages of a Cargo workspace.
+
+use std::path::Path;
+use std::process::Command;
+
+use r_resources::generator::analysis::ValidationOptions;
+use r_resources::generator::generation::{
+    diff_report, emit_markdown, resource_diff, stats_table,
+};
+use r_resources::generator::pipeline::{build_graph, PipelineOutput};
+use r_resources::generator::{build_with_plan_and_options, dry_run_report};
+use r_resources::BuildPlan;
+
+/// Builds the graph of `plan`, printing its notes when `verbose`
+pub fn load(plan: &BuildPlan, verbose: bool) -> Result<PipelineOutput, String> {
+    let output = build_graph(plan).map_err(|err| err.to_string())?;
+    if verbose {
+        for note in &output.analysis_result.notes {
+            eprintln!("note: {note}");
+        }
+    }
+    Ok(output)
+}
+
+/// Runs the whole build of a dry-run `plan` and reports what would be
+/// generated; errors fail the command
+pub fn check(
+    plan: &BuildPlan,
+    options: ValidationOptions,
+) -> Result<String, String> {
+    let artifacts = build_with_plan_and_options(plan, options)
+        .map_err(|err| err.to_string())?;
+    Ok(dry_run_report(&artifacts, None))
+}
+
+/// Builds a dry-run `plan` and reports the sizes of its static data
+pub fn stats(
+    plan: &BuildPlan,
+    options: ValidationOptions,
+) -> Result<String, String> {
+    let artifacts = build_with_plan_and_options(plan, options)
+        .map_err(|err| err.to_string())?;
+    Ok(stats_table(&artifacts.stats))
+}
+
+/// Markdown overview of the resources
+pub fn docs(plan: &BuildPlan, verbose: bool) -> Result<String, String> {
+    let output = load(plan, verbose)?;
+    Ok(emit_markdown(&output.graph))
+}
+
+/// Resources added, removed, or changed since the git revision `base`
+/// (`HEAD`, a branch, a commit); test resources are left out
+pub fn diff(plan: &BuildPlan, base: &str) -> Result<String, String> {
+    let mut plan = plan.clone();
+    plan.tests_resources_dir = None;
+    let current = load(&plan, plan.verbose)?;
+    let checkout = tempfile::tempdir()
+        .map_err(|err| format!("failed to create a directory: {err}"))?;
+    checkout_resources(&plan.resources_dir, base, checkout.path())?;
+    plan.resources_dir = checkout.path().to_path_buf();
+    let before = load(&plan, false)
+        .map_err(|message| format!("at {base}: {message}"))?;
+    Ok(diff_report(&resource_diff(&before.graph, &current.graph)))
+}
+
+/// Writes the files of `res_dir` as of the git revision `rev` to `into`
+fn checkout_resources(res_dir: &Path, rev: &str, into: &Path) -> Result<(), String> {
+    let listing =
+        git(res_dir, &["ls-tree", "-r", "-z", "--name-only", rev, "--", "."])?;
+    for name in listing.split(|&byte| byte == 0).filter(|name| !name.is_empty()) {
+        let name = String::from_utf8_lossy(name);
+        let content = git(res_dir, &["show", &format!("{rev}:./{name}")])?;
+        let path = into.join(name.as_ref());
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|err| {
+                format!("failed to create '{}': {err}", parent.display())
+            })?;
+        }
+        std::fs::write(&path, content)
+            .map_err(|err| format!("failed to write '{}': {err}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Standard output of `git <args>` run in `dir`
+fn git(dir: &Path, args: &[&str]) -> Result<Vec<u8>, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|err| format!("failed to run git: {err}"))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(output.stdout)
+}
+
+/// Writes to `output`, or to stdout without it
+pub fn write_output(output: Option<&Path>, text: &str) -> Result<(), String> {
+    match output {
+        Some(path) => std::fs::write(path, text)
+            .map_err(|err| format!("failed to write '{}': {err}", path.display())),
+        None => {
+            print!("{text}");
+            Ok(())
+        }
+    }
+}
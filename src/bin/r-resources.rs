@@ -0,0 +1,197 @@
+//! `r-resources` command line tool.
+//!
+//! ```text
+//! r-resources check [--res-dir res] [--verbose]
+//! r-resources diff [--res-dir res] [--base HEAD] [--verbose]
+//! r-resources docs [--res-dir res] [--output RESOURCES.md] [--verbose]
+//! r-resources paths [--res-dir res] [--format table|json] [--verbose]
+//! r-resources stats [--res-dir res] [--verbose]
+//! r-resources uses <key> [--res-dir res] [--verbose]
+//! ```
+
+mod commands;
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use r_resources::generator::generation::{
+    paths_json, paths_table, resource_paths, uses_report,
+};
+use r_resources::generator::ir::ResourceKey;
+use r_resources::BuildPlan;
+
+const USAGE: &str = "\
+usage: r-resources <command> [options]
+
+commands:
+    check    Build the resources without writing anything, reporting
+             warnings, errors, and what would be generated
+    diff     List the resources added, removed, or changed since a git
+             revision (`--base`, default HEAD)
+    docs     Write a markdown overview of the resources
+    paths    List the Rust paths generated for every resource key
+    stats    Report the bytes of static data the resources contribute, by
+             namespace and kind
+    uses     List the resources that reference, interpolate, or call a key
+             (`uses auth/title`), directly or through another resource
+
+options:
+    --res-dir <dir>     Resource directory (default: res)
+    --output <file>     Write to a file instead of stdout
+    --base <rev>        `diff` baseline: a commit, branch, or tag
+                        (default: HEAD)
+    --format <format>   `paths` output: table (default) or json
+    --verbose           Also print notes, e.g. elements left out for another
+                        profile";
+
+/// Parsed command line
+struct Args {
+    command: String,
+    /// Resource key given to `uses`
+    key: Option<String>,
+    res_dir: PathBuf,
+    output: Option<PathBuf>,
+    /// Git revision `diff` compares against
+    base: String,
+    format: Format,
+    verbose: bool,
+}
+
+/// Output of the `paths` command
+#[derive(Clone, Copy)]
+enum Format {
+    Table,
+    Json,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let command = args.next().ok_or("missing command")?;
+    let mut parsed = Args {
+        command,
+        key: None,
+        res_dir: PathBuf::from("res"),
+        output: None,
+        base: "HEAD".to_string(),
+        format: Format::Table,
+        verbose: false,
+    };
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{arg} needs a value"));
+        match arg.as_str() {
+            "--res-dir" => parsed.res_dir = PathBuf::from(value()?),
+            "--verbose" => parsed.verbose = true,
+            "--output" => parsed.output = Some(PathBuf::from(value()?)),
+            "--base" => parsed.base = value()?,
+            "--format" => {
+                parsed.format = match value()?.as_str() {
+                    "table" => Format::Table,
+                    "json" => Format::Json,
+                    other => return Err(format!("unknown format '{other}'")),
+                }
+            }
+            _ if !arg.starts_with('-') && parsed.key.is_none() => {
+                parsed.key = Some(arg)
+            }
+            _ => return Err(format!("unknown option '{arg}'")),
+        }
+    }
+    Ok(parsed)
+}
+
+/// Runs the whole build as a dry run; errors fail the command
+fn check(args: &Args) -> Result<(), String> {
+    let tests_dir = args.res_dir.join("tests");
+    let tests_dir = tests_dir.is_dir().then_some(tests_dir);
+    let mut plan =
+        BuildPlan::new(args.res_dir.clone(), tests_dir, "debug").dry_run();
+    plan.verbose = args.verbose;
+    write_output(args, &commands::check(&plan, Default::default())?)
+}
+
+/// Lists the resources changed since `--base`
+fn diff(args: &Args) -> Result<(), String> {
+    let mut plan = BuildPlan::new(args.res_dir.clone(), None, "debug");
+    plan.verbose = args.verbose;
+    write_output(args, &commands::diff(&plan, &args.base)?)
+}
+
+fn docs(args: &Args) -> Result<(), String> {
+    let plan = BuildPlan::new(args.res_dir.clone(), None, "debug");
+    write_output(args, &commands::docs(&plan, args.verbose)?)
+}
+
+fn paths(args: &Args) -> Result<(), String> {
+    let plan = BuildPlan::new(args.res_dir.clone(), None, "debug");
+    let output = commands::load(&plan, args.verbose)?;
+    let report = resource_paths(
+        &output.graph,
+        &plan.type_registry(),
+        &output.analysis_result.warnings,
+        &plan.root_module,
+        &plan.naming,
+    );
+    let text = match args.format {
+        Format::Table => paths_table(&report),
+        Format::Json => paths_json(&report),
+    };
+    write_output(args, &text)
+}
+
+/// Builds the resources without writing anything and prints their sizes
+fn stats(args: &Args) -> Result<(), String> {
+    let mut plan =
+        BuildPlan::new(args.res_dir.clone(), None, "debug").dry_run();
+    plan.verbose = args.verbose;
+    write_output(args, &commands::stats(&plan, Default::default())?)
+}
+
+/// Lists what references, interpolates, or calls the key
+fn uses(args: &Args) -> Result<(), String> {
+    let key = args
+        .key
+        .as_deref()
+        .ok_or("uses needs a resource key, e.g. `uses auth/title`")?;
+    let plan = BuildPlan::new(args.res_dir.clone(), None, "debug");
+    let output = commands::load(&plan, args.verbose)?;
+    let report =
+        uses_report(&output.graph, &ResourceKey::from_path(key))?;
+    write_output(args, &report)
+}
+
+/// Writes to `--output`, or to stdout without it
+fn write_output(args: &Args, text: &str) -> Result<(), String> {
+    commands::write_output(args.output.as_deref(), text)
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    let result = match args.command.as_str() {
+        "check" => check(&args),
+        "diff" => diff(&args),
+        "docs" => docs(&args),
+        "paths" => paths(&args),
+        "stats" => stats(&args),
+        "uses" => uses(&args),
+        "help" | "--help" | "-h" => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        other => {
+            eprintln!("error: unknown command '{other}'\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
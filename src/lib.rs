@@ -94,6 +94,37 @@ pub fn build() {
 /// Build plan for custom resource generation
 pub use generator::input::BuildPlan;
 
+/// Case of the generated names, set through [`BuildPlan::naming`]
+pub use generator::casing::{Case, NamingConfig};
+
+/// Handler for a resource type; implement it to support project-specific
+/// tags and register it with [`BuildPlan::with_custom_types`]
+pub use generator::ir::types::{EmittedItem, ResourceType};
+
+/// Naming rules of the generated code, for tools that need to predict
+/// the Rust path of an XML key without running a build:
+///
+/// ```rust
+/// use r_resources::{const_path_for, module_path_for, sanitize_identifier};
+///
+/// assert_eq!(sanitize_identifier("error-codes"), "error_codes");
+/// assert_eq!(
+///     const_path_for("auth/error-codes/not-found"),
+///     "r::auth::error_codes::NOT_FOUND"
+/// );
+/// assert_eq!(
+///     module_path_for(&["auth", "error-codes"]),
+///     "r::auth::error_codes"
+/// );
+/// ```
+pub use generator::generation::{const_path_for, module_path_for};
+#[doc(no_inline)]
+pub use generator::utils::{const_identifier, sanitize_identifier};
+
+/// A warning of [`build_with_plan`], with its code, key, and file, as
+/// listed in `OutputArtifacts::warnings`
+pub use generator::analysis::AnalysisWarning;
+
 /// Builds resources using a custom build plan (for CLI or advanced setups).
 pub fn build_with_plan(
     plan: &BuildPlan,
@@ -104,11 +135,111 @@ pub fn build_with_plan(
     generator::build_with_plan(plan)
 }
 
+/// Builds resources using a custom build plan, plus resources constructed
+/// in the build script (see [`generator::ir::ResourceGraph::merge`]).
+pub fn build_with_graph(
+    plan: &BuildPlan,
+    extra: generator::ir::ResourceGraph,
+) -> Result<
+    generator::generation::OutputArtifacts,
+    generator::BuildError,
+> {
+    generator::build_with_graph(plan, extra)
+}
+
+/// Parsed resources, for front-ends that read something other than XML
+#[cfg(feature = "tooling")]
+pub use generator::parsing::{ParsedResource, ParsedResourceFile, ScalarValue};
+
+/// Builds resources handed over as parsed files, e.g. by a CSV reader in a
+/// build script, instead of loading XML. `plan` supplies the type,
+/// validation, and generation settings; its directories, profile, tag
+/// aliases, and `file_as_namespace` are not used. Analysis runs as usual:
+///
+/// ```rust
+/// use r_resources::{BuildPlan, ParsedResource, ParsedResourceFile, ResourceKey};
+///
+/// let files = vec![
+///     ParsedResourceFile::new(
+///         "strings.csv".into(),
+///         false,
+///         vec![
+///             ParsedResource::string("title", "Demo"),
+///             ParsedResource::number("max_items", "10", None),
+///         ],
+///     ),
+///     ParsedResourceFile::new(
+///         "flags.csv".into(),
+///         false,
+///         vec![
+///             ParsedResource::bool("beta", true),
+///             ParsedResource::string("title", "Other"),
+///         ],
+///     ),
+/// ];
+/// let plan = BuildPlan::new("csv".into(), None, "release");
+/// let artifacts = r_resources::build_from_parsed(files, &plan).unwrap();
+///
+/// assert!(artifacts.rust.contains("pub const MAX_ITEMS: i64 = 10;"));
+/// assert!(artifacts.rust.contains("pub const BETA: bool = true;"));
+/// // `title` is defined in both files
+/// let title = Some(ResourceKey::from_path("title"));
+/// assert!(artifacts.warnings.iter().any(|w| w.key == title));
+/// ```
+#[cfg(feature = "tooling")]
+pub fn build_from_parsed(
+    files: Vec<ParsedResourceFile>,
+    plan: &BuildPlan,
+) -> Result<
+    generator::generation::OutputArtifacts,
+    generator::BuildError,
+> {
+    generator::build_from_parsed(files, plan)
+}
+
+/// The resource graph, for tools that inspect resources
+#[cfg(feature = "tooling")]
+pub use generator::ir::{ResourceGraph, ResourceKey};
+
+/// Loads, parses, and analyzes the resources of `plan` without generating
+/// code. The graph is indexed, so [`ResourceGraph::dependents_of`] lists
+/// the resources that reference, interpolate, or call a key:
+///
+/// ```rust
+/// use r_resources::{BuildPlan, ResourceKey};
+///
+/// let dir = tempfile::tempdir().unwrap();
+/// std::fs::write(
+///     dir.path().join("values.xml"),
+///     r#"<resources>
+///         <string name="app_name">Demo</string>
+///         <string name="title">@string/app_name</string>
+///         <string name="about">About @string/app_name</string>
+///     </resources>"#,
+/// )
+/// .unwrap();
+/// let plan = BuildPlan::new(dir.path().into(), None, "debug");
+/// let graph = r_resources::load_graph(&plan).unwrap();
+///
+/// let users = graph.dependents_of(&ResourceKey::from_path("app_name"));
+/// assert_eq!(users, [ResourceKey::from_path("about"), ResourceKey::from_path("title")]);
+/// ```
+#[cfg(feature = "tooling")]
+pub fn load_graph(
+    plan: &BuildPlan,
+) -> Result<ResourceGraph, generator::pipeline::PipelineError> {
+    generator::pipeline::build_graph(plan).map(|output| output.graph)
+}
+
 /// Includes the generated resources from the build script.
 ///
 /// This macro must be called once in your code (typically in `main.rs` or `lib.rs`)
 /// to include the generated resource constants.
 ///
+/// The build script must call [`build`] (or
+/// [`generator::write_generated_code_from_env`]); otherwise this fails with
+/// an error saying so.
+///
 /// # Example
 ///
 /// ```rust,ignore
@@ -119,14 +250,110 @@ pub fn build_with_plan(
 #[macro_export]
 macro_rules! include_resources {
     () => {
-        include!(concat!(env!("OUT_DIR"), "/r_generated.rs"));
+        include!(env!(
+            "R_RESOURCES_GENERATED",
+            "r-resources: generated file not found; add `r_resources::build()` to your build.rs"
+        ));
+    };
+}
+
+/// Includes only the `r_tests` module generated from `res/tests/`, e.g. in
+/// an integration test that does not include the main resources.
+///
+/// The build writes the file whenever `res/tests/` has resources, so this
+/// works without `R_RESOURCES_INCLUDE_TESTS`. Do not combine it with
+/// [`include_resources!`] when that already embeds `r_tests`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// r_resources::include_test_resources!();
+/// let _ = r_tests::FIXTURE_USER;
+/// ```
+#[macro_export]
+macro_rules! include_test_resources {
+    () => {
+        include!(env!(
+            "R_RESOURCES_TESTS_GENERATED",
+            "r-resources: generated test resources not found; add XML files to res/tests/ and `r_resources::build()` to your build.rs"
+        ));
     };
 }
 
 pub use bigdecimal::BigDecimal;
 
+/// Parses the literal of a high-precision `<number>`; every generated
+/// `LazyLock<BigDecimal>` static calls this one function, so the parse
+/// code is not repeated per constant. The literal was validated at build
+/// time.
+#[doc(hidden)]
+#[must_use]
+pub fn __parse_big_decimal(literal: &str) -> BigDecimal {
+    literal.parse().expect("valid decimal literal")
+}
+
+/// Snapshot assertion for generated artifacts, see [`generator::generation::OutputArtifacts::normalized`]
+#[cfg(feature = "test-utils")]
+pub use generator::generation::assert_artifact_matches;
+
+/// Re-exported for the `Schedule` accessors generated from `<cron>` resources
+#[cfg(feature = "cron")]
+pub use cron;
+
+/// Re-exported for the `DateTime<Utc>` accessors generated from `<datetime>` resources
+#[cfg(feature = "chrono")]
+pub use chrono;
+
+/// A generated constant as a type, with its key and value. With
+/// [`BuildPlan::emit_resource_markers`] each constant gets a zero-sized
+/// marker implementing it (`r::auth::Title` for `r::auth::TITLE`), so a
+/// resource can be passed generically, e.g. to log its key with its value:
+///
+/// ```rust
+/// use r_resources::Resource;
+///
+/// fn describe<R: Resource>(resource: R) -> String
+/// where
+///     R::Value: std::fmt::Debug,
+/// {
+///     format!("{}={:?}", resource.key(), resource.value())
+/// }
+///
+/// // As generated for `<ns name="auth"><string name="title">`
+/// struct Title;
+/// impl Resource for Title {
+///     const KEY: &'static str = "auth/title";
+///     type Value = &'static str;
+///     const VALUE: Self::Value = "Sign in";
+/// }
+///
+/// assert_eq!(describe(Title), "auth/title=\"Sign in\"");
+/// ```
+///
+/// Templates are functions, and high-precision numbers are statics, so
+/// neither has a marker.
+pub trait Resource {
+    /// Key as written in XML, with its namespaces: `auth/title`
+    const KEY: &'static str;
+    /// Type of the constant
+    type Value: 'static;
+    /// Value of the constant
+    const VALUE: Self::Value;
+
+    /// [`Self::KEY`]
+    fn key(&self) -> &'static str {
+        Self::KEY
+    }
+
+    /// [`Self::VALUE`]
+    fn value(&self) -> Self::Value {
+        Self::VALUE
+    }
+}
+
 /// Typed color parsed from hex (e.g., `#RRGGBB` or `#AARRGGBB`).
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Color {
     pub r: u8,
     pub g: u8,
@@ -168,6 +395,7 @@ impl Color {
 
 /// Typed URL parts split at build-time.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct UrlParts {
     scheme: &'static str,
     host: &'static str,
@@ -199,6 +427,7 @@ impl UrlParts {
 
 /// 2D position.
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Position {
     x: f64,
     y: f64,
@@ -231,6 +460,7 @@ impl Position {
 
 /// Geographic coordinates.
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct LatLng {
     lat: f64,
     lng: f64,
@@ -250,3 +480,172 @@ impl LatLng {
         self.lng
     }
 }
+
+/// Calendar date from a `<date>` resource, validated at build time.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    #[must_use]
+    pub const fn new(year: i32, month: u8, day: u8) -> Self {
+        Self { year, month, day }
+    }
+    #[must_use]
+    pub const fn year(&self) -> i32 {
+        self.year
+    }
+    #[must_use]
+    pub const fn month(&self) -> u8 {
+        self.month
+    }
+    #[must_use]
+    pub const fn day(&self) -> u8 {
+        self.day
+    }
+    /// `(year, month, day)`
+    #[must_use]
+    pub const fn as_tuple(&self) -> (i32, u8, u8) {
+        (self.year, self.month, self.day)
+    }
+}
+
+/// `MAJOR.MINOR.PATCH` version from a `<version>` resource. Orders by
+/// major, then minor, then patch.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl Version {
+    #[must_use]
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+    /// `self >= min`, usable in const contexts where `Ord` is not
+    #[must_use]
+    pub const fn at_least(&self, min: &Self) -> bool {
+        if self.major != min.major {
+            return self.major > min.major;
+        }
+        if self.minor != min.minor {
+            return self.minor > min.minor;
+        }
+        self.patch >= min.patch
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Unit of a [`Dimension`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum DimensionUnit {
+    /// Density-independent pixels (`dp`, or `dip`)
+    Dp,
+    /// Scale-independent pixels (`sp`), for text
+    Sp,
+    /// Physical pixels (`px`)
+    Px,
+}
+
+impl DimensionUnit {
+    /// Suffix as written in resources (`"dp"`)
+    #[must_use]
+    pub const fn suffix(self) -> &'static str {
+        match self {
+            Self::Dp => "dp",
+            Self::Sp => "sp",
+            Self::Px => "px",
+        }
+    }
+}
+
+/// Length from a `<dimension>` resource (`16dp`).
+///
+/// Dimensions add up and scale like numbers, but only within a unit:
+/// `16dp + 4px` has no meaning until a density is known, so `+` panics on
+/// mixed units and [`Dimension::checked_add`] returns `None`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Dimension {
+    value: f32,
+    unit: DimensionUnit,
+}
+
+impl Dimension {
+    #[must_use]
+    pub const fn new(value: f32, unit: DimensionUnit) -> Self {
+        Self { value, unit }
+    }
+    #[must_use]
+    pub const fn value(&self) -> f32 {
+        self.value
+    }
+    #[must_use]
+    pub const fn unit(&self) -> DimensionUnit {
+        self.unit
+    }
+    /// Pixels on a screen of the given `density`, as Android defines it:
+    /// the scale relative to a 160 dpi baseline (`DisplayMetrics.density`,
+    /// 1.0 on mdpi, 1.5 on hdpi, 2.0 on xhdpi, 3.0 on xxhdpi). `dp` are
+    /// multiplied by it. `sp` are too, which assumes the default font
+    /// scale of 1.0; multiply `density` by the user's font scale to honor
+    /// it. `px` are returned as they are.
+    #[must_use]
+    pub fn to_px(&self, density: f32) -> f32 {
+        match self.unit {
+            DimensionUnit::Dp | DimensionUnit::Sp => self.value * density,
+            DimensionUnit::Px => self.value,
+        }
+    }
+    /// Sum of two dimensions in the same unit, or `None` when they differ
+    #[must_use]
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        (self.unit == other.unit)
+            .then(|| Self::new(self.value + other.value, self.unit))
+    }
+}
+
+impl std::ops::Add for Dimension {
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// When the units differ; see [`Dimension::checked_add`]
+    fn add(self, other: Self) -> Self {
+        self.checked_add(other).unwrap_or_else(|| {
+            panic!("cannot add {self} and {other}: units differ")
+        })
+    }
+}
+
+impl std::ops::Mul<f32> for Dimension {
+    type Output = Self;
+
+    fn mul(self, factor: f32) -> Self {
+        Self::new(self.value * factor, self.unit)
+    }
+}
+
+/// `16dp`, `1.5sp`
+impl std::fmt::Display for Dimension {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.value, self.unit.suffix())
+    }
+}
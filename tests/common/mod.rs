@@ -0,0 +1,22 @@
+//! Helpers shared by the integration tests that compile generated code
+
+use std::path::PathBuf;
+
+/// This crate's rlib and the directory of its dependencies, next to the
+/// test binary
+pub fn r_resources_rlib() -> (PathBuf, PathBuf) {
+    let exe = std::env::current_exe().unwrap();
+    let deps = exe.parent().unwrap().to_path_buf();
+    let rlib = std::fs::read_dir(&deps)
+        .unwrap()
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with("libr_resources-") && name.ends_with(".rlib")
+        })
+        .max_by_key(|entry| entry.metadata().and_then(|m| m.modified()).ok())
+        .expect("the r_resources rlib next to the test binary")
+        .path();
+    (deps, rlib)
+}
@@ -0,0 +1,129 @@
+//! End-to-end builds of the resource trees under `tests/fixtures/`.
+//!
+//! Each fixture is a `res/` directory (with `res/tests/` for test
+//! resources) and a `checks.rs` of `#[test]` functions using the generated
+//! `r` module. The directory goes through `build_with_plan`, the artifact
+//! is compiled with rustc together with the checks, and the checks are
+//! run, so constants and functions are compared by value rather than by
+//! the text of the generated code. A new feature adds a directory there
+//! and a test here.
+
+mod common;
+
+use std::path::Path;
+use std::process::Command;
+
+use r_resources::generator::build_with_plan;
+use r_resources::BuildPlan;
+
+use common::r_resources_rlib;
+
+/// Builds `tests/fixtures/<name>` for `profile`, checks that the build
+/// reports warnings of exactly the `warnings` codes (sorted), then
+/// compiles and runs its `checks.rs` against the artifact
+fn check_fixture(name: &str, profile: &str, warnings: &[&str]) {
+    check_fixture_with(name, profile, warnings, |_| {});
+}
+
+/// [`check_fixture`] with the optional modules `configure` enables
+fn check_fixture_with(
+    name: &str,
+    profile: &str,
+    warnings: &[&str],
+    configure: impl FnOnce(&mut BuildPlan),
+) {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name);
+    let res_dir = fixture.join("res");
+    let tests_dir = res_dir.join("tests");
+    let tests_dir = tests_dir.is_dir().then_some(tests_dir);
+    let mut plan = BuildPlan::new(res_dir, tests_dir, profile);
+    plan.embed_test_resources = true;
+    configure(&mut plan);
+    let artifacts = build_with_plan(&plan)
+        .unwrap_or_else(|err| panic!("fixture '{name}' failed to build:\n{err}"));
+
+    let mut codes: Vec<&str> =
+        artifacts.warnings.iter().map(|warning| warning.code).collect();
+    codes.sort_unstable();
+    assert_eq!(codes, warnings, "{:#?}", artifacts.rendered_warnings());
+
+    let tmp = tempfile::tempdir().unwrap();
+    let lib = tmp.path().join("lib.rs");
+    let checks = fixture.join("checks.rs");
+    let source = format!(
+        "{}\n#[cfg(test)]\nmod checks {{\n    use super::*;\n    include!({:?});\n}}\n",
+        artifacts.rust,
+        checks.display().to_string()
+    );
+    std::fs::write(&lib, source).unwrap();
+
+    let (deps, rlib) = r_resources_rlib();
+    let rustc = std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let binary = tmp.path().join("checks");
+    let output = Command::new(&rustc)
+        .args(["--edition", "2021", "--crate-name", "fixture", "--test"])
+        .arg("-L")
+        .arg(format!("dependency={}", deps.display()))
+        .arg("--extern")
+        .arg(format!("r_resources={}", rlib.display()))
+        .arg("-o")
+        .arg(&binary)
+        .arg(&lib)
+        .output()
+        .expect("run rustc");
+    assert!(
+        output.status.success(),
+        "fixture '{name}' does not compile:\n{}\n--- generated code ---\n{}",
+        String::from_utf8_lossy(&output.stderr),
+        artifacts.rust
+    );
+
+    let output = Command::new(&binary).output().expect("run the checks");
+    assert!(
+        output.status.success(),
+        "checks of fixture '{name}' failed:\n{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+#[test]
+fn namespaces_references_and_colors() {
+    // `TITLE` is in `ui::header` and `checkout`, so not in the prelude
+    check_fixture("namespaces", "debug", &["RR0086"]);
+}
+
+#[test]
+fn duplicates_keep_the_first_definition() {
+    check_fixture("duplicates", "debug", &["RR0001"]);
+}
+
+#[test]
+fn profiles_keep_the_active_elements() {
+    check_fixture("profiles", "release", &[]);
+}
+
+#[test]
+fn templates_format_their_arguments() {
+    check_fixture("templates", "debug", &[]);
+}
+
+#[test]
+fn locales_are_complete() {
+    // Every key is in each locale, so none is in the prelude
+    check_fixture("locales", "debug", &["RR0086"]);
+}
+
+#[test]
+fn lookup_finds_colors_by_key() {
+    check_fixture_with("lookup", "debug", &[], |plan| plan.emit_lookup = true);
+}
+
+#[test]
+fn resource_markers_implement_the_trait() {
+    check_fixture_with("markers", "debug", &["RR0001"], |plan| {
+        plan.emit_resource_markers = true;
+    });
+}
@@ -0,0 +1,12 @@
+#[test]
+#[allow(deprecated)]
+fn first_definition_wins() {
+    assert_eq!(r::TITLE, "First");
+    assert_eq!(r::SUBTITLE, "Below First");
+}
+
+#[test]
+fn namespaces_merge_across_files() {
+    assert_eq!(r::auth::LOGIN, "Sign in");
+    assert_eq!(r::auth::LOGOUT, "Sign out");
+}
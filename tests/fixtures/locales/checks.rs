@@ -0,0 +1,16 @@
+#[test]
+fn each_locale_has_its_module() {
+    assert_eq!(r::api::en::UNAUTHORIZED, "Unauthorized");
+    assert_eq!(r::api::fr::UNAUTHORIZED, "Non autorisé");
+    assert_eq!(r::api::fr::greeting("Léa"), "Bonjour Léa");
+}
+
+#[test]
+fn locales_switch_at_runtime() {
+    let unauthorized = |locale: &str| match locale {
+        "fr" => r::api::fr::UNAUTHORIZED,
+        _ => r::api::en::UNAUTHORIZED,
+    };
+    assert_eq!(unauthorized("fr"), "Non autorisé");
+    assert_eq!(unauthorized("de"), "Unauthorized");
+}
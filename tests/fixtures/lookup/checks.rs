@@ -0,0 +1,21 @@
+#[test]
+fn colors_by_full_key() {
+    use r::colors::by_name;
+    use r_resources::Color;
+    let accent = Some(Color::new(0xFF, 0x57, 0x22, 0xFF));
+    let primary = Some(Color::new(0x33, 0x66, 0xFF, 0x80));
+    assert_eq!(by_name("ui/colors/primary"), primary);
+    assert_eq!(by_name("accent"), accent);
+    assert_eq!(by_name("ui/colors/link"), accent);
+}
+
+#[test]
+fn unknown_and_unlisted_keys() {
+    use r::colors::by_name;
+    assert_eq!(by_name("Accent"), None);
+    assert_eq!(by_name("primary"), None);
+    // Not a hex literal, so not in the table
+    assert_eq!(by_name("ui/colors/shadow"), None);
+    // Crate-visible resources are left out
+    assert_eq!(by_name("dev/debug"), None);
+}
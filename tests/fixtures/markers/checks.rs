@@ -0,0 +1,25 @@
+fn log_resource<R: r_resources::Resource>(resource: R) -> String
+where
+    R::Value: std::fmt::Debug,
+{
+    format!("{}={:?}", resource.key(), resource.value())
+}
+
+#[test]
+fn markers_log_their_key_and_value() {
+    assert_eq!(log_resource(r::auth::Title), r#"auth/title="Sign in""#);
+    assert_eq!(log_resource(r::auth::Port), "auth/port=8080");
+    assert_eq!(
+        log_resource(r::auth::Providers),
+        r#"auth/providers=["github", "gitlab"]"#
+    );
+    assert_eq!(log_resource(r::internal::Debug), "internal/debug=true");
+    assert_eq!(r::auth::greet("Bob"), "Hi Bob");
+}
+
+// Deprecated as a duplicate, yet usable without a warning
+#[test]
+#[deny(warnings)]
+fn duplicates_have_markers_without_warnings() {
+    assert_eq!(log_resource(r::AppName), r#"app_name="Other""#);
+}
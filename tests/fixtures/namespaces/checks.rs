@@ -0,0 +1,25 @@
+#[test]
+fn nested_namespaces_and_references() {
+    assert_eq!(r::APP_NAME, "Shop");
+    assert_eq!(r::ui::header::TITLE, "Welcome to Shop");
+    assert_eq!(r::ui::PADDING, 12u16);
+    assert_eq!(r::checkout::STEPS, ["Cart", "Checkout"]);
+    assert_eq!(r::checkout::BANNER, "Shop: pay securely");
+    assert!((r::checkout::TAX - 0.2).abs() < f32::EPSILON);
+}
+
+#[test]
+fn typed_colors() {
+    assert_eq!(r::ui::PRIMARY, "#3366FF");
+    assert_eq!(r::ui::PRIMARY_COLOR, r_resources::Color::new(0x33, 0x66, 0xFF, 0xFF));
+    assert_eq!(r::ui::ACCENT, r::ui::PRIMARY);
+    assert_eq!(r::ui::OVERLAY_COLOR.a, 0x80);
+}
+
+#[test]
+fn prelude_and_test_resources() {
+    use r::prelude::*;
+    assert_eq!(APP_NAME, "Shop");
+    assert_eq!(ui::header::TITLE, "Welcome to Shop");
+    assert_eq!(r_tests::users::ALICE, "alice@example.com");
+}
@@ -0,0 +1,6 @@
+// Built for the release profile
+#[test]
+fn release_values_are_kept() {
+    assert_eq!(r::API_URL, "https://api.example.com");
+    assert!(!r::logging::VERBOSE);
+}
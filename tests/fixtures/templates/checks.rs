@@ -0,0 +1,27 @@
+#[test]
+fn attribute_templates() {
+    assert_eq!(r::greeting("Alice", 5), "Hello Alice, you have 5 messages!");
+}
+
+#[test]
+fn namespaced_templates_call_each_other() {
+    assert_eq!(r::SIGNATURE, "The Acme team");
+    assert_eq!(r::mail::footer("Bob"), "Sent to Bob. The Acme team");
+    assert_eq!(
+        r::mail::full_email("Bob", "bob@example.com"),
+        "Hello Bob! Sent to bob@example.com. The Acme team"
+    );
+}
+
+#[test]
+fn plural_forms() {
+    assert_eq!(r::mail::inbox(1), "You have 1 message");
+    assert_eq!(r::mail::inbox(3), "You have 3 messages");
+}
+
+#[test]
+fn builder_style() {
+    let built = r::mail::welcome_args().name("Acme").count(2).build();
+    assert_eq!(built, r::mail::welcome("Acme", 2));
+    assert_eq!(built, "Welcome to Acme, 2 new");
+}
@@ -0,0 +1,178 @@
+//! The generated code compiles cleanly in a consumer crate that forbids
+//! `unsafe` and denies warnings: every emitter's output for a
+//! representative set of resources is built with rustc against this crate,
+//! with and without the optional modules, and inside a nested module.
+
+mod common;
+
+use std::path::Path;
+use std::process::Command;
+
+use common::r_resources_rlib;
+
+use r_resources::generator::build_with_plan;
+use r_resources::BuildPlan;
+
+/// Lints a strict consumer enables crate-wide
+const CRATE_ATTRIBUTES: &str = "\
+#![forbid(unsafe_code)]
+#![deny(warnings)]
+#![deny(rust_2018_idioms, unused_qualifications, trivial_casts)]
+";
+
+const VALUES: &str = r##"<resources>
+    <string name="app_name" doc="Name shown in the title bar">Demo</string>
+    <string name="separator" quoted="true">" | "</string>
+    <string name="footer">Made with @string/app_name</string>
+    <string name="device_name" max-len="32" as-bytes="true">Capteur été</string>
+    <number name="max_retries">3</number>
+    <number name="port" type="u16">8080</number>
+    <number name="ratio" type="f32">0.75</number>
+    <number name="big" type="bigdecimal">1234567890123456789012345678901234567890</number>
+    <number name="auto_big">123456789012345678901234567890.123456789</number>
+    <bool name="debug">false</bool>
+    <color name="primary">#3366FF</color>
+    <color name="overlay">#80000000</color>
+    <char name="bullet">•</char>
+    <percent name="zoom">37.5%</percent>
+    <size name="max_upload">25MB</size>
+    <date name="launch">2024-02-29</date>
+    <version name="api">1.4.2</version>
+    <dimension name="padding">16dp</dimension>
+    <mime-map name="mime"><entry ext="png">image/png</entry></mime-map>
+    <string-array name="langs" sorted="true"><item>fr</item><item>en</item></string-array>
+    <int-array name="grid"><row><item>1</item><item>2</item></row><row><item>3</item><item>4</item></row></int-array>
+    <enum name="priority" repr="u8"><item value="1">low</item><item value="10">high</item></enum>
+    <template name="greet"><string name="name"/>Hi {name}</template>
+    <template name="signed"><string name="name"/>{@greet}, from {name}</template>
+    <template name="inbox"><number name="count"/>{count} {count:plural(message|messages)}</template>
+    <template name="disk"><number name="size" type="u32"/>Free space in {size:plural(MB)}</template>
+    <template name="total"><number name="count" type="bigdecimal"/>{count} items</template>
+    <ns name="auth" doc="Sign-in screens">
+        <string name="title">Sign in</string>
+        <string name="brand">@string/app_name</string>
+        <color name="accent">@color/primary</color>
+        <ns name="errors">
+            <string name="credentials">Invalid credentials</string>
+        </ns>
+    </ns>
+    <ns name="error" error-enum="AppError">
+        <string name="offline">Offline</string>
+        <number name="offline_code" type="u16">503</number>
+        <string name="busy">Try again later</string>
+        <number name="busy_code">503</number>
+    </ns>
+    <ns name="internal" visibility="crate">
+        <bool name="verbose">true</bool>
+    </ns>
+</resources>"##;
+
+/// Defines `app_name` again, so it is emitted as a deprecated duplicate
+const OVERRIDES: &str = r#"<resources>
+    <string name="app_name">Other</string>
+    <ns name="auth"><string name="title">Log in</string></ns>
+</resources>"#;
+
+const FIXTURES: &str = r#"<resources>
+    <string name="fixture_user">test-user</string>
+    <number name="fixture_id" type="u32">42</number>
+</resources>"#;
+
+/// The plan of a resource directory holding the files above
+fn plan(dir: &Path) -> BuildPlan {
+    let res_dir = dir.join("res");
+    let tests_dir = res_dir.join("tests");
+    std::fs::create_dir_all(&tests_dir).unwrap();
+    std::fs::write(res_dir.join("values.xml"), VALUES).unwrap();
+    std::fs::write(res_dir.join("values_overrides.xml"), OVERRIDES).unwrap();
+    std::fs::write(tests_dir.join("fixtures.xml"), FIXTURES).unwrap();
+    let mut plan = BuildPlan::new(res_dir, Some(tests_dir), "debug");
+    plan.embed_test_resources = true;
+    plan
+}
+
+/// Uses the crate-visible resources, which would otherwise be dead code
+const CRATE_USES: &str = "
+pub fn verbose() -> bool {
+    r::internal::VERBOSE
+}
+";
+
+/// Compiles `code` as a library crate with [`CRATE_ATTRIBUTES`], then as
+/// a test crate so the smoke tests are checked too, panicking with
+/// rustc's output on failure
+fn assert_compiles_cleanly(dir: &Path, code: &str) {
+    let lib = dir.join("lib.rs");
+    let source = format!("{CRATE_ATTRIBUTES}{code}{CRATE_USES}");
+    std::fs::write(&lib, source).unwrap();
+    let (deps, rlib) = r_resources_rlib();
+    let rustc = std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let kinds: [&[&str]; 2] = [&["--crate-type", "lib"], &["--test"]];
+    for kind in kinds {
+        let output = Command::new(&rustc)
+            .args(["--edition", "2021", "--crate-name", "consumer"])
+            .args(kind)
+            .arg("-L")
+            .arg(format!("dependency={}", deps.display()))
+            .arg("--extern")
+            .arg(format!("r_resources={}", rlib.display()))
+            .arg("--out-dir")
+            .arg(dir)
+            .arg(&lib)
+            .output()
+            .expect("run rustc");
+        assert!(
+            output.status.success(),
+            "{}\n--- generated code ---\n{code}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
+
+#[test]
+fn default_output_compiles_under_strict_lints() {
+    let tmp = tempfile::tempdir().unwrap();
+    let artifacts = build_with_plan(&plan(tmp.path())).unwrap();
+    assert!(artifacts.rust.contains("pub mod r_tests {"));
+    assert!(artifacts.rust.contains("#[deprecated"));
+    assert_compiles_cleanly(tmp.path(), &artifacts.rust);
+}
+
+#[test]
+fn output_with_every_option_compiles_under_strict_lints() {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut plan = plan(tmp.path());
+    plan.emit_smoke_tests = true;
+    plan.emit_to_json = true;
+    plan.emit_meta = true;
+    plan.emit_lookup = true;
+    plan.emit_kind_modules = true;
+    plan.emit_resource_markers = true;
+    plan.intern_strings = true;
+    plan.template_builders = true;
+    let artifacts = build_with_plan(&plan).unwrap();
+    assert!(artifacts.rust.contains("pub mod colors {"));
+    assert_compiles_cleanly(tmp.path(), &artifacts.rust);
+}
+
+#[test]
+fn output_compiles_inside_a_nested_module() {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut plan = plan(tmp.path());
+    plan.emit_smoke_tests = true;
+    plan.emit_to_json = true;
+    plan.emit_meta = true;
+    plan.emit_lookup = true;
+    plan.emit_kind_modules = true;
+    plan.emit_resource_markers = true;
+    plan.intern_strings = true;
+    plan.template_builders = true;
+    let artifacts = build_with_plan(&plan).unwrap();
+    assert!(!artifacts.rust.contains("crate::"));
+    // As `mod resources { include_resources!(); }` would place it
+    let nested = format!(
+        "pub mod resources {{\n{}\n}}\npub use resources::r;\n",
+        artifacts.rust
+    );
+    assert_compiles_cleanly(tmp.path(), &nested);
+}
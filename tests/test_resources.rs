@@ -0,0 +1,145 @@
+//! `include_test_resources!` pulls in `r_tests` without the main resources
+
+r_resources::include_test_resources!();
+
+#[test]
+fn test_resources_are_included_on_their_own() {
+    assert_eq!(r_tests::FIXTURE_USER, "test-user");
+    assert_eq!(r_tests::FIXTURE_USER_ID, 42_u32);
+    assert_eq!(r_tests::prelude::FIXTURE_USER, r_tests::FIXTURE_USER);
+}
+
+#[test]
+fn enum_variants_round_trip_through_their_repr() {
+    use r_tests::FixturePriority;
+
+    for &variant in FixturePriority::ALL {
+        let value = u8::from(variant);
+        assert_eq!(FixturePriority::try_from(value), Ok(variant));
+    }
+    assert_eq!(u8::from(FixturePriority::High), 10);
+    assert_eq!(FixturePriority::High.as_str(), "high");
+    assert_eq!(FixturePriority::try_from(5), Err(5));
+}
+
+#[test]
+fn sorted_string_array_lookup() {
+    use r_tests::{fixture_langs_contains, FIXTURE_LANGS, FIXTURE_LANGS_LEN};
+
+    assert_eq!(FIXTURE_LANGS, &["de", "en", "fr"]);
+    assert!(fixture_langs_contains("en"));
+    assert!(!fixture_langs_contains("es"));
+    assert!(!fixture_langs_contains("EN"));
+
+    // Usable where a const length is required
+    let counts = [0u32; FIXTURE_LANGS_LEN];
+    assert_eq!(counts.len(), FIXTURE_LANGS.len());
+}
+
+#[test]
+fn uniform_rows_become_a_fixed_size_grid() {
+    use r_tests::{FIXTURE_LEVEL, FIXTURE_LEVEL_LEN};
+
+    let grid: [[i64; 3]; FIXTURE_LEVEL_LEN] = FIXTURE_LEVEL;
+    assert_eq!(grid[0], [0, 1, 0]);
+    assert_eq!(grid.iter().flatten().sum::<i64>(), 4);
+}
+
+#[test]
+fn array_items_inline_referenced_values() {
+    use r_tests::{FIXTURE_IDS, FIXTURE_LOGINS, FIXTURE_USER};
+
+    assert_eq!(FIXTURE_LOGINS, &[FIXTURE_USER, "guest"]);
+    assert_eq!(FIXTURE_IDS, &[42, 7]);
+}
+
+#[test]
+fn duration_and_size_arrays_hold_parsed_values() {
+    use r_tests::{FIXTURE_BACKOFF, FIXTURE_LIMITS};
+    use std::time::Duration;
+
+    assert_eq!(
+        FIXTURE_BACKOFF,
+        &[Duration::from_millis(100), Duration::from_millis(1500)]
+    );
+    assert_eq!(FIXTURE_LIMITS, &[1024, 2_000_000]);
+}
+
+#[test]
+fn size_is_a_const_byte_count() {
+    use r_tests::FIXTURE_MAX_UPLOAD;
+
+    const LIMIT_KIB: u64 = FIXTURE_MAX_UPLOAD / 1024;
+    assert_eq!(FIXTURE_MAX_UPLOAD, 1_572_864);
+    assert_eq!(LIMIT_KIB, 1536);
+}
+
+#[test]
+fn datetime_and_date_are_consts() {
+    use r_tests::{FIXTURE_LAUNCH, FIXTURE_PROMO_ENDS, FIXTURE_PROMO_ENDS_RFC3339};
+
+    const LAUNCH_YEAR: i32 = FIXTURE_LAUNCH.year();
+    assert_eq!(FIXTURE_PROMO_ENDS, 1_767_225_599);
+    assert_eq!(FIXTURE_PROMO_ENDS_RFC3339, "2025-12-31T23:59:59Z");
+    assert_eq!(LAUNCH_YEAR, 2025);
+    assert_eq!(FIXTURE_LAUNCH.as_tuple(), (2025, 6, 1));
+    assert!(FIXTURE_LAUNCH < r_resources::Date::new(2025, 6, 2));
+}
+
+#[test]
+fn version_compares_in_const_code() {
+    use r_resources::Version;
+    use r_tests::FIXTURE_MIN_SUPPORTED;
+
+    const { assert!(Version::new(1, 10, 0).at_least(&FIXTURE_MIN_SUPPORTED)) };
+    assert!(!Version::new(1, 1, 9).at_least(&FIXTURE_MIN_SUPPORTED));
+    assert!(Version::new(0, 9, 0) < FIXTURE_MIN_SUPPORTED);
+    assert_eq!(FIXTURE_MIN_SUPPORTED.to_string(), "1.2.0");
+}
+
+#[test]
+fn dimension_converts_to_pixels() {
+    use r_resources::DimensionUnit;
+    use r_tests::{FIXTURE_BODY_TEXT, FIXTURE_HAIRLINE, FIXTURE_PADDING};
+
+    const { assert!(FIXTURE_PADDING.value() == 16.0) };
+    assert_eq!(FIXTURE_PADDING.unit(), DimensionUnit::Dp);
+    assert_eq!(FIXTURE_PADDING.to_px(1.0), 16.0);
+    assert_eq!(FIXTURE_PADDING.to_px(1.5), 24.0);
+    assert_eq!(FIXTURE_PADDING.to_px(2.625), 42.0);
+    assert_eq!(FIXTURE_BODY_TEXT.to_px(3.0), 42.0);
+    // Pixels do not depend on the density
+    assert_eq!(FIXTURE_HAIRLINE.to_px(3.0), 1.0);
+    assert_eq!(FIXTURE_PADDING.to_string(), "16dp");
+    assert_eq!((FIXTURE_PADDING * 1.5).to_string(), "24dp");
+}
+
+#[test]
+fn dimension_arithmetic_keeps_units_apart() {
+    use r_tests::{FIXTURE_HAIRLINE, FIXTURE_PADDING};
+
+    assert_eq!((FIXTURE_PADDING + FIXTURE_PADDING).to_string(), "32dp");
+    assert_eq!(FIXTURE_PADDING.checked_add(FIXTURE_HAIRLINE), None);
+}
+
+#[test]
+#[should_panic(expected = "cannot add 16dp and 1px: units differ")]
+fn adding_dimensions_of_different_units_panics() {
+    let _ = r_tests::FIXTURE_PADDING + r_tests::FIXTURE_HAIRLINE;
+}
+
+#[test]
+fn attribute_template_is_a_function() {
+    assert_eq!(
+        r_tests::fixture_greeting("Ada", 3),
+        "Hello Ada, you have 3 messages!"
+    );
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn datetime_has_chrono_accessor() {
+    use r_tests::{FIXTURE_PROMO_ENDS, FIXTURE_PROMO_ENDS_UTC};
+
+    assert_eq!(FIXTURE_PROMO_ENDS_UTC.timestamp(), FIXTURE_PROMO_ENDS);
+}